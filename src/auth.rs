@@ -1,27 +1,39 @@
 use axum::{
-    extract::FromRequestParts,
+    extract::{FromRef, FromRequestParts},
     http::{StatusCode, request::Parts},
 };
 use axum_extra::TypedHeader;
 use chrono::{Duration, Utc};
 use headers::{Authorization, authorization::Bearer};
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
 
 use crate::{
+    config,
+    db::{moderation::bans::ensure_not_banned, user::session::touch_session},
     errors::AppError,
     models::{User, user::Claims},
+    state::{AppState, RedisClient},
 };
+
+/// How long a token, and the session it belongs to, stays valid
+pub const TOKEN_TTL_DAYS: i64 = 7;
+
 pub struct AuthClaims(pub Claims);
 
 impl<S> FromRequestParts<S> for AuthClaims
 where
+    AppState: FromRef<S>,
     S: Send + Sync,
 {
     type Rejection = (StatusCode, String);
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
         let TypedHeader(Authorization(bearer)) =
-            TypedHeader::<Authorization<Bearer>>::from_request_parts(parts, _state)
+            TypedHeader::<Authorization<Bearer>>::from_request_parts(parts, state)
                 .await
                 .map_err(|_| {
                     (
@@ -30,37 +42,217 @@ where
                     )
                 })?;
 
-        AuthClaims::from_token(bearer.token())
+        let claims = AuthClaims::from_token(bearer.token())?;
+        let redis = AppState::from_ref(state).redis;
+        claims.ensure_session(redis.clone()).await?;
+
+        let user_id = Uuid::parse_str(&claims.0.sub)
+            .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid or expired token".into()))?;
+        ensure_not_banned(user_id, redis)
+            .await
+            .map_err(|e| e.to_response())?;
+        Ok(claims)
     }
 }
 
 impl AuthClaims {
     pub fn from_token(token: &str) -> Result<Self, (StatusCode, String)> {
-        let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+        let config = config::get();
         let token_data = decode::<Claims>(
             token,
-            &DecodingKey::from_secret(secret.as_bytes()),
+            &DecodingKey::from_secret(config.jwt_secret.as_bytes()),
             &Validation::new(Algorithm::HS256),
         )
         .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid or expired token".into()))?;
 
         Ok(Self(token_data.claims))
     }
+
+    /// Rejects tokens whose session was revoked and marks the session as used.
+    /// Tokens issued before sessions existed carry none and pass.
+    pub async fn ensure_session(&self, redis: RedisClient) -> Result<(), (StatusCode, String)> {
+        let Some(session_id) = self.0.sid else {
+            return Ok(());
+        };
+        let user_id = Uuid::parse_str(&self.0.sub)
+            .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid or expired token".into()))?;
+
+        let active = touch_session(user_id, session_id, redis)
+            .await
+            .map_err(|e| e.to_response())?;
+        if !active {
+            return Err((StatusCode::UNAUTHORIZED, "Session has been revoked".into()));
+        }
+        Ok(())
+    }
 }
 
-pub fn generate_jwt(user: &User) -> Result<String, AppError> {
-    let expiration = (Utc::now() + Duration::days(7)).timestamp() as usize;
+pub fn generate_jwt(user: &User, session_id: Uuid) -> Result<String, AppError> {
+    let expiration = (Utc::now() + Duration::days(TOKEN_TTL_DAYS)).timestamp() as usize;
     let claims = Claims {
         sub: user.id.to_string(),
         wallet: user.wallet_address.clone(),
         exp: expiration,
+        sid: Some(session_id),
     };
 
-    let secret = std::env::var("JWT_SECRET").map_err(|e| AppError::EnvError(e.to_string()))?;
+    let config = config::get();
     encode(
         &Header::default(),
         &claims,
-        &EncodingKey::from_secret(secret.as_ref()),
+        &EncodingKey::from_secret(config.jwt_secret.as_ref()),
     )
     .map_err(AppError::JwtError)
 }
+
+/// Claims of a user listed in ADMIN_USER_IDS, used to gate support tooling
+pub struct AdminClaims(pub Claims);
+
+impl<S> FromRequestParts<S> for AdminClaims
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthClaims(claims) = AuthClaims::from_request_parts(parts, state).await?;
+
+        let is_admin =
+            Uuid::parse_str(&claims.sub).is_ok_and(|id| config::get().admin_user_ids.contains(&id));
+
+        if !is_admin {
+            tracing::warn!("Non-admin user {} attempted an admin request", claims.sub);
+            return Err((StatusCode::FORBIDDEN, "Admin access required".into()));
+        }
+
+        Ok(Self(claims))
+    }
+}
+
+/// Prefix wallets put before a message they sign, as in stacks.js `hashMessage`
+const STACKS_MESSAGE_PREFIX: &[u8] = b"\x17Stacks Signed Message:\n";
+/// Address versions of single-signature accounts, `SP...` and `ST...`
+const MAINNET_SINGLE_SIG: u8 = 22;
+const TESTNET_SINGLE_SIG: u8 = 26;
+const C32_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Hash a Stacks wallet signs for a plain-text message.
+pub fn stacks_message_hash(message: &str) -> [u8; 32] {
+    let len = message.len() as u64;
+    let mut varint = Vec::with_capacity(9);
+    match len {
+        0..0xfd => varint.push(len as u8),
+        0xfd..=0xffff => {
+            varint.push(0xfd);
+            varint.extend_from_slice(&(len as u16).to_le_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            varint.push(0xfe);
+            varint.extend_from_slice(&(len as u32).to_le_bytes());
+        }
+        _ => {
+            varint.push(0xff);
+            varint.extend_from_slice(&len.to_le_bytes());
+        }
+    }
+
+    Sha256::new()
+        .chain_update(STACKS_MESSAGE_PREFIX)
+        .chain_update(&varint)
+        .chain_update(message.as_bytes())
+        .finalize()
+        .into()
+}
+
+/// Encodes bytes in Crockford base32 the way c32check does, one leading `0`
+/// per leading zero byte.
+fn c32_encode(data: &[u8]) -> String {
+    let mut out = Vec::new();
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for &byte in data.iter().rev() {
+        acc |= (byte as u32) << bits;
+        bits += 8;
+        while bits >= 5 {
+            out.push(C32_ALPHABET[(acc & 31) as usize]);
+            acc >>= 5;
+            bits -= 5;
+        }
+    }
+    if bits > 0 {
+        out.push(C32_ALPHABET[(acc & 31) as usize]);
+    }
+
+    while out.last() == Some(&b'0') {
+        out.pop();
+    }
+    out.extend(data.iter().take_while(|&&byte| byte == 0).map(|_| b'0'));
+    out.reverse();
+
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// Stacks address of a version byte and a public key hash.
+pub fn c32_address(version: u8, hash160: &[u8; 20]) -> String {
+    let checksum = Sha256::digest(
+        Sha256::new()
+            .chain_update([version])
+            .chain_update(hash160)
+            .finalize(),
+    );
+
+    let mut data = hash160.to_vec();
+    data.extend_from_slice(&checksum[..4]);
+    format!(
+        "S{}{}",
+        C32_ALPHABET[(version & 31) as usize] as char,
+        c32_encode(&data)
+    )
+}
+
+/// Single-signature address of a SEC1 encoded secp256k1 public key.
+pub fn stacks_address(public_key: &[u8], mainnet: bool) -> Result<String, AppError> {
+    let key = VerifyingKey::from_sec1_bytes(public_key)
+        .map_err(|_| AppError::BadRequest("Invalid public key".into()))?;
+    let compressed = key.to_encoded_point(true);
+
+    let hash160: [u8; 20] = Ripemd160::digest(Sha256::digest(compressed.as_bytes())).into();
+    let version = if mainnet {
+        MAINNET_SINGLE_SIG
+    } else {
+        TESTNET_SINGLE_SIG
+    };
+    Ok(c32_address(version, &hash160))
+}
+
+/// Address of the wallet that signed `message`, from a hex RSV signature as
+/// Stacks wallets return it.
+pub fn recover_stacks_address(
+    message: &str,
+    signature_hex: &str,
+    mainnet: bool,
+) -> Result<String, AppError> {
+    let invalid = || AppError::Unauthorized("Invalid wallet signature".into());
+
+    let bytes = hex::decode(signature_hex.trim_start_matches("0x")).map_err(|_| invalid())?;
+    if bytes.len() != 65 {
+        return Err(invalid());
+    }
+
+    let mut signature = Signature::from_slice(&bytes[..64]).map_err(|_| invalid())?;
+    let v = bytes[64];
+    let mut recovery_id =
+        RecoveryId::from_byte(if v >= 27 { v - 27 } else { v }).ok_or_else(invalid)?;
+    // Recovery wants a low-S signature, flipping S flips the recovered point
+    if let Some(normalized) = signature.normalize_s() {
+        signature = normalized;
+        recovery_id = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+    }
+
+    let key =
+        VerifyingKey::recover_from_prehash(&stacks_message_hash(message), &signature, recovery_id)
+            .map_err(|_| invalid())?;
+
+    stacks_address(key.to_encoded_point(true).as_bytes(), mainnet)
+}
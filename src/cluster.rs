@@ -0,0 +1,115 @@
+//! Changes every instance sharing Redis must hear about. Some state is kept
+//! in each process's memory, so a change made on one instance is published
+//! on a Redis channel and applied by every instance, the sender included.
+
+use futures::StreamExt;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{
+    config,
+    db::game::words::{apply_dictionary_change, reload_default_dictionary},
+    errors::AppError,
+    models::presence::Presence,
+    presence::push_to_friends,
+    state::{AppState, RedisClient},
+    ws::sessions::close_session_sockets,
+};
+
+const CHANNEL: &str = "stacks_wars:cluster";
+/// Wait before subscribing again after the connection dropped
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ClusterMessage {
+    /// Words added to or removed from the English word set
+    DictionaryChanged {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// A user revoked one of their sessions, its sockets must close
+    #[serde(rename_all = "camelCase")]
+    SessionRevoked { user_id: Uuid, session_id: Uuid },
+    /// A user's presence changed, friends connected anywhere must hear it
+    PresenceChanged { presence: Presence },
+}
+
+pub async fn publish(msg: &ClusterMessage, redis: &RedisClient) -> Result<(), AppError> {
+    let payload = serde_json::to_string(msg).map_err(|e| AppError::Serialization(e.to_string()))?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: i64 = conn
+        .publish(CHANNEL, payload)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+async fn handle(msg: ClusterMessage, state: &AppState) {
+    match msg {
+        ClusterMessage::DictionaryChanged { added, removed } => {
+            apply_dictionary_change(&added, &removed);
+        }
+        ClusterMessage::SessionRevoked {
+            user_id,
+            session_id,
+        } => {
+            close_session_sockets(user_id, session_id, state).await;
+        }
+        ClusterMessage::PresenceChanged { presence } => {
+            push_to_friends(presence).await;
+        }
+    }
+}
+
+/// Applies what other instances publish. Messages sent while the
+/// subscription was down are lost, so state is reloaded from Redis each time
+/// it (re)subscribes.
+pub async fn start_cluster_listener(state: AppState) {
+    loop {
+        if let Err(e) = listen(&state).await {
+            tracing::error!("Cluster channel dropped: {}", e);
+        }
+        tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+    }
+}
+
+async fn listen(state: &AppState) -> Result<(), AppError> {
+    let client = redis::Client::open(config::get().redis_url.as_str())
+        .map_err(AppError::RedisCommandError)?;
+    let mut pubsub = client
+        .get_async_pubsub()
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    pubsub
+        .subscribe(CHANNEL)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    reload_default_dictionary(state.redis.clone()).await?;
+
+    let mut messages = pubsub.on_message();
+    while let Some(msg) = messages.next().await {
+        let payload: String = match msg.get_payload() {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::warn!("Unreadable cluster message: {}", e);
+                continue;
+            }
+        };
+        match serde_json::from_str::<ClusterMessage>(&payload) {
+            Ok(msg) => handle(msg, state).await,
+            Err(e) => tracing::warn!("Unknown cluster message {}: {}", payload, e),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,375 @@
+use axum::http::HeaderValue;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
+use uuid::Uuid;
+
+use crate::models::game::Language;
+
+static CONFIG: OnceLock<Arc<Config>> = OnceLock::new();
+
+/// Settings read from the environment once at startup.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub redis_url: String,
+    pub jwt_secret: String,
+    pub telegram_bot_token: String,
+    /// Announcement chat for games without a route of their own, unless an
+    /// admin set a different default at `/admin/telegram/routes`
+    pub telegram_chat_id: Option<i64>,
+    pub fee_wallet: String,
+    pub stacks_network: String,
+    pub port: u16,
+    pub allowed_origins: Vec<HeaderValue>,
+    pub internal_api_secret: Option<String>,
+    pub admin_user_ids: Vec<Uuid>,
+    /// Directory holding the `words_<language>.json` file of every language
+    /// besides English, which ships with the server
+    pub dictionary_dir: String,
+
+    // Timers
+    pub turn_timer_secs: u64,
+    pub auto_start_timer_secs: u32,
+    pub lobby_countdown_secs: u32,
+    /// How long players have to acknowledge a ready-check
+    pub ready_check_secs: u32,
+    /// Longest a game stays paused before it resumes or ends on its own
+    pub max_pause_secs: u64,
+    /// Sockets that send nothing, not even heartbeats, for this long are closed
+    pub ws_idle_timeout_secs: u64,
+
+    /// Needed by discord announcements and to register slash commands
+    pub discord_bot_token: Option<String>,
+    /// Set whenever discord announcements are enabled
+    pub discord_channel_id: Option<u64>,
+    /// Slash commands are registered when both of these are set
+    pub discord_application_id: Option<u64>,
+    /// Verifies the interactions Discord posts to `/discord/interactions`
+    pub discord_public_key: Option<String>,
+
+    // Feature toggles
+    pub telegram_announcements: bool,
+    /// Needs a build with the `discord` feature
+    pub discord_announcements: bool,
+    /// Only hand out tokens for a signed `/auth/verify` challenge, turning off
+    /// the unsigned `POST /user` sign-in
+    pub require_wallet_signature: bool,
+
+    // Anti-cheat
+    pub anticheat_mode: AntiCheatMode,
+    /// Suspicion score at which a player is flagged or eliminated
+    pub anticheat_threshold: u64,
+    /// Refuse prize lobby joins from accounts sharing a device with a player
+    /// already in the lobby, instead of only reporting them
+    pub block_linked_accounts: bool,
+
+    // Ranked ladder
+    /// Share of ladder rating lost each week a player sits out
+    pub ladder_decay_percent: u32,
+
+    /// Finished lobbies are archived this many days after they finish
+    pub lobby_archive_after_days: u32,
+}
+
+/// What happens once a player's suspicion score reaches the threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AntiCheatMode {
+    Off,
+    /// Record the player for admin review, the game carries on
+    Flag,
+    /// Record the player and eliminate them on the spot
+    Eliminate,
+}
+
+impl FromStr for AntiCheatMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "flag" => Ok(Self::Flag),
+            "eliminate" => Ok(Self::Eliminate),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Every missing or invalid variable found while loading, reported together.
+#[derive(Debug)]
+pub struct ConfigError {
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Invalid configuration:")?;
+        for problem in &self.problems {
+            writeln!(f, "  - {problem}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+struct EnvLoader {
+    problems: Vec<String>,
+}
+
+impl EnvLoader {
+    fn optional(&self, name: &str) -> Option<String> {
+        std::env::var(name)
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    fn required(&mut self, name: &str) -> String {
+        self.optional(name).unwrap_or_else(|| {
+            self.problems.push(format!("{name} must be set"));
+            String::new()
+        })
+    }
+
+    fn parse_or<T: FromStr>(&mut self, name: &str, default: T) -> T {
+        match self.optional(name) {
+            Some(raw) => raw.parse().unwrap_or_else(|_| {
+                self.problems
+                    .push(format!("{name} has an invalid value: {raw}"));
+                default
+            }),
+            None => default,
+        }
+    }
+}
+
+/// Word list of `language` inside the dictionary directory.
+pub fn dictionary_file(dir: &str, language: Language) -> PathBuf {
+    Path::new(dir).join(format!("words_{}.json", language.as_str()))
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut env = EnvLoader {
+            problems: Vec::new(),
+        };
+
+        let redis_url = env.required("REDIS_URL");
+        if !redis_url.is_empty()
+            && !redis_url.starts_with("redis://")
+            && !redis_url.starts_with("rediss://")
+        {
+            env.problems
+                .push(format!("REDIS_URL must be a redis:// URL, got {redis_url}"));
+        }
+
+        let jwt_secret = env.required("JWT_SECRET");
+        let telegram_bot_token = env.required("TELEGRAM_BOT_TOKEN");
+        let fee_wallet = env.required("FEE_WALLET");
+
+        let telegram_announcements = env.parse_or("TELEGRAM_ANNOUNCEMENTS", true);
+        let require_wallet_signature = env.parse_or("REQUIRE_WALLET_SIGNATURE", false);
+        let telegram_chat_id =
+            env.optional("TELEGRAM_CHAT_ID")
+                .and_then(|raw| match raw.parse::<i64>() {
+                    Ok(id) => Some(id),
+                    Err(_) => {
+                        env.problems
+                            .push(format!("TELEGRAM_CHAT_ID must be a number, got {raw}"));
+                        None
+                    }
+                });
+
+        let discord_announcements = env.parse_or("DISCORD_ANNOUNCEMENTS", false);
+        if discord_announcements && !cfg!(feature = "discord") {
+            env.problems
+                .push("DISCORD_ANNOUNCEMENTS needs a build with the discord feature".to_string());
+        }
+        let discord_bot_token = if discord_announcements {
+            Some(env.required("DISCORD_BOT_TOKEN"))
+        } else {
+            env.optional("DISCORD_BOT_TOKEN")
+        };
+        let discord_channel_id = if discord_announcements {
+            let raw = env.required("DISCORD_CHANNEL_ID");
+            match raw.parse::<u64>() {
+                Ok(id) => Some(id),
+                Err(_) if raw.is_empty() => None,
+                Err(_) => {
+                    env.problems
+                        .push(format!("DISCORD_CHANNEL_ID must be a number, got {raw}"));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let discord_application_id =
+            env.optional("DISCORD_APPLICATION_ID")
+                .and_then(|raw| match raw.parse::<u64>() {
+                    Ok(id) => Some(id),
+                    Err(_) => {
+                        env.problems.push(format!(
+                            "DISCORD_APPLICATION_ID must be a number, got {raw}"
+                        ));
+                        None
+                    }
+                });
+        let discord_public_key = env.optional("DISCORD_PUBLIC_KEY");
+
+        let stacks_network = env
+            .optional("STACKS_NETWORK")
+            .unwrap_or_else(|| "testnet".into());
+        if stacks_network != "mainnet" && stacks_network != "testnet" {
+            env.problems.push(format!(
+                "STACKS_NETWORK must be mainnet or testnet, got {stacks_network}"
+            ));
+        }
+
+        let port = env.parse_or("PORT", 3001);
+
+        let mut allowed_origins = Vec::new();
+        let origins = env
+            .optional("ALLOWED_ORIGINS")
+            .unwrap_or_else(|| "http://localhost:3000".into());
+        for origin in origins.split(',').map(str::trim) {
+            match origin.parse::<HeaderValue>() {
+                Ok(value) => allowed_origins.push(value),
+                Err(_) => env
+                    .problems
+                    .push(format!("ALLOWED_ORIGINS has an invalid origin: {origin}")),
+            }
+        }
+
+        let internal_api_secret = env.optional("INTERNAL_API_SECRET");
+
+        let mut admin_user_ids = Vec::new();
+        if let Some(ids) = env.optional("ADMIN_USER_IDS") {
+            for id in ids.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+                match Uuid::parse_str(id) {
+                    Ok(uuid) => admin_user_ids.push(uuid),
+                    Err(_) => env
+                        .problems
+                        .push(format!("ADMIN_USER_IDS has an invalid user id: {id}")),
+                }
+            }
+        }
+
+        let dictionary_dir = env.required("DICTIONARY_DIR");
+        if !dictionary_dir.is_empty() {
+            for language in Language::ALL.into_iter().filter(|l| *l != Language::En) {
+                let path = dictionary_file(&dictionary_dir, language);
+                if !path.is_file() {
+                    env.problems.push(format!(
+                        "DICTIONARY_DIR has no {} dictionary, expected {}",
+                        language.as_str(),
+                        path.display()
+                    ));
+                }
+            }
+        }
+
+        let turn_timer_secs = env.parse_or("TURN_TIMER_SECS", 15);
+        let auto_start_timer_secs = env.parse_or("AUTO_START_TIMER_SECS", 15);
+        let lobby_countdown_secs = env.parse_or("LOBBY_COUNTDOWN_SECS", 15);
+        let ready_check_secs = env.parse_or("READY_CHECK_SECS", 30);
+        let max_pause_secs = env.parse_or("MAX_PAUSE_SECS", 120);
+        for (name, secs) in [
+            ("TURN_TIMER_SECS", turn_timer_secs),
+            ("AUTO_START_TIMER_SECS", auto_start_timer_secs as u64),
+            ("LOBBY_COUNTDOWN_SECS", lobby_countdown_secs as u64),
+            ("READY_CHECK_SECS", ready_check_secs as u64),
+            ("MAX_PAUSE_SECS", max_pause_secs),
+        ] {
+            if !(1..=300).contains(&secs) {
+                env.problems
+                    .push(format!("{name} must be between 1 and 300, got {secs}"));
+            }
+        }
+        let ws_idle_timeout_secs = env.parse_or("WS_IDLE_TIMEOUT_SECS", 300);
+        if !(30..=3600).contains(&ws_idle_timeout_secs) {
+            env.problems.push(format!(
+                "WS_IDLE_TIMEOUT_SECS must be between 30 and 3600, got {ws_idle_timeout_secs}"
+            ));
+        }
+
+        let anticheat_mode = env.parse_or("ANTICHEAT_MODE", AntiCheatMode::Flag);
+        let anticheat_threshold = env.parse_or("ANTICHEAT_THRESHOLD", 3);
+        if anticheat_threshold == 0 {
+            env.problems
+                .push("ANTICHEAT_THRESHOLD must be at least 1".to_string());
+        }
+
+        let block_linked_accounts = env.parse_or("BLOCK_LINKED_ACCOUNTS", false);
+
+        let ladder_decay_percent = env.parse_or("LADDER_DECAY_PERCENT", 10);
+        if ladder_decay_percent > 100 {
+            env.problems.push(format!(
+                "LADDER_DECAY_PERCENT must be between 0 and 100, got {ladder_decay_percent}"
+            ));
+        }
+
+        let lobby_archive_after_days = env.parse_or("LOBBY_ARCHIVE_AFTER_DAYS", 30);
+        if !(1..=365).contains(&lobby_archive_after_days) {
+            env.problems.push(format!(
+                "LOBBY_ARCHIVE_AFTER_DAYS must be between 1 and 365, got {lobby_archive_after_days}"
+            ));
+        }
+
+        if !env.problems.is_empty() {
+            return Err(ConfigError {
+                problems: env.problems,
+            });
+        }
+
+        Ok(Self {
+            redis_url,
+            jwt_secret,
+            telegram_bot_token,
+            telegram_chat_id,
+            fee_wallet,
+            stacks_network,
+            port,
+            allowed_origins,
+            internal_api_secret,
+            admin_user_ids,
+            dictionary_dir,
+            turn_timer_secs,
+            auto_start_timer_secs,
+            lobby_countdown_secs,
+            ready_check_secs,
+            max_pause_secs,
+            ws_idle_timeout_secs,
+            discord_bot_token,
+            discord_channel_id,
+            discord_application_id,
+            discord_public_key,
+            telegram_announcements,
+            discord_announcements,
+            require_wallet_signature,
+            anticheat_mode,
+            anticheat_threshold,
+            block_linked_accounts,
+            ladder_decay_percent,
+            lobby_archive_after_days,
+        })
+    }
+}
+
+/// Makes the loaded config reachable from code that has no `AppState`, such as
+/// game timers and db helpers. Called once from `start_server`.
+pub fn init(config: Arc<Config>) {
+    if CONFIG.set(config).is_err() {
+        tracing::warn!("Config already initialized");
+    }
+}
+
+pub fn get() -> Arc<Config> {
+    CONFIG
+        .get()
+        .cloned()
+        .expect("config::init must be called at startup")
+}
@@ -0,0 +1,34 @@
+use redis::AsyncCommands;
+
+use crate::{
+    errors::AppError,
+    models::{
+        audit::{AuditEntry, AuditFilter},
+        redis::RedisKey,
+    },
+    state::RedisClient,
+};
+
+/// Newest entries matching `filter`. The log is capped, so this scans all of it.
+pub async fn get_audit_entries(
+    filter: &AuditFilter,
+    limit: usize,
+    redis: RedisClient,
+) -> Result<Vec<AuditEntry>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let entries: Vec<String> = conn
+        .lrange(RedisKey::audit_log(), 0, -1)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| serde_json::from_str::<AuditEntry>(entry).ok())
+        .filter(|entry| filter.matches(entry))
+        .take(limit)
+        .collect())
+}
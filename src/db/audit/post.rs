@@ -0,0 +1,37 @@
+use crate::{
+    errors::AppError,
+    models::{audit::AuditEntry, redis::RedisKey},
+    state::RedisClient,
+};
+
+/// Number of audit entries kept, oldest are trimmed first
+const AUDIT_LOG_MAX_LEN: isize = 10_000;
+
+pub async fn record_audit_entry(entry: &AuditEntry, redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::audit_log();
+    let serialized = serde_json::to_string(entry)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize audit entry: {}", e)))?;
+
+    let _: () = redis::pipe()
+        .lpush(&key, serialized)
+        .ignore()
+        .ltrim(&key, 0, AUDIT_LOG_MAX_LEN - 1)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    tracing::info!(
+        "Audit: {} performed {} on {:?}",
+        entry.actor_id,
+        entry.action,
+        entry.target_id
+    );
+
+    Ok(())
+}
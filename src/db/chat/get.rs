@@ -1,14 +1,18 @@
 use uuid::Uuid;
 
 use crate::{
+    db::user::block::get_blocked_ids,
     errors::AppError,
     models::{
-        chat::ChatMessage,
+        chat::{ChatMessage, ChatPage},
         redis::{KeyPart, RedisKey},
     },
     state::RedisClient,
 };
 
+/// Messages sent on connect and the default page size for older history
+pub const CHAT_PAGE_SIZE: usize = 50;
+
 pub async fn get_chat_history(
     lobby_id: Uuid,
     redis: &RedisClient,
@@ -42,3 +46,33 @@ pub async fn get_chat_history(
 
     Ok(chat_messages)
 }
+
+/// Up to `limit` messages from before the message `before`, or the newest ones
+/// without it. Messages from users the viewer blocked are left out.
+pub async fn get_chat_page(
+    lobby_id: Uuid,
+    viewer_id: Uuid,
+    before: Option<Uuid>,
+    limit: usize,
+    redis: &RedisClient,
+) -> Result<ChatPage, AppError> {
+    let mut history = get_chat_history(lobby_id, redis).await?;
+
+    let end = match before {
+        Some(message_id) => history
+            .iter()
+            .position(|m| m.id == message_id)
+            .ok_or_else(|| AppError::NotFound(format!("Chat message {} not found", message_id)))?,
+        None => history.len(),
+    };
+    history.truncate(end);
+
+    let blocked = get_blocked_ids(viewer_id, redis.clone()).await?;
+    history.retain(|m| !blocked.contains(&m.sender.id));
+
+    let start = history.len().saturating_sub(limit);
+    Ok(ChatPage {
+        has_more: start > 0,
+        messages: history.split_off(start),
+    })
+}
@@ -1,3 +1,4 @@
 pub mod delete;
 pub mod get;
+pub mod moderation;
 pub mod post;
@@ -0,0 +1,187 @@
+use bb8::PooledConnection;
+use bb8_redis::RedisConnectionManager;
+use chrono::{DateTime, Duration, Utc};
+use redis::AsyncCommands;
+use std::{cmp::Reverse, collections::HashMap};
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        chat::{ChatMessage, ChatReport},
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Mutes a user in a lobby chat until the returned time.
+pub async fn mute_chat_user(
+    lobby_id: Uuid,
+    user_id: Uuid,
+    minutes: u32,
+    redis: &RedisClient,
+) -> Result<DateTime<Utc>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let until = Utc::now() + Duration::minutes(minutes as i64);
+    let _: () = conn
+        .set_ex(
+            RedisKey::lobby_chat_mute(KeyPart::Id(lobby_id), KeyPart::Id(user_id)),
+            until.to_rfc3339(),
+            minutes as u64 * 60,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(until)
+}
+
+/// Seconds left on a user's mute, if they are muted.
+pub async fn get_chat_mute_secs(
+    lobby_id: Uuid,
+    user_id: Uuid,
+    redis: &RedisClient,
+) -> Result<Option<u64>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    // -2 when there is no mute, -1 would mean one without expiry
+    let ttl: i64 = conn
+        .ttl(RedisKey::lobby_chat_mute(
+            KeyPart::Id(lobby_id),
+            KeyPart::Id(user_id),
+        ))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok((ttl > 0).then_some(ttl as u64))
+}
+
+async fn find_stored_message(
+    lobby_id: Uuid,
+    message_id: Uuid,
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+) -> Result<(String, ChatMessage), AppError> {
+    let stored: Vec<String> = conn
+        .lrange(RedisKey::lobby_chat(KeyPart::Id(lobby_id)), 0, -1)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    stored
+        .into_iter()
+        .find_map(|raw| {
+            let message = serde_json::from_str::<ChatMessage>(&raw).ok()?;
+            (message.id == message_id).then_some((raw, message))
+        })
+        .ok_or_else(|| AppError::NotFound(format!("Chat message {} not found", message_id)))
+}
+
+pub async fn get_chat_message(
+    lobby_id: Uuid,
+    message_id: Uuid,
+    redis: &RedisClient,
+) -> Result<ChatMessage, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (_, message) = find_stored_message(lobby_id, message_id, &mut conn).await?;
+    Ok(message)
+}
+
+/// Removes a message from the lobby history and returns it.
+pub async fn delete_chat_message(
+    lobby_id: Uuid,
+    message_id: Uuid,
+    redis: &RedisClient,
+) -> Result<ChatMessage, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (raw, message) = find_stored_message(lobby_id, message_id, &mut conn).await?;
+    let _: () = conn
+        .lrem(RedisKey::lobby_chat(KeyPart::Id(lobby_id)), 1, raw)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(message)
+}
+
+/// Keeps the first report of a message. Returns false if it was already reported.
+pub async fn store_chat_report(report: &ChatReport, redis: &RedisClient) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let json = serde_json::to_string(report)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize chat report: {}", e)))?;
+
+    let stored: bool = conn
+        .hset_nx(
+            RedisKey::chat_reports(),
+            report.message.id.to_string(),
+            json,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(stored)
+}
+
+/// Open chat reports, newest first.
+pub async fn get_chat_reports(redis: &RedisClient) -> Result<Vec<ChatReport>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let stored: HashMap<String, String> = conn
+        .hgetall(RedisKey::chat_reports())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut reports: Vec<ChatReport> = stored
+        .into_values()
+        .filter_map(|json| match serde_json::from_str::<ChatReport>(&json) {
+            Ok(report) => Some(report),
+            Err(e) => {
+                tracing::warn!("Skipping malformed chat report: {}", e);
+                None
+            }
+        })
+        .collect();
+    reports.sort_by_key(|report| Reverse(report.reported_at));
+
+    Ok(reports)
+}
+
+/// Closes a report once an admin reviewed it.
+pub async fn clear_chat_report(message_id: Uuid, redis: &RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let removed: usize = conn
+        .hdel(RedisKey::chat_reports(), message_id.to_string())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if removed == 0 {
+        return Err(AppError::NotFound(format!(
+            "Chat report {} not found",
+            message_id
+        )));
+    }
+
+    Ok(())
+}
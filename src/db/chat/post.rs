@@ -9,6 +9,9 @@ use crate::{
     state::RedisClient,
 };
 
+/// Messages kept per lobby, older ones are trimmed
+const MAX_STORED_CHAT_MESSAGES: isize = 500;
+
 pub async fn store_chat_message(
     lobby_id: Uuid,
     chat_message: &ChatMessage,
@@ -31,10 +34,10 @@ pub async fn store_chat_message(
         .await
         .map_err(AppError::RedisCommandError)?;
 
-    // Trim to keep only the last 100 messages
+    // Trim to keep only the most recent messages
     let _: () = redis::cmd("LTRIM")
         .arg(&key)
-        .arg(-100) // Keep last 100 messages
+        .arg(-MAX_STORED_CHAT_MESSAGES)
         .arg(-1) // To the end
         .query_async(&mut *conn)
         .await
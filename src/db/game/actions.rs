@@ -0,0 +1,112 @@
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::redis::{KeyPart, RedisKey},
+    state::RedisClient,
+};
+
+/// How long an accepted action id is remembered, comfortably longer than a
+/// client keeps retrying one
+const ACTION_ID_TTL_SECS: u64 = 60;
+
+/// Action ids are used as key parts, so only a small charset is allowed.
+pub fn validate_action_id(action_id: &str) -> Result<(), AppError> {
+    let valid = (1..=64).contains(&action_id.len())
+        && action_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if !valid {
+        return Err(AppError::BadRequest(
+            "actionId must be 1-64 letters, digits, '-' or '_'".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Records a player's action id, returning false if it was already recorded
+/// so a retried action is applied only once.
+pub async fn claim_action(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    action_id: &str,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    validate_action_id(action_id)?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let options = SetOptions::default()
+        .conditional_set(ExistenceCheck::NX)
+        .with_expiration(SetExpiry::EX(ACTION_ID_TTL_SECS));
+    let claimed: Option<String> = conn
+        .set_options(
+            RedisKey::lobby_action(
+                KeyPart::Id(lobby_id),
+                KeyPart::Id(player_id),
+                KeyPart::Str(action_id.to_string()),
+            ),
+            1,
+            options,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(claimed.is_some())
+}
+
+pub async fn is_action_claimed(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    action_id: &str,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    validate_action_id(action_id)?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let exists: bool = conn
+        .exists(RedisKey::lobby_action(
+            KeyPart::Id(lobby_id),
+            KeyPart::Id(player_id),
+            KeyPart::Str(action_id.to_string()),
+        ))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(exists)
+}
+
+/// Forgets an action that was claimed but then rejected, so it isn't acked
+/// as accepted when retried.
+pub async fn release_action(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    action_id: &str,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .del(RedisKey::lobby_action(
+            KeyPart::Id(lobby_id),
+            KeyPart::Id(player_id),
+            KeyPart::Str(action_id.to_string()),
+        ))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        admin::SuspicionFlag,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Number of flags kept for review, oldest are trimmed first
+const FLAGS_MAX_LEN: isize = 1_000;
+
+/// Raises a player's suspicion score for this game and returns the new total.
+pub async fn add_suspicion(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    weight: u64,
+    redis: RedisClient,
+) -> Result<u64, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let total: u64 = conn
+        .hincr(
+            RedisKey::lobby_suspicion(KeyPart::Id(lobby_id)),
+            player_id.to_string(),
+            weight,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(total)
+}
+
+pub async fn record_suspicion_flag(
+    flag: &SuspicionFlag,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::anticheat_flags();
+    let serialized = serde_json::to_string(flag).map_err(|e| {
+        AppError::Serialization(format!("Failed to serialize suspicion flag: {}", e))
+    })?;
+
+    let _: () = redis::pipe()
+        .lpush(&key, serialized)
+        .ignore()
+        .ltrim(&key, 0, FLAGS_MAX_LEN - 1)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Most recent flags first.
+pub async fn get_suspicion_flags(
+    limit: usize,
+    redis: RedisClient,
+) -> Result<Vec<SuspicionFlag>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let entries: Vec<String> = conn
+        .lrange(RedisKey::anticheat_flags(), 0, limit as isize - 1)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| serde_json::from_str(entry).ok())
+        .collect())
+}
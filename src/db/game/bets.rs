@@ -0,0 +1,137 @@
+use redis::{AsyncCommands, Script};
+use std::{collections::HashMap, sync::LazyLock};
+use uuid::Uuid;
+
+use crate::{
+    db::user::cache::invalidate_user,
+    errors::AppError,
+    models::{
+        bet::SpectatorBet,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Takes the stake from the bettor's wars points and stores the bet, as long
+/// as bets are still open, the user has not bet yet and can cover the stake.
+static PLACE_BET: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('EXISTS', KEYS[1]) == 0 then
+            return -1
+        end
+        if redis.call('HEXISTS', KEYS[2], ARGV[1]) == 1 then
+            return -2
+        end
+        local balance = tonumber(redis.call('HGET', KEYS[3], 'wars_point') or '0')
+        local amount = tonumber(ARGV[2])
+        if balance < amount then
+            return -3
+        end
+        redis.call('HINCRBYFLOAT', KEYS[3], 'wars_point', -amount)
+        redis.call('ZINCRBY', KEYS[4], -amount, ARGV[1])
+        redis.call('HSET', KEYS[2], ARGV[1], ARGV[3])
+        return 1
+        ",
+    )
+});
+
+/// Accepts bets for `window_secs` from now.
+pub async fn open_bets(
+    lobby_id: Uuid,
+    window_secs: u64,
+    redis: &RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .set_ex(
+            RedisKey::lobby_bets_open(KeyPart::Id(lobby_id)),
+            1,
+            window_secs,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+pub async fn place_bet(
+    lobby_id: Uuid,
+    bet: &SpectatorBet,
+    redis: &RedisClient,
+) -> Result<(), AppError> {
+    if !bet.amount.is_finite() || bet.amount <= 0.0 {
+        return Err(AppError::BadRequest("Bet amount must be positive".into()));
+    }
+
+    let json = serde_json::to_string(bet)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize bet: {}", e)))?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let placed: i64 = PLACE_BET
+        .key(RedisKey::lobby_bets_open(KeyPart::Id(lobby_id)))
+        .key(RedisKey::lobby_bets(KeyPart::Id(lobby_id)))
+        .key(RedisKey::user(KeyPart::Id(bet.user_id)))
+        .key(RedisKey::users_points())
+        .arg(bet.user_id.to_string())
+        .arg(bet.amount)
+        .arg(json)
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    match placed {
+        1 => {
+            // The stake came out of the user's wars points
+            invalidate_user(bet.user_id);
+            Ok(())
+        }
+        -1 => Err(AppError::BadRequest("Bets are locked for this game".into())),
+        -2 => Err(AppError::BadRequest("You already bet on this game".into())),
+        _ => Err(AppError::BadRequest(
+            "Not enough wars points for this bet".into(),
+        )),
+    }
+}
+
+/// Removes and returns every bet of a game, so each is settled only once.
+pub async fn take_lobby_bets(
+    lobby_id: Uuid,
+    redis: &RedisClient,
+) -> Result<Vec<SpectatorBet>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let bets_key = RedisKey::lobby_bets(KeyPart::Id(lobby_id));
+    let (stored,): (HashMap<String, String>,) = redis::pipe()
+        .atomic()
+        .hgetall(&bets_key)
+        .del(&bets_key)
+        .ignore()
+        .del(RedisKey::lobby_bets_open(KeyPart::Id(lobby_id)))
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(stored
+        .into_values()
+        .filter_map(|json| match serde_json::from_str::<SpectatorBet>(&json) {
+            Ok(bet) => Some(bet),
+            Err(e) => {
+                tracing::warn!("Skipping malformed spectator bet: {}", e);
+                None
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,85 @@
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        lexi_wars::{BotSkill, PracticeBot},
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Creates or refreshes the account a bot skill plays as. Bot accounts have
+/// no wallet and stay out of the wallet index and the leaderboard.
+pub async fn ensure_bot_user(skill: BotSkill, redis: RedisClient) -> Result<Uuid, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let user_id = skill.user_id();
+    let _: () = conn
+        .hset_multiple(
+            RedisKey::user(KeyPart::Id(user_id)),
+            &[
+                ("id", user_id.to_string()),
+                ("wallet_address", String::new()),
+                ("display_name", skill.display_name().to_string()),
+                ("wars_point", "0".to_string()),
+            ],
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(user_id)
+}
+
+pub async fn add_lobby_bot(
+    lobby_id: Uuid,
+    bot: &PracticeBot,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let json = serde_json::to_string(bot)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize bot: {}", e)))?;
+    let _: () = conn
+        .hset(
+            RedisKey::lobby_bots(KeyPart::Id(lobby_id)),
+            bot.user_id.to_string(),
+            json,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+pub async fn get_lobby_bot(
+    lobby_id: Uuid,
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<Option<PracticeBot>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let json: Option<String> = conn
+        .hget(
+            RedisKey::lobby_bots(KeyPart::Id(lobby_id)),
+            user_id.to_string(),
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    json.map(|json| {
+        serde_json::from_str(&json)
+            .map_err(|e| AppError::Deserialization(format!("Failed to deserialize bot: {}", e)))
+    })
+    .transpose()
+}
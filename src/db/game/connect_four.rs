@@ -0,0 +1,154 @@
+use redis::{AsyncCommands, Script};
+use std::{collections::HashMap, sync::LazyLock};
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    games::connect_four::board::{Board, COLUMNS, ROWS},
+    models::redis::{KeyPart, RedisKey},
+    state::RedisClient,
+};
+
+/// A running grid with the players in disc order.
+#[derive(Debug, Clone)]
+pub struct BoardState {
+    pub board: Board,
+    pub players: Vec<Uuid>,
+}
+
+/// Result of dropping a disc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropOutcome {
+    Dropped { row: usize },
+    NotYourTurn,
+    ColumnFull,
+    NotPlaying,
+}
+
+/// Drops the player's disc and hands the turn to the opponent in one step, so
+/// a move can't land after the turn moved on. Returns the row, or -1 when the
+/// player isn't in the match, -2 out of turn and -3 for a full column.
+static DROP_DISC: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local cells = redis.call('HGET', KEYS[1], 'cells')
+        local p1 = redis.call('HGET', KEYS[1], 'p1')
+        local p2 = redis.call('HGET', KEYS[1], 'p2')
+        local mark, other
+        if not cells then
+            return -1
+        elseif p1 == ARGV[1] then
+            mark, other = '1', p2
+        elseif p2 == ARGV[1] then
+            mark, other = '2', p1
+        else
+            return -1
+        end
+        if redis.call('GET', KEYS[2]) ~= ARGV[1] then
+            return -2
+        end
+        local column, columns, rows = tonumber(ARGV[2]), tonumber(ARGV[3]), tonumber(ARGV[4])
+        for row = 0, rows - 1 do
+            local i = row * columns + column + 1
+            if string.sub(cells, i, i) == '.' then
+                cells = string.sub(cells, 1, i - 1) .. mark .. string.sub(cells, i + 1)
+                redis.call('HSET', KEYS[1], 'cells', cells)
+                redis.call('SET', KEYS[2], other)
+                return row
+            end
+        end
+        return -3
+        ",
+    )
+});
+
+/// Starts an empty grid; the first player drops `1` discs.
+pub async fn open_board(
+    lobby_id: Uuid,
+    first: Uuid,
+    second: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::lobby_board(KeyPart::Id(lobby_id));
+    let fields = [
+        ("cells", Board::default().encode()),
+        ("p1", first.to_string()),
+        ("p2", second.to_string()),
+    ];
+    let _: () = redis::pipe()
+        .atomic()
+        .del(&key)
+        .ignore()
+        .hset_multiple(&key, &fields)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+pub async fn get_board(lobby_id: Uuid, redis: RedisClient) -> Result<Option<BoardState>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let map: HashMap<String, String> = conn
+        .hgetall(RedisKey::lobby_board(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if map.is_empty() {
+        return Ok(None);
+    }
+
+    let board = map
+        .get("cells")
+        .and_then(|cells| Board::parse(cells))
+        .ok_or_else(|| AppError::Deserialization("Invalid board cells".into()))?;
+    let players = ["p1", "p2"]
+        .iter()
+        .map(|field| {
+            map.get(*field)
+                .and_then(|id| Uuid::parse_str(id).ok())
+                .ok_or_else(|| AppError::Deserialization(format!("Invalid board {}", field)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Some(BoardState { board, players }))
+}
+
+pub async fn drop_disc(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    column: usize,
+    redis: RedisClient,
+) -> Result<DropOutcome, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let result: i64 = DROP_DISC
+        .key(RedisKey::lobby_board(KeyPart::Id(lobby_id)))
+        .key(RedisKey::lobby_current_turn(KeyPart::Id(lobby_id)))
+        .arg(player_id.to_string())
+        .arg(column)
+        .arg(COLUMNS)
+        .arg(ROWS)
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(match result {
+        -1 => DropOutcome::NotPlaying,
+        -2 => DropOutcome::NotYourTurn,
+        -3 => DropOutcome::ColumnFull,
+        row => DropOutcome::Dropped { row: row as usize },
+    })
+}
@@ -39,23 +39,25 @@ pub async fn get_all_games(redis: RedisClient) -> Result<Vec<GameType>, AppError
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
-    let keys: Vec<String> = conn
-        .keys(RedisKey::game(KeyPart::Wildcard))
+    let game_ids: Vec<String> = conn
+        .smembers(RedisKey::games_all())
         .await
         .map_err(AppError::RedisCommandError)?;
+    if game_ids.is_empty() {
+        return Ok(Vec::new());
+    }
 
-    let mut games = Vec::new();
-    for key in keys {
-        let map: HashMap<String, String> = conn
-            .hgetall(&key)
-            .await
-            .map_err(AppError::RedisCommandError)?;
-
-        if !map.is_empty() {
-            let game = GameType::from_redis_hash(&map)?;
-            games.push(game);
-        }
+    let mut pipe = redis::pipe();
+    for game_id in &game_ids {
+        pipe.hgetall(RedisKey::game(KeyPart::Str(game_id.clone())));
     }
+    let maps: Vec<HashMap<String, String>> = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
 
-    Ok(games)
+    maps.iter()
+        .filter(|map| !map.is_empty())
+        .map(GameType::from_redis_hash)
+        .collect()
 }
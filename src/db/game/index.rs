@@ -0,0 +1,50 @@
+use redis::AsyncCommands;
+
+use crate::{
+    db::utils::scan_keys,
+    errors::AppError,
+    models::redis::{KeyPart, RedisKey},
+    state::RedisClient,
+};
+
+/// Marker set once the games index has been built from existing games
+const GAMES_INDEX_MIGRATION_KEY: &str = "migrations:games_index:v1";
+
+/// Adds the games stored before `games:all` existed to it. Safe to run
+/// repeatedly, it only does work once.
+pub async fn backfill_games_index(redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let done: bool = conn
+        .exists(GAMES_INDEX_MIGRATION_KEY)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if done {
+        return Ok(());
+    }
+
+    let game_keys = scan_keys(&mut conn, &RedisKey::game(KeyPart::Wildcard)).await?;
+    let game_ids: Vec<String> = game_keys
+        .iter()
+        .filter_map(|key| RedisKey::extract_game_id_from_game_key(key))
+        .map(|id| id.to_string())
+        .collect();
+
+    let mut pipe = redis::pipe();
+    if !game_ids.is_empty() {
+        pipe.sadd(RedisKey::games_all(), &game_ids).ignore();
+    }
+    pipe.set(GAMES_INDEX_MIGRATION_KEY, chrono::Utc::now().to_rfc3339())
+        .ignore();
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    tracing::info!("Indexed {} games", game_ids.len());
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use redis::{AsyncCommands, ExistenceCheck, Script, SetExpiry, SetOptions};
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::redis::{KeyPart, RedisKey},
+    state::RedisClient,
+};
+
+/// How long a game stays owned by an instance that stopped heartbeating
+pub const LEASE_TTL_SECS: u64 = 30;
+
+/// Extends the lease while `owner` holds it and the game is counting down or
+/// running. A lease whose game is over is dropped. Returns 1 when the lease
+/// was kept.
+static RENEW_LEASE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('GET', KEYS[1]) ~= ARGV[1] then
+            return 0
+        end
+        local state = redis.call('HGET', KEYS[2], 'state')
+        if state ~= 'Starting' and state ~= 'InProgress' then
+            redis.call('DEL', KEYS[1])
+            return 0
+        end
+        redis.call('EXPIRE', KEYS[1], ARGV[2])
+        return 1
+        ",
+    )
+});
+
+/// Drops the lease only while `owner` still holds it.
+static RELEASE_LEASE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('GET', KEYS[1]) == ARGV[1] then
+            return redis.call('DEL', KEYS[1])
+        end
+        return 0
+        ",
+    )
+});
+
+/// Makes `owner` the instance running the lobby's game, taking over from
+/// whoever held it before.
+pub async fn hold_game_lease(
+    lobby_id: Uuid,
+    owner: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .set_ex(
+            RedisKey::lobby_owner(KeyPart::Id(lobby_id)),
+            owner.to_string(),
+            LEASE_TTL_SECS,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Takes the lease of a game nobody owns. Only one instance wins, so an
+/// orphaned game is recovered once.
+pub async fn claim_game_lease(
+    lobby_id: Uuid,
+    owner: Uuid,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let options = SetOptions::default()
+        .conditional_set(ExistenceCheck::NX)
+        .with_expiration(SetExpiry::EX(LEASE_TTL_SECS));
+    let claimed: Option<String> = conn
+        .set_options(
+            RedisKey::lobby_owner(KeyPart::Id(lobby_id)),
+            owner.to_string(),
+            options,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(claimed.is_some())
+}
+
+/// Heartbeat of a held lease, false once `owner` lost it or the game ended.
+pub async fn renew_game_lease(
+    lobby_id: Uuid,
+    owner: Uuid,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let renewed: i32 = RENEW_LEASE
+        .key(RedisKey::lobby_owner(KeyPart::Id(lobby_id)))
+        .key(RedisKey::lobby(KeyPart::Id(lobby_id)))
+        .arg(owner.to_string())
+        .arg(LEASE_TTL_SECS)
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(renewed == 1)
+}
+
+pub async fn release_game_lease(
+    lobby_id: Uuid,
+    owner: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: i32 = RELEASE_LEASE
+        .key(RedisKey::lobby_owner(KeyPart::Id(lobby_id)))
+        .arg(owner.to_string())
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use redis::AsyncCommands;
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    errors::AppError,
+    games::lexi_wars::{
+        rule_dsl::{RuleDefinition, install_rule_book},
+        rules::rule_names,
+    },
+    models::redis::RedisKey,
+    state::RedisClient,
+};
+
+/// Reads the custom rules and rule order from Redis into memory. Called at
+/// startup and after every admin change.
+pub async fn load_custom_rules(redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (stored, order_json): (HashMap<String, String>, Option<String>) = redis::pipe()
+        .hgetall(RedisKey::lexi_custom_rules())
+        .get(RedisKey::lexi_rule_order())
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut definitions: Vec<RuleDefinition> = stored
+        .into_iter()
+        .filter_map(
+            |(name, json)| match serde_json::from_str::<RuleDefinition>(&json) {
+                Ok(definition) => Some(definition),
+                Err(e) => {
+                    tracing::warn!("Skipping malformed custom rule {}: {}", name, e);
+                    None
+                }
+            },
+        )
+        .collect();
+    definitions.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let order: Vec<String> = match order_json {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| AppError::Deserialization(format!("Failed to parse rule order: {}", e)))?,
+        None => Vec::new(),
+    };
+
+    tracing::info!("Loaded {} custom Lexi Wars rules", definitions.len());
+    install_rule_book(definitions, order);
+    Ok(())
+}
+
+/// Adds a custom rule, replacing an earlier custom rule of the same name.
+pub async fn save_custom_rule(
+    definition: &RuleDefinition,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    definition.validate()?;
+
+    let json = serde_json::to_string(definition)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize rule: {}", e)))?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .hset(RedisKey::lexi_custom_rules(), &definition.name, json)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    drop(conn);
+
+    load_custom_rules(redis).await
+}
+
+/// Sets the rule progression. Every name must be a known rule; rules left out
+/// follow the listed ones in their default order.
+pub async fn set_rule_order(order: Vec<String>, redis: RedisClient) -> Result<(), AppError> {
+    let known = rule_names();
+    if let Some(unknown) = order.iter().find(|name| !known.contains(name)) {
+        return Err(AppError::BadRequest(format!("Unknown rule: {unknown}")));
+    }
+    if order.iter().collect::<HashSet<_>>().len() != order.len() {
+        return Err(AppError::BadRequest("Rule order lists a rule twice".into()));
+    }
+
+    let json = serde_json::to_string(&order)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize rule order: {}", e)))?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .set(RedisKey::lexi_rule_order(), json)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    drop(conn);
+
+    load_custom_rules(redis).await
+}
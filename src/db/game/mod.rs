@@ -1,5 +1,17 @@
+pub mod actions;
+pub mod anticheat;
+pub mod bets;
+pub mod bots;
+pub mod connect_four;
 pub mod get;
+pub mod index;
+pub mod lease;
+pub mod lexi_rules;
 pub mod player_words;
 pub mod post;
+pub mod reactions;
+pub mod rps;
 pub mod state;
+pub mod typing_race;
+pub mod word_reports;
 pub mod words;
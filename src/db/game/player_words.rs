@@ -1,4 +1,5 @@
 use redis::AsyncCommands;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::{
@@ -72,6 +73,47 @@ pub async fn _get_player_used_words(
     }
 }
 
+/// Used words of several players in one round trip, in the order they were played
+pub async fn get_players_used_words(
+    lobby_id: Uuid,
+    player_ids: &[Uuid],
+    redis: RedisClient,
+) -> Result<HashMap<Uuid, Vec<String>>, AppError> {
+    if player_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let mut pipe = redis::pipe();
+    for player_id in player_ids {
+        let player_key = RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(*player_id));
+        pipe.hget(player_key, "used_words");
+    }
+
+    let results: Vec<Option<String>> = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let words = player_ids
+        .iter()
+        .zip(results)
+        .map(|(player_id, words_json)| {
+            let words = words_json
+                .filter(|json| !json.is_empty())
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default();
+            (*player_id, words)
+        })
+        .collect();
+
+    Ok(words)
+}
+
 pub async fn _has_player_used_word(
     lobby_id: Uuid,
     player_id: Uuid,
@@ -1,4 +1,3 @@
-use redis::AsyncCommands;
 use uuid::Uuid;
 
 use crate::{
@@ -38,8 +37,13 @@ pub async fn create_game(
     let hash = game.to_redis_hash();
     let fields: Vec<(&str, &str)> = hash.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
 
-    let _: () = conn
+    let _: () = redis::pipe()
+        .atomic()
         .hset_multiple(&key, &fields)
+        .ignore()
+        .sadd(RedisKey::games_all(), game_id.to_string())
+        .ignore()
+        .query_async(&mut *conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
@@ -0,0 +1,82 @@
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        game::Reaction,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Minimum gap between two reactions of the same user in a lobby
+const REACTION_COOLDOWN_MS: u64 = 1000;
+
+/// Counts a reaction unless the user reacted too recently. Returns whether it
+/// was accepted.
+pub async fn record_reaction(
+    lobby_id: Uuid,
+    user_id: Uuid,
+    reaction: Reaction,
+    redis: &RedisClient,
+) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let accepted: Option<String> = redis::cmd("SET")
+        .arg(RedisKey::lobby_reaction_cooldown(
+            KeyPart::Id(lobby_id),
+            KeyPart::Id(user_id),
+        ))
+        .arg(1)
+        .arg("NX")
+        .arg("PX")
+        .arg(REACTION_COOLDOWN_MS)
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if accepted.is_none() {
+        return Ok(false);
+    }
+
+    let _: () = conn
+        .hincr(
+            RedisKey::lobby_reactions(KeyPart::Id(lobby_id)),
+            reaction.as_str(),
+            1,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(true)
+}
+
+/// How often each reaction was sent in a game.
+pub async fn get_lobby_reactions(
+    lobby_id: Uuid,
+    redis: &RedisClient,
+) -> Result<HashMap<Reaction, u64>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let counts: HashMap<String, u64> = conn
+        .hgetall(RedisKey::lobby_reactions(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(Reaction::ALL
+        .into_iter()
+        .filter_map(|reaction| {
+            counts
+                .get(reaction.as_str())
+                .map(|count| (reaction, *count))
+        })
+        .collect())
+}
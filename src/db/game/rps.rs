@@ -0,0 +1,155 @@
+use redis::{AsyncCommands, Script};
+use std::{collections::HashMap, sync::LazyLock};
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        redis::{KeyPart, RedisKey},
+        rps::RpsChoice,
+    },
+    state::RedisClient,
+};
+
+/// Result of locking in a Rock-Paper-Scissors move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpsMoveOutcome {
+    /// Move stored; `moves` counts everyone who has moved this round
+    Locked {
+        round: u32,
+        moves: usize,
+    },
+    AlreadyMoved,
+    NoOpenRound,
+}
+
+/// Stores a move for the open round unless the player already moved.
+/// Returns `{round, moves}`, or `{-1, 0}` without an open round and
+/// `{-2, 0}` for a repeated move.
+static LOCK_MOVE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local round = redis.call('HGET', KEYS[1], 'round')
+        if not round then
+            return {-1, 0}
+        end
+        if redis.call('HSETNX', KEYS[1], 'move:' .. ARGV[1], ARGV[2]) == 0 then
+            return {-2, 0}
+        end
+        return {tonumber(round), redis.call('HLEN', KEYS[1]) - 1}
+        ",
+    )
+});
+
+/// Closes a round if it's still the open one, returning its moves as a flat
+/// player/choice list and opening the next round. Returns nil when the round
+/// was already closed, so a timeout and the last move can't both resolve it.
+static TAKE_ROUND: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('HGET', KEYS[1], 'round') ~= ARGV[1] then
+            return false
+        end
+        local fields = redis.call('HGETALL', KEYS[1])
+        local moves = {}
+        for i = 1, #fields, 2 do
+            if string.sub(fields[i], 1, 5) == 'move:' then
+                redis.call('HDEL', KEYS[1], fields[i])
+                table.insert(moves, string.sub(fields[i], 6))
+                table.insert(moves, fields[i + 1])
+            end
+        end
+        redis.call('HINCRBY', KEYS[1], 'round', 1)
+        return moves
+        ",
+    )
+});
+
+/// Opens the first round of a match, discarding any earlier round state.
+pub async fn open_rps_match(lobby_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::lobby_rps_round(KeyPart::Id(lobby_id));
+    let _: () = redis::pipe()
+        .atomic()
+        .del(&key)
+        .ignore()
+        .hset(&key, "round", 1)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+pub async fn get_rps_round(lobby_id: Uuid, redis: RedisClient) -> Result<Option<u32>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let round: Option<u32> = conn
+        .hget(RedisKey::lobby_rps_round(KeyPart::Id(lobby_id)), "round")
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(round)
+}
+
+pub async fn lock_rps_move(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    choice: RpsChoice,
+    redis: RedisClient,
+) -> Result<RpsMoveOutcome, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (round, moves): (i64, i64) = LOCK_MOVE
+        .key(RedisKey::lobby_rps_round(KeyPart::Id(lobby_id)))
+        .arg(player_id.to_string())
+        .arg(choice.as_str())
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(match round {
+        -1 => RpsMoveOutcome::NoOpenRound,
+        -2 => RpsMoveOutcome::AlreadyMoved,
+        round => RpsMoveOutcome::Locked {
+            round: round as u32,
+            moves: moves as usize,
+        },
+    })
+}
+
+/// Closes `round` and returns its moves, or `None` if it was already closed.
+pub async fn take_rps_round(
+    lobby_id: Uuid,
+    round: u32,
+    redis: RedisClient,
+) -> Result<Option<HashMap<Uuid, RpsChoice>>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let flat: Option<Vec<String>> = TAKE_ROUND
+        .key(RedisKey::lobby_rps_round(KeyPart::Id(lobby_id)))
+        .arg(round)
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(flat.map(|flat| {
+        flat.chunks_exact(2)
+            .filter_map(|pair| Some((Uuid::parse_str(&pair[0]).ok()?, pair[1].parse().ok()?)))
+            .collect()
+    }))
+}
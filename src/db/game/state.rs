@@ -1,14 +1,23 @@
-use redis::AsyncCommands;
+use chrono::Utc;
+use redis::{AsyncCommands, Script};
 use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::LazyLock};
 use uuid::Uuid;
 
 use crate::{
     errors::AppError,
     games::lexi_wars::rules::RuleContext,
-    models::redis::{KeyPart, RedisKey},
+    models::{
+        game::{GamePause, PauseReason},
+        lexi_wars::RecentWord,
+        redis::{KeyPart, RedisKey},
+    },
     state::RedisClient,
 };
 
+/// How many accepted words observers can see
+const RECENT_WORDS_LEN: isize = 20;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LobbyGameState {
     pub rule_context: RuleContext,
@@ -115,9 +124,268 @@ pub async fn get_rule_index(lobby_id: Uuid, redis: RedisClient) -> Result<Option
     Ok(rule_index)
 }
 
-pub async fn set_current_turn(
+/// A pending turn timeout read from the scheduler's deadline set.
+#[derive(Debug, Clone)]
+pub struct TurnDeadline {
+    pub lobby_id: Uuid,
+    pub player_id: Uuid,
+    pub deadline_ms: i64,
+}
+
+/// Removes a deadline only if it is still the one that was read, so a turn
+/// rescheduled in the meantime is left alone.
+static CLAIM_TURN_DEADLINE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local score = redis.call('ZSCORE', KEYS[1], ARGV[1])
+        if not score or tonumber(score) ~= tonumber(ARGV[2]) then
+            return 0
+        end
+        if redis.call('HGET', KEYS[2], ARGV[1]) ~= ARGV[3] then
+            return 0
+        end
+        redis.call('ZREM', KEYS[1], ARGV[1])
+        redis.call('HDEL', KEYS[2], ARGV[1])
+        return 1
+        ",
+    )
+});
+
+/// Sets the turn deadline for a lobby, replacing any earlier one, and records
+/// when the turn started. Returns the player who owned the previous deadline.
+pub async fn schedule_turn(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    turn_secs: u64,
+    redis: RedisClient,
+) -> Result<Option<Uuid>, AppError> {
+    schedule_turn_ms(lobby_id, player_id, turn_secs * 1000, redis).await
+}
+
+/// Same as `schedule_turn`, for turns not lasting whole seconds.
+pub async fn schedule_turn_ms(
     lobby_id: Uuid,
     player_id: Uuid,
+    turn_ms: u64,
+    redis: RedisClient,
+) -> Result<Option<Uuid>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let now_ms = Utc::now().timestamp_millis();
+    let deadline_ms = now_ms + turn_ms as i64;
+    let lobby_id_str = lobby_id.to_string();
+
+    let (previous,): (Option<String>,) = redis::pipe()
+        .atomic()
+        .hget(RedisKey::turn_owners(), &lobby_id_str)
+        .hset(
+            RedisKey::turn_owners(),
+            &lobby_id_str,
+            player_id.to_string(),
+        )
+        .ignore()
+        .zadd(RedisKey::turn_deadlines(), &lobby_id_str, deadline_ms)
+        .ignore()
+        .hset(RedisKey::turn_started(), &lobby_id_str, now_ms)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(previous.and_then(|id| Uuid::parse_str(&id).ok()))
+}
+
+pub async fn get_turn_deadlines(redis: RedisClient) -> Result<Vec<TurnDeadline>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (deadlines, owners): (Vec<(String, i64)>, HashMap<String, String>) = redis::pipe()
+        .zrange_withscores(RedisKey::turn_deadlines(), 0, -1)
+        .hgetall(RedisKey::turn_owners())
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(deadlines
+        .into_iter()
+        .filter_map(|(lobby, deadline_ms)| {
+            let player_id = owners.get(&lobby).and_then(|id| Uuid::parse_str(id).ok())?;
+            Some(TurnDeadline {
+                lobby_id: Uuid::parse_str(&lobby).ok()?,
+                player_id,
+                deadline_ms,
+            })
+        })
+        .collect())
+}
+
+/// When the current turn of a lobby times out, in unix milliseconds.
+pub async fn get_turn_deadline(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<Option<i64>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let deadline: Option<f64> = conn
+        .zscore(RedisKey::turn_deadlines(), lobby_id.to_string())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(deadline.map(|ms| ms as i64))
+}
+
+/// When the current turn of a lobby started, in unix milliseconds.
+pub async fn get_turn_started(lobby_id: Uuid, redis: RedisClient) -> Result<Option<i64>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let started: Option<i64> = conn
+        .hget(RedisKey::turn_started(), lobby_id.to_string())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(started)
+}
+
+/// Takes ownership of an expired deadline. Only one caller wins, which keeps
+/// the timeout from firing twice when several instances share Redis.
+pub async fn claim_turn_deadline(
+    deadline: &TurnDeadline,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let claimed: i32 = CLAIM_TURN_DEADLINE
+        .key(RedisKey::turn_deadlines())
+        .key(RedisKey::turn_owners())
+        .arg(deadline.lobby_id.to_string())
+        .arg(deadline.deadline_ms)
+        .arg(deadline.player_id.to_string())
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(claimed == 1)
+}
+
+/// Takes the lobby's turn deadline off the scheduler and stores what was left
+/// of it with the pause. Does nothing when the game is already paused or no
+/// turn is running.
+static PAUSE_TURN: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('EXISTS', KEYS[3]) == 1 then
+            return 0
+        end
+        local deadline = redis.call('ZSCORE', KEYS[1], ARGV[1])
+        local owner = redis.call('HGET', KEYS[2], ARGV[1])
+        if not deadline or not owner then
+            return 0
+        end
+        local remaining = math.max(tonumber(deadline) - tonumber(ARGV[2]), 0)
+        redis.call('ZREM', KEYS[1], ARGV[1])
+        redis.call('HDEL', KEYS[2], ARGV[1])
+        redis.call('HSET', KEYS[3], 'reason', ARGV[3], 'paused_at', ARGV[2],
+            'player_id', owner, 'remaining_ms', string.format('%d', remaining))
+        return 1
+        ",
+    )
+});
+
+/// Pauses the lobby's game, freezing the current turn. Returns the pause, or
+/// `None` when the game was already paused or had no running turn.
+pub async fn pause_turn(
+    lobby_id: Uuid,
+    reason: PauseReason,
+    redis: RedisClient,
+) -> Result<Option<GamePause>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let paused: i32 = PAUSE_TURN
+        .key(RedisKey::turn_deadlines())
+        .key(RedisKey::turn_owners())
+        .key(RedisKey::lobby_pause(KeyPart::Id(lobby_id)))
+        .arg(lobby_id.to_string())
+        .arg(Utc::now().timestamp_millis())
+        .arg(reason.as_str())
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    drop(conn);
+
+    if paused == 0 {
+        return Ok(None);
+    }
+    get_game_pause(lobby_id, redis).await
+}
+
+pub async fn get_game_pause(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<Option<GamePause>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let map: HashMap<String, String> = conn
+        .hgetall(RedisKey::lobby_pause(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if map.is_empty() {
+        return Ok(None);
+    }
+    GamePause::from_redis_hash(&map).map(Some)
+}
+
+/// Reads and clears the lobby's pause. Only one caller gets it, so a game
+/// resumed from several places at once gets its turn back once.
+pub async fn take_game_pause(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<Option<GamePause>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let pause_key = RedisKey::lobby_pause(KeyPart::Id(lobby_id));
+    let (map,): (HashMap<String, String>,) = redis::pipe()
+        .atomic()
+        .hgetall(&pause_key)
+        .del(&pause_key)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if map.is_empty() {
+        return Ok(None);
+    }
+    GamePause::from_redis_hash(&map).map(Some)
+}
+
+/// Sets when the lobby's game reaches its time limit, in unix milliseconds.
+pub async fn set_game_ends_at(
+    lobby_id: Uuid,
+    ends_at_ms: i64,
     redis: RedisClient,
 ) -> Result<(), AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
@@ -125,15 +393,206 @@ pub async fn set_current_turn(
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
-    let current_turn_key = RedisKey::lobby_current_turn(KeyPart::Id(lobby_id));
     let _: () = conn
-        .set(&current_turn_key, player_id.to_string())
+        .set(RedisKey::lobby_ends_at(KeyPart::Id(lobby_id)), ends_at_ms)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+pub async fn get_game_ends_at(lobby_id: Uuid, redis: RedisClient) -> Result<Option<i64>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let ends_at: Option<i64> = conn
+        .get(RedisKey::lobby_ends_at(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(ends_at)
+}
+
+/// Clears the time limit, true for the one caller that removed it, so a game
+/// is only ended once when several instances reach its limit.
+pub async fn take_game_ends_at(lobby_id: Uuid, redis: RedisClient) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let removed: usize = conn
+        .del(RedisKey::lobby_ends_at(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(removed > 0)
+}
+
+/// Records games cut off by a shutdown along with the time left on the
+/// current turn, so the next boot can resume them without penalising the
+/// player whose turn it was.
+pub async fn mark_games_interrupted(
+    lobby_ids: &[Uuid],
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    if lobby_ids.is_empty() {
+        return Ok(());
+    }
+
+    let now = Utc::now().timestamp_millis();
+    let deadlines: HashMap<Uuid, i64> = get_turn_deadlines(redis.clone())
+        .await?
+        .into_iter()
+        .map(|d| (d.lobby_id, d.deadline_ms))
+        .collect();
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let entries: Vec<(String, i64)> = lobby_ids
+        .iter()
+        .map(|id| {
+            let remaining_ms = deadlines.get(id).map_or(0, |d| (d - now).max(0));
+            (id.to_string(), remaining_ms)
+        })
+        .collect();
+
+    let _: () = conn
+        .hset_multiple(RedisKey::interrupted_games(), &entries)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Games recorded by [`mark_games_interrupted`], with the milliseconds that
+/// were left on each current turn.
+pub async fn get_interrupted_games(redis: RedisClient) -> Result<HashMap<Uuid, i64>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let entries: HashMap<String, i64> = conn
+        .hgetall(RedisKey::interrupted_games())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(entries
+        .into_iter()
+        .filter_map(|(id, remaining_ms)| Some((Uuid::parse_str(&id).ok()?, remaining_ms)))
+        .collect())
+}
+
+/// Forgets an interrupted game once it has been picked back up.
+pub async fn remove_interrupted_game(lobby_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .hdel(RedisKey::interrupted_games(), lobby_id.to_string())
         .await
         .map_err(AppError::RedisCommandError)?;
 
     Ok(())
 }
 
+pub async fn set_current_turn(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    // Skip votes only count against the turn they were cast in
+    let _: () = redis::pipe()
+        .atomic()
+        .set(
+            RedisKey::lobby_current_turn(KeyPart::Id(lobby_id)),
+            player_id.to_string(),
+        )
+        .ignore()
+        .del(RedisKey::lobby_skip_votes(KeyPart::Id(lobby_id)))
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Records a vote to skip the current turn, returning how many players voted.
+pub async fn add_skip_vote(
+    lobby_id: Uuid,
+    voter_id: Uuid,
+    redis: RedisClient,
+) -> Result<usize, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let votes_key = RedisKey::lobby_skip_votes(KeyPart::Id(lobby_id));
+    let (votes,): (usize,) = redis::pipe()
+        .atomic()
+        .sadd(&votes_key, voter_id.to_string())
+        .ignore()
+        .scard(&votes_key)
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(votes)
+}
+
+/// Drops the current turn's skip votes. Only one caller gets true, so a
+/// majority reached by simultaneous votes skips the turn once.
+pub async fn take_skip_votes(lobby_id: Uuid, redis: RedisClient) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let removed: usize = conn
+        .del(RedisKey::lobby_skip_votes(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(removed > 0)
+}
+
+/// Remembers that the player's turn was skipped, true the first time.
+pub async fn mark_turn_skipped(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let added: usize = conn
+        .sadd(
+            RedisKey::lobby_skipped_players(KeyPart::Id(lobby_id)),
+            player_id.to_string(),
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(added > 0)
+}
+
 pub async fn get_current_turn(
     lobby_id: Uuid,
     redis: RedisClient,
@@ -160,6 +619,51 @@ pub async fn get_current_turn(
     }
 }
 
+/// Milliseconds left in the player's time bank, `None` before their first turn.
+pub async fn get_time_bank(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    redis: RedisClient,
+) -> Result<Option<u64>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let remaining_ms: Option<u64> = conn
+        .hget(
+            RedisKey::lobby_time_banks(KeyPart::Id(lobby_id)),
+            player_id.to_string(),
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(remaining_ms)
+}
+
+pub async fn set_time_bank(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    remaining_ms: u64,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .hset(
+            RedisKey::lobby_time_banks(KeyPart::Id(lobby_id)),
+            player_id.to_string(),
+            remaining_ms,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
 pub async fn add_eliminated_player(
     lobby_id: Uuid,
     player_id: Uuid,
@@ -287,6 +791,192 @@ pub async fn get_current_rule(
     Ok(rule)
 }
 
+/// Adds points for an accepted word and returns the player's new total.
+pub async fn add_player_score(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    points: u64,
+    redis: RedisClient,
+) -> Result<u64, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let total: u64 = conn
+        .hincr(
+            RedisKey::lobby_scores(KeyPart::Id(lobby_id)),
+            player_id.to_string(),
+            points,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(total)
+}
+
+pub async fn get_player_scores(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<HashMap<Uuid, u64>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let scores: HashMap<String, u64> = conn
+        .hgetall(RedisKey::lobby_scores(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(scores
+        .into_iter()
+        .filter_map(|(id, score)| Some((Uuid::parse_str(&id).ok()?, score)))
+        .collect())
+}
+
+/// Adds the time the player took to find an accepted word.
+pub async fn add_response_time(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    elapsed_ms: u64,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: u64 = conn
+        .hincr(
+            RedisKey::lobby_response_times(KeyPart::Id(lobby_id)),
+            player_id.to_string(),
+            elapsed_ms,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+pub async fn get_response_times(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<HashMap<Uuid, u64>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let times: HashMap<String, u64> = conn
+        .hgetall(RedisKey::lobby_response_times(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(times
+        .into_iter()
+        .filter_map(|(id, ms)| Some((Uuid::parse_str(&id).ok()?, ms)))
+        .collect())
+}
+
+/// Remembers an accepted word for the live observer view.
+pub async fn push_recent_word(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    word: &str,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let entry = RecentWord {
+        word: word.to_string(),
+        player_id,
+        timestamp: Utc::now().timestamp(),
+    };
+    let json = serde_json::to_string(&entry)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize recent word: {}", e)))?;
+
+    let key = RedisKey::lobby_recent_words(KeyPart::Id(lobby_id));
+    let _: () = redis::pipe()
+        .atomic()
+        .lpush(&key, json)
+        .ignore()
+        .ltrim(&key, 0, RECENT_WORDS_LEN - 1)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Game state read in a single transaction so observers never see a half-applied turn.
+pub struct LiveGameState {
+    pub game_started: bool,
+    pub current_turn: Option<Uuid>,
+    pub current_rule: Option<String>,
+    pub turn_deadline_ms: Option<i64>,
+    pub eliminated_players: Vec<Uuid>,
+    pub scores: HashMap<Uuid, u64>,
+    pub recent_words: Vec<RecentWord>,
+}
+
+type LiveGameRow = (
+    Option<bool>,
+    Option<String>,
+    Option<String>,
+    Option<i64>,
+    Vec<String>,
+    HashMap<String, u64>,
+    Vec<String>,
+);
+
+pub async fn get_live_game_state(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<LiveGameState, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (game_started, current_turn, current_rule, deadline, eliminated, scores, recent): LiveGameRow =
+        redis::pipe()
+        .atomic()
+        .get(RedisKey::lobby_game_started(KeyPart::Id(lobby_id)))
+        .get(RedisKey::lobby_current_turn(KeyPart::Id(lobby_id)))
+        .get(RedisKey::lobby_current_rule(KeyPart::Id(lobby_id)))
+        .zscore(RedisKey::turn_deadlines(), lobby_id.to_string())
+        .smembers(RedisKey::lobby_eliminated_players(KeyPart::Id(lobby_id)))
+        .hgetall(RedisKey::lobby_scores(KeyPart::Id(lobby_id)))
+        .lrange(RedisKey::lobby_recent_words(KeyPart::Id(lobby_id)), 0, -1)
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(LiveGameState {
+        game_started: game_started.unwrap_or(false),
+        current_turn: current_turn.and_then(|id| Uuid::parse_str(&id).ok()),
+        current_rule,
+        turn_deadline_ms: deadline,
+        eliminated_players: eliminated
+            .iter()
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .collect(),
+        scores: scores
+            .into_iter()
+            .filter_map(|(id, score)| Some((Uuid::parse_str(&id).ok()?, score)))
+            .collect(),
+        recent_words: recent
+            .iter()
+            .filter_map(|json| serde_json::from_str(json).ok())
+            .collect(),
+    })
+}
+
 pub async fn clear_lobby_game_state(lobby_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
@@ -297,15 +987,40 @@ pub async fn clear_lobby_game_state(lobby_id: Uuid, redis: RedisClient) -> Resul
         RedisKey::lobby_rule_context(KeyPart::Id(lobby_id)),
         RedisKey::lobby_rule_index(KeyPart::Id(lobby_id)),
         RedisKey::lobby_current_turn(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_time_banks(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_response_times(KeyPart::Id(lobby_id)),
         RedisKey::lobby_eliminated_players(KeyPart::Id(lobby_id)),
         RedisKey::lobby_game_started(KeyPart::Id(lobby_id)),
         RedisKey::lobby_current_rule(KeyPart::Id(lobby_id)),
         RedisKey::lobby_used_words(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_recent_words(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_rps_round(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_board(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_typing_race(KeyPart::Id(lobby_id)),
         RedisKey::lobby_current_players(KeyPart::Id(lobby_id)),
         RedisKey::lobby_spectators(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_bots(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_skip_votes(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_skipped_players(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_pause(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_ends_at(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_owner(KeyPart::Id(lobby_id)),
     ];
 
-    let _: () = conn.del(&keys).await.map_err(AppError::RedisCommandError)?;
+    let _: () = redis::pipe()
+        .del(&keys)
+        .ignore()
+        .zrem(RedisKey::turn_deadlines(), lobby_id.to_string())
+        .ignore()
+        .hdel(RedisKey::turn_owners(), lobby_id.to_string())
+        .ignore()
+        .hdel(RedisKey::turn_started(), lobby_id.to_string())
+        .ignore()
+        .hdel(RedisKey::interrupted_games(), lobby_id.to_string())
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
 
     Ok(())
 }
@@ -0,0 +1,178 @@
+use redis::{AsyncCommands, Script};
+use std::{collections::HashMap, sync::LazyLock};
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        redis::{KeyPart, RedisKey},
+        typing_race::TypingResult,
+    },
+    state::RedisClient,
+};
+
+/// Result of recording a typing race submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    /// Result stored; `submitted` counts everyone who has submitted so far
+    Recorded {
+        submitted: usize,
+    },
+    AlreadySubmitted,
+    NoOpenRace,
+}
+
+/// The prompt of a running race and when it was shown, in unix millis.
+#[derive(Debug, Clone)]
+pub struct RaceState {
+    pub prompt: String,
+    pub started_at: i64,
+    pub ended: bool,
+}
+
+/// Stores a player's result unless they already submitted or the race is
+/// over. Returns the number of results, `-1` without an open race and `-2`
+/// for a repeated submission.
+static RECORD_RESULT: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('HEXISTS', KEYS[1], 'prompt') == 0
+            or redis.call('HEXISTS', KEYS[1], 'ended') == 1 then
+            return -1
+        end
+        if redis.call('HSETNX', KEYS[1], 'result:' .. ARGV[1], ARGV[2]) == 0 then
+            return -2
+        end
+        local count = 0
+        for _, field in ipairs(redis.call('HKEYS', KEYS[1])) do
+            if string.sub(field, 1, 7) == 'result:' then
+                count = count + 1
+            end
+        end
+        return count
+        ",
+    )
+});
+
+/// Opens a race on `prompt`, discarding any earlier race state.
+pub async fn open_race(
+    lobby_id: Uuid,
+    prompt: &str,
+    started_at: i64,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::lobby_typing_race(KeyPart::Id(lobby_id));
+    let _: () = redis::pipe()
+        .atomic()
+        .del(&key)
+        .ignore()
+        .hset(&key, "prompt", prompt)
+        .ignore()
+        .hset(&key, "started_at", started_at)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+pub async fn get_race(lobby_id: Uuid, redis: RedisClient) -> Result<Option<RaceState>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (prompt, started_at, ended): (Option<String>, Option<i64>, Option<String>) = conn
+        .hget(
+            RedisKey::lobby_typing_race(KeyPart::Id(lobby_id)),
+            &["prompt", "started_at", "ended"],
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(prompt
+        .zip(started_at)
+        .map(|(prompt, started_at)| RaceState {
+            prompt,
+            started_at,
+            ended: ended.is_some(),
+        }))
+}
+
+pub async fn record_submission(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    result: &TypingResult,
+    redis: RedisClient,
+) -> Result<SubmitOutcome, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let json = serde_json::to_string(result).map_err(|e| AppError::Serialization(e.to_string()))?;
+    let count: i64 = RECORD_RESULT
+        .key(RedisKey::lobby_typing_race(KeyPart::Id(lobby_id)))
+        .arg(player_id.to_string())
+        .arg(json)
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(match count {
+        -1 => SubmitOutcome::NoOpenRace,
+        -2 => SubmitOutcome::AlreadySubmitted,
+        count => SubmitOutcome::Recorded {
+            submitted: count as usize,
+        },
+    })
+}
+
+pub async fn get_submissions(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<HashMap<Uuid, TypingResult>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let raw: HashMap<String, String> = conn
+        .hgetall(RedisKey::lobby_typing_race(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(raw
+        .iter()
+        .filter_map(|(field, value)| {
+            let player_id = Uuid::parse_str(field.strip_prefix("result:")?).ok()?;
+            Some((player_id, serde_json::from_str(value).ok()?))
+        })
+        .collect())
+}
+
+/// Marks the race as over, returning false if it already was, so the timer
+/// and the last submission can't both end it.
+pub async fn claim_race_end(lobby_id: Uuid, redis: RedisClient) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let claimed: bool = conn
+        .hset_nx(
+            RedisKey::lobby_typing_race(KeyPart::Id(lobby_id)),
+            "ended",
+            1,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(claimed)
+}
@@ -0,0 +1,182 @@
+use redis::{AsyncCommands, Script};
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        admin::WordReport,
+        game::Language,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// How long a rejected word can still be reported
+const REJECTED_WORDS_TTL_SECS: i64 = 60 * 60;
+
+/// Result of a player reporting a rejected word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportOutcome {
+    Reported,
+    AlreadyReported,
+    /// The player never had the word rejected in this lobby
+    NotRejected,
+}
+
+/// Counts a report once per player, and only for a word the player had
+/// rejected. Returns `1` when counted, `0` for a repeat and `-1` otherwise.
+static REPORT_WORD: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('SISMEMBER', KEYS[1], ARGV[1]) == 0 then
+            return -1
+        end
+        if redis.call('SADD', KEYS[3], ARGV[2]) == 0 then
+            return 0
+        end
+        redis.call('ZINCRBY', KEYS[2], 1, ARGV[1])
+        return 1
+        ",
+    )
+});
+
+/// Reported words are used as key parts; dictionary words are letters only.
+fn is_reportable(word: &str) -> bool {
+    (1..=64).contains(&word.chars().count()) && word.chars().all(char::is_alphabetic)
+}
+
+/// Remembers a word the player had rejected as invalid so they can report it.
+pub async fn record_rejected_word(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    word: &str,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    if !is_reportable(word) {
+        return Ok(());
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::lobby_rejected_words(KeyPart::Id(lobby_id), KeyPart::Id(player_id));
+    let _: () = redis::pipe()
+        .sadd(&key, word)
+        .ignore()
+        .expire(&key, REJECTED_WORDS_TTL_SECS)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+pub async fn report_word(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    word: &str,
+    language: Language,
+    redis: RedisClient,
+) -> Result<ReportOutcome, AppError> {
+    let word = word.trim().to_lowercase();
+    if !is_reportable(&word) {
+        return Ok(ReportOutcome::NotRejected);
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let language_part = || KeyPart::Str(language.as_str().to_string());
+    let outcome: i64 = REPORT_WORD
+        .key(RedisKey::lobby_rejected_words(
+            KeyPart::Id(lobby_id),
+            KeyPart::Id(player_id),
+        ))
+        .key(RedisKey::word_reports(language_part()))
+        .key(RedisKey::word_reporters(
+            language_part(),
+            KeyPart::Str(word.clone()),
+        ))
+        .arg(&word)
+        .arg(player_id.to_string())
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(match outcome {
+        1 => ReportOutcome::Reported,
+        0 => ReportOutcome::AlreadyReported,
+        _ => ReportOutcome::NotRejected,
+    })
+}
+
+/// Most reported words of a language first.
+pub async fn get_word_reports(
+    language: Language,
+    limit: usize,
+    redis: RedisClient,
+) -> Result<Vec<WordReport>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let reports: Vec<(String, u64)> = conn
+        .zrevrange_withscores(
+            RedisKey::word_reports(KeyPart::Str(language.as_str().to_string())),
+            0,
+            limit as isize - 1,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(reports
+        .into_iter()
+        .map(|(word, reports)| WordReport {
+            word,
+            language,
+            reports,
+        })
+        .collect())
+}
+
+/// Drops the reports of words once they were approved or dismissed.
+pub async fn clear_word_reports(
+    words: &[String],
+    language: Language,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    if words.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let language_part = || KeyPart::Str(language.as_str().to_string());
+    let mut pipe = redis::pipe();
+    pipe.atomic()
+        .zrem(RedisKey::word_reports(language_part()), words)
+        .ignore();
+    for word in words {
+        pipe.del(RedisKey::word_reporters(
+            language_part(),
+            KeyPart::Str(word.clone()),
+        ))
+        .ignore();
+    }
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
@@ -1,12 +1,28 @@
 use redis::AsyncCommands;
+use std::{
+    collections::HashSet,
+    sync::{OnceLock, PoisonError, RwLock, RwLockReadGuard},
+};
 use uuid::Uuid;
 
 use crate::{
+    cluster::{ClusterMessage, publish},
+    config,
     errors::AppError,
-    models::redis::{KeyPart, RedisKey},
+    models::{
+        game::Language,
+        redis::{KeyPart, RedisKey},
+    },
     state::RedisClient,
 };
 
+/// Local copy of the default word set so validity checks skip Redis.
+/// Admin word edits reach every instance's copy over the cluster channel.
+static DEFAULT_DICTIONARY: OnceLock<RwLock<HashSet<String>>> = OnceLock::new();
+
+/// Most words a single dictionary edit may touch; bigger lists belong in a pack.
+pub const MAX_DICTIONARY_EDIT: usize = 100;
+
 pub async fn add_word_set(redis: RedisClient) -> Result<(), AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
@@ -47,28 +63,506 @@ pub async fn add_word_set(redis: RedisClient) -> Result<(), AppError> {
     Ok(())
 }
 
-pub async fn is_valid_word(word: &str, redis: RedisClient) -> Result<bool, AppError> {
+/// Word set of a language; English keeps the original default set.
+fn language_words_key(language: Language) -> String {
+    match language {
+        Language::En => RedisKey::words_set(),
+        other => RedisKey::language_words_set(KeyPart::Str(other.as_str().to_string())),
+    }
+}
+
+/// Seeds the word sets of the non-English languages from `words_<language>.json`
+/// in the configured dictionary directory. Config validation already made sure
+/// every file is there, so a file that can't be read fails startup.
+pub async fn add_language_word_sets(redis: RedisClient) -> Result<(), AppError> {
+    let dir = &config::get().dictionary_dir;
+
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
-    let words_key = RedisKey::words_set();
+    for language in Language::ALL.into_iter().filter(|l| *l != Language::En) {
+        let words_key = language_words_key(language);
+        let exists: bool = conn
+            .exists(&words_key)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+
+        if exists {
+            tracing::info!("{} word set already exists in Redis", language.as_str());
+            continue;
+        }
+
+        let path = config::dictionary_file(dir, language);
+        let words_json = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| AppError::EnvError(format!("Failed to read {}: {}", path.display(), e)))?;
+        let words: Vec<String> = serde_json::from_str(&words_json).map_err(|e| {
+            AppError::Deserialization(format!("Failed to parse {}: {}", path.display(), e))
+        })?;
+        let words: Vec<String> = words
+            .into_iter()
+            .map(|w| w.trim().to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        if words.is_empty() {
+            return Err(AppError::Deserialization(format!(
+                "{} has no words",
+                path.display()
+            )));
+        }
+
+        let _: () = conn
+            .sadd(&words_key, &words)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+
+        tracing::info!("Added {} {} words to Redis", words.len(), language.as_str());
+    }
+
+    Ok(())
+}
+
+/// Whether lobbies can be played in `language`.
+pub async fn language_installed(language: Language, redis: RedisClient) -> Result<bool, AppError> {
+    if language == Language::En {
+        return Ok(true);
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let exists: bool = conn
+        .exists(language_words_key(language))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(exists)
+}
+
+pub async fn add_offensive_word_set(redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let words_key = RedisKey::offensive_words_set();
+
+    let exists: bool = conn
+        .exists(&words_key)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if exists {
+        tracing::info!("Offensive word set already exists in Redis");
+        return Ok(());
+    }
+
+    let words_json = include_str!("../../assets/offensive_words.json");
+    let words: Vec<String> = serde_json::from_str(words_json).map_err(|e| {
+        AppError::Deserialization(format!("Failed to parse offensive_words.json: {}", e))
+    })?;
+
+    if !words.is_empty() {
+        let _: () = conn
+            .sadd(&words_key, words)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+    }
+
+    tracing::info!("Successfully added offensive word set to Redis");
+    Ok(())
+}
+
+/// Copies the default word set from Redis into memory. Called once at startup
+/// after the set has been seeded.
+pub async fn load_default_dictionary(redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let words: HashSet<String> = conn
+        .smembers(RedisKey::words_set())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let count = words.len();
+    if DEFAULT_DICTIONARY.set(RwLock::new(words)).is_err() {
+        tracing::warn!("Default dictionary already loaded");
+        return Ok(());
+    }
+
+    tracing::info!("Loaded {} words into the in-memory dictionary", count);
+    Ok(())
+}
+
+/// The in-memory default word set, once loaded at init.
+pub fn default_dictionary() -> Option<RwLockReadGuard<'static, HashSet<String>>> {
+    DEFAULT_DICTIONARY
+        .get()
+        .map(|dictionary| dictionary.read().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Replaces the in-memory dictionary with the word set in Redis, picking up
+/// edits this instance may have missed.
+pub async fn reload_default_dictionary(redis: RedisClient) -> Result<(), AppError> {
+    let Some(dictionary) = DEFAULT_DICTIONARY.get() else {
+        return load_default_dictionary(redis).await;
+    };
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let words: HashSet<String> = conn
+        .smembers(RedisKey::words_set())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    *dictionary.write().unwrap_or_else(PoisonError::into_inner) = words;
+    Ok(())
+}
+
+/// Applies an English word edit to the in-memory dictionary.
+pub fn apply_dictionary_change(added: &[String], removed: &[String]) {
+    let Some(dictionary) = DEFAULT_DICTIONARY.get() else {
+        return;
+    };
+
+    let mut dictionary = dictionary.write().unwrap_or_else(PoisonError::into_inner);
+    dictionary.extend(added.iter().cloned());
+    for word in removed {
+        dictionary.remove(word);
+    }
+}
+
+fn normalize_dictionary_words(words: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut words: Vec<String> = words
+        .into_iter()
+        .map(|w| w.trim().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.sort();
+    words.dedup();
+
+    if words.is_empty() {
+        return Err(AppError::BadRequest("No words given".into()));
+    }
+    if words.len() > MAX_DICTIONARY_EDIT {
+        return Err(AppError::BadRequest(format!(
+            "At most {MAX_DICTIONARY_EDIT} words can be changed at once"
+        )));
+    }
+
+    Ok(words)
+}
+
+/// Adds words to a language's word set, returning the normalized words that
+/// were new. English edits apply to every instance's in-memory copy at once.
+pub async fn add_dictionary_words(
+    words: Vec<String>,
+    language: Language,
+    redis: RedisClient,
+) -> Result<Vec<String>, AppError> {
+    let words = normalize_dictionary_words(words)?;
+    if !language_installed(language, redis.clone()).await? {
+        return Err(AppError::BadRequest(format!(
+            "No {} dictionary is installed",
+            language.as_str()
+        )));
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let words_key = language_words_key(language);
+    let mut pipe = redis::pipe();
+    for word in &words {
+        pipe.sadd(&words_key, word);
+    }
+    let added: Vec<usize> = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let added: Vec<String> = words
+        .into_iter()
+        .zip(added)
+        .filter(|(_, count)| *count > 0)
+        .map(|(word, _)| word)
+        .collect();
+
+    if language == Language::En {
+        publish_dictionary_change(added.clone(), Vec::new(), &redis).await;
+    }
+
+    tracing::info!(
+        "Added {} {} dictionary words",
+        added.len(),
+        language.as_str()
+    );
+    Ok(added)
+}
+
+/// Removes words from a language's word set, returning the normalized words
+/// that were present.
+pub async fn remove_dictionary_words(
+    words: Vec<String>,
+    language: Language,
+    redis: RedisClient,
+) -> Result<Vec<String>, AppError> {
+    let words = normalize_dictionary_words(words)?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let words_key = language_words_key(language);
+    let mut pipe = redis::pipe();
+    for word in &words {
+        pipe.srem(&words_key, word);
+    }
+    let removed: Vec<usize> = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let removed: Vec<String> = words
+        .into_iter()
+        .zip(removed)
+        .filter(|(_, count)| *count > 0)
+        .map(|(word, _)| word)
+        .collect();
+
+    if language == Language::En {
+        publish_dictionary_change(Vec::new(), removed.clone(), &redis).await;
+    }
+
+    tracing::info!(
+        "Removed {} {} dictionary words",
+        removed.len(),
+        language.as_str()
+    );
+    Ok(removed)
+}
+
+/// Applies an English edit here right away and tells the other instances.
+/// Should the publish fail, they catch up when their listener resubscribes.
+async fn publish_dictionary_change(added: Vec<String>, removed: Vec<String>, redis: &RedisClient) {
+    apply_dictionary_change(&added, &removed);
+
+    let msg = ClusterMessage::DictionaryChanged { added, removed };
+    if let Err(e) = publish(&msg, redis).await {
+        tracing::error!("Failed to publish dictionary change: {}", e);
+    }
+}
+
+/// Checks the lobby's dictionary pack, falling back to the word set of the
+/// lobby's language when no pack is selected or the selected pack no longer
+/// exists.
+pub async fn is_valid_word(
+    word: &str,
+    pack: Option<&str>,
+    language: Language,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    let word = word.to_lowercase();
+
+    if pack.is_none()
+        && language == Language::En
+        && let Some(dictionary) = default_dictionary()
+    {
+        return Ok(dictionary.contains(&word));
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let mut words_key = language_words_key(language);
+    if let Some(pack) = pack {
+        let pack_key = RedisKey::word_pack(KeyPart::Str(pack.to_string()));
+        let exists: bool = conn
+            .exists(&pack_key)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+
+        if exists {
+            words_key = pack_key;
+        } else {
+            tracing::warn!(
+                "Dictionary pack {} missing, using the {} word set",
+                pack,
+                language.as_str()
+            );
+            if language == Language::En
+                && let Some(dictionary) = default_dictionary()
+            {
+                return Ok(dictionary.contains(&word));
+            }
+        }
+    }
+
     let is_member: bool = conn
-        .sismember(&words_key, word.to_lowercase())
+        .sismember(&words_key, &word)
         .await
         .map_err(AppError::RedisCommandError)?;
 
     Ok(is_member)
 }
 
-pub async fn _get_random_words(count: usize, redis: RedisClient) -> Result<Vec<String>, AppError> {
+/// Pack names are used as key parts, so only a small charset is allowed.
+pub fn normalize_pack_name(name: &str) -> Result<String, AppError> {
+    let normalized = name.trim().to_lowercase().replace(' ', "-");
+    let valid = (1..=32).contains(&normalized.len())
+        && normalized
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if !valid {
+        return Err(AppError::BadRequest(
+            "Pack name must be 1-32 letters, digits, '-' or '_'".into(),
+        ));
+    }
+
+    Ok(normalized)
+}
+
+/// Replaces the words of a dictionary pack, creating it if needed.
+pub async fn upload_word_pack(
+    name: &str,
+    words: Vec<String>,
+    redis: RedisClient,
+) -> Result<usize, AppError> {
+    let name = normalize_pack_name(name)?;
+
+    let words: Vec<String> = words
+        .into_iter()
+        .map(|w| w.trim().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    if words.is_empty() {
+        return Err(AppError::BadRequest("Dictionary pack has no words".into()));
+    }
+
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
-    let words_key = RedisKey::words_set();
+    let pack_key = RedisKey::word_pack(KeyPart::Str(name.clone()));
+
+    let (count,): (usize,) = redis::pipe()
+        .atomic()
+        .del(&pack_key)
+        .ignore()
+        .sadd(&pack_key, &words)
+        .ignore()
+        .scard(&pack_key)
+        .sadd(RedisKey::word_packs(), &name)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    tracing::info!("Uploaded dictionary pack {} with {} words", name, count);
+    Ok(count)
+}
+
+pub async fn word_pack_exists(name: &str, redis: RedisClient) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let exists: bool = conn
+        .sismember(RedisKey::word_packs(), name)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(exists)
+}
+
+pub async fn get_word_packs(redis: RedisClient) -> Result<Vec<String>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let mut packs: Vec<String> = conn
+        .smembers(RedisKey::word_packs())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    packs.sort();
+
+    Ok(packs)
+}
+
+/// Valid-but-offensive words rejected in family-friendly lobbies.
+pub async fn is_offensive_word(word: &str, redis: RedisClient) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let is_member: bool = conn
+        .sismember(RedisKey::offensive_words_set(), word.to_lowercase())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(is_member)
+}
+
+pub async fn get_offensive_words(redis: RedisClient) -> Result<HashSet<String>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let words: HashSet<String> = conn
+        .smembers(RedisKey::offensive_words_set())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(words)
+}
+
+/// Random words from the set a lobby validates against, falling back to the
+/// language's set like `is_valid_word` when the pack is missing.
+pub async fn get_random_words(
+    count: usize,
+    pack: Option<&str>,
+    language: Language,
+    redis: RedisClient,
+) -> Result<Vec<String>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let mut words_key = language_words_key(language);
+    if let Some(pack) = pack {
+        let pack_key = RedisKey::word_pack(KeyPart::Str(pack.to_string()));
+        let exists: bool = conn
+            .exists(&pack_key)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+        if exists {
+            words_key = pack_key;
+        }
+    }
+
     let words: Vec<String> = conn
         .srandmember_multiple(&words_key, count)
         .await
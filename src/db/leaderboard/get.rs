@@ -1,36 +1,55 @@
 use crate::{
-    db::user::get::get_user_by_id,
+    db::{
+        leaderboard::history::{MAX_MATCH_HISTORY, get_game_breakdowns, get_match_history},
+        user::get::get_user_by_id,
+        utils::zrevrange_offset,
+    },
     errors::AppError,
-    models::{leaderboard::LeaderBoard, redis::RedisKey},
+    models::{
+        leaderboard::{
+            Achievements, HeadToHead, LeaderBoard, MatchRecord, RecentForm, RivalStats, UserStats,
+        },
+        pagination::{PageCursor, Paginated},
+        redis::RedisKey,
+    },
     state::RedisClient,
 };
 use redis::AsyncCommands;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 pub async fn get_leaderboard(
-    limit: Option<u64>,
+    page: u32,
+    limit: u32,
+    after: Option<&PageCursor>,
     redis: RedisClient,
-) -> Result<Vec<LeaderBoard>, AppError> {
+) -> Result<Paginated<LeaderBoard>, AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
     let points_key = RedisKey::users_points();
+    let offset = ((page.saturating_sub(1)) as usize).saturating_mul(limit as usize);
+    let offset = zrevrange_offset(&mut conn, &points_key, offset, after).await?;
+    let end = offset.saturating_add(limit as usize).saturating_sub(1);
 
-    let top_users: Vec<(String, f64)> = if let Some(limit) = limit {
-        conn.zrevrange_withscores(&points_key, 0, limit as isize - 1)
-            .await
-            .map_err(AppError::RedisCommandError)?
-    } else {
-        conn.zrevrange_withscores(&points_key, 0, -1)
-            .await
-            .map_err(AppError::RedisCommandError)?
-    };
+    let total: u64 = conn
+        .zcard(&points_key)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let top_users: Vec<(String, f64)> = conn
+        .zrevrange_withscores(&points_key, offset as isize, end as isize)
+        .await
+        .map_err(AppError::RedisCommandError)?;
 
     if top_users.is_empty() {
-        return Ok(vec![]);
+        return Ok(Paginated::from_offset(Vec::new(), offset, limit, total));
     }
+    let last = top_users
+        .last()
+        .map(|(user_id, wars_point)| PageCursor::new(user_id, *wars_point));
 
     // Get user IDs for batch operations
     let user_ids: Vec<String> = top_users.iter().map(|(id, _)| id.clone()).collect();
@@ -67,6 +86,7 @@ pub async fn get_leaderboard(
 
         // Get user details using existing function
         let user = match get_user_by_id(user_uuid, redis.clone()).await {
+            Ok(user) if user.deleted => continue,
             Ok(mut user) => {
                 // Update wars_point from the sorted set (in case it's more recent)
                 user.wars_point = wars_point;
@@ -84,14 +104,14 @@ pub async fn get_leaderboard(
         leaderboard.push(LeaderBoard {
             user,
             win_rate,
-            rank: (idx + 1) as u64,
+            rank: (offset + idx + 1) as u64,
             total_match: matches,
             total_wins: wins,
             pnl,
         });
     }
 
-    Ok(leaderboard)
+    Ok(Paginated::from_offset(leaderboard, offset, limit, total).with_next_cursor(last))
 }
 
 pub async fn get_user_stat(user_id: Uuid, redis: RedisClient) -> Result<LeaderBoard, AppError> {
@@ -101,6 +121,9 @@ pub async fn get_user_stat(user_id: Uuid, redis: RedisClient) -> Result<LeaderBo
     })?;
 
     let user = get_user_by_id(user_id, redis.clone()).await?;
+    if user.deleted {
+        return Err(AppError::NotFound("User not found".into()));
+    }
 
     let user_id_str = user_id.to_string();
     let matches_key = RedisKey::users_matches();
@@ -143,3 +166,85 @@ pub async fn get_user_stat(user_id: Uuid, redis: RedisClient) -> Result<LeaderBo
         pnl,
     })
 }
+
+/// The user's overall stats with per-game totals, their form over the last
+/// `recent` games and the best results in their stored history.
+pub async fn get_user_stats(
+    user_id: Uuid,
+    recent: usize,
+    redis: RedisClient,
+) -> Result<UserStats, AppError> {
+    let overview = get_user_stat(user_id, redis.clone()).await?;
+    let games = get_game_breakdowns(user_id, redis.clone()).await?;
+    let history = get_match_history(user_id, MAX_MATCH_HISTORY as usize, redis).await?;
+
+    Ok(UserStats {
+        overview,
+        games,
+        recent_form: RecentForm::from_history(&history[..recent.min(history.len())]),
+        achievements: Achievements::from_history(&history),
+    })
+}
+
+/// Head-to-head record of two users over the games both have in their
+/// stored history.
+pub async fn get_head_to_head(
+    a: Uuid,
+    b: Uuid,
+    redis: RedisClient,
+) -> Result<HeadToHead, AppError> {
+    if a == b {
+        return Err(AppError::BadRequest(
+            "Head-to-head needs two different users".into(),
+        ));
+    }
+
+    let user_a = get_user_by_id(a, redis.clone()).await?;
+    let user_b = get_user_by_id(b, redis.clone()).await?;
+    if user_a.deleted || user_b.deleted {
+        return Err(AppError::NotFound("User not found".into()));
+    }
+
+    let history_a = get_match_history(a, MAX_MATCH_HISTORY as usize, redis.clone()).await?;
+    let ranks_b: HashMap<Uuid, usize> = get_match_history(b, MAX_MATCH_HISTORY as usize, redis)
+        .await?
+        .into_iter()
+        .map(|record| (record.lobby_id, record.rank))
+        .collect();
+
+    // Both histories are newest first, so the first shared game is the latest
+    let meetings: Vec<(&MatchRecord, usize)> = history_a
+        .iter()
+        .filter_map(|record| ranks_b.get(&record.lobby_id).map(|rank| (record, *rank)))
+        .collect();
+
+    let count = meetings.len();
+    let average = |sum: usize| {
+        if count > 0 {
+            sum as f64 / count as f64
+        } else {
+            0.0
+        }
+    };
+
+    Ok(HeadToHead {
+        meetings: count,
+        a: RivalStats {
+            user: user_a,
+            wins: meetings
+                .iter()
+                .filter(|(record, rank_b)| record.rank < *rank_b)
+                .count(),
+            average_rank: average(meetings.iter().map(|(record, _)| record.rank).sum()),
+        },
+        b: RivalStats {
+            user: user_b,
+            wins: meetings
+                .iter()
+                .filter(|(record, rank_b)| *rank_b < record.rank)
+                .count(),
+            average_rank: average(meetings.iter().map(|(_, rank_b)| rank_b).sum()),
+        },
+        last_met: meetings.first().map(|(record, _)| record.finished_at),
+    })
+}
@@ -0,0 +1,151 @@
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        leaderboard::{GameBreakdown, MatchRecord},
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Games kept in a user's match history; per-game totals count every game
+pub const MAX_MATCH_HISTORY: isize = 500;
+
+/// Adds a finished game to the user's history and per-game totals.
+pub async fn record_match(
+    user_id: Uuid,
+    record: &MatchRecord,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let json = serde_json::to_string(record)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize match: {}", e)))?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let history_key = RedisKey::user_match_history(KeyPart::Id(user_id));
+    let stats_key = RedisKey::user_game_stats(KeyPart::Id(user_id));
+    let field = |stat: &str| format!("{}:{}", record.game_id, stat);
+
+    let mut pipe = redis::pipe();
+    pipe.lpush(&history_key, json)
+        .ignore()
+        .ltrim(&history_key, 0, MAX_MATCH_HISTORY - 1)
+        .ignore()
+        .hset(&stats_key, field("name"), &record.game_name)
+        .ignore()
+        .hincr(&stats_key, field("games"), 1)
+        .ignore()
+        .hincr(&stats_key, field("rank_sum"), record.rank)
+        .ignore()
+        .hincr(&stats_key, field("wars_point"), record.wars_point)
+        .ignore();
+    if record.rank == 1 {
+        pipe.hincr(&stats_key, field("wins"), 1).ignore();
+    }
+    if let Some(prize) = record.prize {
+        pipe.hincr(&stats_key, field("prize"), prize).ignore();
+    }
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// The user's last `limit` games, newest first.
+pub async fn get_match_history(
+    user_id: Uuid,
+    limit: usize,
+    redis: RedisClient,
+) -> Result<Vec<MatchRecord>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let stored: Vec<String> = conn
+        .lrange(
+            RedisKey::user_match_history(KeyPart::Id(user_id)),
+            0,
+            limit as isize - 1,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(stored
+        .into_iter()
+        .filter_map(|json| match serde_json::from_str::<MatchRecord>(&json) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                tracing::warn!("Skipping malformed match record: {}", e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// The user's totals in each game they played, most played first.
+pub async fn get_game_breakdowns(
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<Vec<GameBreakdown>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let stored: HashMap<String, String> = conn
+        .hgetall(RedisKey::user_game_stats(KeyPart::Id(user_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut by_game: HashMap<Uuid, HashMap<&str, &str>> = HashMap::new();
+    for (field, value) in &stored {
+        let Some((game_id, stat)) = field.split_once(':') else {
+            continue;
+        };
+        if let Ok(game_id) = Uuid::parse_str(game_id) {
+            by_game
+                .entry(game_id)
+                .or_default()
+                .insert(stat, value.as_str());
+        }
+    }
+
+    let mut breakdowns: Vec<GameBreakdown> = by_game
+        .into_iter()
+        .map(|(game_id, stats)| {
+            let number = |stat: &str| -> f64 {
+                stats
+                    .get(stat)
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(0.0)
+            };
+            let games = number("games") as u64;
+            GameBreakdown {
+                game_id,
+                game_name: stats.get("name").unwrap_or(&"").to_string(),
+                games,
+                wins: number("wins") as u64,
+                average_rank: if games > 0 {
+                    number("rank_sum") / games as f64
+                } else {
+                    0.0
+                },
+                total_prize: number("prize"),
+                wars_point: number("wars_point"),
+            }
+        })
+        .collect();
+    breakdowns.sort_by_key(|breakdown| std::cmp::Reverse(breakdown.games));
+
+    Ok(breakdowns)
+}
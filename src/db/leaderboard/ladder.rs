@@ -0,0 +1,171 @@
+use chrono::{DateTime, Duration, Utc};
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    db::{user::get::get_user_by_id, utils::zrevrange_offset},
+    errors::AppError,
+    models::{
+        leaderboard::{LadderEntry, LadderTier},
+        pagination::{PageCursor, Paginated},
+        redis::RedisKey,
+    },
+    state::RedisClient,
+};
+
+/// ISO week label, e.g. `2025-W07`, the ladder rolls over on.
+pub fn ladder_week(now: DateTime<Utc>) -> String {
+    now.format("%G-W%V").to_string()
+}
+
+/// Adds a game's wars points to the ladder and returns the tier before and after.
+pub async fn record_ladder_result(
+    user_id: Uuid,
+    wars_point: f64,
+    redis: RedisClient,
+) -> Result<(LadderTier, LadderTier), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let user_id_str = user_id.to_string();
+    let (previous, updated): (Option<f64>, f64) = redis::pipe()
+        .atomic()
+        .zscore(RedisKey::ladder_rating(), &user_id_str)
+        .zincr(RedisKey::ladder_rating(), &user_id_str, wars_point)
+        .hset(
+            RedisKey::ladder_last_active(),
+            &user_id_str,
+            Utc::now().timestamp(),
+        )
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    // Ratings never go below zero, a bad game only drops you to the floor
+    let rating = if updated < 0.0 {
+        let _: () = conn
+            .zadd(RedisKey::ladder_rating(), &user_id_str, 0.0)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+        0.0
+    } else {
+        updated
+    };
+
+    Ok((
+        LadderTier::from_rating(previous.unwrap_or(0.0)),
+        LadderTier::from_rating(rating),
+    ))
+}
+
+/// Starts a new ladder week if the calendar moved on, decaying everyone who
+/// sat the last week out. Returns whether a rollover happened.
+pub async fn roll_ladder_week(decay_percent: u32, redis: RedisClient) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let now = Utc::now();
+    let week = ladder_week(now);
+
+    // GETSET so only one instance applies the decay for a given week
+    let previous: Option<String> = conn
+        .getset(RedisKey::ladder_week(), &week)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    match previous {
+        Some(previous) if previous != week => {}
+        // First boot starts the ladder without punishing anyone
+        _ => return Ok(false),
+    }
+
+    let cutoff = (now - Duration::weeks(1)).timestamp();
+    let last_active: HashMap<String, i64> = conn
+        .hgetall(RedisKey::ladder_last_active())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    let ratings: Vec<(String, f64)> = conn
+        .zrange_withscores(RedisKey::ladder_rating(), 0, -1)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let keep = 1.0 - decay_percent as f64 / 100.0;
+    let mut pipe = redis::pipe();
+    let mut decayed = 0;
+    for (user_id, rating) in ratings {
+        let inactive = last_active.get(&user_id).is_none_or(|ts| *ts < cutoff);
+        if inactive && rating > 0.0 {
+            pipe.zadd(RedisKey::ladder_rating(), &user_id, rating * keep)
+                .ignore();
+            decayed += 1;
+        }
+    }
+
+    if decayed > 0 {
+        let _: () = pipe
+            .query_async(&mut *conn)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+    }
+
+    tracing::info!(
+        "Ladder rolled over to {}, decayed {} players",
+        week,
+        decayed
+    );
+    Ok(true)
+}
+
+pub async fn get_ladder(
+    page: u32,
+    limit: u32,
+    after: Option<&PageCursor>,
+    redis: RedisClient,
+) -> Result<Paginated<LadderEntry>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let offset = ((page.saturating_sub(1)) as usize).saturating_mul(limit as usize);
+    let offset = zrevrange_offset(&mut conn, &RedisKey::ladder_rating(), offset, after).await?;
+    let end = offset.saturating_add(limit as usize).saturating_sub(1);
+
+    let total: u64 = conn
+        .zcard(RedisKey::ladder_rating())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let top: Vec<(String, f64)> = conn
+        .zrevrange_withscores(RedisKey::ladder_rating(), offset as isize, end as isize)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    let last = top
+        .last()
+        .map(|(user_id, rating)| PageCursor::new(user_id, *rating));
+
+    let mut entries = Vec::with_capacity(top.len());
+    for (idx, (user_id, rating)) in top.into_iter().enumerate() {
+        let Ok(user_id) = Uuid::parse_str(&user_id) else {
+            continue;
+        };
+        let user = match get_user_by_id(user_id, redis.clone()).await {
+            Ok(user) if !user.deleted => user,
+            _ => continue,
+        };
+
+        entries.push(LadderEntry {
+            user,
+            rating,
+            tier: LadderTier::from_rating(rating),
+            rank: (offset + idx + 1) as u64,
+        });
+    }
+
+    Ok(Paginated::from_offset(entries, offset, limit, total).with_next_cursor(last))
+}
@@ -1,2 +1,4 @@
 pub mod get;
+pub mod history;
+pub mod ladder;
 pub mod patch;
@@ -1,4 +1,5 @@
 use crate::{
+    db::user::cache::invalidate_user,
     errors::AppError,
     models::{
         game::ClaimState,
@@ -56,11 +57,13 @@ pub async fn update_user_stats(
         .arg(wars_point)
         .arg(&user_id_str);
 
-    // Update player rank in lobby player hash
+    // Update player rank and earned wars point in lobby player hash
     pipe.cmd("HSET")
         .arg(&player_key)
         .arg("rank")
-        .arg(rank.to_string());
+        .arg(rank.to_string())
+        .arg("wars_point")
+        .arg(wars_point.to_string());
 
     if let Some(prize_amount) = prize {
         if prize_amount != 0.0 {
@@ -73,7 +76,7 @@ pub async fn update_user_stats(
                 .arg("prize")
                 .arg(prize_amount.to_string());
 
-            let claim_json = serde_json::to_string(&ClaimState::NotClaimed)
+            let claim_json = serde_json::to_string(&ClaimState::Claimable)
                 .unwrap_or_else(|_| "null".to_string());
             pipe.cmd("HSET")
                 .arg(&player_key)
@@ -86,6 +89,7 @@ pub async fn update_user_stats(
         .query_async(&mut *conn)
         .await
         .map_err(AppError::RedisCommandError)?;
+    invalidate_user(user_id);
 
     tracing::info!(
         "Updated user stats for {}: rank={}, prize={:?}, wars_point={}",
@@ -98,6 +102,46 @@ pub async fn update_user_stats(
     Ok(())
 }
 
+/// Credits settled spectator bets to the bettors' wars points.
+pub async fn credit_bet_payouts(
+    payouts: &[(Uuid, f64)],
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    if payouts.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let points_key = RedisKey::users_points();
+    let mut pipe = redis::pipe();
+    for (user_id, amount) in payouts {
+        pipe.cmd("HINCRBYFLOAT")
+            .arg(RedisKey::user(KeyPart::Id(*user_id)))
+            .arg("wars_point")
+            .arg(*amount)
+            .ignore();
+        pipe.cmd("ZINCRBY")
+            .arg(&points_key)
+            .arg(*amount)
+            .arg(user_id.to_string())
+            .ignore();
+    }
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    for (user_id, _) in payouts {
+        invalidate_user(*user_id);
+    }
+
+    Ok(())
+}
+
 /// Batch update stats for multiple users (useful for lobby completion)
 pub async fn _batch_update_user_stats(
     user_stats: Vec<(Uuid, Uuid, usize, Option<f64>, f64)>, // (user_id, lobby_id, rank, prize, wars_point)
@@ -151,11 +195,13 @@ pub async fn _batch_update_user_stats(
             .arg(wars_point)
             .arg(&user_id_str);
 
-        // Update player rank in lobby player hash
+        // Update player rank and earned wars point in lobby player hash
         pipe.cmd("HSET")
             .arg(&player_key)
             .arg("rank")
-            .arg(rank.to_string());
+            .arg(rank.to_string())
+            .arg("wars_point")
+            .arg(wars_point.to_string());
 
         // Update player prize in lobby player hash if applicable
         if let Some(prize_amount) = prize {
@@ -169,7 +215,7 @@ pub async fn _batch_update_user_stats(
                     .arg("prize")
                     .arg(prize_amount.to_string());
 
-                let claim_json = serde_json::to_string(&ClaimState::NotClaimed)
+                let claim_json = serde_json::to_string(&ClaimState::Claimable)
                     .unwrap_or_else(|_| "null".to_string());
                 pipe.cmd("HSET")
                     .arg(&player_key)
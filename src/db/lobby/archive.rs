@@ -0,0 +1,143 @@
+//! Archival of finished lobbies. A lobby finished for long enough has its
+//! hash, players and state history moved under `archive:` and leaves the
+//! lobby lists and search indexes, which otherwise grow with every game.
+//! `get_lobby_info_or_archived` still finds it.
+
+use bb8::PooledConnection;
+use bb8_redis::RedisConnectionManager;
+use chrono::{Duration, Utc};
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    db::lobby::{
+        events::publish_lobby_event, get::get_lobby_player_ids, scripts::ARCHIVE_LOBBY,
+        search::unindex_lobby,
+    },
+    errors::AppError,
+    models::{
+        game::{LobbyInfo, LobbyState},
+        lobby::LobbyListEvent,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Finished lobbies read from the state index at a time
+const ARCHIVE_BATCH: isize = 100;
+
+/// Archives every lobby finished more than `after_days` days ago. Returns
+/// how many were archived.
+pub async fn archive_finished_lobbies(
+    after_days: u32,
+    redis: RedisClient,
+) -> Result<usize, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    // The finished index is scored by when each lobby finished
+    let cutoff = (Utc::now() - Duration::days(after_days as i64)).timestamp();
+    let finished_key = RedisKey::lobbies_state(&LobbyState::Finished);
+
+    let mut archived = 0;
+    // Lobbies left in the index, skipped on the next read
+    let mut skipped = 0;
+    loop {
+        let ids: Vec<String> = conn
+            .zrangebyscore_limit(&finished_key, "-inf", cutoff, skipped, ARCHIVE_BATCH)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+
+        for id in &ids {
+            let Ok(lobby_id) = Uuid::parse_str(id) else {
+                skipped += 1;
+                continue;
+            };
+            match archive_lobby(&mut conn, lobby_id).await {
+                Ok(true) => archived += 1,
+                Ok(false) => skipped += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to archive lobby {}: {}", lobby_id, e);
+                    skipped += 1;
+                }
+            }
+        }
+
+        if (ids.len() as isize) < ARCHIVE_BATCH {
+            break;
+        }
+    }
+
+    Ok(archived)
+}
+
+/// Moves one finished lobby into the archive. Returns false when it was
+/// gone or no longer finished.
+async fn archive_lobby(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    lobby_id: Uuid,
+) -> Result<bool, AppError> {
+    let lobby_key = RedisKey::lobby(KeyPart::Id(lobby_id));
+    let map: HashMap<String, String> = conn
+        .hgetall(&lobby_key)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if map.is_empty() {
+        return Ok(false);
+    }
+    let (info, creator_id, game_id) = LobbyInfo::from_redis_hash_partial(&map)?;
+    let player_ids = get_lobby_player_ids(conn, lobby_id).await?;
+
+    let mut moved_keys = vec![
+        lobby_key,
+        RedisKey::lobby_players(KeyPart::Id(lobby_id)),
+        RedisKey::lobby_state_history(KeyPart::Id(lobby_id)),
+    ];
+    moved_keys.extend(
+        player_ids.iter().map(|player_id| {
+            RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(*player_id))
+        }),
+    );
+
+    let mut invocation = ARCHIVE_LOBBY.key(&moved_keys[0]);
+    invocation
+        .key(RedisKey::lobbies_state(&LobbyState::Finished))
+        .key(RedisKey::lobbies_all());
+    for key in &moved_keys {
+        invocation.key(key).key(RedisKey::archived(key));
+    }
+    let result: i32 = invocation
+        .arg(lobby_id.to_string())
+        .arg(Utc::now().timestamp())
+        .invoke_async(&mut **conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if result == 0 {
+        return Ok(false);
+    }
+
+    // Off the remaining lists; players' match history keeps their results
+    let lobby_id_str = lobby_id.to_string();
+    let mut pipe = redis::pipe();
+    pipe.zrem(RedisKey::game_lobbies(KeyPart::Id(game_id)), &lobby_id_str)
+        .ignore();
+    for player_id in &player_ids {
+        pipe.srem(
+            RedisKey::user_lobbies(KeyPart::Id(*player_id)),
+            &lobby_id_str,
+        )
+        .ignore();
+    }
+    unindex_lobby(&mut pipe, &info, creator_id);
+    let _: () = pipe
+        .query_async(&mut **conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    publish_lobby_event(LobbyListEvent::Closed { lobby_id });
+    tracing::info!("Archived lobby {}", lobby_id);
+    Ok(true)
+}
@@ -0,0 +1,42 @@
+use std::sync::LazyLock;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::{db::lobby::get::get_lobby_info, models::lobby::LobbyListEvent, state::RedisClient};
+
+/// Events a lobby stream subscriber may fall behind by before it must resync
+const LOBBY_EVENT_BUFFER: usize = 256;
+
+/// Lobby list changes made on this instance
+static LOBBY_EVENTS: LazyLock<broadcast::Sender<LobbyListEvent>> =
+    LazyLock::new(|| broadcast::channel(LOBBY_EVENT_BUFFER).0);
+
+pub fn subscribe_lobby_events() -> broadcast::Receiver<LobbyListEvent> {
+    LOBBY_EVENTS.subscribe()
+}
+
+pub fn publish_lobby_event(event: LobbyListEvent) {
+    // Only fails when nobody is listening
+    let _ = LOBBY_EVENTS.send(event);
+}
+
+/// Publishes the lobby's current info in the background, skipping the read
+/// when no one is streaming the lobby list.
+pub fn publish_lobby_updated(lobby_id: Uuid, redis: RedisClient) {
+    if LOBBY_EVENTS.receiver_count() == 0 {
+        return;
+    }
+
+    tokio::spawn(async move {
+        match get_lobby_info(lobby_id, redis).await {
+            Ok(lobby) => publish_lobby_event(LobbyListEvent::Updated {
+                lobby: Box::new(lobby),
+            }),
+            Err(e) => tracing::warn!(
+                "Failed to read lobby {} for the lobby stream: {}",
+                lobby_id,
+                e
+            ),
+        }
+    });
+}
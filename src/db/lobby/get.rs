@@ -8,13 +8,22 @@ use uuid::Uuid;
 use crate::{
     db::{
         game::get::get_game,
-        user::get::{get_user_by_id, get_user_by_id_with_conn},
+        user::{
+            cache::{cache_user, cached_user},
+            get::{get_active_users_with_conn, get_user_by_id_with_conn, get_user_or_tombstone},
+        },
+        utils::zrevrange_page,
     },
     errors::AppError,
     models::{
+        User,
+        admin::DisputedClaim,
         game::{
-            ClaimState, LobbyExtended, LobbyInfo, LobbyState, Player, PlayerLobbyInfo, PlayerState,
+            ClaimState, LobbyExtended, LobbyInfo, LobbySettings, LobbyState, Player,
+            PlayerLobbyInfo, PlayerState,
         },
+        lobby::LobbyStateChange,
+        pagination::{PageCursor, Paginated},
         redis::{KeyPart, RedisKey},
     },
     state::RedisClient,
@@ -25,17 +34,17 @@ pub async fn get_lobbies_by_game_id(
     lobby_filters: Option<Vec<LobbyState>>,
     page: u32,
     limit: u32,
+    after: Option<&PageCursor>,
     redis: RedisClient,
-) -> Result<Vec<LobbyInfo>, AppError> {
+) -> Result<Paginated<LobbyInfo>, AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
     let offset = ((page.saturating_sub(1)) as usize).saturating_mul(limit as usize);
-    let end = offset + (limit as usize) - 1;
 
     // 1) build the list of lobby IDs (filtered by state if provided)
-    let lobby_ids: Vec<String> = if let Some(states) = lobby_filters {
+    let ((lobby_ids, offset, last), total) = if let Some(states) = lobby_filters {
         // Union all the per‐state sorted sets
         let state_keys: Vec<String> = states
             .iter()
@@ -59,7 +68,7 @@ pub async fn get_lobbies_by_game_id(
         // Now intersect with the game‐specific set
         let game_key = RedisKey::game_lobbies(KeyPart::Id(game_id));
         let inter_key = RedisKey::temp_inter();
-        let _: () = redis::cmd("ZINTERSTORE")
+        let total: u64 = redis::cmd("ZINTERSTORE")
             .arg(&inter_key)
             .arg(2)
             .arg(&game_key)
@@ -75,13 +84,7 @@ pub async fn get_lobbies_by_game_id(
             .ok();
 
         // Page through the intersection
-        let ids: Vec<String> = redis::cmd("ZREVRANGE")
-            .arg(&inter_key)
-            .arg(offset)
-            .arg(end)
-            .query_async(&mut *conn)
-            .await
-            .map_err(AppError::RedisCommandError)?;
+        let page = zrevrange_page(&mut conn, &inter_key, offset, limit, after).await?;
 
         // Cleanup
         let _: Option<()> = redis::cmd("DEL")
@@ -94,16 +97,16 @@ pub async fn get_lobbies_by_game_id(
             .query_async(&mut *conn)
             .await
             .ok();
-        ids
+        (page, total)
     } else {
         // No state filter → page straight out of game:{game_id}:lobbies
-        redis::cmd("ZREVRANGE")
-            .arg(RedisKey::game_lobbies(KeyPart::Id(game_id)))
-            .arg(offset)
-            .arg(end)
-            .query_async(&mut *conn)
+        let game_key = RedisKey::game_lobbies(KeyPart::Id(game_id));
+        let total: u64 = conn
+            .zcard(&game_key)
             .await
-            .map_err(AppError::RedisCommandError)?
+            .map_err(AppError::RedisCommandError)?;
+        let page = zrevrange_page(&mut conn, &game_key, offset, limit, after).await?;
+        (page, total)
     };
 
     // Filter and collect only valid UUIDs
@@ -112,16 +115,26 @@ pub async fn get_lobbies_by_game_id(
         .filter_map(|id_str| Uuid::parse_str(id_str).ok())
         .collect();
 
+    let out = hydrate_lobby_infos(&mut conn, &valid_ids, &redis).await?;
+    Ok(Paginated::from_offset(out, offset, limit, total).with_next_cursor(last))
+}
+
+/// Reads the lobbies in order and fills in their creators and games.
+pub async fn hydrate_lobby_infos(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    lobby_ids: &[Uuid],
+    redis: &RedisClient,
+) -> Result<Vec<LobbyInfo>, AppError> {
     // Batch all HGETALLs using a Redis pipeline
     let mut pipe = redis::pipe();
-    for lobby_id in &valid_ids {
+    for lobby_id in lobby_ids {
         let key = RedisKey::lobby(KeyPart::Id(*lobby_id));
         pipe.cmd("HGETALL").arg(key);
     }
 
     // Execute pipeline and collect responses
     let results: Vec<HashMap<String, String>> = pipe
-        .query_async(&mut *conn)
+        .query_async(&mut **conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
@@ -144,7 +157,7 @@ pub async fn get_lobbies_by_game_id(
 
     // Fetch creators
     for creator_id in creator_ids {
-        if let Ok(creator) = get_user_by_id(creator_id, redis.clone()).await {
+        if let Ok(creator) = get_user_or_tombstone(creator_id, redis.clone()).await {
             creators.insert(creator_id, creator);
         }
     }
@@ -170,13 +183,34 @@ pub async fn get_lobbies_by_game_id(
 }
 
 pub async fn get_lobby_info(lobby_id: Uuid, redis: RedisClient) -> Result<LobbyInfo, AppError> {
+    read_lobby_info(lobby_id, RedisKey::lobby(KeyPart::Id(lobby_id)), redis).await
+}
+
+/// Like `get_lobby_info`, but also finds lobbies moved to the archive.
+pub async fn get_lobby_info_or_archived(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<LobbyInfo, AppError> {
+    match get_lobby_info(lobby_id, redis.clone()).await {
+        Err(AppError::NotFound(_)) => {
+            let key = RedisKey::archived(&RedisKey::lobby(KeyPart::Id(lobby_id)));
+            read_lobby_info(lobby_id, key, redis).await
+        }
+        result => result,
+    }
+}
+
+async fn read_lobby_info(
+    lobby_id: Uuid,
+    key: String,
+    redis: RedisClient,
+) -> Result<LobbyInfo, AppError> {
     let redis_clone = redis.clone();
     let mut conn = redis_clone.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
-    let key = RedisKey::lobby(KeyPart::Id(lobby_id));
     let map: HashMap<String, String> = redis::cmd("HGETALL")
         .arg(&key)
         .query_async(&mut *conn)
@@ -192,7 +226,7 @@ pub async fn get_lobby_info(lobby_id: Uuid, redis: RedisClient) -> Result<LobbyI
 
     // Hydrate both creator and game concurrently
     let (creator_result, game_result) = tokio::try_join!(
-        get_user_by_id(creator_id, redis.clone()),
+        get_user_or_tombstone(creator_id, redis.clone()),
         get_game(game_id, redis.clone())
     )?;
 
@@ -204,6 +238,28 @@ pub async fn get_lobby_info(lobby_id: Uuid, redis: RedisClient) -> Result<LobbyI
     Ok(info)
 }
 
+/// Reads only the settings fields, without hydrating creator and game.
+pub async fn get_lobby_settings(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<LobbySettings, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let map: HashMap<String, String> = conn
+        .hgetall(RedisKey::lobby(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if map.is_empty() {
+        return Err(AppError::NotFound(format!("Lobby {} not found", lobby_id)));
+    }
+
+    Ok(LobbySettings::from_redis_hash(&map))
+}
+
 pub async fn get_connected_players_ids(
     lobby_id: Uuid,
     redis: RedisClient,
@@ -344,79 +400,46 @@ pub async fn get_all_lobbies_info(
     lobby_filters: Option<Vec<LobbyState>>,
     page: u32,
     limit: u32,
+    after: Option<&PageCursor>,
     redis: RedisClient,
-) -> Result<Vec<LobbyInfo>, AppError> {
+) -> Result<Paginated<LobbyInfo>, AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis conn timed out".into()),
     })?;
 
     let offset = ((page.saturating_sub(1)) as usize).saturating_mul(limit as usize);
-    let end = offset + (limit as usize) - 1;
-    let uuids: Vec<Uuid> = fetch_lobby_uuids(&mut conn, lobby_filters, offset, end).await?;
+    let (uuids, offset, last, total) =
+        fetch_lobby_uuids(&mut conn, lobby_filters, offset, limit, after).await?;
 
     if uuids.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    // Batch all HGETALLs using a Redis pipeline
-    let mut pipe = redis::pipe();
-    for uuid in &uuids {
-        let key = RedisKey::lobby(KeyPart::Id(*uuid));
-        pipe.cmd("HGETALL").arg(key);
+        return Ok(Paginated::from_offset(Vec::new(), offset, limit, total));
     }
 
-    // Execute pipeline and collect responses
-    let results: Vec<HashMap<String, String>> = pipe
-        .query_async(&mut *conn)
-        .await
-        .map_err(AppError::RedisCommandError)?;
-
-    // Parse partial lobbies and collect unique creator and game IDs
-    let mut partial_lobbies = Vec::new();
-    let mut creator_ids = HashSet::new();
-    let mut game_ids = HashSet::new();
-
-    for map in results {
-        if let Ok((lobby, creator_id, game_id)) = LobbyInfo::from_redis_hash_partial(&map) {
-            creator_ids.insert(creator_id);
-            game_ids.insert(game_id);
-            partial_lobbies.push((lobby, creator_id, game_id));
-        }
-    }
-
-    // Batch fetch all creators and games
-    let mut creators = HashMap::new();
-    let mut games = HashMap::new();
-
-    // Fetch creators
-    for creator_id in creator_ids {
-        if let Ok(creator) = get_user_by_id(creator_id, redis.clone()).await {
-            creators.insert(creator_id, creator);
-        }
-    }
+    let out = hydrate_lobby_infos(&mut conn, &uuids, &redis).await?;
+    Ok(Paginated::from_offset(out, offset, limit, total).with_next_cursor(last))
+}
 
-    // Fetch games
-    for game_id in game_ids {
-        if let Ok(game) = get_game(game_id, redis.clone()).await {
-            games.insert(game_id, game);
-        }
-    }
+pub async fn hydrate_players(players: Vec<Player>, redis: RedisClient) -> Vec<Player> {
+    // Recently read users come from the in-process cache
+    let mut users_map = HashMap::new();
+    let user_ids_to_hydrate: Vec<Uuid> = players
+        .iter()
+        .filter(|p| p.user.is_none())
+        .map(|p| p.id)
+        .filter(|user_id| match cached_user(*user_id) {
+            Some(user) => {
+                users_map.insert(*user_id, user);
+                false
+            }
+            None => true,
+        })
+        .collect();
 
-    // Hydrate creators and games
-    let mut out = Vec::new();
-    for (mut lobby, creator_id, game_id) in partial_lobbies {
-        if let (Some(creator), Some(game)) = (creators.get(&creator_id), games.get(&game_id)) {
-            lobby.creator = creator.clone();
-            lobby.game = game.clone();
-            out.push(lobby);
-        }
+    if user_ids_to_hydrate.is_empty() {
+        return apply_users(players, users_map);
     }
 
-    Ok(out)
-}
-
-pub async fn hydrate_players(players: Vec<Player>, redis: RedisClient) -> Vec<Player> {
     // Monitor pool health
     let pool_state = redis.state();
     tracing::debug!(
@@ -430,32 +453,33 @@ pub async fn hydrate_players(players: Vec<Player>, redis: RedisClient) -> Vec<Pl
         Ok(conn) => conn,
         Err(e) => {
             tracing::error!("Failed to get Redis connection for hydration: {}", e);
-            return players; // Return unhydrated players rather than failing
+            // Return what the cache had rather than failing
+            return apply_users(players, users_map);
         }
     };
 
-    let mut hydrated = Vec::new();
-
-    // Collect users that need hydration
-    let user_ids_to_hydrate: Vec<Uuid> = players
-        .iter()
-        .filter(|p| p.user.is_none())
-        .map(|p| p.id)
-        .collect();
-
     // Batch hydrate users using the same connection
-    let mut users_map = HashMap::new();
     for user_id in user_ids_to_hydrate {
         match get_user_by_id_with_conn(user_id, &mut conn).await {
             Ok(user) => {
+                cache_user(&user);
                 users_map.insert(user_id, user);
             }
+            Err(AppError::NotFound(_)) => {
+                users_map.insert(user_id, User::tombstone(user_id));
+            }
             Err(e) => {
                 tracing::warn!("Failed to hydrate user {}: {}", user_id, e);
             }
         }
     }
 
+    apply_users(players, users_map)
+}
+
+fn apply_users(players: Vec<Player>, mut users_map: HashMap<Uuid, User>) -> Vec<Player> {
+    let mut hydrated = Vec::new();
+
     // Apply user data to players
     for mut player in players {
         if player.user.is_none() {
@@ -519,12 +543,11 @@ pub async fn get_lobby_players(
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
-    let pattern = RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Wildcard);
-    let player_keys: Vec<String> = redis::cmd("KEYS")
-        .arg(&pattern)
-        .query_async(&mut *conn)
-        .await
-        .map_err(AppError::RedisCommandError)?;
+    let player_keys: Vec<String> = get_lobby_player_ids(&mut conn, lobby_id)
+        .await?
+        .into_iter()
+        .map(|id| RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(id)))
+        .collect();
 
     if player_keys.is_empty() {
         return Ok(Vec::new());
@@ -577,20 +600,21 @@ pub async fn get_all_lobbies_extended(
     players_filter: Option<PlayerState>,
     page: u32,
     limit: u32,
+    after: Option<&PageCursor>,
     redis: RedisClient,
-) -> Result<Vec<LobbyExtended>, AppError> {
+) -> Result<Paginated<LobbyExtended>, AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
     let offset = ((page.saturating_sub(1)) as usize).saturating_mul(limit as usize);
-    let end = offset + (limit as usize) - 1;
 
-    let uuids: Vec<Uuid> = fetch_lobby_uuids(&mut conn, lobby_filters, offset, end).await?;
+    let (uuids, offset, last, total) =
+        fetch_lobby_uuids(&mut conn, lobby_filters, offset, limit, after).await?;
 
     if uuids.is_empty() {
-        return Ok(Vec::new());
+        return Ok(Paginated::from_offset(Vec::new(), offset, limit, total));
     }
 
     let mut out = Vec::with_capacity(uuids.len());
@@ -612,32 +636,229 @@ pub async fn get_all_lobbies_extended(
         }
     }
 
-    Ok(out)
+    Ok(Paginated::from_offset(out, offset, limit, total).with_next_cursor(last))
 }
 
+/// A lobby of the player's list, with the creator and game ids still to load
+type PlayerLobbyRow = (
+    LobbyInfo,
+    Uuid,
+    Uuid,
+    Option<f64>,
+    Option<usize>,
+    Option<ClaimState>,
+);
+
+/// How long the sets built for one page of player lobbies live if never deleted
+const PLAYER_LOBBIES_TEMP_TTL_SECS: i64 = 30;
+
 pub async fn get_player_lobbies(
     user_id: Uuid,
     claim_filter: Option<ClaimState>,
     lobby_filters: Option<Vec<LobbyState>>,
     page: u32,
     limit: u32,
+    after: Option<&PageCursor>,
     redis: RedisClient,
-) -> Result<Vec<PlayerLobbyInfo>, AppError> {
+) -> Result<Paginated<PlayerLobbyInfo>, AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
-    // Get all lobby player keys for this user
-    let player_pattern = RedisKey::lobby_player(KeyPart::Wildcard, KeyPart::Id(user_id));
-    let player_keys: Vec<String> = redis::cmd("KEYS")
-        .arg(&player_pattern)
-        .query_async(&mut *conn)
+    let offset = ((page.saturating_sub(1)) as usize).saturating_mul(limit as usize);
+
+    // Claims live on the player hashes, so only a claim filter needs them all
+    let (paginated_lobbies, offset, last, total) = match claim_filter {
+        None => {
+            indexed_player_lobbies(&mut conn, user_id, lobby_filters, offset, limit, after).await?
+        }
+        Some(claim_filter) => {
+            claim_filtered_player_lobbies(
+                &mut conn,
+                user_id,
+                claim_filter,
+                lobby_filters,
+                offset,
+                limit,
+                after,
+            )
+            .await?
+        }
+    };
+
+    if paginated_lobbies.is_empty() {
+        return Ok(Paginated::from_offset(Vec::new(), offset, limit, total));
+    }
+
+    // Collect unique creator and game IDs for batch fetching
+    let mut creator_ids = HashSet::new();
+    let mut game_ids = HashSet::new();
+
+    for (_, creator_id, game_id, _, _, _) in &paginated_lobbies {
+        creator_ids.insert(*creator_id);
+        game_ids.insert(*game_id);
+    }
+
+    // Batch fetch creators and games
+    let mut creators = HashMap::new();
+    let mut games = HashMap::new();
+
+    // Fetch creators
+    for creator_id in creator_ids {
+        if let Ok(creator) = get_user_or_tombstone(creator_id, redis.clone()).await {
+            creators.insert(creator_id, creator);
+        }
+    }
+
+    // Fetch games
+    for game_id in game_ids {
+        if let Ok(game) = get_game(game_id, redis.clone()).await {
+            games.insert(game_id, game);
+        }
+    }
+
+    // Hydrate and build final result with player data
+    let mut result = Vec::new();
+    for (mut lobby, creator_id, game_id, prize, rank, claim) in paginated_lobbies {
+        if let (Some(creator), Some(game)) = (creators.get(&creator_id), games.get(&game_id)) {
+            lobby.creator = creator.clone();
+            lobby.game = game.clone();
+
+            result.push(PlayerLobbyInfo {
+                lobby,
+                prize_amount: prize,
+                rank,
+                claim_state: claim,
+            });
+        }
+    }
+
+    Ok(Paginated::from_offset(result, offset, limit, total).with_next_cursor(last))
+}
+
+/// One page of the player's lobbies, newest first, read off the lobby indexes
+/// so only the page itself is loaded. The user's lobby set is intersected with
+/// `lobbies:all`, which scores lobbies by creation time, and the requested
+/// state sets; the total is the intersection's size.
+async fn indexed_player_lobbies(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    user_id: Uuid,
+    lobby_filters: Option<Vec<LobbyState>>,
+    offset: usize,
+    limit: u32,
+    after: Option<&PageCursor>,
+) -> Result<(Vec<PlayerLobbyRow>, usize, Option<PageCursor>, u64), AppError> {
+    let result_key = RedisKey::temp_inter();
+    let mut keys = vec![
+        RedisKey::lobbies_all(),
+        RedisKey::user_lobbies(KeyPart::Id(user_id)),
+    ];
+    let mut temp_keys = vec![result_key.clone()];
+
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    if let Some(states) = lobby_filters {
+        if states.is_empty() {
+            return Ok((Vec::new(), offset, None, 0));
+        }
+        let state_keys: Vec<String> = states.iter().map(RedisKey::lobbies_state).collect();
+        let union_key = RedisKey::temp_union();
+        pipe.cmd("ZUNIONSTORE")
+            .arg(&union_key)
+            .arg(state_keys.len())
+            .arg(&state_keys)
+            .ignore()
+            .expire(&union_key, PLAYER_LOBBIES_TEMP_TTL_SECS)
+            .ignore();
+        keys.push(union_key.clone());
+        temp_keys.push(union_key);
+    }
+
+    // Only lobbies:all keeps its score
+    let weights: Vec<u8> = std::iter::once(1)
+        .chain(std::iter::repeat(0))
+        .take(keys.len())
+        .collect();
+    pipe.cmd("ZINTERSTORE")
+        .arg(&result_key)
+        .arg(keys.len())
+        .arg(&keys)
+        .arg("WEIGHTS")
+        .arg(&weights)
+        .expire(&result_key, PLAYER_LOBBIES_TEMP_TTL_SECS)
+        .ignore();
+
+    let (total,): (u64,) = pipe
+        .query_async(&mut **conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let found = zrevrange_page(conn, &result_key, offset, limit, after).await;
+    let _: Option<()> = conn.del(&temp_keys).await.ok();
+    let (ids, offset, last) = found?;
+
+    let lobby_ids: Vec<Uuid> = ids
+        .iter()
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect();
+    if lobby_ids.is_empty() {
+        return Ok((Vec::new(), offset, last, total));
+    }
+
+    let mut pipe = redis::pipe();
+    for lobby_id in &lobby_ids {
+        pipe.hgetall(RedisKey::lobby_player(
+            KeyPart::Id(*lobby_id),
+            KeyPart::Id(user_id),
+        ))
+        .hgetall(RedisKey::lobby(KeyPart::Id(*lobby_id)));
+    }
+    let hashes: Vec<HashMap<String, String>> = pipe
+        .query_async(&mut **conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
+    let rows = hashes
+        .chunks(2)
+        .filter_map(|pair| {
+            let player = Player::from_redis_hash(&pair[0]).ok()?;
+            let (lobby, creator_id, game_id) = LobbyInfo::from_redis_hash_partial(&pair[1]).ok()?;
+            Some((
+                lobby,
+                creator_id,
+                game_id,
+                player.prize,
+                player.rank,
+                player.claim,
+            ))
+        })
+        .collect();
+
+    Ok((rows, offset, last, total))
+}
+
+/// One page of the player's lobbies whose claim matches `claim_filter`,
+/// newest first. Claims are only on the player hashes, so all of the
+/// player's lobbies are loaded and filtered here.
+async fn claim_filtered_player_lobbies(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    user_id: Uuid,
+    claim_filter: ClaimState,
+    lobby_filters: Option<Vec<LobbyState>>,
+    offset: usize,
+    limit: u32,
+    after: Option<&PageCursor>,
+) -> Result<(Vec<PlayerLobbyRow>, usize, Option<PageCursor>, u64), AppError> {
+    // Get all lobby player keys for this user
+    let player_keys: Vec<String> = get_user_lobby_ids(conn, user_id)
+        .await?
+        .into_iter()
+        .map(|lobby_id| RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(user_id)))
+        .collect();
+
     if player_keys.is_empty() {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), offset, None, 0));
     }
 
     // Batch fetch all player data using pipeline
@@ -647,7 +868,7 @@ pub async fn get_player_lobbies(
     }
 
     let player_results: Vec<HashMap<String, String>> = pipe
-        .query_async(&mut *conn)
+        .query_async(&mut **conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
@@ -657,16 +878,13 @@ pub async fn get_player_lobbies(
     for (key, player_data) in player_keys.iter().zip(player_results.iter()) {
         if let Ok(player) = Player::from_redis_hash(player_data) {
             // Skip lobbies without prizes when filtering by claim state
-            if claim_filter.is_some() && player.prize.is_none() {
+            if player.prize.is_none() {
                 continue;
             }
 
-            // Apply claim filter if specified
             let passes_claim_filter = match (&claim_filter, &player.claim) {
-                (Some(ClaimState::NotClaimed), Some(ClaimState::NotClaimed)) => true,
-                (Some(ClaimState::NotClaimed), None) => player.prize.is_some(),
-                (Some(ClaimState::Claimed { .. }), Some(ClaimState::Claimed { .. })) => true,
-                (None, _) => true,
+                (filter, Some(claim)) => claim.matches_filter(filter),
+                (ClaimState::Claimable, None) => true,
                 _ => false,
             };
 
@@ -679,7 +897,7 @@ pub async fn get_player_lobbies(
     }
 
     if filtered_data.is_empty() {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), offset, None, 0));
     }
 
     // Extract unique lobby IDs for fetching lobby info
@@ -693,7 +911,7 @@ pub async fn get_player_lobbies(
     }
 
     let lobby_results: Vec<HashMap<String, String>> = pipe
-        .query_async(&mut *conn)
+        .query_async(&mut **conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
@@ -727,71 +945,130 @@ pub async fn get_player_lobbies(
     // Sort by created_at (newest first)
     lobbies_with_data.sort_by(|a, b| b.0.created_at.cmp(&a.0.created_at));
 
-    // Apply pagination
-    let offset = ((page.saturating_sub(1)) as usize).saturating_mul(limit as usize);
+    // Apply pagination, continuing after the cursor's lobby when given
+    let total = lobbies_with_data.len() as u64;
+    let created_score = |lobby: &LobbyInfo| lobby.created_at.timestamp_millis() as f64;
+    let offset = match after {
+        Some(cursor) => lobbies_with_data
+            .iter()
+            .position(|(lobby, ..)| lobby.id.to_string() == cursor.id)
+            .map(|index| index + 1)
+            .unwrap_or_else(|| {
+                lobbies_with_data
+                    .iter()
+                    .filter(|(lobby, ..)| created_score(lobby) > cursor.score)
+                    .count()
+            }),
+        None => offset,
+    };
     let paginated_lobbies: Vec<_> = lobbies_with_data
         .into_iter()
         .skip(offset)
         .take(limit as usize)
         .collect();
+    let last = paginated_lobbies
+        .last()
+        .map(|(lobby, ..)| PageCursor::new(lobby.id, created_score(lobby)));
 
-    if paginated_lobbies.is_empty() {
-        return Ok(Vec::new());
-    }
+    Ok((paginated_lobbies, offset, last, total))
+}
 
-    // Collect unique creator and game IDs for batch fetching
-    let mut creator_ids = HashSet::new();
-    let mut game_ids = HashSet::new();
+/// Player ids from the lobby's index set.
+pub async fn get_lobby_player_ids(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    lobby_id: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    let ids: Vec<String> = conn
+        .smembers(RedisKey::lobby_players(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
 
-    for (_, creator_id, game_id, _, _, _) in &paginated_lobbies {
-        creator_ids.insert(*creator_id);
-        game_ids.insert(*game_id);
-    }
+    Ok(ids
+        .iter()
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect())
+}
 
-    // Batch fetch creators and games
-    let mut creators = HashMap::new();
-    let mut games = HashMap::new();
+/// Lobby ids from the user's index set.
+pub async fn get_user_lobby_ids(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    user_id: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    let ids: Vec<String> = conn
+        .smembers(RedisKey::user_lobbies(KeyPart::Id(user_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
 
-    // Fetch creators
-    for creator_id in creator_ids {
-        if let Ok(creator) = get_user_by_id(creator_id, redis.clone()).await {
-            creators.insert(creator_id, creator);
-        }
+    Ok(ids
+        .iter()
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect())
+}
+
+/// The user's lobbies that haven't finished yet, with their state.
+pub async fn get_user_open_lobbies(
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<Vec<(Uuid, LobbyState)>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let lobby_ids = get_user_lobby_ids(&mut conn, user_id).await?;
+    if lobby_ids.is_empty() {
+        return Ok(Vec::new());
     }
 
-    // Fetch games
-    for game_id in game_ids {
-        if let Ok(game) = get_game(game_id, redis.clone()).await {
-            games.insert(game_id, game);
-        }
+    let mut pipe = redis::pipe();
+    for lobby_id in &lobby_ids {
+        pipe.hget(RedisKey::lobby(KeyPart::Id(*lobby_id)), "state");
     }
+    let states: Vec<Option<String>> = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
 
-    // Hydrate and build final result with player data
-    let mut result = Vec::new();
-    for (mut lobby, creator_id, game_id, prize, rank, claim) in paginated_lobbies {
-        if let (Some(creator), Some(game)) = (creators.get(&creator_id), games.get(&game_id)) {
-            lobby.creator = creator.clone();
-            lobby.game = game.clone();
+    Ok(lobby_ids
+        .into_iter()
+        .zip(states)
+        .filter_map(|(lobby_id, state)| {
+            let state = state?.parse::<LobbyState>().ok()?;
+            (state != LobbyState::Finished).then_some((lobby_id, state))
+        })
+        .collect())
+}
 
-            result.push(PlayerLobbyInfo {
-                lobby,
-                prize_amount: prize,
-                rank,
-                claim_state: claim,
-            });
-        }
-    }
+pub async fn get_lobby_ids_by_state(
+    state: LobbyState,
+    redis: RedisClient,
+) -> Result<Vec<Uuid>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
 
-    Ok(result)
+    let ids: Vec<String> = conn
+        .zrange(RedisKey::lobbies_state(&state), 0, -1)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(ids
+        .iter()
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect())
 }
 
+/// A page of lobby ids with the offset it starts at, the cursor after it and
+/// the total count.
 async fn fetch_lobby_uuids(
     conn: &mut PooledConnection<'_, RedisConnectionManager>,
     lobby_filters: Option<Vec<LobbyState>>,
     offset: usize,
-    end: usize,
-) -> Result<Vec<Uuid>, AppError> {
-    let ids: Vec<String> = if let Some(states) = lobby_filters {
+    limit: u32,
+    after: Option<&PageCursor>,
+) -> Result<(Vec<Uuid>, usize, Option<PageCursor>, u64), AppError> {
+    let ((ids, offset, last), total) = if let Some(states) = lobby_filters {
         let keys: Vec<String> = states
             .iter()
             .map(|state| RedisKey::lobbies_state(state))
@@ -812,11 +1089,11 @@ async fn fetch_lobby_uuids(
 
         // If no state sets exist, return empty
         if existing_keys.is_empty() {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), offset, None, 0));
         }
 
         let union = RedisKey::temp_union();
-        let _: () = redis::cmd("ZUNIONSTORE")
+        let total: u64 = redis::cmd("ZUNIONSTORE")
             .arg(&union)
             .arg(existing_keys.len())
             .arg(&existing_keys)
@@ -830,13 +1107,7 @@ async fn fetch_lobby_uuids(
             .await
             .ok();
 
-        let out: Vec<String> = redis::cmd("ZREVRANGE")
-            .arg(&union)
-            .arg(offset)
-            .arg(end)
-            .query_async(&mut **conn)
-            .await
-            .map_err(AppError::RedisCommandError)?;
+        let out = zrevrange_page(conn, &union, offset, limit, after).await?;
 
         // cleanup
         let _: Option<()> = redis::cmd("DEL")
@@ -844,7 +1115,7 @@ async fn fetch_lobby_uuids(
             .query_async(&mut **conn)
             .await
             .ok();
-        out
+        (out, total)
     } else {
         // Check if "lobbies:all" exists before trying to access it
         let exists: bool = redis::cmd("EXISTS")
@@ -854,16 +1125,15 @@ async fn fetch_lobby_uuids(
             .map_err(AppError::RedisCommandError)?;
 
         if !exists {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), offset, None, 0));
         }
 
-        redis::cmd("ZREVRANGE")
-            .arg("lobbies:all")
-            .arg(offset)
-            .arg(end)
-            .query_async(&mut **conn)
+        let total: u64 = conn
+            .zcard(RedisKey::lobbies_all())
             .await
-            .map_err(AppError::RedisCommandError)?
+            .map_err(AppError::RedisCommandError)?;
+        let ids = zrevrange_page(conn, &RedisKey::lobbies_all(), offset, limit, after).await?;
+        (ids, total)
     };
 
     let mut uuids: Vec<Uuid> = ids
@@ -871,7 +1141,7 @@ async fn fetch_lobby_uuids(
         .filter_map(|s| Uuid::parse_str(&s).ok())
         .collect();
     uuids.dedup();
-    Ok(uuids)
+    Ok((uuids, offset, last, total))
 }
 
 pub async fn get_spectators(lobby_id: Uuid, redis: RedisClient) -> Result<Vec<Uuid>, AppError> {
@@ -893,3 +1163,116 @@ pub async fn get_spectators(lobby_id: Uuid, redis: RedisClient) -> Result<Vec<Uu
 
     Ok(spectator_ids)
 }
+
+pub async fn lobby_exists(lobby_id: Uuid, redis: RedisClient) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    conn.exists(RedisKey::lobby(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)
+}
+
+/// The lobby's latest state changes, oldest first, archived or not.
+pub async fn get_lobby_state_history(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<Vec<LobbyStateChange>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::lobby_state_history(KeyPart::Id(lobby_id));
+    let mut raw: Vec<String> = conn
+        .lrange(&key, 0, -1)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if raw.is_empty() {
+        raw = conn
+            .lrange(RedisKey::archived(&key), 0, -1)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+    }
+
+    Ok(raw
+        .iter()
+        .filter_map(|entry| serde_json::from_str::<LobbyStateChange>(entry).ok())
+        .collect())
+}
+
+/// Users currently watching the lobby's game.
+pub async fn get_spectator_users(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<Vec<User>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let ids: Vec<String> = conn
+        .smembers(RedisKey::lobby_spectators(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    get_active_users_with_conn(&ids, &mut conn).await
+}
+
+/// Every claim currently in `Disputed`, with stale index entries skipped.
+pub async fn get_disputed_claims(redis: RedisClient) -> Result<Vec<DisputedClaim>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let members: Vec<String> = conn
+        .smembers(RedisKey::disputed_claims())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let ids: Vec<(Uuid, Uuid)> = members
+        .iter()
+        .filter_map(|m| {
+            let (lobby_id, user_id) = m.split_once(':')?;
+            Some((
+                Uuid::parse_str(lobby_id).ok()?,
+                Uuid::parse_str(user_id).ok()?,
+            ))
+        })
+        .collect();
+
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut pipe = redis::pipe();
+    for (lobby_id, user_id) in &ids {
+        pipe.hget(
+            RedisKey::lobby_player(KeyPart::Id(*lobby_id), KeyPart::Id(*user_id)),
+            &["claim", "prize"],
+        );
+    }
+    let rows: Vec<(Option<String>, Option<String>)> = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let claims = ids
+        .into_iter()
+        .zip(rows)
+        .filter_map(|((lobby_id, user_id), (claim, prize))| {
+            let claim = serde_json::from_str::<ClaimState>(&claim?).ok()?;
+            claim.is_disputed().then(|| DisputedClaim {
+                lobby_id,
+                user_id,
+                prize: prize.and_then(|p| p.parse().ok()),
+                claim,
+            })
+        })
+        .collect();
+
+    Ok(claims)
+}
@@ -0,0 +1,116 @@
+use redis::{AsyncCommands, Script};
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::redis::{KeyPart, RedisKey},
+    state::RedisClient,
+};
+
+/// How long retries with the same key get the first lobby back
+const IDEMPOTENCY_TTL_SECS: u64 = 24 * 60 * 60;
+/// How long a claimed key holds off retries while the first request runs,
+/// payment verification included
+const IDEMPOTENCY_PENDING_SECS: u64 = 5 * 60;
+const PENDING: &str = "pending";
+
+/// Returns what the key already holds, claiming it when it holds nothing.
+static CLAIM_KEY: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local stored = redis.call('GET', KEYS[1])
+        if stored then
+            return stored
+        end
+        redis.call('SET', KEYS[1], ARGV[1], 'EX', ARGV[2])
+        return false
+        ",
+    )
+});
+
+/// Where an `Idempotency-Key` for lobby creation stands.
+pub enum LobbyCreateClaim {
+    /// First use of the key, the caller creates the lobby
+    Claimed,
+    /// A request with the key is still creating its lobby
+    InProgress,
+    /// The lobby an earlier request with the key created
+    Created(Uuid),
+}
+
+pub async fn claim_lobby_create(
+    user_id: Uuid,
+    key: &str,
+    redis: &RedisClient,
+) -> Result<LobbyCreateClaim, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let stored: Option<String> = CLAIM_KEY
+        .key(RedisKey::lobby_create_idempotency(
+            KeyPart::Id(user_id),
+            key.into(),
+        ))
+        .arg(PENDING)
+        .arg(IDEMPOTENCY_PENDING_SECS)
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    match stored.as_deref() {
+        None => Ok(LobbyCreateClaim::Claimed),
+        Some(PENDING) => Ok(LobbyCreateClaim::InProgress),
+        Some(lobby_id) => Uuid::parse_str(lobby_id)
+            .map(LobbyCreateClaim::Created)
+            .map_err(|_| AppError::Deserialization("Invalid idempotent lobby id".into())),
+    }
+}
+
+/// Records the lobby the claimed key created, replayed to its retries.
+pub async fn finish_lobby_create(
+    user_id: Uuid,
+    key: &str,
+    lobby_id: Uuid,
+    redis: &RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .set_ex(
+            RedisKey::lobby_create_idempotency(KeyPart::Id(user_id), key.into()),
+            lobby_id.to_string(),
+            IDEMPOTENCY_TTL_SECS,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Frees the key after a failed create so the client can retry with it.
+pub async fn release_lobby_create(
+    user_id: Uuid,
+    key: &str,
+    redis: &RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .del(RedisKey::lobby_create_idempotency(
+            KeyPart::Id(user_id),
+            key.into(),
+        ))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::{
+    db::{lobby::search::index_lobby, utils::scan_keys},
+    errors::AppError,
+    models::{
+        game::LobbyInfo,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Marker set once the lobby/user index sets have been built from existing data
+const INDEX_MIGRATION_KEY: &str = "migrations:lobby_indexes:v1";
+/// Marker set once the lobby search indexes have been built from existing lobbies
+const SEARCH_INDEX_MIGRATION_KEY: &str = "migrations:lobby_search:v1";
+
+/// Builds the `lobbies:{id}:players` and `users:lobbies:{id}` index sets from the
+/// player hashes already stored. Safe to run repeatedly, it only does work once.
+pub async fn backfill_lobby_indexes(redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let done: bool = conn
+        .exists(INDEX_MIGRATION_KEY)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if done {
+        return Ok(());
+    }
+
+    let pattern = RedisKey::lobby_player(KeyPart::Wildcard, KeyPart::Wildcard);
+    let player_keys = scan_keys(&mut conn, &pattern).await?;
+
+    let mut lobby_players: HashMap<Uuid, Vec<String>> = HashMap::new();
+    let mut user_lobbies: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for key in &player_keys {
+        let (Some(lobby_id), Some(user_id)) = (
+            RedisKey::extract_lobby_id_from_player_key(key),
+            RedisKey::_extract_user_id_from_player_key(key),
+        ) else {
+            continue;
+        };
+        lobby_players
+            .entry(lobby_id)
+            .or_default()
+            .push(user_id.to_string());
+        user_lobbies
+            .entry(user_id)
+            .or_default()
+            .push(lobby_id.to_string());
+    }
+
+    let mut pipe = redis::pipe();
+    for (lobby_id, user_ids) in &lobby_players {
+        pipe.sadd(RedisKey::lobby_players(KeyPart::Id(*lobby_id)), user_ids)
+            .ignore();
+    }
+    for (user_id, lobby_ids) in &user_lobbies {
+        pipe.sadd(RedisKey::user_lobbies(KeyPart::Id(*user_id)), lobby_ids)
+            .ignore();
+    }
+    pipe.set(INDEX_MIGRATION_KEY, chrono::Utc::now().to_rfc3339())
+        .ignore();
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    tracing::info!(
+        "Indexed {} player records across {} lobbies",
+        player_keys.len(),
+        lobby_players.len()
+    );
+    Ok(())
+}
+
+/// Adds the lobbies stored before search existed to the search indexes.
+/// Safe to run repeatedly, it only does work once.
+pub async fn backfill_lobby_search_indexes(redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let done: bool = conn
+        .exists(SEARCH_INDEX_MIGRATION_KEY)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if done {
+        return Ok(());
+    }
+
+    let lobby_keys = scan_keys(&mut conn, &RedisKey::lobby(KeyPart::Wildcard)).await?;
+
+    let mut pipe = redis::pipe();
+    let mut indexed = 0;
+    for key in &lobby_keys {
+        let map: HashMap<String, String> = conn
+            .hgetall(key)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+        match LobbyInfo::from_redis_hash_partial(&map) {
+            Ok((lobby, _, _)) => {
+                index_lobby(&mut pipe, &lobby);
+                indexed += 1;
+            }
+            Err(e) => tracing::warn!("Skipping lobby {} in search backfill: {}", key, e),
+        }
+    }
+    pipe.set(SEARCH_INDEX_MIGRATION_KEY, chrono::Utc::now().to_rfc3339())
+        .ignore();
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    tracing::info!("Indexed {} lobbies for search", indexed);
+    Ok(())
+}
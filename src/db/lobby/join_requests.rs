@@ -36,6 +36,9 @@ impl JoinRequestEntry {
         if let Some(ref display_name) = self.user.display_name {
             map.insert("display_name".into(), display_name.clone());
         }
+        if let Some(ref avatar_url) = self.user.avatar_url {
+            map.insert("avatar_url".into(), avatar_url.clone());
+        }
         map.insert("wars_point".into(), self.user.wars_point.to_string());
         map
     }
@@ -70,10 +73,13 @@ impl JoinRequestEntry {
             wallet_address: data.get("wallet_address").cloned().unwrap_or_default(),
             username: data.get("username").cloned(),
             display_name: data.get("display_name").cloned(),
+            avatar_url: data.get("avatar_url").cloned(),
+            bio: None,
             wars_point: data
                 .get("wars_point")
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(0.0),
+            deleted: false,
         };
 
         Ok(JoinRequestEntry {
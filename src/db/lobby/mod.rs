@@ -1,6 +1,15 @@
+pub mod archive;
 pub mod countdown;
+pub mod events;
 pub mod get;
+pub mod idempotency;
+pub mod index;
 pub mod join_requests;
 pub mod patch;
 pub mod post;
 pub mod put;
+pub mod ready_check;
+pub mod refunds;
+pub mod schedules;
+pub mod search;
+pub mod scripts;
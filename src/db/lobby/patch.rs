@@ -5,12 +5,36 @@ use uuid::Uuid;
 
 use crate::{
     db::{
-        chat::delete::delete_lobby_chat, lobby::join_requests::remove_all_lobby_join_requests,
-        tx::validate_payment_tx, user::get::get_user_by_id,
+        chat::delete::delete_lobby_chat,
+        game::get::get_game,
+        lobby::{
+            events::{publish_lobby_event, publish_lobby_updated},
+            get::{get_lobby_player, get_lobby_player_ids, get_lobby_players},
+            join_requests::remove_all_lobby_join_requests,
+            refunds::record_entry_refund,
+            scripts::{
+                JOIN_PLAYER, LEAVE_PLAYER, SET_PLAYER_FIELD, SWAP_LOBBY_STATE, SWAP_PLAYER_FIELD,
+            },
+            search::{move_created_lobby, unindex_lobby},
+        },
+        moderation::bans::ensure_not_banned,
+        telegram::get::get_lobby_chat_id,
+        tx::{
+            TxVerification,
+            pending::{MAX_PENDING_JOIN_ATTEMPTS, queue_pending_join, remove_pending_join},
+            verify_payment_tx,
+        },
+        user::{
+            delete::ensure_user_active, get::get_user_by_id,
+            linked_accounts::check_prize_lobby_join,
+        },
     },
     errors::AppError,
+    events::{GameEvent, emit},
+    games::registry::find_registration,
     models::{
         game::{ClaimState, LobbyInfo, LobbyState, Player, PlayerState},
+        lobby::{JoinOutcome, LobbyListEvent, LobbyStateChange},
         redis::{KeyPart, RedisKey},
     },
     state::RedisClient,
@@ -22,7 +46,10 @@ pub async fn join_lobby(
     tx_id: Option<String>,
     player_state: PlayerState,
     redis: RedisClient,
-) -> Result<(), AppError> {
+) -> Result<JoinOutcome, AppError> {
+    ensure_user_active(user_id, redis.clone()).await?;
+    ensure_not_banned(user_id, redis.clone()).await?;
+
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
@@ -36,31 +63,38 @@ pub async fn join_lobby(
     if lobby_map.is_empty() {
         return Err(AppError::NotFound(format!("Lobby {} not found", lobby_id)));
     }
-    let (lobby, _creator_id, _game_id) = LobbyInfo::from_redis_hash_partial(&lobby_map)?;
+    let (lobby, _creator_id, game_id) = LobbyInfo::from_redis_hash_partial(&lobby_map)?;
+
+    // Fail fast here too, JOIN_PLAYER re-checks the state atomically
+    if player_state == PlayerState::Joined && lobby.state != LobbyState::Waiting {
+        return Ok(JoinOutcome::Closed);
+    }
+
+    if player_state == PlayerState::Joined {
+        let game = get_game(game_id, redis.clone()).await?;
+        if let Some(max_players) = find_registration(&game).and_then(|g| g.max_players)
+            && lobby.participants >= max_players as usize
+        {
+            return Err(AppError::BadRequest("Lobby is full".into()));
+        }
+    }
 
     let player_key = RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(user_id));
-    let mut existing_player_state: Option<PlayerState> = None;
 
-    if conn
-        .exists(&player_key)
+    // Fail fast before verifying payment, JOIN_PLAYER re-checks atomically
+    let existing_state: Option<String> = conn
+        .hget(&player_key, "state")
         .await
-        .map_err(AppError::RedisCommandError)?
-    {
-        // Check existing player state
-        let player_map: HashMap<String, String> = conn
-            .hgetall(&player_key)
-            .await
-            .map_err(AppError::RedisCommandError)?;
-
-        if let Ok(existing_player) = Player::from_redis_hash(&player_map) {
-            existing_player_state = Some(existing_player.state.clone());
+        .map_err(AppError::RedisCommandError)?;
+    if existing_state.as_deref() == Some("Joined") {
+        return Err(AppError::BadRequest("User already in lobby".into()));
+    }
 
-            if existing_player.state == PlayerState::Joined {
-                return Err(AppError::BadRequest("User already in lobby".into()));
-            }
-        }
+    if player_state == PlayerState::Joined {
+        check_prize_lobby_join(&lobby, user_id, redis.clone()).await?;
     }
 
+    let mut pool_increment = 0;
     if let Some(addr) = &lobby.contract_address {
         let entry_amount = lobby.entry_amount.unwrap_or(0.0);
 
@@ -70,13 +104,40 @@ pub async fn join_lobby(
             })?;
 
             let user = get_user_by_id(user_id, redis.clone()).await?;
-            validate_payment_tx(&tx, &user.wallet_address, addr, entry_amount).await?;
+            match verify_payment_tx(
+                &tx,
+                &user.wallet_address,
+                addr,
+                entry_amount,
+                lobby.asset.as_ref(),
+                redis.clone(),
+            )
+            .await?
+            {
+                TxVerification::Verified => {
+                    remove_pending_join(lobby_id, user_id, redis.clone()).await?;
+                }
+                TxVerification::Pending => {
+                    let attempts =
+                        queue_pending_join(lobby_id, user_id, &tx, redis.clone()).await?;
+                    if attempts > MAX_PENDING_JOIN_ATTEMPTS {
+                        remove_pending_join(lobby_id, user_id, redis.clone()).await?;
+                        return Err(AppError::BadRequest(
+                            "Transaction could not be verified in time".into(),
+                        ));
+                    }
 
-            // Increment pool current amount
-            let _: () = conn
-                .hincr(&lobby_key, "current_amount", entry_amount as i64)
-                .await
-                .map_err(AppError::RedisCommandError)?;
+                    tracing::info!(
+                        "Payment {} for lobby {} still pending (attempt {})",
+                        tx,
+                        lobby_id,
+                        attempts
+                    );
+                    return Ok(JoinOutcome::Pending);
+                }
+            }
+
+            pool_increment = entry_amount as i64;
         }
     }
 
@@ -87,23 +148,41 @@ pub async fn join_lobby(
         .map(|(k, v)| (k.as_str(), v.as_str()))
         .collect();
 
-    let _: () = conn
-        .hset_multiple(&player_key, &player_fields)
+    // Player hash, indexes and counters change together so concurrent joins can't double count
+    let mut invocation = JOIN_PLAYER.prepare_invoke();
+    invocation
+        .key(&player_key)
+        .key(&lobby_key)
+        .key(RedisKey::lobby_players(KeyPart::Id(lobby_id)))
+        .key(RedisKey::user_lobbies(KeyPart::Id(user_id)))
+        .key(RedisKey::lobbies_by_participants())
+        .key(RedisKey::lobbies_by_pool())
+        .arg(user_id.to_string())
+        .arg(lobby_id.to_string())
+        .arg(pool_increment);
+    for (field, value) in &player_fields {
+        invocation.arg(*field).arg(*value);
+    }
+
+    let result: i32 = invocation
+        .invoke_async(&mut *conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
-    let should_increment_participants = player_state == PlayerState::Joined
-        && (existing_player_state.is_none()
-            || existing_player_state == Some(PlayerState::NotJoined));
-
-    if should_increment_participants {
-        let _: () = conn
-            .hincr(&lobby_key, "participants", 1)
-            .await
-            .map_err(AppError::RedisCommandError)?;
+    match result {
+        -1 => return Err(AppError::BadRequest("User already in lobby".into())),
+        -2 => {
+            // The game started while the payment was checked, give the entry back
+            if pool_increment != 0 {
+                record_entry_refund(&lobby, user_id, redis.clone()).await?;
+            }
+            return Ok(JoinOutcome::Closed);
+        }
+        _ => {}
     }
 
-    Ok(())
+    publish_lobby_updated(lobby_id, redis.clone());
+    Ok(JoinOutcome::Joined)
 }
 
 pub async fn leave_lobby(
@@ -128,25 +207,35 @@ pub async fn leave_lobby(
     let (info, creator_id, game_id) = LobbyInfo::from_redis_hash_partial(&m)?;
 
     if creator_id == user_id {
-        // Creator leaving - delete entire lobby
-        let pattern = RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Wildcard);
-        let keys: Vec<String> = redis::cmd("KEYS")
-            .arg(&pattern)
-            .query_async(&mut *conn)
-            .await
-            .map_err(AppError::RedisCommandError)?;
+        // Creator leaving - delete the lobby when nobody else is in it
+        let player_ids = get_lobby_player_ids(&mut conn, lobby_id).await?;
 
-        if keys.len() == 1 {
+        if player_ids.len() == 1 {
             // Only creator left - delete lobby and clean up all references
             let _: () = conn
-                .del(&lobby_key)
+                .del(&[
+                    lobby_key.clone(),
+                    RedisKey::lobby_state_history(KeyPart::Id(lobby_id)),
+                ])
                 .await
                 .map_err(AppError::RedisCommandError)?;
 
-            // Delete all player hashes
-            for key in keys {
+            // Delete all player hashes and the index
+            for player_id in player_ids {
+                let key = RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(player_id));
                 let _: () = conn.del(key).await.map_err(AppError::RedisCommandError)?;
+                let _: () = conn
+                    .srem(
+                        RedisKey::user_lobbies(KeyPart::Id(player_id)),
+                        lobby_id.to_string(),
+                    )
+                    .await
+                    .map_err(AppError::RedisCommandError)?;
             }
+            let _: () = conn
+                .del(RedisKey::lobby_players(KeyPart::Id(lobby_id)))
+                .await
+                .map_err(AppError::RedisCommandError)?;
 
             // Clean up sorted sets - remove lobby from all relevant sets
             let lobby_id_str = lobby_id.to_string();
@@ -169,16 +258,29 @@ pub async fn leave_lobby(
                 .await
                 .map_err(AppError::RedisCommandError)?;
 
+            // Remove from the search indexes
+            let mut pipe = redis::pipe();
+            unindex_lobby(&mut pipe, &info, creator_id);
+            let _: () = pipe
+                .query_async(&mut *conn)
+                .await
+                .map_err(AppError::RedisCommandError)?;
+
             // Update game active lobby count
             //update_game_active_lobby(game_id, false, redis.clone()).await?;
 
             // Delete Telegram lobby creation message if bot is available and tg_msg_id exists
             if let Some(tg_msg_id) = info.tg_msg_id {
+                let redis = redis.clone();
                 tokio::spawn(async move {
-                    let chat_id = std::env::var("TELEGRAM_CHAT_ID")
-                        .expect("TELEGRAM_CHAT_ID must be set")
-                        .parse::<i64>()
-                        .unwrap();
+                    let chat_id = match get_lobby_chat_id(&info, redis).await {
+                        Ok(Some(chat_id)) => chat_id,
+                        Ok(None) => return,
+                        Err(e) => {
+                            tracing::error!("Failed to look up lobby announcement chat: {}", e);
+                            return;
+                        }
+                    };
 
                     if let Err(e) =
                         crate::http::bot::delete_lobby_creation_message(&bot, chat_id, tg_msg_id)
@@ -188,65 +290,133 @@ pub async fn leave_lobby(
                     }
                 });
             }
-        } else {
-            return Err(AppError::BadRequest(
-                "Creator cannot leave lobby with players".into(),
-            ));
+
+            publish_lobby_event(LobbyListEvent::Closed { lobby_id });
+            return Ok(());
         }
 
-        return Ok(());
+        // Hand the lobby to the longest-joined player, then leave like anyone else
+        match next_lobby_owner(lobby_id, user_id, redis.clone()).await? {
+            Some(new_owner) => {
+                let _: () = conn
+                    .hset(&lobby_key, "creator_id", new_owner.to_string())
+                    .await
+                    .map_err(AppError::RedisCommandError)?;
+                move_created_lobby(&mut conn, lobby_id, user_id, new_owner).await?;
+                tracing::info!(
+                    "Lobby {} ownership passed from {} to {}",
+                    lobby_id,
+                    user_id,
+                    new_owner
+                );
+            }
+            None => {
+                return Err(AppError::BadRequest(
+                    "Creator cannot leave lobby with players".into(),
+                ));
+            }
+        }
     }
 
     // Regular player leaving
     let player_key = RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(user_id));
-    if !conn
-        .exists(&player_key)
+
+    // Only paid lobbies refund the pool, and only for players who actually joined
+    let refund = match &info.contract_address {
+        Some(_) => info.entry_amount.unwrap_or(0.0).max(0.0) as i64,
+        None => 0,
+    };
+
+    let result: i32 = LEAVE_PLAYER
+        .key(&player_key)
+        .key(&lobby_key)
+        .key(RedisKey::lobby_players(KeyPart::Id(lobby_id)))
+        .key(RedisKey::user_lobbies(KeyPart::Id(user_id)))
+        .key(RedisKey::lobbies_by_participants())
+        .key(RedisKey::lobbies_by_pool())
+        .arg(user_id.to_string())
+        .arg(lobby_id.to_string())
+        .arg(refund)
+        .invoke_async(&mut *conn)
         .await
-        .map_err(AppError::RedisCommandError)?
-    {
+        .map_err(AppError::RedisCommandError)?;
+
+    if result < 0 {
         return Err(AppError::BadRequest("User not in lobby".into()));
     }
 
-    // Get player state before deletion to check if we need to decrement participant count and pool
-    let player_map: HashMap<String, String> = conn
-        .hgetall(&player_key)
-        .await
-        .map_err(AppError::RedisCommandError)?;
+    // Drop any payment still waiting on confirmation so it can't re-add the player
+    remove_pending_join(lobby_id, user_id, redis.clone()).await?;
 
-    let joined = if let Ok(player) = Player::from_redis_hash(&player_map) {
-        player.state == PlayerState::Joined
-    } else {
-        false
-    };
+    publish_lobby_updated(lobby_id, redis.clone());
+    Ok(())
+}
 
-    let _: () = conn
-        .del(&player_key)
-        .await
-        .map_err(AppError::RedisCommandError)?;
+/// The joined player who has been in the lobby longest, skipping `current_owner`.
+pub async fn next_lobby_owner(
+    lobby_id: Uuid,
+    current_owner: Uuid,
+    redis: RedisClient,
+) -> Result<Option<Uuid>, AppError> {
+    let players = get_lobby_players(lobby_id, Some(PlayerState::Joined), redis).await?;
+
+    Ok(players
+        .into_iter()
+        .filter(|p| p.id != current_owner)
+        .min_by_key(|p| (p.joined_at.is_none(), p.joined_at))
+        .map(|p| p.id))
+}
 
-    if joined {
-        let _: () = conn
-            .hincr(&lobby_key, "participants", -1)
-            .await
-            .map_err(AppError::RedisCommandError)?;
+/// Makes a joined player the lobby creator.
+pub async fn transfer_lobby_ownership(
+    lobby_id: Uuid,
+    new_owner_id: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let new_owner = get_lobby_player(lobby_id, new_owner_id, redis.clone()).await?;
+    if new_owner.state != PlayerState::Joined {
+        return Err(AppError::BadRequest(
+            "New owner must have joined the lobby".into(),
+        ));
     }
 
-    // Only update pool amount for paid lobbies (entry_amount > 0) where player actually paid (not idle)
-    if let Some(_addr) = &info.contract_address {
-        let entry_amount = info.entry_amount.unwrap_or(0.0);
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
 
-        if entry_amount > 0.0 && joined {
-            // Regular paid lobby - refund player by decreasing pool (only if they weren't idle)
-            let _: () = conn
-                .hincr(&lobby_key, "current_amount", -(entry_amount as i64))
-                .await
-                .map_err(AppError::RedisCommandError)?;
-        }
+    let lobby_key = RedisKey::lobby(KeyPart::Id(lobby_id));
+    let old_owner_id: String = conn
+        .hget(&lobby_key, "creator_id")
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    let _: () = conn
+        .hset_multiple(
+            &lobby_key,
+            &[
+                ("creator_id", new_owner_id.to_string()),
+                (
+                    "creator_last_ping",
+                    Utc::now().timestamp_millis().to_string(),
+                ),
+            ],
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if let Ok(old_owner_id) = Uuid::parse_str(&old_owner_id) {
+        move_created_lobby(&mut conn, lobby_id, old_owner_id, new_owner_id).await?;
     }
 
+    publish_lobby_updated(lobby_id, redis.clone());
     Ok(())
 }
 
+/// State changes kept in each lobby's history
+const STATE_HISTORY_LEN: usize = 50;
+
+/// Moves the lobby to `new_state`, refusing moves `LobbyState` doesn't allow,
+/// and records the change in its history.
 pub async fn update_lobby_state(
     lobby_id: Uuid,
     new_state: LobbyState,
@@ -280,25 +450,58 @@ pub async fn update_lobby_state(
     if old_state == new_state {
         return Ok(());
     }
+    if !old_state.can_transition_to(&new_state) {
+        return Err(AppError::BadRequest(format!(
+            "Cannot move lobby from {:?} to {:?}",
+            old_state, new_state
+        )));
+    }
 
-    let _: () = conn
-        .hset(&lobby_key, "state", format!("{:?}", new_state))
+    let now = Utc::now();
+    let change = LobbyStateChange {
+        from: old_state.clone(),
+        to: new_state.clone(),
+        at: now,
+    };
+    let change_json =
+        serde_json::to_string(&change).map_err(|e| AppError::Serialization(e.to_string()))?;
+
+    // Set the state, move the lobby ID between the state ZSETs and log the
+    // change together, unless the state moved since we read it
+    let result: i32 = SWAP_LOBBY_STATE
+        .key(&lobby_key)
+        .key(RedisKey::lobbies_state(&old_state))
+        .key(RedisKey::lobbies_state(&new_state))
+        .key(RedisKey::lobby_state_history(KeyPart::Id(lobby_id)))
+        .arg(format!("{:?}", old_state))
+        .arg(format!("{:?}", new_state))
+        .arg(lobby_id.to_string())
+        .arg(now.timestamp())
+        .arg(change_json)
+        .arg(STATE_HISTORY_LEN)
+        .invoke_async(&mut *conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
-    // Move the lobby ID between the old & new state ZSETs
-    let score = Utc::now().timestamp();
-    let old_z = RedisKey::lobbies_state(&old_state);
-    let new_z = RedisKey::lobbies_state(&new_state);
+    match result {
+        -1 => return Err(AppError::NotFound(format!("Lobby {} not found", lobby_id))),
+        0 => {
+            return Err(AppError::BadRequest(
+                "Lobby state was updated by another request, please retry".into(),
+            ));
+        }
+        _ => {}
+    }
 
-    let _: () = conn
-        .zrem(&old_z, lobby_id.to_string())
-        .await
-        .map_err(AppError::RedisCommandError)?;
-    let _: () = conn
-        .zadd(&new_z, lobby_id.to_string(), score)
-        .await
-        .map_err(AppError::RedisCommandError)?;
+    publish_lobby_event(LobbyListEvent::StateChanged {
+        lobby_id,
+        state: new_state.clone(),
+    });
+    emit(GameEvent::LobbyStateChanged {
+        lobby_id,
+        from: old_state,
+        to: new_state.clone(),
+    });
 
     //if new_state == LobbyState::Finished
     //    && (old_state == LobbyState::Waiting
@@ -353,31 +556,22 @@ pub async fn update_player_state(
     // Build the player hash key
     let player_key = RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(user_id));
 
-    // Fetch the existing hash
-    let map: HashMap<String, String> = conn
-        .hgetall(&player_key)
+    // Update only the "state" field, and only while the player still exists
+    let result: i32 = SET_PLAYER_FIELD
+        .key(&player_key)
+        .arg("state")
+        .arg(format!("{:?}", new_state))
+        .invoke_async(&mut *conn)
         .await
         .map_err(AppError::RedisCommandError)?;
-    if map.is_empty() {
+
+    if result < 0 {
         return Err(AppError::NotFound(format!(
             "Player {} not found in lobby {}",
             user_id, lobby_id
         )));
     }
 
-    let player = Player::from_redis_hash(&map)?;
-
-    // Short‐circuit if no change
-    if player.state == new_state {
-        return Ok(());
-    }
-
-    // Update only the "state" field in Redis
-    let _: () = conn
-        .hset(&player_key, "state", format!("{:?}", new_state))
-        .await
-        .map_err(AppError::RedisCommandError)?;
-
     Ok(())
 }
 
@@ -393,32 +587,104 @@ pub async fn update_claim_state(
     })?;
 
     let player_key = RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(user_id));
-    let map: HashMap<String, String> = conn
-        .hgetall(&player_key)
+
+    let current_json: Option<String> = conn
+        .hget(&player_key, "claim")
         .await
         .map_err(AppError::RedisCommandError)?;
-    if map.is_empty() {
-        return Err(AppError::NotFound(format!(
-            "Player {} not found in lobby {}",
-            user_id, lobby_id
-        )));
-    }
+    let current = current_json
+        .as_deref()
+        .and_then(|v| serde_json::from_str::<ClaimState>(v).ok());
 
-    let current = Player::from_redis_hash(&map)?;
-    if current.claim.as_ref() == Some(&new_claim) {
-        return Ok(());
+    let Some(current) = current else {
+        return Err(AppError::BadRequest(
+            "There is no prize to claim in this lobby".into(),
+        ));
+    };
+    if !current.can_transition_to(&new_claim) {
+        return Err(AppError::BadRequest(format!(
+            "Cannot move claim from {:?} to {:?}",
+            current, new_claim
+        )));
     }
 
     let claim_json =
         serde_json::to_string(&new_claim).map_err(|e| AppError::Serialization(e.to_string()))?;
-    let _: () = conn
-        .hset(&player_key, "claim", claim_json)
+
+    let result: i32 = SWAP_PLAYER_FIELD
+        .key(&player_key)
+        .arg("claim")
+        .arg(current_json.unwrap_or_default())
+        .arg(claim_json)
+        .invoke_async(&mut *conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
+    match result {
+        -1 => {
+            return Err(AppError::NotFound(format!(
+                "Player {} not found in lobby {}",
+                user_id, lobby_id
+            )));
+        }
+        0 => {
+            return Err(AppError::BadRequest(
+                "Claim was updated by another request, please retry".into(),
+            ));
+        }
+        _ => {}
+    }
+
+    if let ClaimState::Claimed { tx_id } = &new_claim {
+        emit(GameEvent::PrizeClaimed {
+            lobby_id,
+            player_id: user_id,
+            tx_id: tx_id.clone(),
+        });
+    }
+
+    // Keep the dispute index in step so admins can find open disputes
+    let member = format!("{lobby_id}:{user_id}");
+    let disputed_key = RedisKey::disputed_claims();
+    if new_claim.is_disputed() {
+        let _: () = conn
+            .sadd(&disputed_key, &member)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+    } else if current.is_disputed() {
+        let _: () = conn
+            .srem(&disputed_key, &member)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+    }
+
     Ok(())
 }
 
+/// Moves a player's prize into `Disputed`, carrying over the claim tx if
+/// one was already submitted.
+pub async fn dispute_claim(
+    lobby_id: Uuid,
+    user_id: Uuid,
+    reason: String,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let player = get_lobby_player(lobby_id, user_id, redis.clone()).await?;
+    let tx_id = player
+        .claim
+        .as_ref()
+        .and_then(|c| c.tx_id())
+        .map(str::to_string);
+
+    update_claim_state(
+        lobby_id,
+        user_id,
+        ClaimState::Disputed { reason, tx_id },
+        redis,
+    )
+    .await
+}
+
 pub async fn add_connected_player(
     lobby_id: Uuid,
     player_id: Uuid,
@@ -494,8 +760,8 @@ pub async fn _update_player_prize(
     if let Some(amount) = prize {
         updates.push(("prize", amount.to_string()));
 
-        // Set claim state to NotClaimed when prize is set
-        let claim_json = serde_json::to_string(&ClaimState::NotClaimed)
+        // Prize becomes claimable as soon as it is set
+        let claim_json = serde_json::to_string(&ClaimState::Claimable)
             .map_err(|e| AppError::Serialization(e.to_string()))?;
         updates.push(("claim", claim_json));
     }
@@ -627,7 +893,7 @@ pub async fn _update_lexi_wars_player(
     player.used_words = Some(used_words.clone());
     if let Some(amount) = prize {
         player.prize = Some(amount);
-        player.claim = Some(ClaimState::NotClaimed);
+        player.claim = Some(ClaimState::Claimable);
     }
 
     let mut updates = Vec::new();
@@ -658,40 +924,50 @@ pub async fn _update_lexi_wars_player(
     Ok(())
 }
 
+/// Adds a spectator, returning how many are now watching.
 pub async fn add_spectator(
     lobby_id: Uuid,
     user_id: Uuid,
     redis: RedisClient,
-) -> Result<(), AppError> {
+) -> Result<usize, AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
     let spectators_key = RedisKey::lobby_spectators(KeyPart::Id(lobby_id));
-    let _: () = conn
+    let (count,): (usize,) = redis::pipe()
+        .atomic()
         .sadd(&spectators_key, user_id.to_string())
+        .ignore()
+        .scard(&spectators_key)
+        .query_async(&mut *conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
-    Ok(())
+    Ok(count)
 }
 
+/// Removes a spectator, returning how many are still watching.
 pub async fn remove_spectator(
     lobby_id: Uuid,
     user_id: Uuid,
     redis: RedisClient,
-) -> Result<(), AppError> {
+) -> Result<usize, AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
     let spectators_key = RedisKey::lobby_spectators(KeyPart::Id(lobby_id));
-    let _: () = conn
+    let (count,): (usize,) = redis::pipe()
+        .atomic()
         .srem(&spectators_key, user_id.to_string())
+        .ignore()
+        .scard(&spectators_key)
+        .query_async(&mut *conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
-    Ok(())
+    Ok(count)
 }
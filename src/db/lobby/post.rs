@@ -3,40 +3,77 @@ use teloxide::Bot;
 use uuid::Uuid;
 
 use crate::{
+    config,
     db::{
         game::get::get_game,
+        lobby::{events::publish_lobby_event, search::index_lobby},
+        telegram::get::get_announcement_chat_id,
         tx::{validate_fee_transfer, validate_payment_tx},
         user::get::get_user_by_id,
     },
     errors::AppError,
+    events::{GameEvent, emit},
+    games::registry::find_registration,
     http::bot::{self, BotNewLobbyPayload},
     models::{
-        game::{LobbyInfo, LobbyPoolInput, LobbyState, Player, PlayerState},
+        game::{LobbyInfo, LobbyPoolInput, LobbySettings, LobbyState, Player, PlayerState},
+        lobby::{LobbyListEvent, LobbySchedule},
         redis::{KeyPart, RedisKey},
     },
     state::RedisClient,
 };
 
+/// What a player asks for when opening a lobby.
+pub struct NewLobby {
+    pub name: String,
+    pub description: Option<String>,
+    pub game_id: Uuid,
+    /// Set for lobbies with an entry fee or a sponsored pool
+    pub pool: Option<LobbyPoolInput>,
+    pub settings: LobbySettings,
+    /// Pays the pool, or the creation fee of a lobby without one
+    pub tx_id: String,
+}
+
 pub async fn create_lobby(
-    name: String,
-    description: Option<String>,
+    new_lobby: NewLobby,
     creator_id: Uuid,
-    game_id: Uuid,
-    pool: Option<LobbyPoolInput>,
-    tx_id: String,
     redis: RedisClient,
     bot: Bot,
 ) -> Result<Uuid, AppError> {
+    let NewLobby {
+        name,
+        description,
+        game_id,
+        pool,
+        settings,
+        tx_id,
+    } = new_lobby;
+
+    if let Some(split) = &settings.prize_split {
+        split.validate()?;
+    }
+    if settings.casual && pool.is_some() {
+        return Err(AppError::BadRequest(
+            "Casual lobbies can't have an entry fee or pool".into(),
+        ));
+    }
+
     let lobby_id = Uuid::new_v4();
     let (creator_user, game) = tokio::try_join!(
         get_user_by_id(creator_id, redis.clone()),
         get_game(game_id, redis.clone())
     )?;
-    let mut conn = redis.get().await.map_err(|e| match e {
-        bb8::RunError::User(err) => AppError::RedisCommandError(err),
-        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
-    })?;
 
+    let registration = find_registration(&game)
+        .ok_or_else(|| AppError::BadRequest(format!("Game {} is not available", game.name)))?;
+    let settings = (registration.validate_settings)(settings, redis.clone()).await?;
+
+    if creator_user.deleted {
+        return Err(AppError::Unauthorized(
+            "This account has been deleted".into(),
+        ));
+    }
     // Create player with minimal data
     let lobby_player = Player::new(creator_user.id, Some(tx_id.clone()), PlayerState::Joined);
     let creator_last_ping = lobby_player.last_ping;
@@ -53,38 +90,107 @@ pub async fn create_lobby(
         created_at: Utc::now(),
         entry_amount: pool.as_ref().map(|p| p.entry_amount),
         current_amount: pool.as_ref().map(|p| p.current_amount),
-        token_symbol: pool.as_ref().and_then(|p| p.token_symbol.clone()),
+        token_symbol: pool.as_ref().and_then(|p| match &p.asset {
+            Some(asset) => Some(asset.symbol().to_string()),
+            None => p.token_symbol.clone(),
+        }),
         token_id: pool.as_ref().and_then(|p| p.token_id.clone()),
+        asset: pool.as_ref().and_then(|p| p.asset.clone()),
         creator_last_ping,
         tg_msg_id: None,
+        tg_chat_id: None,
+        settings,
+        archived: false,
     };
 
     // Store pool if it exists
     if let Some(pool_input) = &pool {
+        if let Some(asset) = &pool_input.asset {
+            asset.validate()?;
+        }
+
         validate_payment_tx(
             &tx_id,
             &creator_user.wallet_address,
             &pool_input.contract_address,
             pool_input.current_amount,
+            pool_input.asset.as_ref(),
         )
         .await?;
     } else {
-        let fee_wallet = std::env::var("FEE_WALLET")
-            .map_err(|_| AppError::EnvError("FEE_WALLET not set".into()))?;
+        let fee_wallet = &config::get().fee_wallet;
 
-        validate_fee_transfer(&tx_id, &creator_user.wallet_address, &fee_wallet).await?;
+        validate_fee_transfer(&tx_id, &creator_user.wallet_address, fee_wallet).await?;
     }
 
+    insert_lobby(&lobby_info, &lobby_player, redis.clone()).await?;
+    announce_lobby(lobby_info, redis, bot);
+
+    Ok(lobby_id)
+}
+
+/// Opens a free lobby for an occurrence of a recurring schedule. Schedules
+/// are set up by admins, so no creation fee is checked.
+pub async fn create_scheduled_lobby(
+    schedule: &LobbySchedule,
+    redis: RedisClient,
+    bot: Bot,
+) -> Result<Uuid, AppError> {
+    let lobby_id = Uuid::new_v4();
+    let (creator_user, game) = tokio::try_join!(
+        get_user_by_id(schedule.creator_id, redis.clone()),
+        get_game(schedule.game_id, redis.clone())
+    )?;
+
+    let lobby_player = Player::new(creator_user.id, None, PlayerState::Joined);
+    let lobby_info = LobbyInfo {
+        id: lobby_id,
+        name: schedule.name.clone(),
+        description: schedule.description.clone(),
+        creator_last_ping: lobby_player.last_ping,
+        creator: creator_user,
+        state: LobbyState::Waiting,
+        game,
+        participants: 1,
+        contract_address: None,
+        created_at: Utc::now(),
+        entry_amount: None,
+        current_amount: None,
+        token_symbol: None,
+        token_id: None,
+        asset: None,
+        tg_msg_id: None,
+        tg_chat_id: None,
+        settings: schedule.settings.clone(),
+        archived: false,
+    };
+
+    insert_lobby(&lobby_info, &lobby_player, redis.clone()).await?;
+    announce_lobby(lobby_info, redis, bot);
+
+    Ok(lobby_id)
+}
+
+async fn insert_lobby(
+    lobby_info: &LobbyInfo,
+    lobby_player: &Player,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let lobby_id = lobby_info.id;
     let lobby_key = RedisKey::lobby(KeyPart::Id(lobby_id));
-    let player_key = RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(creator_user.id));
+    let player_key = RedisKey::lobby_player(KeyPart::Id(lobby_id), KeyPart::Id(lobby_player.id));
 
     let player_hash = lobby_player.to_redis_hash();
     let lobby_fields = lobby_info.to_redis_hash();
     let created_score = lobby_info.created_at.timestamp();
 
-    // Rest of the function remains the same...
-    let _: () = redis::pipe()
-        .cmd("HSET")
+    let mut pipe = redis::pipe();
+    pipe.cmd("HSET")
         .arg(&lobby_key)
         .arg(
             lobby_fields
@@ -102,6 +208,14 @@ pub async fn create_lobby(
                 .collect::<Vec<&str>>(),
         )
         .ignore()
+        .cmd("SADD")
+        .arg(RedisKey::lobby_players(KeyPart::Id(lobby_id)))
+        .arg(lobby_player.id.to_string())
+        .ignore()
+        .cmd("SADD")
+        .arg(RedisKey::user_lobbies(KeyPart::Id(lobby_player.id)))
+        .arg(lobby_id.to_string())
+        .ignore()
         .cmd("ZADD")
         .arg(RedisKey::lobbies_all())
         .arg(created_score)
@@ -113,44 +227,70 @@ pub async fn create_lobby(
         .arg(lobby_id.to_string())
         .ignore()
         .cmd("ZADD")
-        .arg(RedisKey::game_lobbies(KeyPart::Id(game_id)))
+        .arg(RedisKey::game_lobbies(KeyPart::Id(lobby_info.game.id)))
         .arg(created_score)
         .arg(lobby_id.to_string())
+        .ignore();
+    index_lobby(&mut pipe, lobby_info);
+
+    let _: () = pipe
         .query_async(&mut *conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
-    //update_game_active_lobby(game_id, true, redis.clone()).await?;
+    publish_lobby_event(LobbyListEvent::Created {
+        lobby: Box::new(lobby_info.clone()),
+    });
+    emit(GameEvent::LobbyCreated {
+        lobby_id,
+        lobby_name: lobby_info.name.clone(),
+        game_id: lobby_info.game.id,
+        creator_id: lobby_player.id,
+    });
+
+    Ok(())
+}
 
-    let redis_for_tg = redis.clone();
+/// Posts the new lobby to the announcement chat in the background.
+fn announce_lobby(lobby_info: LobbyInfo, redis: RedisClient, bot: Bot) {
     tokio::spawn(async move {
+        let lobby_id = lobby_info.id;
+        let chat_id = match get_announcement_chat_id(lobby_info.game.id, redis.clone()).await {
+            Ok(Some(chat_id)) => chat_id,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!("Failed to look up announcement chat: {}", e);
+                return;
+            }
+        };
+
         let payload = BotNewLobbyPayload {
             lobby_id,
-            lobby_name: lobby_info.name.clone(),
-            description: lobby_info.description.clone(),
+            lobby_name: lobby_info.name,
+            description: lobby_info.description,
             game: lobby_info.game,
-            entry_amount: pool.as_ref().and_then(|p| Some(p.entry_amount)),
-            current_amount: pool.as_ref().map(|p| p.current_amount),
-            contract_address: lobby_info.contract_address.clone(),
-            token_symbol: pool.as_ref().and_then(|p| p.token_symbol.clone()),
-            creator_name: creator_user.display_name.or(creator_user.username),
-            wallet_address: creator_user.wallet_address.clone(),
+            entry_amount: lobby_info.entry_amount,
+            current_amount: lobby_info.current_amount,
+            contract_address: lobby_info.contract_address,
+            token_symbol: lobby_info.token_symbol,
+            creator_name: lobby_info
+                .creator
+                .display_name
+                .or(lobby_info.creator.username),
+            wallet_address: lobby_info.creator.wallet_address,
         };
 
-        let chat_id = std::env::var("TELEGRAM_CHAT_ID")
-            .expect("TELEGRAM_CHAT_ID must be set")
-            .parse::<i64>()
-            .unwrap();
-
         match bot::broadcast_lobby_created(&bot, chat_id, payload).await {
             Ok(msg) => {
-                // Store the telegram message ID in Redis
+                // Store the telegram message ID in Redis, with the chat it's in
                 let lobby_key = RedisKey::lobby(KeyPart::Id(lobby_id));
-                if let Ok(mut conn) = redis_for_tg.get().await {
+                if let Ok(mut conn) = redis.get().await {
                     let _: Result<(), redis::RedisError> = redis::cmd("HSET")
                         .arg(&lobby_key)
                         .arg("tg_msg_id")
                         .arg(msg.id.0)
+                        .arg("tg_chat_id")
+                        .arg(chat_id)
                         .query_async(&mut *conn)
                         .await;
                 }
@@ -160,6 +300,4 @@ pub async fn create_lobby(
             }
         }
     });
-
-    Ok(lobby_id)
 }
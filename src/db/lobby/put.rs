@@ -19,24 +19,24 @@ pub async fn create_current_players(
 
     let current_key = RedisKey::lobby_current_players(KeyPart::Id(lobby_id));
 
-    let _: () = conn
-        .del(&current_key)
-        .await
-        .map_err(AppError::RedisCommandError)?;
+    // Replace the set in one transaction so readers never see it empty
+    let mut pipe = redis::pipe();
+    pipe.atomic().del(&current_key).ignore();
 
-    // Add all current player IDs to the set
     if !current_player_ids.is_empty() {
         let player_id_strings: Vec<String> = current_player_ids
             .into_iter()
             .map(|id| id.to_string())
             .collect();
 
-        let _: () = conn
-            .sadd(&current_key, player_id_strings)
-            .await
-            .map_err(AppError::RedisCommandError)?;
+        pipe.sadd(&current_key, player_id_strings).ignore();
     }
 
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
     Ok(())
 }
 
@@ -0,0 +1,110 @@
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::redis::{KeyPart, RedisKey},
+    state::RedisClient,
+};
+
+/// Opens a ready-check, counting the creator as already acknowledged.
+pub async fn start_ready_check(
+    lobby_id: Uuid,
+    creator_id: Uuid,
+    ttl_secs: u32,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::lobby_ready_check(KeyPart::Id(lobby_id));
+
+    // Expire a little after the deadline so a crashed check cleans itself up
+    let _: () = redis::pipe()
+        .atomic()
+        .del(&key)
+        .ignore()
+        .sadd(&key, creator_id.to_string())
+        .ignore()
+        .expire(&key, ttl_secs as i64 + 5)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Records an ack, returning every player acked so far, or `None` when no
+/// ready-check is running.
+pub async fn ack_ready_check(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    redis: RedisClient,
+) -> Result<Option<Vec<Uuid>>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::lobby_ready_check(KeyPart::Id(lobby_id));
+
+    let active: bool = conn
+        .exists(&key)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if !active {
+        return Ok(None);
+    }
+
+    let _: () = conn
+        .sadd(&key, player_id.to_string())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    drop(conn);
+    get_ready_acks(lobby_id, redis).await
+}
+
+/// Players who acked the running ready-check, `None` when there is none.
+pub async fn get_ready_acks(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<Option<Vec<Uuid>>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::lobby_ready_check(KeyPart::Id(lobby_id));
+
+    let members: Vec<String> = conn
+        .smembers(&key)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if members.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        members
+            .iter()
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .collect(),
+    ))
+}
+
+pub async fn clear_ready_check(lobby_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::lobby_ready_check(KeyPart::Id(lobby_id));
+
+    let _: () = conn.del(&key).await.map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        game::{LobbyInfo, Player},
+        lobby::{LobbyRefund, RefundStatus},
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Marks every paid player's entry as refundable and returns the records.
+///
+/// Sponsored lobbies only refund the creator's sponsorship. Records that
+/// already exist are kept as they are, so a repeated cancel can't reopen a
+/// refund that was paid out.
+pub async fn record_lobby_refunds(
+    lobby: &LobbyInfo,
+    players: &[Player],
+    redis: RedisClient,
+) -> Result<Vec<LobbyRefund>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    if lobby.contract_address.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let entry_amount = lobby.entry_amount.unwrap_or(0.0);
+    let refunds: Vec<LobbyRefund> = players
+        .iter()
+        .filter(|p| p.tx_id.is_some())
+        .filter_map(|p| {
+            let amount = if entry_amount > 0.0 {
+                entry_amount
+            } else if p.id == lobby.creator.id {
+                lobby.current_amount.unwrap_or(0.0)
+            } else {
+                0.0
+            };
+            (amount > 0.0).then(|| LobbyRefund {
+                user_id: p.id,
+                amount,
+                token_symbol: lobby.pool_symbol().to_string(),
+                status: RefundStatus::Refundable,
+            })
+        })
+        .collect();
+
+    if refunds.is_empty() {
+        return Ok(refunds);
+    }
+
+    let key = RedisKey::lobby_refunds(KeyPart::Id(lobby.id));
+    let mut pipe = redis::pipe();
+    for refund in &refunds {
+        let json =
+            serde_json::to_string(refund).map_err(|e| AppError::Serialization(e.to_string()))?;
+        pipe.hset_nx(&key, refund.user_id.to_string(), json)
+            .ignore();
+    }
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(refunds)
+}
+
+/// Marks a single paid entry as refundable, for a payment that confirmed
+/// after its player could no longer join. Returns `None` for lobbies without
+/// an entry fee. An existing record is kept as it is.
+pub async fn record_entry_refund(
+    lobby: &LobbyInfo,
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<Option<LobbyRefund>, AppError> {
+    let entry_amount = lobby.entry_amount.unwrap_or(0.0);
+    if lobby.contract_address.is_none() || entry_amount <= 0.0 {
+        return Ok(None);
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let refund = LobbyRefund {
+        user_id,
+        amount: entry_amount,
+        token_symbol: lobby.pool_symbol().to_string(),
+        status: RefundStatus::Refundable,
+    };
+    let json =
+        serde_json::to_string(&refund).map_err(|e| AppError::Serialization(e.to_string()))?;
+    let _: () = conn
+        .hset_nx(
+            RedisKey::lobby_refunds(KeyPart::Id(lobby.id)),
+            user_id.to_string(),
+            json,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(Some(refund))
+}
+
+pub async fn get_lobby_refunds(
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<Vec<LobbyRefund>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let raw: HashMap<String, String> = conn
+        .hgetall(RedisKey::lobby_refunds(KeyPart::Id(lobby_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut refunds: Vec<LobbyRefund> = raw
+        .values()
+        .filter_map(|v| serde_json::from_str(v).ok())
+        .collect();
+    refunds.sort_by_key(|r| r.user_id);
+
+    Ok(refunds)
+}
+
+/// Records the payout tx of a refund, failing if it was already paid.
+pub async fn mark_refund_paid(
+    lobby_id: Uuid,
+    user_id: Uuid,
+    tx_id: String,
+    redis: RedisClient,
+) -> Result<LobbyRefund, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::lobby_refunds(KeyPart::Id(lobby_id));
+    let raw: Option<String> = conn
+        .hget(&key, user_id.to_string())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut refund: LobbyRefund =
+        raw.and_then(|v| serde_json::from_str(&v).ok())
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "No refund for player {} in lobby {}",
+                    user_id, lobby_id
+                ))
+            })?;
+
+    if refund.status != RefundStatus::Refundable {
+        return Err(AppError::BadRequest("Refund was already paid".into()));
+    }
+
+    refund.status = RefundStatus::Refunded { tx_id };
+    let json =
+        serde_json::to_string(&refund).map_err(|e| AppError::Serialization(e.to_string()))?;
+    let _: () = conn
+        .hset(&key, user_id.to_string(), json)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(refund)
+}
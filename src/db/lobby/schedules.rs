@@ -0,0 +1,273 @@
+use chrono::{DateTime, Utc};
+use redis::{AsyncCommands, Script};
+use std::{collections::HashMap, sync::LazyLock};
+use uuid::Uuid;
+
+use crate::{
+    db::{game::get::get_game, lobby::get::get_lobby_info},
+    errors::AppError,
+    games::{registry::find_registration, schedules::Recurrence},
+    models::{
+        game::{LobbyInfo, LobbySettings},
+        lobby::LobbySchedule,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Moves a schedule to its next run if it is still due at the run the caller
+/// saw, so only one instance opens each occurrence.
+static CLAIM_RUN: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local score = redis.call('ZSCORE', KEYS[1], ARGV[1])
+        if not score or tonumber(score) ~= tonumber(ARGV[2]) then
+            return 0
+        end
+        redis.call('ZADD', KEYS[1], ARGV[3], ARGV[1])
+        redis.call('HSET', KEYS[2], ARGV[1], ARGV[4])
+        return 1
+        ",
+    )
+});
+
+pub async fn create_schedule(
+    name: String,
+    description: Option<String>,
+    creator_id: Uuid,
+    game_id: Uuid,
+    settings: LobbySettings,
+    recurrence: Recurrence,
+    redis: RedisClient,
+) -> Result<LobbySchedule, AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::BadRequest("Schedule name is required".into()));
+    }
+    recurrence.validate()?;
+    if let Some(split) = &settings.prize_split {
+        split.validate()?;
+    }
+
+    let game = get_game(game_id, redis.clone()).await?;
+    let registration = find_registration(&game)
+        .ok_or_else(|| AppError::BadRequest(format!("Game {} is not available", game.name)))?;
+    let settings = (registration.validate_settings)(settings, redis.clone()).await?;
+
+    let now = Utc::now();
+    let schedule = LobbySchedule {
+        id: Uuid::new_v4(),
+        name,
+        description,
+        game_id,
+        creator_id,
+        settings,
+        recurrence,
+        next_run: recurrence.next_after(now),
+        created_at: now,
+    };
+
+    let json = serde_json::to_string(&schedule)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize schedule: {}", e)))?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = redis::pipe()
+        .atomic()
+        .hset(RedisKey::lobby_schedules(), schedule.id.to_string(), json)
+        .ignore()
+        .zadd(
+            RedisKey::lobby_schedules_due(),
+            schedule.id.to_string(),
+            schedule.next_run.timestamp_millis(),
+        )
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(schedule)
+}
+
+/// Every schedule, the next to run first.
+pub async fn get_schedules(redis: RedisClient) -> Result<Vec<LobbySchedule>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let stored: HashMap<String, String> = conn
+        .hgetall(RedisKey::lobby_schedules())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut schedules = stored
+        .into_values()
+        .map(|json| {
+            serde_json::from_str::<LobbySchedule>(&json).map_err(|e| {
+                AppError::Deserialization(format!("Failed to deserialize schedule: {}", e))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    schedules.sort_by_key(|schedule| schedule.next_run);
+
+    Ok(schedules)
+}
+
+/// Schedules whose next run is at or before `now`.
+pub async fn get_due_schedules(
+    now: DateTime<Utc>,
+    redis: RedisClient,
+) -> Result<Vec<LobbySchedule>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let due_ids: Vec<String> = conn
+        .zrangebyscore(
+            RedisKey::lobby_schedules_due(),
+            "-inf",
+            now.timestamp_millis(),
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if due_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let stored: Vec<Option<String>> = redis::cmd("HMGET")
+        .arg(RedisKey::lobby_schedules())
+        .arg(&due_ids)
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(stored
+        .into_iter()
+        .flatten()
+        .filter_map(|json| match serde_json::from_str::<LobbySchedule>(&json) {
+            Ok(schedule) => Some(schedule),
+            Err(e) => {
+                tracing::warn!("Skipping malformed lobby schedule: {}", e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Advances a due schedule to `next_run`. Returns false when another instance
+/// already claimed this run or the schedule was deleted.
+pub async fn claim_schedule_run(
+    schedule: &LobbySchedule,
+    next_run: DateTime<Utc>,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let advanced = LobbySchedule {
+        next_run,
+        ..schedule.clone()
+    };
+    let json = serde_json::to_string(&advanced)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize schedule: {}", e)))?;
+
+    let claimed: i64 = CLAIM_RUN
+        .key(RedisKey::lobby_schedules_due())
+        .key(RedisKey::lobby_schedules())
+        .arg(schedule.id.to_string())
+        .arg(schedule.next_run.timestamp_millis())
+        .arg(next_run.timestamp_millis())
+        .arg(json)
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(claimed == 1)
+}
+
+/// Stops a schedule. Lobbies it already opened stay linked to it.
+pub async fn delete_schedule(schedule_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (removed,): (usize,) = redis::pipe()
+        .atomic()
+        .hdel(RedisKey::lobby_schedules(), schedule_id.to_string())
+        .zrem(RedisKey::lobby_schedules_due(), schedule_id.to_string())
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if removed == 0 {
+        return Err(AppError::NotFound(format!(
+            "Schedule {} not found",
+            schedule_id
+        )));
+    }
+
+    Ok(())
+}
+
+pub async fn add_schedule_lobby(
+    schedule_id: Uuid,
+    lobby_id: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .lpush(
+            RedisKey::schedule_lobbies(KeyPart::Id(schedule_id)),
+            lobby_id.to_string(),
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Lobbies a schedule opened, newest first. Lobbies deleted since are left out.
+pub async fn get_schedule_lobbies(
+    schedule_id: Uuid,
+    limit: usize,
+    redis: RedisClient,
+) -> Result<Vec<LobbyInfo>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let lobby_ids: Vec<String> = conn
+        .lrange(
+            RedisKey::schedule_lobbies(KeyPart::Id(schedule_id)),
+            0,
+            limit as isize - 1,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    drop(conn);
+
+    let mut lobbies = Vec::with_capacity(lobby_ids.len());
+    for lobby_id in lobby_ids.iter().filter_map(|id| Uuid::parse_str(id).ok()) {
+        match get_lobby_info(lobby_id, redis.clone()).await {
+            Ok(lobby) => lobbies.push(lobby),
+            Err(AppError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(lobbies)
+}
@@ -0,0 +1,167 @@
+use std::sync::LazyLock;
+
+use redis::Script;
+
+/// Sets one field on a player hash only if the player still exists, so a
+/// late update can't resurrect a player that just left.
+///
+/// KEYS: player hash. ARGV: field, value.
+/// Returns -1 when the player is gone, 0 when unchanged, 1 when updated.
+pub static SET_PLAYER_FIELD: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('EXISTS', KEYS[1]) == 0 then
+            return -1
+        end
+        if redis.call('HGET', KEYS[1], ARGV[1]) == ARGV[2] then
+            return 0
+        end
+        redis.call('HSET', KEYS[1], ARGV[1], ARGV[2])
+        return 1
+        ",
+    )
+});
+
+/// Compare-and-set on one player hash field, used for validated state
+/// transitions where the read and the write must not interleave.
+///
+/// KEYS: player hash. ARGV: field, expected value ("" for unset), new value.
+/// Returns -1 when the player is gone, 0 when the field changed underneath, 1 when updated.
+pub static SWAP_PLAYER_FIELD: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('EXISTS', KEYS[1]) == 0 then
+            return -1
+        end
+        local current = redis.call('HGET', KEYS[1], ARGV[1]) or ''
+        if current ~= ARGV[2] then
+            return 0
+        end
+        redis.call('HSET', KEYS[1], ARGV[1], ARGV[3])
+        return 1
+        ",
+    )
+});
+
+/// Writes the player hash, both index sets and the lobby counters in one step.
+///
+/// KEYS: player hash, lobby hash, lobby players index, user lobbies index,
+/// participants index, pool index.
+/// ARGV: user id, lobby id, pool increment, then field/value pairs.
+/// Returns -1 when the player had already joined, -2 when they'd join a lobby
+/// that stopped waiting for players, 1 otherwise.
+pub static JOIN_PLAYER: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local previous = redis.call('HGET', KEYS[1], 'state')
+        if previous == 'Joined' then
+            return -1
+        end
+        local joining = false
+        for i = 4, #ARGV, 2 do
+            if ARGV[i] == 'state' then
+                joining = ARGV[i + 1] == 'Joined'
+            end
+        end
+        if joining and redis.call('HGET', KEYS[2], 'state') ~= 'Waiting' then
+            return -2
+        end
+        redis.call('HSET', KEYS[1], unpack(ARGV, 4))
+        redis.call('SADD', KEYS[3], ARGV[1])
+        redis.call('SADD', KEYS[4], ARGV[2])
+        if redis.call('HGET', KEYS[1], 'state') == 'Joined' then
+            redis.call('HINCRBY', KEYS[2], 'participants', 1)
+            redis.call('ZINCRBY', KEYS[5], 1, ARGV[2])
+        end
+        local pool = tonumber(ARGV[3])
+        if pool ~= 0 then
+            redis.call('HINCRBY', KEYS[2], 'current_amount', pool)
+            redis.call('ZINCRBY', KEYS[6], pool, ARGV[2])
+        end
+        return 1
+        ",
+    )
+});
+
+/// Removes a player and reverses their counters, once.
+///
+/// KEYS: player hash, lobby hash, lobby players index, user lobbies index,
+/// participants index, pool index.
+/// ARGV: user id, lobby id, entry amount refunded to the pool.
+/// Returns -1 when the player was not in the lobby, 1 otherwise.
+pub static LEAVE_PLAYER: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local state = redis.call('HGET', KEYS[1], 'state')
+        if not state then
+            return -1
+        end
+        redis.call('DEL', KEYS[1])
+        redis.call('SREM', KEYS[3], ARGV[1])
+        redis.call('SREM', KEYS[4], ARGV[2])
+        if state == 'Joined' then
+            redis.call('HINCRBY', KEYS[2], 'participants', -1)
+            redis.call('ZINCRBY', KEYS[5], -1, ARGV[2])
+            local refund = tonumber(ARGV[3])
+            if refund > 0 then
+                redis.call('HINCRBY', KEYS[2], 'current_amount', -refund)
+                redis.call('ZINCRBY', KEYS[6], -refund, ARGV[2])
+            end
+        end
+        return 1
+        ",
+    )
+});
+
+/// Moves a lobby to a new state, re-files it under that state's index and
+/// logs the change, as long as nothing moved it first.
+///
+/// KEYS: lobby hash, old state index, new state index, state history.
+/// ARGV: expected state, new state, lobby id, index score, history entry,
+/// history length kept.
+/// Returns -1 when the lobby is gone, 0 when its state changed underneath, 1 when moved.
+pub static SWAP_LOBBY_STATE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local current = redis.call('HGET', KEYS[1], 'state')
+        if not current then
+            return -1
+        end
+        if current ~= ARGV[1] then
+            return 0
+        end
+        redis.call('HSET', KEYS[1], 'state', ARGV[2])
+        redis.call('ZREM', KEYS[2], ARGV[3])
+        redis.call('ZADD', KEYS[3], ARGV[4], ARGV[3])
+        redis.call('RPUSH', KEYS[4], ARGV[5])
+        redis.call('LTRIM', KEYS[4], -tonumber(ARGV[6]), -1)
+        return 1
+        ",
+    )
+});
+
+/// Moves a finished lobby's keys into the archive and takes it off the
+/// state and all-lobbies indexes, unless it is no longer finished.
+///
+/// KEYS: lobby hash, finished state index, all lobbies index, then pairs of
+/// a lobby key and its archive key.
+/// ARGV: lobby id, archive timestamp.
+/// Returns 0 when the lobby is gone or not finished, 1 when archived.
+pub static ARCHIVE_LOBBY: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('HGET', KEYS[1], 'state') ~= 'Finished' then
+            return 0
+        end
+        redis.call('HSET', KEYS[1], 'archived_at', ARGV[2])
+        redis.call('ZREM', KEYS[2], ARGV[1])
+        redis.call('ZREM', KEYS[3], ARGV[1])
+        for i = 4, #KEYS, 2 do
+            if redis.call('EXISTS', KEYS[i]) == 1 then
+                redis.call('RENAME', KEYS[i], KEYS[i + 1])
+            end
+        end
+        return 1
+        ",
+    )
+});
@@ -0,0 +1,374 @@
+use bb8::PooledConnection;
+use bb8_redis::RedisConnectionManager;
+use chrono::Utc;
+use redis::{AsyncCommands, FromRedisValue, Pipeline};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::{
+    db::{lobby::get::hydrate_lobby_infos, utils::zrevrange_page},
+    errors::AppError,
+    models::{
+        game::{LobbyInfo, LobbySearch, LobbySort, LobbyState},
+        pagination::{PageCursor, Paginated},
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Sort scores are multiplied by this so lobbies that tie fall back to
+/// newest first, creation time being the smaller part of the score
+const SORT_SCORE_WEIGHT: f64 = 1e10;
+/// How long a search's temp sets live if the search never deletes them
+const TEMP_KEY_TTL_SECS: i64 = 60;
+
+fn name_index_members(lobby: &LobbyInfo) -> Vec<String> {
+    LobbySearch::name_words(&lobby.name)
+        .into_iter()
+        .map(|word| format!("{}:{}", word, lobby.id))
+        .collect()
+}
+
+/// Queues the commands adding a lobby to the search indexes.
+pub fn index_lobby(pipe: &mut Pipeline, lobby: &LobbyInfo) {
+    let lobby_id = lobby.id.to_string();
+    pipe.zadd(
+        RedisKey::lobbies_by_entry(),
+        &lobby_id,
+        lobby.entry_amount.unwrap_or(0.0),
+    )
+    .ignore()
+    .zadd(
+        RedisKey::lobbies_by_pool(),
+        &lobby_id,
+        lobby.current_amount.unwrap_or(0.0),
+    )
+    .ignore()
+    .zadd(
+        RedisKey::lobbies_by_participants(),
+        &lobby_id,
+        lobby.participants,
+    )
+    .ignore()
+    .zadd(
+        RedisKey::user_created_lobbies(KeyPart::Id(lobby.creator.id)),
+        &lobby_id,
+        lobby.created_at.timestamp(),
+    )
+    .ignore();
+
+    if lobby.settings.casual {
+        pipe.zadd(RedisKey::lobbies_casual(), &lobby_id, 0).ignore();
+    }
+
+    let members = name_index_members(lobby);
+    if !members.is_empty() {
+        let scored: Vec<(i32, &String)> = members.iter().map(|member| (0, member)).collect();
+        pipe.zadd_multiple(RedisKey::lobbies_name_index(), &scored)
+            .ignore();
+    }
+}
+
+/// Queues the commands removing a deleted lobby from the search indexes.
+pub fn unindex_lobby(pipe: &mut Pipeline, lobby: &LobbyInfo, creator_id: Uuid) {
+    let lobby_id = lobby.id.to_string();
+    pipe.zrem(RedisKey::lobbies_by_entry(), &lobby_id)
+        .ignore()
+        .zrem(RedisKey::lobbies_by_pool(), &lobby_id)
+        .ignore()
+        .zrem(RedisKey::lobbies_by_participants(), &lobby_id)
+        .ignore()
+        .zrem(RedisKey::lobbies_casual(), &lobby_id)
+        .ignore()
+        .zrem(
+            RedisKey::user_created_lobbies(KeyPart::Id(creator_id)),
+            &lobby_id,
+        )
+        .ignore();
+
+    let members = name_index_members(lobby);
+    if !members.is_empty() {
+        pipe.zrem(RedisKey::lobbies_name_index(), members).ignore();
+    }
+}
+
+/// Files the lobby under its new owner's created lobbies.
+pub async fn move_created_lobby(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    lobby_id: Uuid,
+    from: Uuid,
+    to: Uuid,
+) -> Result<(), AppError> {
+    let created: Option<f64> = conn
+        .zscore(RedisKey::lobbies_all(), lobby_id.to_string())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let _: () = redis::pipe()
+        .zrem(
+            RedisKey::user_created_lobbies(KeyPart::Id(from)),
+            lobby_id.to_string(),
+        )
+        .ignore()
+        .zadd(
+            RedisKey::user_created_lobbies(KeyPart::Id(to)),
+            lobby_id.to_string(),
+            created.unwrap_or_else(|| Utc::now().timestamp() as f64),
+        )
+        .ignore()
+        .query_async(&mut **conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Lobbies with a name word starting with every searched word.
+async fn lobbies_matching_name(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    words: &[String],
+) -> Result<HashSet<String>, AppError> {
+    let mut matching: Option<HashSet<String>> = None;
+
+    for word in words {
+        let min = format!("[{}", word).into_bytes();
+        let mut max = min.clone();
+        max.push(0xff);
+
+        let members: Vec<String> = conn
+            .zrangebylex(RedisKey::lobbies_name_index(), min, max)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+        let ids: HashSet<String> = members
+            .into_iter()
+            .filter_map(|member| member.rsplit_once(':').map(|(_, id)| id.to_string()))
+            .collect();
+
+        let narrowed = match matching {
+            Some(previous) => previous.intersection(&ids).cloned().collect(),
+            None => ids,
+        };
+        if narrowed.is_empty() {
+            return Ok(narrowed);
+        }
+        matching = Some(narrowed);
+    }
+
+    Ok(matching.unwrap_or_default())
+}
+
+/// A score range bound, open ended when unset.
+fn bound<T: ToString>(value: Option<T>, open: &str) -> String {
+    value.map_or_else(|| open.to_string(), |v| v.to_string())
+}
+
+/// Runs `cmd`, which stores into the temp set `key`, with the set's EXPIRE in
+/// the same pipeline, so a search that dies before deleting its temp sets
+/// can't leave them behind. Returns the command's reply.
+async fn store_temp<T: FromRedisValue>(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    key: &str,
+    cmd: redis::Cmd,
+    temp_keys: &mut Vec<String>,
+) -> Result<T, AppError> {
+    let (reply,): (T,) = redis::pipe()
+        .atomic()
+        .add_command(cmd)
+        .expire(key, TEMP_KEY_TTL_SECS)
+        .ignore()
+        .query_async(&mut **conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    temp_keys.push(key.to_string());
+    Ok(reply)
+}
+
+/// Copies the members of `source` scored within `min..=max` into a temp set.
+async fn store_score_range(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    source: String,
+    min: String,
+    max: String,
+    temp_keys: &mut Vec<String>,
+) -> Result<String, AppError> {
+    let key = RedisKey::temp_inter();
+    let mut cmd = redis::cmd("ZRANGESTORE");
+    cmd.arg(&key).arg(source).arg(min).arg(max).arg("BYSCORE");
+    let _: u64 = store_temp(conn, &key, cmd, temp_keys).await?;
+    Ok(key)
+}
+
+/// Stores the lobbies matching the search in a temp set scored in the order
+/// it asks for, returning the set and its size. `None` when nothing matches.
+async fn store_search_results(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    game_id: Option<Uuid>,
+    lobby_filters: Option<Vec<LobbyState>>,
+    search: &LobbySearch,
+    temp_keys: &mut Vec<String>,
+) -> Result<Option<(String, u64)>, AppError> {
+    // Every set is intersected; only the one the results sort by keeps its score
+    let mut base = match game_id {
+        Some(game_id) => RedisKey::game_lobbies(KeyPart::Id(game_id)),
+        None => RedisKey::lobbies_all(),
+    };
+    if search.casual == Some(false) {
+        // An intersection can't leave lobbies out, so the base set goes without them
+        let key = RedisKey::temp_inter();
+        let mut cmd = redis::cmd("ZDIFFSTORE");
+        cmd.arg(&key)
+            .arg(2)
+            .arg(&base)
+            .arg(RedisKey::lobbies_casual());
+        let _: u64 = store_temp(conn, &key, cmd, temp_keys).await?;
+        base = key;
+    }
+    let mut sets: Vec<(String, f64)> = vec![(base, 1.0)];
+
+    match search.sort {
+        LobbySort::Newest => {}
+        LobbySort::BiggestPool => sets.push((RedisKey::lobbies_by_pool(), SORT_SCORE_WEIGHT)),
+        LobbySort::Fullest => sets.push((RedisKey::lobbies_by_participants(), SORT_SCORE_WEIGHT)),
+    }
+
+    if let Some(states) = lobby_filters {
+        let state_keys: Vec<String> = states.iter().map(RedisKey::lobbies_state).collect();
+        let union_key = RedisKey::temp_union();
+        let mut cmd = redis::cmd("ZUNIONSTORE");
+        cmd.arg(&union_key).arg(state_keys.len()).arg(&state_keys);
+        let _: u64 = store_temp(conn, &union_key, cmd, temp_keys).await?;
+        sets.push((union_key, 0.0));
+    }
+
+    if search.casual == Some(true) {
+        sets.push((RedisKey::lobbies_casual(), 0.0));
+    }
+
+    if let Some(creator_id) = search.creator_id {
+        sets.push((RedisKey::user_created_lobbies(KeyPart::Id(creator_id)), 0.0));
+    }
+
+    if !search.name_words.is_empty() {
+        let ids = lobbies_matching_name(conn, &search.name_words).await?;
+        if ids.is_empty() {
+            return Ok(None);
+        }
+
+        let key = RedisKey::temp_inter();
+        let scored: Vec<(i32, &String)> = ids.iter().map(|id| (0, id)).collect();
+        let mut cmd = redis::cmd("ZADD");
+        cmd.arg(&key).arg(&scored);
+        let _: u64 = store_temp(conn, &key, cmd, temp_keys).await?;
+        sets.push((key, 0.0));
+    }
+
+    if search.min_entry.is_some() || search.max_entry.is_some() {
+        let key = store_score_range(
+            conn,
+            RedisKey::lobbies_by_entry(),
+            bound(search.min_entry, "-inf"),
+            bound(search.max_entry, "+inf"),
+            temp_keys,
+        )
+        .await?;
+        sets.push((key, 0.0));
+    }
+
+    if let Some(has_pool) = search.has_pool {
+        // Free lobbies keep a pool of 0 so they stay in the index
+        let (min, max) = if has_pool { ("(0", "+inf") } else { ("0", "0") };
+        let key = store_score_range(
+            conn,
+            RedisKey::lobbies_by_pool(),
+            min.into(),
+            max.into(),
+            temp_keys,
+        )
+        .await?;
+        sets.push((key, 0.0));
+    }
+
+    if search.min_players.is_some() || search.max_players.is_some() {
+        let key = store_score_range(
+            conn,
+            RedisKey::lobbies_by_participants(),
+            bound(search.min_players, "-inf"),
+            bound(search.max_players, "+inf"),
+            temp_keys,
+        )
+        .await?;
+        sets.push((key, 0.0));
+    }
+
+    let result_key = RedisKey::temp_inter();
+    let mut intersect = redis::cmd("ZINTERSTORE");
+    intersect.arg(&result_key).arg(sets.len());
+    for (key, _) in &sets {
+        intersect.arg(key);
+    }
+    intersect.arg("WEIGHTS");
+    for (_, weight) in &sets {
+        intersect.arg(*weight);
+    }
+    let total: u64 = store_temp(conn, &result_key, intersect, temp_keys).await?;
+
+    Ok(Some((result_key, total)))
+}
+
+/// Ids of one page of the stored search results, with where the page starts
+/// and the cursor after it.
+async fn search_page(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    results: Option<(String, u64)>,
+    offset: usize,
+    limit: u32,
+    after: Option<&PageCursor>,
+) -> Result<(Vec<Uuid>, usize, Option<PageCursor>, u64), AppError> {
+    let Some((result_key, total)) = results else {
+        return Ok((Vec::new(), offset, None, 0));
+    };
+
+    let (ids, offset, last) = zrevrange_page(conn, &result_key, offset, limit, after).await?;
+    Ok((
+        ids.iter()
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .collect(),
+        offset,
+        last,
+        total,
+    ))
+}
+
+/// A page of lobbies matching the search, optionally of one game.
+pub async fn search_lobbies(
+    game_id: Option<Uuid>,
+    lobby_filters: Option<Vec<LobbyState>>,
+    search: &LobbySearch,
+    page: u32,
+    limit: u32,
+    after: Option<&PageCursor>,
+    redis: RedisClient,
+) -> Result<Paginated<LobbyInfo>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let offset = ((page.saturating_sub(1)) as usize).saturating_mul(limit as usize);
+
+    let mut temp_keys = Vec::new();
+    let found =
+        match store_search_results(&mut conn, game_id, lobby_filters, search, &mut temp_keys).await
+        {
+            Ok(results) => search_page(&mut conn, results, offset, limit, after).await,
+            Err(e) => Err(e),
+        };
+
+    if !temp_keys.is_empty() {
+        let _: Option<()> = conn.del(&temp_keys).await.ok();
+    }
+    let (ids, offset, last, total) = found?;
+
+    let lobbies = hydrate_lobby_infos(&mut conn, &ids, &redis).await?;
+    Ok(Paginated::from_offset(lobbies, offset, limit, total).with_next_cursor(last))
+}
@@ -1,6 +1,11 @@
+pub mod audit;
 pub mod chat;
 pub mod game;
 pub mod leaderboard;
 pub mod lobby;
+pub mod moderation;
+pub mod telegram;
 pub mod tx;
 pub mod user;
+pub mod utils;
+pub mod webhook;
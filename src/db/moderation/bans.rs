@@ -0,0 +1,177 @@
+use chrono::{Duration, Utc};
+use redis::{AsyncCommands, Script};
+use std::{cmp::Reverse, collections::HashMap, sync::LazyLock};
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{moderation::Ban, redis::RedisKey},
+    state::RedisClient,
+};
+
+const MAX_REASON_LEN: usize = 500;
+
+/// Drops an expired ban only if it is still the one that was read, so a ban
+/// issued in the meantime survives. Returns 1 when it was dropped.
+static REMOVE_EXPIRED_BAN: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('HGET', KEYS[1], ARGV[1]) ~= ARGV[2] then
+            return 0
+        end
+        redis.call('HDEL', KEYS[1], ARGV[1])
+        return 1
+        ",
+    )
+});
+
+/// Bans the user, replacing any ban they already have. No duration makes it
+/// permanent.
+pub async fn ban_user(
+    user_id: Uuid,
+    admin_id: Uuid,
+    reason: String,
+    duration_secs: Option<u64>,
+    redis: RedisClient,
+) -> Result<Ban, AppError> {
+    let reason = reason.trim().to_string();
+    if reason.is_empty() {
+        return Err(AppError::BadRequest("A ban needs a reason".into()));
+    }
+    if reason.chars().count() > MAX_REASON_LEN {
+        return Err(AppError::BadRequest(format!(
+            "Reason can be at most {MAX_REASON_LEN} characters"
+        )));
+    }
+
+    let issued_at = Utc::now();
+    let expires_at = match duration_secs {
+        Some(0) => {
+            return Err(AppError::BadRequest(
+                "Ban duration must be at least a second".into(),
+            ));
+        }
+        Some(secs) => {
+            let duration = i64::try_from(secs)
+                .ok()
+                .and_then(Duration::try_seconds)
+                .ok_or_else(|| AppError::BadRequest("Ban duration is too long".into()))?;
+            Some(
+                issued_at
+                    .checked_add_signed(duration)
+                    .ok_or_else(|| AppError::BadRequest("Ban duration is too long".into()))?,
+            )
+        }
+        None => None,
+    };
+
+    let ban = Ban {
+        user_id,
+        reason,
+        issued_by: admin_id,
+        issued_at,
+        expires_at,
+    };
+    let json = serde_json::to_string(&ban)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize ban: {}", e)))?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .hset(RedisKey::bans(), user_id.to_string(), json)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(ban)
+}
+
+/// Lifts the user's ban, expired or not.
+pub async fn lift_ban(user_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let removed: usize = conn
+        .hdel(RedisKey::bans(), user_id.to_string())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if removed == 0 {
+        return Err(AppError::NotFound(format!(
+            "User {} is not banned",
+            user_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// The user's ban, when they have one that hasn't expired.
+pub async fn get_active_ban(user_id: Uuid, redis: RedisClient) -> Result<Option<Ban>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let json: Option<String> = conn
+        .hget(RedisKey::bans(), user_id.to_string())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    let Some(json) = json else {
+        return Ok(None);
+    };
+
+    let ban: Ban = serde_json::from_str(&json)
+        .map_err(|e| AppError::Deserialization(format!("Failed to deserialize ban: {}", e)))?;
+
+    Ok(ban.is_active().then_some(ban))
+}
+
+/// Refuses users with an active ban.
+pub async fn ensure_not_banned(user_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    match get_active_ban(user_id, redis).await? {
+        Some(ban) => Err(AppError::AccountSuspended {
+            reason: ban.reason,
+            until: ban.expires_at,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Active bans, newest first. Expired ones are cleared out along the way.
+pub async fn get_bans(redis: RedisClient) -> Result<Vec<Ban>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let stored: HashMap<String, String> = conn
+        .hgetall(RedisKey::bans())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut bans = Vec::with_capacity(stored.len());
+    for (user_id, json) in stored {
+        let ban: Ban = serde_json::from_str(&json)
+            .map_err(|e| AppError::Deserialization(format!("Failed to deserialize ban: {}", e)))?;
+        if ban.is_active() {
+            bans.push(ban);
+            continue;
+        }
+
+        let _: i32 = REMOVE_EXPIRED_BAN
+            .key(RedisKey::bans())
+            .arg(user_id)
+            .arg(json)
+            .invoke_async(&mut *conn)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+    }
+    bans.sort_by_key(|ban| Reverse(ban.issued_at));
+
+    Ok(bans)
+}
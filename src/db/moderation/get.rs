@@ -0,0 +1,54 @@
+use redis::AsyncCommands;
+use std::collections::HashSet;
+
+use crate::{
+    errors::AppError,
+    models::{
+        moderation::{BannedWordsConfig, FilterMode},
+        redis::RedisKey,
+    },
+    state::RedisClient,
+};
+
+pub async fn get_banned_words(
+    redis: RedisClient,
+) -> Result<(FilterMode, HashSet<String>), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (mode, words): (Option<String>, HashSet<String>) = redis::pipe()
+        .get(RedisKey::filter_mode())
+        .smembers(RedisKey::banned_words())
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mode = mode.and_then(|m| m.parse().ok()).unwrap_or_default();
+
+    Ok((mode, words))
+}
+
+pub async fn get_banned_words_config(redis: RedisClient) -> Result<BannedWordsConfig, AppError> {
+    let (mode, words) = get_banned_words(redis).await?;
+    let mut words: Vec<String> = words.into_iter().collect();
+    words.sort();
+
+    Ok(BannedWordsConfig { mode, words })
+}
+
+/// True once the list has been seeded, even if admins have since emptied it.
+pub async fn banned_words_seeded(redis: RedisClient) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let exists: bool = conn
+        .exists(RedisKey::filter_mode())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(exists)
+}
@@ -0,0 +1,3 @@
+pub mod bans;
+pub mod get;
+pub mod patch;
@@ -0,0 +1,71 @@
+use crate::{
+    db::moderation::get::banned_words_seeded,
+    errors::AppError,
+    models::{moderation::FilterMode, redis::RedisKey},
+    state::RedisClient,
+};
+
+/// Seeds the banned-word list from the bundled offensive words on first boot.
+pub async fn seed_banned_words(redis: RedisClient) -> Result<(), AppError> {
+    if banned_words_seeded(redis.clone()).await? {
+        return Ok(());
+    }
+
+    let words_json = include_str!("../../assets/offensive_words.json");
+    let words: Vec<String> = serde_json::from_str(words_json).map_err(|e| {
+        AppError::Deserialization(format!("Failed to parse offensive_words.json: {}", e))
+    })?;
+
+    update_banned_words(words, Vec::new(), Some(FilterMode::default()), redis).await?;
+
+    tracing::info!("Seeded banned word list");
+    Ok(())
+}
+
+pub async fn update_banned_words(
+    add: Vec<String>,
+    remove: Vec<String>,
+    mode: Option<FilterMode>,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let normalize = |words: Vec<String>| -> Vec<String> {
+        words
+            .into_iter()
+            .map(|w| w.trim().to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect()
+    };
+    let add = normalize(add);
+    let remove = normalize(remove);
+
+    if add.iter().any(|w| w.contains(char::is_whitespace)) {
+        return Err(AppError::BadRequest(
+            "Banned words must be single words".into(),
+        ));
+    }
+
+    let key = RedisKey::banned_words();
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    if !remove.is_empty() {
+        pipe.srem(&key, remove).ignore();
+    }
+    if !add.is_empty() {
+        pipe.sadd(&key, add).ignore();
+    }
+    if let Some(mode) = mode {
+        pipe.set(RedisKey::filter_mode(), mode.as_str()).ignore();
+    }
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
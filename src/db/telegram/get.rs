@@ -0,0 +1,105 @@
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    config,
+    errors::AppError,
+    models::{
+        game::LobbyInfo,
+        redis::{KeyPart, RedisKey},
+        telegram::{TelegramJoinCode, TelegramRoutes},
+    },
+    state::RedisClient,
+};
+
+/// Field of the routes hash holding the default chat
+pub const DEFAULT_ROUTE: &str = "default";
+
+pub async fn get_join_code(code: &str, redis: RedisClient) -> Result<TelegramJoinCode, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let raw: Option<String> = conn
+        .get(RedisKey::telegram_join_code(KeyPart::Str(code.to_string())))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let raw = raw.ok_or_else(|| AppError::NotFound("Join link not found or expired".into()))?;
+
+    serde_json::from_str(&raw)
+        .map_err(|e| AppError::Deserialization(format!("Failed to parse join code: {}", e)))
+}
+
+pub async fn get_telegram_routes(redis: RedisClient) -> Result<TelegramRoutes, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let stored: HashMap<String, String> = conn
+        .hgetall(RedisKey::telegram_routes())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut default_chat_id = None;
+    let mut games = HashMap::new();
+    for (field, chat_id) in stored {
+        let Ok(chat_id) = chat_id.parse::<i64>() else {
+            tracing::warn!("Ignoring telegram route {} with chat id {}", field, chat_id);
+            continue;
+        };
+        if field == DEFAULT_ROUTE {
+            default_chat_id = Some(chat_id);
+        } else if let Ok(game_id) = Uuid::parse_str(&field) {
+            games.insert(game_id, chat_id);
+        }
+    }
+
+    Ok(TelegramRoutes {
+        default_overridden: default_chat_id.is_some(),
+        default_chat_id: default_chat_id.or(config::get().telegram_chat_id),
+        games,
+    })
+}
+
+/// Chat a new lobby of this game is announced in: its own route, then the
+/// default. `None` when announcements are off or there's nowhere to post.
+pub async fn get_announcement_chat_id(
+    game_id: Uuid,
+    redis: RedisClient,
+) -> Result<Option<i64>, AppError> {
+    let config = config::get();
+    if !config.telegram_announcements {
+        return Ok(None);
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (game_route, default_route): (Option<i64>, Option<i64>) = redis::cmd("HMGET")
+        .arg(RedisKey::telegram_routes())
+        .arg(game_id.to_string())
+        .arg(DEFAULT_ROUTE)
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(game_route.or(default_route).or(config.telegram_chat_id))
+}
+
+/// Chat the lobby's creation message went to, where its follow-ups belong.
+pub async fn get_lobby_chat_id(
+    lobby_info: &LobbyInfo,
+    redis: RedisClient,
+) -> Result<Option<i64>, AppError> {
+    match lobby_info.tg_chat_id {
+        Some(chat_id) => Ok(Some(chat_id)),
+        // Announced before lobbies kept their chat, when routing was global
+        None => get_announcement_chat_id(lobby_info.game.id, redis).await,
+    }
+}
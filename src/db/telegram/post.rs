@@ -0,0 +1,104 @@
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::{
+    db::telegram::get::DEFAULT_ROUTE,
+    errors::AppError,
+    models::{
+        redis::{KeyPart, RedisKey},
+        telegram::TelegramJoinCode,
+    },
+    state::RedisClient,
+};
+
+/// How long a Telegram join button keeps working
+const JOIN_CODE_TTL_SECS: u64 = 24 * 60 * 60;
+
+pub async fn create_join_code(
+    lobby_id: Uuid,
+    telegram_user_id: u64,
+    chat_id: i64,
+    redis: RedisClient,
+) -> Result<String, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let code = Uuid::new_v4().simple().to_string()[..12].to_string();
+    let join_code = TelegramJoinCode {
+        lobby_id,
+        telegram_user_id,
+        chat_id,
+    };
+    let serialized = serde_json::to_string(&join_code)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize join code: {}", e)))?;
+
+    let _: () = conn
+        .set_ex(
+            RedisKey::telegram_join_code(KeyPart::Str(code.clone())),
+            serialized,
+            JOIN_CODE_TTL_SECS,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(code)
+}
+
+/// Records that a Telegram account reached the lobby through a deep link.
+pub async fn associate_telegram_with_lobby(
+    join_code: &TelegramJoinCode,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .hset(
+            RedisKey::lobby_telegram_users(KeyPart::Id(join_code.lobby_id)),
+            join_code.telegram_user_id,
+            join_code.chat_id,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Points a game's announcements at a chat, or the default when `game_id` is
+/// `None`. A `None` chat drops the route, sending the game to the default and
+/// the default back to `TELEGRAM_CHAT_ID`.
+pub async fn set_telegram_route(
+    game_id: Option<Uuid>,
+    chat_id: Option<i64>,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let field = game_id
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| DEFAULT_ROUTE.to_string());
+
+    match chat_id {
+        Some(chat_id) => {
+            let _: () = conn
+                .hset(RedisKey::telegram_routes(), field, chat_id)
+                .await
+                .map_err(AppError::RedisCommandError)?;
+        }
+        None => {
+            let _: () = conn
+                .hdel(RedisKey::telegram_routes(), field)
+                .await
+                .map_err(AppError::RedisCommandError)?;
+        }
+    }
+
+    Ok(())
+}
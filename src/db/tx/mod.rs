@@ -0,0 +1,4 @@
+pub mod pending;
+pub mod validate;
+
+pub use validate::{TxVerification, validate_fee_transfer, validate_payment_tx, verify_payment_tx};
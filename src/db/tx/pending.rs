@@ -0,0 +1,140 @@
+use chrono::Utc;
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::redis::{KeyPart, RedisKey},
+    state::RedisClient,
+};
+
+/// Delay between verification attempts for a pending join.
+pub const PENDING_JOIN_RETRY_SECS: i64 = 10;
+/// Give up on a pending join after this many attempts (~5 minutes).
+pub const MAX_PENDING_JOIN_ATTEMPTS: u32 = 30;
+
+#[derive(Debug, Clone)]
+pub struct PendingJoinTx {
+    pub lobby_id: Uuid,
+    pub user_id: Uuid,
+    pub tx_id: String,
+    pub attempts: u32,
+}
+
+/// Schedules (or reschedules) verification of a join payment and returns the attempt count.
+pub async fn queue_pending_join(
+    lobby_id: Uuid,
+    user_id: Uuid,
+    tx_id: &str,
+    redis: RedisClient,
+) -> Result<u32, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let entry_key = RedisKey::pending_join_tx(KeyPart::Id(lobby_id), KeyPart::Id(user_id));
+    let next_attempt = Utc::now().timestamp() + PENDING_JOIN_RETRY_SECS;
+    let ttl = PENDING_JOIN_RETRY_SECS * (MAX_PENDING_JOIN_ATTEMPTS as i64 + 1);
+
+    let (attempts,): (u32,) = redis::pipe()
+        .hset(&entry_key, "tx_id", tx_id)
+        .ignore()
+        .hincr(&entry_key, "attempts", 1)
+        .expire(&entry_key, ttl)
+        .ignore()
+        .zadd(
+            RedisKey::pending_join_txs(),
+            format!("{lobby_id}:{user_id}"),
+            next_attempt,
+        )
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(attempts)
+}
+
+/// Returns pending joins whose next verification attempt is due.
+pub async fn get_due_pending_joins(redis: RedisClient) -> Result<Vec<PendingJoinTx>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let members: Vec<String> = conn
+        .zrangebyscore(RedisKey::pending_join_txs(), "-inf", Utc::now().timestamp())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut due = Vec::with_capacity(members.len());
+    for member in members {
+        let Some((lobby_id, user_id)) = member
+            .split_once(':')
+            .and_then(|(l, u)| Uuid::parse_str(l).ok().zip(Uuid::parse_str(u).ok()))
+        else {
+            let _: () = conn
+                .zrem(RedisKey::pending_join_txs(), &member)
+                .await
+                .map_err(AppError::RedisCommandError)?;
+            continue;
+        };
+
+        let entry_key = RedisKey::pending_join_tx(KeyPart::Id(lobby_id), KeyPart::Id(user_id));
+        let data: HashMap<String, String> = conn
+            .hgetall(&entry_key)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+
+        let Some(tx_id) = data.get("tx_id").cloned() else {
+            // Entry expired; drop the dangling schedule
+            let _: () = conn
+                .zrem(RedisKey::pending_join_txs(), &member)
+                .await
+                .map_err(AppError::RedisCommandError)?;
+            continue;
+        };
+
+        due.push(PendingJoinTx {
+            lobby_id,
+            user_id,
+            tx_id,
+            attempts: data
+                .get("attempts")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+        });
+    }
+
+    Ok(due)
+}
+
+pub async fn remove_pending_join(
+    lobby_id: Uuid,
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = redis::pipe()
+        .zrem(
+            RedisKey::pending_join_txs(),
+            format!("{lobby_id}:{user_id}"),
+        )
+        .ignore()
+        .del(RedisKey::pending_join_tx(
+            KeyPart::Id(lobby_id),
+            KeyPart::Id(user_id),
+        ))
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
@@ -1,19 +1,48 @@
-use crate::errors::AppError;
+use redis::AsyncCommands;
+
+use crate::{
+    config,
+    errors::AppError,
+    models::{
+        game::PoolAsset,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// How long a successfully verified payment is trusted without asking the API again.
+const VERIFIED_TX_TTL_SECS: u64 = 600;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxVerification {
+    Verified,
+    /// The transaction is not confirmed yet, or the API could not be reached.
+    Pending,
+}
 
-pub async fn validate_payment_tx(
-    tx_id: &str,
-    expected_sender: &str,
-    expected_contract: &str,
-    expected_amount: f64,
-) -> Result<(), AppError> {
-    let network = std::env::var("STACKS_NETWORK").unwrap_or("testnet".to_string());
+/// Fetches a transaction from the Stacks API.
+///
+/// Returns `Ok(None)` for transient conditions (network errors, 404 while the
+/// tx is still propagating, 5xx) so callers can retry instead of rejecting.
+async fn fetch_tx(tx_id: &str) -> Result<Option<serde_json::Value>, AppError> {
+    let network = config::get().stacks_network.clone();
     let url = format!("https://api.{network}.hiro.so/extended/v1/tx/{}", tx_id);
 
-    let res = reqwest::get(&url)
-        .await
-        .map_err(|e| AppError::BadRequest(format!("Failed to fetch tx: {}", e)))?;
+    let res = match reqwest::get(&url).await {
+        Ok(res) => res,
+        Err(e) => {
+            tracing::warn!("Failed to fetch tx {}: {}", tx_id, e);
+            return Ok(None);
+        }
+    };
+
+    let status = res.status();
+    if status == reqwest::StatusCode::NOT_FOUND || status.is_server_error() {
+        tracing::debug!("Tx {} not available yet ({})", tx_id, status);
+        return Ok(None);
+    }
 
-    if !res.status().is_success() {
+    if !status.is_success() {
         return Err(AppError::BadRequest(format!(
             "Transaction not found or failed: {}",
             tx_id
@@ -25,6 +54,93 @@ pub async fn validate_payment_tx(
         .await
         .map_err(|e| AppError::Deserialization(format!("Invalid JSON response: {}", e)))?;
 
+    Ok(Some(json))
+}
+
+/// Validates an entry payment, using a short-lived Redis cache of verified tx ids.
+pub async fn verify_payment_tx(
+    tx_id: &str,
+    expected_sender: &str,
+    expected_contract: &str,
+    expected_amount: f64,
+    asset: Option<&PoolAsset>,
+    redis: RedisClient,
+) -> Result<TxVerification, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let cache_key = RedisKey::tx_verified(KeyPart::Str(tx_id.to_string()));
+    let network = &config::get().stacks_network;
+    let asset_key = asset.and_then(|a| a.asset_id(network)).unwrap_or("stx");
+    let fingerprint =
+        format!("{expected_sender}:{expected_contract}:{expected_amount}:{asset_key}");
+
+    let cached: Option<String> = conn
+        .get(&cache_key)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if cached.as_deref() == Some(fingerprint.as_str()) {
+        return Ok(TxVerification::Verified);
+    }
+
+    let verification = check_payment_tx(
+        tx_id,
+        expected_sender,
+        expected_contract,
+        expected_amount,
+        asset,
+    )
+    .await?;
+
+    if verification == TxVerification::Verified {
+        let _: () = conn
+            .set_ex(&cache_key, fingerprint, VERIFIED_TX_TTL_SECS)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+    }
+
+    Ok(verification)
+}
+
+pub async fn validate_payment_tx(
+    tx_id: &str,
+    expected_sender: &str,
+    expected_contract: &str,
+    expected_amount: f64,
+    asset: Option<&PoolAsset>,
+) -> Result<(), AppError> {
+    match check_payment_tx(
+        tx_id,
+        expected_sender,
+        expected_contract,
+        expected_amount,
+        asset,
+    )
+    .await?
+    {
+        TxVerification::Verified => Ok(()),
+        TxVerification::Pending => Err(AppError::BadRequest(format!(
+            "Transaction is still pending: {}",
+            tx_id
+        ))),
+    }
+}
+
+/// Without an `asset` (lobbies created before pools tracked one) any STX or
+/// fungible token transfer counts, assuming 6 decimals.
+async fn check_payment_tx(
+    tx_id: &str,
+    expected_sender: &str,
+    expected_contract: &str,
+    expected_amount: f64,
+    asset: Option<&PoolAsset>,
+) -> Result<TxVerification, AppError> {
+    let Some(json) = fetch_tx(tx_id).await? else {
+        return Ok(TxVerification::Pending);
+    };
+
     // Validate sender
     let sender_address = json
         .get("sender_address")
@@ -44,6 +160,10 @@ pub async fn validate_payment_tx(
         .and_then(|v| v.as_str())
         .unwrap_or("failed");
 
+    if status == "pending" {
+        return Ok(TxVerification::Pending);
+    }
+
     if status != "success" {
         return Err(AppError::BadRequest("Transaction failed".into()));
     }
@@ -57,16 +177,26 @@ pub async fn validate_payment_tx(
     tracing::info!("Processing {:#?}", events);
     let mut matched = None;
 
+    let network = &config::get().stacks_network;
+    let expected_asset_id = asset.and_then(|a| a.asset_id(network));
+    let expected_units = asset
+        .unwrap_or(&PoolAsset::Stx)
+        .to_base_units(expected_amount)
+        .ok_or_else(|| AppError::BadRequest("Invalid expected amount".into()))?;
+
     for event in events {
         let Some(event_type) = event.get("event_type").and_then(|et| et.as_str()) else {
             tracing::warn!("Skipping event: missing event_type");
             continue;
         };
 
-        if event_type != "stx_asset" && event_type != "fungible_token_asset" {
-            tracing::debug!(
-                "Skipping event: not stx_asset or fungible_token_asset, got {event_type}"
-            );
+        let type_matches = match (asset, expected_asset_id) {
+            (None, _) => event_type == "stx_asset" || event_type == "fungible_token_asset",
+            (Some(_), None) => event_type == "stx_asset",
+            (Some(_), Some(_)) => event_type == "fungible_token_asset",
+        };
+        if !type_matches {
+            tracing::debug!("Skipping event: unexpected event type {event_type}");
             continue;
         }
 
@@ -75,6 +205,14 @@ pub async fn validate_payment_tx(
             continue;
         };
 
+        if let Some(expected_id) = expected_asset_id {
+            let asset_id = asset.get("asset_id").and_then(|a| a.as_str());
+            if asset_id != Some(expected_id) {
+                tracing::debug!("Asset mismatch: expected {expected_id}, got {asset_id:?}");
+                continue;
+            }
+        }
+
         let recipient_matches = asset
             .get("recipient")
             .and_then(|r| r.as_str())
@@ -93,11 +231,11 @@ pub async fn validate_payment_tx(
         let amount_matches = asset
             .get("amount")
             .and_then(|a| a.as_str())
-            .and_then(|s| s.parse::<f64>().ok())
+            .and_then(|s| s.parse::<u128>().ok())
             .map(|a| {
-                let m = a == expected_amount * 1_000_000.0;
+                let m = a == expected_units;
                 if !m {
-                    tracing::debug!("Amount mismatch: expected {expected_amount}, got {a}");
+                    tracing::debug!("Amount mismatch: expected {expected_units}, got {a}");
                 }
                 m
             })
@@ -114,11 +252,11 @@ pub async fn validate_payment_tx(
 
     if matched.is_none() {
         return Err(AppError::BadRequest(
-            "No matching STX asset transfer event found".into(),
+            "No matching asset transfer event found".into(),
         ));
     }
 
-    Ok(())
+    Ok(TxVerification::Verified)
 }
 
 pub async fn validate_fee_transfer(
@@ -126,24 +264,12 @@ pub async fn validate_fee_transfer(
     expected_sender: &str,
     fee_wallet: &str,
 ) -> Result<(), AppError> {
-    let network = std::env::var("STACKS_NETWORK").unwrap_or("testnet".to_string());
-    let url = format!("https://api.{network}.hiro.so/extended/v1/tx/{}", tx_id);
-
-    let res = reqwest::get(&url)
-        .await
-        .map_err(|e| AppError::BadRequest(format!("Failed to fetch tx: {}", e)))?;
-
-    if !res.status().is_success() {
+    let Some(json) = fetch_tx(tx_id).await? else {
         return Err(AppError::BadRequest(format!(
-            "Transaction not found or failed: {}",
+            "Transaction is still pending: {}",
             tx_id
         )));
-    }
-
-    let json: serde_json::Value = res
-        .json()
-        .await
-        .map_err(|e| AppError::Deserialization(format!("Invalid JSON response: {}", e)))?;
+    };
 
     // Validate sender
     let sender_address = json
@@ -203,11 +329,11 @@ pub async fn validate_fee_transfer(
         .ok_or_else(|| AppError::BadRequest("Missing transfer amount".into()))?;
 
     let amount = amount_str
-        .parse::<f64>()
+        .parse::<u128>()
         .map_err(|_| AppError::BadRequest("Invalid amount format".into()))?;
 
     // Expected amount is 0.2 STX = 200,000 microSTX
-    let expected_amount = 200_000.0;
+    let expected_amount: u128 = 200_000;
     if amount != expected_amount {
         return Err(AppError::BadRequest(format!(
             "Invalid fee amount: expected {} microSTX (0.2 STX), got {} microSTX",
@@ -0,0 +1,160 @@
+use redis::AsyncCommands;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::{
+    db::user::{
+        friends::remove_friend,
+        get::{get_active_users_with_conn, get_user_by_id_with_conn},
+    },
+    errors::AppError,
+    models::{
+        User,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Blocks a user. Any friendship or pending friend request between the two
+/// is dropped.
+pub async fn block_user(
+    user_id: Uuid,
+    blocked_id: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    if user_id == blocked_id {
+        return Err(AppError::BadRequest("You can't block yourself".into()));
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    // Only to 404 on unknown ids, deleted users can still be blocked
+    get_user_by_id_with_conn(blocked_id, &mut conn).await?;
+
+    let _: () = redis::pipe()
+        .atomic()
+        .sadd(
+            RedisKey::user_blocked(KeyPart::Id(user_id)),
+            blocked_id.to_string(),
+        )
+        .ignore()
+        .sadd(
+            RedisKey::user_blocked_by(KeyPart::Id(blocked_id)),
+            user_id.to_string(),
+        )
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    drop(conn);
+    remove_friend(user_id, blocked_id, redis).await?;
+
+    Ok(())
+}
+
+pub async fn unblock_user(
+    user_id: Uuid,
+    blocked_id: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = redis::pipe()
+        .atomic()
+        .srem(
+            RedisKey::user_blocked(KeyPart::Id(user_id)),
+            blocked_id.to_string(),
+        )
+        .ignore()
+        .srem(
+            RedisKey::user_blocked_by(KeyPart::Id(blocked_id)),
+            user_id.to_string(),
+        )
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+pub async fn has_blocked(
+    user_id: Uuid,
+    other_id: Uuid,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let blocked: bool = conn
+        .sismember(
+            RedisKey::user_blocked(KeyPart::Id(user_id)),
+            other_id.to_string(),
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(blocked)
+}
+
+/// Ids the user has blocked.
+pub async fn get_blocked_ids(user_id: Uuid, redis: RedisClient) -> Result<HashSet<Uuid>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let ids: Vec<String> = conn
+        .smembers(RedisKey::user_blocked(KeyPart::Id(user_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(ids
+        .iter()
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect())
+}
+
+/// Ids of users who blocked this user, used to skip them when delivering.
+pub async fn get_blocked_by_ids(
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<HashSet<Uuid>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let ids: Vec<String> = conn
+        .smembers(RedisKey::user_blocked_by(KeyPart::Id(user_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(ids
+        .iter()
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect())
+}
+
+pub async fn get_blocked_users(user_id: Uuid, redis: RedisClient) -> Result<Vec<User>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let ids: Vec<String> = conn
+        .smembers(RedisKey::user_blocked(KeyPart::Id(user_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    get_active_users_with_conn(&ids, &mut conn).await
+}
@@ -0,0 +1,58 @@
+use dashmap::DashMap;
+use std::{
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+use crate::models::User;
+
+/// How long a user read for hydration is reused before going back to Redis.
+/// Updates on this instance invalidate right away; other instances catch up
+/// once the entry expires.
+const USER_CACHE_TTL: Duration = Duration::from_secs(15);
+
+static USER_CACHE: LazyLock<DashMap<Uuid, (Instant, User)>> = LazyLock::new(DashMap::new);
+
+/// Last time expired entries were swept out. Users that are never read again
+/// would otherwise stay cached forever.
+static LAST_SWEEP: LazyLock<Mutex<Instant>> = LazyLock::new(|| Mutex::new(Instant::now()));
+
+pub fn cached_user(user_id: Uuid) -> Option<User> {
+    let entry = USER_CACHE.get(&user_id)?;
+    let (cached_at, user) = entry.value();
+    if cached_at.elapsed() < USER_CACHE_TTL {
+        return Some(user.clone());
+    }
+    drop(entry);
+
+    USER_CACHE.remove_if(&user_id, |_, (cached_at, _)| {
+        cached_at.elapsed() >= USER_CACHE_TTL
+    });
+    None
+}
+
+pub fn cache_user(user: &User) {
+    USER_CACHE.insert(user.id, (Instant::now(), user.clone()));
+    sweep_expired();
+}
+
+/// Drops every expired entry, at most once per TTL. Whoever else is sweeping
+/// already has it covered.
+fn sweep_expired() {
+    let Ok(mut last_sweep) = LAST_SWEEP.try_lock() else {
+        return;
+    };
+    if last_sweep.elapsed() < USER_CACHE_TTL {
+        return;
+    }
+    *last_sweep = Instant::now();
+    drop(last_sweep);
+
+    USER_CACHE.retain(|_, (cached_at, _)| cached_at.elapsed() < USER_CACHE_TTL);
+}
+
+/// Drops a user after their hash changed so the next hydration reads it fresh.
+pub fn invalidate_user(user_id: Uuid) {
+    USER_CACHE.remove(&user_id);
+}
@@ -0,0 +1,142 @@
+use chrono::Utc;
+use std::collections::HashMap;
+
+use crate::{
+    db::{lobby::get::get_player_lobbies, user::cache::invalidate_user},
+    errors::AppError,
+    models::{
+        User,
+        game::LobbyState,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+/// Replaces the user's profile with a tombstone and drops them from the wallet,
+/// username, telegram and leaderboard indexes. Lobby and player records are left intact.
+pub async fn soft_delete_user(user_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    let active = get_player_lobbies(
+        user_id,
+        None,
+        Some(vec![
+            LobbyState::Waiting,
+            LobbyState::Starting,
+            LobbyState::InProgress,
+        ]),
+        1,
+        1,
+        None,
+        redis.clone(),
+    )
+    .await?;
+
+    if active.total > 0 {
+        return Err(AppError::BadRequest(
+            "Cannot delete a user who is in an active lobby".into(),
+        ));
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let user_key = RedisKey::user(KeyPart::Id(user_id));
+    let data: HashMap<String, String> = conn
+        .hgetall(&user_key)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if data.is_empty() {
+        return Err(AppError::NotFound("User not found".into()));
+    }
+    if data.get("deleted").is_some_and(|v| v == "true") {
+        return Err(AppError::BadRequest("User is already deleted".into()));
+    }
+
+    let user_id_str = user_id.to_string();
+    let tombstone = User::tombstone(user_id);
+    let tombstone_hash = vec![
+        ("id", user_id_str.clone()),
+        ("wallet_address", tombstone.wallet_address),
+        ("wars_point", tombstone.wars_point.to_string()),
+        ("display_name", User::DELETED_DISPLAY_NAME.to_string()),
+        ("deleted", "true".to_string()),
+        ("deleted_at", Utc::now().to_rfc3339()),
+    ];
+
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+
+    if let Some(wallet) = data.get("wallet_address") {
+        pipe.hdel(RedisKey::users_wallets(), wallet).ignore();
+    }
+    if let Some(username) = data.get("username") {
+        pipe.hdel(RedisKey::users_usernames(), username.to_lowercase())
+            .ignore();
+    }
+    if let Some(telegram_id) = data.get("telegram_id") {
+        pipe.hdel(RedisKey::telegram_links(), telegram_id).ignore();
+    }
+
+    pipe.del(&user_key)
+        .ignore()
+        .hset_multiple(&user_key, &tombstone_hash)
+        .ignore()
+        // Exclude from leaderboards going forward
+        .zrem(RedisKey::users_points(), &user_id_str)
+        .ignore()
+        .zrem(RedisKey::users_matches(), &user_id_str)
+        .ignore()
+        .zrem(RedisKey::users_wins(), &user_id_str)
+        .ignore()
+        .hdel(RedisKey::users_pnl(), &user_id_str)
+        .ignore()
+        .del(RedisKey::user_notification_prefs(KeyPart::Id(user_id)))
+        .ignore()
+        .del(RedisKey::user_friends(KeyPart::Id(user_id)))
+        .ignore()
+        .del(RedisKey::user_friend_requests_in(KeyPart::Id(user_id)))
+        .ignore()
+        .del(RedisKey::user_friend_requests_out(KeyPart::Id(user_id)))
+        .ignore()
+        .del(RedisKey::user_blocked(KeyPart::Id(user_id)))
+        .ignore()
+        // Sign the user out everywhere
+        .del(RedisKey::user_sessions(KeyPart::Id(user_id)))
+        .ignore()
+        .del(RedisKey::user_session_activity(KeyPart::Id(user_id)))
+        .ignore();
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    invalidate_user(user_id);
+
+    tracing::info!("Soft-deleted user {}", user_id);
+    Ok(())
+}
+
+/// Rejects actions from users whose account was soft-deleted but still hold a valid token.
+pub async fn ensure_user_active(user_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let deleted: Option<String> = conn
+        .hget(RedisKey::user(KeyPart::Id(user_id)), "deleted")
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if deleted.as_deref() == Some("true") {
+        return Err(AppError::Unauthorized(
+            "This account has been deleted".into(),
+        ));
+    }
+
+    Ok(())
+}
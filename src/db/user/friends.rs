@@ -0,0 +1,245 @@
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+use crate::{
+    db::user::{
+        delete::ensure_user_active,
+        get::{get_active_users_with_conn, get_user_by_id_with_conn},
+    },
+    errors::AppError,
+    models::{
+        User,
+        friends::FriendStatus,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// Sends a friend request, or accepts straight away when the other user had
+/// already asked.
+pub async fn send_friend_request(
+    user_id: Uuid,
+    friend_id: Uuid,
+    redis: RedisClient,
+) -> Result<FriendStatus, AppError> {
+    if user_id == friend_id {
+        return Err(AppError::BadRequest("You can't befriend yourself".into()));
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let friend = get_user_by_id_with_conn(friend_id, &mut conn).await?;
+    if friend.deleted {
+        return Err(AppError::NotFound("User not found".into()));
+    }
+
+    let (blocked, blocked_by, already_friends, requested_by_friend): (bool, bool, bool, bool) =
+        redis::pipe()
+            .sismember(
+                RedisKey::user_blocked(KeyPart::Id(user_id)),
+                friend_id.to_string(),
+            )
+            .sismember(
+                RedisKey::user_blocked_by(KeyPart::Id(user_id)),
+                friend_id.to_string(),
+            )
+            .sismember(
+                RedisKey::user_friends(KeyPart::Id(user_id)),
+                friend_id.to_string(),
+            )
+            .sismember(
+                RedisKey::user_friend_requests_in(KeyPart::Id(user_id)),
+                friend_id.to_string(),
+            )
+            .query_async(&mut *conn)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+
+    if blocked || blocked_by {
+        return Err(AppError::BadRequest(
+            "Can't send a friend request to this user".into(),
+        ));
+    }
+    if already_friends {
+        return Ok(FriendStatus::Friends);
+    }
+    if requested_by_friend {
+        drop(conn);
+        return accept_friend_request(user_id, friend_id, redis).await;
+    }
+
+    let _: () = redis::pipe()
+        .atomic()
+        .sadd(
+            RedisKey::user_friend_requests_out(KeyPart::Id(user_id)),
+            friend_id.to_string(),
+        )
+        .ignore()
+        .sadd(
+            RedisKey::user_friend_requests_in(KeyPart::Id(friend_id)),
+            user_id.to_string(),
+        )
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(FriendStatus::Pending)
+}
+
+pub async fn accept_friend_request(
+    user_id: Uuid,
+    friend_id: Uuid,
+    redis: RedisClient,
+) -> Result<FriendStatus, AppError> {
+    ensure_user_active(friend_id, redis.clone()).await?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let requested: bool = conn
+        .sismember(
+            RedisKey::user_friend_requests_in(KeyPart::Id(user_id)),
+            friend_id.to_string(),
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if !requested {
+        return Err(AppError::NotFound(
+            "No friend request from this user".into(),
+        ));
+    }
+
+    let _: () = redis::pipe()
+        .atomic()
+        .srem(
+            RedisKey::user_friend_requests_in(KeyPart::Id(user_id)),
+            friend_id.to_string(),
+        )
+        .ignore()
+        .srem(
+            RedisKey::user_friend_requests_out(KeyPart::Id(friend_id)),
+            user_id.to_string(),
+        )
+        .ignore()
+        .sadd(
+            RedisKey::user_friends(KeyPart::Id(user_id)),
+            friend_id.to_string(),
+        )
+        .ignore()
+        .sadd(
+            RedisKey::user_friends(KeyPart::Id(friend_id)),
+            user_id.to_string(),
+        )
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(FriendStatus::Friends)
+}
+
+/// Ends a friendship and drops any pending request in either direction.
+pub async fn remove_friend(
+    user_id: Uuid,
+    friend_id: Uuid,
+    redis: RedisClient,
+) -> Result<FriendStatus, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    for (a, b) in [(user_id, friend_id), (friend_id, user_id)] {
+        pipe.srem(RedisKey::user_friends(KeyPart::Id(a)), b.to_string())
+            .ignore()
+            .srem(
+                RedisKey::user_friend_requests_in(KeyPart::Id(a)),
+                b.to_string(),
+            )
+            .ignore()
+            .srem(
+                RedisKey::user_friend_requests_out(KeyPart::Id(a)),
+                b.to_string(),
+            )
+            .ignore();
+    }
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(FriendStatus::None)
+}
+
+pub async fn are_friends(
+    user_id: Uuid,
+    friend_id: Uuid,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let is_friend: bool = conn
+        .sismember(
+            RedisKey::user_friends(KeyPart::Id(user_id)),
+            friend_id.to_string(),
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(is_friend)
+}
+
+pub async fn get_friend_ids(user_id: Uuid, redis: RedisClient) -> Result<Vec<Uuid>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let ids: Vec<String> = conn
+        .smembers(RedisKey::user_friends(KeyPart::Id(user_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(ids
+        .iter()
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect())
+}
+
+/// Friends, incoming requests and outgoing requests, deleted users left out.
+pub async fn get_friends(
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<(Vec<User>, Vec<User>, Vec<User>), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (friends, incoming, outgoing): (Vec<String>, Vec<String>, Vec<String>) = redis::pipe()
+        .smembers(RedisKey::user_friends(KeyPart::Id(user_id)))
+        .smembers(RedisKey::user_friend_requests_in(KeyPart::Id(user_id)))
+        .smembers(RedisKey::user_friend_requests_out(KeyPart::Id(user_id)))
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok((
+        get_active_users_with_conn(&friends, &mut conn).await?,
+        get_active_users_with_conn(&incoming, &mut conn).await?,
+        get_active_users_with_conn(&outgoing, &mut conn).await?,
+    ))
+}
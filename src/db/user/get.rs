@@ -1,7 +1,10 @@
 use crate::{
+    db::utils::scan_keys,
     errors::AppError,
     models::{
         User,
+        admin::UserError,
+        notification::NotificationPreferences,
         redis::{KeyPart, RedisKey},
     },
     state::RedisClient,
@@ -41,6 +44,9 @@ pub async fn get_user_by_id(user_id: Uuid, redis: RedisClient) -> Result<User, A
             .and_then(|v| v.parse::<f64>().ok())
             .unwrap_or(0.0),
         username: data.get("username").cloned(),
+        avatar_url: data.get("avatar_url").cloned(),
+        bio: data.get("bio").cloned(),
+        deleted: data.get("deleted").is_some_and(|v| v == "true"),
     };
 
     Ok(user)
@@ -73,11 +79,40 @@ pub async fn get_user_by_id_with_conn(
             .and_then(|v| v.parse::<f64>().ok())
             .unwrap_or(0.0),
         username: data.get("username").cloned(),
+        avatar_url: data.get("avatar_url").cloned(),
+        bio: data.get("bio").cloned(),
+        deleted: data.get("deleted").is_some_and(|v| v == "true"),
     };
 
     Ok(user)
 }
 
+/// Loads users by id, skipping ids that are deleted or no longer exist.
+pub async fn get_active_users_with_conn(
+    ids: &[String],
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+) -> Result<Vec<User>, AppError> {
+    let mut users = Vec::with_capacity(ids.len());
+    for id in ids.iter().filter_map(|id| Uuid::parse_str(id).ok()) {
+        match get_user_by_id_with_conn(id, conn).await {
+            Ok(user) if !user.deleted => users.push(user),
+            Ok(_) | Err(AppError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(users)
+}
+
+/// Like `get_user_by_id`, but resolves users that no longer exist to a tombstone
+/// so historical lobbies and standings keep rendering.
+pub async fn get_user_or_tombstone(user_id: Uuid, redis: RedisClient) -> Result<User, AppError> {
+    match get_user_by_id(user_id, redis).await {
+        Err(AppError::NotFound(_)) => Ok(User::tombstone(user_id)),
+        other => other,
+    }
+}
+
 pub async fn get_user_id(identifier: String, redis: RedisClient) -> Result<Uuid, AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
@@ -144,11 +179,7 @@ pub async fn _get_all_users(redis: RedisClient) -> Result<Vec<User>, AppError> {
 
     // Get all user keys
     let pattern = RedisKey::user(KeyPart::Wildcard);
-    let user_keys: Vec<String> = redis::cmd("KEYS")
-        .arg(pattern)
-        .query_async(&mut *conn)
-        .await
-        .map_err(AppError::RedisCommandError)?;
+    let user_keys = scan_keys(&mut conn, &pattern).await?;
 
     let mut users = Vec::new();
 
@@ -162,3 +193,77 @@ pub async fn _get_all_users(redis: RedisClient) -> Result<Vec<User>, AppError> {
 
     Ok(users)
 }
+
+pub async fn get_recent_user_errors(
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<Vec<UserError>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::user_recent_errors(KeyPart::Id(user_id));
+    let raw: Vec<String> = conn
+        .lrange(&key, 0, -1)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    // Newest first, entries that fail to parse are skipped
+    Ok(raw
+        .iter()
+        .filter_map(|entry| serde_json::from_str::<UserError>(entry).ok())
+        .collect())
+}
+
+/// Stacks Wars user linked to a Telegram account, if any.
+pub async fn get_user_id_by_telegram(
+    telegram_user_id: u64,
+    redis: RedisClient,
+) -> Result<Option<Uuid>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let user_id: Option<String> = conn
+        .hget(RedisKey::telegram_links(), telegram_user_id)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(user_id.and_then(|id| Uuid::parse_str(&id).ok()))
+}
+
+pub async fn get_user_telegram_id(
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<Option<u64>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let telegram_id: Option<u64> = conn
+        .hget(RedisKey::user(KeyPart::Id(user_id)), "telegram_id")
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(telegram_id)
+}
+
+pub async fn get_notification_preferences(
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<NotificationPreferences, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let data: HashMap<String, String> = conn
+        .hgetall(RedisKey::user_notification_prefs(KeyPart::Id(user_id)))
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(NotificationPreferences::from_redis_hash(&data))
+}
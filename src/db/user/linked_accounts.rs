@@ -0,0 +1,285 @@
+//! Ties accounts together by where they connect from. Each sign-in and lobby
+//! socket records a hash of the address, and of the address with the user
+//! agent, so accounts sharing one can be reported when they meet in a prize
+//! lobby. Only hashes are kept, and they expire once unused for a while.
+
+use chrono::Utc;
+use redis::AsyncCommands;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+};
+use uuid::Uuid;
+
+use crate::{
+    config,
+    db::{
+        lobby::get::get_lobby_players,
+        user::get::{get_user_by_id, get_user_id},
+    },
+    errors::AppError,
+    models::{
+        admin::{AccountLink, LinkedAccount, MultiAccountReport},
+        game::{LobbyInfo, PlayerState},
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// How long a connection keeps linking accounts after it was last seen
+const LINK_TTL_SECS: i64 = 30 * 24 * 60 * 60;
+/// Number of reports kept for review, oldest are trimmed first
+const REPORTS_MAX_LEN: isize = 1_000;
+
+fn fingerprint(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hex::encode(&hasher.finalize()[..16])
+}
+
+/// Remembers the address and user agent the user connected with, in the
+/// background.
+pub fn record_connection(
+    user_id: Uuid,
+    ip: IpAddr,
+    user_agent: Option<String>,
+    redis: RedisClient,
+) {
+    tokio::spawn(async move {
+        if let Err(e) = store_connection(user_id, ip, user_agent, redis).await {
+            tracing::warn!("Failed to record connection of {}: {}", user_id, e);
+        }
+    });
+}
+
+/// Like `record_connection`, for sign-ins that only know the wallet.
+pub fn record_wallet_connection(
+    wallet_address: String,
+    ip: IpAddr,
+    user_agent: Option<String>,
+    redis: RedisClient,
+) {
+    tokio::spawn(async move {
+        let result = match get_user_id(wallet_address.clone(), redis.clone()).await {
+            Ok(user_id) => store_connection(user_id, ip, user_agent, redis).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to record connection of {}: {}", wallet_address, e);
+        }
+    });
+}
+
+async fn store_connection(
+    user_id: Uuid,
+    ip: IpAddr,
+    user_agent: Option<String>,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let ip = ip.to_canonical().to_string();
+    let network = fingerprint(&[&ip]);
+    let user_networks = RedisKey::user_networks(KeyPart::Id(user_id));
+    let network_users = RedisKey::network_users(KeyPart::Str(network.clone()));
+
+    let mut pipe = redis::pipe();
+    pipe.sadd(&user_networks, &network)
+        .ignore()
+        .expire(&user_networks, LINK_TTL_SECS)
+        .ignore()
+        .sadd(&network_users, user_id.to_string())
+        .ignore()
+        .expire(&network_users, LINK_TTL_SECS)
+        .ignore();
+
+    // Without a user agent there is nothing to tell devices on the network apart
+    if let Some(user_agent) = user_agent {
+        let device = fingerprint(&[&ip, &user_agent]);
+        let user_devices = RedisKey::user_devices(KeyPart::Id(user_id));
+        let device_users = RedisKey::device_users(KeyPart::Str(device.clone()));
+        pipe.sadd(&user_devices, &device)
+            .ignore()
+            .expire(&user_devices, LINK_TTL_SECS)
+            .ignore()
+            .sadd(&device_users, user_id.to_string())
+            .ignore()
+            .expire(&device_users, LINK_TTL_SECS)
+            .ignore();
+    }
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Other accounts seen on the user's devices and networks, the strongest link
+/// for each.
+pub async fn get_linked_accounts(
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<HashMap<Uuid, AccountLink>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let (devices, networks): (Vec<String>, Vec<String>) = redis::pipe()
+        .smembers(RedisKey::user_devices(KeyPart::Id(user_id)))
+        .smembers(RedisKey::user_networks(KeyPart::Id(user_id)))
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let device_keys: Vec<String> = devices
+        .into_iter()
+        .map(|device| RedisKey::device_users(KeyPart::Str(device)))
+        .collect();
+    let network_keys: Vec<String> = networks
+        .into_iter()
+        .map(|network| RedisKey::network_users(KeyPart::Str(network)))
+        .collect();
+
+    let mut linked = HashMap::new();
+    // Devices go first so they win over the network they are on
+    for (keys, link) in [
+        (device_keys, AccountLink::SameDevice),
+        (network_keys, AccountLink::SameNetwork),
+    ] {
+        if keys.is_empty() {
+            continue;
+        }
+        let users: HashSet<String> = conn
+            .sunion(&keys)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+        for other_id in users.iter().filter_map(|id| Uuid::parse_str(id).ok()) {
+            if other_id != user_id {
+                linked.entry(other_id).or_insert(link);
+            }
+        }
+    }
+
+    Ok(linked)
+}
+
+/// Looks for accounts linked to the user among the players already in a
+/// prize lobby. A report is recorded when there are any, and `Err` is
+/// returned when `BLOCK_LINKED_ACCOUNTS` refuses the join.
+pub async fn check_prize_lobby_join(
+    lobby: &LobbyInfo,
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    if lobby.entry_amount.is_none() {
+        return Ok(());
+    }
+
+    let linked = get_linked_accounts(user_id, redis.clone()).await?;
+    if linked.is_empty() {
+        return Ok(());
+    }
+
+    let players = get_lobby_players(lobby.id, Some(PlayerState::Joined), redis.clone()).await?;
+    let linked: Vec<LinkedAccount> = players
+        .into_iter()
+        .filter_map(|player| {
+            let link = *linked.get(&player.id)?;
+            Some(LinkedAccount {
+                user_id: player.id,
+                wallet_address: player.user?.wallet_address,
+                link,
+            })
+        })
+        .collect();
+    if linked.is_empty() {
+        return Ok(());
+    }
+
+    // Shared networks are too common to refuse anyone over
+    let blocked = config::get().block_linked_accounts
+        && linked
+            .iter()
+            .any(|account| account.link == AccountLink::SameDevice);
+
+    let wallet_address = get_user_by_id(user_id, redis.clone()).await?.wallet_address;
+    let report = MultiAccountReport {
+        lobby_id: lobby.id,
+        user_id,
+        wallet_address,
+        linked,
+        blocked,
+        timestamp: Utc::now(),
+    };
+    tracing::warn!(
+        "User {} joining lobby {} looks linked to {} players",
+        user_id,
+        lobby.id,
+        report.linked.len()
+    );
+    record_multi_account_report(&report, redis).await?;
+
+    if blocked {
+        return Err(AppError::BadRequest(
+            "This account is linked to a player already in the lobby".into(),
+        ));
+    }
+    Ok(())
+}
+
+async fn record_multi_account_report(
+    report: &MultiAccountReport,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::multi_account_reports();
+    let serialized = serde_json::to_string(report).map_err(|e| {
+        AppError::Serialization(format!("Failed to serialize multi-account report: {}", e))
+    })?;
+
+    let _: () = redis::pipe()
+        .lpush(&key, serialized)
+        .ignore()
+        .ltrim(&key, 0, REPORTS_MAX_LEN - 1)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Most recent reports first.
+pub async fn get_multi_account_reports(
+    limit: usize,
+    redis: RedisClient,
+) -> Result<Vec<MultiAccountReport>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let entries: Vec<String> = conn
+        .lrange(RedisKey::multi_account_reports(), 0, limit as isize - 1)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| serde_json::from_str(entry).ok())
+        .collect())
+}
@@ -1,3 +1,10 @@
+pub mod block;
+pub mod cache;
+pub mod delete;
+pub mod friends;
 pub mod get;
+pub mod linked_accounts;
 pub mod patch;
 pub mod post;
+pub mod presence;
+pub mod session;
@@ -1,6 +1,13 @@
 use crate::{
+    db::user::{cache::invalidate_user, get::get_user_by_id},
     errors::AppError,
-    models::redis::{KeyPart, RedisKey},
+    models::{
+        User,
+        notification::NotificationPreferences,
+        redis::{KeyPart, RedisKey},
+    },
+    moderation::check_profile_text,
+    notifications::webhook::validate_url,
     state::RedisClient,
 };
 use redis::AsyncCommands;
@@ -15,6 +22,30 @@ fn is_valid_username(username: &str) -> bool {
     len_ok && valid_chars
 }
 
+const MAX_AVATAR_URL_LEN: usize = 512;
+const MAX_BIO_LEN: usize = 160;
+
+async fn validate_display_name(display_name: &str, redis: RedisClient) -> Result<&str, AppError> {
+    let trimmed = display_name.trim();
+    if trimmed.is_empty() || trimmed.len() > 50 {
+        return Err(AppError::BadRequest("Invalid display name".into()));
+    }
+    check_profile_text(trimmed, "Display name", redis).await?;
+
+    Ok(trimmed)
+}
+
+/// Avatars are only ever rendered as images, so only https links are accepted.
+fn validate_avatar_url(avatar_url: &str) -> Result<(), AppError> {
+    let valid = avatar_url.len() <= MAX_AVATAR_URL_LEN
+        && reqwest::Url::parse(avatar_url).is_ok_and(|url| url.scheme() == "https");
+    if !valid {
+        return Err(AppError::BadRequest("Invalid avatar URL".into()));
+    }
+
+    Ok(())
+}
+
 pub async fn update_username(
     user_id: Uuid,
     new_username: String,
@@ -30,6 +61,7 @@ pub async fn update_username(
     if !is_valid_username(&normalized) {
         return Err(AppError::BadRequest("Invalid username".into()));
     }
+    check_profile_text(&normalized, "Username", redis.clone()).await?;
 
     // Get the user's current username, if any
     let user_key = RedisKey::user(KeyPart::Id(user_id));
@@ -82,6 +114,7 @@ pub async fn update_username(
         .hset(&usernames_hash, &normalized, user_id.to_string())
         .await
         .map_err(AppError::RedisCommandError)?;
+    invalidate_user(user_id);
 
     Ok(new_username)
 }
@@ -96,10 +129,7 @@ pub async fn update_display_name(
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
     })?;
 
-    let trimmed = new_display_name.trim();
-    if trimmed.is_empty() || trimmed.len() > 50 {
-        return Err(AppError::BadRequest("Invalid display name".into()));
-    }
+    let trimmed = validate_display_name(&new_display_name, redis.clone()).await?;
 
     let user_key = RedisKey::user(KeyPart::Id(user_id));
 
@@ -107,10 +137,81 @@ pub async fn update_display_name(
         .hset(&user_key, "display_name", trimmed)
         .await
         .map_err(AppError::RedisCommandError)?;
+    invalidate_user(user_id);
 
     Ok(())
 }
 
+/// Applies the profile fields that were sent. An empty avatar URL or bio
+/// clears the field.
+pub async fn update_profile(
+    user_id: Uuid,
+    display_name: Option<String>,
+    avatar_url: Option<String>,
+    bio: Option<String>,
+    redis: RedisClient,
+) -> Result<User, AppError> {
+    let mut set_fields = Vec::new();
+    let mut cleared_fields = Vec::new();
+
+    if let Some(display_name) = &display_name {
+        let trimmed = validate_display_name(display_name, redis.clone()).await?;
+        set_fields.push(("display_name", trimmed.to_string()));
+    }
+
+    if let Some(avatar_url) = &avatar_url {
+        let trimmed = avatar_url.trim();
+        if trimmed.is_empty() {
+            cleared_fields.push("avatar_url");
+        } else {
+            validate_avatar_url(trimmed)?;
+            set_fields.push(("avatar_url", trimmed.to_string()));
+        }
+    }
+
+    if let Some(bio) = &bio {
+        let trimmed = bio.trim();
+        if trimmed.is_empty() {
+            cleared_fields.push("bio");
+        } else {
+            if trimmed.chars().count() > MAX_BIO_LEN {
+                return Err(AppError::BadRequest(format!(
+                    "Bio must be at most {MAX_BIO_LEN} characters"
+                )));
+            }
+            check_profile_text(trimmed, "Bio", redis.clone()).await?;
+            set_fields.push(("bio", trimmed.to_string()));
+        }
+    }
+
+    if !set_fields.is_empty() || !cleared_fields.is_empty() {
+        let mut conn = redis.get().await.map_err(|e| match e {
+            bb8::RunError::User(err) => AppError::RedisCommandError(err),
+            bb8::RunError::TimedOut => {
+                AppError::RedisPoolError("Redis connection timed out".into())
+            }
+        })?;
+
+        let user_key = RedisKey::user(KeyPart::Id(user_id));
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        if !set_fields.is_empty() {
+            pipe.hset_multiple(&user_key, &set_fields).ignore();
+        }
+        if !cleared_fields.is_empty() {
+            pipe.hdel(&user_key, &cleared_fields).ignore();
+        }
+
+        let _: () = pipe
+            .query_async(&mut *conn)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+        invalidate_user(user_id);
+    }
+
+    get_user_by_id(user_id, redis).await
+}
+
 pub async fn _increase_wars_point(
     user_id: Uuid,
     amount: f64,
@@ -141,6 +242,7 @@ pub async fn _increase_wars_point(
         .await
         .map_err(AppError::RedisCommandError)?;
     let new_total = results.0;
+    invalidate_user(user_id);
 
     Ok(new_total)
 }
@@ -175,6 +277,35 @@ pub async fn decrease_wars_point(
         .await
         .map_err(AppError::RedisCommandError)?;
     let new_total = results.0;
+    invalidate_user(user_id);
 
     Ok(new_total)
 }
+
+pub async fn update_notification_preferences(
+    user_id: Uuid,
+    prefs: NotificationPreferences,
+    redis: RedisClient,
+) -> Result<NotificationPreferences, AppError> {
+    if let Some(url) = &prefs.webhook_url {
+        validate_url(url).await?;
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::user_notification_prefs(KeyPart::Id(user_id));
+    let _: () = redis::pipe()
+        .atomic()
+        .del(&key)
+        .ignore()
+        .hset_multiple(&key, &prefs.to_redis_hash())
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(prefs)
+}
@@ -2,18 +2,27 @@ use std::collections::HashMap;
 
 use crate::{
     auth::generate_jwt,
-    db::user::get::_get_all_users,
+    db::user::{get::_get_all_users, session::create_session},
     errors::AppError,
     models::{
         User,
+        admin::UserError,
         redis::{KeyPart, RedisKey},
+        user::LoginChallenge,
     },
     state::RedisClient,
 };
+use chrono::{Duration, Utc};
 use redis::AsyncCommands;
 use uuid::Uuid;
 
-pub async fn create_user(wallet_address: String, redis: RedisClient) -> Result<String, AppError> {
+/// Signs the wallet's user in, creating them on first sign-in, and returns a
+/// token for a new session on `device`.
+pub async fn create_user(
+    wallet_address: String,
+    device: Option<String>,
+    redis: RedisClient,
+) -> Result<String, AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
         bb8::RunError::User(err) => AppError::RedisCommandError(err),
         bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
@@ -48,13 +57,17 @@ pub async fn create_user(wallet_address: String, redis: RedisClient) -> Result<S
             wallet_address: user_data.get("wallet_address").cloned().unwrap_or_default(),
             username: user_data.get("username").cloned(),
             display_name: user_data.get("display_name").cloned(),
+            avatar_url: user_data.get("avatar_url").cloned(),
+            bio: user_data.get("bio").cloned(),
             wars_point: user_data
                 .get("wars_point")
                 .and_then(|p| p.parse().ok())
                 .unwrap_or(0.0),
+            deleted: false,
         };
 
-        let token = generate_jwt(&user)?;
+        let session_id = create_session(user.id, device, redis.clone()).await?;
+        let token = generate_jwt(&user, session_id)?;
         return Ok(token);
     }
 
@@ -64,7 +77,10 @@ pub async fn create_user(wallet_address: String, redis: RedisClient) -> Result<S
         wallet_address: wallet_address.clone(),
         display_name: None,
         username: None,
+        avatar_url: None,
+        bio: None,
         wars_point: 0.0, // Initialize with 0 wars points
+        deleted: false,
     };
 
     let user_key = RedisKey::user(KeyPart::Id(user.id));
@@ -97,7 +113,8 @@ pub async fn create_user(wallet_address: String, redis: RedisClient) -> Result<S
         .await
         .map_err(AppError::RedisCommandError)?;
 
-    let token = generate_jwt(&user)?;
+    let session_id = create_session(user.id, device, redis.clone()).await?;
+    let token = generate_jwt(&user, session_id)?;
     Ok(token)
 }
 
@@ -149,3 +166,204 @@ pub async fn _hydrate_users_points(redis: RedisClient) -> Result<(), AppError> {
 
     Ok(())
 }
+
+/// Number of recent errors kept per user for the support view
+const RECENT_ERRORS_MAX_LEN: isize = 20;
+const RECENT_ERRORS_TTL_SECS: i64 = 7 * 24 * 60 * 60;
+
+pub async fn record_user_error(
+    user_id: Uuid,
+    lobby_id: Uuid,
+    message: String,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::user_recent_errors(KeyPart::Id(user_id));
+    let error = UserError {
+        lobby_id,
+        message,
+        timestamp: chrono::Utc::now(),
+    };
+    let serialized = serde_json::to_string(&error)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize user error: {}", e)))?;
+
+    let _: () = redis::pipe()
+        .lpush(&key, serialized)
+        .ignore()
+        .ltrim(&key, 0, RECENT_ERRORS_MAX_LEN - 1)
+        .ignore()
+        .expire(&key, RECENT_ERRORS_TTL_SECS)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// How long a sign-in challenge waits for its signature
+const LOGIN_CHALLENGE_TTL_SECS: u64 = 5 * 60;
+
+/// A one-time message for the wallet to sign before it gets a token.
+pub async fn create_login_challenge(
+    wallet_address: String,
+    redis: RedisClient,
+) -> Result<LoginChallenge, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let nonce = Uuid::new_v4().simple().to_string();
+    let issued_at = Utc::now();
+    let challenge = LoginChallenge {
+        message: format!(
+            "Sign in to Stacks Wars\n\nWallet: {}\nNonce: {}\nIssued At: {}",
+            wallet_address,
+            nonce,
+            issued_at.to_rfc3339()
+        ),
+        nonce,
+        wallet_address,
+        expires_at: issued_at + Duration::seconds(LOGIN_CHALLENGE_TTL_SECS as i64),
+    };
+
+    let json = serde_json::to_string(&challenge)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize challenge: {}", e)))?;
+    let _: () = conn
+        .set_ex(
+            RedisKey::login_challenge(KeyPart::Str(challenge.nonce.clone())),
+            json,
+            LOGIN_CHALLENGE_TTL_SECS,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(challenge)
+}
+
+/// Redeems a challenge, so each signature signs in once.
+pub async fn take_login_challenge(
+    nonce: &str,
+    redis: RedisClient,
+) -> Result<LoginChallenge, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let json: Option<String> = redis::cmd("GETDEL")
+        .arg(RedisKey::login_challenge(KeyPart::Str(nonce.to_string())))
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let json = json.ok_or_else(|| AppError::Unauthorized("Invalid or expired challenge".into()))?;
+    serde_json::from_str(&json)
+        .map_err(|e| AppError::Deserialization(format!("Failed to parse challenge: {}", e)))
+}
+
+/// How long a Telegram link code can be redeemed with `/link`
+const TELEGRAM_LINK_CODE_TTL_SECS: u64 = 10 * 60;
+
+/// One-time code the user sends to the bot as `/link <code>`.
+pub async fn create_telegram_link_code(
+    user_id: Uuid,
+    redis: RedisClient,
+) -> Result<String, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let code = Uuid::new_v4().simple().to_string()[..8].to_uppercase();
+
+    let _: () = conn
+        .set_ex(
+            RedisKey::telegram_link_code(KeyPart::Str(code.clone())),
+            user_id.to_string(),
+            TELEGRAM_LINK_CODE_TTL_SECS,
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(code)
+}
+
+/// Redeems a link code, replacing any previous link on either side.
+pub async fn link_telegram_account(
+    code: &str,
+    telegram_user_id: u64,
+    redis: RedisClient,
+) -> Result<Uuid, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let code_key = RedisKey::telegram_link_code(KeyPart::Str(code.trim().to_uppercase()));
+    let user_id: Option<String> = redis::cmd("GETDEL")
+        .arg(&code_key)
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let user_id = user_id
+        .and_then(|id| Uuid::parse_str(&id).ok())
+        .ok_or_else(|| AppError::BadRequest("Invalid or expired link code".into()))?;
+
+    let user_key = RedisKey::user(KeyPart::Id(user_id));
+    let links_key = RedisKey::telegram_links();
+
+    let (deleted, previous_telegram_id): (Option<String>, Option<u64>) = redis::pipe()
+        .hget(&user_key, "deleted")
+        .hget(&user_key, "telegram_id")
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if deleted.as_deref() == Some("true") {
+        return Err(AppError::Unauthorized(
+            "This account has been deleted".into(),
+        ));
+    }
+
+    let previous_user_id: Option<String> = conn
+        .hget(&links_key, telegram_user_id)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+
+    if let Some(old_telegram_id) = previous_telegram_id {
+        pipe.hdel(&links_key, old_telegram_id).ignore();
+    }
+    if let Some(old_user_id) = previous_user_id.and_then(|id| Uuid::parse_str(&id).ok())
+        && old_user_id != user_id
+    {
+        pipe.hdel(RedisKey::user(KeyPart::Id(old_user_id)), "telegram_id")
+            .ignore();
+    }
+
+    pipe.hset(&links_key, telegram_user_id, user_id.to_string())
+        .ignore()
+        .hset(&user_key, "telegram_id", telegram_user_id)
+        .ignore();
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    tracing::info!(
+        "Linked telegram account {} to user {}",
+        telegram_user_id,
+        user_id
+    );
+    Ok(user_id)
+}
@@ -0,0 +1,153 @@
+use chrono::Utc;
+use redis::{AsyncCommands, Script};
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        presence::UserStatus,
+        redis::{KeyPart, RedisKey},
+    },
+    state::RedisClient,
+};
+
+/// How long an instance's word that a user is connected lasts without a heartbeat
+pub const PRESENCE_TTL_SECS: u64 = 30;
+
+/// Stores the status friends were last told about, dropping it when the user
+/// went offline. Returns 1 when it differs from the stored one.
+static SWAP_STATUS: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if ARGV[2] == '' then
+            return redis.call('HDEL', KEYS[1], ARGV[1])
+        end
+        if redis.call('HGET', KEYS[1], ARGV[1]) == ARGV[2] then
+            return 0
+        end
+        redis.call('HSET', KEYS[1], ARGV[1], ARGV[2])
+        return 1
+        ",
+    )
+});
+
+fn expires_at() -> i64 {
+    Utc::now().timestamp_millis() + (PRESENCE_TTL_SECS * 1000) as i64
+}
+
+/// Records whether `instance` holds a socket of the user. Each instance keeps
+/// its own entry, so the user is online while any instance has one.
+pub async fn set_user_connected(
+    user_id: Uuid,
+    instance: Uuid,
+    connected: bool,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::user_presence(KeyPart::Id(user_id));
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    if connected {
+        pipe.zadd(&key, instance.to_string(), expires_at())
+            .ignore()
+            .expire(&key, PRESENCE_TTL_SECS as i64)
+            .ignore();
+    } else {
+        pipe.zrem(&key, instance.to_string()).ignore();
+    }
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Keeps the entries of the users connected to `instance` from running out.
+pub async fn renew_user_presence(
+    user_ids: &[Uuid],
+    instance: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    if user_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let expires_at = expires_at();
+    let mut pipe = redis::pipe();
+    for user_id in user_ids {
+        let key = RedisKey::user_presence(KeyPart::Id(*user_id));
+        pipe.zadd(&key, instance.to_string(), expires_at)
+            .ignore()
+            .expire(&key, PRESENCE_TTL_SECS as i64)
+            .ignore();
+    }
+
+    let _: () = pipe
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// True while some live instance holds a socket of the user.
+pub async fn is_user_connected(user_id: Uuid, redis: RedisClient) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    // Entries of instances that died stop counting once they run out
+    let live: usize = conn
+        .zcount(
+            RedisKey::user_presence(KeyPart::Id(user_id)),
+            Utc::now().timestamp_millis(),
+            "+inf",
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(live > 0)
+}
+
+/// Stores the status friends are about to be told about, returning false
+/// when they were already told.
+pub async fn swap_user_status(
+    user_id: Uuid,
+    status: UserStatus,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    let status = match status {
+        UserStatus::Offline => String::new(),
+        status => {
+            serde_json::to_string(&status).map_err(|e| AppError::Serialization(e.to_string()))?
+        }
+    };
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let changed: i64 = SWAP_STATUS
+        .key(RedisKey::users_status())
+        .arg(user_id.to_string())
+        .arg(status)
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(changed == 1)
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Duration, Utc};
+use redis::Script;
+use std::{collections::HashMap, sync::LazyLock};
+use uuid::Uuid;
+
+use crate::{
+    auth::TOKEN_TTL_DAYS,
+    errors::AppError,
+    models::{
+        redis::{KeyPart, RedisKey},
+        user::UserSession,
+    },
+    state::RedisClient,
+};
+
+/// Longest user agent kept as a session's device
+const MAX_DEVICE_LEN: usize = 256;
+
+/// Records the session as used now, unless it was revoked. Returns 1 when it is live.
+static TOUCH_SESSION: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('HEXISTS', KEYS[1], ARGV[1]) == 0 then
+            return 0
+        end
+        redis.call('HSET', KEYS[2], ARGV[1], ARGV[2])
+        return 1
+        ",
+    )
+});
+
+/// Starts a session for a token about to be issued to the user.
+pub async fn create_session(
+    user_id: Uuid,
+    device: Option<String>,
+    redis: RedisClient,
+) -> Result<Uuid, AppError> {
+    let now = Utc::now();
+    let session = UserSession {
+        id: Uuid::new_v4(),
+        device: device.map(|device| device.chars().take(MAX_DEVICE_LEN).collect()),
+        created_at: now,
+        last_active_at: now,
+        current: false,
+    };
+    let json = serde_json::to_string(&session)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize session: {}", e)))?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let sessions_key = RedisKey::user_sessions(KeyPart::Id(user_id));
+    let activity_key = RedisKey::user_session_activity(KeyPart::Id(user_id));
+    let session_id = session.id.to_string();
+    // The newest session outlives every older one, so both hashes go with it
+    let ttl = Duration::days(TOKEN_TTL_DAYS).num_seconds();
+
+    let _: () = redis::pipe()
+        .atomic()
+        .hset(&sessions_key, &session_id, json)
+        .ignore()
+        .hset(&activity_key, &session_id, now.timestamp())
+        .ignore()
+        .expire(&sessions_key, ttl)
+        .ignore()
+        .expire(&activity_key, ttl)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(session.id)
+}
+
+/// Marks the session as used, returning false when it no longer exists.
+pub async fn touch_session(
+    user_id: Uuid,
+    session_id: Uuid,
+    redis: RedisClient,
+) -> Result<bool, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let live: i32 = TOUCH_SESSION
+        .key(RedisKey::user_sessions(KeyPart::Id(user_id)))
+        .key(RedisKey::user_session_activity(KeyPart::Id(user_id)))
+        .arg(session_id.to_string())
+        .arg(Utc::now().timestamp())
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(live == 1)
+}
+
+/// The user's live sessions, most recently used first. Sessions whose
+/// tokens have expired are dropped on the way.
+pub async fn get_sessions(user_id: Uuid, redis: RedisClient) -> Result<Vec<UserSession>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let sessions_key = RedisKey::user_sessions(KeyPart::Id(user_id));
+    let activity_key = RedisKey::user_session_activity(KeyPart::Id(user_id));
+    let (stored, activity): (HashMap<String, String>, HashMap<String, i64>) = redis::pipe()
+        .hgetall(&sessions_key)
+        .hgetall(&activity_key)
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let expired_before = Utc::now() - Duration::days(TOKEN_TTL_DAYS);
+    let mut sessions = Vec::new();
+    let mut stale = Vec::new();
+    for (id, json) in stored {
+        match serde_json::from_str::<UserSession>(&json) {
+            Ok(session) if session.created_at > expired_before => {
+                let last_active_at = activity
+                    .get(&id)
+                    .and_then(|ts| DateTime::from_timestamp(*ts, 0))
+                    .unwrap_or(session.last_active_at);
+                sessions.push(UserSession {
+                    last_active_at,
+                    ..session
+                });
+            }
+            Ok(_) => stale.push(id),
+            Err(e) => {
+                tracing::warn!("Dropping malformed session {} of {}: {}", id, user_id, e);
+                stale.push(id);
+            }
+        }
+    }
+
+    if !stale.is_empty() {
+        let _: () = redis::pipe()
+            .hdel(&sessions_key, &stale)
+            .ignore()
+            .hdel(&activity_key, &stale)
+            .ignore()
+            .query_async(&mut *conn)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+    }
+
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.last_active_at));
+    Ok(sessions)
+}
+
+/// Ends one of the user's sessions; its token stops working right away.
+pub async fn revoke_session(
+    user_id: Uuid,
+    session_id: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let session_id = session_id.to_string();
+    let (removed, _): (u32, u32) = redis::pipe()
+        .atomic()
+        .hdel(RedisKey::user_sessions(KeyPart::Id(user_id)), &session_id)
+        .hdel(
+            RedisKey::user_session_activity(KeyPart::Id(user_id)),
+            &session_id,
+        )
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if removed == 0 {
+        return Err(AppError::NotFound("Session not found".into()));
+    }
+    Ok(())
+}
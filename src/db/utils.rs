@@ -0,0 +1,91 @@
+use bb8::PooledConnection;
+use bb8_redis::RedisConnectionManager;
+use redis::AsyncCommands;
+
+use crate::{errors::AppError, models::pagination::PageCursor};
+
+/// Collects every key matching `pattern` with incremental SCAN so Redis is
+/// never blocked the way `KEYS` blocks it. Meant for migrations and admin
+/// tooling, hot paths should read an index set instead.
+pub async fn scan_keys(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    pattern: &str,
+) -> Result<Vec<String>, AppError> {
+    let mut keys = Vec::new();
+    let mut cursor: u64 = 0;
+
+    loop {
+        let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(500)
+            .query_async(&mut **conn)
+            .await
+            .map_err(AppError::RedisCommandError)?;
+
+        keys.extend(batch);
+        if next == 0 {
+            break;
+        }
+        cursor = next;
+    }
+
+    Ok(keys)
+}
+
+/// Where a page of `key`, read highest score first, starts: right after the
+/// cursor's item, or after everything scored above it once the item is gone.
+/// Without a cursor the page starts at `offset`.
+pub async fn zrevrange_offset(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    key: &str,
+    offset: usize,
+    after: Option<&PageCursor>,
+) -> Result<usize, AppError> {
+    let Some(cursor) = after else {
+        return Ok(offset);
+    };
+
+    let rank: Option<usize> = conn
+        .zrevrank(key, &cursor.id)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    if let Some(rank) = rank {
+        return Ok(rank + 1);
+    }
+
+    let above: usize = conn
+        .zcount(key, format!("({}", cursor.score), "+inf")
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    Ok(above)
+}
+
+/// One page of `key`, highest score first, with where it starts and the
+/// cursor after its last member.
+pub async fn zrevrange_page(
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+    key: &str,
+    offset: usize,
+    limit: u32,
+    after: Option<&PageCursor>,
+) -> Result<(Vec<String>, usize, Option<PageCursor>), AppError> {
+    let offset = zrevrange_offset(conn, key, offset, after).await?;
+    let end = offset.saturating_add(limit as usize).saturating_sub(1);
+
+    let members: Vec<(String, f64)> = conn
+        .zrevrange_withscores(key, offset as isize, end.min(isize::MAX as usize) as isize)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    let last = members
+        .last()
+        .map(|(member, score)| PageCursor::new(member, *score));
+
+    Ok((
+        members.into_iter().map(|(member, _)| member).collect(),
+        offset,
+        last,
+    ))
+}
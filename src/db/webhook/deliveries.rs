@@ -0,0 +1,123 @@
+use redis::Script;
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{redis::RedisKey, webhook::WebhookDelivery},
+    state::RedisClient,
+};
+
+/// Leases up to ARGV[3] deliveries due by ARGV[1] until ARGV[2], so no other
+/// instance picks them up meanwhile, and returns their json. A worker that
+/// dies mid-delivery leaves it to be retried once the lease runs out.
+static CLAIM_DUE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local ids = redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1], 'LIMIT', 0, ARGV[3])
+        local claimed = {}
+        for _, id in ipairs(ids) do
+            local json = redis.call('HGET', KEYS[2], id)
+            if json then
+                redis.call('ZADD', KEYS[1], ARGV[2], id)
+                table.insert(claimed, json)
+            else
+                redis.call('ZREM', KEYS[1], id)
+            end
+        end
+        return claimed
+        ",
+    )
+});
+
+/// Stores the delivery and schedules its next attempt at `due_at_ms`.
+pub async fn schedule_delivery(
+    delivery: &WebhookDelivery,
+    due_at_ms: i64,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let json = serde_json::to_string(delivery).map_err(|e| {
+        AppError::Serialization(format!("Failed to serialize webhook delivery: {}", e))
+    })?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = redis::pipe()
+        .atomic()
+        .hset(
+            RedisKey::webhook_deliveries(),
+            delivery.id.to_string(),
+            json,
+        )
+        .ignore()
+        .zadd(
+            RedisKey::webhook_deliveries_due(),
+            delivery.id.to_string(),
+            due_at_ms,
+        )
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
+
+/// Deliveries due by `now_ms`, leased to the caller until `lease_until_ms`.
+pub async fn claim_due_deliveries(
+    now_ms: i64,
+    lease_until_ms: i64,
+    limit: usize,
+    redis: RedisClient,
+) -> Result<Vec<WebhookDelivery>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let claimed: Vec<String> = CLAIM_DUE
+        .key(RedisKey::webhook_deliveries_due())
+        .key(RedisKey::webhook_deliveries())
+        .arg(now_ms)
+        .arg(lease_until_ms)
+        .arg(limit)
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(claimed
+        .into_iter()
+        .filter_map(
+            |json| match serde_json::from_str::<WebhookDelivery>(&json) {
+                Ok(delivery) => Some(delivery),
+                Err(e) => {
+                    tracing::warn!("Skipping malformed webhook delivery: {}", e);
+                    None
+                }
+            },
+        )
+        .collect())
+}
+
+/// Forgets a delivery once it went through or was given up on.
+pub async fn remove_delivery(delivery_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = redis::pipe()
+        .atomic()
+        .hdel(RedisKey::webhook_deliveries(), delivery_id.to_string())
+        .ignore()
+        .zrem(RedisKey::webhook_deliveries_due(), delivery_id.to_string())
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(())
+}
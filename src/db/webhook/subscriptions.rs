@@ -0,0 +1,228 @@
+use chrono::Utc;
+use redis::{AsyncCommands, Script};
+use std::{collections::HashMap, sync::LazyLock};
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::{
+        redis::RedisKey,
+        webhook::{SignedWebhook, Webhook, WebhookEvent},
+    },
+    state::RedisClient,
+};
+
+const MAX_DESCRIPTION_LEN: usize = 200;
+
+/// Replaces a webhook only while it still exists, so an update racing a
+/// delete can't bring it back. Returns 1 when it was replaced.
+static REPLACE_WEBHOOK: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        if redis.call('HEXISTS', KEYS[1], ARGV[1]) == 0 then
+            return 0
+        end
+        redis.call('HSET', KEYS[1], ARGV[1], ARGV[2])
+        return 1
+        ",
+    )
+});
+
+/// Changes to a webhook, fields left out stay as they are
+#[derive(Debug)]
+pub struct WebhookUpdate {
+    pub url: Option<String>,
+    pub events: Option<Vec<WebhookEvent>>,
+    pub description: Option<String>,
+    pub active: Option<bool>,
+}
+
+fn validate_url(url: &str) -> Result<(), AppError> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| AppError::BadRequest(format!("Invalid webhook url: {}", e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(AppError::BadRequest(
+            "Webhook url must be http or https".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Drops repeats, keeping the first occurrence of each event.
+fn validate_events(events: Vec<WebhookEvent>) -> Result<Vec<WebhookEvent>, AppError> {
+    let mut unique = Vec::with_capacity(events.len());
+    for event in events {
+        if !unique.contains(&event) {
+            unique.push(event);
+        }
+    }
+    if unique.is_empty() {
+        return Err(AppError::BadRequest(
+            "A webhook needs at least one event".into(),
+        ));
+    }
+    Ok(unique)
+}
+
+fn validate_description(description: Option<String>) -> Result<Option<String>, AppError> {
+    let description = description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    if description
+        .as_ref()
+        .is_some_and(|d| d.chars().count() > MAX_DESCRIPTION_LEN)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Description can be at most {MAX_DESCRIPTION_LEN} characters"
+        )));
+    }
+    Ok(description)
+}
+
+/// Registers a webhook with a freshly generated signing secret.
+pub async fn create_webhook(
+    url: String,
+    events: Vec<WebhookEvent>,
+    description: Option<String>,
+    redis: RedisClient,
+) -> Result<SignedWebhook, AppError> {
+    validate_url(&url)?;
+    let signed = SignedWebhook {
+        webhook: Webhook {
+            id: Uuid::new_v4(),
+            url,
+            events: validate_events(events)?,
+            description: validate_description(description)?,
+            active: true,
+            created_at: Utc::now(),
+        },
+        secret: hex::encode(rand::random::<[u8; 32]>()),
+    };
+
+    let json = serde_json::to_string(&signed)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize webhook: {}", e)))?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let _: () = conn
+        .hset(RedisKey::webhooks(), signed.webhook.id.to_string(), json)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(signed)
+}
+
+/// Every webhook with its secret, oldest first.
+pub async fn get_webhooks(redis: RedisClient) -> Result<Vec<SignedWebhook>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let stored: HashMap<String, String> = conn
+        .hgetall(RedisKey::webhooks())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let mut webhooks = stored
+        .into_values()
+        .map(|json| {
+            serde_json::from_str::<SignedWebhook>(&json).map_err(|e| {
+                AppError::Deserialization(format!("Failed to deserialize webhook: {}", e))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    webhooks.sort_by_key(|signed| signed.webhook.created_at);
+
+    Ok(webhooks)
+}
+
+pub async fn get_webhook(webhook_id: Uuid, redis: RedisClient) -> Result<SignedWebhook, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let json: Option<String> = conn
+        .hget(RedisKey::webhooks(), webhook_id.to_string())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+    let json =
+        json.ok_or_else(|| AppError::NotFound(format!("Webhook {} not found", webhook_id)))?;
+
+    serde_json::from_str(&json)
+        .map_err(|e| AppError::Deserialization(format!("Failed to deserialize webhook: {}", e)))
+}
+
+pub async fn update_webhook(
+    webhook_id: Uuid,
+    update: WebhookUpdate,
+    redis: RedisClient,
+) -> Result<Webhook, AppError> {
+    let mut signed = get_webhook(webhook_id, redis.clone()).await?;
+    let webhook = &mut signed.webhook;
+    if let Some(url) = update.url {
+        validate_url(&url)?;
+        webhook.url = url;
+    }
+    if let Some(events) = update.events {
+        webhook.events = validate_events(events)?;
+    }
+    if update.description.is_some() {
+        webhook.description = validate_description(update.description)?;
+    }
+    if let Some(active) = update.active {
+        webhook.active = active;
+    }
+
+    let json = serde_json::to_string(&signed)
+        .map_err(|e| AppError::Serialization(format!("Failed to serialize webhook: {}", e)))?;
+
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let replaced: i32 = REPLACE_WEBHOOK
+        .key(RedisKey::webhooks())
+        .arg(webhook_id.to_string())
+        .arg(json)
+        .invoke_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if replaced == 0 {
+        return Err(AppError::NotFound(format!(
+            "Webhook {} not found",
+            webhook_id
+        )));
+    }
+
+    Ok(signed.webhook)
+}
+
+/// Removes a webhook. Deliveries still queued for it are dropped when they
+/// come due.
+pub async fn delete_webhook(webhook_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let removed: usize = conn
+        .hdel(RedisKey::webhooks(), webhook_id.to_string())
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    if removed == 0 {
+        return Err(AppError::NotFound(format!(
+            "Webhook {} not found",
+            webhook_id
+        )));
+    }
+
+    Ok(())
+}
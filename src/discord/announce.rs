@@ -0,0 +1,108 @@
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        lobby::get::{get_lobby_info, get_lobby_player},
+        user::get::get_user_by_id,
+    },
+    discord::{escape_markdown, player_name, post_message},
+    errors::AppError,
+    events::GameEvent,
+    state::RedisClient,
+};
+
+/// Posts new lobbies and winners to the Discord announcement channel.
+pub async fn announce(event: GameEvent, redis: RedisClient) {
+    let result = match event {
+        GameEvent::LobbyCreated { lobby_id, .. } => announce_lobby(lobby_id, redis).await,
+        GameEvent::GameEnded {
+            lobby_id,
+            winner_id: Some(winner_id),
+        } => announce_winner(lobby_id, winner_id, redis).await,
+        _ => Ok(()),
+    };
+
+    if let Err(e) = result {
+        tracing::error!("Failed to post Discord announcement: {}", e);
+    }
+}
+
+async fn announce_lobby(lobby_id: Uuid, redis: RedisClient) -> Result<(), AppError> {
+    let lobby_info = get_lobby_info(lobby_id, redis).await?;
+
+    let mut content = format!(
+        "🆕 **New Lobby Created**\n\n\
+        🏷 **Lobby Name:** {}\n\
+        🎮 **Game:** {}\n\
+        🧑‍🚀 **Creator:** {}\n",
+        escape_markdown(&lobby_info.name),
+        escape_markdown(&lobby_info.game.name),
+        escape_markdown(&player_name(&lobby_info.creator)),
+    );
+
+    if let Some(description) = &lobby_info.description {
+        content.push_str(&format!(
+            "📝 **Description:** {}\n",
+            escape_markdown(description)
+        ));
+    }
+
+    let token = lobby_info.pool_symbol();
+    match lobby_info.entry_amount {
+        Some(0.0) => {
+            // Sponsored lobby - show pool size instead of entry fee
+            let pool_size = lobby_info.current_amount.unwrap_or(0.0);
+            content.push_str(&format!(
+                "🎁 **Pool Size:** {} {} (Sponsored)\n",
+                pool_size, token
+            ));
+        }
+        Some(amount) => {
+            content.push_str(&format!("💵 **Entry Fee:** {} {}\n", amount, token));
+        }
+        None => {}
+    }
+
+    content.push_str(&format!(
+        "\n🔗 https://stackswars.com/lobby/{}",
+        lobby_info.id
+    ));
+
+    post_message(content).await
+}
+
+async fn announce_winner(
+    lobby_id: Uuid,
+    winner_id: Uuid,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let lobby_info = get_lobby_info(lobby_id, redis.clone()).await?;
+    let winner = get_user_by_id(winner_id, redis.clone()).await?;
+
+    let mut content = format!(
+        "🎉 **Game Finished!**\n\n\
+        🎮 **{}** ({})\n\
+        🏆 **Winner:** {}\n",
+        escape_markdown(&lobby_info.name),
+        escape_markdown(&lobby_info.game.name),
+        escape_markdown(&player_name(&winner)),
+    );
+
+    let prize = get_lobby_player(lobby_id, winner_id, redis)
+        .await
+        .ok()
+        .and_then(|player| player.prize);
+    if let Some(prize) = prize {
+        // Shown net of the entry fee, like the Telegram announcement
+        let net_prize = prize - lobby_info.entry_amount.unwrap_or(0.0);
+        if net_prize > 0.0 {
+            content.push_str(&format!(
+                "💰 **Prize Won:** {:.2} {}\n",
+                net_prize,
+                lobby_info.pool_symbol()
+            ));
+        }
+    }
+
+    post_message(content).await
+}
@@ -0,0 +1,211 @@
+use axum::{
+    Json,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+use ring::signature::{ED25519, UnparsedPublicKey};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    config,
+    db::{
+        leaderboard::get::{get_leaderboard, get_user_stat},
+        user::get::get_user_id,
+    },
+    discord::{escape_markdown, player_name},
+    errors::AppError,
+    state::{AppState, RedisClient},
+};
+
+const SIGNATURE_HEADER: &str = "X-Signature-Ed25519";
+const TIMESTAMP_HEADER: &str = "X-Signature-Timestamp";
+
+// Interaction and response types from the Discord API
+const PING: u8 = 1;
+const APPLICATION_COMMAND: u8 = 2;
+const PONG: u8 = 1;
+const CHANNEL_MESSAGE: u8 = 4;
+/// Only the user who ran the command sees the reply
+const EPHEMERAL: u64 = 1 << 6;
+
+#[derive(Deserialize)]
+struct Interaction {
+    #[serde(rename = "type")]
+    kind: u8,
+    data: Option<CommandData>,
+}
+
+#[derive(Deserialize)]
+struct CommandData {
+    name: String,
+    #[serde(default)]
+    options: Vec<CommandOption>,
+}
+
+#[derive(Deserialize)]
+struct CommandOption {
+    name: String,
+    value: Value,
+}
+
+/// The slash commands registered with Discord at boot.
+pub fn command_definitions() -> Value {
+    json!([
+        {
+            "name": "leaderboard",
+            "description": "Show the top 10 leaderboard",
+        },
+        {
+            "name": "stats",
+            "description": "Show a player's stats",
+            "options": [{
+                // 3 is a string option
+                "type": 3,
+                "name": "player",
+                "description": "Username or wallet address",
+                "required": true,
+            }],
+        },
+    ])
+}
+
+/// Answers the slash commands Discord posts to `/discord/interactions`.
+pub async fn interactions_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    if !verify_signature(&headers, &body) {
+        return Err((StatusCode::UNAUTHORIZED, "Invalid request signature".into()));
+    }
+
+    let interaction: Interaction = serde_json::from_slice(&body).map_err(|e| {
+        AppError::Deserialization(format!("Invalid interaction: {}", e)).to_response()
+    })?;
+
+    match (interaction.kind, interaction.data) {
+        (PING, _) => Ok(Json(json!({ "type": PONG }))),
+        (APPLICATION_COMMAND, Some(data)) => {
+            let content = run_command(data, state.redis).await;
+            Ok(Json(json!({
+                "type": CHANNEL_MESSAGE,
+                "data": { "content": content, "flags": EPHEMERAL },
+            })))
+        }
+        _ => Err(AppError::BadRequest("Unsupported interaction".into()).to_response()),
+    }
+}
+
+/// Discord signs the timestamp followed by the raw body with the
+/// application's key, and stops sending to endpoints that accept bad ones.
+fn verify_signature(headers: &HeaderMap, body: &[u8]) -> bool {
+    let Some(public_key) = config::get()
+        .discord_public_key
+        .as_deref()
+        .and_then(|key| hex::decode(key).ok())
+    else {
+        return false;
+    };
+
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| hex::decode(value).ok());
+    let timestamp = headers.get(TIMESTAMP_HEADER);
+    let (Some(signature), Some(timestamp)) = (signature, timestamp) else {
+        return false;
+    };
+
+    let message = [timestamp.as_bytes(), body].concat();
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(&message, &signature)
+        .is_ok()
+}
+
+async fn run_command(data: CommandData, redis: RedisClient) -> String {
+    match data.name.as_str() {
+        "leaderboard" => leaderboard_command(redis).await,
+        "stats" => {
+            let player = data
+                .options
+                .into_iter()
+                .find(|option| option.name == "player")
+                .and_then(|option| option.value.as_str().map(str::to_string));
+            match player {
+                Some(player) => stats_command(player, redis).await,
+                None => "Usage: /stats <username or wallet>".into(),
+            }
+        }
+        _ => "❌ Unknown command".into(),
+    }
+}
+
+async fn leaderboard_command(redis: RedisClient) -> String {
+    let leaderboard = match get_leaderboard(1, 10, None, redis).await {
+        Ok(data) => data.items,
+        Err(e) => {
+            tracing::error!("Failed to get leaderboard: {}", e);
+            return "❌ Failed to retrieve leaderboard data".into();
+        }
+    };
+
+    if leaderboard.is_empty() {
+        return "📊 No leaderboard data available yet".into();
+    }
+
+    let mut response = "🏆 **Top 10 Leaderboard**\n\n".to_string();
+    for (index, entry) in leaderboard.iter().enumerate() {
+        response.push_str(&format!(
+            "**{}.** {} · 📈 `{:.1}` WP · 🎯 `{:.1}%` ({}/{})\n",
+            index + 1,
+            escape_markdown(&player_name(&entry.user)),
+            entry.user.wars_point,
+            entry.win_rate,
+            entry.total_wins,
+            entry.total_match
+        ));
+    }
+    response.push_str("\n🌐 Join the competition at https://stackswars.com");
+
+    response
+}
+
+async fn stats_command(player: String, redis: RedisClient) -> String {
+    let user_id = match get_user_id(player.trim().to_string(), redis.clone()).await {
+        Ok(user_id) => user_id,
+        Err(AppError::NotFound(_)) => {
+            return format!("🔍 No player named {}", escape_markdown(&player));
+        }
+        Err(e) => {
+            tracing::error!("Failed to look up player {}: {}", player, e);
+            return "❌ Failed to retrieve stats".into();
+        }
+    };
+
+    let stat = match get_user_stat(user_id, redis).await {
+        Ok(stat) => stat,
+        Err(e) => {
+            tracing::error!("Failed to get stats for user {}: {}", user_id, e);
+            return "❌ Failed to retrieve stats".into();
+        }
+    };
+
+    let mut response = format!(
+        "📊 **Stats for {}**\n\n",
+        escape_markdown(&player_name(&stat.user))
+    );
+    response.push_str(&format!("🏅 Rank: `#{}`\n", stat.rank));
+    response.push_str(&format!("📈 Wars Points: `{:.1}`\n", stat.user.wars_point));
+    response.push_str(&format!(
+        "🎯 Win Rate: `{:.1}%` ({}/{})\n",
+        stat.win_rate, stat.total_wins, stat.total_match
+    ));
+    if stat.pnl != 0.0 {
+        let pnl_emoji = if stat.pnl > 0.0 { "💰" } else { "💸" };
+        response.push_str(&format!("{} P&L: `{:.2} STX`\n", pnl_emoji, stat.pnl));
+    }
+
+    response
+}
@@ -0,0 +1,106 @@
+//! Discord counterpart of the Telegram bot: lobby and winner announcements
+//! in a configured channel, and slash commands answered over the
+//! interactions endpoint. Talks to the REST API directly, the bot never
+//! holds a gateway connection.
+
+pub mod announce;
+pub mod commands;
+
+use serde_json::json;
+use std::{sync::LazyLock, time::Duration};
+
+use crate::{config, errors::AppError, models::User};
+
+const API_BASE: &str = "https://discord.com/api/v10";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+});
+
+/// Name shown for a player, the shortened wallet when they have none.
+fn player_name(user: &User) -> String {
+    user.display_name
+        .as_ref()
+        .or(user.username.as_ref())
+        .cloned()
+        .unwrap_or_else(|| {
+            let wallet = &user.wallet_address;
+            format!("{}...{}", &wallet[0..4], &wallet[wallet.len() - 4..])
+        })
+}
+
+/// Escapes Discord markdown so player-chosen names render as typed.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '~' | '`' | '|' | '>' | '#' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+async fn send(request: reqwest::RequestBuilder) -> Result<(), AppError> {
+    let token = config::get()
+        .discord_bot_token
+        .clone()
+        .ok_or_else(|| AppError::BadRequest("DISCORD_BOT_TOKEN is not set".into()))?;
+
+    let res = request
+        .header(reqwest::header::AUTHORIZATION, format!("Bot {token}"))
+        .send()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Discord request failed: {}", e)))?;
+
+    if !res.status().is_success() {
+        return Err(AppError::BadRequest(format!(
+            "Discord returned status {}",
+            res.status()
+        )));
+    }
+    Ok(())
+}
+
+/// Posts to the announcement channel.
+async fn post_message(content: String) -> Result<(), AppError> {
+    let Some(channel_id) = config::get().discord_channel_id else {
+        return Ok(());
+    };
+
+    send(
+        CLIENT
+            .post(format!("{API_BASE}/channels/{channel_id}/messages"))
+            .json(&json!({
+                "content": content,
+                // Names come from players, never let them ping anyone
+                "allowed_mentions": { "parse": [] },
+            })),
+    )
+    .await
+}
+
+/// Replaces the application's global slash commands with ours. Discord
+/// keeps them between restarts, so this only matters when they change.
+pub async fn register_commands() {
+    let config = config::get();
+    let Some(application_id) = config.discord_application_id else {
+        return;
+    };
+    if config.discord_public_key.is_none() {
+        tracing::warn!("DISCORD_PUBLIC_KEY is not set, skipping slash command registration");
+        return;
+    }
+
+    let request = CLIENT
+        .put(format!("{API_BASE}/applications/{application_id}/commands"))
+        .json(&commands::command_definitions());
+    match send(request).await {
+        Ok(()) => tracing::info!("Registered Discord slash commands"),
+        Err(e) => tracing::error!("Failed to register Discord slash commands: {}", e),
+    }
+}
@@ -1,5 +1,7 @@
 use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
 use redis::RedisError;
+use serde_json::json;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -33,6 +35,13 @@ pub enum AppError {
 
     #[error("Not found")]
     NotFound(String),
+
+    /// The user is banned, for good when `until` is unset
+    #[error("Account suspended: {reason}")]
+    AccountSuspended {
+        reason: String,
+        until: Option<DateTime<Utc>>,
+    },
 }
 
 impl AppError {
@@ -51,6 +60,16 @@ impl AppError {
                 "Unexpected server error".into(),
             ),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
+            // Sent as json so clients can show the reason and when it ends
+            AppError::AccountSuspended { reason, until } => (
+                StatusCode::FORBIDDEN,
+                json!({
+                    "error": "accountSuspended",
+                    "reason": reason,
+                    "until": until,
+                })
+                .to_string(),
+            ),
         }
     }
 }
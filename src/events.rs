@@ -0,0 +1,243 @@
+//! In-process event bus. Engines emit what happened in a game and move on;
+//! subscriber tasks started at boot handle the side effects play doesn't wait
+//! on: match history, the ranked ladder, notifications, presence, webhooks,
+//! connection metrics and Discord announcements.
+
+use serde::Serialize;
+use std::{future::Future, sync::LazyLock};
+use teloxide::Bot;
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+use crate::{
+    db::leaderboard::{history::record_match, ladder::record_ladder_result},
+    models::{game::LobbyState, leaderboard::MatchRecord, notification::NotificationEvent},
+    notifications::notify,
+    presence::{refresh_lobby_presence, refresh_presence},
+    state::RedisClient,
+    webhooks::queue_deliveries,
+    ws::{
+        lifecycle::{ConnectionStage, count_connection_event},
+        sessions::SocketKind,
+    },
+};
+
+/// Events a subscriber can fall behind by before it starts missing them
+const EVENT_BUFFER: usize = 1024;
+
+/// Something that happened in a game
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GameEvent {
+    #[serde(rename_all = "camelCase")]
+    LobbyCreated {
+        lobby_id: Uuid,
+        lobby_name: String,
+        game_id: Uuid,
+        creator_id: Uuid,
+    },
+    #[serde(rename_all = "camelCase")]
+    GameStarted {
+        lobby_id: Uuid,
+        lobby_name: String,
+        player_ids: Vec<Uuid>,
+    },
+    #[serde(rename_all = "camelCase")]
+    LobbyStateChanged {
+        lobby_id: Uuid,
+        from: LobbyState,
+        to: LobbyState,
+    },
+    #[serde(rename_all = "camelCase")]
+    PlayerEliminated { lobby_id: Uuid, player_id: Uuid },
+    /// A player's final result, once per ranked player
+    #[serde(rename_all = "camelCase")]
+    PlayerFinished {
+        player_id: Uuid,
+        record: MatchRecord,
+    },
+    #[serde(rename_all = "camelCase")]
+    PrizeAwarded {
+        lobby_id: Uuid,
+        player_id: Uuid,
+        amount: f64,
+        token_symbol: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    PrizeClaimed {
+        lobby_id: Uuid,
+        player_id: Uuid,
+        tx_id: String,
+    },
+    /// `winner_id` is `None` when nobody won outright
+    #[serde(rename_all = "camelCase")]
+    GameEnded {
+        lobby_id: Uuid,
+        winner_id: Option<Uuid>,
+    },
+    /// A step in the life of a socket. `lobby_id` is `None` until the socket
+    /// has been tied to a lobby.
+    #[serde(rename_all = "camelCase")]
+    Connection {
+        user_id: Uuid,
+        lobby_id: Option<Uuid>,
+        socket: SocketKind,
+        #[serde(flatten)]
+        stage: ConnectionStage,
+    },
+}
+
+static EVENTS: LazyLock<broadcast::Sender<GameEvent>> =
+    LazyLock::new(|| broadcast::channel(EVENT_BUFFER).0);
+
+/// Hands the event to every subscriber without waiting on any of them.
+pub fn emit(event: GameEvent) {
+    // Only fails when nothing subscribed, when there's nobody to tell
+    let _ = EVENTS.send(event);
+}
+
+/// Starts the subscriber tasks. Runs at boot, before any game can emit.
+pub fn start_event_subscribers(redis: RedisClient, bot: Bot) {
+    let history_redis = redis.clone();
+    spawn_subscriber("history", move |event| {
+        record_history(event, history_redis.clone())
+    });
+
+    let ladder_redis = redis.clone();
+    let ladder_bot = bot.clone();
+    spawn_subscriber("ladder", move |event| {
+        update_ladder(event, ladder_redis.clone(), ladder_bot.clone())
+    });
+
+    let notifications_redis = redis.clone();
+    spawn_subscriber("notifications", move |event| {
+        send_notifications(event, notifications_redis.clone(), bot.clone())
+    });
+
+    #[cfg(feature = "discord")]
+    if crate::config::get().discord_announcements {
+        let discord_redis = redis.clone();
+        spawn_subscriber("discord", move |event| {
+            crate::discord::announce::announce(event, discord_redis.clone())
+        });
+    }
+
+    let presence_redis = redis.clone();
+    spawn_subscriber("presence", move |event| {
+        update_presence(event, presence_redis.clone())
+    });
+
+    spawn_subscriber("connections", count_connection_event);
+
+    spawn_subscriber("webhooks", move |event| {
+        queue_deliveries(event, redis.clone())
+    });
+}
+
+/// Runs `handle` on each event in order. A subscriber that falls too far
+/// behind skips what it missed rather than holding up the others.
+fn spawn_subscriber<F, Fut>(name: &'static str, mut handle: F)
+where
+    F: FnMut(GameEvent) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    // Subscribe before returning so no event emitted after boot is missed
+    let mut events = EVENTS.subscribe();
+    tokio::spawn(async move {
+        tracing::info!("Starting {} event subscriber", name);
+        loop {
+            match events.recv().await {
+                Ok(event) => handle(event).await,
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("{} event subscriber missed {} events", name, missed);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+async fn record_history(event: GameEvent, redis: RedisClient) {
+    if let GameEvent::PlayerFinished { player_id, record } = event
+        && let Err(e) = record_match(player_id, &record, redis).await
+    {
+        tracing::error!("Failed to record match for player {}: {}", player_id, e);
+    }
+}
+
+async fn update_ladder(event: GameEvent, redis: RedisClient, bot: Bot) {
+    let GameEvent::PlayerFinished { player_id, record } = event else {
+        return;
+    };
+
+    match record_ladder_result(player_id, record.wars_point, redis.clone()).await {
+        Ok((from, to)) if from != to => {
+            notify(
+                player_id,
+                NotificationEvent::TierChanged {
+                    lobby_id: record.lobby_id,
+                    from,
+                    to,
+                },
+                bot,
+                redis,
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Failed to update ladder for player {}: {}", player_id, e);
+        }
+    }
+}
+
+async fn update_presence(event: GameEvent, redis: RedisClient) {
+    match event {
+        GameEvent::GameStarted { player_ids, .. } => {
+            for player_id in player_ids {
+                refresh_presence(player_id);
+            }
+        }
+        GameEvent::GameEnded { lobby_id, .. } => refresh_lobby_presence(lobby_id, redis).await,
+        _ => {}
+    }
+}
+
+async fn send_notifications(event: GameEvent, redis: RedisClient, bot: Bot) {
+    match event {
+        GameEvent::GameStarted {
+            lobby_id,
+            lobby_name,
+            player_ids,
+        } => {
+            for player_id in player_ids {
+                notify(
+                    player_id,
+                    NotificationEvent::GameStarted {
+                        lobby_id,
+                        lobby_name: lobby_name.clone(),
+                    },
+                    bot.clone(),
+                    redis.clone(),
+                );
+            }
+        }
+        GameEvent::PrizeAwarded {
+            lobby_id,
+            player_id,
+            amount,
+            token_symbol,
+        } => {
+            notify(
+                player_id,
+                NotificationEvent::PrizeWon {
+                    lobby_id,
+                    amount,
+                    token_symbol,
+                },
+                bot,
+                redis,
+            );
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,23 @@
+use std::time::Duration;
+use tokio::time::{MissedTickBehavior, interval};
+
+use crate::{config, db::lobby::archive::archive_finished_lobbies, state::RedisClient};
+
+/// Archives lobbies finished more than `LOBBY_ARCHIVE_AFTER_DAYS` ago, hourly.
+pub async fn start_archive_worker(redis: RedisClient) {
+    tracing::info!("Starting lobby archive worker");
+
+    let mut ticker = interval(Duration::from_secs(60 * 60));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        ticker.tick().await;
+
+        let after_days = config::get().lobby_archive_after_days;
+        match archive_finished_lobbies(after_days, redis.clone()).await {
+            Ok(0) => {}
+            Ok(archived) => tracing::info!("Archived {} finished lobbies", archived),
+            Err(e) => tracing::error!("Failed to archive finished lobbies: {}", e),
+        }
+    }
+}
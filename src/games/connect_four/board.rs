@@ -0,0 +1,93 @@
+pub const COLUMNS: usize = 7;
+pub const ROWS: usize = 6;
+/// Discs in a row needed to win
+const CONNECT: usize = 4;
+
+const EMPTY: u8 = b'.';
+
+/// A Connect Four grid. Cells are stored row by row from the bottom, `.` for
+/// empty and `1`/`2` for the first and second player, which is also how the
+/// grid is kept in Redis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    cells: Vec<u8>,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self {
+            cells: vec![EMPTY; COLUMNS * ROWS],
+        }
+    }
+}
+
+impl Board {
+    pub fn parse(encoded: &str) -> Option<Self> {
+        let cells = encoded.as_bytes().to_vec();
+        let valid =
+            cells.len() == COLUMNS * ROWS && cells.iter().all(|c| matches!(c, b'.' | b'1' | b'2'));
+        valid.then_some(Self { cells })
+    }
+
+    pub fn encode(&self) -> String {
+        String::from_utf8_lossy(&self.cells).into_owned()
+    }
+
+    /// The player at a cell: 0 when empty, otherwise 1 or 2.
+    pub fn get(&self, column: usize, row: usize) -> u8 {
+        match self.cells[row * COLUMNS + column] {
+            EMPTY => 0,
+            c => c - b'0',
+        }
+    }
+
+    /// Drops a disc of `player` (1 or 2), returning the row it lands on, or
+    /// `None` when the column is full.
+    pub fn drop_disc(&mut self, column: usize, player: u8) -> Option<usize> {
+        let row = (0..ROWS).find(|&row| self.get(column, row) == 0)?;
+        self.cells[row * COLUMNS + column] = b'0' + player;
+        Some(row)
+    }
+
+    pub fn is_full(&self) -> bool {
+        !self.cells.contains(&EMPTY)
+    }
+
+    /// The line of four or more through the disc at (`column`, `row`), as
+    /// (column, row) pairs.
+    pub fn winning_line(&self, column: usize, row: usize) -> Option<Vec<(usize, usize)>> {
+        let player = self.get(column, row);
+        if player == 0 {
+            return None;
+        }
+
+        let directions: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+        directions.into_iter().find_map(|(dc, dr)| {
+            let mut line = vec![(column, row)];
+            for sign in [1, -1] {
+                let (mut c, mut r) = (column as isize, row as isize);
+                loop {
+                    c += dc * sign;
+                    r += dr * sign;
+                    let inside =
+                        (0..COLUMNS as isize).contains(&c) && (0..ROWS as isize).contains(&r);
+                    if !inside || self.get(c as usize, r as usize) != player {
+                        break;
+                    }
+                    line.push((c as usize, r as usize));
+                }
+            }
+            (line.len() >= CONNECT).then(|| {
+                line.sort();
+                line
+            })
+        })
+    }
+
+    /// Rows from the bottom, each cell 0 when empty, otherwise 1 or 2.
+    pub fn rows(&self) -> Vec<Vec<u8>> {
+        (0..ROWS)
+            .map(|row| (0..COLUMNS).map(|column| self.get(column, row)).collect())
+            .collect()
+    }
+}
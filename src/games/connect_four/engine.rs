@@ -0,0 +1,674 @@
+use axum::extract::ws::Message;
+use chrono::Utc;
+use futures::StreamExt;
+use rand::seq::SliceRandom;
+use teloxide::Bot;
+use uuid::Uuid;
+
+use crate::{
+    config,
+    db::{
+        game::{
+            actions::{claim_action, release_action},
+            connect_four::{DropOutcome, drop_disc, get_board, open_board},
+            state::{
+                clear_lobby_game_state, get_current_turn, schedule_turn, set_current_turn,
+                set_game_started,
+            },
+        },
+        lobby::{
+            get::{get_lobby_info, get_lobby_players},
+            patch::update_lobby_state,
+            put::create_current_players,
+        },
+    },
+    events::{GameEvent, emit},
+    games::{
+        connect_four::board::COLUMNS,
+        core::{
+            EngineError, GameEngine, GameMessage,
+            bets::{handle_bet, settle_bets},
+            delivery::{
+                broadcast_to_lobby_and_spectators, broadcast_to_player, broadcast_to_spectators,
+                send_result_to_player,
+            },
+            lifecycle,
+            reactions::handle_reaction,
+            recovery::resume_turn,
+            results::{send_player_results, settle_draw},
+            turns::TurnBasedEngine,
+        },
+    },
+    models::{
+        bet::{BetSettlement, SpectatorBet},
+        connect_four::{ConnectFourClientMessage, ConnectFourServerMessage},
+        error_code::ErrorCode,
+        game::{LobbyState, Player, PlayerState, Reaction},
+        lobby::LobbyRefund,
+        notification::NotificationEvent,
+        queue::QueuePolicy,
+    },
+    notifications::notify,
+    state::{ConnectionInfoMap, RedisClient},
+    ws::{errors::report_socket_error, handlers::utils::ack_results_for_player},
+};
+
+pub struct ConnectFour;
+
+impl GameMessage for ConnectFourServerMessage {
+    fn should_queue(&self) -> bool {
+        ConnectFourServerMessage::should_queue(self)
+    }
+
+    fn queue_policy(&self) -> QueuePolicy {
+        ConnectFourServerMessage::queue_policy(self)
+    }
+}
+
+impl GameEngine for ConnectFour {
+    type ServerMessage = ConnectFourServerMessage;
+
+    async fn start(
+        lobby_id: Uuid,
+        player_ids: Vec<Uuid>,
+        connections: &ConnectionInfoMap,
+        redis: RedisClient,
+        bot: Bot,
+    ) -> Result<(), EngineError> {
+        start_game(lobby_id, player_ids, connections, redis, bot).await
+    }
+
+    /// Connect Four is won or lost outright, there's no score
+    async fn player_score(_lobby_id: Uuid, _player_id: Uuid, _redis: &RedisClient) -> u64 {
+        0
+    }
+
+    fn wars_point_bonus(_score: u64) -> f64 {
+        0.0
+    }
+
+    fn countdown_message(remaining_secs: u32) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::Start {
+            time: remaining_secs,
+            started: false,
+        }
+    }
+
+    fn start_failed_message() -> ConnectFourServerMessage {
+        ConnectFourServerMessage::StartFailed
+    }
+
+    fn refund_message(refund: LobbyRefund) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::Refund { refund }
+    }
+
+    fn rank_message(rank: usize) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::Rank {
+            rank: rank.to_string(),
+        }
+    }
+
+    fn prize_message(amount: f64, token_symbol: String) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::Prize {
+            amount,
+            token_symbol,
+        }
+    }
+
+    fn wars_point_message(wars_point: f64) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::WarsPoint { wars_point }
+    }
+
+    fn reaction_message(user_id: Uuid, reaction: Reaction) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::Reaction {
+            user_id,
+            emoji: reaction,
+        }
+    }
+
+    fn bet_placed_message(bet: SpectatorBet) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::BetPlaced { bet }
+    }
+
+    fn bet_settled_message(settlement: BetSettlement) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::BetSettled { settlement }
+    }
+
+    fn error_message(code: ErrorCode, message: String) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::Error { code, message }
+    }
+
+    async fn send_to_player(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        msg: &ConnectFourServerMessage,
+        connections: &ConnectionInfoMap,
+        redis: &RedisClient,
+    ) {
+        broadcast_to_player(player_id, lobby_id, msg, connections, redis).await;
+    }
+
+    async fn send_result(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        msg: &ConnectFourServerMessage,
+        connections: &ConnectionInfoMap,
+        redis: &RedisClient,
+    ) {
+        send_result_to_player(player_id, lobby_id, msg, connections, redis).await;
+    }
+}
+
+impl TurnBasedEngine for ConnectFour {
+    fn turn_countdown_message(remaining_secs: u64) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::Countdown {
+            time: remaining_secs,
+        }
+    }
+
+    fn turn_message(current_turn: Player, countdown: u64) -> ConnectFourServerMessage {
+        ConnectFourServerMessage::Turn {
+            current_turn: Box::new(current_turn),
+            countdown,
+        }
+    }
+
+    /// Running out of time forfeits the game to the opponent
+    async fn handle_turn_timeout(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        connections: ConnectionInfoMap,
+        redis: RedisClient,
+        _bot: Bot,
+    ) {
+        match get_current_turn(lobby_id, redis.clone()).await {
+            Ok(Some(current_turn_id)) if current_turn_id == player_id => {}
+            Ok(_) => {
+                tracing::debug!("Turn has already changed for lobby {}", lobby_id);
+                return;
+            }
+            Err(e) => {
+                tracing::error!("Failed to check current turn: {}", e);
+                return;
+            }
+        }
+
+        tracing::info!("Player {} timed out in lobby {}", player_id, lobby_id);
+        let opponent_id = match get_board(lobby_id, redis.clone()).await {
+            Ok(Some(state)) => state.players.into_iter().find(|id| *id != player_id),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::error!("Failed to get board for lobby {}: {}", lobby_id, e);
+                None
+            }
+        };
+        let Some(opponent_id) = opponent_id else {
+            tracing::error!("No opponent found for timed out player {}", player_id);
+            return;
+        };
+
+        if let Err(e) = end_game(
+            lobby_id,
+            opponent_id,
+            player_id,
+            Vec::new(),
+            &connections,
+            redis,
+        )
+        .await
+        {
+            tracing::error!("Failed to end game: {}", e);
+        }
+    }
+}
+
+pub fn start_auto_start_timer(
+    lobby_id: Uuid,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) {
+    lifecycle::start_auto_start_timer::<ConnectFour>(lobby_id, connections, redis, telegram_bot);
+}
+
+pub async fn handle_incoming_messages(
+    player: &Player,
+    lobby_id: Uuid,
+    mut receiver: impl StreamExt<Item = Result<Message, axum::Error>> + Unpin,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) {
+    while let Some(msg_result) = receiver.next().await {
+        match msg_result {
+            Ok(Message::Text(text)) => {
+                let parsed = match serde_json::from_str::<ConnectFourClientMessage>(&text) {
+                    Ok(msg) => msg,
+                    Err(e) => {
+                        tracing::info!("Invalid message format from {}: {}", player.id, e);
+                        continue;
+                    }
+                };
+
+                match parsed {
+                    ConnectFourClientMessage::Ping { ts } => {
+                        let now = Utc::now().timestamp_millis() as u64;
+                        let pong = now.saturating_sub(ts);
+                        let pong_msg = ConnectFourServerMessage::Pong { ts, pong };
+                        broadcast_to_player(player.id, lobby_id, &pong_msg, connections, &redis)
+                            .await;
+                    }
+                    ConnectFourClientMessage::PlaceBet { winner_id, amount } => {
+                        handle_bet::<ConnectFour>(
+                            lobby_id,
+                            player.id,
+                            winner_id,
+                            amount,
+                            connections,
+                            &redis,
+                        )
+                        .await;
+                    }
+                    ConnectFourClientMessage::Reaction { emoji } => {
+                        handle_reaction::<ConnectFour>(
+                            lobby_id,
+                            player.id,
+                            emoji,
+                            connections,
+                            &redis,
+                        )
+                        .await;
+                    }
+                    ConnectFourClientMessage::ResultAck => {
+                        if let Err(e) = ack_results_for_player(player.id, lobby_id, &redis).await {
+                            tracing::error!(
+                                "Failed to ack results for player {}: {}",
+                                player.id,
+                                e
+                            );
+                        }
+                    }
+                    ConnectFourClientMessage::DropDisc { column, action_id } => {
+                        if let Err(e) = handle_drop(
+                            player.id,
+                            lobby_id,
+                            column,
+                            action_id,
+                            connections,
+                            &redis,
+                            &telegram_bot,
+                        )
+                        .await
+                        {
+                            tracing::error!("Failed to handle move of {}: {}", player.id, e);
+                        }
+                    }
+                }
+            }
+            Ok(Message::Close(_)) => {
+                tracing::debug!("Player {} closed the connection", player.id);
+                break;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::debug!("WebSocket error for player {}: {}", player.id, e);
+                break;
+            }
+        }
+    }
+}
+
+async fn send_error(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    code: ErrorCode,
+    message: &str,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let error_msg = ConnectFourServerMessage::Error {
+        code,
+        message: message.to_string(),
+    };
+    broadcast_to_player(player_id, lobby_id, &error_msg, connections, redis).await;
+}
+
+async fn handle_drop(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    column: usize,
+    action_id: Option<String>,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    telegram_bot: &Bot,
+) -> Result<(), EngineError> {
+    if column >= COLUMNS {
+        send_error(
+            player_id,
+            lobby_id,
+            ErrorCode::BadRequest,
+            "No such column",
+            connections,
+            redis,
+        )
+        .await;
+        return Ok(());
+    }
+
+    // A retried move that was already applied only needs its ack
+    if let Some(action_id) = &action_id {
+        match claim_action(lobby_id, player_id, action_id, redis.clone()).await {
+            Ok(true) => {}
+            Ok(false) => {
+                let ack_msg = ConnectFourServerMessage::ActionAck {
+                    action_id: action_id.clone(),
+                };
+                broadcast_to_player(player_id, lobby_id, &ack_msg, connections, redis).await;
+                return Ok(());
+            }
+            Err(e) => {
+                report_socket_error::<ConnectFour>(player_id, lobby_id, &e, connections, redis)
+                    .await;
+                return Ok(());
+            }
+        }
+    }
+
+    let outcome = drop_disc(lobby_id, player_id, column, redis.clone()).await;
+    if let Some(action_id) = &action_id
+        && !matches!(outcome, Ok(DropOutcome::Dropped { .. }))
+    {
+        release_action(lobby_id, player_id, action_id, redis.clone()).await?;
+    }
+
+    let row = match outcome? {
+        DropOutcome::Dropped { row } => row,
+        DropOutcome::NotYourTurn => {
+            send_error(
+                player_id,
+                lobby_id,
+                ErrorCode::NotYourTurn,
+                "It's not your turn",
+                connections,
+                redis,
+            )
+            .await;
+            return Ok(());
+        }
+        DropOutcome::ColumnFull => {
+            send_error(
+                player_id,
+                lobby_id,
+                ErrorCode::BadRequest,
+                "That column is full",
+                connections,
+                redis,
+            )
+            .await;
+            return Ok(());
+        }
+        DropOutcome::NotPlaying => {
+            send_error(
+                player_id,
+                lobby_id,
+                ErrorCode::GameNotStarted,
+                "Game has not started yet",
+                connections,
+                redis,
+            )
+            .await;
+            return Ok(());
+        }
+    };
+
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+    let dropped_msg = ConnectFourServerMessage::DiscDropped {
+        player_id,
+        column,
+        row,
+    };
+    broadcast_to_lobby_and_spectators(&dropped_msg, &players, lobby_id, connections, redis).await;
+    if let Some(action_id) = action_id {
+        let ack_msg = ConnectFourServerMessage::ActionAck { action_id };
+        broadcast_to_player(player_id, lobby_id, &ack_msg, connections, redis).await;
+    }
+
+    let Some(state) = get_board(lobby_id, redis.clone()).await? else {
+        return Err("Board disappeared mid-game".into());
+    };
+    let Some(opponent_id) = state.players.iter().copied().find(|id| *id != player_id) else {
+        return Err("Board has no opponent".into());
+    };
+
+    if let Some(line) = state.board.winning_line(column, row) {
+        end_game(
+            lobby_id,
+            player_id,
+            opponent_id,
+            line,
+            connections,
+            redis.clone(),
+        )
+        .await
+    } else if state.board.is_full() {
+        end_in_draw(lobby_id, &players, connections, redis.clone()).await
+    } else {
+        start_turn(
+            opponent_id,
+            lobby_id,
+            &players,
+            connections,
+            redis,
+            telegram_bot,
+        )
+        .await;
+        Ok(())
+    }
+}
+
+async fn start_game(
+    lobby_id: Uuid,
+    mut connected_player_ids: Vec<Uuid>,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) -> Result<(), EngineError> {
+    if connected_player_ids.len() != 2 {
+        return Err(format!(
+            "Connect Four needs two players, {} connected",
+            connected_player_ids.len()
+        )
+        .into());
+    }
+
+    // Whoever moves first is drawn at random
+    connected_player_ids.shuffle(&mut rand::rng());
+    let (first, second) = (connected_player_ids[0], connected_player_ids[1]);
+
+    set_game_started(lobby_id, true, redis.clone()).await?;
+    create_current_players(lobby_id, connected_player_ids.clone(), redis.clone()).await?;
+    open_board(lobby_id, first, second, redis.clone()).await?;
+    set_current_turn(lobby_id, first, redis.clone()).await?;
+
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+    let game_started_msg = ConnectFourServerMessage::Start {
+        time: 0,
+        started: true,
+    };
+    broadcast_to_lobby_and_spectators(&game_started_msg, &players, lobby_id, connections, &redis)
+        .await;
+
+    if let Some(state) = get_board(lobby_id, redis.clone()).await? {
+        let board_msg = ConnectFourServerMessage::Board {
+            rows: state.board.rows(),
+            players: state.players,
+        };
+        broadcast_to_lobby_and_spectators(&board_msg, &players, lobby_id, connections, &redis)
+            .await;
+    }
+
+    start_turn(
+        first,
+        lobby_id,
+        &players,
+        connections,
+        &redis,
+        &telegram_bot,
+    )
+    .await;
+
+    tracing::info!("Connect Four started for lobby {}", lobby_id);
+    Ok(())
+}
+
+/// Gives the current turn its timer back. A game whose board or turn was lost
+/// can't be told apart from a draw, so it ends as one and paid entries are
+/// refunded.
+pub async fn recover_game(
+    lobby_id: Uuid,
+    remaining_ms: Option<i64>,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) -> Result<(), EngineError> {
+    let board = get_board(lobby_id, redis.clone()).await?;
+    let current_turn = get_current_turn(lobby_id, redis.clone()).await?;
+
+    match (board, current_turn) {
+        (Some(state), Some(player_id)) if state.players.contains(&player_id) => {
+            let turn_secs =
+                resume_turn(lobby_id, player_id, remaining_ms, &redis, &telegram_bot).await?;
+            tracing::info!(
+                "Resumed lobby {} with {}s left for player {}",
+                lobby_id,
+                turn_secs,
+                player_id
+            );
+            Ok(())
+        }
+        _ => {
+            tracing::info!("Lobby {} cannot be resumed, ending in a draw", lobby_id);
+            let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+            end_in_draw(lobby_id, &players, &connections, redis).await
+        }
+    }
+}
+
+/// Announces the turn and hands its countdown to the turn scheduler.
+async fn start_turn(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    players: &[Player],
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    telegram_bot: &Bot,
+) {
+    let turn_secs = config::get().turn_timer_secs;
+    if let Some(current_player) = players.iter().find(|p| p.id == player_id) {
+        let turn_msg = ConnectFourServerMessage::Turn {
+            current_turn: Box::new(current_player.clone()),
+            countdown: turn_secs,
+        };
+        broadcast_to_lobby_and_spectators(&turn_msg, players, lobby_id, connections, redis).await;
+    }
+
+    // Players watching the game already see the turn change
+    if !connections.lock().await.contains_key(&player_id) {
+        notify(
+            player_id,
+            NotificationEvent::YourTurn { lobby_id },
+            telegram_bot.clone(),
+            redis.clone(),
+        );
+    }
+
+    if let Err(e) = schedule_turn(lobby_id, player_id, turn_secs, redis.clone()).await {
+        tracing::error!("Failed to schedule turn for lobby {}: {}", lobby_id, e);
+    }
+}
+
+async fn end_game(
+    lobby_id: Uuid,
+    winner_id: Uuid,
+    loser_id: Uuid,
+    line: Vec<(usize, usize)>,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+) -> Result<(), EngineError> {
+    update_lobby_state(lobby_id, LobbyState::Finished, redis.clone()).await?;
+
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+    let win_msg = ConnectFourServerMessage::Win {
+        player_id: winner_id,
+        line,
+    };
+    broadcast_to_lobby_and_spectators(&win_msg, &players, lobby_id, connections, &redis).await;
+
+    let lobby_info = get_lobby_info(lobby_id, redis.clone()).await?;
+    let standings = [winner_id, loser_id];
+    for (index, &player_id) in standings.iter().enumerate() {
+        let rank = index + 1;
+        send_player_results::<ConnectFour>(
+            player_id,
+            &lobby_info,
+            standings.len(),
+            rank,
+            connections,
+            &redis,
+        )
+        .await;
+    }
+
+    settle_bets::<ConnectFour>(lobby_id, Some(winner_id), connections, &redis).await;
+    emit(GameEvent::GameEnded {
+        lobby_id,
+        winner_id: Some(winner_id),
+    });
+
+    let gameover_msg = ConnectFourServerMessage::GameOver;
+    for player_id in standings {
+        send_result_to_player(player_id, lobby_id, &gameover_msg, connections, &redis).await;
+    }
+    broadcast_to_spectators(&gameover_msg, lobby_id, connections, &redis).await;
+
+    if let Err(e) = clear_lobby_game_state(lobby_id, redis.clone()).await {
+        tracing::error!("Failed to clear lobby game state: {}", e);
+    }
+
+    tracing::info!("Connect Four ended for lobby {}", lobby_id);
+    Ok(())
+}
+
+/// A full grid without a winner closes the lobby and refunds paid entries.
+async fn end_in_draw(
+    lobby_id: Uuid,
+    players: &[Player],
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+) -> Result<(), EngineError> {
+    update_lobby_state(lobby_id, LobbyState::Finished, redis.clone()).await?;
+
+    let draw_msg = ConnectFourServerMessage::Draw;
+    broadcast_to_lobby_and_spectators(&draw_msg, players, lobby_id, connections, &redis).await;
+
+    let lobby_info = get_lobby_info(lobby_id, redis.clone()).await?;
+    let joined: Vec<Uuid> = players
+        .iter()
+        .filter(|p| p.state == PlayerState::Joined)
+        .map(|p| p.id)
+        .collect();
+    settle_draw::<ConnectFour>(&lobby_info, &joined, connections, &redis).await?;
+
+    let gameover_msg = ConnectFourServerMessage::GameOver;
+    for &player_id in &joined {
+        send_result_to_player(player_id, lobby_id, &gameover_msg, connections, &redis).await;
+    }
+    broadcast_to_spectators(&gameover_msg, lobby_id, connections, &redis).await;
+
+    if let Err(e) = clear_lobby_game_state(lobby_id, redis.clone()).await {
+        tracing::error!("Failed to clear lobby game state: {}", e);
+    }
+
+    tracing::info!("Connect Four ended in a draw for lobby {}", lobby_id);
+    Ok(())
+}
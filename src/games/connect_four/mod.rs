@@ -0,0 +1,32 @@
+pub mod board;
+pub mod engine;
+
+pub use engine::{handle_incoming_messages, start_auto_start_timer};
+
+use axum::routing::get;
+use futures::FutureExt;
+
+use crate::{
+    games::{core::turns::TurnHooks, registry::GameRegistration},
+    ws::handlers::connect_four_handler,
+};
+
+pub fn registration() -> GameRegistration {
+    GameRegistration {
+        name: "Connect Four",
+        description: "Drop discs in turn and connect four in a row to take the pool.",
+        // No artwork uploaded yet
+        image_url: "",
+        tags: &["strategy", "duel", "turn-based"],
+        min_players: 2,
+        max_players: Some(2),
+        ws_path: "/ws/connect-four/{lobby_id}",
+        ws_handler: || get(connect_four_handler),
+        // Nothing game-specific to configure
+        validate_settings: |settings, _redis| futures::future::ready(Ok(settings)).boxed(),
+        turns: Some(TurnHooks::of::<engine::ConnectFour>()),
+        recover: |lobby_id, remaining_ms, connections, redis, bot| {
+            engine::recover_game(lobby_id, remaining_ms, connections, redis, bot).boxed()
+        },
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        game::bets::{open_bets, place_bet, take_lobby_bets},
+        leaderboard::patch::credit_bet_payouts,
+        lobby::get::{get_lobby_info, get_lobby_players},
+    },
+    errors::AppError,
+    games::core::{GameEngine, delivery::broadcast_to_spectators},
+    models::{
+        bet::{BetSettlement, SpectatorBet},
+        game::PlayerState,
+    },
+    state::{ConnectionInfoMap, RedisClient},
+    ws::errors::report_socket_error,
+};
+
+/// What each stake pays out, in order. Stakes are `(backed player, amount)`.
+/// Backers of the winner share the whole pot in proportion to their stake;
+/// without a winner, or when nobody backed them, every stake is returned.
+pub fn split_pot(stakes: &[(Uuid, f64)], winner_id: Option<Uuid>) -> Vec<f64> {
+    let pot: f64 = stakes.iter().map(|(_, amount)| amount).sum();
+    let backed: f64 = stakes
+        .iter()
+        .filter(|(player_id, _)| Some(*player_id) == winner_id)
+        .map(|(_, amount)| amount)
+        .sum();
+
+    stakes
+        .iter()
+        .map(|&(player_id, amount)| {
+            if backed <= 0.0 {
+                amount
+            } else if Some(player_id) == winner_id {
+                amount / backed * pot
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Opens spectator betting for the lobby's bet window once a game starts.
+pub async fn open_betting(lobby_id: Uuid, redis: &RedisClient) {
+    let window_secs = match get_lobby_info(lobby_id, redis.clone()).await {
+        Ok(lobby) => lobby.settings.bet_window_secs(),
+        Err(e) => {
+            tracing::error!("Failed to get lobby {} for betting: {}", lobby_id, e);
+            return;
+        }
+    };
+    if window_secs == 0 {
+        return;
+    }
+
+    if let Err(e) = open_bets(lobby_id, window_secs, redis).await {
+        tracing::error!("Failed to open bets for lobby {}: {}", lobby_id, e);
+    }
+}
+
+/// Places a spectator's bet on a player and shows it to the other spectators.
+pub async fn handle_bet<E: GameEngine>(
+    lobby_id: Uuid,
+    user_id: Uuid,
+    winner_id: Uuid,
+    amount: f64,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let bet = SpectatorBet {
+        user_id,
+        winner_id,
+        amount,
+        placed_at: Utc::now(),
+    };
+
+    if let Err(e) = validate_and_place(lobby_id, &bet, redis).await {
+        report_socket_error::<E>(user_id, lobby_id, &e, connections, redis).await;
+        return;
+    }
+
+    tracing::info!(
+        "Spectator {} bet {} on {} in lobby {}",
+        user_id,
+        amount,
+        winner_id,
+        lobby_id
+    );
+    let placed_msg = E::bet_placed_message(bet);
+    broadcast_to_spectators(&placed_msg, lobby_id, connections, redis).await;
+}
+
+async fn validate_and_place(
+    lobby_id: Uuid,
+    bet: &SpectatorBet,
+    redis: &RedisClient,
+) -> Result<(), AppError> {
+    let players = get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await?;
+    if players.iter().any(|p| p.id == bet.user_id) {
+        return Err(AppError::BadRequest(
+            "Players can't bet on their own game".into(),
+        ));
+    }
+    if !players.iter().any(|p| p.id == bet.winner_id) {
+        return Err(AppError::BadRequest(
+            "You can only bet on a player in this game".into(),
+        ));
+    }
+
+    place_bet(lobby_id, bet, redis).await
+}
+
+/// Pays out the spectator bets of a finished game. Pass no winner to return
+/// every stake, as after a draw.
+pub async fn settle_bets<E: GameEngine>(
+    lobby_id: Uuid,
+    winner_id: Option<Uuid>,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let bets = match take_lobby_bets(lobby_id, redis).await {
+        Ok(bets) => bets,
+        Err(e) => {
+            tracing::error!("Failed to read bets of lobby {}: {}", lobby_id, e);
+            return;
+        }
+    };
+    if bets.is_empty() {
+        return;
+    }
+
+    let stakes: Vec<(Uuid, f64)> = bets.iter().map(|b| (b.winner_id, b.amount)).collect();
+    let payouts = split_pot(&stakes, winner_id);
+
+    let credits: Vec<(Uuid, f64)> = bets
+        .iter()
+        .zip(&payouts)
+        .filter(|(_, payout)| **payout > 0.0)
+        .map(|(bet, payout)| (bet.user_id, *payout))
+        .collect();
+    if let Err(e) = credit_bet_payouts(&credits, redis.clone()).await {
+        tracing::error!("Failed to pay out bets of lobby {}: {}", lobby_id, e);
+        return;
+    }
+
+    // Stakes are returned rather than settled when nobody backed the winner
+    let settled_winner = winner_id.filter(|id| bets.iter().any(|b| b.winner_id == *id));
+    let bet_count = bets.len();
+    for (bet, payout) in bets.into_iter().zip(payouts) {
+        let settlement = BetSettlement {
+            winner_id: settled_winner,
+            stake: bet.amount,
+            payout,
+        };
+        let settled_msg = E::bet_settled_message(settlement);
+        E::send_to_player(bet.user_id, lobby_id, &settled_msg, connections, redis).await;
+    }
+
+    tracing::info!("Settled {} spectator bets in lobby {}", bet_count, lobby_id);
+}
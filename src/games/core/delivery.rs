@@ -0,0 +1,163 @@
+use axum::extract::ws::Message;
+use uuid::Uuid;
+
+use crate::{
+    db::lobby::get::get_spectators,
+    games::core::GameMessage,
+    models::{game::Player, lexi_wars::BotSkill},
+    state::{ConnectionInfoMap, RedisClient},
+    ws::handlers::utils::{queue_message_for_player, queue_result_for_player},
+};
+
+pub async fn broadcast_to_player<M: GameMessage>(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    msg: &M,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    if let Some(serialized) = serialize(msg) {
+        deliver(player_id, lobby_id, msg, &serialized, connections, redis).await;
+    }
+}
+
+fn serialize<M: GameMessage>(msg: &M) -> Option<String> {
+    serde_json::to_string(msg)
+        .inspect_err(|e| tracing::error!("Failed to serialize message: {}", e))
+        .ok()
+}
+
+/// Hands an already serialized message to the player's writer, or queues it
+/// when they are offline or their connection is gone.
+async fn deliver<M: GameMessage>(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    msg: &M,
+    serialized: &str,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let conn_info = connections.lock().await.get(&player_id).cloned();
+    if let Some(conn_info) = conn_info {
+        let Err(e) = conn_info.sender.send(Message::Text(
+            conn_info.protocol_version.adapt(serialized).into(),
+        )) else {
+            return;
+        };
+        tracing::debug!(
+            "Failed to send direct message to player {}: {}",
+            player_id,
+            e
+        );
+    }
+
+    // Player not connected or their connection failed, queue if message should be queued.
+    // Bots never connect, so nothing is kept for them
+    if msg.should_queue() && BotSkill::from_user_id(player_id).is_none() {
+        let _ = queue_message_for_player(
+            player_id,
+            lobby_id,
+            serialized.to_string(),
+            msg.queue_policy(),
+            redis,
+        )
+        .await;
+    }
+}
+
+/// Delivers an end-of-game message that must survive a disconnect. The message
+/// is stored until the client acks it, then sent live if the player is connected.
+pub async fn send_result_to_player<M: GameMessage>(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    msg: &M,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    if BotSkill::from_user_id(player_id).is_some() {
+        return;
+    }
+
+    let serialized = match serde_json::to_string(msg) {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::error!("Failed to serialize message: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = queue_result_for_player(player_id, lobby_id, serialized.clone(), redis).await {
+        tracing::error!(
+            "Failed to store result for player {} in lobby {}: {}",
+            player_id,
+            lobby_id,
+            e
+        );
+    }
+
+    let conn_info = connections.lock().await.get(&player_id).cloned();
+    if let Some(conn_info) = conn_info
+        && let Err(e) = conn_info.sender.send(Message::Text(
+            conn_info.protocol_version.adapt(&serialized).into(),
+        ))
+    {
+        tracing::debug!(
+            "Failed to send result to player {}, it will be replayed on reconnect: {}",
+            player_id,
+            e
+        );
+    }
+}
+
+pub async fn broadcast_to_lobby<M: GameMessage>(
+    msg: &M,
+    players: &[Player],
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let Some(serialized) = serialize(msg) else {
+        return;
+    };
+    for player in players {
+        deliver(player.id, lobby_id, msg, &serialized, connections, redis).await;
+    }
+}
+
+pub async fn broadcast_to_spectators<M: GameMessage>(
+    msg: &M,
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let Some(serialized) = serialize(msg) else {
+        return;
+    };
+    if let Ok(spectator_ids) = get_spectators(lobby_id, redis.clone()).await {
+        for spectator_id in spectator_ids {
+            deliver(spectator_id, lobby_id, msg, &serialized, connections, redis).await;
+        }
+    }
+}
+
+pub async fn broadcast_to_lobby_and_spectators<M: GameMessage>(
+    msg: &M,
+    players: &[Player],
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    broadcast_to_lobby(msg, players, lobby_id, connections, redis).await;
+    broadcast_to_spectators(msg, lobby_id, connections, redis).await;
+}
+
+pub async fn broadcast_to_player_and_spectators<M: GameMessage>(
+    msg: &M,
+    player_id: Uuid,
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    broadcast_to_player(player_id, lobby_id, msg, connections, redis).await;
+    broadcast_to_spectators(msg, lobby_id, connections, redis).await;
+}
@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use teloxide::Bot;
+use tokio::time::sleep;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::{
+    config,
+    db::lobby::{
+        get::{get_connected_players_ids, get_lobby_info, get_lobby_players},
+        patch::update_lobby_state,
+        refunds::record_lobby_refunds,
+    },
+    games::{
+        core::{GameEngine, bets::open_betting},
+        ownership::hold_game,
+    },
+    models::game::{LobbyState, PlayerState},
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+/// Counts down before a game, starting early once every joined player is
+/// connected. When time runs out without enough players the start is canceled.
+///
+/// The countdown and the game start run in a `game` span nested in the caller's,
+/// so they log under the correlation id of the connection that started them.
+pub fn start_auto_start_timer<E: GameEngine>(
+    lobby_id: Uuid,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) {
+    let auto_start_secs = config::get().auto_start_timer_secs;
+    let span = tracing::info_span!("game", %lobby_id);
+    tokio::spawn(
+        async move {
+            // The countdown and the game's own timers run here from now on
+            if let Err(e) = hold_game(lobby_id, &redis).await {
+                tracing::error!("Failed to take lease of lobby {}: {}", lobby_id, e);
+            }
+
+            for i in (0..=auto_start_secs).rev() {
+                // Get current lobby state from Redis
+                let connected_player_ids =
+                    match get_connected_players_ids(lobby_id, redis.clone()).await {
+                        Ok(ids) => ids,
+                        Err(e) => {
+                            tracing::error!("Failed to get connected players: {}", e);
+                            return;
+                        }
+                    };
+
+                let lobby_players =
+                    match get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone())
+                        .await
+                    {
+                        Ok(players) => players,
+                        Err(e) => {
+                            tracing::error!("Failed to get lobby players: {}", e);
+                            return;
+                        }
+                    };
+
+                let connected_count = connected_player_ids.len();
+                let total_players = lobby_players.len();
+
+                tracing::info!(
+                    "Auto-start timer: {}s, connected: {}/{}",
+                    i,
+                    connected_count,
+                    total_players
+                );
+
+                // If all players are connected, start immediately
+                if connected_count == total_players {
+                    tracing::info!("All players connected, starting game early");
+                    match E::start(
+                        lobby_id,
+                        connected_player_ids,
+                        &connections,
+                        redis.clone(),
+                        telegram_bot.clone(),
+                    )
+                    .await
+                    {
+                        Ok(()) => open_betting(lobby_id, &redis).await,
+                        Err(e) => tracing::error!("Failed to start game: {}", e),
+                    }
+                    return;
+                }
+
+                // Send countdown update to connected players
+                let start_msg = E::countdown_message(i);
+                for player_id in &connected_player_ids {
+                    E::send_to_player(*player_id, lobby_id, &start_msg, &connections, &redis).await;
+                }
+
+                if i == 0 {
+                    // Timer expired, check if we have sufficient players
+                    let required_players = std::cmp::max(2, total_players.div_ceil(2)); // At least 2 players and 50% (rounded up)
+
+                    tracing::info!(
+                        "Auto-start timer expired: connected {}/{}, required: {}",
+                        connected_count,
+                        total_players,
+                        required_players
+                    );
+
+                    if connected_count >= required_players && connected_count >= 2 {
+                        tracing::info!(
+                            "Sufficient players connected ({}%), starting game",
+                            (connected_count * 100) / total_players
+                        );
+                        match E::start(
+                            lobby_id,
+                            connected_player_ids,
+                            &connections,
+                            redis.clone(),
+                            telegram_bot.clone(),
+                        )
+                        .await
+                        {
+                            Ok(()) => open_betting(lobby_id, &redis).await,
+                            Err(e) => tracing::error!("Failed to start game: {}", e),
+                        }
+                    } else {
+                        tracing::info!("Not enough players connected, canceling game");
+                        let start_failed_msg = E::start_failed_message();
+                        for player_id in &connected_player_ids {
+                            E::send_to_player(
+                                *player_id,
+                                lobby_id,
+                                &start_failed_msg,
+                                &connections,
+                                &redis,
+                            )
+                            .await;
+                        }
+
+                        cancel_start::<E>(lobby_id, &connections, &redis).await;
+                    }
+                    return;
+                }
+
+                sleep(Duration::from_secs(1)).await;
+            }
+        }
+        .instrument(span),
+    );
+}
+
+/// Handles a failed auto-start. Paid lobbies are closed and every paid entry
+/// becomes refundable; free lobbies go back to waiting.
+async fn cancel_start<E: GameEngine>(
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let refunds = match get_lobby_info(lobby_id, redis.clone()).await {
+        Ok(lobby_info) if lobby_info.contract_address.is_some() => {
+            match get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await {
+                Ok(players) => record_lobby_refunds(&lobby_info, &players, redis.clone())
+                    .await
+                    .map_err(|e| tracing::error!("Failed to record refunds: {}", e))
+                    .ok(),
+                Err(e) => {
+                    tracing::error!("Failed to get lobby players: {}", e);
+                    None
+                }
+            }
+        }
+        Ok(_) => None,
+        Err(e) => {
+            tracing::error!("Failed to get lobby info: {}", e);
+            None
+        }
+    };
+
+    let Some(refunds) = refunds else {
+        // Reset lobby state
+        if let Err(e) = update_lobby_state(lobby_id, LobbyState::Waiting, redis.clone()).await {
+            tracing::error!("Error updating game state to Waiting: {}", e);
+        }
+        return;
+    };
+
+    if let Err(e) = update_lobby_state(lobby_id, LobbyState::Finished, redis.clone()).await {
+        tracing::error!("Error updating game state to Finished: {}", e);
+    }
+
+    tracing::info!(
+        "Canceled paid lobby {}, {} entries refundable",
+        lobby_id,
+        refunds.len()
+    );
+    for refund in refunds {
+        let player_id = refund.user_id;
+        let msg = E::refund_message(refund);
+        E::send_to_player(player_id, lobby_id, &msg, connections, redis).await;
+    }
+}
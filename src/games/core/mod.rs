@@ -0,0 +1,92 @@
+//! Pieces every game engine shares: prize and wars point policy, the
+//! auto-start countdown, message delivery, turn timers and end-of-game
+//! results. A game plugs in by implementing [`GameEngine`], and
+//! [`turns::TurnBasedEngine`] when it's played in timed turns. How a game
+//! comes back after a restart is its [`recovery::RecoverHook`].
+
+pub mod bets;
+pub mod delivery;
+pub mod lifecycle;
+pub mod prize;
+pub mod reactions;
+pub mod recovery;
+pub mod results;
+pub mod turns;
+
+use std::future::Future;
+
+use serde::Serialize;
+use teloxide::Bot;
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        bet::{BetSettlement, SpectatorBet},
+        error_code::ErrorCode,
+        game::Reaction,
+        lobby::LobbyRefund,
+        queue::QueuePolicy,
+    },
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+pub type EngineError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A server message that knows whether and how it's kept for an offline player.
+pub trait GameMessage: Serialize + Send + Sync {
+    fn should_queue(&self) -> bool;
+    fn queue_policy(&self) -> QueuePolicy;
+}
+
+/// What a game provides so the shared lifecycle can drive it.
+pub trait GameEngine: Send + Sync + 'static {
+    type ServerMessage: GameMessage;
+
+    /// Begins the game with the players connected when the countdown ended.
+    fn start(
+        lobby_id: Uuid,
+        player_ids: Vec<Uuid>,
+        connections: &ConnectionInfoMap,
+        redis: RedisClient,
+        bot: Bot,
+    ) -> impl Future<Output = Result<(), EngineError>> + Send;
+
+    /// In-game score of a player, 0 for games without one.
+    fn player_score(
+        lobby_id: Uuid,
+        player_id: Uuid,
+        redis: &RedisClient,
+    ) -> impl Future<Output = u64> + Send;
+
+    /// Extra wars points earned from the in-game score.
+    fn wars_point_bonus(score: u64) -> f64;
+
+    fn countdown_message(remaining_secs: u32) -> Self::ServerMessage;
+    fn start_failed_message() -> Self::ServerMessage;
+    fn refund_message(refund: LobbyRefund) -> Self::ServerMessage;
+    fn rank_message(rank: usize) -> Self::ServerMessage;
+    fn prize_message(amount: f64, token_symbol: String) -> Self::ServerMessage;
+    fn wars_point_message(wars_point: f64) -> Self::ServerMessage;
+    fn reaction_message(user_id: Uuid, reaction: Reaction) -> Self::ServerMessage;
+    fn bet_placed_message(bet: SpectatorBet) -> Self::ServerMessage;
+    fn bet_settled_message(settlement: BetSettlement) -> Self::ServerMessage;
+    fn error_message(code: ErrorCode, message: String) -> Self::ServerMessage;
+
+    /// Sends live, queueing for later when the message should survive a disconnect.
+    fn send_to_player(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        msg: &Self::ServerMessage,
+        connections: &ConnectionInfoMap,
+        redis: &RedisClient,
+    ) -> impl Future<Output = ()> + Send;
+
+    /// Stores an end-of-game message until the client acks it, then sends it live.
+    fn send_result(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        msg: &Self::ServerMessage,
+        connections: &ConnectionInfoMap,
+        redis: &RedisClient,
+    ) -> impl Future<Output = ()> + Send;
+}
@@ -0,0 +1,166 @@
+use uuid::Uuid;
+
+use crate::models::game::LobbyInfo;
+
+/// Wars points taken from a player who leaves a lobby they joined
+pub const LEAVE_PENALTY: f64 = 10.0;
+
+/// Pool paid out at the end of a game, `None` for free and casual lobbies.
+pub fn total_pool(lobby_info: &LobbyInfo, connected_players_count: usize) -> Option<f64> {
+    if lobby_info.settings.casual {
+        return None;
+    }
+    lobby_info.contract_address.as_ref()?;
+
+    let entry_amount = lobby_info.entry_amount.unwrap_or(0.0);
+
+    // Sponsored lobbies are pre-funded, paid ones grow with every connected player
+    let pool = if entry_amount == 0.0 {
+        lobby_info.current_amount.unwrap_or(0.0)
+    } else {
+        entry_amount * connected_players_count as f64
+    };
+
+    (pool > 0.0).then_some(pool)
+}
+
+/// Prize for a 1-based finishing `position`, following the lobby's prize split.
+pub fn get_prize(
+    lobby_info: &LobbyInfo,
+    connected_players_count: usize,
+    position: usize,
+) -> Option<f64> {
+    let total_pool = total_pool(lobby_info, connected_players_count)?;
+
+    let percentages = lobby_info
+        .settings
+        .prize_split
+        .clone()
+        .unwrap_or_default()
+        .percentages(connected_players_count);
+
+    let percentage = position
+        .checked_sub(1)
+        .and_then(|i| percentages.get(i))
+        .copied()
+        .unwrap_or(0.0);
+
+    Some((total_pool * percentage) / 100.0)
+}
+
+/// Where a player finished. Players drawn on a rank all take it and split
+/// the prizes of the positions they cover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placing {
+    pub rank: usize,
+    /// Players sharing the rank, this one included
+    pub tied: usize,
+}
+
+impl Placing {
+    pub fn shared(rank: usize, tied: usize) -> Self {
+        Self {
+            rank,
+            tied: tied.max(1),
+        }
+    }
+}
+
+impl From<usize> for Placing {
+    fn from(rank: usize) -> Self {
+        Self::shared(rank, 1)
+    }
+}
+
+/// Orders players best first, lowest `key` first, into groups of players
+/// whose keys are equal. Players within a group keep the order given.
+pub fn group_by_key<K: Ord>(players: &[Uuid], key: impl Fn(&Uuid) -> K) -> Vec<Vec<Uuid>> {
+    let mut keyed: Vec<(K, Uuid)> = players.iter().map(|id| (key(id), *id)).collect();
+    // Stable, so level players stay in the given order
+    keyed.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut groups: Vec<Vec<Uuid>> = Vec::new();
+    let mut previous: Option<&K> = None;
+    for (key, id) in &keyed {
+        match (previous, groups.last_mut()) {
+            (Some(prev), Some(group)) if prev == key => group.push(*id),
+            _ => groups.push(vec![*id]),
+        }
+        previous = Some(key);
+    }
+    groups
+}
+
+/// Placing of every player in groups ordered best first. A group of several
+/// players draws on its rank, and the next group starts after the places it
+/// covers.
+pub fn group_placings(groups: &[Vec<Uuid>]) -> Vec<(Uuid, Placing)> {
+    let mut rank = 1;
+    let mut placings = Vec::new();
+    for group in groups {
+        let placing = Placing::shared(rank, group.len());
+        placings.extend(group.iter().map(|&player_id| (player_id, placing)));
+        rank += group.len();
+    }
+    placings
+}
+
+/// Prize for a placing: the prizes of the positions a draw covers, summed
+/// and split evenly between the drawn players.
+pub fn get_placing_prize(
+    lobby_info: &LobbyInfo,
+    connected_players_count: usize,
+    placing: Placing,
+) -> Option<f64> {
+    let total: f64 = (placing.rank..placing.rank + placing.tied)
+        .map(|position| get_prize(lobby_info, connected_players_count, position))
+        .sum::<Option<f64>>()?;
+
+    Some(total / placing.tied as f64)
+}
+
+/// Wars points for a finishing `rank`, `score_bonus` being the game's own bonus.
+pub fn calculate_wars_point(
+    lobby_info: &LobbyInfo,
+    connected_players_count: usize,
+    rank: usize,
+    prize: Option<f64>,
+    player_id: Uuid,
+    score_bonus: f64,
+) -> f64 {
+    let base_point = (connected_players_count - rank + 1) * 2;
+    let mut total_point = base_point as f64 + score_bonus;
+
+    // Add pool bonus if there's a pool (prize and entry amount exist)
+    if let (Some(prize_amount), Some(entry_amount)) = (prize, lobby_info.entry_amount) {
+        let pool_bonus = if entry_amount != 0.0 {
+            (prize_amount / connected_players_count as f64) + (entry_amount / 5.0)
+        } else {
+            0.0
+        };
+        total_point += pool_bonus;
+    }
+
+    // Add sponsor bonus if this is a sponsored lobby and the player is the sponsor (creator)
+    if let (Some(entry_amount), Some(current_amount)) =
+        (lobby_info.entry_amount, lobby_info.current_amount)
+        && entry_amount == 0.0
+        && current_amount > 0.0
+        && player_id == lobby_info.creator.id
+    {
+        let sponsor_bonus = 2.5 * connected_players_count as f64;
+        total_point += sponsor_bonus;
+    }
+
+    // Cap at 50 points maximum
+    total_point.min(50.0)
+}
+
+/// Wars points a player loses for leaving a lobby. The creator leaves for
+/// free, and casual lobbies never touch wars points.
+pub fn leave_penalty(casual: bool, is_creator: bool) -> Option<f64> {
+    if casual || is_creator {
+        return None;
+    }
+    Some(LEAVE_PENALTY)
+}
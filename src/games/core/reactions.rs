@@ -0,0 +1,39 @@
+use uuid::Uuid;
+
+use crate::{
+    db::{game::reactions::record_reaction, lobby::get::get_lobby_players},
+    games::core::{GameEngine, delivery::broadcast_to_lobby_and_spectators},
+    models::game::{PlayerState, Reaction},
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+/// Relays a player's or spectator's reaction to everyone watching the game.
+/// Reactions sent faster than the cooldown are dropped silently.
+pub async fn handle_reaction<E: GameEngine>(
+    lobby_id: Uuid,
+    user_id: Uuid,
+    reaction: Reaction,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    match record_reaction(lobby_id, user_id, reaction, redis).await {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(e) => {
+            tracing::error!("Failed to record reaction in lobby {}: {}", lobby_id, e);
+            return;
+        }
+    }
+
+    let players = match get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
+    {
+        Ok(players) => players,
+        Err(e) => {
+            tracing::error!("Failed to get players of lobby {}: {}", lobby_id, e);
+            return;
+        }
+    };
+
+    let msg = E::reaction_message(user_id, reaction);
+    broadcast_to_lobby_and_spectators(&msg, &players, lobby_id, connections, redis).await;
+}
@@ -0,0 +1,52 @@
+use futures::future::BoxFuture;
+use teloxide::Bot;
+use uuid::Uuid;
+
+use crate::{
+    config,
+    db::game::state::schedule_turn,
+    errors::AppError,
+    games::core::EngineError,
+    models::notification::NotificationEvent,
+    notifications::notify,
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+/// Minimum time a resumed turn gets, so players have a chance to reconnect.
+const RESUME_GRACE_SECS: u64 = 10;
+
+/// Picks a started game back up after a restart, or ends it when it can't go
+/// on. Gets the turn time that was left when the old process stopped, if any.
+pub type RecoverHook = fn(
+    Uuid,
+    Option<i64>,
+    ConnectionInfoMap,
+    RedisClient,
+    Bot,
+) -> BoxFuture<'static, Result<(), EngineError>>;
+
+/// Hands a resumed turn back to the turn scheduler with the time it had left,
+/// never less than the grace period. Returns the seconds the turn got.
+pub async fn resume_turn(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    remaining_ms: Option<i64>,
+    redis: &RedisClient,
+    telegram_bot: &Bot,
+) -> Result<u64, AppError> {
+    let turn_secs = remaining_ms
+        .map_or(config::get().turn_timer_secs, |ms| {
+            (ms.max(0) as u64).div_ceil(1000)
+        })
+        .max(RESUME_GRACE_SECS);
+
+    schedule_turn(lobby_id, player_id, turn_secs, redis.clone()).await?;
+    notify(
+        player_id,
+        NotificationEvent::YourTurn { lobby_id },
+        telegram_bot.clone(),
+        redis.clone(),
+    );
+
+    Ok(turn_secs)
+}
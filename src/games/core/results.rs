@@ -0,0 +1,148 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        leaderboard::patch::update_user_stats,
+        lobby::{get::get_lobby_players, refunds::record_lobby_refunds},
+    },
+    events::{GameEvent, emit},
+    games::core::{
+        EngineError, GameEngine,
+        bets::settle_bets,
+        prize::{Placing, calculate_wars_point, get_placing_prize},
+    },
+    models::{
+        game::{LobbyInfo, PlayerState},
+        leaderboard::MatchRecord,
+        lexi_wars::BotSkill,
+    },
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+/// Delivers a player's rank, prize and wars points and records them in the
+/// user's stats, then emits the result for match history, the ranked ladder
+/// and notifications. A drawn placing shares its prizes between the drawn
+/// players. Casual lobbies only deliver the rank.
+pub async fn send_player_results<E: GameEngine>(
+    player_id: Uuid,
+    lobby_info: &LobbyInfo,
+    connected_players_count: usize,
+    placing: impl Into<Placing>,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    // Practice bots have no stats and nobody to deliver results to
+    if BotSkill::from_user_id(player_id).is_some() {
+        return;
+    }
+
+    let lobby_id = lobby_info.id;
+    let placing = placing.into();
+    let rank = placing.rank;
+    let score = E::player_score(lobby_id, player_id, redis).await;
+    let prize = get_placing_prize(lobby_info, connected_players_count, placing);
+    let wars_point = calculate_wars_point(
+        lobby_info,
+        connected_players_count,
+        rank,
+        prize,
+        player_id,
+        E::wars_point_bonus(score),
+    );
+
+    // End-of-game messages are kept until the client acks them
+    let rank_msg = E::rank_message(rank);
+    E::send_result(player_id, lobby_id, &rank_msg, connections, redis).await;
+
+    if lobby_info.settings.casual {
+        tracing::info!(
+            "Player {} finished casual lobby {} at rank {}",
+            player_id,
+            lobby_id,
+            rank
+        );
+        return;
+    }
+
+    // Send prize if applicable
+    if let Some(amount) = prize {
+        let prize_msg = E::prize_message(amount, lobby_info.pool_symbol().to_string());
+        E::send_result(player_id, lobby_id, &prize_msg, connections, redis).await;
+    }
+
+    // Send wars point message
+    let wars_point_msg = E::wars_point_message(wars_point);
+    E::send_result(player_id, lobby_id, &wars_point_msg, connections, redis).await;
+
+    // Update user stats
+    match update_user_stats(player_id, lobby_id, rank, prize, wars_point, redis.clone()).await {
+        Ok(()) => {
+            tracing::info!(
+                "Player {} earned {} wars points (rank: {}, prize: {:?})",
+                player_id,
+                wars_point,
+                rank,
+                prize
+            );
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to update user stats for player {}: {}",
+                player_id,
+                e
+            );
+        }
+    }
+
+    let record = MatchRecord {
+        lobby_id,
+        game_id: lobby_info.game.id,
+        game_name: lobby_info.game.name.clone(),
+        rank,
+        players: connected_players_count,
+        prize,
+        wars_point,
+        finished_at: Utc::now(),
+    };
+    emit(GameEvent::PlayerFinished { player_id, record });
+
+    if let Some(amount) = prize.filter(|amount| *amount > 0.0) {
+        emit(GameEvent::PrizeAwarded {
+            lobby_id,
+            player_id,
+            amount,
+            token_symbol: lobby_info.token_symbol.clone(),
+        });
+    }
+}
+
+/// Settles a game nobody won: the entries of `player_ids` are refunded, bets
+/// are returned and the game ends without a winner. The engine still sends
+/// its own draw and game over messages.
+pub async fn settle_draw<E: GameEngine>(
+    lobby_info: &LobbyInfo,
+    player_ids: &[Uuid],
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) -> Result<(), EngineError> {
+    let lobby_id = lobby_info.id;
+    let drawn: Vec<_> = get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone())
+        .await?
+        .into_iter()
+        .filter(|p| player_ids.contains(&p.id))
+        .collect();
+
+    for refund in record_lobby_refunds(lobby_info, &drawn, redis.clone()).await? {
+        let player_id = refund.user_id;
+        let refund_msg = E::refund_message(refund);
+        E::send_result(player_id, lobby_id, &refund_msg, connections, redis).await;
+    }
+    settle_bets::<E>(lobby_id, None, connections, redis).await;
+    emit(GameEvent::GameEnded {
+        lobby_id,
+        winner_id: None,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use std::future::Future;
+
+use futures::{FutureExt, future::BoxFuture};
+use teloxide::Bot;
+use uuid::Uuid;
+
+use crate::{
+    db::game::state::TurnDeadline,
+    games::core::{
+        GameEngine,
+        delivery::{broadcast_to_lobby_and_spectators, broadcast_to_player},
+    },
+    models::game::Player,
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+/// A game played in timed turns, driven by the shared turn scheduler.
+pub trait TurnBasedEngine: GameEngine {
+    /// Time left, sent to the player whose turn it is
+    fn turn_countdown_message(remaining_secs: u64) -> Self::ServerMessage;
+
+    /// Whose turn it is, sent to everyone in the lobby
+    fn turn_message(current_turn: Player, countdown: u64) -> Self::ServerMessage;
+
+    /// Called once a turn deadline has passed.
+    fn handle_turn_timeout(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        connections: ConnectionInfoMap,
+        redis: RedisClient,
+        bot: Bot,
+    ) -> impl Future<Output = ()> + Send;
+}
+
+pub type CountdownHook = for<'a> fn(
+    &'a TurnDeadline,
+    u64,
+    &'a [Player],
+    &'a ConnectionInfoMap,
+    &'a RedisClient,
+) -> BoxFuture<'a, ()>;
+
+pub type TimeoutHook =
+    fn(TurnDeadline, ConnectionInfoMap, RedisClient, Bot) -> BoxFuture<'static, ()>;
+
+/// What the turn scheduler calls for a game's running turns.
+#[derive(Clone, Copy)]
+pub struct TurnHooks {
+    pub countdown: CountdownHook,
+    pub timeout: TimeoutHook,
+}
+
+impl TurnHooks {
+    pub fn of<E: TurnBasedEngine>() -> Self {
+        Self {
+            countdown: broadcast_countdown::<E>,
+            timeout: expire_turn::<E>,
+        }
+    }
+}
+
+fn broadcast_countdown<'a, E: TurnBasedEngine>(
+    deadline: &'a TurnDeadline,
+    remaining_secs: u64,
+    players: &'a [Player],
+    connections: &'a ConnectionInfoMap,
+    redis: &'a RedisClient,
+) -> BoxFuture<'a, ()> {
+    async move {
+        let countdown_msg = E::turn_countdown_message(remaining_secs);
+        broadcast_to_player(
+            deadline.player_id,
+            deadline.lobby_id,
+            &countdown_msg,
+            connections,
+            redis,
+        )
+        .await;
+
+        if let Some(current_player) = players.iter().find(|p| p.id == deadline.player_id) {
+            let turn_msg = E::turn_message(current_player.clone(), remaining_secs);
+            broadcast_to_lobby_and_spectators(
+                &turn_msg,
+                players,
+                deadline.lobby_id,
+                connections,
+                redis,
+            )
+            .await;
+        }
+    }
+    .boxed()
+}
+
+fn expire_turn<E: TurnBasedEngine>(
+    deadline: TurnDeadline,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    bot: Bot,
+) -> BoxFuture<'static, ()> {
+    async move {
+        let countdown_msg = E::turn_countdown_message(0);
+        broadcast_to_player(
+            deadline.player_id,
+            deadline.lobby_id,
+            &countdown_msg,
+            &connections,
+            &redis,
+        )
+        .await;
+
+        E::handle_turn_timeout(
+            deadline.player_id,
+            deadline.lobby_id,
+            connections,
+            redis,
+            bot,
+        )
+        .await;
+    }
+    .boxed()
+}
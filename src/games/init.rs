@@ -1,7 +1,40 @@
+use chrono::Utc;
+use std::{collections::HashMap, time::Duration};
+use teloxide::Bot;
+use tokio::time::{MissedTickBehavior, interval};
+use uuid::Uuid;
+
 use crate::{
-    db::game::{get::get_all_games, post::create_game, words::add_word_set},
+    db::{
+        game::{
+            get::get_all_games,
+            lease::LEASE_TTL_SECS,
+            lexi_rules::load_custom_rules,
+            post::create_game,
+            state::{
+                get_game_started, get_interrupted_games, get_turn_deadlines,
+                remove_interrupted_game,
+            },
+            words::{
+                add_language_word_sets, add_offensive_word_set, add_word_set,
+                load_default_dictionary,
+            },
+        },
+        lobby::{
+            get::{get_lobby_ids_by_state, get_lobby_info},
+            patch::update_lobby_state,
+        },
+        moderation::patch::seed_banned_words,
+    },
     errors::AppError,
-    state::RedisClient,
+    games::{
+        ownership::adopt_game,
+        registry::{GameRegistration, find_registration, registered_games},
+        typing_race::prompts::load_prompt_corpus,
+    },
+    models::game::LobbyState,
+    shutdown::is_draining,
+    state::{ConnectionInfoMap, RedisClient},
 };
 
 pub async fn initialize_games(redis: RedisClient) -> Result<(), AppError> {
@@ -9,15 +42,22 @@ pub async fn initialize_games(redis: RedisClient) -> Result<(), AppError> {
 
     // Initialize word set
     add_word_set(redis.clone()).await?;
+    add_language_word_sets(redis.clone()).await?;
+    add_offensive_word_set(redis.clone()).await?;
+    seed_banned_words(redis.clone()).await?;
+    load_default_dictionary(redis.clone()).await?;
+    load_prompt_corpus(redis.clone()).await?;
+    load_custom_rules(redis.clone()).await?;
 
-    // Try to get all games from Redis
+    // Store any registered game that isn't in Redis yet
     match get_all_games(redis.clone()).await {
         Ok(games) => {
-            if games.is_empty() {
-                tracing::info!("No games found in database, adding default games");
-                add_default_games(redis).await?;
-            } else {
-                tracing::info!("Found {} existing games in database", games.len());
+            tracing::info!("Found {} existing games in database", games.len());
+            for registration in registered_games() {
+                if games.iter().any(|g| g.name == registration.name) {
+                    continue;
+                }
+                add_registered_game(registration, redis.clone()).await?;
             }
         }
         Err(e) => {
@@ -28,21 +68,142 @@ pub async fn initialize_games(redis: RedisClient) -> Result<(), AppError> {
     Ok(())
 }
 
-async fn add_default_games(redis: RedisClient) -> Result<(), AppError> {
+/// Picks up games left `InProgress` by a shutdown or crash. Only games no
+/// live instance holds the lease of are touched; each one is adopted first so
+/// a single instance recovers it. Started games are handed to their
+/// registered recover hook, which resumes or ends them, and games that never
+/// started go back to `Waiting` so no lobby stays stuck. Runs at boot before
+/// the turn scheduler starts, then periodically to catch games of instances
+/// that died.
+pub async fn recover_in_progress_games(
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    bot: Bot,
+) -> Result<(), AppError> {
+    let lobby_ids = get_lobby_ids_by_state(LobbyState::InProgress, redis.clone()).await?;
+    if lobby_ids.is_empty() {
+        return Ok(());
+    }
+
+    let interrupted = get_interrupted_games(redis.clone()).await?;
+    let now = Utc::now().timestamp_millis();
+    let deadlines: HashMap<Uuid, i64> = get_turn_deadlines(redis.clone())
+        .await?
+        .into_iter()
+        .map(|d| (d.lobby_id, d.deadline_ms))
+        .collect();
+
+    for lobby_id in lobby_ids {
+        // Games another live instance runs are left to it
+        match adopt_game(lobby_id, &redis).await {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(e) => {
+                tracing::error!("Failed to adopt lobby {}: {}", lobby_id, e);
+                continue;
+            }
+        }
+
+        // Prefer the time saved at shutdown; after a crash fall back to
+        // whatever deadline was still pending.
+        let remaining_ms = interrupted
+            .get(&lobby_id)
+            .copied()
+            .or_else(|| deadlines.get(&lobby_id).map(|d| d - now));
+        if interrupted.contains_key(&lobby_id)
+            && let Err(e) = remove_interrupted_game(lobby_id, redis.clone()).await
+        {
+            tracing::error!("Failed to clear interrupted lobby {}: {}", lobby_id, e);
+        }
+
+        tracing::info!("Recovering orphaned lobby {}", lobby_id);
+        if let Err(e) = recover_game(lobby_id, remaining_ms, &connections, &redis, &bot).await {
+            tracing::error!("Failed to recover lobby {}: {}", lobby_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Looks for games whose instance died once per lease period.
+pub async fn start_orphan_sweep(connections: ConnectionInfoMap, redis: RedisClient, bot: Bot) {
+    let mut ticker = interval(Duration::from_secs(LEASE_TTL_SECS));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    // The first tick fires right away, and boot recovery just ran
+    ticker.tick().await;
+
+    loop {
+        ticker.tick().await;
+
+        if is_draining() {
+            continue;
+        }
+
+        if let Err(e) =
+            recover_in_progress_games(connections.clone(), redis.clone(), bot.clone()).await
+        {
+            tracing::error!("Failed to recover orphaned games: {}", e);
+        }
+    }
+}
+
+async fn recover_game(
+    lobby_id: Uuid,
+    remaining_ms: Option<i64>,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    bot: &Bot,
+) -> Result<(), AppError> {
+    // The auto-start timer died with the old process before the game began
+    if !get_game_started(lobby_id, redis.clone()).await? {
+        tracing::info!("Lobby {} never started, resetting to Waiting", lobby_id);
+        return update_lobby_state(lobby_id, LobbyState::Waiting, redis.clone()).await;
+    }
+
+    let lobby = get_lobby_info(lobby_id, redis.clone()).await?;
+    let Some(registration) = find_registration(&lobby.game) else {
+        tracing::warn!(
+            "Lobby {} runs unregistered game {}, resetting to Waiting",
+            lobby_id,
+            lobby.game.name
+        );
+        return update_lobby_state(lobby_id, LobbyState::Waiting, redis.clone()).await;
+    };
+
+    if let Err(e) = (registration.recover)(
+        lobby_id,
+        remaining_ms,
+        connections.clone(),
+        redis.clone(),
+        bot.clone(),
+    )
+    .await
+    {
+        tracing::error!(
+            "Failed to recover {} lobby {}: {}",
+            registration.name,
+            lobby_id,
+            e
+        );
+    }
+
+    Ok(())
+}
+
+async fn add_registered_game(
+    registration: &GameRegistration,
+    redis: RedisClient,
+) -> Result<(), AppError> {
     let game_id = create_game(
-        "Lexi Wars".to_string(),
-        "A word battle game where players compete with words.".to_string(),
-        "https://res.cloudinary.com/dapbvli1v/image/upload/Lexi_Wars2_yuuoam.png".to_string(),
-        Some(vec![
-            "word".to_string(),
-            "strategy".to_string(),
-            "multiplayer".to_string(),
-        ]),
-        2,
+        registration.name.to_string(),
+        registration.description.to_string(),
+        registration.image_url.to_string(),
+        Some(registration.tags.iter().map(|t| t.to_string()).collect()),
+        registration.min_players,
         redis,
     )
     .await?;
 
-    tracing::info!("Added Lexi Wars game with ID: {}", game_id);
+    tracing::info!("Added {} game with ID: {}", registration.name, game_id);
     Ok(())
 }
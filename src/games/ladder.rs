@@ -0,0 +1,21 @@
+use std::time::Duration;
+use tokio::time::{MissedTickBehavior, interval};
+
+use crate::{config, db::leaderboard::ladder::roll_ladder_week, state::RedisClient};
+
+/// Checks hourly whether a new ladder week began and applies inactivity decay.
+pub async fn start_ladder_worker(redis: RedisClient) {
+    tracing::info!("Starting ladder worker");
+
+    let mut ticker = interval(Duration::from_secs(60 * 60));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        ticker.tick().await;
+
+        let decay_percent = config::get().ladder_decay_percent;
+        if let Err(e) = roll_ladder_week(decay_percent, redis.clone()).await {
+            tracing::error!("Failed to roll over ladder week: {}", e);
+        }
+    }
+}
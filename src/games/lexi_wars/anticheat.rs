@@ -0,0 +1,47 @@
+/// Time to read the new turn before typing can start.
+const REACTION_MS: u64 = 300;
+/// Faster than a quick human typist, around 200 words per minute.
+const MIN_MS_PER_CHAR: u64 = 60;
+
+/// Why a submission looked automated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suspicion {
+    /// Faster than a human could plausibly type the word
+    TooFast,
+    /// So fast the word can only have been pasted or sent by a script
+    Pasted,
+}
+
+impl Suspicion {
+    /// Amount added to the player's suspicion score.
+    pub fn weight(self) -> u64 {
+        match self {
+            Suspicion::TooFast => 1,
+            Suspicion::Pasted => 2,
+        }
+    }
+
+    pub fn reason(self) -> &'static str {
+        match self {
+            Suspicion::TooFast => "Word submitted faster than it could be typed",
+            Suspicion::Pasted => "Word appears to have been pasted",
+        }
+    }
+}
+
+/// Minimum time a human needs from the start of the turn to submit `word`.
+pub fn min_type_time_ms(word: &str) -> u64 {
+    REACTION_MS + word.chars().count() as u64 * MIN_MS_PER_CHAR
+}
+
+/// Checks how long a submission took, measured from the start of the turn.
+pub fn check_submission(word: &str, elapsed_ms: u64) -> Option<Suspicion> {
+    let min_ms = min_type_time_ms(word);
+    if elapsed_ms * 3 < min_ms {
+        Some(Suspicion::Pasted)
+    } else if elapsed_ms < min_ms {
+        Some(Suspicion::TooFast)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,274 @@
+//! Practice bots for free lobbies. A bot is a lobby player without a socket:
+//! it comes online with the first player to reach the game and answers its
+//! turns by submitting dictionary words through the same path as a player's
+//! socket, so solo players get the real game loop to practice against.
+
+use axum::extract::ws::Message;
+use rand::{Rng, rng, seq::SliceRandom};
+use std::time::Duration;
+use teloxide::Bot;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        game::{
+            bots::{add_lobby_bot, ensure_bot_user, get_lobby_bot},
+            state::{get_current_turn, get_rule_context, get_rule_index},
+            words::{get_random_words, is_offensive_word, is_word_used_in_lobby},
+        },
+        lobby::{
+            get::{get_lobby_info, get_lobby_player, get_lobby_players, get_lobby_settings},
+            patch::{add_connected_player, join_lobby},
+            ready_check::ack_ready_check,
+        },
+    },
+    errors::AppError,
+    games::lexi_wars::{engine::handle_incoming_messages, rules::get_enabled_rule_by_index},
+    models::{
+        game::{LobbyState, PlayerState},
+        lexi_wars::{BotSkill, LexiWarsClientMessage, PracticeBot},
+        lobby::JoinOutcome,
+    },
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+const MIN_RESPONSE_DELAY_MS: u64 = 500;
+const MAX_RESPONSE_DELAY_MS: u64 = 30_000;
+/// Words drawn from the dictionary per look for a fitting one
+const SAMPLE_SIZE: usize = 500;
+const SAMPLE_ATTEMPTS: usize = 3;
+
+pub fn is_bot(user_id: Uuid) -> bool {
+    BotSkill::from_user_id(user_id).is_some()
+}
+
+/// Adds a bot of the given skill to a free Lexi Wars lobby that is still
+/// waiting for players. Only the creator can add one.
+pub async fn add_practice_bot(
+    lobby_id: Uuid,
+    caller_id: Uuid,
+    skill: BotSkill,
+    response_delay_ms: Option<u64>,
+    redis: RedisClient,
+) -> Result<PracticeBot, AppError> {
+    let lobby_info = get_lobby_info(lobby_id, redis.clone()).await?;
+    if lobby_info.creator.id != caller_id {
+        return Err(AppError::Unauthorized(
+            "Only the creator can add bots".into(),
+        ));
+    }
+    if lobby_info.game.name != super::registration().name {
+        return Err(AppError::BadRequest("Bots can only play Lexi Wars".into()));
+    }
+    if lobby_info.contract_address.is_some() || lobby_info.entry_amount.is_some() {
+        return Err(AppError::BadRequest(
+            "Bots can only join free lobbies".into(),
+        ));
+    }
+    if lobby_info.state != LobbyState::Waiting {
+        return Err(AppError::BadRequest(
+            "Bots can only join lobbies waiting for players".into(),
+        ));
+    }
+
+    let response_delay_ms = response_delay_ms.unwrap_or(skill.default_response_delay_ms());
+    if !(MIN_RESPONSE_DELAY_MS..=MAX_RESPONSE_DELAY_MS).contains(&response_delay_ms) {
+        return Err(AppError::BadRequest(format!(
+            "Response delay must be between {MIN_RESPONSE_DELAY_MS} and {MAX_RESPONSE_DELAY_MS} ms"
+        )));
+    }
+
+    let user_id = ensure_bot_user(skill, redis.clone()).await?;
+    if join_lobby(lobby_id, user_id, None, PlayerState::Joined, redis.clone()).await?
+        == JoinOutcome::Closed
+    {
+        return Err(AppError::BadRequest(
+            "Bots can only join lobbies waiting for players".into(),
+        ));
+    }
+
+    let bot = PracticeBot {
+        user_id,
+        skill,
+        response_delay_ms,
+    };
+    add_lobby_bot(lobby_id, &bot, redis).await?;
+
+    tracing::info!("Added {:?} bot to lobby {}", skill, lobby_id);
+    Ok(bot)
+}
+
+/// Marks the lobby's bots connected, returning the ones that weren't yet.
+pub async fn connect_bots(
+    lobby_id: Uuid,
+    connected_player_ids: &[Uuid],
+    redis: &RedisClient,
+) -> Vec<Uuid> {
+    let players = match get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
+    {
+        Ok(players) => players,
+        Err(e) => {
+            tracing::error!("Failed to get players of lobby {}: {}", lobby_id, e);
+            return Vec::new();
+        }
+    };
+
+    let mut connected = Vec::new();
+    for player in players {
+        if !is_bot(player.id) || connected_player_ids.contains(&player.id) {
+            continue;
+        }
+        match add_connected_player(lobby_id, player.id, redis.clone()).await {
+            Ok(()) => connected.push(player.id),
+            Err(e) => tracing::error!("Failed to connect bot {}: {}", player.id, e),
+        }
+    }
+
+    connected
+}
+
+/// Bots are always ready, so they ack a ready-check as soon as it opens.
+pub async fn ack_ready_check_for_bots(lobby_id: Uuid, redis: &RedisClient) {
+    let players = match get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
+    {
+        Ok(players) => players,
+        Err(e) => {
+            tracing::error!("Failed to get players of lobby {}: {}", lobby_id, e);
+            return;
+        }
+    };
+
+    for player in players.into_iter().filter(|player| is_bot(player.id)) {
+        if let Err(e) = ack_ready_check(lobby_id, player.id, redis.clone()).await {
+            tracing::error!("Failed to ack ready-check for bot {}: {}", player.id, e);
+        }
+    }
+}
+
+/// Plays the bot's turn in the background: after its response delay it
+/// submits a word, unless it blanks and lets the turn time out.
+pub fn play_turn(
+    bot_id: Uuid,
+    lobby_id: Uuid,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) {
+    tokio::spawn(async move {
+        if let Err(e) = take_turn(bot_id, lobby_id, &connections, &redis, telegram_bot).await {
+            tracing::error!("Bot {} failed to play in lobby {}: {}", bot_id, lobby_id, e);
+        }
+    });
+}
+
+async fn take_turn(
+    bot_id: Uuid,
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    telegram_bot: Bot,
+) -> Result<(), AppError> {
+    let Some(bot) = get_lobby_bot(lobby_id, bot_id, redis.clone()).await? else {
+        return Err(AppError::NotFound(format!(
+            "Bot {} not found in lobby {}",
+            bot_id, lobby_id
+        )));
+    };
+
+    let (delay_ms, blank) = {
+        let mut rng = rng();
+        let jitter = rng.random_range(0.75..1.25);
+        (
+            (bot.response_delay_ms as f64 * jitter) as u64,
+            rng.random_bool(bot.skill.miss_chance()),
+        )
+    };
+    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+
+    if blank || get_current_turn(lobby_id, redis.clone()).await? != Some(bot_id) {
+        return Ok(());
+    }
+
+    let Some(word) = pick_word(lobby_id, bot.skill, redis).await? else {
+        tracing::info!("Bot {} found no word in lobby {}", bot_id, lobby_id);
+        return Ok(());
+    };
+
+    let player = get_lobby_player(lobby_id, bot_id, redis.clone()).await?;
+    let entry = serde_json::to_string(&LexiWarsClientMessage::WordEntry {
+        word,
+        action_id: None,
+    })
+    .map_err(|e| AppError::Serialization(format!("Failed to serialize word entry: {}", e)))?;
+    let receiver = futures::stream::iter([Ok(Message::Text(entry.into()))]);
+
+    handle_incoming_messages(
+        &player,
+        lobby_id,
+        receiver,
+        connections,
+        redis.clone(),
+        telegram_bot,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Draws dictionary words until one satisfies the current rule. Easy bots
+/// play the shortest fitting word, hard bots the longest.
+async fn pick_word(
+    lobby_id: Uuid,
+    skill: BotSkill,
+    redis: &RedisClient,
+) -> Result<Option<String>, AppError> {
+    let (rule_context, rule_index, settings) = tokio::join!(
+        get_rule_context(lobby_id, redis.clone()),
+        get_rule_index(lobby_id, redis.clone()),
+        get_lobby_settings(lobby_id, redis.clone())
+    );
+    let (Some(rule_context), Some(rule_index)) = (rule_context?, rule_index?) else {
+        return Ok(None);
+    };
+    let settings = settings?;
+    let enabled_rules = settings.enabled_rules();
+    let Some(rule) = get_enabled_rule_by_index(rule_index, &rule_context, enabled_rules.as_deref())
+    else {
+        return Ok(None);
+    };
+
+    for _ in 0..SAMPLE_ATTEMPTS {
+        let words = get_random_words(
+            SAMPLE_SIZE,
+            settings.dictionary_pack.as_deref(),
+            settings.language(),
+            redis.clone(),
+        )
+        .await?;
+
+        let mut candidates: Vec<String> = words
+            .into_iter()
+            .filter(|word| {
+                (rule.name == "min_length" || word.len() >= rule_context.min_word_length)
+                    && (rule.validate)(word, &rule_context).is_ok()
+            })
+            .collect();
+        match skill {
+            BotSkill::Easy => candidates.sort_by_key(String::len),
+            BotSkill::Medium => candidates.shuffle(&mut rng()),
+            BotSkill::Hard => candidates.sort_by_key(|word| std::cmp::Reverse(word.len())),
+        }
+
+        for word in candidates {
+            if is_word_used_in_lobby(lobby_id, &word, redis.clone()).await? {
+                continue;
+            }
+            if settings.family_friendly && is_offensive_word(&word, redis.clone()).await? {
+                continue;
+            }
+            return Ok(Some(word));
+        }
+    }
+
+    Ok(None)
+}
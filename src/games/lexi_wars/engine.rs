@@ -1,43 +1,79 @@
 use axum::extract::ws::Message;
 use chrono::Utc;
 use futures::StreamExt;
-use std::time::Duration;
-use tokio::time::sleep;
+use std::collections::HashMap;
 
 use crate::{
+    config::{self, AntiCheatMode},
     db::{
         game::{
-            player_words::add_player_used_word,
+            actions::{claim_action, is_action_claimed},
+            anticheat::{add_suspicion, record_suspicion_flag},
+            player_words::{add_player_used_word, get_players_used_words},
             state::{
-                add_eliminated_player, clear_lobby_game_state, get_current_turn,
-                get_eliminated_players, get_rule_context, get_rule_index, set_current_rule,
+                add_eliminated_player, add_player_score, add_response_time, add_skip_vote,
+                clear_lobby_game_state, get_current_rule, get_current_turn, get_eliminated_players,
+                get_game_pause, get_player_scores, get_response_times, get_rule_context,
+                get_rule_index, get_time_bank, get_turn_deadline, get_turn_started,
+                mark_turn_skipped, push_recent_word, schedule_turn_ms, set_current_rule,
                 set_current_turn, set_game_started, set_rule_context, set_rule_index,
+                set_time_bank, take_skip_votes,
             },
-            words::{add_used_word, is_valid_word, is_word_used_in_lobby},
+            word_reports::{ReportOutcome, record_rejected_word, report_word},
+            words::{add_used_word, is_offensive_word, is_valid_word, is_word_used_in_lobby},
         },
-        leaderboard::patch::update_user_stats,
         lobby::{
             get::{
                 get_connected_players_ids, get_current_players_ids, get_lobby_info,
-                get_lobby_players,
+                get_lobby_players, get_lobby_settings,
             },
             patch::{add_spectator, update_lobby_state},
             put::{create_current_players, remove_current_player},
         },
+        telegram::get::get_lobby_chat_id,
+        user::get::get_user_telegram_id,
     },
-    games::lexi_wars::{
-        rules::{RuleContext, get_rule_by_index, get_rules},
-        utils::{
-            broadcast_to_lobby_and_spectators, broadcast_to_player,
-            broadcast_to_player_and_spectators, generate_random_letter,
+    errors::AppError,
+    events::{GameEvent, emit},
+    games::{
+        core::{
+            EngineError, GameEngine, GameMessage,
+            bets::{handle_bet, settle_bets},
+            lifecycle,
+            prize::{get_placing_prize, get_prize, group_placings},
+            reactions::handle_reaction,
+            results::send_player_results,
+            turns::TurnBasedEngine,
+        },
+        lexi_wars::{
+            anticheat::check_submission,
+            bot::{is_bot, play_turn},
+            pause::{pause_game, resume_game},
+            rules::{RuleContext, get_enabled_rule_by_index, get_enabled_rules},
+            scoring::{score_wars_point_bonus, score_word, word_highlights},
+            tiebreak::{SurvivorRecord, rank_survivors, rank_survivors_by_words},
+            time_limit::start_time_limit,
+            utils::{
+                broadcast_to_lobby_and_spectators, broadcast_to_player,
+                broadcast_to_player_and_spectators, broadcast_to_spectators,
+                generate_random_letter, send_result_to_player,
+            },
         },
     },
     http::bot::{self, BotLobbyWinnerPayload, RunnerUp},
     models::{
-        game::{LobbyInfo, LobbyState, Player, PlayerState},
-        lexi_wars::{LexiWarsClientMessage, LexiWarsServerMessage, PlayerStanding},
+        admin::SuspicionFlag,
+        bet::{BetSettlement, SpectatorBet},
+        error_code::ErrorCode,
+        game::{Language, LobbyInfo, LobbyState, PauseReason, Player, Reaction},
+        lexi_wars::{GameEndReason, LexiWarsClientMessage, LexiWarsServerMessage, PlayerStanding},
+        lobby::LobbyRefund,
+        notification::NotificationEvent,
+        queue::QueuePolicy,
     },
+    notifications::notify,
     state::{ConnectionInfoMap, RedisClient},
+    ws::{errors::report_socket_error, handlers::utils::ack_results_for_player},
 };
 use teloxide::Bot;
 use uuid::Uuid;
@@ -46,6 +82,10 @@ use uuid::Uuid;
 struct GameContext {
     rule_context: RuleContext,
     rule_index: usize,
+    family_friendly: bool,
+    dictionary_pack: Option<String>,
+    language: Language,
+    enabled_rules: Option<Vec<String>>,
 }
 
 async fn validate_word(
@@ -60,23 +100,35 @@ async fn validate_word(
     let game_context = if let Some(cached) = cached_game_context {
         cached.clone()
     } else {
-        let (rule_context_result, rule_index_result) = tokio::join!(
+        let (rule_context_result, rule_index_result, settings_result) = tokio::join!(
             get_rule_context(lobby_id, redis.clone()),
-            get_rule_index(lobby_id, redis.clone())
+            get_rule_index(lobby_id, redis.clone()),
+            get_lobby_settings(lobby_id, redis.clone())
         );
 
         let rule_context = rule_context_result?.ok_or("No rule context found")?;
         let rule_index = rule_index_result?.ok_or("No rule index found")?;
 
+        let settings = settings_result?;
+
         GameContext {
             rule_context,
             rule_index,
+            family_friendly: settings.family_friendly,
+            enabled_rules: settings.enabled_rules(),
+            language: settings.language(),
+            dictionary_pack: settings.dictionary_pack,
         }
     };
 
     let (used_in_lobby_result, valid_word_result) = tokio::join!(
         is_word_used_in_lobby(lobby_id, &cleaned_word, redis.clone()),
-        is_valid_word(&cleaned_word, redis.clone())
+        is_valid_word(
+            &cleaned_word,
+            game_context.dictionary_pack.as_deref(),
+            game_context.language,
+            redis.clone()
+        )
     );
 
     if used_in_lobby_result? {
@@ -87,8 +139,16 @@ async fn validate_word(
         return Ok((game_context, false));
     }
 
+    if game_context.family_friendly && is_offensive_word(&cleaned_word, redis.clone()).await? {
+        return Ok((game_context, false));
+    }
+
     // Apply rule validation
-    if let Some(rule) = get_rule_by_index(game_context.rule_index, &game_context.rule_context) {
+    if let Some(rule) = get_enabled_rule_by_index(
+        game_context.rule_index,
+        &game_context.rule_context,
+        game_context.enabled_rules.as_deref(),
+    ) {
         // Check minimum word length first (unless it's the min_length rule itself)
         if rule.name != "min_length" {
             if cleaned_word.len() < game_context.rule_context.min_word_length {
@@ -107,130 +167,141 @@ async fn validate_word(
     Ok((game_context, true))
 }
 
-fn get_prize(
-    lobby_info: &crate::models::game::LobbyInfo,
-    connected_players_count: usize,
-    position: usize,
-) -> Option<f64> {
-    if lobby_info.contract_address.is_none() {
-        return None;
-    }
+/// Lexi Wars hooks for the shared engine core.
+pub struct LexiWars;
 
-    let entry_amount = lobby_info.entry_amount.unwrap_or(0.0);
-    let current_amount = lobby_info.current_amount.unwrap_or(0.0);
+impl GameMessage for LexiWarsServerMessage {
+    fn should_queue(&self) -> bool {
+        LexiWarsServerMessage::should_queue(self)
+    }
 
-    // Calculate total pool based on lobby type
-    let total_pool = if entry_amount == 0.0 {
-        // Sponsored lobby - use current_amount as the pre-funded pool
-        current_amount
-    } else {
-        // Regular paid lobby - calculate from entry amount * connected players
-        entry_amount * connected_players_count as f64
-    };
+    fn queue_policy(&self) -> QueuePolicy {
+        LexiWarsServerMessage::queue_policy(self)
+    }
+}
 
-    // No prizes if there's no pool
-    if total_pool <= 0.0 {
-        return None;
+impl TurnBasedEngine for LexiWars {
+    fn turn_countdown_message(remaining_secs: u64) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::Countdown {
+            time: remaining_secs,
+        }
     }
 
-    let prize = match position {
-        1 => {
-            if connected_players_count == 2 {
-                (total_pool * 70.0) / 100.0
-            } else {
-                (total_pool * 50.0) / 100.0
-            }
+    fn turn_message(current_turn: Player, countdown: u64) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::Turn {
+            current_turn,
+            countdown,
         }
-        2 => (total_pool * 30.0) / 100.0,
-        3 => (total_pool * 20.0) / 100.0,
-        _ => 0.0,
-    };
+    }
 
-    Some(prize)
+    async fn handle_turn_timeout(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        connections: ConnectionInfoMap,
+        redis: RedisClient,
+        bot: Bot,
+    ) {
+        handle_turn_timeout(player_id, lobby_id, connections, redis, bot).await;
+    }
 }
 
-fn calculate_wars_point(
-    lobby_info: &crate::models::game::LobbyInfo,
-    connected_players_count: usize,
-    rank: usize,
-    prize: Option<f64>,
-    player_id: Uuid,
-) -> f64 {
-    let base_point = (connected_players_count - rank + 1) * 2;
-    let mut total_point = base_point as f64;
-
-    // Add pool bonus if there's a pool (prize and entry amount exist)
-    if let (Some(prize_amount), Some(entry_amount)) = (prize, lobby_info.entry_amount) {
-        let pool_bonus = if entry_amount != 0.0 {
-            (prize_amount / connected_players_count as f64) + (entry_amount / 5.0)
-        } else {
-            0.0
-        };
-        total_point += pool_bonus;
+impl GameEngine for LexiWars {
+    type ServerMessage = LexiWarsServerMessage;
+
+    async fn start(
+        lobby_id: Uuid,
+        player_ids: Vec<Uuid>,
+        connections: &ConnectionInfoMap,
+        redis: RedisClient,
+        bot: Bot,
+    ) -> Result<(), EngineError> {
+        start_game(lobby_id, player_ids, connections, redis, bot).await
     }
 
-    // Add sponsor bonus if this is a sponsored lobby and the player is the sponsor (creator)
-    if let (Some(entry_amount), Some(current_amount)) =
-        (lobby_info.entry_amount, lobby_info.current_amount)
-    {
-        if entry_amount == 0.0 && current_amount > 0.0 && player_id == lobby_info.creator.id {
-            let sponsor_bonus = 2.5 * connected_players_count as f64;
-            total_point += sponsor_bonus;
+    async fn player_score(lobby_id: Uuid, player_id: Uuid, redis: &RedisClient) -> u64 {
+        match get_player_scores(lobby_id, redis.clone()).await {
+            Ok(scores) => scores.get(&player_id).copied().unwrap_or(0),
+            Err(e) => {
+                tracing::warn!("Failed to get scores for lobby {}: {}", lobby_id, e);
+                0
+            }
         }
     }
 
-    // Cap at 50 points maximum
-    total_point.min(50.0)
-}
+    fn wars_point_bonus(score: u64) -> f64 {
+        score_wars_point_bonus(score)
+    }
 
-async fn send_rank_prize_and_wars_point(
-    player_id: Uuid,
-    lobby_id: Uuid,
-    lobby_info: &crate::models::game::LobbyInfo,
-    connected_players_count: usize,
-    rank: usize,
-    connections: &ConnectionInfoMap,
-    redis: &RedisClient,
-) {
-    let prize = get_prize(lobby_info, connected_players_count, rank);
-    let wars_point =
-        calculate_wars_point(lobby_info, connected_players_count, rank, prize, player_id);
+    fn countdown_message(remaining_secs: u32) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::Start {
+            time: remaining_secs,
+            started: false,
+        }
+    }
 
-    // Send rank message
-    let rank_msg = LexiWarsServerMessage::Rank {
-        rank: rank.to_string(),
-    };
-    broadcast_to_player(player_id, lobby_id, &rank_msg, connections, redis).await;
+    fn start_failed_message() -> LexiWarsServerMessage {
+        LexiWarsServerMessage::StartFailed
+    }
 
-    // Send prize if applicable
-    if let Some(amount) = prize {
-        let prize_msg = LexiWarsServerMessage::Prize { amount };
-        broadcast_to_player(player_id, lobby_id, &prize_msg, connections, redis).await;
+    fn refund_message(refund: LobbyRefund) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::Refund { refund }
     }
 
-    // Send wars point message
-    let wars_point_msg = LexiWarsServerMessage::WarsPoint { wars_point };
-    broadcast_to_player(player_id, lobby_id, &wars_point_msg, connections, redis).await;
+    fn rank_message(rank: usize) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::Rank {
+            rank: rank.to_string(),
+        }
+    }
 
-    // Update user stats
-    match update_user_stats(player_id, lobby_id, rank, prize, wars_point, redis.clone()).await {
-        Ok(()) => {
-            tracing::info!(
-                "Player {} earned {} wars points (rank: {}, prize: {:?})",
-                player_id,
-                wars_point,
-                rank,
-                prize
-            );
+    fn prize_message(amount: f64, token_symbol: String) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::Prize {
+            amount,
+            token_symbol,
         }
-        Err(e) => {
-            tracing::error!(
-                "Failed to update user stats for player {}: {}",
-                player_id,
-                e
-            );
+    }
+
+    fn wars_point_message(wars_point: f64) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::WarsPoint { wars_point }
+    }
+
+    fn reaction_message(user_id: Uuid, reaction: Reaction) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::Reaction {
+            user_id,
+            emoji: reaction,
         }
     }
+
+    fn bet_placed_message(bet: SpectatorBet) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::BetPlaced { bet }
+    }
+
+    fn bet_settled_message(settlement: BetSettlement) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::BetSettled { settlement }
+    }
+
+    fn error_message(code: ErrorCode, message: String) -> LexiWarsServerMessage {
+        LexiWarsServerMessage::Error { code, message }
+    }
+
+    async fn send_to_player(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        msg: &LexiWarsServerMessage,
+        connections: &ConnectionInfoMap,
+        redis: &RedisClient,
+    ) {
+        broadcast_to_player(player_id, lobby_id, msg, connections, redis).await;
+    }
+
+    async fn send_result(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        msg: &LexiWarsServerMessage,
+        connections: &ConnectionInfoMap,
+        redis: &RedisClient,
+    ) {
+        send_result_to_player(player_id, lobby_id, msg, connections, redis).await;
+    }
 }
 
 pub async fn handle_incoming_messages(
@@ -268,25 +339,257 @@ pub async fn handle_incoming_messages(
                             )
                             .await;
                         }
-                        LexiWarsClientMessage::WordEntry { word } => {
+                        LexiWarsClientMessage::PlaceBet { winner_id, amount } => {
+                            handle_bet::<LexiWars>(
+                                lobby_id,
+                                player.id,
+                                winner_id,
+                                amount,
+                                connections,
+                                &redis,
+                            )
+                            .await;
+                        }
+                        LexiWarsClientMessage::Reaction { emoji } => {
+                            handle_reaction::<LexiWars>(
+                                lobby_id,
+                                player.id,
+                                emoji,
+                                connections,
+                                &redis,
+                            )
+                            .await;
+                        }
+                        LexiWarsClientMessage::ResultAck => {
+                            if let Err(e) =
+                                ack_results_for_player(player.id, lobby_id, &redis).await
+                            {
+                                tracing::error!(
+                                    "Failed to ack results for player {}: {}",
+                                    player.id,
+                                    e
+                                );
+                            }
+                        }
+                        LexiWarsClientMessage::ReportWord { word } => {
+                            let language = match get_lobby_settings(lobby_id, redis.clone()).await {
+                                Ok(settings) => settings.language(),
+                                Err(e) => {
+                                    report_socket_error::<LexiWars>(
+                                        player.id,
+                                        lobby_id,
+                                        &e,
+                                        connections,
+                                        &redis,
+                                    )
+                                    .await;
+                                    continue;
+                                }
+                            };
+
+                            let reply = match report_word(
+                                lobby_id,
+                                player.id,
+                                &word,
+                                language,
+                                redis.clone(),
+                            )
+                            .await
+                            {
+                                Ok(ReportOutcome::Reported | ReportOutcome::AlreadyReported) => {
+                                    LexiWarsServerMessage::WordReported {
+                                        word: word.trim().to_lowercase(),
+                                    }
+                                }
+                                Ok(ReportOutcome::NotRejected) => LexiWarsServerMessage::Error {
+                                    code: ErrorCode::BadRequest,
+                                    message: "Only your rejected words can be reported".to_string(),
+                                },
+                                Err(e) => {
+                                    report_socket_error::<LexiWars>(
+                                        player.id,
+                                        lobby_id,
+                                        &e,
+                                        connections,
+                                        &redis,
+                                    )
+                                    .await;
+                                    continue;
+                                }
+                            };
+                            broadcast_to_player(player.id, lobby_id, &reply, connections, &redis)
+                                .await;
+                        }
+                        LexiWarsClientMessage::VoteSkip => {
+                            if let Err(e) = handle_skip_vote(
+                                player.id,
+                                lobby_id,
+                                connections,
+                                &redis,
+                                &_telegram_bot_clone,
+                            )
+                            .await
+                            {
+                                report_socket_error::<LexiWars>(
+                                    player.id,
+                                    lobby_id,
+                                    &e,
+                                    connections,
+                                    &redis,
+                                )
+                                .await;
+                            }
+                        }
+                        LexiWarsClientMessage::PauseGame | LexiWarsClientMessage::ResumeGame => {
+                            let pause = matches!(parsed, LexiWarsClientMessage::PauseGame);
+                            let reply = match toggle_pause(
+                                player.id,
+                                lobby_id,
+                                pause,
+                                connections,
+                                &redis,
+                                &_telegram_bot_clone,
+                            )
+                            .await
+                            {
+                                Ok(None) => continue,
+                                Ok(Some((code, message))) => {
+                                    LexiWarsServerMessage::Error { code, message }
+                                }
+                                Err(e) => {
+                                    report_socket_error::<LexiWars>(
+                                        player.id,
+                                        lobby_id,
+                                        &e,
+                                        connections,
+                                        &redis,
+                                    )
+                                    .await;
+                                    continue;
+                                }
+                            };
+                            broadcast_to_player(player.id, lobby_id, &reply, connections, &redis)
+                                .await;
+                        }
+                        LexiWarsClientMessage::WordEntry { word, action_id } => {
                             let cleaned_word = word.trim().to_lowercase();
 
+                            match get_game_pause(lobby_id, redis.clone()).await {
+                                Ok(None) => {}
+                                Ok(Some(_)) => {
+                                    let paused_msg = LexiWarsServerMessage::Validate {
+                                        message: "The game is paused".to_string(),
+                                        code: ErrorCode::GamePaused,
+                                    };
+                                    broadcast_to_player(
+                                        player.id,
+                                        lobby_id,
+                                        &paused_msg,
+                                        connections,
+                                        &redis,
+                                    )
+                                    .await;
+                                    continue;
+                                }
+                                Err(e) => {
+                                    report_socket_error::<LexiWars>(
+                                        player.id,
+                                        lobby_id,
+                                        &e,
+                                        connections,
+                                        &redis,
+                                    )
+                                    .await;
+                                    continue;
+                                }
+                            }
+
+                            // A retried entry that was already applied only needs its ack
+                            if let Some(action_id) = &action_id {
+                                match is_action_claimed(
+                                    lobby_id,
+                                    player.id,
+                                    action_id,
+                                    redis.clone(),
+                                )
+                                .await
+                                {
+                                    Ok(false) => {}
+                                    Ok(true) => {
+                                        let ack_msg = LexiWarsServerMessage::ActionAck {
+                                            action_id: action_id.clone(),
+                                        };
+                                        broadcast_to_player(
+                                            player.id,
+                                            lobby_id,
+                                            &ack_msg,
+                                            connections,
+                                            &redis,
+                                        )
+                                        .await;
+                                        continue;
+                                    }
+                                    Err(e) => {
+                                        report_socket_error::<LexiWars>(
+                                            player.id,
+                                            lobby_id,
+                                            &e,
+                                            connections,
+                                            &redis,
+                                        )
+                                        .await;
+                                        continue;
+                                    }
+                                }
+                            }
+
                             // Check if it's the player's turn
                             let current_turn_id =
                                 match get_current_turn(lobby_id, redis.clone()).await {
                                     Ok(Some(id)) => id,
                                     Ok(None) => {
                                         tracing::error!("No current turn set");
+                                        let error_msg = LexiWarsServerMessage::Error {
+                                            code: ErrorCode::GameNotStarted,
+                                            message: "Game has not started yet".to_string(),
+                                        };
+                                        broadcast_to_player(
+                                            player.id,
+                                            lobby_id,
+                                            &error_msg,
+                                            connections,
+                                            &redis,
+                                        )
+                                        .await;
                                         continue;
                                     }
                                     Err(e) => {
-                                        tracing::error!("Failed to get current turn: {}", e);
+                                        report_socket_error::<LexiWars>(
+                                            player.id,
+                                            lobby_id,
+                                            &e,
+                                            connections,
+                                            &redis,
+                                        )
+                                        .await;
                                         continue;
                                     }
                                 };
 
                             if player.id != current_turn_id {
                                 tracing::info!("Not {}'s turn", player.id);
+                                let error_msg = LexiWarsServerMessage::Error {
+                                    code: ErrorCode::NotYourTurn,
+                                    message: "It's not your turn".to_string(),
+                                };
+                                broadcast_to_player(
+                                    player.id,
+                                    lobby_id,
+                                    &error_msg,
+                                    connections,
+                                    &redis,
+                                )
+                                .await;
                                 continue;
                             }
 
@@ -321,12 +624,46 @@ pub async fn handle_incoming_messages(
                                         &redis,
                                     )
                                     .await;
-                                } else if !is_valid_word(&cleaned_word, redis.clone())
+                                } else if !is_valid_word(
+                                    &cleaned_word,
+                                    game_context.dictionary_pack.as_deref(),
+                                    game_context.language,
+                                    redis.clone(),
+                                )
+                                .await
+                                .unwrap_or(false)
+                                {
+                                    if let Err(e) = record_rejected_word(
+                                        lobby_id,
+                                        player.id,
+                                        &cleaned_word,
+                                        redis.clone(),
+                                    )
                                     .await
-                                    .unwrap_or(false)
+                                    {
+                                        tracing::warn!("Failed to record rejected word: {}", e);
+                                    }
+                                    let validation_msg = LexiWarsServerMessage::Validate {
+                                        message: "Invalid word".to_string(),
+                                        code: ErrorCode::InvalidWord,
+                                    };
+                                    broadcast_to_player(
+                                        player.id,
+                                        lobby_id,
+                                        &validation_msg,
+                                        connections,
+                                        &redis,
+                                    )
+                                    .await;
+                                } else if game_context.family_friendly
+                                    && is_offensive_word(&cleaned_word, redis.clone())
+                                        .await
+                                        .unwrap_or(false)
                                 {
                                     let validation_msg = LexiWarsServerMessage::Validate {
-                                        msg: "Invalid word".to_string(),
+                                        message: "Word not allowed in family-friendly lobbies"
+                                            .to_string(),
+                                        code: ErrorCode::WordNotAllowed,
                                     };
                                     broadcast_to_player(
                                         player.id,
@@ -338,9 +675,10 @@ pub async fn handle_incoming_messages(
                                     .await;
                                 } else {
                                     // Rule validation failed
-                                    if let Some(rule) = get_rule_by_index(
+                                    if let Some(rule) = get_enabled_rule_by_index(
                                         game_context.rule_index,
                                         &game_context.rule_context,
+                                        game_context.enabled_rules.as_deref(),
                                     ) {
                                         if rule.name != "min_length"
                                             && cleaned_word.len()
@@ -350,8 +688,10 @@ pub async fn handle_incoming_messages(
                                                 "Word must be at least {} characters!",
                                                 game_context.rule_context.min_word_length
                                             );
-                                            let validation_msg =
-                                                LexiWarsServerMessage::Validate { msg: reason };
+                                            let validation_msg = LexiWarsServerMessage::Validate {
+                                                message: reason,
+                                                code: ErrorCode::RuleViolation,
+                                            };
                                             broadcast_to_player(
                                                 player.id,
                                                 lobby_id,
@@ -364,8 +704,10 @@ pub async fn handle_incoming_messages(
                                             &cleaned_word,
                                             &game_context.rule_context,
                                         ) {
-                                            let validation_msg =
-                                                LexiWarsServerMessage::Validate { msg: reason };
+                                            let validation_msg = LexiWarsServerMessage::Validate {
+                                                message: reason,
+                                                code: ErrorCode::RuleViolation,
+                                            };
                                             broadcast_to_player(
                                                 player.id,
                                                 lobby_id,
@@ -380,10 +722,58 @@ pub async fn handle_incoming_messages(
                                 continue;
                             }
 
+                            if check_submission_speed(
+                                player.id,
+                                lobby_id,
+                                &cleaned_word,
+                                connections,
+                                &redis,
+                                &_telegram_bot_clone,
+                            )
+                            .await
+                            {
+                                continue;
+                            }
+
+                            if let Some(action_id) = action_id {
+                                match claim_action(lobby_id, player.id, &action_id, redis.clone())
+                                    .await
+                                {
+                                    // Applied below, unless a concurrent retry got here first
+                                    Ok(claimed) => {
+                                        let ack_msg =
+                                            LexiWarsServerMessage::ActionAck { action_id };
+                                        broadcast_to_player(
+                                            player.id,
+                                            lobby_id,
+                                            &ack_msg,
+                                            connections,
+                                            &redis,
+                                        )
+                                        .await;
+                                        if !claimed {
+                                            continue;
+                                        }
+                                    }
+                                    Err(e) => {
+                                        report_socket_error::<LexiWars>(
+                                            player.id,
+                                            lobby_id,
+                                            &e,
+                                            connections,
+                                            &redis,
+                                        )
+                                        .await;
+                                        continue;
+                                    }
+                                }
+                            }
+
                             // Update current rule
-                            if let Some(rule) = get_rule_by_index(
+                            if let Some(rule) = get_enabled_rule_by_index(
                                 game_context.rule_index,
                                 &game_context.rule_context,
+                                game_context.enabled_rules.as_deref(),
                             ) {
                                 if let Err(e) = set_current_rule(
                                     lobby_id,
@@ -396,14 +786,39 @@ pub async fn handle_incoming_messages(
                                 }
                             }
 
-                            let (add_used_result, add_player_result, current_players_result) = tokio::join!(
-                                add_used_word(lobby_id, &cleaned_word, redis.clone()),
+                            // Score the word against the rule it had to satisfy
+                            let rule_name = get_enabled_rule_by_index(
+                                game_context.rule_index,
+                                &game_context.rule_context,
+                                game_context.enabled_rules.as_deref(),
+                            )
+                            .map(|rule| rule.name);
+                            let points = score_word(&cleaned_word, rule_name.as_deref());
+                            let total_score =
+                                match add_player_score(lobby_id, player.id, points, redis.clone())
+                                    .await
+                                {
+                                    Ok(total) => Some(total),
+                                    Err(e) => {
+                                        tracing::error!("Failed to add score: {}", e);
+                                        None
+                                    }
+                                };
+
+                            let (
+                                add_used_result,
+                                add_player_result,
+                                recent_result,
+                                current_players_result,
+                            ) = tokio::join!(
+                                add_used_word(lobby_id, &cleaned_word, redis.clone()),
                                 add_player_used_word(
                                     lobby_id,
                                     player.id,
                                     &cleaned_word,
                                     redis.clone()
                                 ),
+                                push_recent_word(lobby_id, player.id, &cleaned_word, redis.clone()),
                                 get_current_players_ids(lobby_id, redis.clone())
                             );
 
@@ -416,11 +831,22 @@ pub async fn handle_incoming_messages(
                                 tracing::error!("Failed to add player used word: {}", e);
                             }
 
+                            if let Err(e) = recent_result {
+                                tracing::error!("Failed to record recent word: {}", e);
+                            }
+
                             // Get current players to find next player
                             let current_players_ids = match current_players_result {
                                 Ok(ids) => ids,
                                 Err(e) => {
-                                    tracing::error!("Failed to get current players: {}", e);
+                                    report_socket_error::<LexiWars>(
+                                        player.id,
+                                        lobby_id,
+                                        &e,
+                                        connections,
+                                        &redis,
+                                    )
+                                    .await;
                                     continue;
                                 }
                             };
@@ -439,7 +865,12 @@ pub async fn handle_incoming_messages(
 
                                 if wrapped {
                                     // We wrapped back to first player, advance rules
-                                    let total_rules = get_rules(&game_context.rule_context).len();
+                                    let total_rules = get_enabled_rules(
+                                        &game_context.rule_context,
+                                        game_context.enabled_rules.as_deref(),
+                                    )
+                                    .len()
+                                    .max(1);
                                     new_rule_index = (game_context.rule_index + 1) % total_rules;
 
                                     // If we wrapped to first rule again, increase difficulty
@@ -471,6 +902,10 @@ pub async fn handle_incoming_messages(
                                     tracing::error!("Failed to update rule context: {}", e);
                                 }
 
+                                // Stop the player's clock before handing the turn on
+                                record_response_time(lobby_id, player.id, &redis).await;
+                                bank_turn_time(lobby_id, player.id, &redis).await;
+
                                 // Set next turn
                                 if let Err(e) =
                                     set_current_turn(lobby_id, next_player_id, redis.clone()).await
@@ -480,9 +915,11 @@ pub async fn handle_incoming_messages(
                                 }
 
                                 // Update current rule for next turn
-                                if let Some(next_rule) =
-                                    get_rule_by_index(new_rule_index, &new_rule_context)
-                                {
+                                if let Some(next_rule) = get_enabled_rule_by_index(
+                                    new_rule_index,
+                                    &new_rule_context,
+                                    game_context.enabled_rules.as_deref(),
+                                ) {
                                     if let Err(e) = set_current_rule(
                                         lobby_id,
                                         Some(next_rule.description.clone()),
@@ -496,6 +933,7 @@ pub async fn handle_incoming_messages(
                                     // Send rule to the next player (current turn)
                                     let rule_msg = LexiWarsServerMessage::Rule {
                                         rule: next_rule.description.clone(),
+                                        language: game_context.language,
                                     };
 
                                     broadcast_to_player_and_spectators(
@@ -508,9 +946,27 @@ pub async fn handle_incoming_messages(
                                     .await;
                                 }
 
-                                // Broadcast word entry to all players
+                                // Broadcast word entry to all players, never echoing
+                                // offensive words into a family-friendly lobby
+                                let word = if game_context.family_friendly
+                                    && is_offensive_word(&cleaned_word, redis.clone())
+                                        .await
+                                        .unwrap_or(true)
+                                {
+                                    "*".repeat(cleaned_word.len())
+                                } else {
+                                    cleaned_word.clone()
+                                };
+                                let score_msg = total_score.map(|total_score| {
+                                    LexiWarsServerMessage::ScoreUpdate {
+                                        player_id: player.id,
+                                        word: word.clone(),
+                                        points,
+                                        total_score,
+                                    }
+                                });
                                 let word_entry_msg = LexiWarsServerMessage::WordEntry {
-                                    word: cleaned_word.clone(),
+                                    word,
                                     sender: player.clone(),
                                 };
 
@@ -526,6 +982,17 @@ pub async fn handle_incoming_messages(
                                     )
                                     .await;
 
+                                    if let Some(score_msg) = &score_msg {
+                                        broadcast_to_lobby_and_spectators(
+                                            score_msg,
+                                            &players,
+                                            lobby_id,
+                                            connections,
+                                            &redis,
+                                        )
+                                        .await;
+                                    }
+
                                     // Find next player object for turn message
                                     if let Some(next_player) =
                                         players.iter().find(|p| p.id == next_player_id)
@@ -533,7 +1000,13 @@ pub async fn handle_incoming_messages(
                                         // Broadcast turn change to all players and spectators
                                         let next_turn_msg = LexiWarsServerMessage::Turn {
                                             current_turn: next_player.clone(),
-                                            countdown: 15,
+                                            countdown: turn_time_ms(
+                                                lobby_id,
+                                                next_player_id,
+                                                &redis,
+                                            )
+                                            .await
+                                            .div_ceil(1000),
                                         };
                                         broadcast_to_lobby_and_spectators(
                                             &next_turn_msg,
@@ -582,343 +1055,571 @@ pub async fn handle_incoming_messages(
     }
 }
 
-fn start_turn_timer(
+/// Pauses or resumes the game for the lobby creator. Returns the error code
+/// and message to reply with when there was nothing to do.
+async fn toggle_pause(
     player_id: Uuid,
     lobby_id: Uuid,
-    connections: ConnectionInfoMap,
-    redis: RedisClient,
-    telegram_bot: teloxide::Bot,
-) {
-    tokio::spawn(async move {
-        for i in (0..=15).rev() {
-            // Check if the turn is still this player's
-            match get_current_turn(lobby_id, redis.clone()).await {
-                Ok(Some(current_turn_id)) if current_turn_id == player_id => {
-                    // Send countdown to current player and spectators
-                    let countdown_msg = LexiWarsServerMessage::Countdown { time: i };
-                    broadcast_to_player(player_id, lobby_id, &countdown_msg, &connections, &redis)
-                        .await;
-
-                    // Send turn info to all players
-                    if let Ok(players) = get_lobby_players(lobby_id, None, redis.clone()).await {
-                        if let Some(current_player) =
-                            players.iter().find(|p| p.id == current_turn_id)
-                        {
-                            let turn_msg = LexiWarsServerMessage::Turn {
-                                current_turn: current_player.clone(),
-                                countdown: i,
-                            };
-                            broadcast_to_lobby_and_spectators(
-                                &turn_msg,
-                                &players,
-                                lobby_id,
-                                &connections,
-                                &redis,
-                            )
-                            .await;
-                        }
-                    }
-                }
-                Ok(Some(_)) => {
-                    // Turn has already changed, stop timer
-                    let countdown_msg = LexiWarsServerMessage::Countdown { time: 15 };
-
-                    broadcast_to_player(player_id, lobby_id, &countdown_msg, &connections, &redis)
-                        .await;
-                    tracing::info!("Turn changed, stopping timer for player {}", player_id);
-                    return;
-                }
-                Ok(None) => {
-                    tracing::error!("No current turn set for lobby {}", lobby_id);
-                    return;
-                }
-                Err(e) => {
-                    tracing::error!("Failed to check current turn: {}", e);
-                    return;
-                }
-            }
+    pause: bool,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    telegram_bot: &Bot,
+) -> Result<Option<(ErrorCode, String)>, AppError> {
+    let lobby_info = get_lobby_info(lobby_id, redis.clone()).await?;
+    if lobby_info.creator.id != player_id {
+        let action = if pause { "pause" } else { "resume" };
+        return Ok(Some((
+            ErrorCode::NotLobbyCreator,
+            format!("Only the creator can {action} the game"),
+        )));
+    }
 
-            sleep(Duration::from_secs(1)).await;
+    if pause {
+        if !pause_game(
+            lobby_id,
+            PauseReason::Creator,
+            connections,
+            redis,
+            telegram_bot,
+        )
+        .await?
+        {
+            return Ok(Some((
+                ErrorCode::InvalidLobbyState,
+                "The game is already paused or has no turn running".to_string(),
+            )));
         }
+    } else if !resume_game(lobby_id, connections, redis, telegram_bot).await? {
+        return Ok(Some((
+            ErrorCode::InvalidLobbyState,
+            "The game is not paused".to_string(),
+        )));
+    }
 
-        // Time ran out - eliminate player
-        match get_current_turn(lobby_id, redis.clone()).await {
-            Ok(Some(current_turn_id)) if current_turn_id == player_id => {
-                tracing::info!("Player {} timed out in lobby {}", player_id, lobby_id);
+    Ok(None)
+}
 
-                // Handle turn timeout - eliminate player and advance turn
-                if let Ok(current_players) = get_current_players_ids(lobby_id, redis.clone()).await
-                {
-                    // Eliminate the player
-                    if let Err(e) = add_eliminated_player(lobby_id, player_id, redis.clone()).await
-                    {
-                        tracing::error!("Failed to eliminate player: {}", e);
-                        return;
-                    }
+/// Counts a vote to skip the current player. Voting opens halfway through the
+/// turn; once a majority of the other players agree the turn ends early, and
+/// a player skipped before is eliminated as if their time ran out.
+async fn handle_skip_vote(
+    voter_id: Uuid,
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    telegram_bot: &Bot,
+) -> Result<(), AppError> {
+    let Some(current_turn) = get_current_turn(lobby_id, redis.clone()).await? else {
+        return Err(AppError::BadRequest("No turn is being played".into()));
+    };
+    if get_game_pause(lobby_id, redis.clone()).await?.is_some() {
+        return Err(AppError::BadRequest("The game is paused".into()));
+    }
+    if current_turn == voter_id {
+        return Err(AppError::BadRequest(
+            "You can't vote to skip your own turn".into(),
+        ));
+    }
 
-                    // Add eliminated player as spectator so they can continue watching
-                    if let Err(e) = add_spectator(lobby_id, player_id, redis.clone()).await {
-                        tracing::error!("Failed to add eliminated player as spectator: {}", e);
-                    }
-                    let spectator_msg = LexiWarsServerMessage::Spectator;
-                    broadcast_to_player(player_id, lobby_id, &spectator_msg, &connections, &redis)
-                        .await;
+    let current_players = get_current_players_ids(lobby_id, redis.clone()).await?;
+    if !current_players.contains(&voter_id) {
+        return Err(AppError::BadRequest(
+            "Only players still in the game can vote".into(),
+        ));
+    }
 
-                    // Remove from current players (don't touch connected players)
-                    if let Err(e) = remove_current_player(lobby_id, player_id, redis.clone()).await
-                    {
-                        tracing::error!("Failed to remove timed out player from current: {}", e);
-                        return;
-                    }
+    let (started_ms, deadline_ms) = tokio::join!(
+        get_turn_started(lobby_id, redis.clone()),
+        get_turn_deadline(lobby_id, redis.clone())
+    );
+    let (Some(started_ms), Some(deadline_ms)) = (started_ms?, deadline_ms?) else {
+        return Err(AppError::BadRequest("No turn is being played".into()));
+    };
+    if Utc::now().timestamp_millis() < started_ms + (deadline_ms - started_ms) / 2 {
+        return Err(AppError::BadRequest(
+            "Skip votes open halfway through the turn".into(),
+        ));
+    }
 
-                    // Get updated current players and calculate position for stats
-                    let remaining_players =
-                        match get_current_players_ids(lobby_id, redis.clone()).await {
-                            Ok(players) => players,
-                            Err(e) => {
-                                tracing::error!("Failed to get remaining players: {}", e);
-                                return;
-                            }
-                        };
-
-                    let connected_player_ids =
-                        match get_connected_players_ids(lobby_id, redis.clone()).await {
-                            Ok(ids) => ids,
-                            Err(e) => {
-                                tracing::error!("Failed to get connected players: {}", e);
-                                return;
-                            }
-                        };
+    let votes = add_skip_vote(lobby_id, voter_id, redis.clone()).await?;
+    let required = current_players.len().saturating_sub(1) / 2 + 1;
 
-                    // Broadcast updated players count
-                    let players_count_msg = LexiWarsServerMessage::PlayersCount {
-                        connected_players: connected_player_ids.len(),
-                        remaining_players: remaining_players.len(),
-                    };
-                    if let Ok(players) = get_lobby_players(lobby_id, None, redis.clone()).await {
-                        broadcast_to_lobby_and_spectators(
-                            &players_count_msg,
-                            &players,
-                            lobby_id,
-                            &connections,
-                            &redis,
-                        )
-                        .await;
-                    }
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+    let vote_msg = LexiWarsServerMessage::SkipVote {
+        player_id: current_turn,
+        votes,
+        required,
+    };
+    broadcast_to_lobby_and_spectators(&vote_msg, &players, lobby_id, connections, redis).await;
 
-                    let position = remaining_players.len() + 1;
+    if votes < required || !take_skip_votes(lobby_id, redis.clone()).await? {
+        return Ok(());
+    }
 
-                    // Get lobby info and connected players count for prize calculation
-                    if let Ok(lobby_info) = get_lobby_info(lobby_id, redis.clone()).await {
-                        let connected_players_count = connected_player_ids.len();
+    if !mark_turn_skipped(lobby_id, current_turn, redis.clone()).await? {
+        tracing::info!(
+            "Player {} was voted out again in lobby {}",
+            current_turn,
+            lobby_id
+        );
+        handle_turn_timeout(
+            current_turn,
+            lobby_id,
+            connections.clone(),
+            redis.clone(),
+            telegram_bot.clone(),
+        )
+        .await;
+        return Ok(());
+    }
 
-                        // Send stats to eliminated player
-                        send_rank_prize_and_wars_point(
-                            player_id,
-                            lobby_id,
-                            &lobby_info,
-                            connected_players_count,
-                            position,
-                            &connections,
-                            &redis,
-                        )
-                        .await;
-                    }
+    tracing::info!(
+        "Skipping turn of player {} in lobby {}",
+        current_turn,
+        lobby_id
+    );
 
-                    if remaining_players.len() <= 1 {
-                        // Game over
-                        if let Err(e) = end_game(
-                            lobby_id,
-                            connected_player_ids,
-                            &connections,
-                            redis.clone(),
-                            telegram_bot.clone(),
-                        )
-                        .await
-                        {
-                            tracing::error!("Failed to end game: {}", e);
-                        }
-                    } else {
-                        // Find next active player
-                        if let Some(current_index) =
-                            current_players.iter().position(|&id| id == player_id)
-                        {
-                            let next_index = current_index % remaining_players.len();
-                            let next_player_id = remaining_players[next_index];
+    let index = current_players
+        .iter()
+        .position(|&id| id == current_turn)
+        .ok_or_else(|| AppError::NotFound("Current player is not in the game".into()))?;
+    let next_player_id = current_players[(index + 1) % current_players.len()];
 
-                            // Set next turn
-                            if let Err(e) =
-                                set_current_turn(lobby_id, next_player_id, redis.clone()).await
-                            {
-                                tracing::error!("Failed to set current turn: {}", e);
-                                return;
-                            }
+    bank_turn_time(lobby_id, current_turn, redis).await;
+    set_current_turn(lobby_id, next_player_id, redis.clone()).await?;
 
-                            // Notify all players about elimination and next turn
-                            if let Ok(players) =
-                                get_lobby_players(lobby_id, None, redis.clone()).await
-                            {
-                                if let Some(next_player) =
-                                    players.iter().find(|p| p.id == next_player_id)
-                                {
-                                    let next_turn_msg = LexiWarsServerMessage::Turn {
-                                        current_turn: next_player.clone(),
-                                        countdown: 15,
-                                    };
-                                    broadcast_to_lobby_and_spectators(
-                                        &next_turn_msg,
-                                        &players,
-                                        lobby_id,
-                                        &connections,
-                                        &redis,
-                                    )
-                                    .await;
-                                }
-                            }
+    let skipped_msg = LexiWarsServerMessage::TurnSkipped {
+        player_id: current_turn,
+    };
+    broadcast_to_lobby_and_spectators(&skipped_msg, &players, lobby_id, connections, redis).await;
+
+    // The rule stays the same, it just has a new player to answer it
+    if let Some(rule) = get_current_rule(lobby_id, redis.clone()).await? {
+        let settings = get_lobby_settings(lobby_id, redis.clone()).await?;
+        let rule_msg = LexiWarsServerMessage::Rule {
+            rule,
+            language: settings.language(),
+        };
+        broadcast_to_player_and_spectators(&rule_msg, next_player_id, lobby_id, connections, redis)
+            .await;
+    }
 
-                            // Start timer for next player
-                            start_turn_timer(
-                                next_player_id,
-                                lobby_id,
-                                connections,
-                                redis,
-                                telegram_bot.clone(),
-                            );
-                        }
-                    }
-                }
+    if let Some(next_player) = players.iter().find(|p| p.id == next_player_id) {
+        let turn_msg = LexiWarsServerMessage::Turn {
+            current_turn: next_player.clone(),
+            countdown: turn_time_ms(lobby_id, next_player_id, redis)
+                .await
+                .div_ceil(1000),
+        };
+        broadcast_to_lobby_and_spectators(&turn_msg, &players, lobby_id, connections, redis).await;
+    }
+
+    start_turn_timer(
+        next_player_id,
+        lobby_id,
+        connections.clone(),
+        redis.clone(),
+        telegram_bot.clone(),
+    );
+
+    Ok(())
+}
+
+/// Runs the anti-cheat heuristics on an accepted word. Returns true when the
+/// player was eliminated and the word must be dropped.
+async fn check_submission_speed(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    word: &str,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    telegram_bot: &teloxide::Bot,
+) -> bool {
+    let config = config::get();
+    if config.anticheat_mode == AntiCheatMode::Off {
+        return false;
+    }
+
+    let started_ms = match get_turn_started(lobby_id, redis.clone()).await {
+        Ok(Some(started_ms)) => started_ms,
+        Ok(None) => return false,
+        Err(e) => {
+            tracing::error!("Failed to get turn start for lobby {}: {}", lobby_id, e);
+            return false;
+        }
+    };
+    let elapsed_ms = Utc::now()
+        .timestamp_millis()
+        .saturating_sub(started_ms)
+        .max(0) as u64;
+
+    let Some(suspicion) = check_submission(word, elapsed_ms) else {
+        return false;
+    };
+
+    let suspicion_score =
+        match add_suspicion(lobby_id, player_id, suspicion.weight(), redis.clone()).await {
+            Ok(score) => score,
+            Err(e) => {
+                tracing::error!("Failed to add suspicion for {}: {}", player_id, e);
+                return false;
             }
-            Ok(Some(_)) => {
-                // Turn has already changed, nothing to do
-                tracing::debug!("Turn has already changed for lobby {}", lobby_id);
+        };
+    tracing::warn!(
+        "Suspicious submission from {} in lobby {}: {:?} ({}ms for {:?}), score {}",
+        player_id,
+        lobby_id,
+        suspicion,
+        elapsed_ms,
+        word,
+        suspicion_score
+    );
+
+    let warning_msg = LexiWarsServerMessage::SuspicionWarning {
+        reason: suspicion.reason().to_string(),
+        suspicion_score,
+        threshold: config.anticheat_threshold,
+    };
+    broadcast_to_player(player_id, lobby_id, &warning_msg, connections, redis).await;
+
+    // Only act once, on the submission that crosses the threshold
+    let previous_score = suspicion_score - suspicion.weight();
+    if suspicion_score < config.anticheat_threshold || previous_score >= config.anticheat_threshold
+    {
+        return false;
+    }
+
+    let eliminate = config.anticheat_mode == AntiCheatMode::Eliminate;
+    let flag = SuspicionFlag {
+        lobby_id,
+        player_id,
+        suspicion_score,
+        reason: suspicion.reason().to_string(),
+        eliminated: eliminate,
+        timestamp: Utc::now(),
+    };
+    if let Err(e) = record_suspicion_flag(&flag, redis.clone()).await {
+        tracing::error!("Failed to flag {} for review: {}", player_id, e);
+    }
+
+    if eliminate {
+        tracing::info!(
+            "Eliminating {} in lobby {} for cheating",
+            player_id,
+            lobby_id
+        );
+        // Same path as running out of time, it is still this player's turn
+        handle_turn_timeout(
+            player_id,
+            lobby_id,
+            connections.clone(),
+            redis.clone(),
+            telegram_bot.clone(),
+        )
+        .await;
+    }
+
+    eliminate
+}
+
+/// How long the player's turn lasts: what is left of their bank in a
+/// time-bank lobby, the difficulty's turn timer otherwise.
+pub(crate) async fn turn_time_ms(lobby_id: Uuid, player_id: Uuid, redis: &RedisClient) -> u64 {
+    let settings = match get_lobby_settings(lobby_id, redis.clone()).await {
+        Ok(settings) => Some(settings),
+        Err(e) => {
+            tracing::warn!("Failed to get settings for lobby {}: {}", lobby_id, e);
+            None
+        }
+    };
+
+    if let Some(bank_secs) = settings.as_ref().and_then(|s| s.time_bank_secs) {
+        return match get_time_bank(lobby_id, player_id, redis.clone()).await {
+            Ok(remaining_ms) => remaining_ms.unwrap_or(bank_secs * 1000),
+            Err(e) => {
+                tracing::error!("Failed to get time bank of {}: {}", player_id, e);
+                bank_secs * 1000
             }
-            Ok(None) => {
-                tracing::error!("No current turn set for lobby {}", lobby_id);
+        };
+    }
+
+    settings
+        .and_then(|settings| settings.difficulty().turn_timer_secs())
+        .unwrap_or(config::get().turn_timer_secs)
+        * 1000
+}
+
+/// Adds how long the player took to find their word, a tiebreak between
+/// players still in the game when it ends.
+async fn record_response_time(lobby_id: Uuid, player_id: Uuid, redis: &RedisClient) {
+    let started_ms = match get_turn_started(lobby_id, redis.clone()).await {
+        Ok(Some(started_ms)) => started_ms,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!("Failed to get turn start for lobby {}: {}", lobby_id, e);
+            return;
+        }
+    };
+    let elapsed_ms = Utc::now()
+        .timestamp_millis()
+        .saturating_sub(started_ms)
+        .max(0) as u64;
+
+    if let Err(e) = add_response_time(lobby_id, player_id, elapsed_ms, redis.clone()).await {
+        tracing::error!("Failed to record response time of {}: {}", player_id, e);
+    }
+}
+
+/// Saves what is left of the player's bank once they play their word. The
+/// turn deadline is where their bank runs out, so that is what is kept.
+async fn bank_turn_time(lobby_id: Uuid, player_id: Uuid, redis: &RedisClient) {
+    match get_lobby_settings(lobby_id, redis.clone()).await {
+        Ok(settings) if settings.time_bank_secs.is_some() => {}
+        Ok(_) => return,
+        Err(e) => {
+            tracing::warn!("Failed to get settings for lobby {}: {}", lobby_id, e);
+            return;
+        }
+    }
+
+    let deadline_ms = match get_turn_deadline(lobby_id, redis.clone()).await {
+        Ok(Some(deadline_ms)) => deadline_ms,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!("Failed to get turn deadline for lobby {}: {}", lobby_id, e);
+            return;
+        }
+    };
+    let remaining_ms = deadline_ms
+        .saturating_sub(Utc::now().timestamp_millis())
+        .max(0) as u64;
+
+    if let Err(e) = set_time_bank(lobby_id, player_id, remaining_ms, redis.clone()).await {
+        tracing::error!("Failed to save time bank of {}: {}", player_id, e);
+    }
+}
+
+fn start_turn_timer(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: teloxide::Bot,
+) {
+    tokio::spawn(async move {
+        // Players watching the game already see the turn change
+        if is_bot(player_id) {
+            play_turn(
+                player_id,
+                lobby_id,
+                connections.clone(),
+                redis.clone(),
+                telegram_bot.clone(),
+            );
+        } else if !connections.lock().await.contains_key(&player_id) {
+            notify(
+                player_id,
+                NotificationEvent::YourTurn { lobby_id },
+                telegram_bot.clone(),
+                redis.clone(),
+            );
+        }
+
+        // Countdowns and the timeout itself are driven by the turn scheduler
+        let turn_ms = turn_time_ms(lobby_id, player_id, &redis).await;
+        match schedule_turn_ms(lobby_id, player_id, turn_ms, redis.clone()).await {
+            Ok(Some(previous)) if previous != player_id => {
+                let time = turn_time_ms(lobby_id, previous, &redis)
+                    .await
+                    .div_ceil(1000);
+                let reset_msg = LexiWarsServerMessage::Countdown { time };
+                broadcast_to_player(previous, lobby_id, &reset_msg, &connections, &redis).await;
             }
+            Ok(_) => {}
             Err(e) => {
-                tracing::error!("Failed to check current turn: {}", e);
+                tracing::error!("Failed to schedule turn for lobby {}: {}", lobby_id, e);
             }
         }
     });
 }
 
-pub fn start_auto_start_timer(
+/// Called by the turn scheduler once a turn deadline has passed.
+async fn handle_turn_timeout(
+    player_id: Uuid,
     lobby_id: Uuid,
     connections: ConnectionInfoMap,
     redis: RedisClient,
     telegram_bot: teloxide::Bot,
 ) {
-    tokio::spawn(async move {
-        for i in (0..=15).rev() {
-            // Get current lobby state from Redis
-            let connected_player_ids =
-                match get_connected_players_ids(lobby_id, redis.clone()).await {
-                    Ok(ids) => ids,
-                    Err(e) => {
-                        tracing::error!("Failed to get connected players: {}", e);
-                        return;
-                    }
-                };
+    // Time ran out - eliminate player if it is still their turn
+    match get_current_turn(lobby_id, redis.clone()).await {
+        Ok(Some(current_turn_id)) if current_turn_id == player_id => {
+            tracing::info!("Player {} timed out in lobby {}", player_id, lobby_id);
+
+            // Handle turn timeout - eliminate player and advance turn
+            if let Ok(current_players) = get_current_players_ids(lobby_id, redis.clone()).await {
+                // Eliminate the player
+                if let Err(e) = add_eliminated_player(lobby_id, player_id, redis.clone()).await {
+                    tracing::error!("Failed to eliminate player: {}", e);
+                    return;
+                }
+                emit(GameEvent::PlayerEliminated {
+                    lobby_id,
+                    player_id,
+                });
+
+                // Add eliminated player as spectator so they can continue watching
+                if let Err(e) = add_spectator(lobby_id, player_id, redis.clone()).await {
+                    tracing::error!("Failed to add eliminated player as spectator: {}", e);
+                }
+                let spectator_msg = LexiWarsServerMessage::Spectator;
+                broadcast_to_player(player_id, lobby_id, &spectator_msg, &connections, &redis)
+                    .await;
+
+                // Remove from current players (don't touch connected players)
+                if let Err(e) = remove_current_player(lobby_id, player_id, redis.clone()).await {
+                    tracing::error!("Failed to remove timed out player from current: {}", e);
+                    return;
+                }
 
-            let lobby_players =
-                match get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await {
+                // Get updated current players and calculate position for stats
+                let remaining_players = match get_current_players_ids(lobby_id, redis.clone()).await
+                {
                     Ok(players) => players,
                     Err(e) => {
-                        tracing::error!("Failed to get lobby players: {}", e);
+                        tracing::error!("Failed to get remaining players: {}", e);
                         return;
                     }
                 };
 
-            let connected_count = connected_player_ids.len();
-            let total_players = lobby_players.len();
-
-            tracing::info!(
-                "Auto-start timer: {}s, connected: {}/{}",
-                i,
-                connected_count,
-                total_players
-            );
+                let connected_player_ids =
+                    match get_connected_players_ids(lobby_id, redis.clone()).await {
+                        Ok(ids) => ids,
+                        Err(e) => {
+                            tracing::error!("Failed to get connected players: {}", e);
+                            return;
+                        }
+                    };
 
-            // If all players are connected, start immediately
-            if connected_count == total_players {
-                tracing::info!("All players connected, starting game early");
-                if let Err(e) = start_game(
-                    lobby_id,
-                    connected_player_ids,
-                    &connections,
-                    redis.clone(),
-                    telegram_bot.clone(),
-                )
-                .await
-                {
-                    tracing::error!("Failed to start game: {}", e);
+                // Broadcast updated players count
+                let players_count_msg = LexiWarsServerMessage::PlayersCount {
+                    connected_players: connected_player_ids.len(),
+                    remaining_players: remaining_players.len(),
+                };
+                if let Ok(players) = get_lobby_players(lobby_id, None, redis.clone()).await {
+                    broadcast_to_lobby_and_spectators(
+                        &players_count_msg,
+                        &players,
+                        lobby_id,
+                        &connections,
+                        &redis,
+                    )
+                    .await;
                 }
-                return;
-            }
 
-            // Send countdown update to connected players
-            let start_msg = LexiWarsServerMessage::Start {
-                time: i,
-                started: false,
-            };
-            for player_id in &connected_player_ids {
-                broadcast_to_player(*player_id, lobby_id, &start_msg, &connections, &redis).await;
-            }
+                let position = remaining_players.len() + 1;
+
+                // Get lobby info and connected players count for prize calculation
+                if let Ok(lobby_info) = get_lobby_info(lobby_id, redis.clone()).await {
+                    let connected_players_count = connected_player_ids.len();
 
-            if i == 0 {
-                // Timer expired, check if we have sufficient players
-                let required_players = std::cmp::max(2, (total_players + 1) / 2); // At least 2 players and 50% (rounded up)
-
-                tracing::info!(
-                    "Auto-start timer expired: connected {}/{}, required: {}",
-                    connected_count,
-                    total_players,
-                    required_players
-                );
-
-                if connected_count >= required_players && connected_count >= 2 {
-                    tracing::info!(
-                        "Sufficient players connected ({}%), starting game",
-                        (connected_count * 100) / total_players
-                    );
-                    if let Err(e) = start_game(
+                    // Send stats to eliminated player
+                    send_player_results::<LexiWars>(
+                        player_id,
+                        &lobby_info,
+                        connected_players_count,
+                        position,
+                        &connections,
+                        &redis,
+                    )
+                    .await;
+                }
+
+                if remaining_players.len() <= 1 {
+                    // Game over
+                    if let Err(e) = end_game(
                         lobby_id,
                         connected_player_ids,
                         &connections,
                         redis.clone(),
                         telegram_bot.clone(),
+                        None,
                     )
                     .await
                     {
-                        tracing::error!("Failed to start game: {}", e);
+                        tracing::error!("Failed to end game: {}", e);
                     }
                 } else {
-                    tracing::info!("Not enough players connected, canceling game");
-                    let start_failed_msg = LexiWarsServerMessage::StartFailed;
-                    for player_id in &connected_player_ids {
-                        broadcast_to_player(
-                            *player_id,
-                            lobby_id,
-                            &start_failed_msg,
-                            &connections,
-                            &redis,
-                        )
-                        .await;
-                    }
-
-                    // Reset lobby state
-                    if let Err(e) =
-                        update_lobby_state(lobby_id, LobbyState::Waiting, redis.clone()).await
+                    // Find next active player
+                    if let Some(current_index) =
+                        current_players.iter().position(|&id| id == player_id)
                     {
-                        tracing::error!("Error updating game state to Waiting: {}", e);
+                        let next_index = current_index % remaining_players.len();
+                        let next_player_id = remaining_players[next_index];
+
+                        // Set next turn
+                        if let Err(e) =
+                            set_current_turn(lobby_id, next_player_id, redis.clone()).await
+                        {
+                            tracing::error!("Failed to set current turn: {}", e);
+                            return;
+                        }
+
+                        // Notify all players about elimination and next turn
+                        if let Ok(players) = get_lobby_players(lobby_id, None, redis.clone()).await
+                        {
+                            if let Some(next_player) =
+                                players.iter().find(|p| p.id == next_player_id)
+                            {
+                                let next_turn_msg = LexiWarsServerMessage::Turn {
+                                    current_turn: next_player.clone(),
+                                    countdown: turn_time_ms(lobby_id, next_player_id, &redis)
+                                        .await
+                                        .div_ceil(1000),
+                                };
+                                broadcast_to_lobby_and_spectators(
+                                    &next_turn_msg,
+                                    &players,
+                                    lobby_id,
+                                    &connections,
+                                    &redis,
+                                )
+                                .await;
+                            }
+                        }
+
+                        // Start timer for next player
+                        start_turn_timer(
+                            next_player_id,
+                            lobby_id,
+                            connections,
+                            redis,
+                            telegram_bot.clone(),
+                        );
                     }
                 }
-                return;
             }
-
-            sleep(Duration::from_secs(1)).await;
         }
-    });
+        Ok(Some(_)) => {
+            // Turn has already changed, nothing to do
+            tracing::debug!("Turn has already changed for lobby {}", lobby_id);
+        }
+        Ok(None) => {
+            tracing::error!("No current turn set for lobby {}", lobby_id);
+        }
+        Err(e) => {
+            tracing::error!("Failed to check current turn: {}", e);
+        }
+    }
+}
+
+pub fn start_auto_start_timer(
+    lobby_id: Uuid,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: teloxide::Bot,
+) {
+    lifecycle::start_auto_start_timer::<LexiWars>(lobby_id, connections, redis, telegram_bot);
 }
 
 async fn start_game(
@@ -927,7 +1628,7 @@ async fn start_game(
     connections: &ConnectionInfoMap,
     redis: RedisClient,
     telegram_bot: teloxide::Bot,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<(), EngineError> {
     // Set game as started
     set_game_started(lobby_id, true, redis.clone()).await?;
 
@@ -942,8 +1643,12 @@ async fn start_game(
         set_current_turn(lobby_id, first_player_id, redis.clone()).await?;
 
         // Get rule context and set first rule
+        let settings = get_lobby_settings(lobby_id, redis.clone()).await?;
+        let enabled_rules = settings.enabled_rules();
         if let Some(rule_context) = get_rule_context(lobby_id, redis.clone()).await? {
-            if let Some(first_rule) = get_rule_by_index(0, &rule_context) {
+            if let Some(first_rule) =
+                get_enabled_rule_by_index(0, &rule_context, enabled_rules.as_deref())
+            {
                 set_current_rule(
                     lobby_id,
                     Some(first_rule.description.clone()),
@@ -954,6 +1659,7 @@ async fn start_game(
                 // Send the rule to the current player
                 let rule_msg = LexiWarsServerMessage::Rule {
                     rule: first_rule.description,
+                    language: settings.language(),
                 };
                 broadcast_to_player_and_spectators(
                     &rule_msg,
@@ -970,7 +1676,9 @@ async fn start_game(
         if let Some(first_player) = players.iter().find(|p| p.id == first_player_id) {
             let turn_msg = LexiWarsServerMessage::Turn {
                 current_turn: first_player.clone(),
-                countdown: 15,
+                countdown: turn_time_ms(lobby_id, first_player_id, &redis)
+                    .await
+                    .div_ceil(1000),
             };
             broadcast_to_lobby_and_spectators(&turn_msg, &players, lobby_id, connections, &redis)
                 .await;
@@ -1004,6 +1712,17 @@ async fn start_game(
         )
         .await;
 
+        if let Some(max_game_secs) = settings.max_game_secs {
+            start_time_limit(
+                lobby_id,
+                max_game_secs,
+                connections.clone(),
+                redis.clone(),
+                telegram_bot.clone(),
+            )
+            .await?;
+        }
+
         // Start turn timer for first player
         start_turn_timer(
             first_player_id,
@@ -1029,6 +1748,7 @@ async fn end_game(
     connections: &ConnectionInfoMap,
     redis: RedisClient,
     telegram_bot: Bot,
+    reason: Option<GameEndReason>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Update game state first to prevent race conditions
     update_lobby_state(lobby_id, LobbyState::Finished, redis.clone()).await?;
@@ -1038,21 +1758,68 @@ async fn end_game(
     let lobby_info = get_lobby_info(lobby_id, redis.clone()).await?;
     let connected_players_count = connected_player_ids.len();
 
+    let scores = get_player_scores(lobby_id, redis.clone())
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to get scores for lobby {}: {}", lobby_id, e);
+            HashMap::new()
+        });
+    let score_of = |player_id: &Uuid| scores.get(player_id).copied().unwrap_or(0);
+
+    let player_ids: Vec<Uuid> = players.iter().map(|p| p.id).collect();
+    let used_words = get_players_used_words(lobby_id, &player_ids, redis.clone())
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to get used words for lobby {}: {}", lobby_id, e);
+            HashMap::new()
+        });
+    let words_of = |player_id: &Uuid| used_words.get(player_id).cloned().unwrap_or_default();
+
+    let response_times = get_response_times(lobby_id, redis.clone())
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to get response times for lobby {}: {}", lobby_id, e);
+            HashMap::new()
+        });
+
+    // Several survivors (e.g. a force-ended game) are ordered by score, then
+    // by how fast they found their words. At the time limit words played come first
+    let rank = match reason {
+        Some(GameEndReason::TimeLimitReached) => rank_survivors_by_words,
+        None => rank_survivors,
+    };
+    let remaining_players = get_current_players_ids(lobby_id, redis.clone())
+        .await
+        .map(|ids| {
+            let survivors = ids
+                .into_iter()
+                .map(|player_id| SurvivorRecord {
+                    player_id,
+                    score: score_of(&player_id),
+                    words: used_words.get(&player_id).map_or(0, Vec::len) as u64,
+                    response_ms: response_times.get(&player_id).copied().unwrap_or(0),
+                })
+                .collect();
+            rank(survivors)
+        });
+
+    // Survivors level on every tiebreak draw: they share the rank and its prizes
+    let survivor_placings = match &remaining_players {
+        Ok(groups) => group_placings(groups),
+        Err(_) => Vec::new(),
+    };
+
     // Handle remaining player(s) - give them final ranking
-    if let Ok(remaining_players) = get_current_players_ids(lobby_id, redis.clone()).await {
-        for (index, &remaining_player_id) in remaining_players.iter().enumerate() {
-            let final_rank = index + 1;
-            send_rank_prize_and_wars_point(
-                remaining_player_id,
-                lobby_id,
-                &lobby_info,
-                connected_players_count,
-                final_rank,
-                connections,
-                &redis,
-            )
-            .await;
-        }
+    for &(remaining_player_id, placing) in &survivor_placings {
+        send_player_results::<LexiWars>(
+            remaining_player_id,
+            &lobby_info,
+            connected_players_count,
+            placing,
+            connections,
+            &redis,
+        )
+        .await;
     }
 
     // Get eliminated players for final standing
@@ -1062,15 +1829,19 @@ async fn end_game(
     let mut final_standings = Vec::new();
 
     // Add remaining players first (winners)
-    if let Ok(remaining_player_ids) = get_current_players_ids(lobby_id, redis.clone()).await {
-        for (index, &player_id) in remaining_player_ids.iter().enumerate() {
-            if let Some(mut player) = players.iter().find(|p| p.id == player_id).cloned() {
-                let rank = index + 1;
-                // Calculate and set the prize for this player
-                player.prize = get_prize(&lobby_info, connected_players_count, rank);
-
-                final_standings.push(PlayerStanding { player, rank });
-            }
+    for &(player_id, placing) in &survivor_placings {
+        if let Some(mut player) = players.iter().find(|p| p.id == player_id).cloned() {
+            // Calculate and set the prize for this player
+            player.prize = get_placing_prize(&lobby_info, connected_players_count, placing);
+
+            let words = words_of(&player_id);
+            final_standings.push(PlayerStanding {
+                player,
+                rank: placing.rank,
+                score: score_of(&player_id),
+                highlights: word_highlights(&words),
+                used_words: words,
+            });
         }
     }
 
@@ -1081,34 +1852,76 @@ async fn end_game(
             // Calculate and set the prize for this player
             player.prize = get_prize(&lobby_info, connected_players_count, rank);
 
-            final_standings.push(PlayerStanding { player, rank });
+            let words = words_of(&player_id);
+            final_standings.push(PlayerStanding {
+                player,
+                rank,
+                score: score_of(&player_id),
+                highlights: word_highlights(&words),
+                used_words: words,
+            });
         }
     }
 
-    // Send game over messages
-    let gameover_msg = LexiWarsServerMessage::GameOver;
-    broadcast_to_lobby_and_spectators(&gameover_msg, &players, lobby_id, connections, &redis).await;
+    // A drawn first place has no single winner, so bets on it are returned
+    let drawn_first = survivor_placings
+        .first()
+        .is_some_and(|(_, placing)| placing.tied > 1);
+    let winner_id = if drawn_first {
+        None
+    } else {
+        final_standings.first().map(|standing| standing.player.id)
+    };
+    settle_bets::<LexiWars>(lobby_id, winner_id, connections, &redis).await;
+    emit(GameEvent::GameEnded {
+        lobby_id,
+        winner_id,
+    });
 
-    // Broadcast final standing
+    // Send game over and final standing, players must ack them like their results
+    let gameover_msg = LexiWarsServerMessage::GameOver { reason };
     let final_standing_msg = LexiWarsServerMessage::FinalStanding {
         standing: final_standings.iter().cloned().collect(),
     };
-    broadcast_to_lobby_and_spectators(&final_standing_msg, &players, lobby_id, connections, &redis)
+    for player in &players {
+        send_result_to_player(player.id, lobby_id, &gameover_msg, connections, &redis).await;
+        send_result_to_player(
+            player.id,
+            lobby_id,
+            &final_standing_msg,
+            connections,
+            &redis,
+        )
         .await;
+    }
+    broadcast_to_spectators(&gameover_msg, lobby_id, connections, &redis).await;
+    broadcast_to_spectators(&final_standing_msg, lobby_id, connections, &redis).await;
 
     if let Some(tg_msg_id) = lobby_info.tg_msg_id {
+        let redis = redis.clone();
         tokio::spawn(async move {
-            let winner_payload = create_winner_payload(
+            let mut winner_payload = create_winner_payload(
                 lobby_id,
                 &lobby_info,
                 &final_standings,
                 connected_players_count,
                 tg_msg_id,
             );
-            let chat_id = std::env::var("TELEGRAM_CHAT_ID")
-                .expect("TELEGRAM_CHAT_ID must be set")
-                .parse::<i64>()
-                .unwrap();
+            winner_payload.winner_telegram_id =
+                get_user_telegram_id(final_standings[0].player.id, redis.clone())
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to look up winner telegram id: {}", e);
+                        None
+                    });
+            let chat_id = match get_lobby_chat_id(&lobby_info, redis).await {
+                Ok(Some(chat_id)) => chat_id,
+                Ok(None) => return,
+                Err(e) => {
+                    tracing::error!("Failed to look up lobby announcement chat: {}", e);
+                    return;
+                }
+            };
 
             if let Err(e) =
                 bot::broadcast_lobby_winner(&telegram_bot, chat_id, winner_payload).await
@@ -1127,6 +1940,45 @@ async fn end_game(
     Ok(())
 }
 
+/// Ends a game that cannot be resumed, settling it with the current standings.
+pub async fn force_end_game(
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let connected_player_ids = get_connected_players_ids(lobby_id, redis.clone()).await?;
+    end_game(
+        lobby_id,
+        connected_player_ids,
+        connections,
+        redis,
+        telegram_bot,
+        None,
+    )
+    .await
+}
+
+/// Ends a game that reached its time limit, ranking the players still in it
+/// by words played.
+pub async fn end_game_at_time_limit(
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let connected_player_ids = get_connected_players_ids(lobby_id, redis.clone()).await?;
+    end_game(
+        lobby_id,
+        connected_player_ids,
+        connections,
+        redis,
+        telegram_bot,
+        Some(GameEndReason::TimeLimitReached),
+    )
+    .await
+}
+
 fn create_winner_payload(
     lobby_id: Uuid,
     lobby_info: &LobbyInfo,
@@ -1207,7 +2059,9 @@ fn create_winner_payload(
         winner_name,
         winner_wallet,
         winner_prize: winner.player.prize,
+        winner_telegram_id: None,
         entry_amount: lobby_info.entry_amount,
+        token_symbol: lobby_info.pool_symbol().to_string(),
         runner_ups,
         tg_msg_id,
     }
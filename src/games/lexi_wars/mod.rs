@@ -1,5 +1,45 @@
+pub mod anticheat;
+pub mod bot;
 pub mod engine;
+pub mod pause;
+pub mod recovery;
+pub mod rule_dsl;
 pub mod rules;
+pub mod scoring;
+pub mod settings;
+pub mod tiebreak;
+pub mod time_limit;
 pub mod utils;
 
 pub use engine::{handle_incoming_messages, start_auto_start_timer};
+
+use axum::routing::get;
+use futures::FutureExt;
+
+use crate::{
+    games::{core::turns::TurnHooks, registry::GameRegistration},
+    ws::handlers::lexi_wars_handler,
+};
+
+/// Name Lexi Wars is registered and stored under
+pub const NAME: &str = "Lexi Wars";
+
+pub fn registration() -> GameRegistration {
+    GameRegistration {
+        name: NAME,
+        description: "A word battle game where players compete with words.",
+        image_url: "https://res.cloudinary.com/dapbvli1v/image/upload/Lexi_Wars2_yuuoam.png",
+        tags: &["word", "strategy", "multiplayer"],
+        min_players: 2,
+        max_players: None,
+        ws_path: "/ws/lexiwars/{lobby_id}",
+        ws_handler: || get(lexi_wars_handler),
+        validate_settings: |settings, redis| {
+            settings::validate_lobby_settings(settings, redis).boxed()
+        },
+        turns: Some(TurnHooks::of::<engine::LexiWars>()),
+        recover: |lobby_id, remaining_ms, connections, redis, bot| {
+            recovery::recover_game(lobby_id, remaining_ms, connections, redis, bot).boxed()
+        },
+    }
+}
@@ -0,0 +1,214 @@
+//! Pausing a running game. A pause takes the turn deadline off the scheduler
+//! and keeps what was left of it, so nobody loses time while the game waits.
+//! The creator can pause and resume, and the game pauses on its own while
+//! most players are disconnected. A pause never outlasts `MAX_PAUSE_SECS`:
+//! the game then resumes, or ends if most players are still gone.
+
+use std::time::Duration;
+
+use teloxide::Bot;
+use uuid::Uuid;
+
+use crate::{
+    config,
+    db::{
+        game::state::{
+            get_current_turn, get_game_pause, pause_turn, schedule_turn_ms, take_game_pause,
+        },
+        lobby::get::{get_current_players_ids, get_lobby_players},
+    },
+    errors::AppError,
+    games::lexi_wars::{
+        bot::{is_bot, play_turn},
+        engine::{force_end_game, turn_time_ms},
+        utils::broadcast_to_lobby_and_spectators,
+    },
+    models::{game::PauseReason, lexi_wars::LexiWarsServerMessage},
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+/// Freezes the current turn and tells everyone. Returns false when the game
+/// was already paused or had no running turn.
+pub async fn pause_game(
+    lobby_id: Uuid,
+    reason: PauseReason,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    telegram_bot: &Bot,
+) -> Result<bool, AppError> {
+    let Some(pause) = pause_turn(lobby_id, reason, redis.clone()).await? else {
+        return Ok(false);
+    };
+
+    let max_pause_secs = config::get().max_pause_secs;
+    tracing::info!("Paused lobby {} ({:?})", lobby_id, reason);
+
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+    let paused_msg = LexiWarsServerMessage::GamePaused {
+        reason,
+        paused_at: pause.paused_at,
+        resumes_at: pause.expires_at(max_pause_secs),
+    };
+    broadcast_to_lobby_and_spectators(&paused_msg, &players, lobby_id, connections, redis).await;
+
+    let connections = connections.clone();
+    let redis = redis.clone();
+    let telegram_bot = telegram_bot.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(max_pause_secs)).await;
+        if let Err(e) = expire_pause(
+            lobby_id,
+            pause.paused_at,
+            &connections,
+            &redis,
+            &telegram_bot,
+        )
+        .await
+        {
+            tracing::error!("Failed to end pause of lobby {}: {}", lobby_id, e);
+        }
+    });
+
+    Ok(true)
+}
+
+/// Gives the current player their turn back with the time they had left.
+/// Returns false when the game wasn't paused.
+pub async fn resume_game(
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    telegram_bot: &Bot,
+) -> Result<bool, AppError> {
+    let Some(pause) = take_game_pause(lobby_id, redis.clone()).await? else {
+        return Ok(false);
+    };
+    let Some(current_turn) = get_current_turn(lobby_id, redis.clone()).await? else {
+        return Ok(false);
+    };
+
+    // The turn may have moved on just as the game was paused
+    let turn_ms = if current_turn == pause.player_id {
+        pause.remaining_ms
+    } else {
+        turn_time_ms(lobby_id, current_turn, redis).await
+    };
+    schedule_turn_ms(lobby_id, current_turn, turn_ms, redis.clone()).await?;
+    tracing::info!("Resumed lobby {}", lobby_id);
+
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+    broadcast_to_lobby_and_spectators(
+        &LexiWarsServerMessage::GameResumed,
+        &players,
+        lobby_id,
+        connections,
+        redis,
+    )
+    .await;
+    if let Some(current_player) = players.iter().find(|p| p.id == current_turn) {
+        let turn_msg = LexiWarsServerMessage::Turn {
+            current_turn: current_player.clone(),
+            countdown: turn_ms.div_ceil(1000),
+        };
+        broadcast_to_lobby_and_spectators(&turn_msg, &players, lobby_id, connections, redis).await;
+    }
+
+    if is_bot(current_turn) {
+        play_turn(
+            current_turn,
+            lobby_id,
+            connections.clone(),
+            redis.clone(),
+            telegram_bot.clone(),
+        );
+    }
+
+    Ok(true)
+}
+
+/// Pauses the game once more than half of the players still in it are
+/// disconnected, and resumes a pause they caused once enough are back.
+pub async fn check_connected_players(
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    telegram_bot: &Bot,
+) -> Result<(), AppError> {
+    let majority_gone = majority_disconnected(lobby_id, connections, redis).await?;
+
+    match get_game_pause(lobby_id, redis.clone()).await? {
+        None if majority_gone => {
+            pause_game(
+                lobby_id,
+                PauseReason::Disconnects,
+                connections,
+                redis,
+                telegram_bot,
+            )
+            .await?;
+        }
+        Some(pause) if pause.reason == PauseReason::Disconnects && !majority_gone => {
+            resume_game(lobby_id, connections, redis, telegram_bot).await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+async fn majority_disconnected(
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) -> Result<bool, AppError> {
+    // Bots never connect and never leave
+    let player_ids: Vec<Uuid> = get_current_players_ids(lobby_id, redis.clone())
+        .await?
+        .into_iter()
+        .filter(|id| !is_bot(*id))
+        .collect();
+
+    let online = connections.lock().await;
+    let disconnected = player_ids
+        .iter()
+        .filter(|id| !online.contains_key(id))
+        .count();
+
+    Ok(disconnected * 2 > player_ids.len())
+}
+
+/// Runs once a pause reaches `MAX_PAUSE_SECS`, unless the game was resumed
+/// (or paused again) in the meantime.
+async fn expire_pause(
+    lobby_id: Uuid,
+    paused_at: i64,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    telegram_bot: &Bot,
+) -> Result<(), AppError> {
+    match get_game_pause(lobby_id, redis.clone()).await? {
+        Some(pause) if pause.paused_at == paused_at => {}
+        _ => return Ok(()),
+    }
+
+    if !majority_disconnected(lobby_id, connections, redis).await? {
+        tracing::info!("Pause of lobby {} ran out, resuming", lobby_id);
+        resume_game(lobby_id, connections, redis, telegram_bot).await?;
+        return Ok(());
+    }
+
+    // Claim the pause so the game can't be resumed while it ends
+    if take_game_pause(lobby_id, redis.clone()).await?.is_none() {
+        return Ok(());
+    }
+    tracing::info!(
+        "Pause of lobby {} ran out with most players gone, ending game",
+        lobby_id
+    );
+    if let Err(e) = force_end_game(lobby_id, connections, redis.clone(), telegram_bot.clone()).await
+    {
+        tracing::error!("Failed to end lobby {}: {}", lobby_id, e);
+    }
+
+    Ok(())
+}
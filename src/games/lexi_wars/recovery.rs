@@ -0,0 +1,56 @@
+use teloxide::Bot;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        game::state::{get_current_turn, get_game_ends_at, take_game_pause},
+        lobby::get::get_current_players_ids,
+    },
+    games::{
+        core::{EngineError, recovery::resume_turn},
+        lexi_wars::{engine::force_end_game, time_limit::arm_time_limit},
+    },
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+/// Gives the current turn its timer back, along with the game's time limit.
+/// A game without a live turn or with a single player left is ended.
+pub async fn recover_game(
+    lobby_id: Uuid,
+    remaining_ms: Option<i64>,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) -> Result<(), EngineError> {
+    let current_players = get_current_players_ids(lobby_id, redis.clone()).await?;
+    let current_turn = get_current_turn(lobby_id, redis.clone()).await?;
+
+    // A paused game comes back running, with the turn time it was paused with
+    let remaining_ms = match take_game_pause(lobby_id, redis.clone()).await? {
+        Some(pause) => Some(pause.remaining_ms as i64),
+        None => remaining_ms,
+    };
+
+    match current_turn {
+        Some(player_id) if current_players.len() > 1 && current_players.contains(&player_id) => {
+            let turn_secs =
+                resume_turn(lobby_id, player_id, remaining_ms, &redis, &telegram_bot).await?;
+
+            if let Some(ends_at_ms) = get_game_ends_at(lobby_id, redis.clone()).await? {
+                arm_time_limit(lobby_id, ends_at_ms, connections, redis, telegram_bot);
+            }
+
+            tracing::info!(
+                "Resumed lobby {} with {}s left for player {}",
+                lobby_id,
+                turn_secs,
+                player_id
+            );
+            Ok(())
+        }
+        _ => {
+            tracing::info!("Lobby {} cannot be resumed, ending game", lobby_id);
+            force_end_game(lobby_id, &connections, redis, telegram_bot).await
+        }
+    }
+}
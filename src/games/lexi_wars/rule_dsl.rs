@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, PoisonError, RwLock};
+use utoipa::ToSchema;
+
+use crate::{
+    errors::AppError,
+    games::lexi_wars::rules::{Rule, RuleContext, builtin_rule_names},
+};
+
+/// Check a custom rule applies to a word. Text params may use `{letter}` for
+/// the turn's random letter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleCheck {
+    MinLength { length: usize },
+    MaxLength { length: usize },
+    ExactLength { length: usize },
+    StartsWith { text: String },
+    EndsWith { text: String },
+    Contains { text: String },
+    NotContains { text: String },
+}
+
+impl RuleCheck {
+    fn passes(&self, word: &str, ctx: &RuleContext) -> bool {
+        let length = word.chars().count();
+        match self {
+            RuleCheck::MinLength { length: min } => length >= *min,
+            RuleCheck::MaxLength { length: max } => length <= *max,
+            RuleCheck::ExactLength { length: exact } => length == *exact,
+            RuleCheck::StartsWith { text } => word.starts_with(&render(text, ctx)),
+            RuleCheck::EndsWith { text } => word.ends_with(&render(text, ctx)),
+            RuleCheck::Contains { text } => word.contains(&render(text, ctx)),
+            RuleCheck::NotContains { text } => !word.contains(&render(text, ctx)),
+        }
+    }
+}
+
+/// A rule defined at runtime instead of compiled into the server.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RuleDefinition {
+    pub name: String,
+    #[serde(flatten)]
+    pub check: RuleCheck,
+    /// Shown to players, `{letter}` and `{min_length}` are filled in per turn
+    pub description: String,
+}
+
+impl RuleDefinition {
+    pub fn validate(&self) -> Result<(), AppError> {
+        let valid_name = (1..=32).contains(&self.name.len())
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid_name {
+            return Err(AppError::BadRequest(
+                "Rule name must be 1-32 lowercase letters, digits or '_'".into(),
+            ));
+        }
+        if builtin_rule_names().contains(&self.name) {
+            return Err(AppError::BadRequest(format!(
+                "{} is a built-in rule",
+                self.name
+            )));
+        }
+        if self.description.trim().is_empty() || self.description.len() > 200 {
+            return Err(AppError::BadRequest(
+                "Rule description must be 1-200 characters".into(),
+            ));
+        }
+
+        let valid_check = match &self.check {
+            RuleCheck::MinLength { length }
+            | RuleCheck::MaxLength { length }
+            | RuleCheck::ExactLength { length } => (1..=30).contains(length),
+            RuleCheck::StartsWith { text }
+            | RuleCheck::EndsWith { text }
+            | RuleCheck::Contains { text }
+            | RuleCheck::NotContains { text } => (1..=16).contains(&text.len()),
+        };
+        if !valid_check {
+            return Err(AppError::BadRequest(
+                "Rule lengths must be 1-30 and texts 1-16 characters".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn to_rule(&self, ctx: &RuleContext) -> Rule {
+        let check = self.check.clone();
+        let description = self.description.clone();
+        Rule {
+            name: self.name.clone(),
+            description: render(&self.description, ctx),
+            validate: Arc::new(move |word: &str, ctx: &RuleContext| {
+                if check.passes(word, ctx) {
+                    Ok(())
+                } else {
+                    Err(render(&description, ctx))
+                }
+            }),
+        }
+    }
+}
+
+fn render(template: &str, ctx: &RuleContext) -> String {
+    template
+        .replace("{letter}", &ctx.random_letter.to_string())
+        .replace("{min_length}", &ctx.min_word_length.to_string())
+}
+
+struct RuleBook {
+    definitions: Vec<RuleDefinition>,
+    order: Vec<String>,
+}
+
+/// Local copy of the custom rules and rule order kept in Redis, so rules stay
+/// synchronous for the engine.
+static RULE_BOOK: RwLock<RuleBook> = RwLock::new(RuleBook {
+    definitions: Vec::new(),
+    order: Vec::new(),
+});
+
+/// Replaces the in-memory custom rules and rule order.
+pub fn install_rule_book(definitions: Vec<RuleDefinition>, order: Vec<String>) {
+    let mut book = RULE_BOOK.write().unwrap_or_else(PoisonError::into_inner);
+    book.definitions = definitions;
+    book.order = order;
+}
+
+pub fn custom_rules() -> Vec<RuleDefinition> {
+    RULE_BOOK
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .definitions
+        .clone()
+}
+
+/// Moves the rules named in the admin order to the front, in that order.
+/// Rules it doesn't mention keep their default order after them.
+pub(crate) fn order_rules(mut rules: Vec<Rule>) -> Vec<Rule> {
+    let book = RULE_BOOK.read().unwrap_or_else(PoisonError::into_inner);
+    if book.order.is_empty() {
+        return rules;
+    }
+
+    rules.sort_by_key(|rule| {
+        book.order
+            .iter()
+            .position(|name| *name == rule.name)
+            .unwrap_or(usize::MAX)
+    });
+    rules
+}
@@ -1,25 +1,62 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::games::lexi_wars::rule_dsl;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RuleContext {
     pub min_word_length: usize,
     pub random_letter: char,
 }
 
+pub type RuleValidator = Arc<dyn Fn(&str, &RuleContext) -> Result<(), String> + Send + Sync>;
+
 #[derive(Clone)]
 pub struct Rule {
     pub name: String,
     pub description: String,
-    pub validate: fn(&str, &RuleContext) -> Result<(), String>,
+    pub validate: RuleValidator,
+}
+
+fn builtin(validate: fn(&str, &RuleContext) -> Result<(), String>) -> RuleValidator {
+    Arc::new(validate)
 }
 
+/// Every rule in progression order: the built-in rules followed by the
+/// custom ones, rearranged by the admin rule order when one is set.
 pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
+    let mut rules = builtin_rules(ctx);
+    rules.extend(
+        rule_dsl::custom_rules()
+            .iter()
+            .map(|definition| definition.to_rule(ctx)),
+    );
+    rule_dsl::order_rules(rules)
+}
+
+/// Names of the rules compiled into the server, which custom rules can't reuse.
+pub fn builtin_rule_names() -> Vec<String> {
+    builtin_rules(&rule_names_context())
+        .into_iter()
+        .map(|rule| rule.name)
+        .collect()
+}
+
+fn rule_names_context() -> RuleContext {
+    RuleContext {
+        min_word_length: 0,
+        random_letter: 'a',
+    }
+}
+
+fn builtin_rules(ctx: &RuleContext) -> Vec<Rule> {
     vec![
         Rule {
             name: "min_length".to_string(),
             description: format!("Word must be at least {} characters!", ctx.min_word_length),
-            validate: |word, ctx| {
+            validate: builtin(|word, ctx| {
                 if word.len() < ctx.min_word_length {
                     Err(format!(
                         "Word must be at least {} characters!",
@@ -28,7 +65,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "contains_letter".to_string(),
@@ -36,13 +73,13 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must contain the letter '{}' and be at least {} characters long",
                 ctx.random_letter, ctx.min_word_length
             ),
-            validate: |word, ctx| {
+            validate: builtin(|word, ctx| {
                 if !word.contains(ctx.random_letter) {
                     Err(format!("Word must contain '{}'", ctx.random_letter))
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "not_contains_letter".to_string(),
@@ -50,13 +87,13 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must NOT contain the letter '{}' and be at least {} characters long",
                 ctx.random_letter, ctx.min_word_length
             ),
-            validate: |word, ctx| {
+            validate: builtin(|word, ctx| {
                 if word.contains(ctx.random_letter) {
                     Err(format!("Word must NOT contain '{}'", ctx.random_letter))
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "starts_with_letter".to_string(),
@@ -64,13 +101,13 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must start with the letter '{}' and be at least {} characters long",
                 ctx.random_letter, ctx.min_word_length
             ),
-            validate: |word, ctx| {
+            validate: builtin(|word, ctx| {
                 if !word.starts_with(ctx.random_letter) {
                     Err(format!("Word must start with '{}'", ctx.random_letter))
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "ends_with_letter".to_string(),
@@ -78,13 +115,13 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must end with the letter '{}' and be at least {} characters long",
                 ctx.random_letter, ctx.min_word_length
             ),
-            validate: |word, ctx| {
+            validate: builtin(|word, ctx| {
                 if !word.ends_with(ctx.random_letter) {
                     Err(format!("Word must end with '{}'", ctx.random_letter))
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "ends_with_tion".to_string(),
@@ -92,13 +129,13 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must end with 'tion' and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 if !word.ends_with("tion") {
                     Err("Word must end with 'tion'".to_string())
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "starts_with_co".to_string(),
@@ -106,13 +143,13 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must start with 'co' and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 if !word.starts_with("co") {
                     Err("Word must start with 'co'".to_string())
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "double_letters".to_string(),
@@ -120,7 +157,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must contain at least two pairs of double letters and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 let chars: Vec<char> = word.chars().collect();
                 let mut double_letter_count = 0;
                 let mut i = 0;
@@ -139,12 +176,12 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "exact_length".to_string(),
             description: format!("Word must have exactly {} letters", ctx.min_word_length + 2),
-            validate: |word, ctx| {
+            validate: builtin(|word, ctx| {
                 let target_length = ctx.min_word_length + 2;
                 if word.len() != target_length {
                     Err(format!(
@@ -154,7 +191,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "consonant_start_end".to_string(),
@@ -162,7 +199,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must start and end with a consonant and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 let chars: Vec<char> = word.to_lowercase().chars().collect();
                 if chars.is_empty() {
                     return Err("Word cannot be empty".to_string());
@@ -177,7 +214,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "vowel_start_end".to_string(),
@@ -185,7 +222,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must start and end with a vowel and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 let chars: Vec<char> = word.to_lowercase().chars().collect();
                 if chars.is_empty() {
                     return Err("Word cannot be empty".to_string());
@@ -200,7 +237,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "triple_letter".to_string(),
@@ -208,7 +245,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must contain at least one letter that appears exactly three times and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 let mut letter_counts: HashMap<char, usize> = HashMap::new();
                 for ch in word.chars() {
                     *letter_counts.entry(ch).or_insert(0) += 1;
@@ -223,7 +260,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "palindrome".to_string(),
@@ -231,14 +268,14 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must be a palindrome and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 let reversed: String = word.chars().rev().collect();
                 if word != reversed {
                     Err("Word must be a palindrome".to_string())
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "no_repeating_letters".to_string(),
@@ -246,14 +283,14 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must have no repeating letters and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 let unique_chars: std::collections::HashSet<char> = word.chars().collect();
                 if unique_chars.len() != word.len() {
                     Err("Word must have no repeating letters!".to_string())
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "exact_vowels_consonants".to_string(),
@@ -261,7 +298,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must contain exactly 3 vowels and 3 consonants and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 let vowels = "aeiou";
                 let mut vowel_count = 0;
                 let mut consonant_count = 0;
@@ -281,7 +318,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "same_letter_three_times".to_string(),
@@ -289,7 +326,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must contain the same letter three times and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 let mut letter_counts: HashMap<char, usize> = HashMap::new();
                 for ch in word.chars() {
                     *letter_counts.entry(ch).or_insert(0) += 1;
@@ -300,7 +337,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
         Rule {
             name: "equal_vowels_consonants".to_string(),
@@ -308,7 +345,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 "Word must have an equal number of vowels and consonants and be at least {} characters long",
                 ctx.min_word_length
             ),
-            validate: |word, _ctx| {
+            validate: builtin(|word, _ctx| {
                 let vowels = "aeiou";
                 let mut vowel_count = 0;
                 let mut consonant_count = 0;
@@ -328,7 +365,7 @@ pub fn get_rules(ctx: &RuleContext) -> Vec<Rule> {
                 } else {
                     Ok(())
                 }
-            },
+            }),
         },
     ]
 }
@@ -340,3 +377,32 @@ pub fn find_rule_by_name<'a>(rules: &'a [Rule], name: &str) -> Option<&'a Rule>
 pub fn get_rule_by_index(index: usize, ctx: &RuleContext) -> Option<Rule> {
     get_rules(ctx).get(index).cloned()
 }
+
+/// Names of every rule, in progression order.
+pub fn rule_names() -> Vec<String> {
+    get_rules(&rule_names_context())
+        .into_iter()
+        .map(|rule| rule.name)
+        .collect()
+}
+
+/// Rules a lobby plays with, keeping the default progression order.
+/// `None` enables every rule.
+pub fn get_enabled_rules(ctx: &RuleContext, enabled: Option<&[String]>) -> Vec<Rule> {
+    let rules = get_rules(ctx);
+    match enabled {
+        Some(names) => rules
+            .into_iter()
+            .filter(|rule| names.contains(&rule.name))
+            .collect(),
+        None => rules,
+    }
+}
+
+pub fn get_enabled_rule_by_index(
+    index: usize,
+    ctx: &RuleContext,
+    enabled: Option<&[String]>,
+) -> Option<Rule> {
+    get_enabled_rules(ctx, enabled).get(index).cloned()
+}
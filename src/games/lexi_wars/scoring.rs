@@ -0,0 +1,61 @@
+use crate::{games::lexi_wars::rules::rule_names, models::lexi_wars::WordHighlights};
+
+/// Points per letter on top of the length score, rarer letters are worth more.
+fn letter_rarity(letter: char) -> u64 {
+    match letter {
+        'a' | 'e' | 'i' | 'o' | 'u' | 'l' | 'n' | 's' | 't' | 'r' => 1,
+        'd' | 'g' => 2,
+        'b' | 'c' | 'm' | 'p' => 3,
+        'f' | 'h' | 'v' | 'w' | 'y' => 4,
+        'k' => 5,
+        'j' | 'x' => 8,
+        'q' | 'z' => 10,
+        _ => 0,
+    }
+}
+
+/// Later rules in the progression are harder, so they pay more.
+fn rule_multiplier(rule_name: Option<&str>) -> f64 {
+    rule_name
+        .and_then(|name| rule_names().iter().position(|rule| rule == name))
+        .map_or(1.0, |index| 1.0 + index as f64 * 0.1)
+}
+
+/// Score for an accepted word: length plus letter rarity, scaled by the
+/// difficulty of the rule it satisfied.
+pub fn score_word(word: &str, rule_name: Option<&str>) -> u64 {
+    let length_score = word.chars().count() as u64 * 10;
+    let rarity_score: u64 = word.chars().map(letter_rarity).sum();
+
+    ((length_score + rarity_score) as f64 * rule_multiplier(rule_name)).round() as u64
+}
+
+/// Longest of the words, the earliest played wins a tie.
+pub fn longest_word(words: &[String]) -> Option<&str> {
+    words
+        .iter()
+        .rev()
+        .max_by_key(|word| word.chars().count())
+        .map(String::as_str)
+}
+
+/// Word with the most letter rarity, the earliest played wins a tie.
+pub fn rarest_word(words: &[String]) -> Option<&str> {
+    words
+        .iter()
+        .rev()
+        .max_by_key(|word| word.chars().map(letter_rarity).sum::<u64>())
+        .map(String::as_str)
+}
+
+pub(crate) fn word_highlights(words: &[String]) -> WordHighlights {
+    WordHighlights {
+        longest: longest_word(words).map(str::to_string),
+        rarest: rarest_word(words).map(str::to_string),
+    }
+}
+
+/// Small wars point bonus for a game's score, capped so rank still dominates.
+pub fn score_wars_point_bonus(score: u64) -> f64 {
+    (score as f64 / 100.0).min(5.0)
+}
@@ -0,0 +1,72 @@
+use crate::{
+    db::game::words::{language_installed, normalize_pack_name, word_pack_exists},
+    errors::AppError,
+    games::lexi_wars::rules::rule_names,
+    models::game::LobbySettings,
+    state::RedisClient,
+};
+
+/// Bounds of a player's time bank, in seconds
+const MIN_TIME_BANK_SECS: u64 = 30;
+const MAX_TIME_BANK_SECS: u64 = 600;
+/// Bounds of the game length, in seconds
+const MIN_GAME_SECS: u64 = 60;
+const MAX_GAME_SECS: u64 = 3600;
+
+/// Resolves the dictionary pack, language, rule selection, time bank and game
+/// length of a new Lexi Wars lobby.
+pub async fn validate_lobby_settings(
+    mut settings: LobbySettings,
+    redis: RedisClient,
+) -> Result<LobbySettings, AppError> {
+    if let Some(pack) = &settings.dictionary_pack {
+        let pack = normalize_pack_name(pack)?;
+        if !word_pack_exists(&pack, redis.clone()).await? {
+            return Err(AppError::BadRequest(format!(
+                "Unknown dictionary pack: {}",
+                pack
+            )));
+        }
+        settings.dictionary_pack = Some(pack);
+    }
+    if let Some(language) = settings.language
+        && !language_installed(language, redis.clone()).await?
+    {
+        return Err(AppError::BadRequest(format!(
+            "No {} dictionary is installed",
+            language.as_str()
+        )));
+    }
+    if let Some(rules) = &settings.rules {
+        let known = rule_names();
+        if let Some(unknown) = rules.iter().find(|rule| !known.contains(rule)) {
+            return Err(AppError::BadRequest(format!("Unknown rule: {}", unknown)));
+        }
+        // Keep the progression order and drop duplicates
+        let selected: Vec<String> = known.into_iter().filter(|r| rules.contains(r)).collect();
+        if selected.is_empty() {
+            return Err(AppError::BadRequest(
+                "At least one rule must be enabled".into(),
+            ));
+        }
+        settings.rules = Some(selected);
+    }
+    if let Some(bank) = settings.time_bank_secs
+        && !(MIN_TIME_BANK_SECS..=MAX_TIME_BANK_SECS).contains(&bank)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Time bank must be between {} and {} seconds",
+            MIN_TIME_BANK_SECS, MAX_TIME_BANK_SECS
+        )));
+    }
+    if let Some(max_secs) = settings.max_game_secs
+        && !(MIN_GAME_SECS..=MAX_GAME_SECS).contains(&max_secs)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Game length must be between {} and {} seconds",
+            MIN_GAME_SECS, MAX_GAME_SECS
+        )));
+    }
+
+    Ok(settings)
+}
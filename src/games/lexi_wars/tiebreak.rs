@@ -0,0 +1,69 @@
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+/// How a player still in the game did, for ordering survivors when a game
+/// ends with more than one of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurvivorRecord {
+    pub player_id: Uuid,
+    pub score: u64,
+    /// Accepted words
+    pub words: u64,
+    /// Time spent on the turns those words were played in
+    pub response_ms: u64,
+}
+
+impl SurvivorRecord {
+    /// Average time to an accepted word, `None` without any.
+    pub fn average_response_ms(&self) -> Option<u64> {
+        (self.words > 0).then(|| self.response_ms / self.words)
+    }
+
+    /// Higher score first, then the faster average answer. Players who never
+    /// played a word come after those who did.
+    fn compare(&self, other: &Self) -> Ordering {
+        other.score.cmp(&self.score).then_with(|| {
+            match (self.average_response_ms(), other.average_response_ms()) {
+                (Some(mine), Some(theirs)) => mine.cmp(&theirs),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        })
+    }
+}
+
+/// Orders survivors best first into groups of players level on every
+/// tiebreak. Players within a group keep the order they were given in.
+pub fn rank_survivors(survivors: Vec<SurvivorRecord>) -> Vec<Vec<Uuid>> {
+    group_survivors(survivors, SurvivorRecord::compare)
+}
+
+/// Same as [`rank_survivors`] for a game cut off by its time limit, where the
+/// most words played wins before score and speed are looked at.
+pub fn rank_survivors_by_words(survivors: Vec<SurvivorRecord>) -> Vec<Vec<Uuid>> {
+    group_survivors(survivors, |a, b| {
+        b.words.cmp(&a.words).then_with(|| a.compare(b))
+    })
+}
+
+fn group_survivors(
+    mut survivors: Vec<SurvivorRecord>,
+    compare: impl Fn(&SurvivorRecord, &SurvivorRecord) -> Ordering,
+) -> Vec<Vec<Uuid>> {
+    // Stable, so level players stay in the given order
+    survivors.sort_by(&compare);
+
+    let mut groups: Vec<Vec<Uuid>> = Vec::new();
+    let mut previous: Option<&SurvivorRecord> = None;
+    for survivor in &survivors {
+        match (previous, groups.last_mut()) {
+            (Some(prev), Some(group)) if compare(prev, survivor) == Ordering::Equal => {
+                group.push(survivor.player_id);
+            }
+            _ => groups.push(vec![survivor.player_id]),
+        }
+        previous = Some(survivor);
+    }
+    groups
+}
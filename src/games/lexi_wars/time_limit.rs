@@ -0,0 +1,68 @@
+//! Lobbies with `max_game_secs` set end their game once it runs that long.
+//! Difficulty ramps slowly, so without a limit a game between strong players
+//! could go on for as long as they do. The limit is kept in Redis, so a
+//! restarted server picks it up again.
+
+use chrono::Utc;
+use std::time::Duration;
+use teloxide::Bot;
+use uuid::Uuid;
+
+use crate::{
+    db::game::state::{get_game_ends_at, set_game_ends_at, take_game_ends_at},
+    errors::AppError,
+    games::lexi_wars::engine::end_game_at_time_limit,
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+/// Starts counting down a new game's time limit.
+pub async fn start_time_limit(
+    lobby_id: Uuid,
+    max_game_secs: u64,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) -> Result<(), AppError> {
+    let ends_at_ms = Utc::now().timestamp_millis() + (max_game_secs * 1000) as i64;
+    set_game_ends_at(lobby_id, ends_at_ms, redis.clone()).await?;
+    arm_time_limit(lobby_id, ends_at_ms, connections, redis, telegram_bot);
+    Ok(())
+}
+
+/// Waits in the background until `ends_at_ms`, then ends the game unless it
+/// finished first.
+pub fn arm_time_limit(
+    lobby_id: Uuid,
+    ends_at_ms: i64,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) {
+    tokio::spawn(async move {
+        let wait_ms = (ends_at_ms - Utc::now().timestamp_millis()).max(0) as u64;
+        tokio::time::sleep(Duration::from_millis(wait_ms)).await;
+
+        // Gone once the game is over; only one instance gets to take it
+        match get_game_ends_at(lobby_id, redis.clone()).await {
+            Ok(Some(current)) if current == ends_at_ms => {}
+            Ok(_) => return,
+            Err(e) => {
+                tracing::error!("Failed to get time limit of lobby {}: {}", lobby_id, e);
+                return;
+            }
+        }
+        match take_game_ends_at(lobby_id, redis.clone()).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                tracing::error!("Failed to claim time limit of lobby {}: {}", lobby_id, e);
+                return;
+            }
+        }
+
+        tracing::info!("Lobby {} reached its time limit", lobby_id);
+        if let Err(e) = end_game_at_time_limit(lobby_id, &connections, redis, telegram_bot).await {
+            tracing::error!("Failed to end lobby {} at its time limit: {}", lobby_id, e);
+        }
+    });
+}
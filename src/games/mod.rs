@@ -1,2 +1,12 @@
+pub mod archive;
+pub mod connect_four;
+pub mod core;
 pub mod init;
+pub mod ladder;
 pub mod lexi_wars;
+pub mod ownership;
+pub mod registry;
+pub mod rps;
+pub mod scheduler;
+pub mod schedules;
+pub mod typing_race;
@@ -0,0 +1,91 @@
+//! Which instance runs each game. Several instances can share Redis, and a
+//! game's timers live in the process that started it, so every running game
+//! is leased to one instance. The lease is kept alive by a heartbeat and runs
+//! out when its instance dies, which is how boot recovery tells an orphaned
+//! game from one a live instance is still running.
+
+use std::{
+    collections::HashSet,
+    sync::{LazyLock, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use tokio::time::{MissedTickBehavior, interval};
+use uuid::Uuid;
+
+use crate::{
+    db::game::lease::{
+        LEASE_TTL_SECS, claim_game_lease, hold_game_lease, release_game_lease, renew_game_lease,
+    },
+    errors::AppError,
+    state::RedisClient,
+};
+
+/// Identifies this process in game leases and user presence
+static INSTANCE_ID: LazyLock<Uuid> = LazyLock::new(Uuid::new_v4);
+
+/// Games this instance holds the lease of
+static OWNED: LazyLock<Mutex<HashSet<Uuid>>> = LazyLock::new(Default::default);
+
+fn owned() -> MutexGuard<'static, HashSet<Uuid>> {
+    OWNED.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Marks the lobby's game as run by this instance.
+pub async fn hold_game(lobby_id: Uuid, redis: &RedisClient) -> Result<(), AppError> {
+    hold_game_lease(lobby_id, *INSTANCE_ID, redis.clone()).await?;
+    owned().insert(lobby_id);
+    Ok(())
+}
+
+/// Takes over a game no live instance runs. False when another instance got
+/// to it first.
+pub async fn adopt_game(lobby_id: Uuid, redis: &RedisClient) -> Result<bool, AppError> {
+    let adopted = claim_game_lease(lobby_id, *INSTANCE_ID, redis.clone()).await?;
+    if adopted {
+        owned().insert(lobby_id);
+    }
+    Ok(adopted)
+}
+
+pub fn instance_id() -> Uuid {
+    *INSTANCE_ID
+}
+
+pub fn owned_games() -> Vec<Uuid> {
+    owned().iter().copied().collect()
+}
+
+/// Gives up every lease at shutdown, so the next instance to look can resume
+/// the games right away instead of waiting for the leases to run out.
+pub async fn release_owned_games(redis: &RedisClient) {
+    let lobby_ids: Vec<Uuid> = owned().drain().collect();
+    for lobby_id in lobby_ids {
+        if let Err(e) = release_game_lease(lobby_id, *INSTANCE_ID, redis.clone()).await {
+            tracing::error!("Failed to release lease of lobby {}: {}", lobby_id, e);
+        }
+    }
+}
+
+/// Renews the leases of the games this instance runs, forgetting the ones
+/// that ended or were taken over.
+pub async fn start_lease_heartbeat(redis: RedisClient) {
+    let mut ticker = interval(Duration::from_secs(LEASE_TTL_SECS / 3));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        ticker.tick().await;
+
+        for lobby_id in owned_games() {
+            match renew_game_lease(lobby_id, *INSTANCE_ID, redis.clone()).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    owned().remove(&lobby_id);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to renew lease of lobby {}: {}", lobby_id, e);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::LazyLock;
+
+use axum::routing::MethodRouter;
+use futures::future::BoxFuture;
+
+use crate::{
+    errors::AppError,
+    games::{
+        connect_four,
+        core::{recovery::RecoverHook, turns::TurnHooks},
+        lexi_wars, rps, typing_race,
+    },
+    models::game::{GameType, LobbySettings},
+    state::{AppState, RedisClient},
+};
+
+pub type SettingsValidator =
+    fn(LobbySettings, RedisClient) -> BoxFuture<'static, Result<LobbySettings, AppError>>;
+
+/// Everything the server needs to know about a playable game. Adding a game
+/// means adding its registration to `GAMES`.
+pub struct GameRegistration {
+    /// Matches the stored `GameType` name
+    pub name: &'static str,
+    pub description: &'static str,
+    pub image_url: &'static str,
+    pub tags: &'static [&'static str],
+    pub min_players: u8,
+    /// Joins beyond this are refused; unlimited when unset
+    pub max_players: Option<u8>,
+    /// Game socket route, with a `{lobby_id}` segment
+    pub ws_path: &'static str,
+    /// Builds the handler that runs the game's engine over the socket
+    pub ws_handler: fn() -> MethodRouter<AppState>,
+    /// Checks and normalizes game-specific lobby settings at creation
+    pub validate_settings: SettingsValidator,
+    /// Set for games played in timed turns
+    pub turns: Option<TurnHooks>,
+    /// Brings back a game left `InProgress` by a restart
+    pub recover: RecoverHook,
+}
+
+static GAMES: LazyLock<Vec<GameRegistration>> = LazyLock::new(|| {
+    vec![
+        lexi_wars::registration(),
+        rps::registration(),
+        connect_four::registration(),
+        typing_race::registration(),
+    ]
+});
+
+pub fn registered_games() -> &'static [GameRegistration] {
+    &GAMES
+}
+
+pub fn find_registration(game: &GameType) -> Option<&'static GameRegistration> {
+    GAMES.iter().find(|g| g.name == game.name)
+}
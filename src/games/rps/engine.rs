@@ -0,0 +1,573 @@
+use axum::extract::ws::Message;
+use chrono::Utc;
+use futures::StreamExt;
+use std::{collections::HashMap, time::Duration};
+use teloxide::Bot;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        game::{
+            rps::{RpsMoveOutcome, get_rps_round, lock_rps_move, open_rps_match, take_rps_round},
+            state::{
+                add_player_score, clear_lobby_game_state, get_player_scores, set_game_started,
+            },
+        },
+        lobby::{
+            get::{get_current_players_ids, get_lobby_info, get_lobby_players, get_lobby_settings},
+            patch::update_lobby_state,
+            put::create_current_players,
+        },
+    },
+    events::{GameEvent, emit},
+    games::core::{
+        EngineError, GameEngine, GameMessage,
+        bets::{handle_bet, settle_bets},
+        delivery::{
+            broadcast_to_lobby_and_spectators, broadcast_to_player, broadcast_to_spectators,
+            send_result_to_player,
+        },
+        lifecycle,
+        prize::{group_by_key, group_placings},
+        reactions::handle_reaction,
+        results::{send_player_results, settle_draw},
+    },
+    models::{
+        bet::{BetSettlement, SpectatorBet},
+        error_code::ErrorCode,
+        game::{LobbyInfo, LobbyState, Player, Reaction},
+        lobby::LobbyRefund,
+        queue::QueuePolicy,
+        rps::{RpsChoice, RpsClientMessage, RpsServerMessage},
+    },
+    state::{ConnectionInfoMap, RedisClient},
+    ws::{errors::report_socket_error, handlers::utils::ack_results_for_player},
+};
+
+/// Seconds each player has to lock in a move
+pub const ROUND_SECS: u64 = 10;
+
+pub struct Rps;
+
+impl GameMessage for RpsServerMessage {
+    fn should_queue(&self) -> bool {
+        RpsServerMessage::should_queue(self)
+    }
+
+    fn queue_policy(&self) -> QueuePolicy {
+        RpsServerMessage::queue_policy(self)
+    }
+}
+
+impl GameEngine for Rps {
+    type ServerMessage = RpsServerMessage;
+
+    async fn start(
+        lobby_id: Uuid,
+        player_ids: Vec<Uuid>,
+        connections: &ConnectionInfoMap,
+        redis: RedisClient,
+        _bot: Bot,
+    ) -> Result<(), EngineError> {
+        start_match(lobby_id, player_ids, connections, redis).await
+    }
+
+    /// Rounds won
+    async fn player_score(lobby_id: Uuid, player_id: Uuid, redis: &RedisClient) -> u64 {
+        match get_player_scores(lobby_id, redis.clone()).await {
+            Ok(scores) => scores.get(&player_id).copied().unwrap_or(0),
+            Err(e) => {
+                tracing::warn!("Failed to get scores for lobby {}: {}", lobby_id, e);
+                0
+            }
+        }
+    }
+
+    /// Rounds won already decide the rank, so they earn nothing extra
+    fn wars_point_bonus(_score: u64) -> f64 {
+        0.0
+    }
+
+    fn countdown_message(remaining_secs: u32) -> RpsServerMessage {
+        RpsServerMessage::Start {
+            time: remaining_secs,
+            started: false,
+        }
+    }
+
+    fn start_failed_message() -> RpsServerMessage {
+        RpsServerMessage::StartFailed
+    }
+
+    fn refund_message(refund: LobbyRefund) -> RpsServerMessage {
+        RpsServerMessage::Refund { refund }
+    }
+
+    fn rank_message(rank: usize) -> RpsServerMessage {
+        RpsServerMessage::Rank {
+            rank: rank.to_string(),
+        }
+    }
+
+    fn prize_message(amount: f64, token_symbol: String) -> RpsServerMessage {
+        RpsServerMessage::Prize {
+            amount,
+            token_symbol,
+        }
+    }
+
+    fn wars_point_message(wars_point: f64) -> RpsServerMessage {
+        RpsServerMessage::WarsPoint { wars_point }
+    }
+
+    fn reaction_message(user_id: Uuid, reaction: Reaction) -> RpsServerMessage {
+        RpsServerMessage::Reaction {
+            user_id,
+            emoji: reaction,
+        }
+    }
+
+    fn bet_placed_message(bet: SpectatorBet) -> RpsServerMessage {
+        RpsServerMessage::BetPlaced { bet }
+    }
+
+    fn bet_settled_message(settlement: BetSettlement) -> RpsServerMessage {
+        RpsServerMessage::BetSettled { settlement }
+    }
+
+    fn error_message(code: ErrorCode, message: String) -> RpsServerMessage {
+        RpsServerMessage::Error { code, message }
+    }
+
+    async fn send_to_player(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        msg: &RpsServerMessage,
+        connections: &ConnectionInfoMap,
+        redis: &RedisClient,
+    ) {
+        broadcast_to_player(player_id, lobby_id, msg, connections, redis).await;
+    }
+
+    async fn send_result(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        msg: &RpsServerMessage,
+        connections: &ConnectionInfoMap,
+        redis: &RedisClient,
+    ) {
+        send_result_to_player(player_id, lobby_id, msg, connections, redis).await;
+    }
+}
+
+pub fn start_auto_start_timer(
+    lobby_id: Uuid,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) {
+    lifecycle::start_auto_start_timer::<Rps>(lobby_id, connections, redis, telegram_bot);
+}
+
+pub async fn handle_incoming_messages(
+    player: &Player,
+    lobby_id: Uuid,
+    mut receiver: impl StreamExt<Item = Result<Message, axum::Error>> + Unpin,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+    _telegram_bot: Bot,
+) {
+    while let Some(msg_result) = receiver.next().await {
+        match msg_result {
+            Ok(Message::Text(text)) => {
+                let parsed = match serde_json::from_str::<RpsClientMessage>(&text) {
+                    Ok(msg) => msg,
+                    Err(e) => {
+                        tracing::info!("Invalid message format from {}: {}", player.id, e);
+                        continue;
+                    }
+                };
+
+                match parsed {
+                    RpsClientMessage::Ping { ts } => {
+                        let now = Utc::now().timestamp_millis() as u64;
+                        let pong = now.saturating_sub(ts);
+                        let pong_msg = RpsServerMessage::Pong { ts, pong };
+                        broadcast_to_player(player.id, lobby_id, &pong_msg, connections, &redis)
+                            .await;
+                    }
+                    RpsClientMessage::PlaceBet { winner_id, amount } => {
+                        handle_bet::<Rps>(
+                            lobby_id,
+                            player.id,
+                            winner_id,
+                            amount,
+                            connections,
+                            &redis,
+                        )
+                        .await;
+                    }
+                    RpsClientMessage::Reaction { emoji } => {
+                        handle_reaction::<Rps>(lobby_id, player.id, emoji, connections, &redis)
+                            .await;
+                    }
+                    RpsClientMessage::ResultAck => {
+                        if let Err(e) = ack_results_for_player(player.id, lobby_id, &redis).await {
+                            tracing::error!(
+                                "Failed to ack results for player {}: {}",
+                                player.id,
+                                e
+                            );
+                        }
+                    }
+                    RpsClientMessage::Move { choice } => {
+                        handle_move(player.id, lobby_id, choice, connections, &redis).await;
+                    }
+                }
+            }
+            Ok(Message::Close(_)) => {
+                tracing::debug!("Player {} closed the connection", player.id);
+                break;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::debug!("WebSocket error for player {}: {}", player.id, e);
+                break;
+            }
+        }
+    }
+}
+
+async fn handle_move(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    choice: RpsChoice,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let (round, moves) = match lock_rps_move(lobby_id, player_id, choice, redis.clone()).await {
+        Ok(RpsMoveOutcome::Locked { round, moves }) => (round, moves),
+        Ok(RpsMoveOutcome::AlreadyMoved) => {
+            let error_msg = RpsServerMessage::Error {
+                code: ErrorCode::BadRequest,
+                message: "You already moved this round".to_string(),
+            };
+            broadcast_to_player(player_id, lobby_id, &error_msg, connections, redis).await;
+            return;
+        }
+        Ok(RpsMoveOutcome::NoOpenRound) => {
+            let error_msg = RpsServerMessage::Error {
+                code: ErrorCode::GameNotStarted,
+                message: "Game has not started yet".to_string(),
+            };
+            broadcast_to_player(player_id, lobby_id, &error_msg, connections, redis).await;
+            return;
+        }
+        Err(e) => {
+            report_socket_error::<Rps>(player_id, lobby_id, &e, connections, redis).await;
+            return;
+        }
+    };
+
+    let players = get_lobby_players(lobby_id, None, redis.clone())
+        .await
+        .unwrap_or_default();
+    let locked_msg = RpsServerMessage::MoveLocked { player_id };
+    broadcast_to_lobby_and_spectators(&locked_msg, &players, lobby_id, connections, redis).await;
+
+    let player_count = get_current_players_ids(lobby_id, redis.clone())
+        .await
+        .map(|ids| ids.len())
+        .unwrap_or(2);
+    if moves >= player_count
+        && let Err(e) = resolve_round(lobby_id, round, connections.clone(), redis.clone()).await
+    {
+        tracing::error!(
+            "Failed to resolve round {} of lobby {}: {}",
+            round,
+            lobby_id,
+            e
+        );
+    }
+}
+
+async fn start_match(
+    lobby_id: Uuid,
+    connected_player_ids: Vec<Uuid>,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+) -> Result<(), EngineError> {
+    if connected_player_ids.len() != 2 {
+        return Err(format!(
+            "Rock-Paper-Scissors needs two players, {} connected",
+            connected_player_ids.len()
+        )
+        .into());
+    }
+
+    set_game_started(lobby_id, true, redis.clone()).await?;
+    create_current_players(lobby_id, connected_player_ids.clone(), redis.clone()).await?;
+    open_rps_match(lobby_id, redis.clone()).await?;
+
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+    let game_started_msg = RpsServerMessage::Start {
+        time: 0,
+        started: true,
+    };
+    broadcast_to_lobby_and_spectators(&game_started_msg, &players, lobby_id, connections, &redis)
+        .await;
+
+    let best_of = get_lobby_settings(lobby_id, redis.clone()).await?.best_of();
+    open_round(lobby_id, 1, best_of, connections.clone(), redis);
+
+    tracing::info!("Rock-Paper-Scissors match started for lobby {}", lobby_id);
+    Ok(())
+}
+
+/// Replays the open round with a fresh countdown, since its timer died with
+/// the old process. Moves already locked in for it still count. A match
+/// without an open round or missing a player ends with the current score.
+pub async fn recover_game(
+    lobby_id: Uuid,
+    _remaining_ms: Option<i64>,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    _telegram_bot: Bot,
+) -> Result<(), EngineError> {
+    let player_ids = get_current_players_ids(lobby_id, redis.clone()).await?;
+    let round = get_rps_round(lobby_id, redis.clone()).await?;
+
+    match round {
+        Some(round) if player_ids.len() == 2 => {
+            let best_of = get_lobby_settings(lobby_id, redis.clone()).await?.best_of();
+            open_round(lobby_id, round, best_of, connections, redis);
+            tracing::info!("Resumed lobby {} at round {}", lobby_id, round);
+            Ok(())
+        }
+        _ => {
+            tracing::info!("Lobby {} cannot be resumed, ending match", lobby_id);
+            end_match(lobby_id, player_ids, &connections, redis).await
+        }
+    }
+}
+
+/// Announces a round and resolves it when time runs out, unless both players
+/// moved first.
+fn open_round(
+    lobby_id: Uuid,
+    round: u32,
+    best_of: u8,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+) {
+    tokio::spawn(
+        async move {
+            let players = get_lobby_players(lobby_id, None, redis.clone())
+                .await
+                .unwrap_or_default();
+            let round_msg = RpsServerMessage::Round {
+                round,
+                best_of,
+                countdown: ROUND_SECS,
+            };
+            broadcast_to_lobby_and_spectators(&round_msg, &players, lobby_id, &connections, &redis)
+                .await;
+
+            tokio::time::sleep(Duration::from_secs(ROUND_SECS)).await;
+            if let Err(e) = resolve_round(lobby_id, round, connections, redis).await {
+                tracing::error!(
+                    "Failed to resolve round {} of lobby {}: {}",
+                    round,
+                    lobby_id,
+                    e
+                );
+            }
+        }
+        .in_current_span(),
+    );
+}
+
+/// Winner of a round between two players. A player who didn't move loses to
+/// one who did; equal or missing moves are a draw.
+fn round_winner(players: &[Uuid], moves: &HashMap<Uuid, RpsChoice>) -> Option<Uuid> {
+    let [a, b] = players else {
+        return None;
+    };
+    match (moves.get(a), moves.get(b)) {
+        (Some(x), Some(y)) if x.beats(*y) => Some(*a),
+        (Some(x), Some(y)) if y.beats(*x) => Some(*b),
+        (Some(_), None) => Some(*a),
+        (None, Some(_)) => Some(*b),
+        _ => None,
+    }
+}
+
+/// Closes a round once, scores it and either opens the next round or ends the
+/// match. A round neither player moved in ends the match with the current score.
+async fn resolve_round(
+    lobby_id: Uuid,
+    round: u32,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+) -> Result<(), EngineError> {
+    let Some(moves) = take_rps_round(lobby_id, round, redis.clone()).await? else {
+        // Already resolved by the other player's move or the timer
+        return Ok(());
+    };
+
+    let player_ids = get_current_players_ids(lobby_id, redis.clone()).await?;
+    let winner_id = round_winner(&player_ids, &moves);
+    if let Some(winner_id) = winner_id {
+        add_player_score(lobby_id, winner_id, 1, redis.clone()).await?;
+    }
+    let scores = get_player_scores(lobby_id, redis.clone()).await?;
+
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+    let result_msg = RpsServerMessage::RoundResult {
+        round,
+        moves: moves.clone(),
+        winner_id,
+        scores: scores.clone(),
+    };
+    broadcast_to_lobby_and_spectators(&result_msg, &players, lobby_id, &connections, &redis).await;
+
+    let best_of = get_lobby_settings(lobby_id, redis.clone()).await?.best_of();
+    let wins_needed = (best_of / 2 + 1) as u64;
+    // Draws don't count toward best-of, so cap the rounds of a stalemate
+    let max_rounds = best_of as u32 * 3;
+    let decided = scores.values().any(|&wins| wins >= wins_needed);
+
+    if decided || moves.is_empty() || round >= max_rounds {
+        end_match(lobby_id, player_ids, &connections, redis).await
+    } else {
+        open_round(lobby_id, round + 1, best_of, connections, redis);
+        Ok(())
+    }
+}
+
+/// Ranks players by rounds won and delivers the results. A tied score, a
+/// 0-0 included, is a draw.
+async fn end_match(
+    lobby_id: Uuid,
+    player_ids: Vec<Uuid>,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+) -> Result<(), EngineError> {
+    update_lobby_state(lobby_id, LobbyState::Finished, redis.clone()).await?;
+
+    let lobby_info = get_lobby_info(lobby_id, redis.clone()).await?;
+    let scores = get_player_scores(lobby_id, redis.clone())
+        .await
+        .unwrap_or_default();
+    let groups = group_by_key(&player_ids, |id| {
+        std::cmp::Reverse(scores.get(id).copied().unwrap_or(0))
+    });
+    let winner_id = match groups.first().map(Vec::as_slice) {
+        Some(&[winner]) => winner,
+        _ => return end_in_draw(&lobby_info, &player_ids, connections, redis).await,
+    };
+
+    let connected_players_count = player_ids.len();
+    for (player_id, placing) in group_placings(&groups) {
+        send_player_results::<Rps>(
+            player_id,
+            &lobby_info,
+            connected_players_count,
+            placing,
+            connections,
+            &redis,
+        )
+        .await;
+    }
+
+    settle_bets::<Rps>(lobby_id, Some(winner_id), connections, &redis).await;
+    emit(GameEvent::GameEnded {
+        lobby_id,
+        winner_id: Some(winner_id),
+    });
+
+    let gameover_msg = RpsServerMessage::GameOver;
+    for &player_id in &player_ids {
+        send_result_to_player(player_id, lobby_id, &gameover_msg, connections, &redis).await;
+    }
+    broadcast_to_spectators(&gameover_msg, lobby_id, connections, &redis).await;
+
+    if let Err(e) = clear_lobby_game_state(lobby_id, redis.clone()).await {
+        tracing::error!("Failed to clear lobby game state: {}", e);
+    }
+
+    tracing::info!("Rock-Paper-Scissors match ended for lobby {}", lobby_id);
+    Ok(())
+}
+
+/// Ends a match neither player won: entries are refunded and bets returned.
+async fn end_in_draw(
+    lobby_info: &LobbyInfo,
+    player_ids: &[Uuid],
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+) -> Result<(), EngineError> {
+    let lobby_id = lobby_info.id;
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+
+    let draw_msg = RpsServerMessage::Draw;
+    broadcast_to_lobby_and_spectators(&draw_msg, &players, lobby_id, connections, &redis).await;
+
+    settle_draw::<Rps>(lobby_info, player_ids, connections, &redis).await?;
+
+    let gameover_msg = RpsServerMessage::GameOver;
+    for &player_id in player_ids {
+        send_result_to_player(player_id, lobby_id, &gameover_msg, connections, &redis).await;
+    }
+    broadcast_to_spectators(&gameover_msg, lobby_id, connections, &redis).await;
+
+    if let Err(e) = clear_lobby_game_state(lobby_id, redis.clone()).await {
+        tracing::error!("Failed to clear lobby game state: {}", e);
+    }
+
+    tracing::info!("Rock-Paper-Scissors match drawn in lobby {}", lobby_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn duel() -> (Uuid, Uuid) {
+        (Uuid::new_v4(), Uuid::new_v4())
+    }
+
+    #[test]
+    fn test_winning_choice_takes_the_round() {
+        let (a, b) = duel();
+        let moves = HashMap::from([(a, RpsChoice::Paper), (b, RpsChoice::Rock)]);
+        assert_eq!(round_winner(&[a, b], &moves), Some(a));
+
+        let moves = HashMap::from([(a, RpsChoice::Paper), (b, RpsChoice::Scissors)]);
+        assert_eq!(round_winner(&[a, b], &moves), Some(b));
+    }
+
+    #[test]
+    fn test_same_choice_is_a_draw() {
+        let (a, b) = duel();
+        let moves = HashMap::from([(a, RpsChoice::Rock), (b, RpsChoice::Rock)]);
+        assert_eq!(round_winner(&[a, b], &moves), None);
+    }
+
+    #[test]
+    fn test_missing_move_loses_to_any_move() {
+        let (a, b) = duel();
+        let moves = HashMap::from([(b, RpsChoice::Scissors)]);
+        assert_eq!(round_winner(&[a, b], &moves), Some(b));
+    }
+
+    #[test]
+    fn test_no_moves_or_wrong_player_count_has_no_winner() {
+        let (a, b) = duel();
+        assert_eq!(round_winner(&[a, b], &HashMap::new()), None);
+
+        let moves = HashMap::from([(a, RpsChoice::Rock)]);
+        assert_eq!(round_winner(&[a], &moves), None);
+    }
+}
@@ -0,0 +1,30 @@
+pub mod engine;
+pub mod settings;
+
+pub use engine::{handle_incoming_messages, start_auto_start_timer};
+
+use axum::routing::get;
+use futures::FutureExt;
+
+use crate::{games::registry::GameRegistration, ws::handlers::rps_handler};
+
+pub fn registration() -> GameRegistration {
+    GameRegistration {
+        name: "Rock Paper Scissors",
+        description: "A quick best-of duel of rock, paper and scissors.",
+        // No artwork uploaded yet
+        image_url: "",
+        tags: &["quick", "duel", "luck"],
+        min_players: 2,
+        max_players: Some(2),
+        ws_path: "/ws/rps/{lobby_id}",
+        ws_handler: || get(rps_handler),
+        validate_settings: |settings, redis| {
+            settings::validate_lobby_settings(settings, redis).boxed()
+        },
+        turns: None,
+        recover: |lobby_id, remaining_ms, connections, redis, bot| {
+            engine::recover_game(lobby_id, remaining_ms, connections, redis, bot).boxed()
+        },
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{errors::AppError, models::game::LobbySettings, state::RedisClient};
+
+const MAX_BEST_OF: u8 = 9;
+
+/// Checks the match length of a new Rock-Paper-Scissors lobby.
+pub async fn validate_lobby_settings(
+    settings: LobbySettings,
+    _redis: RedisClient,
+) -> Result<LobbySettings, AppError> {
+    if let Some(best_of) = settings.best_of {
+        validate_best_of(best_of)?;
+    }
+
+    Ok(settings)
+}
+
+/// A best-of needs an odd number of rounds so one player can take the majority.
+fn validate_best_of(best_of: u8) -> Result<(), AppError> {
+    if best_of == 0 || best_of > MAX_BEST_OF || best_of.is_multiple_of(2) {
+        return Err(AppError::BadRequest(format!(
+            "bestOf must be an odd number between 1 and {}",
+            MAX_BEST_OF
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odd_best_of_up_to_the_cap_is_accepted() {
+        for best_of in [1, 3, 5, 7, MAX_BEST_OF] {
+            assert!(validate_best_of(best_of).is_ok(), "bestOf {}", best_of);
+        }
+    }
+
+    #[test]
+    fn test_zero_even_and_oversized_best_of_are_rejected() {
+        for best_of in [0, 2, 4, 8, MAX_BEST_OF + 2, u8::MAX - 1] {
+            assert!(validate_best_of(best_of).is_err(), "bestOf {}", best_of);
+        }
+    }
+}
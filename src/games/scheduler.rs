@@ -0,0 +1,151 @@
+use chrono::Utc;
+use std::{collections::HashMap, time::Duration};
+use teloxide::Bot;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        game::state::{TurnDeadline, claim_turn_deadline, get_turn_deadlines},
+        lobby::get::{get_lobby_info, get_lobby_players},
+    },
+    games::{core::turns::TurnHooks, registry::find_registration},
+    models::game::Player,
+    shutdown::is_draining,
+    state::{ConnectionInfoMap, RedisClient},
+};
+
+/// Players per lobby, refreshed only when the turn moves to someone else.
+type PlayersCache = HashMap<Uuid, (Uuid, Vec<Player>)>;
+
+/// Turn hooks of each lobby's game, looked up once per lobby.
+type HooksCache = HashMap<Uuid, TurnHooks>;
+
+/// Drives every turn countdown on this instance from one task. Deadlines live
+/// in Redis so a timeout fires once even when several instances run.
+pub async fn start_turn_scheduler(connections: ConnectionInfoMap, redis: RedisClient, bot: Bot) {
+    tracing::info!("Starting turn scheduler");
+
+    let mut ticker = interval(Duration::from_secs(1));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut players_cache = PlayersCache::new();
+    let mut hooks_cache = HooksCache::new();
+
+    loop {
+        ticker.tick().await;
+
+        // Leave deadlines untouched so the next boot can resume these turns
+        if is_draining() {
+            continue;
+        }
+
+        let deadlines = match get_turn_deadlines(redis.clone()).await {
+            Ok(deadlines) => deadlines,
+            Err(e) => {
+                tracing::error!("Failed to read turn deadlines: {}", e);
+                continue;
+            }
+        };
+
+        players_cache.retain(|lobby_id, _| deadlines.iter().any(|d| d.lobby_id == *lobby_id));
+        hooks_cache.retain(|lobby_id, _| deadlines.iter().any(|d| d.lobby_id == *lobby_id));
+        let now = Utc::now().timestamp_millis();
+
+        for deadline in deadlines {
+            let Some(hooks) = turn_hooks(deadline.lobby_id, &mut hooks_cache, &redis).await else {
+                continue;
+            };
+
+            let remaining_ms = deadline.deadline_ms - now;
+            if remaining_ms > 0 {
+                let remaining_secs = (remaining_ms as u64).div_ceil(1000);
+                let Some(players) = current_players(&deadline, &mut players_cache, &redis).await
+                else {
+                    continue;
+                };
+                (hooks.countdown)(&deadline, remaining_secs, players, &connections, &redis).await;
+                continue;
+            }
+
+            match claim_turn_deadline(&deadline, redis.clone()).await {
+                Ok(true) => {
+                    players_cache.remove(&deadline.lobby_id);
+                    // Deadlines outlive the connection that set them, so the
+                    // timeout is traced by lobby rather than correlation id
+                    let span = tracing::info_span!("game", lobby_id = %deadline.lobby_id);
+                    tokio::spawn(
+                        (hooks.timeout)(deadline, connections.clone(), redis.clone(), bot.clone())
+                            .instrument(span),
+                    );
+                }
+                Ok(false) => {
+                    // Rescheduled or claimed by another instance
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to claim turn deadline for lobby {}: {}",
+                        deadline.lobby_id,
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Finds how the lobby's game handles turns. Deadlines of a game without
+/// timed turns are left alone.
+async fn turn_hooks(
+    lobby_id: Uuid,
+    hooks_cache: &mut HooksCache,
+    redis: &RedisClient,
+) -> Option<TurnHooks> {
+    if let Some(hooks) = hooks_cache.get(&lobby_id) {
+        return Some(*hooks);
+    }
+
+    let lobby = match get_lobby_info(lobby_id, redis.clone()).await {
+        Ok(lobby) => lobby,
+        Err(e) => {
+            tracing::error!("Failed to get lobby {} for its turn: {}", lobby_id, e);
+            return None;
+        }
+    };
+    let Some(hooks) = find_registration(&lobby.game).and_then(|game| game.turns) else {
+        tracing::warn!(
+            "Lobby {} has a turn deadline but {} has no turns",
+            lobby_id,
+            lobby.game.name
+        );
+        return None;
+    };
+    hooks_cache.insert(lobby_id, hooks);
+    Some(hooks)
+}
+
+async fn current_players<'a>(
+    deadline: &TurnDeadline,
+    players_cache: &'a mut PlayersCache,
+    redis: &RedisClient,
+) -> Option<&'a [Player]> {
+    let lobby_id = deadline.lobby_id;
+    let stale = players_cache
+        .get(&lobby_id)
+        .is_none_or(|(owner, _)| *owner != deadline.player_id);
+    if stale {
+        match get_lobby_players(lobby_id, None, redis.clone()).await {
+            Ok(players) => {
+                players_cache.insert(lobby_id, (deadline.player_id, players));
+            }
+            Err(e) => {
+                tracing::error!("Failed to get players for lobby {}: {}", lobby_id, e);
+                return None;
+            }
+        }
+    }
+
+    players_cache
+        .get(&lobby_id)
+        .map(|(_, players)| players.as_slice())
+}
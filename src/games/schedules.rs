@@ -0,0 +1,126 @@
+use chrono::{DateTime, Datelike, Days, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use teloxide::Bot;
+use tokio::time::{MissedTickBehavior, interval};
+use utoipa::ToSchema;
+
+use crate::{
+    db::lobby::{
+        post::create_scheduled_lobby,
+        schedules::{add_schedule_lobby, claim_schedule_run, get_due_schedules},
+    },
+    errors::AppError,
+    models::lobby::LobbySchedule,
+    shutdown::is_draining,
+    state::RedisClient,
+};
+
+/// When a recurring lobby opens, in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "every", rename_all = "camelCase")]
+pub enum Recurrence {
+    Day {
+        hour: u32,
+        minute: u32,
+    },
+    Week {
+        #[schema(value_type = String, example = "Fri")]
+        weekday: Weekday,
+        hour: u32,
+        minute: u32,
+    },
+}
+
+impl Recurrence {
+    pub fn validate(&self) -> Result<(), AppError> {
+        let (hour, minute) = self.time_of_day();
+        if hour > 23 || minute > 59 {
+            return Err(AppError::BadRequest(
+                "Recurrence time must be a valid UTC hour and minute".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn time_of_day(&self) -> (u32, u32) {
+        match *self {
+            Recurrence::Day { hour, minute } | Recurrence::Week { hour, minute, .. } => {
+                (hour, minute)
+            }
+        }
+    }
+
+    /// The first occurrence strictly after `after`.
+    pub fn next_after(&self, after: DateTime<Utc>) -> DateTime<Utc> {
+        let (hour, minute) = self.time_of_day();
+        let time = NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or(NaiveTime::MIN);
+
+        (0..=7)
+            .filter_map(|offset| after.date_naive().checked_add_days(Days::new(offset)))
+            .map(|date| date.and_time(time).and_utc())
+            .find(|candidate| {
+                *candidate > after
+                    && match self {
+                        Recurrence::Day { .. } => true,
+                        Recurrence::Week { weekday, .. } => candidate.weekday() == *weekday,
+                    }
+            })
+            .unwrap_or(after + chrono::Duration::weeks(1))
+    }
+}
+
+/// Opens a fresh lobby for every schedule that came due. Checked every
+/// 30 seconds; a claim in Redis keeps instances from opening it twice.
+pub async fn start_schedule_worker(redis: RedisClient, bot: Bot) {
+    tracing::info!("Starting lobby schedule worker");
+
+    let mut ticker = interval(Duration::from_secs(30));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        ticker.tick().await;
+
+        if is_draining() {
+            continue;
+        }
+
+        let now = Utc::now();
+        let due = match get_due_schedules(now, redis.clone()).await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::error!("Failed to read due lobby schedules: {}", e);
+                continue;
+            }
+        };
+
+        for schedule in due {
+            // Occurrences missed while the server was down are skipped, not replayed
+            let next_run = schedule.recurrence.next_after(now);
+            match claim_schedule_run(&schedule, next_run, redis.clone()).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    tracing::error!("Failed to claim lobby schedule {}: {}", schedule.id, e);
+                    continue;
+                }
+            }
+
+            if let Err(e) = open_occurrence(&schedule, &redis, &bot).await {
+                tracing::error!("Failed to open lobby for schedule {}: {}", schedule.id, e);
+            }
+        }
+    }
+}
+
+async fn open_occurrence(
+    schedule: &LobbySchedule,
+    redis: &RedisClient,
+    bot: &Bot,
+) -> Result<(), AppError> {
+    let lobby_id = create_scheduled_lobby(schedule, redis.clone(), bot.clone()).await?;
+    add_schedule_lobby(schedule.id, lobby_id, redis.clone()).await?;
+
+    tracing::info!("Opened lobby {} for schedule {}", lobby_id, schedule.id);
+    Ok(())
+}
@@ -0,0 +1,560 @@
+use axum::extract::ws::Message;
+use chrono::Utc;
+use futures::StreamExt;
+use std::time::Duration;
+use teloxide::Bot;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        game::{
+            state::{
+                add_player_score, clear_lobby_game_state, get_player_scores, set_game_started,
+            },
+            typing_race::{
+                SubmitOutcome, claim_race_end, get_race, get_submissions, open_race,
+                record_submission,
+            },
+        },
+        lobby::{
+            get::{get_current_players_ids, get_lobby_info, get_lobby_players},
+            patch::update_lobby_state,
+            put::create_current_players,
+        },
+    },
+    events::{GameEvent, emit},
+    games::{
+        core::{
+            EngineError, GameEngine, GameMessage,
+            bets::{handle_bet, settle_bets},
+            delivery::{
+                broadcast_to_lobby_and_spectators, broadcast_to_player, broadcast_to_spectators,
+                send_result_to_player,
+            },
+            lifecycle,
+            prize::{group_by_key, group_placings},
+            reactions::handle_reaction,
+            results::{send_player_results, settle_draw},
+        },
+        typing_race::{
+            prompts::random_prompt,
+            scoring::{score_submission, score_wars_point_bonus},
+        },
+    },
+    models::{
+        bet::{BetSettlement, SpectatorBet},
+        error_code::ErrorCode,
+        game::{LobbyInfo, LobbyState, Player, Reaction},
+        lobby::LobbyRefund,
+        queue::QueuePolicy,
+        typing_race::{
+            TypingRaceClientMessage, TypingRaceServerMessage, TypingResult, TypingStanding,
+        },
+    },
+    state::{ConnectionInfoMap, RedisClient},
+    ws::{errors::report_socket_error, handlers::utils::ack_results_for_player},
+};
+
+/// Words in each race prompt
+pub const PROMPT_WORDS: usize = 12;
+/// Seconds players have to submit the prompt
+pub const RACE_SECS: u64 = 60;
+
+pub struct TypingRace;
+
+impl GameMessage for TypingRaceServerMessage {
+    fn should_queue(&self) -> bool {
+        TypingRaceServerMessage::should_queue(self)
+    }
+
+    fn queue_policy(&self) -> QueuePolicy {
+        TypingRaceServerMessage::queue_policy(self)
+    }
+}
+
+impl GameEngine for TypingRace {
+    type ServerMessage = TypingRaceServerMessage;
+
+    async fn start(
+        lobby_id: Uuid,
+        player_ids: Vec<Uuid>,
+        connections: &ConnectionInfoMap,
+        redis: RedisClient,
+        _bot: Bot,
+    ) -> Result<(), EngineError> {
+        start_race(lobby_id, player_ids, connections, redis).await
+    }
+
+    /// Race score of the player's submission
+    async fn player_score(lobby_id: Uuid, player_id: Uuid, redis: &RedisClient) -> u64 {
+        match get_player_scores(lobby_id, redis.clone()).await {
+            Ok(scores) => scores.get(&player_id).copied().unwrap_or(0),
+            Err(e) => {
+                tracing::warn!("Failed to get scores for lobby {}: {}", lobby_id, e);
+                0
+            }
+        }
+    }
+
+    fn wars_point_bonus(score: u64) -> f64 {
+        score_wars_point_bonus(score)
+    }
+
+    fn countdown_message(remaining_secs: u32) -> TypingRaceServerMessage {
+        TypingRaceServerMessage::Start {
+            time: remaining_secs,
+            started: false,
+        }
+    }
+
+    fn start_failed_message() -> TypingRaceServerMessage {
+        TypingRaceServerMessage::StartFailed
+    }
+
+    fn refund_message(refund: LobbyRefund) -> TypingRaceServerMessage {
+        TypingRaceServerMessage::Refund { refund }
+    }
+
+    fn rank_message(rank: usize) -> TypingRaceServerMessage {
+        TypingRaceServerMessage::Rank {
+            rank: rank.to_string(),
+        }
+    }
+
+    fn prize_message(amount: f64, token_symbol: String) -> TypingRaceServerMessage {
+        TypingRaceServerMessage::Prize {
+            amount,
+            token_symbol,
+        }
+    }
+
+    fn wars_point_message(wars_point: f64) -> TypingRaceServerMessage {
+        TypingRaceServerMessage::WarsPoint { wars_point }
+    }
+
+    fn reaction_message(user_id: Uuid, reaction: Reaction) -> TypingRaceServerMessage {
+        TypingRaceServerMessage::Reaction {
+            user_id,
+            emoji: reaction,
+        }
+    }
+
+    fn bet_placed_message(bet: SpectatorBet) -> TypingRaceServerMessage {
+        TypingRaceServerMessage::BetPlaced { bet }
+    }
+
+    fn bet_settled_message(settlement: BetSettlement) -> TypingRaceServerMessage {
+        TypingRaceServerMessage::BetSettled { settlement }
+    }
+
+    fn error_message(code: ErrorCode, message: String) -> TypingRaceServerMessage {
+        TypingRaceServerMessage::Error { code, message }
+    }
+
+    async fn send_to_player(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        msg: &TypingRaceServerMessage,
+        connections: &ConnectionInfoMap,
+        redis: &RedisClient,
+    ) {
+        broadcast_to_player(player_id, lobby_id, msg, connections, redis).await;
+    }
+
+    async fn send_result(
+        player_id: Uuid,
+        lobby_id: Uuid,
+        msg: &TypingRaceServerMessage,
+        connections: &ConnectionInfoMap,
+        redis: &RedisClient,
+    ) {
+        send_result_to_player(player_id, lobby_id, msg, connections, redis).await;
+    }
+}
+
+pub fn start_auto_start_timer(
+    lobby_id: Uuid,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    telegram_bot: Bot,
+) {
+    lifecycle::start_auto_start_timer::<TypingRace>(lobby_id, connections, redis, telegram_bot);
+}
+
+/// Sends the prompt with the time left to submit it, for a reconnecting
+/// player or spectator. Nothing is sent once the race is over.
+pub async fn send_race_state(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let race = match get_race(lobby_id, redis.clone()).await {
+        Ok(Some(race)) if !race.ended => race,
+        Ok(_) => return,
+        Err(e) => {
+            tracing::error!("Failed to get race for lobby {}: {}", lobby_id, e);
+            return;
+        }
+    };
+
+    let elapsed_secs = (Utc::now().timestamp_millis() - race.started_at).max(0) as u64 / 1000;
+    let prompt_msg = TypingRaceServerMessage::Prompt {
+        text: race.prompt,
+        countdown: RACE_SECS.saturating_sub(elapsed_secs),
+    };
+    broadcast_to_player(player_id, lobby_id, &prompt_msg, connections, redis).await;
+}
+
+pub async fn handle_incoming_messages(
+    player: &Player,
+    lobby_id: Uuid,
+    mut receiver: impl StreamExt<Item = Result<Message, axum::Error>> + Unpin,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+    _telegram_bot: Bot,
+) {
+    while let Some(msg_result) = receiver.next().await {
+        match msg_result {
+            Ok(Message::Text(text)) => {
+                let parsed = match serde_json::from_str::<TypingRaceClientMessage>(&text) {
+                    Ok(msg) => msg,
+                    Err(e) => {
+                        tracing::info!("Invalid message format from {}: {}", player.id, e);
+                        continue;
+                    }
+                };
+
+                match parsed {
+                    TypingRaceClientMessage::Ping { ts } => {
+                        let now = Utc::now().timestamp_millis() as u64;
+                        let pong = now.saturating_sub(ts);
+                        let pong_msg = TypingRaceServerMessage::Pong { ts, pong };
+                        broadcast_to_player(player.id, lobby_id, &pong_msg, connections, &redis)
+                            .await;
+                    }
+                    TypingRaceClientMessage::PlaceBet { winner_id, amount } => {
+                        handle_bet::<TypingRace>(
+                            lobby_id,
+                            player.id,
+                            winner_id,
+                            amount,
+                            connections,
+                            &redis,
+                        )
+                        .await;
+                    }
+                    TypingRaceClientMessage::Reaction { emoji } => {
+                        handle_reaction::<TypingRace>(
+                            lobby_id,
+                            player.id,
+                            emoji,
+                            connections,
+                            &redis,
+                        )
+                        .await;
+                    }
+                    TypingRaceClientMessage::ResultAck => {
+                        if let Err(e) = ack_results_for_player(player.id, lobby_id, &redis).await {
+                            tracing::error!(
+                                "Failed to ack results for player {}: {}",
+                                player.id,
+                                e
+                            );
+                        }
+                    }
+                    TypingRaceClientMessage::Submit { text } => {
+                        handle_submission(player.id, lobby_id, &text, connections, &redis).await;
+                    }
+                }
+            }
+            Ok(Message::Close(_)) => {
+                tracing::debug!("Player {} closed the connection", player.id);
+                break;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::debug!("WebSocket error for player {}: {}", player.id, e);
+                break;
+            }
+        }
+    }
+}
+
+async fn handle_submission(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    text: &str,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let race = match get_race(lobby_id, redis.clone()).await {
+        Ok(Some(race)) if !race.ended => race,
+        Ok(_) => {
+            let error_msg = TypingRaceServerMessage::Error {
+                code: ErrorCode::GameNotStarted,
+                message: "No race is running".to_string(),
+            };
+            broadcast_to_player(player_id, lobby_id, &error_msg, connections, redis).await;
+            return;
+        }
+        Err(e) => {
+            report_socket_error::<TypingRace>(player_id, lobby_id, &e, connections, redis).await;
+            return;
+        }
+    };
+
+    let elapsed_ms = (Utc::now().timestamp_millis() - race.started_at).max(0) as u64;
+    let scored = score_submission(&race.prompt, text, elapsed_ms);
+    let result = TypingResult {
+        score: scored.score,
+        accuracy: scored.accuracy,
+        wpm: scored.wpm,
+        elapsed_ms,
+    };
+
+    let submitted = match record_submission(lobby_id, player_id, &result, redis.clone()).await {
+        Ok(SubmitOutcome::Recorded { submitted }) => submitted,
+        Ok(SubmitOutcome::AlreadySubmitted) => {
+            let error_msg = TypingRaceServerMessage::Error {
+                code: ErrorCode::BadRequest,
+                message: "You already submitted".to_string(),
+            };
+            broadcast_to_player(player_id, lobby_id, &error_msg, connections, redis).await;
+            return;
+        }
+        Ok(SubmitOutcome::NoOpenRace) => {
+            let error_msg = TypingRaceServerMessage::Error {
+                code: ErrorCode::GameNotStarted,
+                message: "No race is running".to_string(),
+            };
+            broadcast_to_player(player_id, lobby_id, &error_msg, connections, redis).await;
+            return;
+        }
+        Err(e) => {
+            report_socket_error::<TypingRace>(player_id, lobby_id, &e, connections, redis).await;
+            return;
+        }
+    };
+
+    if let Err(e) = add_player_score(lobby_id, player_id, result.score, redis.clone()).await {
+        tracing::error!("Failed to add score for player {}: {}", player_id, e);
+    }
+
+    let score = result.score;
+    let result_msg = TypingRaceServerMessage::Result { result };
+    broadcast_to_player(player_id, lobby_id, &result_msg, connections, redis).await;
+
+    let players = get_lobby_players(lobby_id, None, redis.clone())
+        .await
+        .unwrap_or_default();
+    let submitted_msg = TypingRaceServerMessage::Submitted { player_id, score };
+    broadcast_to_lobby_and_spectators(&submitted_msg, &players, lobby_id, connections, redis).await;
+
+    let player_count = get_current_players_ids(lobby_id, redis.clone())
+        .await
+        .map(|ids| ids.len())
+        .unwrap_or(usize::MAX);
+    if submitted >= player_count
+        && let Err(e) = end_race(lobby_id, connections, redis.clone()).await
+    {
+        tracing::error!("Failed to end race of lobby {}: {}", lobby_id, e);
+    }
+}
+
+async fn start_race(
+    lobby_id: Uuid,
+    connected_player_ids: Vec<Uuid>,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+) -> Result<(), EngineError> {
+    let prompt = random_prompt(PROMPT_WORDS).ok_or("Typing prompt corpus is not loaded")?;
+
+    set_game_started(lobby_id, true, redis.clone()).await?;
+    create_current_players(lobby_id, connected_player_ids, redis.clone()).await?;
+    open_race(
+        lobby_id,
+        &prompt,
+        Utc::now().timestamp_millis(),
+        redis.clone(),
+    )
+    .await?;
+
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+    let game_started_msg = TypingRaceServerMessage::Start {
+        time: 0,
+        started: true,
+    };
+    broadcast_to_lobby_and_spectators(&game_started_msg, &players, lobby_id, connections, &redis)
+        .await;
+
+    let prompt_msg = TypingRaceServerMessage::Prompt {
+        text: prompt,
+        countdown: RACE_SECS,
+    };
+    broadcast_to_lobby_and_spectators(&prompt_msg, &players, lobby_id, connections, &redis).await;
+
+    arm_race_end(
+        lobby_id,
+        Duration::from_secs(RACE_SECS),
+        connections.clone(),
+        redis,
+    );
+
+    tracing::info!("Typing race started for lobby {}", lobby_id);
+    Ok(())
+}
+
+/// Ends the race once `wait` has passed, unless every player submitted first.
+fn arm_race_end(
+    lobby_id: Uuid,
+    wait: Duration,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+) {
+    tokio::spawn(
+        async move {
+            tokio::time::sleep(wait).await;
+            if let Err(e) = end_race(lobby_id, &connections, redis).await {
+                tracing::error!("Failed to end race of lobby {}: {}", lobby_id, e);
+            }
+        }
+        .in_current_span(),
+    );
+}
+
+/// Gives a running race its end timer back with the time it had left. A race
+/// that is over or was never opened ends with the submissions it has.
+pub async fn recover_game(
+    lobby_id: Uuid,
+    _remaining_ms: Option<i64>,
+    connections: ConnectionInfoMap,
+    redis: RedisClient,
+    _telegram_bot: Bot,
+) -> Result<(), EngineError> {
+    match get_race(lobby_id, redis.clone()).await? {
+        Some(race) if !race.ended => {
+            let ends_at_ms = race.started_at + (RACE_SECS * 1000) as i64;
+            let wait_ms = (ends_at_ms - Utc::now().timestamp_millis()).max(0) as u64;
+            arm_race_end(lobby_id, Duration::from_millis(wait_ms), connections, redis);
+            tracing::info!("Resumed race of lobby {} with {}ms left", lobby_id, wait_ms);
+            Ok(())
+        }
+        _ => {
+            tracing::info!("Lobby {} cannot be resumed, ending race", lobby_id);
+            end_race(lobby_id, &connections, redis).await
+        }
+    }
+}
+
+/// Ranks players by score, the faster of a tie first, and delivers the
+/// results. Players level on both draw, those who never submitted share the
+/// last placing, and a race nobody submitted in is a draw. Runs once per race.
+async fn end_race(
+    lobby_id: Uuid,
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+) -> Result<(), EngineError> {
+    if !claim_race_end(lobby_id, redis.clone()).await? {
+        return Ok(());
+    }
+    update_lobby_state(lobby_id, LobbyState::Finished, redis.clone()).await?;
+
+    let lobby_info = get_lobby_info(lobby_id, redis.clone()).await?;
+    let mut submissions = get_submissions(lobby_id, redis.clone()).await?;
+    let player_ids = get_current_players_ids(lobby_id, redis.clone()).await?;
+
+    if submissions.is_empty() {
+        return end_in_draw(&lobby_info, &player_ids, connections, redis).await;
+    }
+
+    // Players level on score and time draw, and those who never submitted
+    // all share the last placing
+    let groups = group_by_key(&player_ids, |id| match submissions.get(id) {
+        Some(result) => (0, std::cmp::Reverse(result.score), result.elapsed_ms),
+        None => (1, std::cmp::Reverse(0), 0),
+    });
+    let placings = group_placings(&groups);
+
+    let standing: Vec<TypingStanding> = placings
+        .iter()
+        .map(|&(id, placing)| TypingStanding {
+            player_id: id,
+            rank: placing.rank,
+            result: submissions.remove(&id),
+        })
+        .collect();
+
+    let connected_players_count = player_ids.len();
+    for &(player_id, placing) in &placings {
+        send_player_results::<TypingRace>(
+            player_id,
+            &lobby_info,
+            connected_players_count,
+            placing,
+            connections,
+            &redis,
+        )
+        .await;
+    }
+
+    // A drawn first place has no single winner, so bets on it are returned
+    let winner_id = match groups.first().map(Vec::as_slice) {
+        Some(&[winner]) => Some(winner),
+        _ => None,
+    };
+    settle_bets::<TypingRace>(lobby_id, winner_id, connections, &redis).await;
+    emit(GameEvent::GameEnded {
+        lobby_id,
+        winner_id,
+    });
+
+    let standing_msg = TypingRaceServerMessage::FinalStanding { standing };
+    let gameover_msg = TypingRaceServerMessage::GameOver;
+    for &player_id in &player_ids {
+        send_result_to_player(player_id, lobby_id, &standing_msg, connections, &redis).await;
+        send_result_to_player(player_id, lobby_id, &gameover_msg, connections, &redis).await;
+    }
+    broadcast_to_spectators(&standing_msg, lobby_id, connections, &redis).await;
+    broadcast_to_spectators(&gameover_msg, lobby_id, connections, &redis).await;
+
+    if let Err(e) = clear_lobby_game_state(lobby_id, redis.clone()).await {
+        tracing::error!("Failed to clear lobby game state: {}", e);
+    }
+
+    tracing::info!("Typing race ended for lobby {}", lobby_id);
+    Ok(())
+}
+
+/// Ends a race nobody submitted in: entries are refunded and bets returned.
+async fn end_in_draw(
+    lobby_info: &LobbyInfo,
+    player_ids: &[Uuid],
+    connections: &ConnectionInfoMap,
+    redis: RedisClient,
+) -> Result<(), EngineError> {
+    let lobby_id = lobby_info.id;
+    let players = get_lobby_players(lobby_id, None, redis.clone()).await?;
+
+    let draw_msg = TypingRaceServerMessage::Draw;
+    broadcast_to_lobby_and_spectators(&draw_msg, &players, lobby_id, connections, &redis).await;
+
+    settle_draw::<TypingRace>(lobby_info, player_ids, connections, &redis).await?;
+
+    let gameover_msg = TypingRaceServerMessage::GameOver;
+    for &player_id in player_ids {
+        send_result_to_player(player_id, lobby_id, &gameover_msg, connections, &redis).await;
+    }
+    broadcast_to_spectators(&gameover_msg, lobby_id, connections, &redis).await;
+
+    if let Err(e) = clear_lobby_game_state(lobby_id, redis.clone()).await {
+        tracing::error!("Failed to clear lobby game state: {}", e);
+    }
+
+    tracing::info!(
+        "Typing race in lobby {} ended without submissions",
+        lobby_id
+    );
+    Ok(())
+}
@@ -0,0 +1,29 @@
+pub mod engine;
+pub mod prompts;
+pub mod scoring;
+
+pub use engine::{handle_incoming_messages, start_auto_start_timer};
+
+use axum::routing::get;
+use futures::FutureExt;
+
+use crate::{games::registry::GameRegistration, ws::handlers::typing_race_handler};
+
+pub fn registration() -> GameRegistration {
+    GameRegistration {
+        name: "Typing Race",
+        description: "Everyone types the same prompt; the fastest accurate typist wins.",
+        // No artwork uploaded yet
+        image_url: "",
+        tags: &["quick", "speed", "words"],
+        min_players: 2,
+        max_players: None,
+        ws_path: "/ws/typing-race/{lobby_id}",
+        ws_handler: || get(typing_race_handler),
+        validate_settings: |settings, _redis| async move { Ok(settings) }.boxed(),
+        turns: None,
+        recover: |lobby_id, remaining_ms, connections, redis, bot| {
+            engine::recover_game(lobby_id, remaining_ms, connections, redis, bot).boxed()
+        },
+    }
+}
@@ -0,0 +1,50 @@
+use rand::seq::IndexedRandom;
+use std::sync::OnceLock;
+
+use crate::{
+    db::game::words::{default_dictionary, get_offensive_words},
+    errors::AppError,
+    state::RedisClient,
+};
+
+/// Word lengths that make for a readable prompt
+const MIN_WORD_LEN: usize = 3;
+const MAX_WORD_LEN: usize = 8;
+
+/// Dictionary words prompts are drawn from.
+static PROMPT_CORPUS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Builds the prompt corpus from the default dictionary, leaving out
+/// offensive words. Must run after the dictionary is loaded.
+pub async fn load_prompt_corpus(redis: RedisClient) -> Result<(), AppError> {
+    let offensive = get_offensive_words(redis).await?;
+    let dictionary = default_dictionary().ok_or(AppError::InternalError)?;
+
+    let mut corpus: Vec<String> = dictionary
+        .iter()
+        .filter(|word| (MIN_WORD_LEN..=MAX_WORD_LEN).contains(&word.len()))
+        .filter(|word| word.chars().all(|c| c.is_ascii_lowercase()))
+        .filter(|word| !offensive.contains(*word))
+        .cloned()
+        .collect();
+    corpus.sort();
+
+    let count = corpus.len();
+    if PROMPT_CORPUS.set(corpus).is_err() {
+        tracing::warn!("Prompt corpus already loaded");
+        return Ok(());
+    }
+
+    tracing::info!("Loaded {} words into the typing prompt corpus", count);
+    Ok(())
+}
+
+/// A prompt of `words` random corpus words, `None` before the corpus is loaded.
+pub fn random_prompt(words: usize) -> Option<String> {
+    let corpus = PROMPT_CORPUS.get().filter(|corpus| !corpus.is_empty())?;
+    let mut rng = rand::rng();
+    let prompt: Vec<&str> = (0..words)
+        .filter_map(|_| corpus.choose(&mut rng).map(String::as_str))
+        .collect();
+    Some(prompt.join(" "))
+}
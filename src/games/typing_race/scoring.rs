@@ -0,0 +1,50 @@
+/// Characters per word in the standard words-per-minute measure
+const CHARS_PER_WORD: f64 = 5.0;
+/// Floor on elapsed time so an instant submission can't divide by zero
+const MIN_ELAPSED_MS: u64 = 1_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypingScore {
+    pub correct_words: usize,
+    /// Share of prompt words typed correctly, 0 to 1
+    pub accuracy: f64,
+    /// Speed over the correctly typed words only
+    pub wpm: f64,
+    pub score: u64,
+}
+
+/// Scores a submission against the prompt. Words are compared in order, so
+/// a skipped word costs every word after it. The score is speed weighted by
+/// accuracy, which keeps fast but sloppy typing from winning.
+pub fn score_submission(prompt: &str, typed: &str, elapsed_ms: u64) -> TypingScore {
+    let expected: Vec<&str> = prompt.split_whitespace().collect();
+    let correct: Vec<&str> = expected
+        .iter()
+        .zip(typed.split_whitespace())
+        .filter(|(expected, typed)| *expected == typed)
+        .map(|(expected, _)| *expected)
+        .collect();
+
+    let accuracy = if expected.is_empty() {
+        0.0
+    } else {
+        correct.len() as f64 / expected.len() as f64
+    };
+
+    // Each correct word counts with the space after it
+    let correct_chars: usize = correct.iter().map(|word| word.len() + 1).sum();
+    let minutes = elapsed_ms.max(MIN_ELAPSED_MS) as f64 / 60_000.0;
+    let wpm = correct_chars as f64 / CHARS_PER_WORD / minutes;
+
+    TypingScore {
+        correct_words: correct.len(),
+        accuracy,
+        wpm,
+        score: (wpm * accuracy * 10.0).round() as u64,
+    }
+}
+
+/// Small wars point bonus for a race score, capped so rank still dominates.
+pub fn score_wars_point_bonus(score: u64) -> f64 {
+    (score as f64 / 200.0).min(5.0)
+}
@@ -9,7 +9,7 @@ use teloxide::{
     types::{ChatId, InlineKeyboardButton, InlineKeyboardMarkup, InputFile, MessageId, ParseMode},
 };
 
-use crate::models::game::GameType;
+use crate::{config, models::game::GameType};
 use uuid::Uuid;
 
 pub struct BotNewLobbyPayload {
@@ -33,7 +33,10 @@ pub struct BotLobbyWinnerPayload {
     pub winner_name: Option<String>,
     pub winner_wallet: String,
     pub winner_prize: Option<f64>,
+    /// Set when the winner linked their Telegram account, used to @mention them
+    pub winner_telegram_id: Option<u64>,
     pub entry_amount: Option<f64>,
+    pub token_symbol: String,
     pub runner_ups: Vec<RunnerUp>,
     pub tg_msg_id: i32,
 }
@@ -51,7 +54,7 @@ pub async fn broadcast_lobby_created(
     chat_id: i64,
     payload: BotNewLobbyPayload,
 ) -> Result<teloxide::types::Message, teloxide::RequestError> {
-    let network = std::env::var("STACKS_NETWORK").unwrap_or("testnet".to_string());
+    let network = config::get().stacks_network.clone();
     let wallet = payload.wallet_address.clone();
     let truncated_wallet = format!("{}...{}", &wallet[0..4], &wallet[wallet.len() - 4..]);
 
@@ -161,7 +164,7 @@ pub async fn broadcast_lobby_winner(
         &winner_wallet[winner_wallet.len() - 4..]
     );
 
-    let winner_display = payload
+    let mut winner_display = payload
         .winner_name
         .as_ref()
         .map(|name| {
@@ -173,6 +176,13 @@ pub async fn broadcast_lobby_winner(
         })
         .unwrap_or_else(|| encode_text(&winner_wallet).to_string());
 
+    if let Some(telegram_id) = payload.winner_telegram_id {
+        winner_display = format!(
+            "<a href=\"tg://user?id={}\">{}</a>",
+            telegram_id, winner_display
+        );
+    }
+
     let mut content = format!(
         "🎉 <b>Game Finished!</b>\n\n\
         🏆 <b>Winner:</b> {}\n",
@@ -183,11 +193,17 @@ pub async fn broadcast_lobby_winner(
     match (payload.winner_prize, payload.entry_amount) {
         (Some(prize), Some(entry)) => {
             let net_prize = prize - entry;
-            content.push_str(&format!("💰 <b>Prize Won:</b> {:.2} STX\n", net_prize));
+            content.push_str(&format!(
+                "💰 <b>Prize Won:</b> {:.2} {}\n",
+                net_prize, payload.token_symbol
+            ));
         }
         (Some(prize), None) => {
             // No entry fee, so full prize amount
-            content.push_str(&format!("💰 <b>Prize Won:</b> {:.2} STX\n", prize));
+            content.push_str(&format!(
+                "💰 <b>Prize Won:</b> {:.2} {}\n",
+                prize, payload.token_symbol
+            ));
         }
         _ => {
             // No prize information available
@@ -236,7 +252,8 @@ pub async fn broadcast_lobby_winner(
                 };
 
                 if net_prize > 0.0 {
-                    runner_up_line.push_str(&format!(" - {:.2} STX", net_prize));
+                    runner_up_line
+                        .push_str(&format!(" - {:.2} {}", net_prize, payload.token_symbol));
                 }
             }
 
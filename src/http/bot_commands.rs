@@ -1,10 +1,26 @@
+use reqwest::Url;
 use teloxide::{
     prelude::*,
-    types::{Message, ParseMode},
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, Message, ParseMode},
     utils::command::BotCommands,
 };
 
-use crate::{db::leaderboard::get::get_leaderboard, state::RedisClient};
+use crate::{
+    db::{
+        game::get::get_all_games,
+        leaderboard::get::{get_leaderboard, get_user_stat},
+        lobby::{
+            get::get_all_lobbies_info,
+            post::{NewLobby, create_lobby},
+        },
+        telegram::post::create_join_code,
+        user::{get::get_user_id_by_telegram, post::link_telegram_account},
+    },
+    errors::AppError,
+    games::lexi_wars,
+    models::game::{LobbySettings, LobbyState},
+    state::RedisClient,
+};
 
 #[derive(BotCommands, Clone)]
 #[command(
@@ -14,6 +30,14 @@ use crate::{db::leaderboard::get::get_leaderboard, state::RedisClient};
 pub enum Command {
     #[command(description = "Show the top 10 leaderboard")]
     Leaderboard,
+    #[command(description = "Show your stats (requires a linked account)")]
+    Stats,
+    #[command(description = "List lobbies that are open or in progress")]
+    ActiveLobbies,
+    #[command(description = "Create a lobby: /newlobby <fee tx id> <lobby name>")]
+    NewLobby(String),
+    #[command(description = "Link your Stacks Wars account: /link <code>")]
+    Link(String),
 }
 
 pub async fn handle_command(
@@ -24,9 +48,155 @@ pub async fn handle_command(
 ) -> ResponseResult<()> {
     match cmd {
         Command::Leaderboard => handle_leaderboard_command(bot, msg, redis).await,
+        Command::Stats => handle_stats_command(bot, msg, redis).await,
+        Command::ActiveLobbies => handle_active_lobbies_command(bot, msg, redis).await,
+        Command::NewLobby(args) => handle_new_lobby_command(bot, msg, args, redis).await,
+        Command::Link(code) => handle_link_command(bot, msg, code, redis).await,
     }
 }
 
+async fn handle_link_command(
+    bot: Bot,
+    msg: Message,
+    code: String,
+    redis: RedisClient,
+) -> ResponseResult<()> {
+    let Some(telegram_user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+
+    if code.trim().is_empty() {
+        bot.send_message(msg.chat.id, "Usage: /link <code>").await?;
+        return Ok(());
+    }
+
+    match link_telegram_account(&code, telegram_user.id.0, redis).await {
+        Ok(_) => {
+            bot.send_message(msg.chat.id, "✅ Your Telegram account is now linked")
+                .await?;
+        }
+        Err(AppError::BadRequest(reason)) | Err(AppError::Unauthorized(reason)) => {
+            bot.send_message(msg.chat.id, format!("❌ {}", reason))
+                .await?;
+        }
+        Err(e) => {
+            tracing::error!("Failed to link telegram account: {}", e);
+            bot.send_message(msg.chat.id, "❌ Failed to link your account")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_new_lobby_command(
+    bot: Bot,
+    msg: Message,
+    args: String,
+    redis: RedisClient,
+) -> ResponseResult<()> {
+    let Some(telegram_user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+    let telegram_user_id = telegram_user.id.0;
+
+    let mut parts = args.trim().splitn(2, char::is_whitespace);
+    let (Some(tx_id), Some(name)) = (parts.next().filter(|s| !s.is_empty()), parts.next()) else {
+        bot.send_message(msg.chat.id, "Usage: /newlobby <fee tx id> <lobby name>")
+            .await?;
+        return Ok(());
+    };
+    let name = name.trim().to_string();
+
+    let user_id = match get_user_id_by_telegram(telegram_user_id, redis.clone()).await {
+        Ok(Some(user_id)) => user_id,
+        Ok(None) => {
+            bot.send_message(
+                msg.chat.id,
+                "🔗 Link your Telegram account on stackswars.com before creating lobbies here",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::error!("Failed to look up telegram link: {}", e);
+            bot.send_message(msg.chat.id, "❌ Failed to create lobby")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    // Lexi Wars is the only game that can be started from Telegram for now
+    let game_id = match get_all_games(redis.clone()).await {
+        Ok(games) => match games.iter().find(|g| g.name == lexi_wars::NAME) {
+            Some(game) => game.id,
+            None => {
+                bot.send_message(msg.chat.id, "❌ Lexi Wars is not available")
+                    .await?;
+                return Ok(());
+            }
+        },
+        Err(e) => {
+            tracing::error!("Failed to get games: {}", e);
+            bot.send_message(msg.chat.id, "❌ Failed to create lobby")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let new_lobby = NewLobby {
+        name: name.clone(),
+        description: None,
+        game_id,
+        pool: None,
+        settings: LobbySettings::default(),
+        tx_id: tx_id.to_string(),
+    };
+    let lobby_id = match create_lobby(new_lobby, user_id, redis.clone(), bot.clone()).await {
+        Ok(lobby_id) => lobby_id,
+        Err(e) => {
+            tracing::error!("Failed to create lobby from telegram: {}", e);
+            bot.send_message(msg.chat.id, format!("❌ Failed to create lobby: {}", e))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let code = match create_join_code(lobby_id, telegram_user_id, msg.chat.id.0, redis).await {
+        Ok(code) => code,
+        Err(e) => {
+            tracing::error!("Failed to create join code for lobby {}: {}", lobby_id, e);
+            bot.send_message(msg.chat.id, "❌ Lobby created but the join link failed")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let join_url = Url::parse(&format!("https://stackswars.com/tg/join/{}", code)).unwrap();
+    let keyboard = InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::url(
+        "🚀 Join Lobby",
+        join_url,
+    )]]);
+
+    bot.send_message(
+        msg.chat.id,
+        format!(
+            "🆕 <b>{}</b> is open, tap below to join",
+            html_escape::encode_text(&name)
+        ),
+    )
+    .parse_mode(ParseMode::Html)
+    .reply_markup(keyboard)
+    .await?;
+
+    tracing::info!(
+        "Lobby {} created from telegram by user {}",
+        lobby_id,
+        user_id
+    );
+    Ok(())
+}
+
 async fn handle_leaderboard_command(
     bot: Bot,
     msg: Message,
@@ -34,8 +204,8 @@ async fn handle_leaderboard_command(
 ) -> ResponseResult<()> {
     tracing::debug!("Processing /leaderboard command from chat {}", msg.chat.id);
 
-    let leaderboard = match get_leaderboard(Some(10), redis).await {
-        Ok(data) => data,
+    let leaderboard = match get_leaderboard(1, 10, None, redis).await {
+        Ok(data) => data.items,
         Err(e) => {
             tracing::error!("Failed to get leaderboard: {}", e);
             bot.send_message(msg.chat.id, "❌ Failed to retrieve leaderboard data")
@@ -103,3 +273,147 @@ async fn handle_leaderboard_command(
     tracing::debug!("Successfully sent leaderboard to chat {}", msg.chat.id);
     Ok(())
 }
+
+async fn handle_stats_command(bot: Bot, msg: Message, redis: RedisClient) -> ResponseResult<()> {
+    let Some(telegram_user) = msg.from.as_ref() else {
+        return Ok(());
+    };
+
+    let user_id = match get_user_id_by_telegram(telegram_user.id.0, redis.clone()).await {
+        Ok(Some(user_id)) => user_id,
+        Ok(None) => {
+            bot.send_message(
+                msg.chat.id,
+                "🔗 Link your Telegram account on stackswars.com to see your stats",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::error!("Failed to look up telegram link: {}", e);
+            bot.send_message(msg.chat.id, "❌ Failed to retrieve your stats")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let stat = match get_user_stat(user_id, redis).await {
+        Ok(stat) => stat,
+        Err(e) => {
+            tracing::error!("Failed to get stats for user {}: {}", user_id, e);
+            bot.send_message(msg.chat.id, "❌ Failed to retrieve your stats")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let display_name = stat
+        .user
+        .display_name
+        .as_ref()
+        .or(stat.user.username.as_ref())
+        .map(|name| html_escape::encode_text(name).to_string())
+        .unwrap_or_else(|| {
+            let wallet = &stat.user.wallet_address;
+            format!("{}...{}", &wallet[0..4], &wallet[wallet.len() - 4..])
+        });
+
+    let mut response = format!("📊 <b>Stats for {}</b>\n\n", display_name);
+    response.push_str(&format!("🏅 Rank: <code>#{}</code>\n", stat.rank));
+    response.push_str(&format!(
+        "📈 Wars Points: <code>{:.1}</code>\n",
+        stat.user.wars_point
+    ));
+    response.push_str(&format!(
+        "🎯 Win Rate: <code>{:.1}%</code> ({}/{})\n",
+        stat.win_rate, stat.total_wins, stat.total_match
+    ));
+    if stat.pnl != 0.0 {
+        let pnl_emoji = if stat.pnl > 0.0 { "💰" } else { "💸" };
+        response.push_str(&format!(
+            "{} P&L: <code>{:.2} STX</code>\n",
+            pnl_emoji, stat.pnl
+        ));
+    }
+
+    bot.send_message(msg.chat.id, response)
+        .parse_mode(ParseMode::Html)
+        .await?;
+
+    Ok(())
+}
+
+async fn handle_active_lobbies_command(
+    bot: Bot,
+    msg: Message,
+    redis: RedisClient,
+) -> ResponseResult<()> {
+    tracing::debug!(
+        "Processing /activelobbies command from chat {}",
+        msg.chat.id
+    );
+
+    let lobbies = match get_all_lobbies_info(
+        Some(vec![
+            LobbyState::Waiting,
+            LobbyState::Starting,
+            LobbyState::InProgress,
+        ]),
+        1,
+        10,
+        None,
+        redis,
+    )
+    .await
+    {
+        Ok(lobbies) => lobbies,
+        Err(e) => {
+            tracing::error!("Failed to get active lobbies: {}", e);
+            bot.send_message(msg.chat.id, "❌ Failed to retrieve active lobbies")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    if lobbies.items.is_empty() {
+        bot.send_message(msg.chat.id, "🕹 No active lobbies right now")
+            .await?;
+        return Ok(());
+    }
+
+    let mut response = format!("🕹 <b>Active Lobbies</b> ({} total)\n\n", lobbies.total);
+
+    for lobby in &lobbies.items {
+        let state = match lobby.state {
+            LobbyState::Waiting => "⏳ Waiting",
+            LobbyState::Starting => "🚦 Starting",
+            _ => "🎮 In progress",
+        };
+
+        response.push_str(&format!(
+            "<b>{}</b> · {}\n",
+            html_escape::encode_text(&lobby.name),
+            html_escape::encode_text(&lobby.game.name)
+        ));
+        response.push_str(&format!(
+            "   {} · 👥 {} players\n",
+            state, lobby.participants
+        ));
+
+        if let Some(entry) = lobby.entry_amount {
+            let token = lobby.pool_symbol();
+            response.push_str(&format!("   💵 Entry: <code>{} {}</code>\n", entry, token));
+        }
+
+        response.push_str(&format!(
+            "   🔗 <code>https://stackswars.com/lobby/{}</code>\n\n",
+            lobby.id
+        ));
+    }
+
+    bot.send_message(msg.chat.id, response)
+        .parse_mode(ParseMode::Html)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,1681 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    auth::AdminClaims,
+    db::{
+        audit::{get::get_audit_entries, post::record_audit_entry},
+        chat::moderation::{clear_chat_report, get_chat_reports},
+        game::{
+            anticheat::get_suspicion_flags,
+            get::get_game,
+            lexi_rules::{save_custom_rule, set_rule_order},
+            word_reports::{clear_word_reports, get_word_reports},
+            words::{add_dictionary_words, remove_dictionary_words, upload_word_pack},
+        },
+        lobby::{
+            get::{
+                get_connected_players_ids, get_disputed_claims, get_lobby_state_history,
+                get_player_lobbies,
+            },
+            patch::update_claim_state,
+            refunds::mark_refund_paid,
+            schedules::{create_schedule, delete_schedule, get_schedules},
+        },
+        moderation::{
+            bans::{ban_user, get_bans, lift_ban},
+            get::get_banned_words_config,
+            patch::update_banned_words,
+        },
+        telegram::{get::get_telegram_routes, post::set_telegram_route},
+        user::{
+            delete::soft_delete_user,
+            get::{get_recent_user_errors, get_user_by_id},
+            linked_accounts::{get_linked_accounts, get_multi_account_reports},
+        },
+        webhook::subscriptions::{
+            WebhookUpdate, create_webhook, delete_webhook, get_webhooks, update_webhook,
+        },
+    },
+    errors::AppError,
+    games::lexi_wars::{
+        rule_dsl::{RuleDefinition, custom_rules},
+        rules::rule_names,
+        utils::broadcast_to_player,
+    },
+    games::schedules::Recurrence,
+    models::{
+        admin::{
+            AccountLink, ConnectionMetrics, DisputedClaim, LexiRulesView, LinkedAccount,
+            MultiAccountReport, SupportActiveLobby, SupportConnections, SupportPendingClaim,
+            SupportView, SuspicionFlag, WordReport,
+        },
+        audit::{AuditEntry, AuditFilter},
+        chat::ChatReport,
+        game::{ClaimState, Language, LobbySettings, LobbyState},
+        lexi_wars::LexiWarsServerMessage,
+        lobby::{LobbySchedule, LobbyStateChange},
+        moderation::{Ban, BannedWordsConfig, FilterMode},
+        telegram::TelegramRoutes,
+        webhook::{SignedWebhook, Webhook, WebhookEvent},
+    },
+    state::AppState,
+    ws::{lifecycle::get_connection_metrics, sessions::close_user_sockets},
+};
+
+fn wallet_hint(wallet_address: &str) -> String {
+    let chars: Vec<char> = wallet_address.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{head}...{tail}")
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/users/{user_id}/support-view",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "Support view of the user", body = SupportView),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_support_view_handler(
+    Path(user_id): Path<Uuid>,
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<SupportView>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    // Every access is audited, even ones that end up failing below
+    let entry = AuditEntry::new(admin_id, "support_view", Some(user_id));
+    record_audit_entry(&entry, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to write audit entry: {}", e);
+            e.to_response()
+        })?;
+
+    let user = get_user_by_id(user_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get user {} for support view: {}", user_id, e);
+            e.to_response()
+        })?;
+
+    let active = get_player_lobbies(
+        user_id,
+        None,
+        Some(vec![
+            LobbyState::Waiting,
+            LobbyState::Starting,
+            LobbyState::InProgress,
+        ]),
+        1,
+        u32::MAX,
+        None,
+        state.redis.clone(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to get active lobbies for {}: {}", user_id, e);
+        e.to_response()
+    })?;
+
+    let unclaimed = get_player_lobbies(
+        user_id,
+        Some(ClaimState::Claimable),
+        None,
+        1,
+        u32::MAX,
+        None,
+        state.redis.clone(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to get pending claims for {}: {}", user_id, e);
+        e.to_response()
+    })?;
+
+    let recent_errors = get_recent_user_errors(user_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get recent errors for {}: {}", user_id, e);
+            e.to_response()
+        })?;
+
+    let mut connected_lobbies = Vec::new();
+    for info in &active.items {
+        match get_connected_players_ids(info.lobby.id, state.redis.clone()).await {
+            Ok(ids) if ids.contains(&user_id) => connected_lobbies.push(info.lobby.id),
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!(
+                    "Failed to get connected players for {}: {}",
+                    info.lobby.id,
+                    e
+                );
+            }
+        }
+    }
+
+    let connections = SupportConnections {
+        game_socket: state.connections.lock().await.contains_key(&user_id),
+        chat_socket: state.chat_connections.lock().await.contains_key(&user_id),
+        connected_lobbies,
+    };
+
+    let active_lobbies = active
+        .items
+        .into_iter()
+        .map(|info| SupportActiveLobby {
+            id: info.lobby.id,
+            name: info.lobby.name,
+            state: info.lobby.state,
+        })
+        .collect();
+
+    let pending_claims = unclaimed
+        .items
+        .into_iter()
+        .map(|info| SupportPendingClaim {
+            lobby_id: info.lobby.id,
+            lobby_name: info.lobby.name,
+            rank: info.rank,
+            prize: info.prize_amount,
+            token_symbol: info.lobby.token_symbol,
+        })
+        .collect();
+
+    tracing::info!("Admin {} viewed support info for {}", admin_id, user_id);
+
+    Ok(Json(SupportView {
+        user_id,
+        username: user.username,
+        display_name: user.display_name,
+        wallet_hint: wallet_hint(&user.wallet_address),
+        wars_point: user.wars_point,
+        connections,
+        active_lobbies,
+        pending_claims,
+        recent_errors,
+    }))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UploadDictionaryPackPayload {
+    pub name: String,
+    pub words: Vec<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/dictionary-packs",
+    tag = "admin",
+    request_body = UploadDictionaryPackPayload,
+    responses(
+        (status = 200, description = "Words stored in the pack", body = usize),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn upload_dictionary_pack_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<UploadDictionaryPackPayload>,
+) -> Result<Json<usize>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let count = upload_word_pack(&payload.name, payload.words, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to upload dictionary pack {}: {}", payload.name, e);
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(
+        admin_id,
+        format!("upload_dictionary_pack:{}", payload.name),
+        None,
+    );
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json(count))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct DictionaryWordsPayload {
+    pub words: Vec<String>,
+    #[serde(default)]
+    pub language: Language,
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/dictionary/words",
+    tag = "admin",
+    request_body = DictionaryWordsPayload,
+    responses(
+        (status = 200, description = "Words added", body = [String]),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn add_dictionary_words_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<DictionaryWordsPayload>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let added = add_dictionary_words(payload.words, payload.language, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to add dictionary words: {}", e);
+            e.to_response()
+        })?;
+
+    if !added.is_empty() {
+        let entry = AuditEntry::new(
+            admin_id,
+            format!(
+                "add_dictionary_words:{}:{}",
+                payload.language.as_str(),
+                added.join(",")
+            ),
+            None,
+        );
+        if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+            tracing::error!("Failed to write audit entry: {}", e);
+        }
+    }
+
+    Ok(Json(added))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/dictionary/words",
+    tag = "admin",
+    request_body = DictionaryWordsPayload,
+    responses(
+        (status = 200, description = "Words removed", body = [String]),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn remove_dictionary_words_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<DictionaryWordsPayload>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let removed = remove_dictionary_words(payload.words, payload.language, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to remove dictionary words: {}", e);
+            e.to_response()
+        })?;
+
+    if !removed.is_empty() {
+        let entry = AuditEntry::new(
+            admin_id,
+            format!(
+                "remove_dictionary_words:{}:{}",
+                payload.language.as_str(),
+                removed.join(",")
+            ),
+            None,
+        );
+        if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+            tracing::error!("Failed to write audit entry: {}", e);
+        }
+    }
+
+    Ok(Json(removed))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WordReportsQuery {
+    #[serde(default)]
+    pub language: Language,
+    pub limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/dictionary/reports",
+    tag = "admin",
+    params(WordReportsQuery),
+    responses(
+        (status = 200, description = "Most reported words", body = [WordReport]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_word_reports_handler(
+    AdminClaims(_): AdminClaims,
+    Query(query): Query<WordReportsQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<WordReport>>, (StatusCode, String)> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+
+    let reports = get_word_reports(query.language, limit, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get word reports: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(reports))
+}
+
+/// Adds reported words to the dictionary and closes their reports.
+#[utoipa::path(
+    post,
+    path = "/admin/dictionary/reports/approve",
+    tag = "admin",
+    request_body = DictionaryWordsPayload,
+    responses(
+        (status = 200, description = "Words added to the dictionary", body = [String]),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn approve_word_reports_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<DictionaryWordsPayload>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let words: Vec<String> = payload
+        .words
+        .iter()
+        .map(|w| w.trim().to_lowercase())
+        .collect();
+    let added = add_dictionary_words(payload.words, payload.language, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to approve word reports: {}", e);
+            e.to_response()
+        })?;
+
+    if let Err(e) = clear_word_reports(&words, payload.language, state.redis.clone()).await {
+        tracing::error!("Failed to clear approved word reports: {}", e);
+    }
+
+    let entry = AuditEntry::new(
+        admin_id,
+        format!(
+            "approve_word_reports:{}:{}",
+            payload.language.as_str(),
+            words.join(",")
+        ),
+        None,
+    );
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json(added))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/dictionary/reports",
+    tag = "admin",
+    request_body = DictionaryWordsPayload,
+    responses(
+        (status = 200, description = "Reports dismissed", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn dismiss_word_reports_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<DictionaryWordsPayload>,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let words: Vec<String> = payload
+        .words
+        .iter()
+        .map(|w| w.trim().to_lowercase())
+        .collect();
+    clear_word_reports(&words, payload.language, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to dismiss word reports: {}", e);
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(
+        admin_id,
+        format!(
+            "dismiss_word_reports:{}:{}",
+            payload.language.as_str(),
+            words.join(",")
+        ),
+        None,
+    );
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json("Word reports dismissed"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/chat/reports",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Open chat reports", body = [ChatReport]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_chat_reports_handler(
+    AdminClaims(_): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ChatReport>>, (StatusCode, String)> {
+    let reports = get_chat_reports(&state.redis).await.map_err(|e| {
+        tracing::error!("Failed to get chat reports: {}", e);
+        e.to_response()
+    })?;
+
+    Ok(Json(reports))
+}
+
+/// Closes a chat report after review.
+#[utoipa::path(
+    delete,
+    path = "/admin/chat/reports/{message_id}",
+    tag = "admin",
+    params(("message_id" = Uuid, Path, description = "Chat message id")),
+    responses(
+        (status = 200, description = "Report dismissed", body = String),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn dismiss_chat_report_handler(
+    Path(message_id): Path<Uuid>,
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    clear_chat_report(message_id, &state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to dismiss chat report {}: {}", message_id, e);
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(admin_id, format!("dismiss_chat_report:{message_id}"), None);
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json("Chat report dismissed"))
+}
+
+fn lexi_rules_view() -> LexiRulesView {
+    LexiRulesView {
+        progression: rule_names(),
+        custom_rules: custom_rules(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/lexi-rules",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Lexi Wars rules", body = LexiRulesView),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_lexi_rules_handler(AdminClaims(_): AdminClaims) -> Json<LexiRulesView> {
+    Json(lexi_rules_view())
+}
+
+/// Adds or replaces a custom rule; new rules join the end of the progression.
+#[utoipa::path(
+    post,
+    path = "/admin/lexi-rules",
+    tag = "admin",
+    request_body = RuleDefinition,
+    responses(
+        (status = 200, description = "Rule saved", body = LexiRulesView),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn save_lexi_rule_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<RuleDefinition>,
+) -> Result<Json<LexiRulesView>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    save_custom_rule(&payload, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to save rule {}: {}", payload.name, e);
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(admin_id, format!("save_lexi_rule:{}", payload.name), None);
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json(lexi_rules_view()))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct LexiRuleOrderPayload {
+    pub order: Vec<String>,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/admin/lexi-rules/order",
+    tag = "admin",
+    request_body = LexiRuleOrderPayload,
+    responses(
+        (status = 200, description = "Rule order saved", body = LexiRulesView),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn set_lexi_rule_order_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<LexiRuleOrderPayload>,
+) -> Result<Json<LexiRulesView>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    set_rule_order(payload.order, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to set rule order: {}", e);
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(admin_id, "reorder_lexi_rules", None);
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json(lexi_rules_view()))
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSchedulePayload {
+    pub name: String,
+    pub description: Option<String>,
+    pub game_id: Uuid,
+    #[serde(default)]
+    pub settings: LobbySettings,
+    pub recurrence: Recurrence,
+}
+
+/// Sets up a free lobby that opens again at every occurrence, hosted by the
+/// admin who created it.
+#[utoipa::path(
+    post,
+    path = "/admin/schedules",
+    tag = "admin",
+    request_body = CreateSchedulePayload,
+    responses(
+        (status = 200, description = "Schedule created", body = LobbySchedule),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn create_schedule_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<CreateSchedulePayload>,
+) -> Result<Json<LobbySchedule>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let schedule = create_schedule(
+        payload.name,
+        payload.description,
+        admin_id,
+        payload.game_id,
+        payload.settings,
+        payload.recurrence,
+        state.redis.clone(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create lobby schedule: {}", e);
+        e.to_response()
+    })?;
+
+    let entry = AuditEntry::new(
+        admin_id,
+        format!("create_lobby_schedule:{}", schedule.id),
+        None,
+    );
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json(schedule))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/schedules",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Lobby schedules", body = [LobbySchedule]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_schedules_handler(
+    AdminClaims(_): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<LobbySchedule>>, (StatusCode, String)> {
+    let schedules = get_schedules(state.redis.clone()).await.map_err(|e| {
+        tracing::error!("Failed to get lobby schedules: {}", e);
+        e.to_response()
+    })?;
+
+    Ok(Json(schedules))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/schedules/{schedule_id}",
+    tag = "admin",
+    params(("schedule_id" = Uuid, Path, description = "Schedule id")),
+    responses(
+        (status = 200, description = "Schedule deleted", body = String),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn delete_schedule_handler(
+    Path(schedule_id): Path<Uuid>,
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    delete_schedule(schedule_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete lobby schedule {}: {}", schedule_id, e);
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(
+        admin_id,
+        format!("delete_lobby_schedule:{schedule_id}"),
+        None,
+    );
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json("success"))
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookPayload {
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub description: Option<String>,
+}
+
+/// Registers an integration to receive the events it subscribes to. The
+/// response carries the signing secret, which is not shown again.
+#[utoipa::path(
+    post,
+    path = "/admin/webhooks",
+    tag = "admin",
+    request_body = CreateWebhookPayload,
+    responses(
+        (status = 200, description = "Webhook created", body = SignedWebhook),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn create_webhook_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<CreateWebhookPayload>,
+) -> Result<Json<SignedWebhook>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let signed = create_webhook(
+        payload.url,
+        payload.events,
+        payload.description,
+        state.redis.clone(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to create webhook: {}", e);
+        e.to_response()
+    })?;
+
+    let entry = AuditEntry::new(
+        admin_id,
+        format!("create_webhook:{}", signed.webhook.id),
+        None,
+    );
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json(signed))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/webhooks",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Webhooks, without their secrets", body = [Webhook]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_webhooks_handler(
+    AdminClaims(_): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Webhook>>, (StatusCode, String)> {
+    let webhooks = get_webhooks(state.redis.clone()).await.map_err(|e| {
+        tracing::error!("Failed to get webhooks: {}", e);
+        e.to_response()
+    })?;
+
+    Ok(Json(
+        webhooks.into_iter().map(|signed| signed.webhook).collect(),
+    ))
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhookPayload {
+    pub url: Option<String>,
+    pub events: Option<Vec<WebhookEvent>>,
+    /// An empty description clears it
+    pub description: Option<String>,
+    /// False pauses deliveries, including retries already queued
+    pub active: Option<bool>,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/admin/webhooks/{webhook_id}",
+    tag = "admin",
+    params(("webhook_id" = Uuid, Path, description = "Webhook id")),
+    request_body = UpdateWebhookPayload,
+    responses(
+        (status = 200, description = "Webhook updated", body = Webhook),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn update_webhook_handler(
+    Path(webhook_id): Path<Uuid>,
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<UpdateWebhookPayload>,
+) -> Result<Json<Webhook>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let update = WebhookUpdate {
+        url: payload.url,
+        events: payload.events,
+        description: payload.description,
+        active: payload.active,
+    };
+    let webhook = update_webhook(webhook_id, update, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to update webhook {}: {}", webhook_id, e);
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(admin_id, format!("update_webhook:{webhook_id}"), None);
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json(webhook))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/webhooks/{webhook_id}",
+    tag = "admin",
+    params(("webhook_id" = Uuid, Path, description = "Webhook id")),
+    responses(
+        (status = 200, description = "Webhook deleted", body = String),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn delete_webhook_handler(
+    Path(webhook_id): Path<Uuid>,
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    delete_webhook(webhook_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete webhook {}: {}", webhook_id, e);
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(admin_id, format!("delete_webhook:{webhook_id}"), None);
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json("success"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/telegram/routes",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Announcement chat per game", body = TelegramRoutes),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_telegram_routes_handler(
+    AdminClaims(_): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<TelegramRoutes>, (StatusCode, String)> {
+    let routes = get_telegram_routes(state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get telegram routes: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(routes))
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTelegramRoutePayload {
+    /// Left out to change the default chat
+    pub game_id: Option<Uuid>,
+    /// Null removes the route, falling back to the default, or to
+    /// `TELEGRAM_CHAT_ID` for the default itself
+    pub chat_id: Option<i64>,
+}
+
+/// Points a game's lobby and winner announcements at a Telegram chat. Lobbies
+/// already announced keep posting their results where they were announced.
+#[utoipa::path(
+    patch,
+    path = "/admin/telegram/routes",
+    tag = "admin",
+    request_body = UpdateTelegramRoutePayload,
+    responses(
+        (status = 200, description = "Routes after the change", body = TelegramRoutes),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn update_telegram_route_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<UpdateTelegramRoutePayload>,
+) -> Result<Json<TelegramRoutes>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    if let Some(game_id) = payload.game_id {
+        get_game(game_id, state.redis.clone())
+            .await
+            .map_err(|e| e.to_response())?;
+    }
+
+    set_telegram_route(payload.game_id, payload.chat_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to update telegram route: {}", e);
+            e.to_response()
+        })?;
+
+    let route = payload
+        .game_id
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| "default".into());
+    let entry = AuditEntry::new(admin_id, format!("set_telegram_route:{route}"), None);
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    let routes = get_telegram_routes(state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get telegram routes: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(routes))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/users/{user_id}",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "User deleted", body = String),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn delete_user_handler(
+    Path(user_id): Path<Uuid>,
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    soft_delete_user(user_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Admin {} failed to delete user {}: {}",
+                admin_id,
+                user_id,
+                e
+            );
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(admin_id, "delete_user", Some(user_id));
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json("success"))
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BanUserPayload {
+    /// Shown to the user and kept in the audit log
+    pub reason: String,
+    /// Left out for a permanent ban
+    pub duration_secs: Option<u64>,
+}
+
+/// Bans a user, replacing any ban they already have, and closes their
+/// sockets. They are refused on every authenticated request, lobby join and
+/// socket connection until it expires or is lifted.
+#[utoipa::path(
+    post,
+    path = "/admin/users/{user_id}/ban",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    request_body = BanUserPayload,
+    responses(
+        (status = 200, description = "User banned", body = Ban),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn ban_user_handler(
+    Path(user_id): Path<Uuid>,
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<BanUserPayload>,
+) -> Result<Json<Ban>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    if user_id == admin_id {
+        return Err(AppError::BadRequest("You can't ban yourself".into()).to_response());
+    }
+    get_user_by_id(user_id, state.redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+
+    let ban = ban_user(
+        user_id,
+        admin_id,
+        payload.reason,
+        payload.duration_secs,
+        state.redis.clone(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Admin {} failed to ban user {}: {}", admin_id, user_id, e);
+        e.to_response()
+    })?;
+
+    let closed = close_user_sockets(user_id, &state).await;
+    tracing::info!(
+        "Admin {} banned user {}, closed {} sockets",
+        admin_id,
+        user_id,
+        closed
+    );
+
+    let entry = AuditEntry::new(admin_id, "ban_user", Some(user_id)).with_reason(&ban.reason);
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json(ban))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/users/{user_id}/ban",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "Ban lifted", body = String),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn lift_ban_handler(
+    Path(user_id): Path<Uuid>,
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    lift_ban(user_id, state.redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+
+    let entry = AuditEntry::new(admin_id, "lift_ban", Some(user_id));
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json("success"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/bans",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Active bans, newest first", body = [Ban]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_bans_handler(
+    AdminClaims(_): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Ban>>, (StatusCode, String)> {
+    let bans = get_bans(state.redis.clone()).await.map_err(|e| {
+        tracing::error!("Failed to get bans: {}", e);
+        e.to_response()
+    })?;
+
+    Ok(Json(bans))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/lobby/{lobby_id}/state-history",
+    tag = "admin",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    responses(
+        (status = 200, description = "The lobby's latest state changes, oldest first", body = [LobbyStateChange]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_lobby_state_history_handler(
+    Path(lobby_id): Path<Uuid>,
+    AdminClaims(_): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<LobbyStateChange>>, (StatusCode, String)> {
+    let history = get_lobby_state_history(lobby_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get state history of lobby {}: {}", lobby_id, e);
+            e.to_response()
+        })?;
+
+    Ok(Json(history))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/connections",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Socket counts of this instance", body = ConnectionMetrics),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_connection_metrics_handler(
+    AdminClaims(_): AdminClaims,
+    State(state): State<AppState>,
+) -> Json<ConnectionMetrics> {
+    Json(get_connection_metrics(&state).await)
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SuspicionFlagsQuery {
+    pub limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/anticheat/flags",
+    tag = "admin",
+    params(SuspicionFlagsQuery),
+    responses(
+        (status = 200, description = "Recent anti-cheat flags", body = [SuspicionFlag]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_suspicion_flags_handler(
+    AdminClaims(_): AdminClaims,
+    Query(query): Query<SuspicionFlagsQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<SuspicionFlag>>, (StatusCode, String)> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+
+    let flags = get_suspicion_flags(limit, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get suspicion flags: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(flags))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/anticheat/multi-account",
+    tag = "admin",
+    params(SuspicionFlagsQuery),
+    responses(
+        (status = 200, description = "Recent prize lobby joins by linked accounts", body = [MultiAccountReport]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_multi_account_reports_handler(
+    AdminClaims(_): AdminClaims,
+    Query(query): Query<SuspicionFlagsQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<MultiAccountReport>>, (StatusCode, String)> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+
+    let reports = get_multi_account_reports(limit, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get multi-account reports: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(reports))
+}
+
+/// Accounts seen on the same devices or networks as the user lately.
+#[utoipa::path(
+    get,
+    path = "/admin/users/{user_id}/linked-accounts",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "Linked accounts", body = [LinkedAccount]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_linked_accounts_handler(
+    Path(user_id): Path<Uuid>,
+    AdminClaims(_): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<LinkedAccount>>, (StatusCode, String)> {
+    let linked = get_linked_accounts(user_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get accounts linked to {}: {}", user_id, e);
+            e.to_response()
+        })?;
+
+    let mut accounts = Vec::with_capacity(linked.len());
+    for (other_id, link) in linked {
+        // Deleted accounts keep their links until they expire
+        let Ok(user) = get_user_by_id(other_id, state.redis.clone()).await else {
+            continue;
+        };
+        accounts.push(LinkedAccount {
+            user_id: other_id,
+            wallet_address: user.wallet_address,
+            link,
+        });
+    }
+    accounts.sort_by_key(|account| account.link != AccountLink::SameDevice);
+
+    Ok(Json(accounts))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditLogQuery {
+    pub actor_id: Option<Uuid>,
+    pub target_id: Option<Uuid>,
+    pub action: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/audit",
+    tag = "admin",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "Matching audit entries", body = [AuditEntry]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_audit_log_handler(
+    AdminClaims(_): AdminClaims,
+    Query(query): Query<AuditLogQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<AuditEntry>>, (StatusCode, String)> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let filter = AuditFilter {
+        actor_id: query.actor_id,
+        target_id: query.target_id,
+        action: query.action,
+        since: query.since,
+        until: query.until,
+    };
+
+    let entries = get_audit_entries(&filter, limit, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get audit entries: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(entries))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/banned-words",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Banned words and filter mode", body = BannedWordsConfig),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_banned_words_handler(
+    AdminClaims(_): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<BannedWordsConfig>, (StatusCode, String)> {
+    let config = get_banned_words_config(state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get banned words: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(config))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateBannedWordsPayload {
+    #[serde(default)]
+    pub add: Vec<String>,
+    #[serde(default)]
+    pub remove: Vec<String>,
+    pub mode: Option<FilterMode>,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/admin/banned-words",
+    tag = "admin",
+    request_body = UpdateBannedWordsPayload,
+    responses(
+        (status = 200, description = "Updated banned words", body = BannedWordsConfig),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn update_banned_words_handler(
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<UpdateBannedWordsPayload>,
+) -> Result<Json<BannedWordsConfig>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    update_banned_words(
+        payload.add,
+        payload.remove,
+        payload.mode,
+        state.redis.clone(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update banned words: {}", e);
+        e.to_response()
+    })?;
+
+    let entry = AuditEntry::new(admin_id, "update_banned_words", None);
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    let config = get_banned_words_config(state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get banned words: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(config))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/claims/disputed",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Disputed prize claims", body = [DisputedClaim]),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_disputed_claims_handler(
+    AdminClaims(_): AdminClaims,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<DisputedClaim>>, (StatusCode, String)> {
+    let claims = get_disputed_claims(state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get disputed claims: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(claims))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ResolveClaimPayload {
+    pub claim: ClaimState,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/admin/lobby/{lobby_id}/claims/{user_id}",
+    tag = "admin",
+    params(
+        ("lobby_id" = Uuid, Path, description = "Lobby id"),
+        ("user_id" = Uuid, Path, description = "User id"),
+    ),
+    request_body = ResolveClaimPayload,
+    responses(
+        (status = 200, description = "Claim resolved", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn resolve_claim_handler(
+    Path((lobby_id, user_id)): Path<(Uuid, Uuid)>,
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<ResolveClaimPayload>,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    if payload.claim.is_disputed() {
+        return Err(AppError::BadRequest("Disputes are opened by players".into()).to_response());
+    }
+
+    update_claim_state(lobby_id, user_id, payload.claim, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Admin {} failed to resolve claim of {} in lobby {}: {}",
+                admin_id,
+                user_id,
+                lobby_id,
+                e
+            );
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(admin_id, format!("resolve_claim:{lobby_id}"), Some(user_id));
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    Ok(Json("success"))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct MarkRefundPaidPayload {
+    pub tx_id: String,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/admin/lobby/{lobby_id}/refunds/{user_id}",
+    tag = "admin",
+    params(
+        ("lobby_id" = Uuid, Path, description = "Lobby id"),
+        ("user_id" = Uuid, Path, description = "User id"),
+    ),
+    request_body = MarkRefundPaidPayload,
+    responses(
+        (status = 200, description = "Refund marked as paid", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn mark_refund_paid_handler(
+    Path((lobby_id, user_id)): Path<(Uuid, Uuid)>,
+    AdminClaims(claims): AdminClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<MarkRefundPaidPayload>,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let admin_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let refund = mark_refund_paid(lobby_id, user_id, payload.tx_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Admin {} failed to mark refund of {} in lobby {} as paid: {}",
+                admin_id,
+                user_id,
+                lobby_id,
+                e
+            );
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(
+        admin_id,
+        format!("mark_refund_paid:{lobby_id}"),
+        Some(user_id),
+    );
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    let msg = LexiWarsServerMessage::Refund { refund };
+    broadcast_to_player(user_id, lobby_id, &msg, &state.connections, &state.redis).await;
+
+    Ok(Json("success"))
+}
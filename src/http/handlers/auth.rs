@@ -0,0 +1,130 @@
+use axum::{
+    Json,
+    extract::{ConnectInfo, Query, State},
+    http::{HeaderMap, StatusCode, header::USER_AGENT},
+};
+use serde::Deserialize;
+use std::net::SocketAddr;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    auth::recover_stacks_address,
+    config,
+    db::user::{
+        linked_accounts::record_wallet_connection,
+        post::{create_login_challenge, create_user, take_login_challenge},
+    },
+    errors::AppError,
+    models::user::LoginChallenge,
+    state::AppState,
+};
+
+/// Device a sign-in came from, as its user agent names it
+pub fn device_name(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|agent| agent.trim().to_string())
+        .filter(|agent| !agent.is_empty())
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChallengeQuery {
+    pub wallet_address: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/auth/challenge",
+    tag = "auth",
+    params(ChallengeQuery),
+    responses(
+        (status = 200, description = "Message for the wallet to sign", body = LoginChallenge),
+        (status = 400, description = "Invalid wallet address"),
+    ),
+)]
+pub async fn get_challenge_handler(
+    Query(query): Query<ChallengeQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<LoginChallenge>, (StatusCode, String)> {
+    let wallet_address = query.wallet_address.trim().to_string();
+    if wallet_address.is_empty()
+        || wallet_address.len() > 64
+        || !wallet_address.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return Err(AppError::BadRequest("Invalid wallet address".into()).to_response());
+    }
+
+    let challenge = create_login_challenge(wallet_address, state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to create login challenge: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(challenge))
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyChallengePayload {
+    pub nonce: String,
+    /// Hex RSV signature of the challenge message
+    pub signature: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/verify",
+    tag = "auth",
+    request_body = VerifyChallengePayload,
+    responses(
+        (status = 200, description = "Token for the wallet's user", body = String),
+        (status = 401, description = "Expired challenge or signature from another wallet"),
+    ),
+)]
+pub async fn verify_challenge_handler(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<VerifyChallengePayload>,
+) -> Result<Json<String>, (StatusCode, String)> {
+    let challenge = take_login_challenge(&payload.nonce, state.redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+
+    let mainnet = config::get().stacks_network == "mainnet";
+    let signer = recover_stacks_address(&challenge.message, &payload.signature, mainnet)
+        .map_err(|e| e.to_response())?;
+    if signer != challenge.wallet_address {
+        tracing::warn!(
+            "Challenge for {} was signed by {}",
+            challenge.wallet_address,
+            signer
+        );
+        return Err(
+            AppError::Unauthorized("Signature does not match the wallet".into()).to_response(),
+        );
+    }
+
+    let token = create_user(
+        challenge.wallet_address.clone(),
+        device_name(&headers),
+        state.redis.clone(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Error signing in {}: {}", challenge.wallet_address, e);
+        e.to_response()
+    })?;
+    record_wallet_connection(
+        challenge.wallet_address.clone(),
+        addr.ip(),
+        device_name(&headers),
+        state.redis,
+    );
+
+    tracing::info!("Wallet {} signed in", challenge.wallet_address);
+    Ok(Json(token))
+}
@@ -0,0 +1,75 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    db::{
+        chat::get::{CHAT_PAGE_SIZE, get_chat_page},
+        lobby::get::get_lobby_players,
+    },
+    errors::AppError,
+    models::{chat::ChatPage, game::PlayerState},
+    state::AppState,
+};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChatHistoryQuery {
+    pub before: Option<Uuid>,
+    pub limit: Option<usize>,
+}
+
+/// Older lobby chat for members, paging back from the `before` message id.
+#[utoipa::path(
+    get,
+    path = "/chat/lobby/{lobby_id}",
+    tag = "chat",
+    params(
+        ("lobby_id" = Uuid, Path, description = "Lobby id"),
+        ChatHistoryQuery,
+    ),
+    responses(
+        (status = 200, description = "A page of older messages", body = ChatPage),
+        (status = 401, description = "Not a member of the lobby"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_lobby_chat_handler(
+    Path(lobby_id): Path<Uuid>,
+    Query(query): Query<ChatHistoryQuery>,
+    AuthClaims(claims): AuthClaims,
+    State(state): State<AppState>,
+) -> Result<Json<ChatPage>, (StatusCode, String)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let players = get_lobby_players(lobby_id, Some(PlayerState::Joined), state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving players in {lobby_id}: {}", e);
+            e.to_response()
+        })?;
+    if !players.iter().any(|p| p.id == user_id) {
+        return Err(
+            AppError::Unauthorized("Only lobby members can read the chat".into()).to_response(),
+        );
+    }
+
+    let limit = query.limit.unwrap_or(CHAT_PAGE_SIZE).clamp(1, 100);
+    let page = get_chat_page(lobby_id, user_id, query.before, limit, &state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving chat in {lobby_id}: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(page))
+}
@@ -0,0 +1,318 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    db::user::{
+        block::{block_user, get_blocked_users, unblock_user},
+        delete::ensure_user_active,
+        friends::{
+            accept_friend_request, get_friend_ids, get_friends, remove_friend, send_friend_request,
+        },
+    },
+    errors::AppError,
+    models::{
+        User,
+        friends::{FriendAction, FriendEntry, FriendStatus, FriendsList},
+        presence::{Presence, UserStatus},
+    },
+    presence::get_status,
+    state::AppState,
+};
+
+/// Friend lists are private, so callers may only act on their own.
+fn own_user_id(claims_sub: &str, user_id: Uuid) -> Result<Uuid, (StatusCode, String)> {
+    let caller_id = caller_id(claims_sub)?;
+
+    if caller_id != user_id {
+        return Err(
+            AppError::Unauthorized("You can only manage your own friends".into()).to_response(),
+        );
+    }
+
+    Ok(caller_id)
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FriendActionPayload {
+    pub action: FriendAction,
+    pub friend_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/user/{user_id}/friends",
+    tag = "friends",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    request_body = FriendActionPayload,
+    responses(
+        (status = 200, description = "Friendship after the action", body = FriendStatus),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn friend_action_handler(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    AuthClaims(claims): AuthClaims,
+    Json(payload): Json<FriendActionPayload>,
+) -> Result<Json<FriendStatus>, (StatusCode, String)> {
+    let user_id = own_user_id(&claims.sub, user_id)?;
+
+    ensure_user_active(user_id, state.redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+
+    let status = match payload.action {
+        FriendAction::Request => send_friend_request(user_id, payload.friend_id, state.redis).await,
+        FriendAction::Accept => {
+            accept_friend_request(user_id, payload.friend_id, state.redis).await
+        }
+        FriendAction::Remove => remove_friend(user_id, payload.friend_id, state.redis).await,
+    }
+    .map_err(|e| {
+        tracing::error!(
+            "Friend action {:?} from {} on {} failed: {}",
+            payload.action,
+            user_id,
+            payload.friend_id,
+            e
+        );
+        e.to_response()
+    })?;
+
+    Ok(Json(status))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FriendsQuery {
+    /// Only list friends that are currently connected
+    #[serde(default)]
+    pub online: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/user/{user_id}/friends",
+    tag = "friends",
+    params(
+        ("user_id" = Uuid, Path, description = "User id"),
+        FriendsQuery,
+    ),
+    responses(
+        (status = 200, description = "Friends and pending requests", body = FriendsList),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_friends_handler(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<FriendsQuery>,
+    AuthClaims(claims): AuthClaims,
+) -> Result<Json<FriendsList>, (StatusCode, String)> {
+    let user_id = own_user_id(&claims.sub, user_id)?;
+
+    let (friends, incoming_requests, outgoing_requests) = get_friends(user_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get friends for {}: {}", user_id, e);
+            e.to_response()
+        })?;
+
+    let friends = {
+        let conns = state.connections.lock().await;
+        let chat_conns = state.chat_connections.lock().await;
+        friends
+            .into_iter()
+            .map(|user| FriendEntry {
+                online: conns.contains_key(&user.id) || chat_conns.contains_key(&user.id),
+                user,
+            })
+            .filter(|entry| !query.online || entry.online)
+            .collect()
+    };
+
+    Ok(Json(FriendsList {
+        friends,
+        incoming_requests,
+        outgoing_requests,
+    }))
+}
+
+fn caller_id(claims_sub: &str) -> Result<Uuid, (StatusCode, String)> {
+    Uuid::parse_str(claims_sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })
+}
+
+/// Blocks the user in the path for the caller.
+#[utoipa::path(
+    post,
+    path = "/user/{user_id}/block",
+    tag = "friends",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "User blocked", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn block_user_handler(
+    State(state): State<AppState>,
+    Path(blocked_id): Path<Uuid>,
+    AuthClaims(claims): AuthClaims,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let user_id = caller_id(&claims.sub)?;
+
+    block_user(user_id, blocked_id, state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to block {} for {}: {}", blocked_id, user_id, e);
+            e.to_response()
+        })?;
+
+    tracing::info!("User {} blocked {}", user_id, blocked_id);
+    Ok(Json("success"))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/user/{user_id}/block",
+    tag = "friends",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "User unblocked", body = String),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn unblock_user_handler(
+    State(state): State<AppState>,
+    Path(blocked_id): Path<Uuid>,
+    AuthClaims(claims): AuthClaims,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let user_id = caller_id(&claims.sub)?;
+
+    unblock_user(user_id, blocked_id, state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to unblock {} for {}: {}", blocked_id, user_id, e);
+            e.to_response()
+        })?;
+
+    Ok(Json("success"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/user/blocked",
+    tag = "friends",
+    responses(
+        (status = 200, description = "Users the caller blocked", body = [User]),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_blocked_users_handler(
+    State(state): State<AppState>,
+    AuthClaims(claims): AuthClaims,
+) -> Result<Json<Vec<User>>, (StatusCode, String)> {
+    let user_id = caller_id(&claims.sub)?;
+
+    let users = get_blocked_users(user_id, state.redis).await.map_err(|e| {
+        tracing::error!("Failed to get blocked users for {}: {}", user_id, e);
+        e.to_response()
+    })?;
+
+    Ok(Json(users))
+}
+
+/// Most users one presence request can ask about
+const MAX_PRESENCE_USERS: usize = 100;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PresenceQuery {
+    /// Comma-separated user ids
+    pub user_ids: String,
+}
+
+/// Presence of the given users. Only friends' lobbies and games are shown,
+/// anyone else who is connected is just online.
+#[utoipa::path(
+    get,
+    path = "/presence",
+    tag = "friends",
+    params(PresenceQuery),
+    responses(
+        (status = 200, description = "Presence of each user, in request order", body = [Presence]),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_presence_handler(
+    State(state): State<AppState>,
+    Query(query): Query<PresenceQuery>,
+    AuthClaims(claims): AuthClaims,
+) -> Result<Json<Vec<Presence>>, (StatusCode, String)> {
+    let caller_id = caller_id(&claims.sub)?;
+
+    let user_ids = query
+        .user_ids
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            Uuid::parse_str(id)
+                .map_err(|_| AppError::BadRequest(format!("Invalid user id: {id}")).to_response())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if user_ids.len() > MAX_PRESENCE_USERS {
+        return Err(AppError::BadRequest(format!(
+            "At most {MAX_PRESENCE_USERS} users per request"
+        ))
+        .to_response());
+    }
+
+    let friend_ids = get_friend_ids(caller_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get friends of {}: {}", caller_id, e);
+            e.to_response()
+        })?;
+
+    let mut presences = Vec::with_capacity(user_ids.len());
+    for user_id in user_ids {
+        let status = get_status(user_id, state.redis.clone())
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get presence of {}: {}", user_id, e);
+                e.to_response()
+            })?;
+
+        let status = match status {
+            UserStatus::InLobby { .. } | UserStatus::InGame { .. }
+                if user_id != caller_id && !friend_ids.contains(&user_id) =>
+            {
+                UserStatus::Online
+            }
+            status => status,
+        };
+        presences.push(Presence { user_id, status });
+    }
+
+    Ok(Json(presences))
+}
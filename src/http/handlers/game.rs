@@ -4,18 +4,20 @@ use axum::{
     http::StatusCode,
 };
 use serde::Deserialize;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
     db::game::{
         get::{get_all_games, get_game},
         post::create_game,
+        words::get_word_packs,
     },
     models::game::GameType,
     state::AppState,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AddGamePayload {
     pub name: String,
     pub description: String,
@@ -23,6 +25,16 @@ pub struct AddGamePayload {
     pub tags: Option<Vec<String>>,
     pub min_players: u8,
 }
+#[utoipa::path(
+    post,
+    path = "/game",
+    tag = "game",
+    request_body = AddGamePayload,
+    responses(
+        (status = 200, description = "Id of the new game", body = Uuid),
+        (status = 400, description = "Invalid request"),
+    ),
+)]
 pub async fn create_game_handler(
     State(state): State<AppState>,
     Json(payload): Json<AddGamePayload>,
@@ -45,6 +57,16 @@ pub async fn create_game_handler(
     Ok(Json(id))
 }
 
+#[utoipa::path(
+    get,
+    path = "/game/{game_id}",
+    tag = "game",
+    params(("game_id" = Uuid, Path, description = "Game id")),
+    responses(
+        (status = 200, description = "The game", body = GameType),
+        (status = 404, description = "Game not found"),
+    ),
+)]
 pub async fn get_game_handler(
     Path(game_id): Path<Uuid>,
     State(state): State<AppState>,
@@ -58,6 +80,14 @@ pub async fn get_game_handler(
     Ok(Json(game))
 }
 
+#[utoipa::path(
+    get,
+    path = "/game",
+    tag = "game",
+    responses(
+        (status = 200, description = "All games", body = [GameType]),
+    ),
+)]
 pub async fn get_all_games_handler(
     State(state): State<AppState>,
 ) -> Result<Json<Vec<GameType>>, (StatusCode, String)> {
@@ -69,3 +99,22 @@ pub async fn get_all_games_handler(
     tracing::info!("Success retrieving all game");
     Ok(Json(games))
 }
+
+#[utoipa::path(
+    get,
+    path = "/dictionary-packs",
+    tag = "game",
+    responses(
+        (status = 200, description = "Names of the dictionary packs", body = [String]),
+    ),
+)]
+pub async fn get_dictionary_packs_handler(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    let packs = get_word_packs(state.redis.clone()).await.map_err(|e| {
+        tracing::error!("Error retrieving dictionary packs: {}", e);
+        e.to_response()
+    })?;
+
+    Ok(Json(packs))
+}
@@ -0,0 +1,141 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        game::state::{
+            get_current_rule, get_current_turn, get_eliminated_players, get_game_started,
+            get_rule_context, get_rule_index,
+        },
+        lobby::get::{
+            get_connected_players_ids, get_current_players_ids, get_lobby_info, get_lobby_players,
+            get_spectators,
+        },
+    },
+    models::internal::{
+        ConnectionCounts, GameStateSnapshot, LobbyConnectionCounts, LobbyIntrospection,
+    },
+    state::AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/internal/lobby/{lobby_id}",
+    tag = "internal",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    responses(
+        (status = 200, description = "Lobby and game state", body = LobbyIntrospection),
+        (status = 401, description = "Missing or invalid internal secret"),
+        (status = 404, description = "Lobby not found"),
+    ),
+    security(("internal_secret" = [])),
+)]
+pub async fn get_lobby_introspection_handler(
+    Path(lobby_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<LobbyIntrospection>, (StatusCode, String)> {
+    let redis = state.redis.clone();
+
+    let lobby = get_lobby_info(lobby_id, redis.clone()).await.map_err(|e| {
+        tracing::error!("Internal API failed to get lobby {}: {}", lobby_id, e);
+        e.to_response()
+    })?;
+
+    let (
+        players,
+        spectators,
+        connected_ids,
+        current_players,
+        eliminated_players,
+        game_started,
+        current_turn,
+        current_rule,
+        rule_index,
+        rule_context,
+    ) = tokio::try_join!(
+        get_lobby_players(lobby_id, None, redis.clone()),
+        get_spectators(lobby_id, redis.clone()),
+        get_connected_players_ids(lobby_id, redis.clone()),
+        get_current_players_ids(lobby_id, redis.clone()),
+        get_eliminated_players(lobby_id, redis.clone()),
+        get_game_started(lobby_id, redis.clone()),
+        get_current_turn(lobby_id, redis.clone()),
+        get_current_rule(lobby_id, redis.clone()),
+        get_rule_index(lobby_id, redis.clone()),
+        get_rule_context(lobby_id, redis.clone()),
+    )
+    .map_err(|e| {
+        tracing::error!(
+            "Internal API failed to get state for lobby {}: {}",
+            lobby_id,
+            e
+        );
+        e.to_response()
+    })?;
+
+    let live_sockets = {
+        let conns = state.connections.lock().await;
+        connected_ids
+            .iter()
+            .filter(|id| conns.contains_key(id))
+            .count()
+    };
+    let live_chat_sockets = {
+        let chat_conns = state.chat_connections.lock().await;
+        players
+            .iter()
+            .filter(|p| chat_conns.contains_key(&p.id))
+            .count()
+    };
+
+    let connections = LobbyConnectionCounts {
+        connected_players: connected_ids.len(),
+        live_sockets,
+        live_chat_sockets,
+        spectators: spectators.len(),
+    };
+
+    let game = GameStateSnapshot {
+        game_started,
+        current_turn,
+        current_rule,
+        rule_index,
+        rule_context,
+        current_players,
+        eliminated_players,
+    };
+
+    Ok(Json(LobbyIntrospection {
+        lobby,
+        players,
+        spectators,
+        game,
+        connections,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/internal/connections",
+    tag = "internal",
+    responses(
+        (status = 200, description = "Open sockets on this instance", body = ConnectionCounts),
+        (status = 401, description = "Missing or invalid internal secret"),
+    ),
+    security(("internal_secret" = [])),
+)]
+pub async fn get_connection_counts_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ConnectionCounts>, (StatusCode, String)> {
+    let game_sockets = state.connections.lock().await.len();
+    let chat_sockets = state.chat_connections.lock().await.len();
+
+    Ok(Json(ConnectionCounts {
+        game_sockets,
+        chat_sockets,
+    }))
+}
@@ -1,30 +1,59 @@
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
 };
 use serde::Deserialize;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 use crate::{
     db::{
-        leaderboard::get::{get_leaderboard, get_user_stat},
+        leaderboard::{
+            get::{get_head_to_head, get_leaderboard, get_user_stat, get_user_stats},
+            ladder::get_ladder,
+        },
         user::get::get_user_id,
     },
-    models::leaderboard::LeaderBoard,
+    models::{
+        leaderboard::{HeadToHead, LadderEntry, LeaderBoard, UserStats},
+        pagination::{PageCursor, Paginated},
+    },
     state::AppState,
 };
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct LeaderboardQuery {
-    pub limit: Option<u64>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    /// `nextCursor` of the previous page, continues after it instead of `page`
+    pub cursor: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/leaderboard",
+    tag = "leaderboard",
+    params(LeaderboardQuery),
+    responses(
+        (status = 200, description = "Leaderboard page", body = Paginated<LeaderBoard>),
+    ),
+)]
 pub async fn get_leaderboard_handler(
     Query(query): Query<LeaderboardQuery>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<LeaderBoard>>, (StatusCode, String)> {
-    let leaderboard = get_leaderboard(query.limit, state.redis)
+) -> Result<Json<Paginated<LeaderBoard>>, (StatusCode, String)> {
+    let after = PageCursor::from_query(query.cursor.as_deref()).map_err(|e| e.to_response())?;
+    let (page, limit) = match (query.page, &after) {
+        (None, None) => (1, query.limit.unwrap_or(u32::MAX).max(1)),
+        (page, _) => (
+            page.unwrap_or(1).max(1),
+            query.limit.unwrap_or(12).clamp(1, 100),
+        ),
+    };
+
+    let leaderboard = get_leaderboard(page, limit, after.as_ref(), state.redis)
         .await
         .map_err(|e| {
             tracing::error!("Failed to get leaderboard: {}", e);
@@ -34,12 +63,51 @@ pub async fn get_leaderboard_handler(
     Ok(Json(leaderboard))
 }
 
-#[derive(Deserialize)]
+#[utoipa::path(
+    get,
+    path = "/ladder",
+    tag = "leaderboard",
+    params(LeaderboardQuery),
+    responses(
+        (status = 200, description = "Ladder page", body = Paginated<LadderEntry>),
+    ),
+)]
+pub async fn get_ladder_handler(
+    Query(query): Query<LeaderboardQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Paginated<LadderEntry>>, (StatusCode, String)> {
+    let after = PageCursor::from_query(query.cursor.as_deref()).map_err(|e| e.to_response())?;
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query.limit.unwrap_or(12).clamp(1, 100);
+
+    let ladder = get_ladder(page, limit, after.as_ref(), state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get ladder: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(ladder))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GetUserStatPayload {
     pub user_id: Option<Uuid>,
     pub identifier: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/user/stat",
+    tag = "leaderboard",
+    params(GetUserStatPayload),
+    responses(
+        (status = 200, description = "The user's leaderboard entry", body = LeaderBoard),
+        (status = 400, description = "Invalid request"),
+        (status = 404, description = "Not found"),
+    ),
+)]
 pub async fn get_user_stat_handler(
     Query(payload): Query<GetUserStatPayload>,
     State(state): State<AppState>,
@@ -84,3 +152,76 @@ pub async fn get_user_stat_handler(
 
     Ok(Json(user_stat))
 }
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserStatsQuery {
+    /// Games counted in the recent form
+    pub recent: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/user/{user_id}/stats",
+    tag = "leaderboard",
+    params(
+        ("user_id" = Uuid, Path, description = "User id"),
+        UserStatsQuery,
+    ),
+    responses(
+        (status = 200, description = "The user's stats", body = UserStats),
+        (status = 404, description = "User not found"),
+    ),
+)]
+pub async fn get_user_stats_handler(
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<UserStatsQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<UserStats>, (StatusCode, String)> {
+    let recent = query.recent.unwrap_or(10).clamp(1, 50);
+
+    let stats = get_user_stats(user_id, recent, state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get stats for {}: {}", user_id, e);
+            e.to_response()
+        })?;
+
+    Ok(Json(stats))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HeadToHeadQuery {
+    pub a: Uuid,
+    pub b: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/stats/h2h",
+    tag = "leaderboard",
+    params(HeadToHeadQuery),
+    responses(
+        (status = 200, description = "Record of the two users against each other", body = HeadToHead),
+        (status = 400, description = "Invalid request"),
+    ),
+)]
+pub async fn get_head_to_head_handler(
+    Query(query): Query<HeadToHeadQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<HeadToHead>, (StatusCode, String)> {
+    let h2h = get_head_to_head(query.a, query.b, state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to get head-to-head of {} and {}: {}",
+                query.a,
+                query.b,
+                e
+            );
+            e.to_response()
+        })?;
+
+    Ok(Json(h2h))
+}
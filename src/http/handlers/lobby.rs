@@ -1,32 +1,64 @@
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
 };
+use chrono::Utc;
+use futures::Stream;
 use serde::Deserialize;
+use std::collections::HashMap;
+use tokio::sync::broadcast::error::RecvError;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
     auth::AuthClaims,
-    db::lobby::{
-        get::{
-            get_all_lobbies_extended, get_all_lobbies_info, get_lobbies_by_game_id,
-            get_lobby_extended, get_lobby_info, get_lobby_players, get_player_lobbies,
+    db::{
+        audit::post::record_audit_entry,
+        game::{
+            player_words::get_players_used_words, reactions::get_lobby_reactions,
+            state::get_live_game_state,
         },
-        patch::{
-            join_lobby, leave_lobby, update_claim_state, update_lobby_state, update_player_state,
+        lobby::{
+            events::subscribe_lobby_events,
+            get::{
+                get_all_lobbies_extended, get_all_lobbies_info, get_lobbies_by_game_id,
+                get_lobby_extended, get_lobby_info, get_lobby_info_or_archived, get_lobby_player,
+                get_lobby_players, get_player_lobbies, get_spectator_users,
+            },
+            idempotency::{
+                LobbyCreateClaim, claim_lobby_create, finish_lobby_create, release_lobby_create,
+            },
+            patch::{
+                dispute_claim, join_lobby, leave_lobby, update_claim_state, update_lobby_state,
+                update_player_state,
+            },
+            post::{NewLobby, create_lobby},
+            refunds::get_lobby_refunds,
+            schedules::get_schedule_lobbies,
+            search::search_lobbies,
         },
-        post::create_lobby,
+        user::get::get_user_id,
     },
     errors::AppError,
-    models::game::{
-        ClaimState, LobbyExtended, LobbyInfo, LobbyPoolInput, LobbyQuery, LobbyState, Player,
-        PlayerLobbyInfo, PlayerQuery, PlayerState, parse_lobby_states, parse_player_state,
+    games::lexi_wars::{bot::add_practice_bot, scoring::word_highlights},
+    models::{
+        User,
+        audit::AuditEntry,
+        game::{
+            ClaimState, LobbyExtended, LobbyInfo, LobbyPoolInput, LobbyQuery, LobbySearch,
+            LobbySearchQuery, LobbySettings, LobbyState, Player, PlayerLobbyInfo, PlayerQuery,
+            PlayerResult, PlayerState, PoolAsset, Reaction, parse_lobby_states, parse_player_state,
+        },
+        lexi_wars::{BotSkill, LiveGameSnapshot, PlayerWords, PracticeBot},
+        lobby::{JoinOutcome, LobbyListEvent, LobbyRefund},
+        pagination::{PageCursor, Paginated},
     },
-    state::AppState,
+    state::{AppState, RedisClient},
 };
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct CreateLobbyPayload {
     pub name: String,
     pub description: Option<String>,
@@ -36,12 +68,59 @@ pub struct CreateLobbyPayload {
     pub tx_id: String,
     pub token_symbol: Option<String>,
     pub token_id: Option<String>,
+    pub asset: Option<PoolAsset>,
     pub game_id: Uuid,
+    #[serde(default)]
+    pub settings: LobbySettings,
+}
+
+/// Names a create request, so a retried submit gets the first request's
+/// lobby back instead of creating another
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, AppError> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+
+    value
+        .to_str()
+        .ok()
+        .filter(|key| {
+            !key.is_empty()
+                && key.len() <= 128
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+        .map(|key| Some(key.to_string()))
+        .ok_or_else(|| {
+            AppError::BadRequest(
+                "Idempotency-Key must be up to 128 letters, digits, '-', '_' or '.'".into(),
+            )
+        })
 }
 
+#[utoipa::path(
+    post,
+    path = "/lobby",
+    tag = "lobby",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key get the first lobby back for 24 hours"),
+    ),
+    request_body = CreateLobbyPayload,
+    responses(
+        (status = 200, description = "Id of the new lobby", body = Uuid),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 409, description = "A request with the same Idempotency-Key is still running"),
+    ),
+    security(("bearer" = [])),
+)]
 pub async fn create_lobby_handler(
     State(state): State<AppState>,
     AuthClaims(claims): AuthClaims,
+    headers: HeaderMap,
     Json(payload): Json<CreateLobbyPayload>,
 ) -> Result<Json<Uuid>, (StatusCode, String)> {
     let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
@@ -49,6 +128,29 @@ pub async fn create_lobby_handler(
         AppError::Unauthorized("Invalid user ID in token".into()).to_response()
     })?;
 
+    let idempotency_key = idempotency_key(&headers).map_err(|e| e.to_response())?;
+    if let Some(key) = &idempotency_key {
+        let claim = claim_lobby_create(user_id, key, &state.redis)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to claim idempotency key: {}", e);
+                e.to_response()
+            })?;
+        match claim {
+            LobbyCreateClaim::Claimed => {}
+            LobbyCreateClaim::InProgress => {
+                return Err((
+                    StatusCode::CONFLICT,
+                    "A lobby with this Idempotency-Key is still being created".to_string(),
+                ));
+            }
+            LobbyCreateClaim::Created(lobby_id) => {
+                tracing::info!("Replaying lobby {} for {}", lobby_id, user_id);
+                return Ok(Json(lobby_id));
+            }
+        }
+    }
+
     let pool = match (
         payload.entry_amount,
         payload.current_amount,
@@ -61,23 +163,33 @@ pub async fn create_lobby_handler(
                 contract_address,
                 token_symbol: payload.token_symbol.clone().or(Some("STX".to_string())),
                 token_id: payload.token_id.clone(),
+                asset: payload.asset.clone(),
             })
         }
         _ => None,
     };
 
-    let lobby_id = create_lobby(
-        payload.name,
-        payload.description,
-        user_id,
-        payload.game_id,
+    let new_lobby = NewLobby {
+        name: payload.name,
+        description: payload.description,
+        game_id: payload.game_id,
         pool,
-        payload.tx_id,
-        state.redis.clone(),
-        state.bot.clone(),
-    )
-    .await
-    .map_err(|err| {
+        settings: payload.settings,
+        tx_id: payload.tx_id,
+    };
+    let created = create_lobby(new_lobby, user_id, state.redis.clone(), state.bot.clone()).await;
+
+    if let Some(key) = &idempotency_key {
+        let recorded = match &created {
+            Ok(lobby_id) => finish_lobby_create(user_id, key, *lobby_id, &state.redis).await,
+            Err(_) => release_lobby_create(user_id, key, &state.redis).await,
+        };
+        if let Err(e) = recorded {
+            tracing::error!("Failed to record idempotency key for {}: {}", user_id, e);
+        }
+    }
+
+    let lobby_id = created.map_err(|err| {
         tracing::error!("Error creating lobby: {}", err);
         err.to_response()
     })?;
@@ -86,6 +198,19 @@ pub async fn create_lobby_handler(
     Ok(Json(lobby_id))
 }
 
+#[utoipa::path(
+    get,
+    path = "/lobby/extended/{lobby_id}",
+    tag = "lobby",
+    params(
+        ("lobby_id" = Uuid, Path, description = "Lobby id"),
+        LobbyQuery,
+    ),
+    responses(
+        (status = 200, description = "Lobby with its players", body = LobbyExtended),
+        (status = 404, description = "Lobby not found"),
+    ),
+)]
 pub async fn get_lobby_extended_handler(
     Path(lobby_id): Path<Uuid>,
     Query(query): Query<LobbyQuery>,
@@ -103,38 +228,134 @@ pub async fn get_lobby_extended_handler(
     Ok(Json(extended))
 }
 
+/// Search and filters of a lobby list query, with the creator looked up.
+async fn lobby_search(
+    query: &LobbySearchQuery,
+    redis: &RedisClient,
+) -> Result<LobbySearch, AppError> {
+    if let (Some(min), Some(max)) = (query.min_entry, query.max_entry)
+        && min > max
+    {
+        return Err(AppError::BadRequest(
+            "min_entry can't be above max_entry".into(),
+        ));
+    }
+    if let (Some(min), Some(max)) = (query.min_players, query.max_players)
+        && min > max
+    {
+        return Err(AppError::BadRequest(
+            "min_players can't be above max_players".into(),
+        ));
+    }
+
+    let creator_id = match query.creator.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(creator) => match Uuid::parse_str(creator) {
+            Ok(id) => Some(id),
+            Err(_) => Some(get_user_id(creator.to_string(), redis.clone()).await?),
+        },
+    };
+
+    Ok(LobbySearch {
+        name_words: query
+            .search
+            .as_deref()
+            .map(LobbySearch::name_words)
+            .unwrap_or_default(),
+        creator_id,
+        min_entry: query.min_entry,
+        max_entry: query.max_entry,
+        has_pool: query.has_pool,
+        casual: query.casual,
+        min_players: query.min_players,
+        max_players: query.max_players,
+        sort: query.sort,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/game/lobbies/{game_id}",
+    tag = "lobby",
+    params(
+        ("game_id" = Uuid, Path, description = "Game id"),
+        LobbySearchQuery,
+    ),
+    responses(
+        (status = 200, description = "Lobbies of the game", body = Paginated<LobbyInfo>),
+        (status = 400, description = "Invalid request"),
+    ),
+)]
 pub async fn get_lobbies_by_game_id_handler(
     Path(game_id): Path<Uuid>,
-    Query(query): Query<LobbyQuery>,
+    Query(query): Query<LobbySearchQuery>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<LobbyInfo>>, (StatusCode, String)> {
+) -> Result<Json<Paginated<LobbyInfo>>, (StatusCode, String)> {
+    let search = lobby_search(&query, &state.redis).await.map_err(|e| {
+        tracing::error!("Invalid lobby search: {}", e);
+        e.to_response()
+    })?;
     let lobby_filters = parse_lobby_states(query.lobby_state);
 
-    let (page, limit) = match query.page {
-        Some(p) => (p.max(1), query.limit.unwrap_or(12).min(100)),
-        None => (1, u32::MAX),
+    let after = PageCursor::from_query(query.cursor.as_deref()).map_err(|e| e.to_response())?;
+    let (page, limit) = match (query.page, &after) {
+        (None, None) => (1, u32::MAX),
+        (page, _) => (
+            page.unwrap_or(1).max(1),
+            query.limit.unwrap_or(12).clamp(1, 100),
+        ),
     };
 
-    let lobbies = get_lobbies_by_game_id(game_id, lobby_filters, page, limit, state.redis.clone())
+    let lobbies = if search.is_empty() {
+        get_lobbies_by_game_id(
+            game_id,
+            lobby_filters,
+            page,
+            limit,
+            after.as_ref(),
+            state.redis.clone(),
+        )
         .await
-        .map_err(|e| {
-            tracing::error!("Error retrieving lobbies by game ID: {}", e);
-            e.to_response()
-        })?;
+    } else {
+        search_lobbies(
+            Some(game_id),
+            lobby_filters,
+            &search,
+            page,
+            limit,
+            after.as_ref(),
+            state.redis.clone(),
+        )
+        .await
+    }
+    .map_err(|e| {
+        tracing::error!("Error retrieving lobbies by game ID: {}", e);
+        e.to_response()
+    })?;
 
     tracing::info!(
         "Retrieved {} lobbies for game ID: {}",
-        lobbies.len(),
+        lobbies.items.len(),
         game_id
     );
     Ok(Json(lobbies))
 }
 
+#[utoipa::path(
+    get,
+    path = "/lobby/{lobby_id}",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    responses(
+        (status = 200, description = "Lobby info, `archived` once it has been moved out of the lobby lists", body = LobbyInfo),
+        (status = 404, description = "Lobby not found"),
+    ),
+)]
 pub async fn get_lobby_info_handler(
     Path(lobby_id): Path<Uuid>,
     State(state): State<AppState>,
 ) -> Result<Json<LobbyInfo>, (StatusCode, String)> {
-    let lobby_info = get_lobby_info(lobby_id, state.redis.clone())
+    let lobby_info = get_lobby_info_or_archived(lobby_id, state.redis.clone())
         .await
         .map_err(|e| {
             tracing::error!("Error retrieving lobby info: {}", e);
@@ -145,16 +366,29 @@ pub async fn get_lobby_info_handler(
     Ok(Json(lobby_info))
 }
 
+#[utoipa::path(
+    get,
+    path = "/lobby/extended",
+    tag = "lobby",
+    params(LobbyQuery),
+    responses(
+        (status = 200, description = "Lobbies with their players", body = Paginated<LobbyExtended>),
+    ),
+)]
 pub async fn get_all_lobbies_extended_handler(
     Query(query): Query<LobbyQuery>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<LobbyExtended>>, (StatusCode, String)> {
+) -> Result<Json<Paginated<LobbyExtended>>, (StatusCode, String)> {
     let lobby_filters = parse_lobby_states(query.lobby_state);
     let players_filter = parse_player_state(query.player_state);
 
-    let (page, limit) = match query.page {
-        Some(p) => (p.max(1), query.limit.unwrap_or(12).min(100)),
-        None => (1, u32::MAX),
+    let after = PageCursor::from_query(query.cursor.as_deref()).map_err(|e| e.to_response())?;
+    let (page, limit) = match (query.page, &after) {
+        (None, None) => (1, u32::MAX),
+        (page, _) => (
+            page.unwrap_or(1).max(1),
+            query.limit.unwrap_or(12).clamp(1, 100),
+        ),
     };
 
     let lobbies = get_all_lobbies_extended(
@@ -162,6 +396,7 @@ pub async fn get_all_lobbies_extended_handler(
         players_filter,
         page,
         limit,
+        after.as_ref(),
         state.redis.clone(),
     )
     .await
@@ -170,32 +405,113 @@ pub async fn get_all_lobbies_extended_handler(
         e.to_response()
     })?;
 
-    tracing::info!("Retrieved {} extended lobbies", lobbies.len());
+    tracing::info!("Retrieved {} extended lobbies", lobbies.items.len());
     Ok(Json(lobbies))
 }
 
+#[utoipa::path(
+    get,
+    path = "/lobby",
+    tag = "lobby",
+    params(LobbySearchQuery),
+    responses(
+        (status = 200, description = "Lobbies", body = Paginated<LobbyInfo>),
+        (status = 400, description = "Invalid request"),
+    ),
+)]
 pub async fn get_all_lobbies_info_handler(
-    Query(query): Query<LobbyQuery>,
+    Query(query): Query<LobbySearchQuery>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<LobbyInfo>>, (StatusCode, String)> {
+) -> Result<Json<Paginated<LobbyInfo>>, (StatusCode, String)> {
+    let search = lobby_search(&query, &state.redis).await.map_err(|e| {
+        tracing::error!("Invalid lobby search: {}", e);
+        e.to_response()
+    })?;
     let lobby_filters = parse_lobby_states(query.lobby_state);
 
-    let (page, limit) = match query.page {
-        Some(p) => (p.max(1), query.limit.unwrap_or(12).min(100)),
-        None => (1, u32::MAX),
+    let after = PageCursor::from_query(query.cursor.as_deref()).map_err(|e| e.to_response())?;
+    let (page, limit) = match (query.page, &after) {
+        (None, None) => (1, u32::MAX),
+        (page, _) => (
+            page.unwrap_or(1).max(1),
+            query.limit.unwrap_or(12).clamp(1, 100),
+        ),
     };
 
-    let lobbies = get_all_lobbies_info(lobby_filters, page, limit, state.redis.clone())
+    let lobbies = if search.is_empty() {
+        get_all_lobbies_info(
+            lobby_filters,
+            page,
+            limit,
+            after.as_ref(),
+            state.redis.clone(),
+        )
         .await
-        .map_err(|e| {
-            tracing::error!("Error retrieving lobbies: {}", e);
-            e.to_response()
-        })?;
+    } else {
+        search_lobbies(
+            None,
+            lobby_filters,
+            &search,
+            page,
+            limit,
+            after.as_ref(),
+            state.redis.clone(),
+        )
+        .await
+    }
+    .map_err(|e| {
+        tracing::error!("Error retrieving lobbies: {}", e);
+        e.to_response()
+    })?;
 
-    tracing::info!("Retrieved {} lobbies", lobbies.len());
+    tracing::info!("Retrieved {} lobbies", lobbies.items.len());
     Ok(Json(lobbies))
 }
 
+/// Lobby list changes as Server-Sent Events, so the lobby browser stays
+/// current without polling or a websocket.
+#[utoipa::path(
+    get,
+    path = "/lobby/stream",
+    tag = "lobby",
+    responses(
+        (
+            status = 200,
+            description = "Stream of lobby list events, named by their `type`",
+            content_type = "text/event-stream",
+            body = LobbyListEvent,
+        ),
+    ),
+)]
+pub async fn lobby_stream_handler() -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let events = futures::stream::unfold(subscribe_lobby_events(), |mut rx| async move {
+        let event = match rx.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(missed)) => {
+                tracing::debug!("Lobby stream client missed {} events", missed);
+                LobbyListEvent::Resync
+            }
+            Err(RecvError::Closed) => return None,
+        };
+        let sse = Event::default().event(event.name()).json_data(&event);
+        Some((sse, rx))
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[utoipa::path(
+    get,
+    path = "/lobby/players/{lobby_id}",
+    tag = "lobby",
+    params(
+        ("lobby_id" = Uuid, Path, description = "Lobby id"),
+        PlayerQuery,
+    ),
+    responses(
+        (status = 200, description = "Players in the lobby", body = [Player]),
+    ),
+)]
 pub async fn get_players_handler(
     Path(lobby_id): Path<Uuid>,
     Query(query): Query<PlayerQuery>,
@@ -214,11 +530,245 @@ pub async fn get_players_handler(
     Ok(Json(players))
 }
 
-#[derive(Deserialize)]
+#[utoipa::path(
+    get,
+    path = "/lobby/{lobby_id}/spectators",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    responses(
+        (status = 200, description = "Users watching the lobby", body = [User]),
+    ),
+)]
+pub async fn get_spectators_handler(
+    Path(lobby_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<User>>, (StatusCode, String)> {
+    let spectators = get_spectator_users(lobby_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving spectators in {lobby_id}: {}", e);
+            e.to_response()
+        })?;
+
+    tracing::info!("Retrieved {} spectators", spectators.len());
+    Ok(Json(spectators))
+}
+
+#[utoipa::path(
+    get,
+    path = "/lobby/{lobby_id}/refunds",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    responses(
+        (status = 200, description = "Refunds owed to players", body = [LobbyRefund]),
+    ),
+)]
+pub async fn get_lobby_refunds_handler(
+    Path(lobby_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<LobbyRefund>>, (StatusCode, String)> {
+    let refunds = get_lobby_refunds(lobby_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving refunds in {lobby_id}: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(refunds))
+}
+
+#[utoipa::path(
+    get,
+    path = "/lobby/{lobby_id}/reactions",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    responses(
+        (status = 200, description = "How often each reaction was sent", body = HashMap<Reaction, u64>),
+    ),
+)]
+pub async fn get_lobby_reactions_handler(
+    Path(lobby_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<HashMap<Reaction, u64>>, (StatusCode, String)> {
+    let reactions = get_lobby_reactions(lobby_id, &state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving reactions in {lobby_id}: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(reactions))
+}
+
+#[utoipa::path(
+    get,
+    path = "/lobby/{lobby_id}/words",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    responses(
+        (status = 200, description = "Words each player used", body = [PlayerWords]),
+        (status = 404, description = "Lobby not found"),
+    ),
+)]
+pub async fn get_lobby_words_handler(
+    Path(lobby_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<PlayerWords>>, (StatusCode, String)> {
+    let lobby = get_lobby_info(lobby_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error getting lobby info: {}", e);
+            e.to_response()
+        })?;
+
+    if lobby.state != LobbyState::Finished {
+        return Err(AppError::BadRequest("Game is not finished".into()).to_response());
+    }
+
+    let mut players = get_lobby_players(lobby_id, Some(PlayerState::Joined), state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving players in {lobby_id}: {}", e);
+            e.to_response()
+        })?;
+    players.sort_by_key(|p| p.rank.unwrap_or(usize::MAX));
+
+    let player_ids: Vec<Uuid> = players.iter().map(|p| p.id).collect();
+    let mut used_words = get_players_used_words(lobby_id, &player_ids, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving used words in {lobby_id}: {}", e);
+            e.to_response()
+        })?;
+
+    let review = players
+        .into_iter()
+        .map(|player| {
+            let words = used_words.remove(&player.id).unwrap_or_default();
+            PlayerWords {
+                highlights: word_highlights(&words),
+                words,
+                player,
+            }
+        })
+        .collect();
+
+    Ok(Json(review))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ScheduleLobbiesQuery {
+    pub limit: Option<usize>,
+}
+
+/// Lobbies a recurring schedule has opened, newest first.
+#[utoipa::path(
+    get,
+    path = "/schedules/{schedule_id}/lobbies",
+    tag = "lobby",
+    params(
+        ("schedule_id" = Uuid, Path, description = "Schedule id"),
+        ScheduleLobbiesQuery,
+    ),
+    responses(
+        (status = 200, description = "Lobbies the schedule opened", body = [LobbyInfo]),
+    ),
+)]
+pub async fn get_schedule_lobbies_handler(
+    Path(schedule_id): Path<Uuid>,
+    Query(query): Query<ScheduleLobbiesQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<LobbyInfo>>, (StatusCode, String)> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+
+    let lobbies = get_schedule_lobbies(schedule_id, limit, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving lobbies of schedule {schedule_id}: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(lobbies))
+}
+
+#[utoipa::path(
+    get,
+    path = "/lobby/{lobby_id}/live",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    responses(
+        (status = 200, description = "Current state of the game", body = LiveGameSnapshot),
+        (status = 404, description = "No game in progress"),
+    ),
+)]
+pub async fn get_live_game_handler(
+    Path(lobby_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<LiveGameSnapshot>, (StatusCode, String)> {
+    let lobby = get_lobby_info(lobby_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error getting lobby info: {}", e);
+            e.to_response()
+        })?;
+
+    if lobby.state != LobbyState::InProgress {
+        return Err(AppError::BadRequest("Game is not in progress".into()).to_response());
+    }
+
+    let (live, players) = tokio::try_join!(
+        get_live_game_state(lobby_id, state.redis.clone()),
+        get_lobby_players(lobby_id, Some(PlayerState::Joined), state.redis.clone()),
+    )
+    .map_err(|e| {
+        tracing::error!("Error retrieving live game {lobby_id}: {}", e);
+        e.to_response()
+    })?;
+
+    let current_turn = live
+        .current_turn
+        .and_then(|id| players.iter().find(|p| p.id == id).cloned());
+    let turn_remaining_ms = live
+        .turn_deadline_ms
+        .map(|deadline| (deadline - Utc::now().timestamp_millis()).max(0));
+    let remaining_players = players
+        .into_iter()
+        .filter(|p| !live.eliminated_players.contains(&p.id))
+        .collect();
+
+    Ok(Json(LiveGameSnapshot {
+        lobby,
+        game_started: live.game_started,
+        current_turn,
+        current_rule: live.current_rule,
+        turn_remaining_ms,
+        remaining_players,
+        eliminated_players: live.eliminated_players,
+        scores: live.scores,
+        recent_words: live.recent_words,
+    }))
+}
+
+#[derive(Deserialize, ToSchema)]
 pub struct JoinLobbyPayload {
     pub tx_id: Option<String>,
 }
 
+#[utoipa::path(
+    patch,
+    path = "/lobby/{lobby_id}/join",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    request_body = JoinLobbyPayload,
+    responses(
+        (status = 200, description = "Joined the lobby", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
 pub async fn join_lobby_handler(
     Path(lobby_id): Path<Uuid>,
     AuthClaims(claims): AuthClaims,
@@ -230,7 +780,7 @@ pub async fn join_lobby_handler(
         AppError::Unauthorized("Invalid user ID in token".into()).to_response()
     })?;
 
-    join_lobby(
+    let outcome = join_lobby(
         lobby_id,
         user_id,
         payload.tx_id,
@@ -243,10 +793,35 @@ pub async fn join_lobby_handler(
         e.to_response()
     })?;
 
+    match outcome {
+        JoinOutcome::Pending => {
+            tracing::info!("Join to lobby {lobby_id} pending tx confirmation");
+            return Ok(Json("pending"));
+        }
+        JoinOutcome::Closed => {
+            return Err(
+                AppError::BadRequest("Lobby is no longer accepting players".into()).to_response(),
+            );
+        }
+        JoinOutcome::Joined => {}
+    }
+
     tracing::info!("Success joining lobby {lobby_id}");
     Ok(Json("success"))
 }
 
+#[utoipa::path(
+    patch,
+    path = "/lobby/{lobby_id}/leave",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    responses(
+        (status = 200, description = "Left the lobby", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
 pub async fn leave_lobby_handler(
     Path(lobby_id): Path<Uuid>,
     AuthClaims(claims): AuthClaims,
@@ -268,11 +843,25 @@ pub async fn leave_lobby_handler(
     Ok(Json("success"))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct KickPlayerPayload {
     pub player_id: Uuid,
 }
 
+#[utoipa::path(
+    patch,
+    path = "/lobby/{lobby_id}/kick",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    request_body = KickPlayerPayload,
+    responses(
+        (status = 200, description = "Player kicked", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
 pub async fn kick_player_handler(
     State(state): State<AppState>,
     AuthClaims(claims): AuthClaims,
@@ -318,20 +907,96 @@ pub async fn kick_player_handler(
         e.to_response()
     })?;
 
+    let entry = AuditEntry::new(
+        caller_id,
+        format!("kick_player:{lobby_id}"),
+        Some(payload.player_id),
+    );
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
     tracing::info!("Success kicking player");
     Ok(Json("success".to_string()))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
+pub struct AddBotPayload {
+    pub skill: BotSkill,
+    /// Roughly how long the bot takes to answer, the skill's default when unset
+    pub response_delay_ms: Option<u64>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/lobby/{lobby_id}/bots",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    request_body = AddBotPayload,
+    responses(
+        (status = 200, description = "Bot added", body = PracticeBot),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn add_bot_handler(
+    State(state): State<AppState>,
+    AuthClaims(claims): AuthClaims,
+    Path(lobby_id): Path<Uuid>,
+    Json(payload): Json<AddBotPayload>,
+) -> Result<Json<PracticeBot>, (StatusCode, String)> {
+    let caller_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let bot = add_practice_bot(
+        lobby_id,
+        caller_id,
+        payload.skill,
+        payload.response_delay_ms,
+        state.redis.clone(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Error adding bot to lobby {lobby_id}: {}", e);
+        e.to_response()
+    })?;
+
+    Ok(Json(bot))
+}
+
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateLobbyStatePayload {
     pub new_state: LobbyState,
 }
 
+#[utoipa::path(
+    patch,
+    path = "/lobby/{lobby_id}/state",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    request_body = UpdateLobbyStatePayload,
+    responses(
+        (status = 200, description = "Lobby state updated", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
 pub async fn update_lobby_state_handler(
     Path(lobby_id): Path<Uuid>,
+    AuthClaims(claims): AuthClaims,
     State(state): State<AppState>,
     Json(payload): Json<UpdateLobbyStatePayload>,
 ) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
     update_lobby_state(lobby_id, payload.new_state.clone(), state.redis.clone())
         .await
         .map_err(|e| {
@@ -339,15 +1004,37 @@ pub async fn update_lobby_state_handler(
             e.to_response()
         })?;
 
+    let entry = AuditEntry::new(
+        user_id,
+        format!("update_lobby_state:{lobby_id}:{:?}", payload.new_state),
+        None,
+    );
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
     tracing::info!("Game state update to {:?}", payload.new_state);
     Ok(Json("success"))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdatePlayerStatePayload {
     pub new_state: PlayerState,
 }
 
+#[utoipa::path(
+    patch,
+    path = "/lobby/{lobby_id}/player-state",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    request_body = UpdatePlayerStatePayload,
+    responses(
+        (status = 200, description = "Player state updated", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
 pub async fn update_player_state_handler(
     Path(lobby_id): Path<Uuid>,
     AuthClaims(claims): AuthClaims,
@@ -375,11 +1062,24 @@ pub async fn update_player_state_handler(
     Ok(Json("success"))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateClaimStatePayload {
     pub claim: ClaimState,
 }
 
+#[utoipa::path(
+    patch,
+    path = "/lobby/{lobby_id}/claim-state",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    request_body = UpdateClaimStatePayload,
+    responses(
+        (status = 200, description = "Claim state updated", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
 pub async fn update_claim_state_handler(
     Path(lobby_id): Path<Uuid>,
     AuthClaims(claims): AuthClaims,
@@ -391,6 +1091,13 @@ pub async fn update_claim_state_handler(
         AppError::Unauthorized("Invalid user ID in token".into()).to_response()
     })?;
 
+    // Every other transition goes through the dispute or admin endpoints
+    if !payload.claim.is_claimed() {
+        return Err(
+            AppError::BadRequest("Players can only mark a prize as claimed".into()).to_response(),
+        );
+    }
+
     update_claim_state(lobby_id, user_id, payload.claim, state.redis.clone())
         .await
         .map_err(|e| {
@@ -398,11 +1105,118 @@ pub async fn update_claim_state_handler(
             e.to_response()
         })?;
 
+    let entry = AuditEntry::new(user_id, format!("claim_prize:{lobby_id}"), Some(user_id));
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
     tracing::info!("Claim state updated for lobby {lobby_id}");
     Ok(Json("success"))
 }
 
-#[derive(Deserialize)]
+const MAX_DISPUTE_REASON_LEN: usize = 500;
+
+#[derive(Deserialize, ToSchema)]
+pub struct DisputeClaimPayload {
+    pub reason: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/lobby/{lobby_id}/claim-state/dispute",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    request_body = DisputeClaimPayload,
+    responses(
+        (status = 200, description = "Claim disputed", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn dispute_claim_handler(
+    Path(lobby_id): Path<Uuid>,
+    AuthClaims(claims): AuthClaims,
+    State(state): State<AppState>,
+    Json(payload): Json<DisputeClaimPayload>,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let reason = payload.reason.trim();
+    if reason.is_empty() || reason.chars().count() > MAX_DISPUTE_REASON_LEN {
+        return Err(AppError::BadRequest(format!(
+            "Dispute reason must be between 1 and {MAX_DISPUTE_REASON_LEN} characters"
+        ))
+        .to_response());
+    }
+
+    dispute_claim(lobby_id, user_id, reason.to_string(), state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error disputing claim in lobby {}: {}", lobby_id, e);
+            e.to_response()
+        })?;
+
+    let entry = AuditEntry::new(user_id, format!("dispute_claim:{lobby_id}"), Some(user_id));
+    if let Err(e) = record_audit_entry(&entry, state.redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    tracing::info!("Player {user_id} disputed their claim in lobby {lobby_id}");
+    Ok(Json("success"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/lobby/{lobby_id}/my-result",
+    tag = "lobby",
+    params(("lobby_id" = Uuid, Path, description = "Lobby id")),
+    responses(
+        (status = 200, description = "The caller's result in the lobby", body = PlayerResult),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_my_result_handler(
+    Path(lobby_id): Path<Uuid>,
+    AuthClaims(claims): AuthClaims,
+    State(state): State<AppState>,
+) -> Result<Json<PlayerResult>, (StatusCode, String)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let lobby = get_lobby_info(lobby_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving lobby {}: {}", lobby_id, e);
+            e.to_response()
+        })?;
+
+    let player = get_lobby_player(lobby_id, user_id, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving result for player {}: {}", user_id, e);
+            e.to_response()
+        })?;
+
+    Ok(Json(PlayerResult {
+        lobby_id,
+        lobby_state: lobby.state,
+        rank: player.rank,
+        prize: player.prize,
+        wars_point: player.wars_point,
+        claim: player.claim,
+    }))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PlayerLobbyQuery {
     pub user_id: Option<Uuid>,
     pub identifier: Option<String>,
@@ -410,13 +1224,25 @@ pub struct PlayerLobbyQuery {
     pub claim_state: Option<String>,
     pub page: Option<u32>,
     pub limit: Option<u32>,
+    /// `nextCursor` of the previous page, continues after it instead of `page`
+    pub cursor: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/user/lobbies",
+    tag = "user",
+    params(PlayerLobbyQuery),
+    responses(
+        (status = 200, description = "Lobbies the user played in", body = Paginated<PlayerLobbyInfo>),
+        (status = 400, description = "Invalid request"),
+    ),
+)]
 #[axum::debug_handler]
 pub async fn get_player_lobbies_handler(
     Query(query): Query<PlayerLobbyQuery>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<PlayerLobbyInfo>>, (StatusCode, String)> {
+) -> Result<Json<Paginated<PlayerLobbyInfo>>, (StatusCode, String)> {
     // Determine the user_id from either direct user_id or identifier
     let user_id = match (query.user_id, query.identifier) {
         (Some(id), _) => {
@@ -445,8 +1271,9 @@ pub async fn get_player_lobbies_handler(
 
     let claim_filter = parse_claim_state(query.claim_state);
     let lobby_filters = parse_lobby_states(query.lobby_state);
+    let after = PageCursor::from_query(query.cursor.as_deref()).map_err(|e| e.to_response())?;
     let page = query.page.unwrap_or(1);
-    let limit = query.limit.unwrap_or(10).min(100);
+    let limit = query.limit.unwrap_or(10).clamp(1, 100);
 
     let lobbies = get_player_lobbies(
         user_id,
@@ -454,6 +1281,7 @@ pub async fn get_player_lobbies_handler(
         lobby_filters,
         page,
         limit,
+        after.as_ref(),
         state.redis,
     )
     .await
@@ -470,7 +1298,15 @@ fn parse_claim_state(claim_param: Option<String>) -> Option<ClaimState> {
         "claimed" => Some(ClaimState::Claimed {
             tx_id: String::new(),
         }), // We'll match any claimed state
-        "notclaimed" | "not_claimed" => Some(ClaimState::NotClaimed),
+        "claimable" | "notclaimed" | "not_claimed" => Some(ClaimState::Claimable),
+        "pending" | "pendingverification" => Some(ClaimState::PendingVerification),
+        "disputed" => Some(ClaimState::Disputed {
+            reason: String::new(),
+            tx_id: None,
+        }),
+        "refunded" => Some(ClaimState::Refunded {
+            tx_id: String::new(),
+        }),
         other => {
             tracing::warn!("Invalid claim_state filter: {}", other);
             None
@@ -1,5 +1,11 @@
+pub mod admin;
+pub mod auth;
+pub mod chat;
+pub mod friends;
 pub mod game;
+pub mod internal;
 pub mod leaderboard;
 pub mod lobby;
+pub mod telegram;
 pub mod token_info;
 pub mod user;
@@ -0,0 +1,45 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+
+use crate::{
+    db::telegram::{get::get_join_code, post::associate_telegram_with_lobby},
+    models::telegram::TelegramJoinLink,
+    state::AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/tg/join/{code}",
+    tag = "telegram",
+    params(("code" = String, Path, description = "Telegram join code")),
+    responses(
+        (status = 200, description = "Lobby the code joins", body = TelegramJoinLink),
+        (status = 404, description = "Unknown or expired code"),
+    ),
+)]
+pub async fn telegram_join_handler(
+    Path(code): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<TelegramJoinLink>, (StatusCode, String)> {
+    let join_code = get_join_code(&code, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error resolving telegram join code {}: {}", code, e);
+            e.to_response()
+        })?;
+
+    associate_telegram_with_lobby(&join_code, state.redis.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!("Error associating telegram user with lobby: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(TelegramJoinLink {
+        lobby_id: join_code.lobby_id,
+        join_url: format!("https://stackswars.com/lobby/{}", join_code.lobby_id),
+    }))
+}
@@ -1,5 +1,6 @@
 use axum::{extract::Path, http::StatusCode, response::Json};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::errors::AppError;
 
@@ -17,7 +18,7 @@ struct TokenMetrics {
     price_usd: f64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenInfo {
     pub contract_id: String,
@@ -80,6 +81,15 @@ pub async fn get_token_info(contract_address: String) -> Result<TokenInfo, AppEr
     })
 }
 
+#[utoipa::path(
+    get,
+    path = "/token_info/{contract_address}",
+    tag = "token",
+    params(("contract_address" = String, Path, description = "Token contract address")),
+    responses(
+        (status = 200, description = "Token details and price", body = TokenInfo),
+    ),
+)]
 pub async fn get_token_info_handler(
     Path(contract_address): Path<String>,
 ) -> Result<Json<TokenInfo>, (StatusCode, String)> {
@@ -89,6 +99,15 @@ pub async fn get_token_info_handler(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/token_info/testnet/{contract_address}",
+    tag = "token",
+    params(("contract_address" = String, Path, description = "Token contract address")),
+    responses(
+        (status = 200, description = "Testnet token details", body = TokenInfo),
+    ),
+)]
 pub async fn get_testnet_token_info_handler(
     Path(contract_address): Path<String>,
 ) -> Result<Json<TokenInfo>, (StatusCode, String)> {
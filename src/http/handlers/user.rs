@@ -1,38 +1,80 @@
 use axum::{
     Json,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Path, State},
+    http::{HeaderMap, StatusCode},
 };
 use serde::Deserialize;
+use std::net::SocketAddr;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{
     auth::AuthClaims,
+    config,
     db::user::{
-        get::get_user_by_id,
-        patch::{update_display_name, update_username},
-        post::create_user,
+        delete::{ensure_user_active, soft_delete_user},
+        get::{get_notification_preferences, get_user_by_id},
+        linked_accounts::record_wallet_connection,
+        patch::{
+            update_display_name, update_notification_preferences, update_profile, update_username,
+        },
+        post::{create_telegram_link_code, create_user},
+        session::{get_sessions, revoke_session},
     },
     errors::AppError,
-    models::User,
+    http::handlers::auth::device_name,
+    models::{User, notification::NotificationPreferences, user::UserSession},
     state::AppState,
+    ws::sessions::revoke_session_sockets,
 };
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct CreateUserPayload {
     pub wallet_address: String,
 }
 
+#[utoipa::path(
+    post,
+    path = "/user",
+    tag = "user",
+    request_body = CreateUserPayload,
+    responses(
+        (status = 200, description = "Token for the user", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Signed sign-in through /auth/verify is required"),
+    ),
+)]
 pub async fn create_user_handler(
     State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<CreateUserPayload>,
 ) -> Result<Json<String>, (StatusCode, String)> {
-    match create_user(payload.wallet_address.clone(), state.redis.clone()).await {
+    if config::get().require_wallet_signature {
+        return Err(AppError::Unauthorized(
+            "Sign in with a wallet signature through /auth/challenge".into(),
+        )
+        .to_response());
+    }
+
+    match create_user(
+        payload.wallet_address.clone(),
+        device_name(&headers),
+        state.redis.clone(),
+    )
+    .await
+    {
         Ok(token) => {
             tracing::info!(
                 "User created with wallet address: {}",
                 payload.wallet_address
             );
+            record_wallet_connection(
+                payload.wallet_address,
+                addr.ip(),
+                device_name(&headers),
+                state.redis,
+            );
             Ok(Json(token))
         }
         Err(err) => {
@@ -42,6 +84,16 @@ pub async fn create_user_handler(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/user/{user_id}",
+    tag = "user",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "The user", body = User),
+        (status = 404, description = "User not found"),
+    ),
+)]
 pub async fn get_user_handler(
     State(state): State<AppState>,
     Path(user_id): Path<Uuid>,
@@ -56,10 +108,22 @@ pub async fn get_user_handler(
     Ok(Json(user))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UsernamePayload {
     pub username: String,
 }
+#[utoipa::path(
+    patch,
+    path = "/user/username",
+    tag = "user",
+    request_body = UsernamePayload,
+    responses(
+        (status = 200, description = "The new username", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
 pub async fn update_username_handler(
     State(state): State<AppState>,
     AuthClaims(claims): AuthClaims,
@@ -83,10 +147,22 @@ pub async fn update_username_handler(
     Ok(Json(username))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct DisplayNamePayload {
     pub display_name: String,
 }
+#[utoipa::path(
+    patch,
+    path = "/user/display_name",
+    tag = "user",
+    request_body = DisplayNamePayload,
+    responses(
+        (status = 200, description = "The new display name", body = String),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
 pub async fn update_display_name_handler(
     State(state): State<AppState>,
     AuthClaims(claims): AuthClaims,
@@ -109,3 +185,249 @@ pub async fn update_display_name_handler(
     tracing::info!("Display name updated for user ID: {}", user_id);
     Ok(Json(display_name))
 }
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateProfilePayload {
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub bio: Option<String>,
+}
+#[utoipa::path(
+    patch,
+    path = "/user/{user_id}",
+    tag = "user",
+    params(("user_id" = Uuid, Path, description = "User id")),
+    request_body = UpdateProfilePayload,
+    responses(
+        (status = 200, description = "The updated user", body = User),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn update_profile_handler(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    AuthClaims(claims): AuthClaims,
+    Json(payload): Json<UpdateProfilePayload>,
+) -> Result<Json<User>, (StatusCode, String)> {
+    let caller_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    if caller_id != user_id {
+        return Err(
+            AppError::Unauthorized("You can only update your own profile".into()).to_response(),
+        );
+    }
+
+    ensure_user_active(user_id, state.redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+
+    let user = update_profile(
+        user_id,
+        payload.display_name,
+        payload.avatar_url,
+        payload.bio,
+        state.redis,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Error updating profile: {}", e);
+        e.to_response()
+    })?;
+
+    tracing::info!("Profile updated for user ID: {}", user_id);
+    Ok(Json(user))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/user",
+    tag = "user",
+    responses(
+        (status = 200, description = "Account deleted", body = String),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn delete_user_handler(
+    State(state): State<AppState>,
+    AuthClaims(claims): AuthClaims,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    soft_delete_user(user_id, state.redis).await.map_err(|e| {
+        tracing::error!("Error deleting user {}: {}", user_id, e);
+        e.to_response()
+    })?;
+
+    tracing::info!("User {} deleted their account", user_id);
+    Ok(Json("success"))
+}
+
+#[utoipa::path(
+    post,
+    path = "/user/link-telegram",
+    tag = "user",
+    responses(
+        (status = 200, description = "Code to send the Telegram bot", body = String),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn create_telegram_link_code_handler(
+    State(state): State<AppState>,
+    AuthClaims(claims): AuthClaims,
+) -> Result<Json<String>, (StatusCode, String)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    ensure_user_active(user_id, state.redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+
+    let code = create_telegram_link_code(user_id, state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error creating telegram link code: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(code))
+}
+
+#[utoipa::path(
+    get,
+    path = "/user/notifications",
+    tag = "user",
+    responses(
+        (status = 200, description = "Notification preferences", body = NotificationPreferences),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_notification_preferences_handler(
+    State(state): State<AppState>,
+    AuthClaims(claims): AuthClaims,
+) -> Result<Json<NotificationPreferences>, (StatusCode, String)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let prefs = get_notification_preferences(user_id, state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error retrieving notification preferences: {}", e);
+            e.to_response()
+        })?;
+
+    Ok(Json(prefs))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/user/notifications",
+    tag = "user",
+    request_body = NotificationPreferences,
+    responses(
+        (status = 200, description = "Saved preferences", body = NotificationPreferences),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn update_notification_preferences_handler(
+    State(state): State<AppState>,
+    AuthClaims(claims): AuthClaims,
+    Json(payload): Json<NotificationPreferences>,
+) -> Result<Json<NotificationPreferences>, (StatusCode, String)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let prefs = update_notification_preferences(user_id, payload, state.redis)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error updating notification preferences: {}", e);
+            e.to_response()
+        })?;
+
+    tracing::info!("Notification preferences updated for user ID: {}", user_id);
+    Ok(Json(prefs))
+}
+
+#[utoipa::path(
+    get,
+    path = "/user/me/sessions",
+    tag = "user",
+    responses(
+        (status = 200, description = "Signed-in sessions, most recently used first", body = [UserSession]),
+        (status = 401, description = "Missing or invalid token"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn get_sessions_handler(
+    State(state): State<AppState>,
+    AuthClaims(claims): AuthClaims,
+) -> Result<Json<Vec<UserSession>>, (StatusCode, String)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    let mut sessions = get_sessions(user_id, state.redis).await.map_err(|e| {
+        tracing::error!("Error retrieving sessions of {}: {}", user_id, e);
+        e.to_response()
+    })?;
+    for session in &mut sessions {
+        session.current = claims.sid == Some(session.id);
+    }
+
+    Ok(Json(sessions))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/user/me/sessions/{session_id}",
+    tag = "user",
+    params(("session_id" = Uuid, Path, description = "Session id")),
+    responses(
+        (status = 200, description = "Session revoked and its sockets closed", body = String),
+        (status = 401, description = "Missing or invalid token"),
+        (status = 404, description = "Session not found"),
+    ),
+    security(("bearer" = [])),
+)]
+pub async fn revoke_session_handler(
+    State(state): State<AppState>,
+    Path(session_id): Path<Uuid>,
+    AuthClaims(claims): AuthClaims,
+) -> Result<Json<&'static str>, (StatusCode, String)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        tracing::error!("Unauthorized access attempt");
+        AppError::Unauthorized("Invalid user ID in token".into()).to_response()
+    })?;
+
+    revoke_session(user_id, session_id, state.redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+    let closed = revoke_session_sockets(user_id, session_id, &state).await;
+
+    tracing::info!(
+        "User {} revoked session {}, closing {} sockets",
+        user_id,
+        session_id,
+        closed
+    );
+    Ok(Json("success"))
+}
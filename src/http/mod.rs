@@ -1,6 +1,7 @@
 pub mod bot;
 pub mod bot_commands;
 pub mod handlers;
+pub mod openapi;
 pub mod routes;
 
 pub use routes::create_http_routes;
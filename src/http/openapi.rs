@@ -0,0 +1,172 @@
+use axum::{Json, response::Html};
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+};
+
+use crate::http::handlers::{
+    admin, auth, chat, friends, game, internal, leaderboard, lobby, telegram, token_info, user,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Stacks Wars API"),
+    paths(
+        lobby::create_lobby_handler,
+        lobby::get_all_lobbies_info_handler,
+        lobby::get_all_lobbies_extended_handler,
+        lobby::get_lobby_info_handler,
+        lobby::get_lobby_extended_handler,
+        lobby::lobby_stream_handler,
+        lobby::get_lobbies_by_game_id_handler,
+        lobby::get_players_handler,
+        lobby::get_spectators_handler,
+        lobby::get_lobby_refunds_handler,
+        lobby::get_lobby_reactions_handler,
+        lobby::get_lobby_words_handler,
+        lobby::get_live_game_handler,
+        lobby::get_schedule_lobbies_handler,
+        lobby::join_lobby_handler,
+        lobby::leave_lobby_handler,
+        lobby::kick_player_handler,
+        lobby::add_bot_handler,
+        lobby::update_lobby_state_handler,
+        lobby::update_player_state_handler,
+        lobby::update_claim_state_handler,
+        lobby::dispute_claim_handler,
+        lobby::get_my_result_handler,
+        lobby::get_player_lobbies_handler,
+        auth::get_challenge_handler,
+        auth::verify_challenge_handler,
+        user::create_user_handler,
+        user::get_user_handler,
+        user::update_username_handler,
+        user::update_display_name_handler,
+        user::update_profile_handler,
+        user::delete_user_handler,
+        user::create_telegram_link_code_handler,
+        user::get_notification_preferences_handler,
+        user::update_notification_preferences_handler,
+        user::get_sessions_handler,
+        user::revoke_session_handler,
+        friends::friend_action_handler,
+        friends::get_friends_handler,
+        friends::block_user_handler,
+        friends::unblock_user_handler,
+        friends::get_blocked_users_handler,
+        friends::get_presence_handler,
+        game::create_game_handler,
+        game::get_all_games_handler,
+        game::get_game_handler,
+        game::get_dictionary_packs_handler,
+        leaderboard::get_leaderboard_handler,
+        leaderboard::get_ladder_handler,
+        leaderboard::get_user_stat_handler,
+        leaderboard::get_user_stats_handler,
+        leaderboard::get_head_to_head_handler,
+        chat::get_lobby_chat_handler,
+        telegram::telegram_join_handler,
+        token_info::get_token_info_handler,
+        token_info::get_testnet_token_info_handler,
+        admin::get_support_view_handler,
+        admin::upload_dictionary_pack_handler,
+        admin::add_dictionary_words_handler,
+        admin::remove_dictionary_words_handler,
+        admin::get_word_reports_handler,
+        admin::approve_word_reports_handler,
+        admin::dismiss_word_reports_handler,
+        admin::get_chat_reports_handler,
+        admin::dismiss_chat_report_handler,
+        admin::get_lexi_rules_handler,
+        admin::save_lexi_rule_handler,
+        admin::set_lexi_rule_order_handler,
+        admin::create_schedule_handler,
+        admin::get_schedules_handler,
+        admin::delete_schedule_handler,
+        admin::create_webhook_handler,
+        admin::get_webhooks_handler,
+        admin::update_webhook_handler,
+        admin::delete_webhook_handler,
+        admin::get_telegram_routes_handler,
+        admin::update_telegram_route_handler,
+        admin::delete_user_handler,
+        admin::ban_user_handler,
+        admin::lift_ban_handler,
+        admin::get_bans_handler,
+        admin::get_lobby_state_history_handler,
+        admin::get_connection_metrics_handler,
+        admin::get_suspicion_flags_handler,
+        admin::get_multi_account_reports_handler,
+        admin::get_linked_accounts_handler,
+        admin::get_audit_log_handler,
+        admin::get_banned_words_handler,
+        admin::update_banned_words_handler,
+        admin::get_disputed_claims_handler,
+        admin::resolve_claim_handler,
+        admin::mark_refund_paid_handler,
+        internal::get_lobby_introspection_handler,
+        internal::get_connection_counts_handler,
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = "lobby", description = "Lobbies, players and game results"),
+        (name = "auth", description = "Sign in with a wallet signature"),
+        (name = "user", description = "Accounts and profiles"),
+        (name = "friends", description = "Friends, blocked users and presence"),
+        (name = "game", description = "Available games"),
+        (name = "leaderboard", description = "Rankings and player stats"),
+        (name = "chat", description = "Lobby chat history"),
+        (name = "telegram", description = "Telegram join links"),
+        (name = "token", description = "Token prices for pool entry"),
+        (name = "admin", description = "Moderation and support, admins only"),
+        (name = "internal", description = "Game state for internal tooling"),
+    )
+)]
+pub struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "internal_secret",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("x-internal-secret"))),
+        );
+    }
+}
+
+pub async fn openapi_json_handler() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// Swagger UI for the spec, served from the swagger-ui-dist CDN build
+pub async fn swagger_ui_handler() -> Html<&'static str> {
+    Html(SWAGGER_UI_HTML)
+}
+
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8" />
+    <title>Stacks Wars API</title>
+    <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+    <script>
+        window.ui = SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+    </script>
+</body>
+</html>
+"##;
@@ -1,26 +1,65 @@
 use axum::{
     Router, middleware as axum_middleware,
-    routing::{get, patch, post},
+    routing::{delete, get, patch, post},
 };
 
 use crate::{
     http::handlers::{
-        game::{create_game_handler, get_all_games_handler, get_game_handler},
-        leaderboard::{get_leaderboard_handler, get_user_stat_handler},
+        admin::{
+            add_dictionary_words_handler, approve_word_reports_handler, ban_user_handler,
+            create_schedule_handler, create_webhook_handler, delete_schedule_handler,
+            delete_user_handler as admin_delete_user_handler, delete_webhook_handler,
+            dismiss_chat_report_handler, dismiss_word_reports_handler, get_audit_log_handler,
+            get_banned_words_handler, get_bans_handler, get_chat_reports_handler,
+            get_connection_metrics_handler, get_disputed_claims_handler, get_lexi_rules_handler,
+            get_linked_accounts_handler, get_lobby_state_history_handler,
+            get_multi_account_reports_handler, get_schedules_handler, get_support_view_handler,
+            get_suspicion_flags_handler, get_telegram_routes_handler, get_webhooks_handler,
+            get_word_reports_handler, lift_ban_handler, mark_refund_paid_handler,
+            remove_dictionary_words_handler, resolve_claim_handler, save_lexi_rule_handler,
+            set_lexi_rule_order_handler, update_banned_words_handler,
+            update_telegram_route_handler, update_webhook_handler, upload_dictionary_pack_handler,
+        },
+        auth::{get_challenge_handler, verify_challenge_handler},
+        chat::get_lobby_chat_handler,
+        friends::{
+            block_user_handler, friend_action_handler, get_blocked_users_handler,
+            get_friends_handler, get_presence_handler, unblock_user_handler,
+        },
+        game::{
+            create_game_handler, get_all_games_handler, get_dictionary_packs_handler,
+            get_game_handler,
+        },
+        internal::{get_connection_counts_handler, get_lobby_introspection_handler},
+        leaderboard::{
+            get_head_to_head_handler, get_ladder_handler, get_leaderboard_handler,
+            get_user_stat_handler, get_user_stats_handler,
+        },
         lobby::{
-            create_lobby_handler, get_all_lobbies_extended_handler, get_all_lobbies_info_handler,
+            add_bot_handler, create_lobby_handler, dispute_claim_handler,
+            get_all_lobbies_extended_handler, get_all_lobbies_info_handler, get_live_game_handler,
             get_lobbies_by_game_id_handler, get_lobby_extended_handler, get_lobby_info_handler,
-            get_player_lobbies_handler, get_players_handler, join_lobby_handler,
-            kick_player_handler, leave_lobby_handler, update_claim_state_handler,
-            update_lobby_state_handler, update_player_state_handler,
+            get_lobby_reactions_handler, get_lobby_refunds_handler, get_lobby_words_handler,
+            get_my_result_handler, get_player_lobbies_handler, get_players_handler,
+            get_schedule_lobbies_handler, get_spectators_handler, join_lobby_handler,
+            kick_player_handler, leave_lobby_handler, lobby_stream_handler,
+            update_claim_state_handler, update_lobby_state_handler, update_player_state_handler,
         },
+        telegram::telegram_join_handler,
         token_info::{get_testnet_token_info_handler, get_token_info_handler},
         user::{
-            create_user_handler, get_user_handler, update_display_name_handler,
+            create_telegram_link_code_handler, create_user_handler, delete_user_handler,
+            get_notification_preferences_handler, get_sessions_handler, get_user_handler,
+            revoke_session_handler, update_display_name_handler,
+            update_notification_preferences_handler, update_profile_handler,
             update_username_handler,
         },
     },
-    middleware::{create_api_rate_limiter, create_auth_rate_limiter, rate_limit_middleware},
+    http::openapi::{openapi_json_handler, swagger_ui_handler},
+    middleware::{
+        create_api_rate_limiter, create_auth_rate_limiter, internal_auth_middleware,
+        rate_limit_middleware,
+    },
     state::AppState,
 };
 
@@ -30,14 +69,81 @@ pub fn create_http_routes(state: AppState) -> Router {
 
     // Routes that need stricter rate limiting (user creation, lobby join/leave)
     let auth_routes = Router::new()
+        .route("/auth/challenge", get(get_challenge_handler))
+        .route("/auth/verify", post(verify_challenge_handler))
         .route("/user", post(create_user_handler))
         .route("/game", post(create_game_handler))
         .route("/lobby", post(create_lobby_handler))
+        .route(
+            "/admin/dictionary-packs",
+            post(upload_dictionary_pack_handler),
+        )
         .route("/lobby/{lobby_id}/join", patch(join_lobby_handler))
         .route("/lobby/{lobby_id}/leave", patch(leave_lobby_handler))
         .route("/user/username", patch(update_username_handler))
         .route("/user/display_name", patch(update_display_name_handler))
+        .route("/user/{user_id}", patch(update_profile_handler))
+        .route("/user/{user_id}/friends", post(friend_action_handler))
+        .route(
+            "/user/{user_id}/block",
+            post(block_user_handler).delete(unblock_user_handler),
+        )
+        .route("/user", delete(delete_user_handler))
+        .route(
+            "/user/link-telegram",
+            post(create_telegram_link_code_handler),
+        )
+        .route(
+            "/user/notifications",
+            patch(update_notification_preferences_handler),
+        )
+        .route(
+            "/user/me/sessions/{session_id}",
+            delete(revoke_session_handler),
+        )
+        .route("/admin/users/{user_id}", delete(admin_delete_user_handler))
+        .route(
+            "/admin/users/{user_id}/ban",
+            post(ban_user_handler).delete(lift_ban_handler),
+        )
+        .route("/admin/banned-words", patch(update_banned_words_handler))
+        .route(
+            "/admin/dictionary/words",
+            post(add_dictionary_words_handler).delete(remove_dictionary_words_handler),
+        )
+        .route(
+            "/admin/dictionary/reports",
+            delete(dismiss_word_reports_handler),
+        )
+        .route(
+            "/admin/dictionary/reports/approve",
+            post(approve_word_reports_handler),
+        )
+        .route("/admin/lexi-rules", post(save_lexi_rule_handler))
+        .route("/admin/schedules", post(create_schedule_handler))
+        .route(
+            "/admin/schedules/{schedule_id}",
+            delete(delete_schedule_handler),
+        )
+        .route("/admin/webhooks", post(create_webhook_handler))
+        .route(
+            "/admin/telegram/routes",
+            patch(update_telegram_route_handler),
+        )
+        .route(
+            "/admin/webhooks/{webhook_id}",
+            patch(update_webhook_handler).delete(delete_webhook_handler),
+        )
+        .route(
+            "/admin/chat/reports/{message_id}",
+            delete(dismiss_chat_report_handler),
+        )
+        .route(
+            "/admin/lexi-rules/order",
+            patch(set_lexi_rule_order_handler),
+        )
         .route("/lobby/{lobby_id}/kick", patch(kick_player_handler))
+        .route("/lobby/{lobby_id}/bots", post(add_bot_handler))
         .route("/lobby/{lobby_id}/state", patch(update_lobby_state_handler))
         .route(
             "/lobby/{lobby_id}/player-state",
@@ -47,6 +153,18 @@ pub fn create_http_routes(state: AppState) -> Router {
             "/lobby/{lobby_id}/claim-state",
             patch(update_claim_state_handler),
         )
+        .route(
+            "/lobby/{lobby_id}/claim-state/dispute",
+            post(dispute_claim_handler),
+        )
+        .route(
+            "/admin/lobby/{lobby_id}/claims/{user_id}",
+            patch(resolve_claim_handler),
+        )
+        .route(
+            "/admin/lobby/{lobby_id}/refunds/{user_id}",
+            patch(mark_refund_paid_handler),
+        )
         .layer(axum_middleware::from_fn(move |req, next| {
             rate_limit_middleware(auth_rate_limiter.clone(), req, next)
         }));
@@ -54,10 +172,21 @@ pub fn create_http_routes(state: AppState) -> Router {
     // Regular API routes with moderate rate limiting
     let api_routes = Router::new()
         .route("/user/stat", get(get_user_stat_handler))
+        .route("/user/{user_id}/stats", get(get_user_stats_handler))
+        .route("/stats/h2h", get(get_head_to_head_handler))
         .route("/user/{user_id}", get(get_user_handler))
         .route("/user/lobbies", get(get_player_lobbies_handler))
+        .route("/user/{user_id}/friends", get(get_friends_handler))
+        .route("/presence", get(get_presence_handler))
+        .route("/user/blocked", get(get_blocked_users_handler))
+        .route("/user/me/sessions", get(get_sessions_handler))
+        .route(
+            "/user/notifications",
+            get(get_notification_preferences_handler),
+        )
         .route("/game", get(get_all_games_handler))
         .route("/game/{game_id}", get(get_game_handler))
+        .route("/dictionary-packs", get(get_dictionary_packs_handler))
         .route(
             "/game/lobbies/{game_id}",
             get(get_lobbies_by_game_id_handler),
@@ -65,12 +194,57 @@ pub fn create_http_routes(state: AppState) -> Router {
         .route("/lobby", get(get_all_lobbies_info_handler))
         .route("/lobby/{lobby_id}", get(get_lobby_info_handler))
         .route("/lobby/extended", get(get_all_lobbies_extended_handler))
+        .route("/lobby/stream", get(lobby_stream_handler))
         .route(
             "/lobby/extended/{lobby_id}",
             get(get_lobby_extended_handler),
         )
         .route("/lobby/players/{lobby_id}", get(get_players_handler))
+        .route("/lobby/{lobby_id}/my-result", get(get_my_result_handler))
+        .route("/lobby/{lobby_id}/spectators", get(get_spectators_handler))
+        .route("/lobby/{lobby_id}/live", get(get_live_game_handler))
+        .route("/lobby/{lobby_id}/refunds", get(get_lobby_refunds_handler))
+        .route("/lobby/{lobby_id}/words", get(get_lobby_words_handler))
+        .route(
+            "/lobby/{lobby_id}/reactions",
+            get(get_lobby_reactions_handler),
+        )
+        .route("/chat/lobby/{lobby_id}", get(get_lobby_chat_handler))
         .route("/leaderboard", get(get_leaderboard_handler))
+        .route("/ladder", get(get_ladder_handler))
+        .route("/tg/join/{code}", get(telegram_join_handler))
+        .route(
+            "/admin/users/{user_id}/support-view",
+            get(get_support_view_handler),
+        )
+        .route("/admin/anticheat/flags", get(get_suspicion_flags_handler))
+        .route(
+            "/admin/anticheat/multi-account",
+            get(get_multi_account_reports_handler),
+        )
+        .route(
+            "/admin/users/{user_id}/linked-accounts",
+            get(get_linked_accounts_handler),
+        )
+        .route("/admin/banned-words", get(get_banned_words_handler))
+        .route("/admin/claims/disputed", get(get_disputed_claims_handler))
+        .route("/admin/audit", get(get_audit_log_handler))
+        .route("/admin/bans", get(get_bans_handler))
+        .route("/admin/connections", get(get_connection_metrics_handler))
+        .route(
+            "/admin/lobby/{lobby_id}/state-history",
+            get(get_lobby_state_history_handler),
+        )
+        .route("/admin/dictionary/reports", get(get_word_reports_handler))
+        .route("/admin/lexi-rules", get(get_lexi_rules_handler))
+        .route("/admin/schedules", get(get_schedules_handler))
+        .route("/admin/webhooks", get(get_webhooks_handler))
+        .route("/admin/telegram/routes", get(get_telegram_routes_handler))
+        .route("/admin/chat/reports", get(get_chat_reports_handler))
+        .route(
+            "/schedules/{schedule_id}/lobbies",
+            get(get_schedule_lobbies_handler),
+        )
         .route(
             "/token_info/{contract_address}",
             get(get_token_info_handler),
@@ -79,12 +253,35 @@ pub fn create_http_routes(state: AppState) -> Router {
             "/token_info/testnet/{contract_address}",
             get(get_testnet_token_info_handler),
         )
+        .route("/openapi.json", get(openapi_json_handler))
+        .route("/docs", get(swagger_ui_handler))
         .layer(axum_middleware::from_fn(move |req, next| {
             rate_limit_middleware(api_rate_limiter.clone(), req, next)
         }));
 
-    Router::new()
+    // Read-only game state for dashboards and game-master tooling, shared-secret protected
+    let internal_routes = Router::new()
+        .route(
+            "/internal/lobby/{lobby_id}",
+            get(get_lobby_introspection_handler),
+        )
+        .route("/internal/connections", get(get_connection_counts_handler))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            internal_auth_middleware,
+        ));
+
+    let router = Router::new()
         .merge(auth_routes)
         .merge(api_routes)
-        .with_state(state)
+        .merge(internal_routes);
+
+    // Signed by Discord itself, so it sits outside the user rate limits
+    #[cfg(feature = "discord")]
+    let router = router.route(
+        "/discord/interactions",
+        post(crate::discord::commands::interactions_handler),
+    );
+
+    router.with_state(state)
 }
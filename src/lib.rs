@@ -1,36 +1,66 @@
 pub mod auth;
+mod cluster;
+pub mod config;
 mod db;
+#[cfg(feature = "discord")]
+mod discord;
 pub mod errors;
+mod events;
 pub mod games;
 mod http;
 mod middleware;
 mod models;
+mod moderation;
+mod notifications;
+mod presence;
+mod shutdown;
 mod state;
+mod webhooks;
 pub mod ws;
 
 use axum::{Router, middleware as axum_middleware};
 use bb8::Pool;
 use bb8_redis::RedisConnectionManager;
-use middleware::{cors_layer, create_global_rate_limiter, rate_limit_middleware};
+use middleware::{
+    correlation_id_middleware, cors_layer, create_global_rate_limiter, rate_limit_middleware,
+};
 use state::{AppState, ChatConnectionInfoMap, ConnectionInfoMap};
-use std::{net::SocketAddr, time::Duration};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 use teloxide::{Bot, prelude::*};
 use tokio::signal;
 
 use crate::{
-    games::init::initialize_games,
+    events::start_event_subscribers,
+    games::{
+        archive::start_archive_worker,
+        init::{initialize_games, recover_in_progress_games, start_orphan_sweep},
+        ladder::start_ladder_worker,
+        ownership::start_lease_heartbeat,
+        scheduler::start_turn_scheduler,
+        schedules::start_schedule_worker,
+    },
     http::bot_commands::{Command, handle_command},
+    webhooks::start_webhook_worker,
+    ws::handlers::lobby::pending_tx::start_pending_tx_worker,
 };
 
 pub async fn start_server() {
     dotenvy::dotenv().ok();
     tracing_subscriber::fmt::init();
 
-    let redis_url = std::env::var("REDIS_URL").expect("REDIS_URL must be set");
-    let manager = RedisConnectionManager::new(redis_url).unwrap();
+    // Validate all configuration up front so misconfiguration fails at boot
+    let config = match config::Config::from_env() {
+        Ok(config) => Arc::new(config),
+        Err(e) => {
+            tracing::error!("{}", e);
+            panic!("{}", e);
+        }
+    };
+    config::init(config.clone());
+
+    let manager = RedisConnectionManager::new(config.redis_url.clone()).unwrap();
 
-    let bot_token = std::env::var("TELEGRAM_BOT_TOKEN").expect("TELEGRAM_BOT_TOKEN must be set");
-    let bot = Bot::new(bot_token);
+    let bot = Bot::new(config.telegram_bot_token.clone());
 
     let redis_pool = Pool::builder()
         .max_size(100)
@@ -42,12 +72,25 @@ pub async fn start_server() {
         .await
         .unwrap();
 
+    // Games are listed from their index, so it must cover older games first
+    if let Err(e) = db::game::index::backfill_games_index(redis_pool.clone()).await {
+        tracing::error!("Failed to backfill games index: {}", e);
+    }
+
     // Initialize games in database
     if let Err(e) = initialize_games(redis_pool.clone()).await {
         tracing::error!("Failed to initialize games: {}", e);
         panic!("Failed to initialize games: {}", e);
     }
 
+    if let Err(e) = db::lobby::index::backfill_lobby_indexes(redis_pool.clone()).await {
+        tracing::error!("Failed to backfill lobby indexes: {}", e);
+    }
+
+    if let Err(e) = db::lobby::index::backfill_lobby_search_indexes(redis_pool.clone()).await {
+        tracing::error!("Failed to backfill lobby search indexes: {}", e);
+    }
+
     let connections: ConnectionInfoMap = Default::default();
     let chat_connections: ChatConnectionInfoMap = Default::default();
     let state = AppState {
@@ -55,8 +98,42 @@ pub async fn start_server() {
         chat_connections,
         redis: redis_pool.clone(),
         bot: bot.clone(),
+        config: config.clone(),
     };
 
+    presence::init(
+        state.connections.clone(),
+        state.chat_connections.clone(),
+        redis_pool.clone(),
+    );
+    tokio::spawn(presence::start_presence_heartbeat());
+
+    // Apply changes other instances make to state kept in memory
+    let state_clone = state.clone();
+    tokio::spawn(async move {
+        cluster::start_cluster_listener(state_clone).await;
+    });
+
+    // Subscribers must be listening before recovered games emit anything
+    start_event_subscribers(redis_pool.clone(), bot.clone());
+
+    // Keep the games this instance runs leased, so other instances leave them be
+    let redis_clone = redis_pool.clone();
+    tokio::spawn(async move {
+        start_lease_heartbeat(redis_clone).await;
+    });
+
+    // Resume or settle games interrupted by the last shutdown
+    if let Err(e) = recover_in_progress_games(
+        state.connections.clone(),
+        redis_pool.clone(),
+        bot.clone(),
+    )
+    .await
+    {
+        tracing::error!("Failed to recover in-progress games: {}", e);
+    }
+
     // Start Telegram bot command handler
     let bot_clone = bot.clone();
     let redis_clone = redis_pool.clone();
@@ -64,22 +141,77 @@ pub async fn start_server() {
         start_bot_command_handler(bot_clone, redis_clone).await;
     });
 
+    // Retry joins whose entry payment was still pending
+    let connections_clone = state.connections.clone();
+    let chat_connections_clone = state.chat_connections.clone();
+    let redis_clone = redis_pool.clone();
+    tokio::spawn(async move {
+        start_pending_tx_worker(connections_clone, chat_connections_clone, redis_clone).await;
+    });
+
+    // Single task driving turn countdowns and timeouts
+    let connections_clone = state.connections.clone();
+    let redis_clone = redis_pool.clone();
+    let bot_clone = bot.clone();
+    tokio::spawn(async move {
+        start_turn_scheduler(connections_clone, redis_clone, bot_clone).await;
+    });
+
+    // Pick up games of instances that died without handing them over
+    let connections_clone = state.connections.clone();
+    let redis_clone = redis_pool.clone();
+    let bot_clone = bot.clone();
+    tokio::spawn(async move {
+        start_orphan_sweep(connections_clone, redis_clone, bot_clone).await;
+    });
+
+    // Weekly ladder rollover and inactivity decay
+    let redis_clone = redis_pool.clone();
+    tokio::spawn(async move {
+        start_ladder_worker(redis_clone).await;
+    });
+
+    // Opens lobbies for recurring schedules as they come due
+    let redis_clone = redis_pool.clone();
+    let bot_clone = bot.clone();
+    tokio::spawn(async move {
+        start_schedule_worker(redis_clone, bot_clone).await;
+    });
+
+    // Moves long-finished lobbies out of the lobby lists
+    let redis_clone = redis_pool.clone();
+    tokio::spawn(async move {
+        start_archive_worker(redis_clone).await;
+    });
+
+    // Sends queued integration webhooks, retrying failed ones
+    let redis_clone = redis_pool.clone();
+    tokio::spawn(async move {
+        start_webhook_worker(redis_clone).await;
+    });
+
+    // Keep Discord's slash commands in step with the ones we answer
+    #[cfg(feature = "discord")]
+    if config.discord_bot_token.is_some() && config.discord_application_id.is_some() {
+        tokio::spawn(discord::register_commands());
+    }
+
     // Create rate limiters
     let global_rate_limiter = create_global_rate_limiter();
 
+    let shutdown_state = state.clone();
+
     let app = Router::new()
         .merge(http::create_http_routes(state.clone()))
         .merge(ws::create_ws_routes(state))
         .layer(axum_middleware::from_fn(move |req, next| {
             rate_limit_middleware(global_rate_limiter.clone(), req, next)
         }))
-        .layer(cors_layer())
+        .layer(cors_layer(&config))
+        .layer(axum_middleware::from_fn(correlation_id_middleware))
         .fallback(|| async { "404 Not Found" });
 
-    let port = std::env::var("PORT")
-        .ok()
-        .and_then(|s| s.parse::<u16>().ok())
-        .unwrap_or(3001);
+    let port = config.port;
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{port}"))
         .await
@@ -91,14 +223,14 @@ pub async fn start_server() {
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
-    .with_graceful_shutdown(shutdown_signal());
+    .with_graceful_shutdown(shutdown_signal(shutdown_state));
 
     if let Err(e) = server.await {
         tracing::error!("Server error: {}", e);
     }
 }
 
-async fn shutdown_signal() {
+async fn shutdown_signal(state: AppState) {
     let ctrl_c = async {
         signal::ctrl_c()
             .await
@@ -124,6 +256,9 @@ async fn shutdown_signal() {
             tracing::info!("SIGTERM received, shutting down");
         },
     }
+
+    // Settle in-flight games before the server stops accepting connections
+    shutdown::drain(&state).await;
 }
 
 async fn start_bot_command_handler(bot: Bot, redis: bb8::Pool<RedisConnectionManager>) {
@@ -1,12 +1,16 @@
 use axum::{
-    extract::{ConnectInfo, Request},
-    http::StatusCode,
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderValue, StatusCode},
     middleware::Next,
     response::Response,
 };
 use governor::{Quota, RateLimiter, clock::DefaultClock, state::keyed::DefaultKeyedStateStore};
 use std::{net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
 use tower_http::cors::CorsLayer;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::{config::Config, state::AppState};
 
 pub type IpRateLimiter = Arc<RateLimiter<String, DefaultKeyedStateStore<String>, DefaultClock>>;
 
@@ -59,13 +63,75 @@ pub async fn rate_limit_middleware(
     }
 }
 
-// CORS configuration using multiple allowed origins from env
-pub fn cors_layer() -> CorsLayer {
-    let allowed_origins = std::env::var("ALLOWED_ORIGINS")
-        .unwrap_or_else(|_| "http://localhost:3000".to_string())
-        .split(',')
-        .map(|s| s.trim().parse().unwrap())
-        .collect::<Vec<_>>();
+// Shared-secret check for the internal API, which is disabled when INTERNAL_API_SECRET is unset
+pub async fn internal_auth_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let Some(secret) = state.config.internal_api_secret.as_deref() else {
+        tracing::warn!("Internal API request rejected, INTERNAL_API_SECRET is not set");
+        return Err(StatusCode::NOT_FOUND);
+    };
+
+    let provided = request
+        .headers()
+        .get("x-internal-secret")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+
+    // Compare without short-circuiting so the secret can't be guessed byte by byte
+    let matches = provided.len() == secret.len()
+        && provided
+            .bytes()
+            .zip(secret.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0;
+
+    if !matches {
+        tracing::warn!("Internal API request with invalid secret");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    Ok(next.run(request).await)
+}
+
+const CORRELATION_ID_HEADER: &str = "x-request-id";
+
+// Runs each request, and the WebSocket it may upgrade to, in a span carrying a
+// correlation id. A well-formed id sent by the caller is kept so logs can be
+// matched with the client's; the id is echoed back in the response.
+pub async fn correlation_id_middleware(request: Request, next: Next) -> Response {
+    let correlation_id = request
+        .headers()
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|id| {
+            (1..=64).contains(&id.len())
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+        .map(String::from)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let span = tracing::info_span!(
+        "request",
+        correlation_id = %correlation_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+    let mut response = next.run(request).instrument(span).await;
+
+    if let Ok(value) = HeaderValue::from_str(&correlation_id) {
+        response.headers_mut().insert(CORRELATION_ID_HEADER, value);
+    }
+    response
+}
+
+// CORS configuration using multiple allowed origins from config
+pub fn cors_layer(config: &Config) -> CorsLayer {
+    let allowed_origins = config.allowed_origins.clone();
 
     tracing::info!("CORS allowed origins: {:?}", allowed_origins);
 
@@ -83,6 +149,7 @@ pub fn cors_layer() -> CorsLayer {
             axum::http::header::AUTHORIZATION,
             axum::http::header::CONTENT_TYPE,
             axum::http::header::ACCEPT,
+            axum::http::HeaderName::from_static("idempotency-key"),
         ])
         .allow_credentials(true)
         .max_age(Duration::from_secs(3600))
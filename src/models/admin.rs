@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    games::lexi_wars::rule_dsl::RuleDefinition,
+    models::game::{ClaimState, Language, LobbyState},
+    ws::lifecycle::DisconnectReason,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserError {
+    pub lobby_id: Uuid,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportConnections {
+    pub game_socket: bool,
+    pub chat_socket: bool,
+    /// Lobbies whose connected set still lists the user
+    pub connected_lobbies: Vec<Uuid>,
+}
+
+/// Sockets open on this instance, and how many reached each step of their
+/// life since it started
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionMetrics {
+    pub open_game_sockets: usize,
+    pub open_chat_sockets: usize,
+    pub connected: u64,
+    pub authenticated: u64,
+    pub joined_lobby: u64,
+    pub spectating: u64,
+    /// Closed sockets by reason
+    pub disconnected: BTreeMap<DisconnectReason, u64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportActiveLobby {
+    pub id: Uuid,
+    pub name: String,
+    pub state: LobbyState,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportPendingClaim {
+    pub lobby_id: Uuid,
+    pub lobby_name: String,
+    pub rank: Option<usize>,
+    pub prize: Option<f64>,
+    pub token_symbol: Option<String>,
+}
+
+/// Read-only view of a user for support staff, with wallet and tx details stripped
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportView {
+    pub user_id: Uuid,
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub wallet_hint: String,
+    pub wars_point: f64,
+    pub connections: SupportConnections,
+    pub active_lobbies: Vec<SupportActiveLobby>,
+    pub pending_claims: Vec<SupportPendingClaim>,
+    pub recent_errors: Vec<UserError>,
+}
+
+/// A player whose submissions crossed the anti-cheat threshold
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SuspicionFlag {
+    pub lobby_id: Uuid,
+    pub player_id: Uuid,
+    pub suspicion_score: u64,
+    pub reason: String,
+    pub eliminated: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// What two accounts were seen sharing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountLink {
+    /// Same address and user agent, most likely the same browser
+    SameDevice,
+    /// Same address only, which households and public networks also share
+    SameNetwork,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedAccount {
+    pub user_id: Uuid,
+    pub wallet_address: String,
+    pub link: AccountLink,
+}
+
+/// A player who joined a prize lobby alongside accounts they look linked to
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiAccountReport {
+    pub lobby_id: Uuid,
+    pub user_id: Uuid,
+    pub wallet_address: String,
+    pub linked: Vec<LinkedAccount>,
+    /// Whether the join was refused
+    pub blocked: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A prize payout a player has reported a problem with
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DisputedClaim {
+    pub lobby_id: Uuid,
+    pub user_id: Uuid,
+    pub prize: Option<f64>,
+    pub claim: ClaimState,
+}
+
+/// A word players flagged as wrongly rejected
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WordReport {
+    pub word: String,
+    pub language: Language,
+    pub reports: u64,
+}
+
+/// The Lexi Wars rule progression and the custom rules in it
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LexiRulesView {
+    pub progression: Vec<String>,
+    pub custom_rules: Vec<RuleDefinition>,
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub actor_id: Uuid,
+    pub action: String,
+    pub target_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+    /// Why the admin took the action, for those that ask for one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(actor_id: Uuid, action: impl Into<String>, target_id: Option<Uuid>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            actor_id,
+            action: action.into(),
+            target_id,
+            timestamp: Utc::now(),
+            reason: None,
+        }
+    }
+
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+/// Narrows an audit log query; unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub actor_id: Option<Uuid>,
+    pub target_id: Option<Uuid>,
+    /// Matches the action name, without any `:lobby_id` suffix
+    pub action: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        let action_name = entry.action.split(':').next().unwrap_or_default();
+        self.actor_id.is_none_or(|id| id == entry.actor_id)
+            && self.target_id.is_none_or(|id| Some(id) == entry.target_id)
+            && self.action.as_deref().is_none_or(|a| a == action_name)
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+            && self.until.is_none_or(|until| entry.timestamp <= until)
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Wars points a spectator staked on a player winning the game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpectatorBet {
+    pub user_id: Uuid,
+    pub winner_id: Uuid,
+    pub amount: f64,
+    pub placed_at: DateTime<Utc>,
+}
+
+/// How a spectator's bet was settled; `winner_id` is unset when every stake
+/// was returned because the game had no winner or nobody backed them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BetSettlement {
+    pub winner_id: Option<Uuid>,
+    pub stake: f64,
+    pub payout: f64,
+}
@@ -1,16 +1,48 @@
-use crate::models::game::Player;
+use crate::models::{friends::LobbyInvite, game::Player, presence::Presence};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum ChatClientMessage {
-    Chat { text: String },
-    Ping { ts: u64 },
+    Chat {
+        text: String,
+    },
+    Ping {
+        ts: u64,
+    },
+    /// Lobby creator or admin only
+    #[serde(rename_all = "camelCase")]
+    MuteUser {
+        user_id: Uuid,
+        minutes: u32,
+    },
+    /// The author, lobby creator or an admin
+    #[serde(rename_all = "camelCase")]
+    DeleteMessage {
+        message_id: Uuid,
+    },
+    #[serde(rename_all = "camelCase")]
+    ReportMessage {
+        message_id: Uuid,
+        reason: Option<String>,
+    },
+    /// Sent while the user types, relayed to the lobby at most every few seconds
+    Typing,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PresenceStatus {
+    Joined,
+    /// Connected but silent, not even heartbeats, for a while
+    Away,
+    Left,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ChatMessage {
     pub id: Uuid,
     pub text: String,
@@ -18,14 +50,76 @@ pub struct ChatMessage {
     pub timestamp: DateTime<Utc>,
 }
 
+/// A page of lobby chat, oldest message first.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatPage {
+    pub messages: Vec<ChatMessage>,
+    /// Older messages exist before the first one
+    pub has_more: bool,
+}
+
+/// A chat message a player flagged, kept for admins after the lobby chat expires.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatReport {
+    pub lobby_id: Uuid,
+    pub message: ChatMessage,
+    pub reported_by: Uuid,
+    pub reason: Option<String>,
+    pub reported_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum ChatServerMessage {
-    PermitChat { allowed: bool },
-    Chat { message: ChatMessage },
-    ChatHistory { messages: Vec<ChatMessage> },
-    Pong { ts: u64, pong: u64 },
-    Error { message: String },
+    PermitChat {
+        allowed: bool,
+    },
+    Chat {
+        message: Box<ChatMessage>,
+    },
+    #[serde(rename_all = "camelCase")]
+    ChatHistory {
+        messages: Vec<ChatMessage>,
+        has_more: bool,
+    },
+    Pong {
+        ts: u64,
+        pong: u64,
+    },
+    Error {
+        message: String,
+    },
+    LobbyInvite {
+        invite: LobbyInvite,
+    },
+    FriendPresence {
+        presence: Presence,
+    },
+    #[serde(rename_all = "camelCase")]
+    UserMuted {
+        user_id: Uuid,
+        until: DateTime<Utc>,
+    },
+    /// Tombstone for a deleted message
+    #[serde(rename_all = "camelCase")]
+    MessageDeleted {
+        message_id: Uuid,
+    },
+    #[serde(rename_all = "camelCase")]
+    MessageReported {
+        message_id: Uuid,
+    },
+    #[serde(rename_all = "camelCase")]
+    Typing {
+        user_id: Uuid,
+    },
+    #[serde(rename_all = "camelCase")]
+    Presence {
+        user_id: Uuid,
+        status: PresenceStatus,
+    },
 }
 
 impl ChatServerMessage {
@@ -33,12 +127,19 @@ impl ChatServerMessage {
         match self {
             // Time-sensitive messages that should NOT be queued
             ChatServerMessage::Pong { .. } => false,
+            ChatServerMessage::LobbyInvite { .. } => false,
+            ChatServerMessage::FriendPresence { .. } => false,
+            ChatServerMessage::MessageReported { .. } => false,
+            ChatServerMessage::Typing { .. } => false,
+            ChatServerMessage::Presence { .. } => false,
 
             // Important messages that SHOULD be queued
             ChatServerMessage::PermitChat { .. } => true,
             ChatServerMessage::Chat { .. } => true,
             ChatServerMessage::ChatHistory { .. } => true,
             ChatServerMessage::Error { .. } => true,
+            ChatServerMessage::UserMuted { .. } => true,
+            ChatServerMessage::MessageDeleted { .. } => true,
         }
     }
 }
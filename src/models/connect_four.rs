@@ -0,0 +1,162 @@
+use crate::models::{
+    bet::{BetSettlement, SpectatorBet},
+    error_code::ErrorCode,
+    game::{Player, Reaction},
+    lobby::LobbyRefund,
+    queue::QueuePolicy,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ConnectFourClientMessage {
+    /// Drops a disc into a column, 0 being the leftmost. `action_id` lets a
+    /// retried move be applied only once
+    #[serde(rename_all = "camelCase")]
+    DropDisc {
+        column: usize,
+        action_id: Option<String>,
+    },
+    Ping {
+        ts: u64,
+    },
+    /// Confirms receipt of the player's rank, prize and wars point
+    ResultAck,
+    /// Quick reaction, also accepted from spectators
+    Reaction {
+        emoji: Reaction,
+    },
+    /// Spectators only: stakes wars points on a player winning
+    #[serde(rename_all = "camelCase")]
+    PlaceBet {
+        winner_id: Uuid,
+        amount: f64,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ConnectFourServerMessage {
+    Start {
+        time: u32,
+        started: bool,
+    },
+    StartFailed,
+    Spectator,
+    /// Whole grid, rows from the bottom with 0 empty and 1/2 for `players[0]`/`players[1]`
+    Board {
+        rows: Vec<Vec<u8>>,
+        players: Vec<Uuid>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Turn {
+        current_turn: Box<Player>,
+        countdown: u64,
+    },
+    Countdown {
+        time: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    DiscDropped {
+        player_id: Uuid,
+        column: usize,
+        row: usize,
+    },
+    /// Four in a row, or the opponent forfeited when `line` is empty
+    #[serde(rename_all = "camelCase")]
+    Win {
+        player_id: Uuid,
+        line: Vec<(usize, usize)>,
+    },
+    /// The grid filled up without a winner; paid entries are refunded
+    Draw,
+    GameOver,
+    Rank {
+        rank: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Prize {
+        amount: f64,
+        token_symbol: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    WarsPoint {
+        wars_point: f64,
+    },
+    /// Status of the player's entry refund after the game was canceled
+    Refund {
+        refund: LobbyRefund,
+    },
+    /// The move sent with this id was applied
+    #[serde(rename_all = "camelCase")]
+    ActionAck {
+        action_id: String,
+    },
+    Pong {
+        ts: u64,
+        pong: u64,
+    },
+    Error {
+        code: ErrorCode,
+        message: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Reaction {
+        user_id: Uuid,
+        emoji: Reaction,
+    },
+    /// A spectator's bet, shown to the other spectators
+    BetPlaced {
+        bet: SpectatorBet,
+    },
+    /// The bettor's payout once the game is over
+    BetSettled {
+        settlement: BetSettlement,
+    },
+}
+
+impl ConnectFourServerMessage {
+    pub fn should_queue(&self) -> bool {
+        match self {
+            // Time-sensitive messages that should NOT be queued
+            ConnectFourServerMessage::Countdown { .. } => false,
+            ConnectFourServerMessage::Pong { .. } => false,
+            ConnectFourServerMessage::Reaction { .. } => false,
+            ConnectFourServerMessage::BetPlaced { .. } => false,
+            ConnectFourServerMessage::Start { started: false, .. } => false,
+
+            // Important messages that SHOULD be queued
+            ConnectFourServerMessage::Start { started: true, .. } => true,
+            ConnectFourServerMessage::StartFailed => true,
+            ConnectFourServerMessage::Spectator => true,
+            ConnectFourServerMessage::DiscDropped { .. } => true,
+            ConnectFourServerMessage::ActionAck { .. } => true,
+            ConnectFourServerMessage::Win { .. } => true,
+            ConnectFourServerMessage::Draw => true,
+            ConnectFourServerMessage::GameOver => true,
+            ConnectFourServerMessage::Rank { .. } => true,
+            ConnectFourServerMessage::Prize { .. } => true,
+            ConnectFourServerMessage::WarsPoint { .. } => true,
+            ConnectFourServerMessage::Refund { .. } => true,
+            ConnectFourServerMessage::BetSettled { .. } => true,
+            ConnectFourServerMessage::Error { .. } => true,
+
+            // Kept only as the latest copy, see queue_policy
+            ConnectFourServerMessage::Board { .. } => true,
+            ConnectFourServerMessage::Turn { .. } => true,
+        }
+    }
+
+    /// TTL and compaction used when this message is queued
+    pub fn queue_policy(&self) -> QueuePolicy {
+        match self {
+            ConnectFourServerMessage::Turn { .. } => QueuePolicy::latest(15),
+            ConnectFourServerMessage::Board { .. } => QueuePolicy::latest(120),
+            ConnectFourServerMessage::ActionAck { .. } | ConnectFourServerMessage::Error { .. } => {
+                QueuePolicy::ttl(15)
+            }
+            _ => QueuePolicy::DEFAULT,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+
+/// Stable codes sent with websocket errors so clients can branch on them
+/// instead of matching on message text. Never rename a variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // Gameplay
+    NotYourTurn,
+    GameNotStarted,
+    InvalidWord,
+    WordNotAllowed,
+    RuleViolation,
+    GamePaused,
+
+    // Lobby
+    NotLobbyCreator,
+    InvalidLobbyState,
+    JoinRequestMissing,
+    JoinRequestPending,
+    JoinRequestRejected,
+
+    // Friends
+    NotFriends,
+    FriendOffline,
+
+    // Moderation
+    AccountSuspended,
+
+    // Generic
+    BadRequest,
+    Unauthorized,
+    NotFound,
+    InternalError,
+}
+
+impl From<&AppError> for ErrorCode {
+    fn from(error: &AppError) -> Self {
+        match error {
+            AppError::BadRequest(_) | AppError::Deserialization(_) => ErrorCode::BadRequest,
+            AppError::Unauthorized(_) | AppError::JwtError(_) => ErrorCode::Unauthorized,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::AccountSuspended { .. } => ErrorCode::AccountSuspended,
+            _ => ErrorCode::InternalError,
+        }
+    }
+}
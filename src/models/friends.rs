@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::User;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum FriendAction {
+    Request,
+    Accept,
+    /// Unfriends, or withdraws/declines a pending request
+    Remove,
+}
+
+/// Relationship between two users after a friend action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum FriendStatus {
+    None,
+    Pending,
+    Friends,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FriendEntry {
+    pub user: User,
+    /// Has a lobby, game or chat socket open on this instance
+    pub online: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FriendsList {
+    pub friends: Vec<FriendEntry>,
+    pub incoming_requests: Vec<User>,
+    pub outgoing_requests: Vec<User>,
+}
+
+/// Invitation pushed to a friend's open socket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyInvite {
+    pub lobby_id: Uuid,
+    pub lobby_name: String,
+    pub from: User,
+}
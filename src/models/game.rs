@@ -2,6 +2,7 @@ use std::{collections::HashMap, str::FromStr};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{errors::AppError, models::User};
@@ -9,9 +10,13 @@ use crate::{errors::AppError, models::User};
 #[derive(Deserialize)]
 pub struct WsQueryParams {
     pub user_id: Uuid,
+    pub protocol_version: Option<u32>,
+    /// Token of the user, ties the socket to its session so revoking the
+    /// session closes it
+    pub token: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GameType {
     pub id: Uuid,
@@ -72,7 +77,7 @@ impl GameType {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum PlayerState {
     NotJoined,
@@ -91,32 +96,81 @@ impl FromStr for PlayerState {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Payout lifecycle of a prize.
+///
+/// The normal path is `Claimable -> Claimed`. Players can dispute a prize at
+/// any point before it is refunded, and admins move disputes back onto the
+/// normal path or refund them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 #[serde(tag = "status", content = "data", rename_all = "camelCase")]
 pub enum ClaimState {
-    Claimed { tx_id: String },
-    NotClaimed,
+    /// Prize is on hold until an admin confirms the result
+    PendingVerification,
+    /// Prize can be withdrawn from the pool contract
+    #[serde(alias = "notClaimed")]
+    Claimable,
+    Claimed {
+        tx_id: String,
+    },
+    /// The player reported a payout problem; keeps the claim tx if there was one
+    Disputed {
+        reason: String,
+        tx_id: Option<String>,
+    },
+    /// Paid out by an admin outside the pool contract
+    Refunded {
+        tx_id: String,
+    },
 }
 
 impl ClaimState {
     pub fn matches_filter(&self, filter: &ClaimState) -> bool {
-        match (self, filter) {
-            (ClaimState::NotClaimed, ClaimState::NotClaimed) => true,
-            (ClaimState::Claimed { .. }, ClaimState::Claimed { .. }) => true,
-            _ => false,
-        }
+        std::mem::discriminant(self) == std::mem::discriminant(filter)
     }
 
     pub fn is_claimed(&self) -> bool {
         matches!(self, ClaimState::Claimed { .. })
     }
 
-    pub fn is_not_claimed(&self) -> bool {
-        matches!(self, ClaimState::NotClaimed)
+    pub fn is_claimable(&self) -> bool {
+        matches!(self, ClaimState::Claimable)
+    }
+
+    pub fn is_disputed(&self) -> bool {
+        matches!(self, ClaimState::Disputed { .. })
+    }
+
+    pub fn tx_id(&self) -> Option<&str> {
+        match self {
+            ClaimState::Claimed { tx_id } | ClaimState::Refunded { tx_id } => Some(tx_id),
+            ClaimState::Disputed { tx_id, .. } => tx_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn can_transition_to(&self, next: &ClaimState) -> bool {
+        matches!(
+            (self, next),
+            (ClaimState::PendingVerification, ClaimState::Claimable)
+                | (ClaimState::Claimable, ClaimState::Claimed { .. })
+                | (
+                    ClaimState::PendingVerification
+                        | ClaimState::Claimable
+                        | ClaimState::Claimed { .. },
+                    ClaimState::Disputed { .. }
+                )
+                | (
+                    ClaimState::Disputed { .. },
+                    ClaimState::PendingVerification
+                        | ClaimState::Claimable
+                        | ClaimState::Claimed { .. }
+                        | ClaimState::Refunded { .. }
+                )
+        )
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Player {
     pub id: Uuid,
@@ -128,7 +182,10 @@ pub struct Player {
     pub tx_id: Option<String>,
     pub claim: Option<ClaimState>,
     pub prize: Option<f64>,
+    pub wars_point: Option<f64>,
     pub last_ping: Option<u64>,
+    /// When the player paid in, used to pick the next lobby owner
+    pub joined_at: Option<u64>,
 
     // Hydrated user data (not stored in Redis)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -160,9 +217,15 @@ impl Player {
         if let Some(ref prize) = self.prize {
             map.insert("prize".into(), prize.to_string());
         }
+        if let Some(ref wars_point) = self.wars_point {
+            map.insert("wars_point".into(), wars_point.to_string());
+        }
         if let Some(ref last_ping) = self.last_ping {
             map.insert("last_ping".into(), last_ping.to_string());
         }
+        if let Some(ref joined_at) = self.joined_at {
+            map.insert("joined_at".into(), joined_at.to_string());
+        }
 
         map
     }
@@ -194,8 +257,12 @@ impl Player {
 
         let prize = data.get("prize").and_then(|v| v.parse::<f64>().ok());
 
+        let wars_point = data.get("wars_point").and_then(|v| v.parse::<f64>().ok());
+
         let last_ping = data.get("last_ping").and_then(|v| v.parse::<u64>().ok());
 
+        let joined_at = data.get("joined_at").and_then(|v| v.parse::<u64>().ok());
+
         Ok(Player {
             id,
             state,
@@ -204,13 +271,17 @@ impl Player {
             tx_id,
             claim,
             prize,
+            wars_point,
             last_ping,
+            joined_at,
             user: None, // Will be hydrated separately
         })
     }
 
     // Helper to create a new player with minimal data
     pub fn new(user_id: Uuid, tx_id: Option<String>, state: PlayerState) -> Self {
+        let now = Utc::now().timestamp_millis() as u64;
+        let joined_at = (state == PlayerState::Joined).then_some(now);
         Player {
             id: user_id,
             state,
@@ -219,7 +290,9 @@ impl Player {
             tx_id,
             claim: None,
             prize: None,
-            last_ping: Some(Utc::now().timestamp_millis() as u64),
+            wars_point: None,
+            last_ping: Some(now),
+            joined_at,
             user: None,
         }
     }
@@ -236,6 +309,9 @@ impl Player {
                 wars_point: 0.0,
                 username: None,
                 display_name: None,
+                avatar_url: None,
+                bio: None,
+                deleted: false,
             }
         })
     }
@@ -245,7 +321,7 @@ impl Player {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LobbyPoolInput {
     pub entry_amount: f64,
     pub current_amount: f64,
@@ -253,13 +329,122 @@ pub struct LobbyPoolInput {
     #[serde(default = "default_token_symbol")]
     pub token_symbol: Option<String>,
     pub token_id: Option<String>,
+    #[serde(default)]
+    pub asset: Option<PoolAsset>,
 }
 
 fn default_token_symbol() -> Option<String> {
     Some("STX".to_string())
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+const SBTC_MAINNET_ASSET: &str = "SM3VDXK3WZZSA84XXFKAFAF15NNZX32CTSG82JFQ4.sbtc-token::sbtc-token";
+const SBTC_TESTNET_ASSET: &str = "ST1F7QA2MDF17S807EPA36TSS8AMEFY4KA9TVGWXT.sbtc-token::sbtc-token";
+
+/// Parses a plain decimal like `"12.345"` into base units of a token with
+/// `decimals` decimals, rounding half up past the last one.
+fn decimal_to_base_units(amount: &str, decimals: u8) -> Option<u128> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+
+    let decimals = decimals as usize;
+    let mut units: u128 = 0;
+    let digits = whole.chars().chain(
+        fraction
+            .chars()
+            .chain(std::iter::repeat('0'))
+            .take(decimals),
+    );
+    for c in digits {
+        let digit = c.to_digit(10)?;
+        units = units.checked_mul(10)?.checked_add(digit as u128)?;
+    }
+
+    match fraction.chars().nth(decimals) {
+        Some(c) if c.to_digit(10)? >= 5 => units.checked_add(1),
+        _ => Some(units),
+    }
+}
+
+/// Token a lobby pool is paid in.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PoolAsset {
+    Stx,
+    Sbtc,
+    /// Any SIP-010 token, `asset_id` being `<contract>::<token name>`
+    #[serde(rename_all = "camelCase")]
+    Sip010 {
+        asset_id: String,
+        symbol: String,
+        decimals: u8,
+    },
+}
+
+impl PoolAsset {
+    pub fn symbol(&self) -> &str {
+        match self {
+            PoolAsset::Stx => "STX",
+            PoolAsset::Sbtc => "sBTC",
+            PoolAsset::Sip010 { symbol, .. } => symbol,
+        }
+    }
+
+    pub fn decimals(&self) -> u8 {
+        match self {
+            PoolAsset::Stx => 6,
+            PoolAsset::Sbtc => 8,
+            PoolAsset::Sip010 { decimals, .. } => *decimals,
+        }
+    }
+
+    /// Fungible token identifier as reported in tx events, `None` for STX.
+    pub fn asset_id(&self, network: &str) -> Option<&str> {
+        match self {
+            PoolAsset::Stx => None,
+            PoolAsset::Sbtc if network == "mainnet" => Some(SBTC_MAINNET_ASSET),
+            PoolAsset::Sbtc => Some(SBTC_TESTNET_ASSET),
+            PoolAsset::Sip010 { asset_id, .. } => Some(asset_id),
+        }
+    }
+
+    /// Converts a display amount into the token's smallest unit, `None` for
+    /// negative or non-finite amounts. Works on the amount's decimal digits,
+    /// since an f64 can't hold 18-decimal base units exactly.
+    pub fn to_base_units(&self, amount: f64) -> Option<u128> {
+        if !amount.is_finite() || amount < 0.0 {
+            return None;
+        }
+        decimal_to_base_units(&amount.to_string(), self.decimals())
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        if let PoolAsset::Sip010 {
+            asset_id,
+            symbol,
+            decimals,
+        } = self
+        {
+            let valid_id = asset_id
+                .split_once("::")
+                .is_some_and(|(contract, name)| contract.contains('.') && !name.is_empty());
+            if !valid_id {
+                return Err(AppError::BadRequest(format!(
+                    "Invalid SIP-010 asset id: {asset_id}"
+                )));
+            }
+            if symbol.trim().is_empty() || *decimals > 18 {
+                return Err(AppError::BadRequest(
+                    "SIP-010 asset needs a symbol and at most 18 decimals".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum LobbyState {
     Waiting,
@@ -282,7 +467,28 @@ impl FromStr for LobbyState {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl LobbyState {
+    /// Moves a lobby may make. Finished is final; everything else can still
+    /// be called off, and a countdown or a game that never got going falls
+    /// back to Waiting.
+    pub fn can_transition_to(&self, next: &LobbyState) -> bool {
+        matches!(
+            (self, next),
+            (
+                LobbyState::Waiting,
+                LobbyState::Starting | LobbyState::InProgress | LobbyState::Finished
+            ) | (
+                LobbyState::Starting,
+                LobbyState::Waiting | LobbyState::InProgress | LobbyState::Finished
+            ) | (
+                LobbyState::InProgress,
+                LobbyState::Waiting | LobbyState::Finished
+            )
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerLobbyInfo {
     #[serde(flatten)]
@@ -292,7 +498,18 @@ pub struct PlayerLobbyInfo {
     pub claim_state: Option<ClaimState>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerResult {
+    pub lobby_id: Uuid,
+    pub lobby_state: LobbyState,
+    pub rank: Option<usize>,
+    pub prize: Option<f64>,
+    pub wars_point: Option<f64>,
+    pub claim: Option<ClaimState>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LobbyInfo {
     pub id: Uuid,
@@ -310,11 +527,446 @@ pub struct LobbyInfo {
     pub current_amount: Option<f64>,
     pub token_symbol: Option<String>,
     pub token_id: Option<String>,
+    /// `None` for lobbies created before pools tracked their asset
+    #[serde(default)]
+    pub asset: Option<PoolAsset>,
     pub creator_last_ping: Option<u64>,
     pub tg_msg_id: Option<i32>,
+    /// Chat `tg_msg_id` was posted in, so replies follow it after a reroute
+    pub tg_chat_id: Option<i64>,
+    #[serde(default)]
+    pub settings: LobbySettings,
+    /// Finished long enough ago to be moved out of the lobby lists
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// Creator-tunable options, stored as flat fields on the lobby hash.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbySettings {
+    /// Max messages kept for a disconnected player.
+    pub queue_max_len: Option<usize>,
+    /// Only messages queued within this window are replayed on reconnect.
+    pub replay_window_secs: Option<u64>,
+    /// Rejects offensive-but-valid words in word games.
+    #[serde(default)]
+    pub family_friendly: bool,
+    /// Named dictionary pack used instead of the default word set.
+    pub dictionary_pack: Option<String>,
+    /// Dictionary language for word games; English when unset.
+    pub language: Option<Language>,
+    /// Lexi Wars preset; standard when unset.
+    pub difficulty: Option<Difficulty>,
+    /// Lexi Wars rules to play with, overriding the preset's rule set.
+    pub rules: Option<Vec<String>>,
+    /// Players must acknowledge a ready-check before the countdown starts.
+    #[serde(default)]
+    pub ready_check: bool,
+    /// Percentage of joined players needed to pass the ready-check; all when unset.
+    pub ready_quorum: Option<u8>,
+    /// How the pool is paid out; the standard split when unset.
+    pub prize_split: Option<PrizeSplit>,
+    /// Rock-Paper-Scissors rounds in a match, odd; 3 when unset.
+    pub best_of: Option<u8>,
+    /// Seconds after the start that spectators may still bet; 60 when unset, 0 turns betting off.
+    pub bet_window_secs: Option<u64>,
+    /// Lexi Wars seconds each player gets for the whole game, used up while
+    /// it is their turn, instead of a fresh timer every turn.
+    pub time_bank_secs: Option<u64>,
+    /// Warm-up lobby without entry fees, prizes, bets, wars points or stats.
+    #[serde(default)]
+    pub casual: bool,
+    /// Lexi Wars game length in seconds, after which the players still in
+    /// are ranked by words played; no limit when unset.
+    pub max_game_secs: Option<u64>,
+}
+
+const MAX_PRIZE_PLACES: usize = 10;
+const MAX_BET_WINDOW_SECS: u64 = 600;
+
+/// Share of the pool paid to each finishing position.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PrizeSplit {
+    /// 70/30 heads-up, 50/30/20 otherwise
+    #[default]
+    Standard,
+    WinnerTakesAll,
+    /// Pool shared equally by the top `places` finishers
+    Equal {
+        places: u8,
+    },
+    /// Percentage per position, 1st place first
+    Custom {
+        percentages: Vec<u8>,
+    },
+}
+
+impl PrizeSplit {
+    /// Percentage of the pool for each position, 1st place first.
+    pub fn percentages(&self, players: usize) -> Vec<f64> {
+        match self {
+            PrizeSplit::Standard if players == 2 => vec![70.0, 30.0],
+            PrizeSplit::Standard => vec![50.0, 30.0, 20.0],
+            PrizeSplit::WinnerTakesAll => vec![100.0],
+            PrizeSplit::Equal { places } => {
+                let places = (*places as usize).min(players).max(1);
+                vec![100.0 / places as f64; places]
+            }
+            PrizeSplit::Custom { percentages } => percentages.iter().map(|&p| p as f64).collect(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        match self {
+            PrizeSplit::Equal { places } if *places == 0 || *places as usize > MAX_PRIZE_PLACES => {
+                Err(AppError::BadRequest(format!(
+                    "Equal split needs between 1 and {MAX_PRIZE_PLACES} places"
+                )))
+            }
+            PrizeSplit::Custom { percentages } => {
+                if percentages.is_empty() || percentages.len() > MAX_PRIZE_PLACES {
+                    return Err(AppError::BadRequest(format!(
+                        "Custom split needs between 1 and {MAX_PRIZE_PLACES} places"
+                    )));
+                }
+                let total: u32 = percentages.iter().map(|&p| p as u32).sum();
+                if total > 100 {
+                    return Err(AppError::BadRequest(format!(
+                        "Prize split adds up to {total}%, at most 100% is allowed"
+                    )));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Language of the dictionary a word game is played in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    Es,
+    Fr,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::En, Language::Es, Language::Fr];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Es => "es",
+            Language::Fr => "fr",
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "en" => Ok(Language::En),
+            "es" => Ok(Language::Es),
+            "fr" => Ok(Language::Fr),
+            other => Err(format!("Unknown Language: {}", other)),
+        }
+    }
+}
+
+/// Why a running game was paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PauseReason {
+    /// The lobby creator paused it
+    Creator,
+    /// More than half of the players lost their connection
+    Disconnects,
+}
+
+impl PauseReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PauseReason::Creator => "creator",
+            PauseReason::Disconnects => "disconnects",
+        }
+    }
+}
+
+impl FromStr for PauseReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "creator" => Ok(PauseReason::Creator),
+            "disconnects" => Ok(PauseReason::Disconnects),
+            other => Err(format!("Unknown PauseReason: {}", other)),
+        }
+    }
+}
+
+/// A paused game, holding the turn that was frozen until it resumes.
+#[derive(Debug, Clone)]
+pub struct GamePause {
+    pub reason: PauseReason,
+    /// Unix milliseconds
+    pub paused_at: i64,
+    pub player_id: Uuid,
+    /// What was left of the turn when the game was paused
+    pub remaining_ms: u64,
+}
+
+impl GamePause {
+    pub fn from_redis_hash(map: &HashMap<String, String>) -> Result<Self, AppError> {
+        let field = |name: &str| {
+            map.get(name)
+                .ok_or_else(|| AppError::Deserialization(format!("Missing pause {name}")))
+        };
+
+        Ok(Self {
+            reason: field("reason")?
+                .parse()
+                .map_err(|e| AppError::Deserialization(format!("Invalid pause reason: {}", e)))?,
+            paused_at: field("paused_at")?
+                .parse()
+                .map_err(|_| AppError::Deserialization("Invalid pause paused_at".into()))?,
+            player_id: field("player_id")?
+                .parse()
+                .map_err(|_| AppError::Deserialization("Invalid pause player_id".into()))?,
+            remaining_ms: field("remaining_ms")?
+                .parse()
+                .map_err(|_| AppError::Deserialization("Invalid pause remaining_ms".into()))?,
+        })
+    }
+
+    /// When the pause runs out, in unix milliseconds.
+    pub fn expires_at(&self, max_pause_secs: u64) -> i64 {
+        self.paused_at + (max_pause_secs * 1000) as i64
+    }
+}
+
+/// Quick reaction players and spectators can send during a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum Reaction {
+    #[serde(rename = "🎉")]
+    Party,
+    #[serde(rename = "😱")]
+    Shocked,
+    #[serde(rename = "🔥")]
+    Fire,
+}
+
+impl Reaction {
+    pub const ALL: [Reaction; 3] = [Reaction::Party, Reaction::Shocked, Reaction::Fire];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Reaction::Party => "🎉",
+            Reaction::Shocked => "😱",
+            Reaction::Fire => "🔥",
+        }
+    }
+}
+
+/// Lexi Wars difficulty preset chosen at lobby creation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum Difficulty {
+    Casual,
+    #[default]
+    Standard,
+    Hardcore,
+}
+
+impl Difficulty {
+    pub fn min_word_length(self) -> usize {
+        match self {
+            Difficulty::Casual => 3,
+            Difficulty::Standard => 4,
+            Difficulty::Hardcore => 6,
+        }
+    }
+
+    /// Turn length override, `None` keeps the configured default.
+    pub fn turn_timer_secs(self) -> Option<u64> {
+        match self {
+            Difficulty::Casual => Some(20),
+            Difficulty::Standard => None,
+            Difficulty::Hardcore => Some(10),
+        }
+    }
+
+    /// Rules the preset plays with, `None` meaning every rule.
+    pub fn rules(self) -> Option<Vec<String>> {
+        match self {
+            Difficulty::Casual => Some(
+                [
+                    "min_length",
+                    "contains_letter",
+                    "starts_with_letter",
+                    "ends_with_letter",
+                    "not_contains_letter",
+                ]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            ),
+            Difficulty::Standard | Difficulty::Hardcore => None,
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "casual" => Ok(Difficulty::Casual),
+            "standard" => Ok(Difficulty::Standard),
+            "hardcore" => Ok(Difficulty::Hardcore),
+            other => Err(format!("Unknown Difficulty: {}", other)),
+        }
+    }
+}
+
+impl LobbySettings {
+    pub fn to_redis_fields(&self) -> Vec<(String, String)> {
+        let mut fields = Vec::new();
+        if let Some(max_len) = self.queue_max_len {
+            fields.push(("queue_max_len".into(), max_len.to_string()));
+        }
+        if let Some(window) = self.replay_window_secs {
+            fields.push(("replay_window_secs".into(), window.to_string()));
+        }
+        if self.family_friendly {
+            fields.push(("family_friendly".into(), "true".into()));
+        }
+        if let Some(pack) = &self.dictionary_pack {
+            fields.push(("dictionary_pack".into(), pack.clone()));
+        }
+        if let Some(language) = self.language {
+            fields.push(("language".into(), language.as_str().into()));
+        }
+        if let Some(difficulty) = self.difficulty {
+            fields.push((
+                "difficulty".into(),
+                format!("{difficulty:?}").to_lowercase(),
+            ));
+        }
+        if let Some(rules) = &self.rules {
+            fields.push(("rules".into(), rules.join(",")));
+        }
+        if self.ready_check {
+            fields.push(("ready_check".into(), "true".into()));
+        }
+        if let Some(quorum) = self.ready_quorum {
+            fields.push(("ready_quorum".into(), quorum.to_string()));
+        }
+        if let Some(json) = self
+            .prize_split
+            .as_ref()
+            .and_then(|split| serde_json::to_string(split).ok())
+        {
+            fields.push(("prize_split".into(), json));
+        }
+        if let Some(best_of) = self.best_of {
+            fields.push(("best_of".into(), best_of.to_string()));
+        }
+        if let Some(window) = self.bet_window_secs {
+            fields.push(("bet_window_secs".into(), window.to_string()));
+        }
+        if let Some(bank) = self.time_bank_secs {
+            fields.push(("time_bank_secs".into(), bank.to_string()));
+        }
+        if self.casual {
+            fields.push(("casual".into(), "true".into()));
+        }
+        if let Some(max_secs) = self.max_game_secs {
+            fields.push(("max_game_secs".into(), max_secs.to_string()));
+        }
+        fields
+    }
+
+    pub fn from_redis_hash(map: &HashMap<String, String>) -> Self {
+        Self {
+            queue_max_len: map.get("queue_max_len").and_then(|s| s.parse().ok()),
+            replay_window_secs: map.get("replay_window_secs").and_then(|s| s.parse().ok()),
+            family_friendly: map
+                .get("family_friendly")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(false),
+            dictionary_pack: map.get("dictionary_pack").cloned(),
+            language: map.get("language").and_then(|s| s.parse().ok()),
+            difficulty: map.get("difficulty").and_then(|s| s.parse().ok()),
+            rules: map
+                .get("rules")
+                .map(|s| s.split(',').map(String::from).collect()),
+            ready_check: map
+                .get("ready_check")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(false),
+            ready_quorum: map.get("ready_quorum").and_then(|s| s.parse().ok()),
+            prize_split: map
+                .get("prize_split")
+                .and_then(|s| serde_json::from_str(s).ok()),
+            best_of: map.get("best_of").and_then(|s| s.parse().ok()),
+            bet_window_secs: map.get("bet_window_secs").and_then(|s| s.parse().ok()),
+            time_bank_secs: map.get("time_bank_secs").and_then(|s| s.parse().ok()),
+            casual: map
+                .get("casual")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(false),
+            max_game_secs: map.get("max_game_secs").and_then(|s| s.parse().ok()),
+        }
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty.unwrap_or_default()
+    }
+
+    pub fn language(&self) -> Language {
+        self.language.unwrap_or_default()
+    }
+
+    pub fn best_of(&self) -> u8 {
+        self.best_of.unwrap_or(3)
+    }
+
+    pub fn bet_window_secs(&self) -> u64 {
+        // Bets move wars points, which casual lobbies leave alone
+        if self.casual {
+            return 0;
+        }
+        self.bet_window_secs.unwrap_or(60).min(MAX_BET_WINDOW_SECS)
+    }
+
+    /// Acks needed from `joined` players to pass the ready-check.
+    pub fn ready_required(&self, joined: usize) -> usize {
+        let quorum = self.ready_quorum.unwrap_or(100).clamp(1, 100) as usize;
+        (joined * quorum).div_ceil(100).max(1)
+    }
+
+    /// Rules in play, `None` meaning every rule.
+    pub fn enabled_rules(&self) -> Option<Vec<String>> {
+        self.rules.clone().or_else(|| self.difficulty().rules())
+    }
 }
 
 impl LobbyInfo {
+    /// Symbol of the pool token, falling back to the legacy field and then STX.
+    pub fn pool_symbol(&self) -> &str {
+        self.asset
+            .as_ref()
+            .map(PoolAsset::symbol)
+            .or(self.token_symbol.as_deref())
+            .unwrap_or("STX")
+    }
+
     pub fn to_redis_hash(&self) -> Vec<(String, String)> {
         let mut fields = vec![
             ("id".into(), self.id.to_string()),
@@ -343,12 +995,23 @@ impl LobbyInfo {
         if let Some(token_id) = &self.token_id {
             fields.push(("token_id".into(), token_id.clone()));
         }
+        if let Some(json) = self
+            .asset
+            .as_ref()
+            .and_then(|asset| serde_json::to_string(asset).ok())
+        {
+            fields.push(("asset".into(), json));
+        }
         if let Some(creator_last_ping) = self.creator_last_ping {
             fields.push(("creator_last_ping".into(), creator_last_ping.to_string()));
         }
         if let Some(tg_msg_id) = self.tg_msg_id {
             fields.push(("tg_msg_id".into(), tg_msg_id.to_string()));
         }
+        if let Some(tg_chat_id) = self.tg_chat_id {
+            fields.push(("tg_chat_id".into(), tg_chat_id.to_string()));
+        }
+        fields.extend(self.settings.to_redis_fields());
         fields
     }
 
@@ -374,6 +1037,9 @@ impl LobbyInfo {
             wars_point: 0.0,
             username: None,
             display_name: None,
+            avatar_url: None,
+            bio: None,
+            deleted: false,
         };
 
         let placeholder_game = GameType {
@@ -418,26 +1084,108 @@ impl LobbyInfo {
             current_amount: map.get("current_amount").and_then(|s| s.parse().ok()),
             token_symbol: map.get("token_symbol").cloned(),
             token_id: map.get("token_id").cloned(),
+            asset: map.get("asset").and_then(|s| serde_json::from_str(s).ok()),
             creator_last_ping: map.get("creator_last_ping").and_then(|s| s.parse().ok()),
             tg_msg_id: map.get("tg_msg_id").and_then(|s| s.parse().ok()),
+            tg_chat_id: map.get("tg_chat_id").and_then(|s| s.parse().ok()),
+            settings: LobbySettings::from_redis_hash(map),
+            archived: map.contains_key("archived_at"),
         };
 
         Ok((lobby, creator_id, game_id))
     }
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToSchema)]
 pub struct LobbyExtended {
     pub lobby: LobbyInfo,
     pub players: Vec<Player>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct LobbyQuery {
     pub lobby_state: Option<String>,
     pub player_state: Option<String>,
     pub page: Option<u32>,
     pub limit: Option<u32>,
+    /// `nextCursor` of the previous page, continues after it instead of `page`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LobbySort {
+    #[default]
+    Newest,
+    BiggestPool,
+    Fullest,
+}
+
+/// Query of the lobby list endpoints
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LobbySearchQuery {
+    pub lobby_state: Option<String>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    /// `nextCursor` of the previous page, continues after it instead of `page`
+    pub cursor: Option<String>,
+    /// Lobbies with a word in their name starting with each word searched
+    pub search: Option<String>,
+    /// Username, wallet address or id of the lobby creator
+    pub creator: Option<String>,
+    pub min_entry: Option<f64>,
+    pub max_entry: Option<f64>,
+    pub has_pool: Option<bool>,
+    /// Only casual lobbies when true, none of them when false
+    pub casual: Option<bool>,
+    pub min_players: Option<usize>,
+    pub max_players: Option<usize>,
+    #[serde(default)]
+    #[param(inline)]
+    pub sort: LobbySort,
+}
+
+/// Search and filters for a lobby list, answered from the lobby indexes
+#[derive(Debug, Clone, Default)]
+pub struct LobbySearch {
+    pub name_words: Vec<String>,
+    pub creator_id: Option<Uuid>,
+    pub min_entry: Option<f64>,
+    pub max_entry: Option<f64>,
+    pub has_pool: Option<bool>,
+    pub casual: Option<bool>,
+    pub min_players: Option<usize>,
+    pub max_players: Option<usize>,
+    pub sort: LobbySort,
+}
+
+impl LobbySearch {
+    /// Lowercased words of a lobby name or search text, without duplicates
+    pub fn name_words(text: &str) -> Vec<String> {
+        let mut words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        words.sort();
+        words.dedup();
+        words
+    }
+
+    /// True when the plain lobby list answers it
+    pub fn is_empty(&self) -> bool {
+        self.name_words.is_empty()
+            && self.creator_id.is_none()
+            && self.min_entry.is_none()
+            && self.max_entry.is_none()
+            && self.has_pool.is_none()
+            && self.casual.is_none()
+            && self.min_players.is_none()
+            && self.max_players.is_none()
+            && self.sort == LobbySort::Newest
+    }
 }
 
 pub fn parse_lobby_states(state_param: Option<String>) -> Option<Vec<LobbyState>> {
@@ -462,7 +1210,8 @@ pub fn parse_lobby_states(state_param: Option<String>) -> Option<Vec<LobbyState>
         .filter(|states: &Vec<LobbyState>| !states.is_empty())
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PlayerQuery {
     pub player_state: Option<String>,
 }
@@ -477,3 +1226,192 @@ pub fn parse_player_state(param: Option<String>) -> Option<PlayerState> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(decimals: u8) -> PoolAsset {
+        PoolAsset::Sip010 {
+            asset_id: "SP000000000000000000002Q6VF78.token::token".into(),
+            symbol: "TKN".into(),
+            decimals,
+        }
+    }
+
+    #[test]
+    fn base_units_keep_every_decimal() {
+        assert_eq!(PoolAsset::Stx.to_base_units(0.2), Some(200_000));
+        assert_eq!(PoolAsset::Stx.to_base_units(0.1 + 0.2), Some(300_000));
+        assert_eq!(PoolAsset::Sbtc.to_base_units(0.00012345), Some(12_345));
+        assert_eq!(
+            token(18).to_base_units(1.5),
+            Some(1_500_000_000_000_000_000)
+        );
+        assert_eq!(
+            token(18).to_base_units(12345.678),
+            Some(12_345_678_000_000_000_000_000)
+        );
+        assert_eq!(token(0).to_base_units(7.0), Some(7));
+    }
+
+    #[test]
+    fn base_units_round_extra_decimals() {
+        assert_eq!(token(2).to_base_units(1.005), Some(101));
+        assert_eq!(token(2).to_base_units(1.004), Some(100));
+    }
+
+    #[test]
+    fn base_units_reject_invalid_amounts() {
+        assert_eq!(PoolAsset::Stx.to_base_units(-1.0), None);
+        assert_eq!(PoolAsset::Stx.to_base_units(f64::NAN), None);
+        assert_eq!(PoolAsset::Stx.to_base_units(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn lobby_state_transitions() {
+        use LobbyState::*;
+
+        let cases = [
+            (Waiting, Waiting, false),
+            (Waiting, Starting, true),
+            (Waiting, InProgress, true),
+            (Waiting, Finished, true),
+            (Starting, Waiting, true),
+            (Starting, Starting, false),
+            (Starting, InProgress, true),
+            (Starting, Finished, true),
+            (InProgress, Waiting, true),
+            (InProgress, Starting, false),
+            (InProgress, InProgress, false),
+            (InProgress, Finished, true),
+            (Finished, Waiting, false),
+            (Finished, Starting, false),
+            (Finished, InProgress, false),
+            (Finished, Finished, false),
+        ];
+        for (from, to, allowed) in cases {
+            assert_eq!(from.can_transition_to(&to), allowed, "{from:?} -> {to:?}");
+        }
+    }
+
+    #[test]
+    fn claim_state_transitions() {
+        let pending = ClaimState::PendingVerification;
+        let claimable = ClaimState::Claimable;
+        let claimed = ClaimState::Claimed {
+            tx_id: "0xclaim".into(),
+        };
+        let disputed = ClaimState::Disputed {
+            reason: "not paid".into(),
+            tx_id: None,
+        };
+        let refunded = ClaimState::Refunded {
+            tx_id: "0xrefund".into(),
+        };
+
+        let allowed = [
+            (&pending, &claimable),
+            (&claimable, &claimed),
+            (&pending, &disputed),
+            (&claimable, &disputed),
+            (&claimed, &disputed),
+            (&disputed, &pending),
+            (&disputed, &claimable),
+            (&disputed, &claimed),
+            (&disputed, &refunded),
+        ];
+        for (from, to) in allowed {
+            assert!(from.can_transition_to(to), "{from:?} -> {to:?}");
+        }
+
+        let rejected = [
+            (&pending, &claimed),
+            (&pending, &refunded),
+            (&claimable, &pending),
+            (&claimable, &refunded),
+            (&claimed, &claimable),
+            (&claimed, &refunded),
+            (&disputed, &disputed),
+            (&refunded, &claimable),
+            (&refunded, &claimed),
+            (&refunded, &disputed),
+        ];
+        for (from, to) in rejected {
+            assert!(!from.can_transition_to(to), "{from:?} -> {to:?}");
+        }
+    }
+
+    #[test]
+    fn prize_split_percentages() {
+        assert_eq!(PrizeSplit::Standard.percentages(2), vec![70.0, 30.0]);
+        assert_eq!(PrizeSplit::Standard.percentages(5), vec![50.0, 30.0, 20.0]);
+        assert_eq!(PrizeSplit::WinnerTakesAll.percentages(4), vec![100.0]);
+        assert_eq!(
+            PrizeSplit::Equal { places: 4 }.percentages(4),
+            vec![25.0; 4]
+        );
+        // Never more places than players
+        assert_eq!(
+            PrizeSplit::Equal { places: 5 }.percentages(2),
+            vec![50.0; 2]
+        );
+        assert_eq!(
+            PrizeSplit::Custom {
+                percentages: vec![60, 25, 15]
+            }
+            .percentages(3),
+            vec![60.0, 25.0, 15.0]
+        );
+
+        for players in 2..=10 {
+            for split in [
+                PrizeSplit::Standard,
+                PrizeSplit::WinnerTakesAll,
+                PrizeSplit::Equal { places: 3 },
+            ] {
+                let total: f64 = split.percentages(players).iter().sum();
+                assert!((total - 100.0).abs() < 1e-9, "{split:?} with {players}");
+            }
+        }
+    }
+
+    #[test]
+    fn prize_split_validation() {
+        let valid = [
+            PrizeSplit::Standard,
+            PrizeSplit::WinnerTakesAll,
+            PrizeSplit::Equal { places: 1 },
+            PrizeSplit::Equal { places: 10 },
+            PrizeSplit::Custom {
+                percentages: vec![100],
+            },
+            PrizeSplit::Custom {
+                percentages: vec![50, 30, 20],
+            },
+            PrizeSplit::Custom {
+                percentages: vec![10; 10],
+            },
+        ];
+        for split in valid {
+            assert!(split.validate().is_ok(), "{split:?}");
+        }
+
+        let invalid = [
+            PrizeSplit::Equal { places: 0 },
+            PrizeSplit::Equal { places: 11 },
+            PrizeSplit::Custom {
+                percentages: vec![],
+            },
+            PrizeSplit::Custom {
+                percentages: vec![60, 50],
+            },
+            PrizeSplit::Custom {
+                percentages: vec![5; 11],
+            },
+        ];
+        for split in invalid {
+            assert!(split.validate().is_err(), "{split:?}");
+        }
+    }
+}
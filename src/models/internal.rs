@@ -0,0 +1,48 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    games::lexi_wars::rules::RuleContext,
+    models::game::{LobbyInfo, Player},
+};
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GameStateSnapshot {
+    pub game_started: bool,
+    pub current_turn: Option<Uuid>,
+    pub current_rule: Option<String>,
+    pub rule_index: Option<usize>,
+    pub rule_context: Option<RuleContext>,
+    pub current_players: Vec<Uuid>,
+    pub eliminated_players: Vec<Uuid>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyConnectionCounts {
+    /// Players tracked as connected in redis for this lobby
+    pub connected_players: usize,
+    /// Of those, players with a live game socket on this instance
+    pub live_sockets: usize,
+    pub live_chat_sockets: usize,
+    pub spectators: usize,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyIntrospection {
+    pub lobby: LobbyInfo,
+    pub players: Vec<Player>,
+    pub spectators: Vec<Uuid>,
+    pub game: GameStateSnapshot,
+    pub connections: LobbyConnectionCounts,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionCounts {
+    pub game_sockets: usize,
+    pub chat_sockets: usize,
+}
@@ -1,7 +1,10 @@
 use crate::models::User;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LeaderBoard {
     pub user: User,
@@ -11,3 +14,170 @@ pub struct LeaderBoard {
     pub total_wins: u64,
     pub pnl: f64,
 }
+
+/// Ranked ladder tier, promoted or demoted as the ladder rating moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LadderTier {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    Diamond,
+}
+
+impl LadderTier {
+    pub fn from_rating(rating: f64) -> Self {
+        match rating {
+            r if r >= 1000.0 => LadderTier::Diamond,
+            r if r >= 500.0 => LadderTier::Platinum,
+            r if r >= 250.0 => LadderTier::Gold,
+            r if r >= 100.0 => LadderTier::Silver,
+            _ => LadderTier::Bronze,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LadderTier::Bronze => "Bronze",
+            LadderTier::Silver => "Silver",
+            LadderTier::Gold => "Gold",
+            LadderTier::Platinum => "Platinum",
+            LadderTier::Diamond => "Diamond",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LadderEntry {
+    pub user: User,
+    pub rating: f64,
+    pub tier: LadderTier,
+    pub rank: u64,
+}
+
+/// One finished game in a user's match history
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchRecord {
+    pub lobby_id: Uuid,
+    pub game_id: Uuid,
+    pub game_name: String,
+    pub rank: usize,
+    pub players: usize,
+    pub prize: Option<f64>,
+    pub wars_point: f64,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// A user's totals in one game
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GameBreakdown {
+    pub game_id: Uuid,
+    pub game_name: String,
+    pub games: u64,
+    pub wins: u64,
+    pub average_rank: f64,
+    pub total_prize: f64,
+    pub wars_point: f64,
+}
+
+/// How a user did over their last games
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentForm {
+    pub games: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    pub average_rank: f64,
+    pub wars_point: f64,
+    /// Rank in each of those games, oldest first
+    pub ranks: Vec<usize>,
+}
+
+/// Best results found in the stored match history
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Achievements {
+    pub longest_win_streak: usize,
+    pub biggest_prize: Option<MatchRecord>,
+    pub most_wars_points: Option<MatchRecord>,
+}
+
+impl RecentForm {
+    /// Form over `history`, given newest first.
+    pub fn from_history(history: &[MatchRecord]) -> Self {
+        if history.is_empty() {
+            return Self::default();
+        }
+
+        let games = history.len();
+        let wins = history.iter().filter(|record| record.rank == 1).count();
+        let rank_sum: usize = history.iter().map(|record| record.rank).sum();
+
+        Self {
+            games,
+            wins,
+            win_rate: (wins as f64 / games as f64) * 100.0,
+            average_rank: rank_sum as f64 / games as f64,
+            wars_point: history.iter().map(|record| record.wars_point).sum(),
+            ranks: history.iter().rev().map(|record| record.rank).collect(),
+        }
+    }
+}
+
+impl Achievements {
+    /// Best results in `history`, given newest first.
+    pub fn from_history(history: &[MatchRecord]) -> Self {
+        let mut longest_win_streak = 0;
+        let mut streak = 0;
+        for record in history {
+            streak = if record.rank == 1 { streak + 1 } else { 0 };
+            longest_win_streak = longest_win_streak.max(streak);
+        }
+
+        Self {
+            longest_win_streak,
+            biggest_prize: history
+                .iter()
+                .filter(|record| record.prize.is_some_and(|prize| prize > 0.0))
+                .max_by(|a, b| a.prize.unwrap_or(0.0).total_cmp(&b.prize.unwrap_or(0.0)))
+                .cloned(),
+            most_wars_points: history
+                .iter()
+                .max_by(|a, b| a.wars_point.total_cmp(&b.wars_point))
+                .cloned(),
+        }
+    }
+}
+
+/// One player's side of a head-to-head
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RivalStats {
+    pub user: User,
+    /// Meetings this player finished ahead of the other
+    pub wins: usize,
+    pub average_rank: f64,
+}
+
+/// How two players fared in the games they both played
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadToHead {
+    pub meetings: usize,
+    pub a: RivalStats,
+    pub b: RivalStats,
+    pub last_met: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserStats {
+    pub overview: LeaderBoard,
+    pub games: Vec<GameBreakdown>,
+    pub recent_form: RecentForm,
+    pub achievements: Achievements,
+}
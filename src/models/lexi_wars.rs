@@ -1,17 +1,180 @@
-use crate::models::game::Player;
+use crate::{
+    config,
+    models::{
+        bet::{BetSettlement, SpectatorBet},
+        error_code::ErrorCode,
+        game::{Language, LobbyInfo, PauseReason, Player, Reaction},
+        lobby::LobbyRefund,
+        queue::QueuePolicy,
+    },
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum LexiWarsClientMessage {
-    WordEntry { word: String },
-    Ping { ts: u64 },
+    /// `action_id` lets a retried entry be applied only once
+    #[serde(rename_all = "camelCase")]
+    WordEntry {
+        word: String,
+        action_id: Option<String>,
+    },
+    Ping {
+        ts: u64,
+    },
+    /// Confirms receipt of the player's rank, prize and wars point
+    ResultAck,
+    /// Quick reaction, also accepted from spectators
+    Reaction {
+        emoji: Reaction,
+    },
+    /// Spectators only: stakes wars points on a player winning
+    #[serde(rename_all = "camelCase")]
+    PlaceBet {
+        winner_id: Uuid,
+        amount: f64,
+    },
+    /// Flags a word rejected as invalid for an admin to review
+    ReportWord {
+        word: String,
+    },
+    /// Votes to skip the current player, once half their turn is gone
+    VoteSkip,
+    /// Lobby creator only
+    PauseGame,
+    /// Lobby creator only, also ends a pause caused by disconnects
+    ResumeGame,
+}
+
+/// Why a game ended before a single player was left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GameEndReason {
+    /// The lobby's `maxGameSecs` ran out; players still in are ranked by words played
+    TimeLimitReached,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct PlayerStanding {
     pub player: Player,
+    /// Drawn players share a rank and split its prizes, the next rank
+    /// skipping the places they cover
     pub rank: usize,
+    #[serde(default)]
+    pub score: u64,
+    #[serde(default)]
+    pub used_words: Vec<String>,
+    #[serde(default)]
+    pub highlights: WordHighlights,
+}
+
+/// Standout words from a player's game
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct WordHighlights {
+    pub longest: Option<String>,
+    pub rarest: Option<String>,
+}
+
+/// A player's words for the post-game review
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerWords {
+    pub player: Player,
+    pub words: Vec<String>,
+    pub highlights: WordHighlights,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentWord {
+    pub word: String,
+    pub player_id: Uuid,
+    pub timestamp: i64,
+}
+
+/// Point-in-time view of a running game for observers.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveGameSnapshot {
+    pub lobby: LobbyInfo,
+    pub game_started: bool,
+    pub current_turn: Option<Player>,
+    pub current_rule: Option<String>,
+    /// Milliseconds left on the current turn
+    pub turn_remaining_ms: Option<i64>,
+    pub remaining_players: Vec<Player>,
+    pub eliminated_players: Vec<Uuid>,
+    pub scores: HashMap<Uuid, u64>,
+    /// Newest first
+    pub recent_words: Vec<RecentWord>,
+}
+
+/// How well a practice bot plays
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum BotSkill {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl BotSkill {
+    const ALL: [Self; 3] = [Self::Easy, Self::Medium, Self::Hard];
+
+    /// Every skill plays as its own fixed account, so bots are recognized
+    /// by id alone
+    pub const fn user_id(self) -> Uuid {
+        match self {
+            Self::Easy => Uuid::from_u128(0x00000000_0000_4000_8000_00000000b071),
+            Self::Medium => Uuid::from_u128(0x00000000_0000_4000_8000_00000000b072),
+            Self::Hard => Uuid::from_u128(0x00000000_0000_4000_8000_00000000b073),
+        }
+    }
+
+    pub fn from_user_id(user_id: Uuid) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|skill| skill.user_id() == user_id)
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Easy => "Lexi Bot (Easy)",
+            Self::Medium => "Lexi Bot (Medium)",
+            Self::Hard => "Lexi Bot (Hard)",
+        }
+    }
+
+    /// Delay before answering when the lobby creator doesn't pick one
+    pub fn default_response_delay_ms(self) -> u64 {
+        match self {
+            Self::Easy => 6_000,
+            Self::Medium => 4_000,
+            Self::Hard => 2_500,
+        }
+    }
+
+    /// Chance of blanking on a turn and running out of time
+    pub fn miss_chance(self) -> f64 {
+        match self {
+            Self::Easy => 0.15,
+            Self::Medium => 0.07,
+            Self::Hard => 0.02,
+        }
+    }
+}
+
+/// A bot filling a slot in a free lobby
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PracticeBot {
+    pub user_id: Uuid,
+    pub skill: BotSkill,
+    pub response_delay_ms: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,10 +183,15 @@ pub enum LexiWarsServerMessage {
     #[serde(rename_all = "camelCase")]
     Turn {
         current_turn: Player,
+        /// Seconds left on the turn; what is left of the player's time bank
+        /// in a time-bank lobby
         countdown: u64,
     },
+    /// The English rule description, tagged with the lobby's language so the
+    /// client can show a localized version
     Rule {
         rule: String,
+        language: Language,
     },
     Countdown {
         time: u64,
@@ -32,7 +200,13 @@ pub enum LexiWarsServerMessage {
         rank: String,
     },
     Validate {
-        msg: String,
+        message: String,
+        code: ErrorCode,
+    },
+    /// The entry or move sent with this id was applied
+    #[serde(rename_all = "camelCase")]
+    ActionAck {
+        action_id: String,
     },
     WordEntry {
         word: String,
@@ -41,12 +215,21 @@ pub enum LexiWarsServerMessage {
     UsedWord {
         word: String,
     },
-    GameOver,
+    WordReported {
+        word: String,
+    },
+    GameOver {
+        /// Set when the game didn't end with the last player standing
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<GameEndReason>,
+    },
     FinalStanding {
         standing: Vec<PlayerStanding>,
     },
+    #[serde(rename_all = "camelCase")]
     Prize {
         amount: f64,
+        token_symbol: String,
     },
     #[serde(rename_all = "camelCase")]
     WarsPoint {
@@ -67,6 +250,73 @@ pub enum LexiWarsServerMessage {
         connected_players: usize,
         remaining_players: usize,
     },
+    Error {
+        code: ErrorCode,
+        message: String,
+    },
+    /// Sent to everyone after each accepted word
+    #[serde(rename_all = "camelCase")]
+    ScoreUpdate {
+        player_id: Uuid,
+        word: String,
+        points: u64,
+        total_score: u64,
+    },
+    /// Someone started watching the game
+    #[serde(rename_all = "camelCase")]
+    SpectatorJoined {
+        user_id: Uuid,
+        spectator_count: usize,
+    },
+    #[serde(rename_all = "camelCase")]
+    SpectatorLeft {
+        user_id: Uuid,
+        spectator_count: usize,
+    },
+    /// Sent to a player whose submission looked automated
+    #[serde(rename_all = "camelCase")]
+    SuspicionWarning {
+        reason: String,
+        suspicion_score: u64,
+        threshold: u64,
+    },
+    /// Status of the player's entry refund after the game was canceled
+    Refund {
+        refund: LobbyRefund,
+    },
+    #[serde(rename_all = "camelCase")]
+    Reaction {
+        user_id: Uuid,
+        emoji: Reaction,
+    },
+    /// A spectator's bet, shown to the other spectators
+    BetPlaced {
+        bet: SpectatorBet,
+    },
+    /// The bettor's payout once the game is over
+    BetSettled {
+        settlement: BetSettlement,
+    },
+    /// Skip votes cast against the current player so far
+    #[serde(rename_all = "camelCase")]
+    SkipVote {
+        player_id: Uuid,
+        votes: usize,
+        required: usize,
+    },
+    /// The player was voted out of their turn; a second skip eliminates them
+    #[serde(rename_all = "camelCase")]
+    TurnSkipped {
+        player_id: Uuid,
+    },
+    /// Turn timers are frozen until the game resumes, at `resumesAt` at the latest
+    #[serde(rename_all = "camelCase")]
+    GamePaused {
+        reason: PauseReason,
+        paused_at: i64,
+        resumes_at: i64,
+    },
+    GameResumed,
 }
 
 impl LexiWarsServerMessage {
@@ -75,16 +325,22 @@ impl LexiWarsServerMessage {
             // Time-sensitive messages that should NOT be queued
             LexiWarsServerMessage::Countdown { .. } => false,
             LexiWarsServerMessage::Pong { .. } => false,
+            LexiWarsServerMessage::Reaction { .. } => false,
+            LexiWarsServerMessage::BetPlaced { .. } => false,
             LexiWarsServerMessage::Start { started: false, .. } => false,
-            LexiWarsServerMessage::Turn { .. } => false,
-            LexiWarsServerMessage::Rule { .. } => false,
+            LexiWarsServerMessage::SpectatorJoined { .. } => false,
+            LexiWarsServerMessage::SpectatorLeft { .. } => false,
+            LexiWarsServerMessage::SkipVote { .. } => false,
 
             // Important messages that SHOULD be queued
             LexiWarsServerMessage::Rank { .. } => true,
             LexiWarsServerMessage::Validate { .. } => true,
+            LexiWarsServerMessage::ActionAck { .. } => true,
+            LexiWarsServerMessage::Error { .. } => true,
             LexiWarsServerMessage::WordEntry { .. } => true,
             LexiWarsServerMessage::UsedWord { .. } => true,
-            LexiWarsServerMessage::GameOver => true,
+            LexiWarsServerMessage::WordReported { .. } => true,
+            LexiWarsServerMessage::GameOver { .. } => true,
             LexiWarsServerMessage::FinalStanding { .. } => true,
             LexiWarsServerMessage::Prize { .. } => true,
             LexiWarsServerMessage::WarsPoint { .. } => true,
@@ -92,6 +348,38 @@ impl LexiWarsServerMessage {
             LexiWarsServerMessage::StartFailed => true,
             LexiWarsServerMessage::Spectator => true,
             LexiWarsServerMessage::PlayersCount { .. } => true,
+            LexiWarsServerMessage::ScoreUpdate { .. } => true,
+            LexiWarsServerMessage::SuspicionWarning { .. } => true,
+            LexiWarsServerMessage::Refund { .. } => true,
+            LexiWarsServerMessage::BetSettled { .. } => true,
+            LexiWarsServerMessage::TurnSkipped { .. } => true,
+            LexiWarsServerMessage::GamePaused { .. } => true,
+            LexiWarsServerMessage::GameResumed => true,
+
+            // Kept only as the latest copy, see queue_policy
+            LexiWarsServerMessage::Turn { .. } => true,
+            LexiWarsServerMessage::Rule { .. } => true,
+        }
+    }
+
+    /// TTL and compaction used when this message is queued
+    pub fn queue_policy(&self) -> QueuePolicy {
+        match self {
+            // Stale once the turn is over
+            LexiWarsServerMessage::Turn { .. } | LexiWarsServerMessage::Rule { .. } => {
+                QueuePolicy::latest(config::get().turn_timer_secs as i64)
+            }
+            LexiWarsServerMessage::PlayersCount { .. } => QueuePolicy::latest(60),
+            // Only the latest pause and resume matter, and no pause outlasts MAX_PAUSE_SECS
+            LexiWarsServerMessage::GamePaused { .. } | LexiWarsServerMessage::GameResumed => {
+                QueuePolicy::latest(300)
+            }
+            LexiWarsServerMessage::Validate { .. }
+            | LexiWarsServerMessage::ActionAck { .. }
+            | LexiWarsServerMessage::TurnSkipped { .. }
+            | LexiWarsServerMessage::WordReported { .. }
+            | LexiWarsServerMessage::Error { .. } => QueuePolicy::ttl(15),
+            _ => QueuePolicy::DEFAULT,
         }
     }
 }
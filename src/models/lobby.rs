@@ -1,8 +1,17 @@
-use crate::models::{
-    game::{LobbyState, Player, PlayerState},
-    user::User,
+use crate::{
+    games::schedules::Recurrence,
+    models::{
+        error_code::ErrorCode,
+        friends::LobbyInvite,
+        game::{LobbyInfo, LobbySettings, LobbyState, Player, PlayerState},
+        presence::Presence,
+        queue::QueuePolicy,
+        user::User,
+    },
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -19,21 +28,76 @@ pub struct JoinRequest {
     pub state: JoinState,
 }
 
-//#[derive(Serialize)]
-//pub struct PaginatedResponse<T> {
-//    pub data: Vec<T>,
-//    pub pagination: PaginationMeta,
-//}
-
-//#[derive(Serialize)]
-//pub struct PaginationMeta {
-//    pub page: u32,
-//    pub limit: u32,
-//    pub total_count: u32,
-//    pub total_pages: u32,
-//    pub has_next: bool,
-//    pub has_previous: bool,
-//}
+/// A free lobby opened again at every occurrence of its recurrence
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbySchedule {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub game_id: Uuid,
+    pub creator_id: Uuid,
+    #[serde(default)]
+    pub settings: LobbySettings,
+    pub recurrence: Recurrence,
+    pub next_run: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A change to the lobby list, streamed to lobby browsers over SSE
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LobbyListEvent {
+    Created {
+        lobby: Box<LobbyInfo>,
+    },
+    /// Players joined or left, or the lobby changed hands
+    Updated {
+        lobby: Box<LobbyInfo>,
+    },
+    #[serde(rename_all = "camelCase")]
+    StateChanged {
+        lobby_id: Uuid,
+        state: LobbyState,
+    },
+    #[serde(rename_all = "camelCase")]
+    Closed {
+        lobby_id: Uuid,
+    },
+    /// The client fell behind and missed events; it should refetch the list
+    Resync,
+}
+
+impl LobbyListEvent {
+    /// SSE event name, the same as the `type` field
+    pub fn name(&self) -> &'static str {
+        match self {
+            LobbyListEvent::Created { .. } => "created",
+            LobbyListEvent::Updated { .. } => "updated",
+            LobbyListEvent::StateChanged { .. } => "stateChanged",
+            LobbyListEvent::Closed { .. } => "closed",
+            LobbyListEvent::Resync => "resync",
+        }
+    }
+}
+
+/// One entry of a lobby's state history
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyStateChange {
+    pub from: LobbyState,
+    pub to: LobbyState,
+    pub at: DateTime<Utc>,
+}
+
+/// Result of a join attempt; paid joins stay `Pending` until the entry tx confirms.
+/// `Closed` means the lobby stopped waiting for players before the join landed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JoinOutcome {
+    Joined,
+    Pending,
+    Closed,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
@@ -77,6 +141,21 @@ pub enum LobbyClientMessage {
     },
 
     RequestLeave,
+
+    /// Invites a friend to this lobby
+    #[serde(rename_all = "camelCase")]
+    InviteFriend {
+        user_id: Uuid,
+    },
+
+    /// Confirms the player is present during a ready-check
+    ReadyCheckAck,
+
+    /// Hands the lobby to another joined player
+    #[serde(rename_all = "camelCase")]
+    TransferOwnership {
+        user_id: Uuid,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -118,6 +197,7 @@ pub enum LobbyServerMessage {
     Rejected,
     Pending,
     Error {
+        code: ErrorCode,
         message: String,
     },
     Pong {
@@ -136,6 +216,50 @@ pub enum LobbyServerMessage {
     IsConnectedPlayer {
         response: bool,
     },
+
+    #[serde(rename_all = "camelCase")]
+    JoinPending {
+        tx_id: String,
+    },
+
+    LobbyInvite {
+        invite: LobbyInvite,
+    },
+
+    /// A friend came online, went offline, or moved between lobbies and games
+    FriendPresence {
+        presence: Presence,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    InviteSent {
+        user_id: Uuid,
+    },
+
+    /// Players must send `ReadyCheckAck` within `timeout_secs`
+    #[serde(rename_all = "camelCase")]
+    ReadyCheck {
+        timeout_secs: u32,
+        required: usize,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    ReadyCheckUpdate {
+        ready_players: Vec<Uuid>,
+        required: usize,
+    },
+
+    /// Not enough players acked in time; the lobby is back to waiting
+    #[serde(rename_all = "camelCase")]
+    ReadyCheckFailed {
+        missing_players: Vec<Uuid>,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    OwnershipTransferred {
+        previous_owner_id: Uuid,
+        new_owner: User,
+    },
 }
 
 impl LobbyServerMessage {
@@ -145,6 +269,11 @@ impl LobbyServerMessage {
             // Time-sensitive messages that should NOT be queued
             LobbyServerMessage::Countdown { .. } => false,
             LobbyServerMessage::Pong { .. } => false,
+            // Delivered only to an open socket, possibly one for another lobby
+            LobbyServerMessage::LobbyInvite { .. } => false,
+            LobbyServerMessage::FriendPresence { .. } => false,
+            LobbyServerMessage::ReadyCheck { .. } => false,
+            LobbyServerMessage::ReadyCheckUpdate { .. } => false,
 
             // Important messages that SHOULD be queued
             LobbyServerMessage::Error { .. } => true,
@@ -160,6 +289,42 @@ impl LobbyServerMessage {
             LobbyServerMessage::Pending { .. } => true,
             LobbyServerMessage::WarsPointDeduction { .. } => true,
             LobbyServerMessage::IsConnectedPlayer { .. } => true,
+            LobbyServerMessage::JoinPending { .. } => true,
+            LobbyServerMessage::InviteSent { .. } => true,
+            LobbyServerMessage::ReadyCheckFailed { .. } => true,
+            LobbyServerMessage::OwnershipTransferred { .. } => true,
+        }
+    }
+
+    /// TTL and compaction used when this message is queued
+    pub fn queue_policy(&self) -> QueuePolicy {
+        match self {
+            LobbyServerMessage::Error { .. } => QueuePolicy::ttl(30),
+
+            // Snapshots: only the newest one matters
+            LobbyServerMessage::LobbyState { .. }
+            | LobbyServerMessage::PlayerUpdated { .. }
+            | LobbyServerMessage::PendingPlayers { .. }
+            | LobbyServerMessage::PlayersNotJoined { .. } => QueuePolicy::latest(120),
+
+            _ => QueuePolicy::DEFAULT,
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(tag = "status", content = "data", rename_all = "camelCase")]
+pub enum RefundStatus {
+    Refundable,
+    Refunded { tx_id: String },
+}
+
+/// Entry money owed back to a player after a paid lobby was canceled
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyRefund {
+    pub user_id: Uuid,
+    pub amount: f64,
+    pub token_symbol: String,
+    pub status: RefundStatus,
+}
@@ -1,9 +1,26 @@
+pub mod admin;
+pub mod audit;
+pub mod bet;
 pub mod chat;
+pub mod connect_four;
+pub mod error_code;
+pub mod friends;
 pub mod game;
+pub mod internal;
 pub mod leaderboard;
 pub mod lexi_wars;
 pub mod lobby;
+pub mod moderation;
+pub mod notification;
+pub mod pagination;
+pub mod presence;
+pub mod protocol;
+pub mod queue;
 pub mod redis;
+pub mod rps;
+pub mod telegram;
+pub mod typing_race;
 pub mod user;
+pub mod webhook;
 
 pub use user::User;
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// How chat messages containing a banned word are handled. Names are always
+/// rejected since a masked name is no use to anyone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum FilterMode {
+    /// Replace banned words with asterisks and deliver the rest
+    #[default]
+    Mask,
+    /// Refuse the message
+    Reject,
+}
+
+impl FilterMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FilterMode::Mask => "mask",
+            FilterMode::Reject => "reject",
+        }
+    }
+}
+
+impl FromStr for FilterMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mask" => Ok(FilterMode::Mask),
+            "reject" => Ok(FilterMode::Reject),
+            other => Err(format!("Unknown FilterMode: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BannedWordsConfig {
+    pub mode: FilterMode,
+    pub words: Vec<String>,
+}
+
+/// A user barred from the platform, until `expires_at` or for good when
+/// it is unset.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Ban {
+    pub user_id: Uuid,
+    pub reason: String,
+    pub issued_by: Uuid,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Ban {
+    pub fn is_active(&self) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| expires_at > Utc::now())
+    }
+}
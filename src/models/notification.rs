@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::leaderboard::LadderTier;
+
+/// Game events a user can be notified about outside the websocket.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum NotificationEvent {
+    #[serde(rename_all = "camelCase")]
+    GameStarted { lobby_id: Uuid, lobby_name: String },
+    #[serde(rename_all = "camelCase")]
+    YourTurn { lobby_id: Uuid },
+    #[serde(rename_all = "camelCase")]
+    PrizeWon {
+        lobby_id: Uuid,
+        amount: f64,
+        token_symbol: Option<String>,
+    },
+    /// Ladder tier moved after a ranked game
+    #[serde(rename_all = "camelCase")]
+    TierChanged {
+        lobby_id: Uuid,
+        from: LadderTier,
+        to: LadderTier,
+    },
+}
+
+impl NotificationEvent {
+    pub fn lobby_id(&self) -> Uuid {
+        match self {
+            NotificationEvent::GameStarted { lobby_id, .. }
+            | NotificationEvent::YourTurn { lobby_id }
+            | NotificationEvent::PrizeWon { lobby_id, .. }
+            | NotificationEvent::TierChanged { lobby_id, .. } => *lobby_id,
+        }
+    }
+
+    /// Human readable text for chat-style channels.
+    pub fn message(&self) -> String {
+        match self {
+            NotificationEvent::GameStarted { lobby_name, .. } => {
+                format!("🎮 {} has started, jump back in!", lobby_name)
+            }
+            NotificationEvent::YourTurn { .. } => "⏰ It's your turn!".to_string(),
+            NotificationEvent::PrizeWon {
+                amount,
+                token_symbol,
+                ..
+            } => format!(
+                "💰 You won {} {}",
+                amount,
+                token_symbol.as_deref().unwrap_or("STX")
+            ),
+            NotificationEvent::TierChanged { from, to, .. } if to > from => {
+                format!("📈 Promoted to {} on the ladder!", to.name())
+            }
+            NotificationEvent::TierChanged { to, .. } => {
+                format!("📉 Dropped to {} on the ladder", to.name())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPreferences {
+    /// Direct message through the bot, needs a linked Telegram account
+    pub telegram: bool,
+    pub webhook_url: Option<String>,
+
+    pub game_started: bool,
+    pub your_turn: bool,
+    pub prize_won: bool,
+    #[serde(default = "enabled")]
+    pub tier_changed: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            telegram: true,
+            webhook_url: None,
+            game_started: true,
+            your_turn: true,
+            prize_won: true,
+            tier_changed: true,
+        }
+    }
+}
+
+impl NotificationPreferences {
+    pub fn allows(&self, event: &NotificationEvent) -> bool {
+        match event {
+            NotificationEvent::GameStarted { .. } => self.game_started,
+            NotificationEvent::YourTurn { .. } => self.your_turn,
+            NotificationEvent::PrizeWon { .. } => self.prize_won,
+            NotificationEvent::TierChanged { .. } => self.tier_changed,
+        }
+    }
+
+    pub fn to_redis_hash(&self) -> Vec<(String, String)> {
+        let mut fields = vec![
+            ("telegram".to_string(), self.telegram.to_string()),
+            ("game_started".to_string(), self.game_started.to_string()),
+            ("your_turn".to_string(), self.your_turn.to_string()),
+            ("prize_won".to_string(), self.prize_won.to_string()),
+            ("tier_changed".to_string(), self.tier_changed.to_string()),
+        ];
+        if let Some(url) = &self.webhook_url {
+            fields.push(("webhook_url".to_string(), url.clone()));
+        }
+        fields
+    }
+
+    pub fn from_redis_hash(map: &HashMap<String, String>) -> Self {
+        let defaults = Self::default();
+        let flag =
+            |key: &str, default: bool| map.get(key).and_then(|v| v.parse().ok()).unwrap_or(default);
+
+        Self {
+            telegram: flag("telegram", defaults.telegram),
+            webhook_url: map.get("webhook_url").cloned(),
+            game_started: flag("game_started", defaults.game_started),
+            your_turn: flag("your_turn", defaults.your_turn),
+            prize_won: flag("prize_won", defaults.prize_won),
+            tier_changed: flag("tier_changed", defaults.tier_changed),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::errors::AppError;
+
+/// Standard envelope for paginated list endpoints.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub limit: u32,
+    pub total: u64,
+    pub has_more: bool,
+    /// Pass back as `cursor` to continue right after the last item, even
+    /// when items were added to the list in between
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Paginated<T> {
+    /// A page starting `offset` items into the list, as reached by a cursor.
+    pub fn from_offset(items: Vec<T>, offset: usize, limit: u32, total: u64) -> Self {
+        let page = (offset / (limit.max(1) as usize)) as u32 + 1;
+        let has_more = (offset as u64).saturating_add(limit as u64) < total;
+        Self {
+            items,
+            page,
+            limit,
+            total,
+            has_more,
+            next_cursor: None,
+        }
+    }
+
+    /// Hands out a cursor after `last`, the last item read, when the list goes on.
+    pub fn with_next_cursor(mut self, last: Option<PageCursor>) -> Self {
+        if self.has_more {
+            self.next_cursor = last.map(|cursor| cursor.encode());
+        }
+        self
+    }
+}
+
+/// Position in a list: the last item read and the score it was sorted by.
+/// The score places the next page when the item has left the list since.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageCursor {
+    pub id: String,
+    pub score: f64,
+}
+
+impl PageCursor {
+    pub fn new(id: impl ToString, score: f64) -> Self {
+        Self {
+            id: id.to_string(),
+            score,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.id, self.score)
+    }
+
+    pub fn decode(token: &str) -> Result<Self, AppError> {
+        token
+            .rsplit_once('_')
+            .and_then(|(id, score)| {
+                let score: f64 = score.parse().ok()?;
+                (!id.is_empty() && score.is_finite()).then(|| Self::new(id, score))
+            })
+            .ok_or_else(|| AppError::BadRequest("Invalid cursor".into()))
+    }
+
+    /// The cursor of a list query, if it passed one.
+    pub fn from_query(token: Option<&str>) -> Result<Option<Self>, AppError> {
+        token.map(Self::decode).transpose()
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Where a user is, going by the sockets they have open on any instance and
+/// the lobbies they're in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum UserStatus {
+    Offline,
+    /// Connected but in no open lobby
+    Online,
+    #[serde(rename_all = "camelCase")]
+    InLobby {
+        lobby_id: Uuid,
+    },
+    #[serde(rename_all = "camelCase")]
+    InGame {
+        lobby_id: Uuid,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Presence {
+    pub user_id: Uuid,
+    #[serde(flatten)]
+    pub status: UserStatus,
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Websocket protocol spoken on a single connection. Messages are always
+/// built in the current shape and rewritten for older clients on the way out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProtocolVersion(u32);
+
+impl ProtocolVersion {
+    /// Frontends that predate versioning and never send `protocol_version`
+    pub const LEGACY: Self = Self(1);
+    /// Adds error codes and renames Lexi Wars `validate.msg` to `message`
+    pub const CURRENT: Self = Self(2);
+
+    /// Picks the highest version both sides understand.
+    pub fn negotiate(requested: Option<u32>) -> Self {
+        match requested {
+            Some(v) => Self(v.clamp(Self::LEGACY.0, Self::CURRENT.0)),
+            None => Self::LEGACY,
+        }
+    }
+
+    pub fn number(self) -> u32 {
+        self.0
+    }
+
+    pub fn capabilities(self) -> Vec<String> {
+        let mut capabilities = Vec::new();
+        if self >= Self(2) {
+            capabilities.push("errorCodes".to_string());
+        }
+        capabilities
+    }
+
+    /// Handshake sent right after connecting. Legacy clients don't know it.
+    pub fn hello(self) -> Option<String> {
+        if self == Self::LEGACY {
+            return None;
+        }
+        serde_json::to_string(&ProtocolServerMessage::Hello {
+            protocol_version: self.0,
+            capabilities: self.capabilities(),
+        })
+        .ok()
+    }
+
+    /// Rewrites a serialized server message into the shape this version expects.
+    pub fn adapt(self, payload: &str) -> String {
+        if self >= Self::CURRENT {
+            return payload.to_string();
+        }
+
+        let Ok(mut value) = serde_json::from_str::<Value>(payload) else {
+            return payload.to_string();
+        };
+        let Some(object) = value.as_object_mut() else {
+            return payload.to_string();
+        };
+
+        object.remove("code");
+        if object.get("type").and_then(Value::as_str) == Some("validate")
+            && let Some(message) = object.remove("message")
+        {
+            object.insert("msg".to_string(), message);
+        }
+
+        serde_json::to_string(&value).unwrap_or_else(|_| payload.to_string())
+    }
+}
+
+impl Default for ProtocolVersion {
+    fn default() -> Self {
+        Self::LEGACY
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ProtocolServerMessage {
+    #[serde(rename_all = "camelCase")]
+    Hello {
+        protocol_version: u32,
+        capabilities: Vec<String>,
+    },
+}
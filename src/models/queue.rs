@@ -0,0 +1,25 @@
+/// How a server message is kept for a player who is offline when it's sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueuePolicy {
+    pub ttl_secs: i64,
+    /// Older queued messages of the same type are dropped when this one is queued.
+    pub latest_only: bool,
+}
+
+impl QueuePolicy {
+    pub const DEFAULT: Self = Self::ttl(120);
+
+    pub const fn ttl(ttl_secs: i64) -> Self {
+        Self {
+            ttl_secs,
+            latest_only: false,
+        }
+    }
+
+    pub const fn latest(ttl_secs: i64) -> Self {
+        Self {
+            ttl_secs,
+            latest_only: true,
+        }
+    }
+}
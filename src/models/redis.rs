@@ -33,10 +33,170 @@ impl RedisKey {
         "users:points".to_string()
     }
 
+    /// Ranked ladder rating, kept apart from lifetime wars points
+    pub fn ladder_rating() -> String {
+        "ladder:rating".to_string()
+    }
+
+    /// Unix seconds of each user's last ranked game
+    pub fn ladder_last_active() -> String {
+        "ladder:last_active".to_string()
+    }
+
+    /// ISO week the ladder was last rolled over in
+    pub fn ladder_week() -> String {
+        "ladder:week".to_string()
+    }
+
+    /// Lobbies the user has a player record in
+    pub fn user_lobbies(user_id: KeyPart) -> String {
+        format!("users:lobbies:{user_id}")
+    }
+
+    /// Lobbies the user currently owns, scored by creation time
+    pub fn user_created_lobbies(user_id: KeyPart) -> String {
+        format!("users:created_lobbies:{user_id}")
+    }
+
+    pub fn user_notification_prefs(user_id: KeyPart) -> String {
+        format!("users:notification_prefs:{user_id}")
+    }
+
+    pub fn user_recent_errors(user_id: KeyPart) -> String {
+        format!("users:recent_errors:{user_id}")
+    }
+
+    /// Instances holding a socket of the user, scored by when their word runs out
+    pub fn user_presence(user_id: KeyPart) -> String {
+        format!("users:presence:{user_id}")
+    }
+
+    /// Status friends were last told about, user id to `UserStatus` json
+    pub fn users_status() -> String {
+        "users:status".to_string()
+    }
+
+    pub fn user_friends(user_id: KeyPart) -> String {
+        format!("users:friends:{user_id}")
+    }
+
+    /// Friend requests other users sent to this user
+    pub fn user_friend_requests_in(user_id: KeyPart) -> String {
+        format!("users:friend_requests_in:{user_id}")
+    }
+
+    /// Friend requests this user sent that are still pending
+    pub fn user_friend_requests_out(user_id: KeyPart) -> String {
+        format!("users:friend_requests_out:{user_id}")
+    }
+
+    /// Users this user has blocked
+    pub fn user_blocked(user_id: KeyPart) -> String {
+        format!("users:blocked:{user_id}")
+    }
+
+    /// Users who have blocked this user, the reverse of `user_blocked`
+    pub fn user_blocked_by(user_id: KeyPart) -> String {
+        format!("users:blocked_by:{user_id}")
+    }
+
+    /// The user's finished games, newest first and capped
+    pub fn user_match_history(user_id: KeyPart) -> String {
+        format!("users:match_history:{user_id}")
+    }
+
+    /// Running totals per game, fields are `{game_id}:{stat}`
+    pub fn user_game_stats(user_id: KeyPart) -> String {
+        format!("users:game_stats:{user_id}")
+    }
+
+    pub fn telegram_links() -> String {
+        "telegram:links".to_string()
+    }
+
+    pub fn telegram_link_code(code: KeyPart) -> String {
+        format!("telegram:link_codes:{code}")
+    }
+
+    /// Announcement chat id per game id, and under `default` for the rest
+    pub fn telegram_routes() -> String {
+        "telegram:routes".to_string()
+    }
+
+    /// `LoginChallenge` json of a sign-in waiting for its signature
+    pub fn login_challenge(nonce: KeyPart) -> String {
+        format!("auth:challenges:{nonce}")
+    }
+
+    /// Session id -> `UserSession` json of each token the user signed in with
+    pub fn user_sessions(user_id: KeyPart) -> String {
+        format!("users:sessions:{user_id}")
+    }
+
+    /// Session id -> unix time the session was last used
+    pub fn user_session_activity(user_id: KeyPart) -> String {
+        format!("users:session_activity:{user_id}")
+    }
+
+    pub fn telegram_join_code(code: KeyPart) -> String {
+        format!("telegram:join_codes:{code}")
+    }
+
+    pub fn audit_log() -> String {
+        "audit:log".to_string()
+    }
+
+    pub fn anticheat_flags() -> String {
+        "anticheat:flags".to_string()
+    }
+
+    /// Multi-account reports, newest first
+    pub fn multi_account_reports() -> String {
+        "anticheat:multi_account".to_string()
+    }
+
+    /// Hashed addresses the user connected from
+    pub fn user_networks(user_id: KeyPart) -> String {
+        format!("users:networks:{user_id}")
+    }
+
+    /// Hashed address and user agent pairs the user connected with
+    pub fn user_devices(user_id: KeyPart) -> String {
+        format!("users:devices:{user_id}")
+    }
+
+    /// Users seen connecting from a hashed address
+    pub fn network_users(network: KeyPart) -> String {
+        format!("anticheat:networks:{network}")
+    }
+
+    /// Users seen connecting with a hashed address and user agent pair
+    pub fn device_users(device: KeyPart) -> String {
+        format!("anticheat:devices:{device}")
+    }
+
+    pub fn banned_words() -> String {
+        "moderation:banned_words".to_string()
+    }
+
+    pub fn filter_mode() -> String {
+        "moderation:filter_mode".to_string()
+    }
+
+    /// Banned users, user id to `Ban` json
+    pub fn bans() -> String {
+        "moderation:bans".to_string()
+    }
+
     pub fn game(game_id: KeyPart) -> String {
         format!("games:{game_id}:data")
     }
 
+    /// Ids of every stored game
+    pub fn games_all() -> String {
+        "games:all".to_string()
+    }
+
     pub fn game_lobbies(game_id: KeyPart) -> String {
         format!("games:{game_id}:lobbies")
     }
@@ -49,6 +209,11 @@ impl RedisKey {
         format!("lobbies:{lobby_id}:player:{player_id}")
     }
 
+    /// Ids of every player hash in the lobby, kept so reads never scan keys
+    pub fn lobby_players(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:players")
+    }
+
     pub fn lobby_connected_players(lobby_id: KeyPart) -> String {
         format!("lobbies:{lobby_id}:connected_players")
     }
@@ -61,6 +226,41 @@ impl RedisKey {
         format!("lobbies:{lobby_id}:current_players")
     }
 
+    pub fn lobby_bots(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:bots")
+    }
+
+    pub fn lobby_skip_votes(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:skip_votes")
+    }
+
+    pub fn lobby_skipped_players(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:skipped_players")
+    }
+
+    pub fn lobby_pause(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:pause")
+    }
+
+    pub fn lobby_ends_at(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:ends_at")
+    }
+
+    /// Instance running the lobby's game, expiring unless it heartbeats
+    pub fn lobby_owner(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:owner")
+    }
+
+    /// The lobby's latest state changes, oldest first
+    pub fn lobby_state_history(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:state_history")
+    }
+
+    /// Where a key of an archived lobby is kept, out of the `lobbies:` namespace
+    pub fn archived(key: &str) -> String {
+        format!("archive:{key}")
+    }
+
     pub fn lobbies_state(state: &LobbyState) -> String {
         format!("lobbies:{}:state", format!("{state:?}").to_lowercase())
     }
@@ -69,15 +269,57 @@ impl RedisKey {
         "lobbies:all".to_string()
     }
 
+    /// Lobbies scored by entry amount, 0 for free ones
+    pub fn lobbies_by_entry() -> String {
+        "lobbies:by_entry".to_string()
+    }
+
+    /// Lobbies scored by the amount currently in their pool
+    pub fn lobbies_by_pool() -> String {
+        "lobbies:by_pool".to_string()
+    }
+
+    /// Lobbies scored by joined players
+    pub fn lobbies_by_participants() -> String {
+        "lobbies:by_participants".to_string()
+    }
+
+    pub fn lobbies_casual() -> String {
+        "lobbies:casual".to_string()
+    }
+
+    /// `{word}:{lobby_id}` for every word of every lobby name, for prefix search
+    pub fn lobbies_name_index() -> String {
+        "lobbies:name_index".to_string()
+    }
+
+    pub fn lobby_telegram_users(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:telegram_users")
+    }
+
     pub fn lobby_chat(lobby_id: KeyPart) -> String {
         format!("lobbies:{lobby_id}:chats")
     }
 
+    /// Reported chat messages, message id to `ChatReport` json
+    pub fn chat_reports() -> String {
+        "chat_reports".to_string()
+    }
+
     // temporary keys
     pub fn lobby_countdown(lobby_id: KeyPart) -> String {
         format!("lobbies:{lobby_id}:countdown")
     }
 
+    /// Set while a user is muted in a lobby chat, expiring with the mute
+    pub fn lobby_chat_mute(lobby_id: KeyPart, user_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:chat_mutes:{user_id}")
+    }
+
+    pub fn lobby_ready_check(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:ready_check")
+    }
+
     pub fn lobby_used_words(lobby_id: KeyPart) -> String {
         format!("lobbies:{lobby_id}:used_words")
     }
@@ -94,6 +336,11 @@ impl RedisKey {
         format!("lobbies:{lobby_id}:current_turn")
     }
 
+    /// Player id -> milliseconds left in their time bank
+    pub fn lobby_time_banks(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:time_banks")
+    }
+
     pub fn lobby_eliminated_players(lobby_id: KeyPart) -> String {
         format!("lobbies:{lobby_id}:eliminated_players")
     }
@@ -106,10 +353,151 @@ impl RedisKey {
         format!("lobbies:{lobby_id}:current_rule")
     }
 
+    pub fn lobby_scores(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:scores")
+    }
+
+    /// Player id -> total milliseconds taken on turns they played a word in
+    pub fn lobby_response_times(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:response_times")
+    }
+
+    /// Refund records of a canceled lobby, user id to `LobbyRefund` json
+    pub fn lobby_refunds(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:refunds")
+    }
+
+    /// Marks a player's turn action as applied, expiring after the dedupe window
+    pub fn lobby_action(lobby_id: KeyPart, player_id: KeyPart, action_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:actions:{player_id}:{action_id}")
+    }
+
+    /// Connect Four grid and player order
+    pub fn lobby_board(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:board")
+    }
+
+    /// Typing race prompt, start time, end flag and each player's result
+    pub fn lobby_typing_race(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:typing_race")
+    }
+
+    /// Rock-Paper-Scissors round state: the round number and each locked move
+    pub fn lobby_rps_round(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:rps_round")
+    }
+
+    pub fn lobby_recent_words(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:recent_words")
+    }
+
+    /// Words a player had rejected as invalid, the only ones they may report
+    pub fn lobby_rejected_words(lobby_id: KeyPart, player_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:rejected_words:{player_id}")
+    }
+
+    /// Reaction counts of a game, emoji to count
+    pub fn lobby_reactions(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:reactions")
+    }
+
+    /// Set briefly after a user reacts, rate limiting their reactions
+    pub fn lobby_reaction_cooldown(lobby_id: KeyPart, user_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:reaction_cooldown:{user_id}")
+    }
+
+    /// Spectator bets of a game, user id to bet json
+    pub fn lobby_bets(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:bets")
+    }
+
+    /// Exists while spectators may still bet, expiring when bets lock
+    pub fn lobby_bets_open(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:bets_open")
+    }
+
+    pub fn lobby_suspicion(lobby_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:suspicion")
+    }
+
+    pub fn turn_deadlines() -> String {
+        "games:turn_deadlines".to_string()
+    }
+
+    pub fn turn_owners() -> String {
+        "games:turn_owners".to_string()
+    }
+
+    pub fn turn_started() -> String {
+        "games:turn_started".to_string()
+    }
+
+    pub fn interrupted_games() -> String {
+        "games:interrupted".to_string()
+    }
+
     pub fn words_set() -> String {
         "games:word_set".to_string()
     }
 
+    /// Word set of a non-English dictionary; English uses `words_set`
+    pub fn language_words_set(language: KeyPart) -> String {
+        format!("games:word_set:{language}")
+    }
+
+    /// Reported words of a language, scored by how many players reported them
+    pub fn word_reports(language: KeyPart) -> String {
+        format!("games:word_reports:{language}")
+    }
+
+    /// Players who reported a word, so each counts once
+    pub fn word_reporters(language: KeyPart, word: KeyPart) -> String {
+        format!("games:word_reports:{language}:{word}")
+    }
+
+    /// Custom Lexi Wars rules, rule name to `RuleDefinition` json
+    pub fn lexi_custom_rules() -> String {
+        "games:lexi_rules:custom".to_string()
+    }
+
+    /// Admin-set Lexi Wars rule progression, a json list of rule names
+    pub fn lexi_rule_order() -> String {
+        "games:lexi_rules:order".to_string()
+    }
+
+    pub fn offensive_words_set() -> String {
+        "games:offensive_word_set".to_string()
+    }
+
+    pub fn word_packs() -> String {
+        "games:word_packs".to_string()
+    }
+
+    pub fn word_pack(name: KeyPart) -> String {
+        format!("games:word_packs:{name}")
+    }
+
+    /// Recurring lobby schedules, schedule id to `LobbySchedule` json
+    pub fn lobby_schedules() -> String {
+        "lobby_schedules".to_string()
+    }
+
+    /// Schedule ids scored by their next run in unix millis
+    pub fn lobby_schedules_due() -> String {
+        "lobby_schedules:due".to_string()
+    }
+
+    /// Lobbies a schedule opened, newest first
+    pub fn schedule_lobbies(schedule_id: KeyPart) -> String {
+        format!("lobby_schedules:{schedule_id}:lobbies")
+    }
+
+    /// Lobby a create request with this `Idempotency-Key` made, or a marker
+    /// while the first request is still running
+    pub fn lobby_create_idempotency(user_id: KeyPart, key: KeyPart) -> String {
+        format!("idempotency:lobby_create:{user_id}:{key}")
+    }
+
     pub fn lobby_join_requests(lobby_id: KeyPart) -> String {
         format!("lobbies:{}:join_requests", lobby_id)
     }
@@ -132,10 +520,46 @@ impl RedisKey {
         format!("lobbies:{lobby_id}:missed_msgs:{player_id}")
     }
 
+    pub fn player_pending_results(lobby_id: KeyPart, player_id: KeyPart) -> String {
+        format!("lobbies:{lobby_id}:pending_results:{player_id}")
+    }
+
     pub fn player_missed_chat_msgs(lobby_id: KeyPart, player_id: KeyPart) -> String {
         format!("lobbies:{lobby_id}:missed_chat_msgs:{player_id}")
     }
 
+    /// `{lobby_id}:{user_id}` members for every claim currently in dispute
+    pub fn disputed_claims() -> String {
+        "claims:disputed".to_string()
+    }
+
+    pub fn tx_verified(tx_id: KeyPart) -> String {
+        format!("txs:verified:{tx_id}")
+    }
+
+    pub fn pending_join_txs() -> String {
+        "txs:pending_joins".to_string()
+    }
+
+    pub fn pending_join_tx(lobby_id: KeyPart, user_id: KeyPart) -> String {
+        format!("txs:pending_joins:{lobby_id}:{user_id}")
+    }
+
+    /// Integration webhooks, webhook id to `SignedWebhook` json
+    pub fn webhooks() -> String {
+        "webhooks".to_string()
+    }
+
+    /// Payloads still to be delivered, delivery id to `WebhookDelivery` json
+    pub fn webhook_deliveries() -> String {
+        "webhooks:deliveries".to_string()
+    }
+
+    /// Delivery ids scored by their next attempt in unix millis
+    pub fn webhook_deliveries_due() -> String {
+        "webhooks:deliveries:due".to_string()
+    }
+
     // Key parsing utilities
     pub fn _extract_user_id_from_user_key(key: &str) -> Option<Uuid> {
         // Parse "users:{uuid}" to extract user_id
@@ -146,6 +570,12 @@ impl RedisKey {
         }
     }
 
+    pub fn extract_game_id_from_game_key(key: &str) -> Option<Uuid> {
+        // Parse "games:{game_id}:data" to extract game_id
+        let game_id_str = key.strip_prefix("games:")?.strip_suffix(":data")?;
+        Uuid::parse_str(game_id_str).ok()
+    }
+
     pub fn extract_lobby_id_from_player_key(key: &str) -> Option<Uuid> {
         // Parse "lobbies:{lobby_id}:player:{user_id}" to extract lobby_id
         let parts: Vec<&str> = key.split(':').collect();
@@ -0,0 +1,185 @@
+use crate::models::{
+    bet::{BetSettlement, SpectatorBet},
+    error_code::ErrorCode,
+    game::Reaction,
+    lobby::LobbyRefund,
+    queue::QueuePolicy,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpsChoice {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl RpsChoice {
+    pub fn beats(self, other: RpsChoice) -> bool {
+        matches!(
+            (self, other),
+            (RpsChoice::Rock, RpsChoice::Scissors)
+                | (RpsChoice::Paper, RpsChoice::Rock)
+                | (RpsChoice::Scissors, RpsChoice::Paper)
+        )
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RpsChoice::Rock => "rock",
+            RpsChoice::Paper => "paper",
+            RpsChoice::Scissors => "scissors",
+        }
+    }
+}
+
+impl FromStr for RpsChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rock" => Ok(RpsChoice::Rock),
+            "paper" => Ok(RpsChoice::Paper),
+            "scissors" => Ok(RpsChoice::Scissors),
+            other => Err(format!("Unknown RpsChoice: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RpsClientMessage {
+    /// Locks in the player's choice for the current round
+    Move {
+        choice: RpsChoice,
+    },
+    Ping {
+        ts: u64,
+    },
+    /// Confirms receipt of the player's rank, prize and wars point
+    ResultAck,
+    /// Quick reaction, also accepted from spectators
+    Reaction {
+        emoji: Reaction,
+    },
+    /// Spectators only: stakes wars points on a player winning
+    #[serde(rename_all = "camelCase")]
+    PlaceBet {
+        winner_id: Uuid,
+        amount: f64,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RpsServerMessage {
+    Start {
+        time: u32,
+        started: bool,
+    },
+    StartFailed,
+    Spectator,
+    /// A new round is open for moves
+    #[serde(rename_all = "camelCase")]
+    Round {
+        round: u32,
+        best_of: u8,
+        countdown: u64,
+    },
+    /// A player has chosen, without revealing what
+    #[serde(rename_all = "camelCase")]
+    MoveLocked {
+        player_id: Uuid,
+    },
+    /// Both choices revealed; `winner_id` is unset on a draw
+    #[serde(rename_all = "camelCase")]
+    RoundResult {
+        round: u32,
+        moves: HashMap<Uuid, RpsChoice>,
+        winner_id: Option<Uuid>,
+        scores: HashMap<Uuid, u64>,
+    },
+    /// The match ended level, entries are refunded
+    Draw,
+    GameOver,
+    Rank {
+        rank: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Prize {
+        amount: f64,
+        token_symbol: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    WarsPoint {
+        wars_point: f64,
+    },
+    /// Status of the player's entry refund after the game was canceled
+    Refund {
+        refund: LobbyRefund,
+    },
+    Pong {
+        ts: u64,
+        pong: u64,
+    },
+    Error {
+        code: ErrorCode,
+        message: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Reaction {
+        user_id: Uuid,
+        emoji: Reaction,
+    },
+    /// A spectator's bet, shown to the other spectators
+    BetPlaced {
+        bet: SpectatorBet,
+    },
+    /// The bettor's payout once the game is over
+    BetSettled {
+        settlement: BetSettlement,
+    },
+}
+
+impl RpsServerMessage {
+    pub fn should_queue(&self) -> bool {
+        match self {
+            // Time-sensitive messages that should NOT be queued
+            RpsServerMessage::Pong { .. } => false,
+            RpsServerMessage::Reaction { .. } => false,
+            RpsServerMessage::BetPlaced { .. } => false,
+            RpsServerMessage::Start { started: false, .. } => false,
+            RpsServerMessage::MoveLocked { .. } => false,
+
+            // Important messages that SHOULD be queued
+            RpsServerMessage::Start { started: true, .. } => true,
+            RpsServerMessage::StartFailed => true,
+            RpsServerMessage::Spectator => true,
+            RpsServerMessage::RoundResult { .. } => true,
+            RpsServerMessage::Draw => true,
+            RpsServerMessage::GameOver => true,
+            RpsServerMessage::Rank { .. } => true,
+            RpsServerMessage::Prize { .. } => true,
+            RpsServerMessage::WarsPoint { .. } => true,
+            RpsServerMessage::Refund { .. } => true,
+            RpsServerMessage::BetSettled { .. } => true,
+            RpsServerMessage::Error { .. } => true,
+
+            // Kept only as the latest copy, see queue_policy
+            RpsServerMessage::Round { .. } => true,
+        }
+    }
+
+    /// TTL and compaction used when this message is queued
+    pub fn queue_policy(&self) -> QueuePolicy {
+        match self {
+            // Stale once the round is resolved
+            RpsServerMessage::Round { .. } => QueuePolicy::latest(15),
+            RpsServerMessage::Error { .. } => QueuePolicy::ttl(15),
+            _ => QueuePolicy::DEFAULT,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What a `/tg/join/{code}` deep link points at.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelegramJoinCode {
+    pub lobby_id: Uuid,
+    pub telegram_user_id: u64,
+    pub chat_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TelegramJoinLink {
+    pub lobby_id: Uuid,
+    pub join_url: String,
+}
+
+/// Which Telegram chat each game's lobbies and winners are announced in
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TelegramRoutes {
+    /// Chat for games without a route, `None` leaves them unannounced
+    pub default_chat_id: Option<i64>,
+    /// False while the default still comes from `TELEGRAM_CHAT_ID`
+    pub default_overridden: bool,
+    pub games: HashMap<Uuid, i64>,
+}
@@ -0,0 +1,161 @@
+use crate::models::{
+    bet::{BetSettlement, SpectatorBet},
+    error_code::ErrorCode,
+    game::Reaction,
+    lobby::LobbyRefund,
+    queue::QueuePolicy,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A player's scored submission.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypingResult {
+    pub score: u64,
+    pub accuracy: f64,
+    pub wpm: f64,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypingStanding {
+    pub player_id: Uuid,
+    pub rank: usize,
+    /// Unset for a player who never submitted
+    pub result: Option<TypingResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TypingRaceClientMessage {
+    /// The player's typed prompt, accepted once per race
+    Submit {
+        text: String,
+    },
+    Ping {
+        ts: u64,
+    },
+    /// Confirms receipt of the player's rank, prize and wars point
+    ResultAck,
+    /// Quick reaction, also accepted from spectators
+    Reaction {
+        emoji: Reaction,
+    },
+    /// Spectators only: stakes wars points on a player winning
+    #[serde(rename_all = "camelCase")]
+    PlaceBet {
+        winner_id: Uuid,
+        amount: f64,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TypingRaceServerMessage {
+    Start {
+        time: u32,
+        started: bool,
+    },
+    StartFailed,
+    Spectator,
+    /// The text everyone races to type, and the seconds left to submit it
+    Prompt {
+        text: String,
+        countdown: u64,
+    },
+    /// Sent to a player after their submission is scored
+    Result {
+        result: TypingResult,
+    },
+    /// Someone finished, without revealing their text
+    #[serde(rename_all = "camelCase")]
+    Submitted {
+        player_id: Uuid,
+        score: u64,
+    },
+    FinalStanding {
+        standing: Vec<TypingStanding>,
+    },
+    /// Nobody submitted, entries are refunded
+    Draw,
+    GameOver,
+    Rank {
+        rank: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Prize {
+        amount: f64,
+        token_symbol: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    WarsPoint {
+        wars_point: f64,
+    },
+    /// Status of the player's entry refund after the game was canceled
+    Refund {
+        refund: LobbyRefund,
+    },
+    Pong {
+        ts: u64,
+        pong: u64,
+    },
+    Error {
+        code: ErrorCode,
+        message: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Reaction {
+        user_id: Uuid,
+        emoji: Reaction,
+    },
+    /// A spectator's bet, shown to the other spectators
+    BetPlaced {
+        bet: SpectatorBet,
+    },
+    /// The bettor's payout once the game is over
+    BetSettled {
+        settlement: BetSettlement,
+    },
+}
+
+impl TypingRaceServerMessage {
+    pub fn should_queue(&self) -> bool {
+        match self {
+            // Time-sensitive messages that should NOT be queued
+            TypingRaceServerMessage::Pong { .. } => false,
+            TypingRaceServerMessage::Reaction { .. } => false,
+            TypingRaceServerMessage::BetPlaced { .. } => false,
+            TypingRaceServerMessage::Start { started: false, .. } => false,
+
+            // Important messages that SHOULD be queued
+            TypingRaceServerMessage::Start { started: true, .. } => true,
+            TypingRaceServerMessage::StartFailed => true,
+            TypingRaceServerMessage::Spectator => true,
+            TypingRaceServerMessage::Result { .. } => true,
+            TypingRaceServerMessage::Submitted { .. } => true,
+            TypingRaceServerMessage::FinalStanding { .. } => true,
+            TypingRaceServerMessage::Draw => true,
+            TypingRaceServerMessage::GameOver => true,
+            TypingRaceServerMessage::Rank { .. } => true,
+            TypingRaceServerMessage::Prize { .. } => true,
+            TypingRaceServerMessage::WarsPoint { .. } => true,
+            TypingRaceServerMessage::Refund { .. } => true,
+            TypingRaceServerMessage::BetSettled { .. } => true,
+            TypingRaceServerMessage::Error { .. } => true,
+
+            // Kept only as the latest copy, see queue_policy
+            TypingRaceServerMessage::Prompt { .. } => true,
+        }
+    }
+
+    /// TTL and compaction used when this message is queued
+    pub fn queue_policy(&self) -> QueuePolicy {
+        match self {
+            TypingRaceServerMessage::Prompt { .. } => QueuePolicy::latest(60),
+            TypingRaceServerMessage::Error { .. } => QueuePolicy::ttl(15),
+            _ => QueuePolicy::DEFAULT,
+        }
+    }
+}
@@ -1,9 +1,11 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::models::game::Player;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct User {
     pub id: Uuid,
@@ -12,6 +14,30 @@ pub struct User {
 
     pub username: Option<String>,
     pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub bio: Option<String>,
+
+    /// Soft-deleted users keep a tombstone profile so old games still resolve
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+impl User {
+    pub const DELETED_DISPLAY_NAME: &'static str = "Deleted player";
+
+    /// Placeholder profile for deleted or missing users referenced by old games.
+    pub fn tombstone(id: Uuid) -> Self {
+        Self {
+            id,
+            wallet_address: String::new(),
+            wars_point: 0.0,
+            username: None,
+            display_name: Some(Self::DELETED_DISPLAY_NAME.to_string()),
+            avatar_url: None,
+            bio: None,
+            deleted: true,
+        }
+    }
 }
 
 impl From<Player> for User {
@@ -25,4 +51,32 @@ pub struct Claims {
     pub sub: String,    // user ID
     pub wallet: String, // wallet address
     pub exp: usize,     // expiration time
+    /// Session the token belongs to, missing on tokens issued before sessions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<Uuid>,
+}
+
+/// A signed-in token of the user and the device it was issued to
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserSession {
+    pub id: Uuid,
+    /// User agent that signed in, when it sent one
+    pub device: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_active_at: DateTime<Utc>,
+    /// Whether this is the session of the token making the request
+    #[serde(default)]
+    pub current: bool,
+}
+
+/// Message a wallet signs to prove it owns its address, redeemable once
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginChallenge {
+    pub nonce: String,
+    pub wallet_address: String,
+    /// Exact text to sign, the nonce included
+    pub message: String,
+    pub expires_at: DateTime<Utc>,
 }
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Events an integration can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookEvent {
+    LobbyCreated,
+    GameStarted,
+    GameEnded,
+    PrizeAwarded,
+    PrizeClaimed,
+}
+
+/// An external consumer the subscribed events are POSTed to
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub description: Option<String>,
+    /// Paused webhooks keep their settings but receive nothing
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A webhook with the secret its payloads are signed with. The secret is
+/// only ever returned when the webhook is created.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedWebhook {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+/// A payload waiting to reach a webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub body: String,
+    /// Failed attempts so far
+    pub attempts: u32,
+}
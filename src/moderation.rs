@@ -0,0 +1,78 @@
+use std::collections::HashSet;
+
+use crate::{
+    db::moderation::get::get_banned_words, errors::AppError, models::moderation::FilterMode,
+    state::RedisClient,
+};
+
+/// Byte ranges of the words in `text` that are on the banned list. Words are
+/// runs of alphanumerics compared case-insensitively, so `bad_word` and
+/// `bad.word` are split the same way as `bad word`.
+fn banned_spans(text: &str, banned: &HashSet<String>) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                if banned.contains(&text[s..i].to_lowercase()) {
+                    spans.push((s, i));
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    spans
+}
+
+pub fn contains_banned(text: &str, banned: &HashSet<String>) -> bool {
+    !banned_spans(text, banned).is_empty()
+}
+
+pub fn mask_banned(text: &str, banned: &HashSet<String>) -> String {
+    let mut masked = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for (start, end) in banned_spans(text, banned) {
+        masked.push_str(&text[last..start]);
+        masked.extend(std::iter::repeat_n('*', text[start..end].chars().count()));
+        last = end;
+    }
+    masked.push_str(&text[last..]);
+
+    masked
+}
+
+/// Applies the filter to a chat message. Returns the text to deliver, or a
+/// `BadRequest` when the filter is in reject mode.
+pub async fn filter_chat_text(text: &str, redis: RedisClient) -> Result<String, AppError> {
+    let (mode, banned) = get_banned_words(redis).await?;
+
+    match mode {
+        FilterMode::Mask => Ok(mask_banned(text, &banned)),
+        FilterMode::Reject if contains_banned(text, &banned) => Err(AppError::BadRequest(
+            "Message contains a banned word".into(),
+        )),
+        FilterMode::Reject => Ok(text.to_string()),
+    }
+}
+
+/// Profile fields (names, bio) are refused outright whatever the mode.
+pub async fn check_profile_text(
+    text: &str,
+    field: &str,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let (_, banned) = get_banned_words(redis).await?;
+
+    if contains_banned(text, &banned) {
+        return Err(AppError::BadRequest(format!(
+            "{field} contains a banned word"
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+pub mod telegram;
+pub mod webhook;
+
+use teloxide::Bot;
+use uuid::Uuid;
+
+use crate::{
+    db::user::get::get_notification_preferences,
+    errors::AppError,
+    models::notification::{NotificationEvent, NotificationPreferences},
+    state::RedisClient,
+};
+
+/// A way of reaching a user outside the game websocket.
+pub enum NotificationChannel {
+    Telegram,
+    Webhook(String),
+}
+
+impl NotificationChannel {
+    /// Channels the user opted into, in delivery order.
+    pub fn from_preferences(prefs: &NotificationPreferences) -> Vec<Self> {
+        let mut channels = Vec::new();
+        if prefs.telegram {
+            channels.push(NotificationChannel::Telegram);
+        }
+        if let Some(url) = &prefs.webhook_url {
+            channels.push(NotificationChannel::Webhook(url.clone()));
+        }
+        channels
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NotificationChannel::Telegram => "telegram",
+            NotificationChannel::Webhook(_) => "webhook",
+        }
+    }
+
+    async fn deliver(
+        &self,
+        user_id: Uuid,
+        event: &NotificationEvent,
+        bot: &Bot,
+        redis: &RedisClient,
+    ) -> Result<(), AppError> {
+        match self {
+            NotificationChannel::Telegram => {
+                telegram::send(user_id, event, bot, redis.clone()).await
+            }
+            NotificationChannel::Webhook(url) => webhook::send(url, user_id, event).await,
+        }
+    }
+}
+
+/// Delivers `event` to every channel the user enabled. Runs in the background so
+/// game loops never wait on Telegram or a slow webhook.
+pub fn notify(user_id: Uuid, event: NotificationEvent, bot: Bot, redis: RedisClient) {
+    tokio::spawn(async move {
+        let prefs = match get_notification_preferences(user_id, redis.clone()).await {
+            Ok(prefs) => prefs,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to load notification preferences for {}: {}",
+                    user_id,
+                    e
+                );
+                return;
+            }
+        };
+
+        if !prefs.allows(&event) {
+            return;
+        }
+
+        for channel in NotificationChannel::from_preferences(&prefs) {
+            if let Err(e) = channel.deliver(user_id, &event, &bot, &redis).await {
+                tracing::warn!(
+                    "Failed to send {} notification to {}: {}",
+                    channel.name(),
+                    user_id,
+                    e
+                );
+            }
+        }
+    });
+}
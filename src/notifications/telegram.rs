@@ -0,0 +1,32 @@
+use teloxide::{Bot, prelude::Requester, types::ChatId};
+use uuid::Uuid;
+
+use crate::{
+    db::user::get::get_user_telegram_id, errors::AppError, models::notification::NotificationEvent,
+    state::RedisClient,
+};
+
+/// Sends a direct message through the bot. Users without a linked Telegram
+/// account are skipped silently.
+pub async fn send(
+    user_id: Uuid,
+    event: &NotificationEvent,
+    bot: &Bot,
+    redis: RedisClient,
+) -> Result<(), AppError> {
+    let Some(telegram_id) = get_user_telegram_id(user_id, redis).await? else {
+        return Ok(());
+    };
+
+    let text = format!(
+        "{}\nhttps://stackswars.com/lobby/{}",
+        event.message(),
+        event.lobby_id()
+    );
+
+    bot.send_message(ChatId(telegram_id as i64), text)
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Telegram delivery failed: {}", e)))?;
+
+    Ok(())
+}
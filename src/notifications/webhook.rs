@@ -0,0 +1,136 @@
+use reqwest::{
+    Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect::Policy,
+};
+use serde::Serialize;
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+use uuid::Uuid;
+
+use crate::{errors::AppError, models::notification::NotificationEvent};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Shared by every send. Redirects are never followed and hostnames only
+/// resolve to public addresses, so a user-supplied URL can't reach the
+/// server's own network.
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .redirect(Policy::none())
+        .dns_resolver(Arc::new(PublicResolver))
+        .build()
+        .unwrap_or_default()
+});
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload<'a> {
+    user_id: Uuid,
+    event: &'a NotificationEvent,
+}
+
+/// Resolves hostnames like the system resolver but drops every address a
+/// webhook must not reach. Checked on each connection, so a host that later
+/// starts resolving to a private address is still refused.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", name.as_str()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// False for loopback, private, link-local (cloud metadata included),
+/// shared, multicast and otherwise non-routable addresses.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // 100.64.0.0/10, carrier-grade NAT
+                || (a == 100 && (b & 0xc0) == 64)
+                // 0.0.0.0/8 and 240.0.0.0/4
+                || a == 0
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public(IpAddr::V4(v4));
+            }
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local())
+        }
+    }
+}
+
+/// Accepts only https URLs whose host resolves to public addresses.
+pub async fn validate_url(url: &str) -> Result<(), AppError> {
+    let parsed =
+        Url::parse(url).map_err(|_| AppError::BadRequest("Webhook URL is invalid".into()))?;
+    if parsed.scheme() != "https" {
+        return Err(AppError::BadRequest("Webhook URL must use https".into()));
+    }
+
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| AppError::BadRequest("Webhook URL has no host".into()))?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|_| AppError::BadRequest("Webhook host could not be resolved".into()))?
+        .collect();
+    if addrs.is_empty() || addrs.iter().any(|addr| !is_public(addr.ip())) {
+        return Err(AppError::BadRequest(
+            "Webhook URL must point to a public address".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+pub async fn send(url: &str, user_id: Uuid, event: &NotificationEvent) -> Result<(), AppError> {
+    // IP literals skip the resolver, so they're checked here
+    validate_url(url).await?;
+
+    let res = CLIENT
+        .post(url)
+        .json(&WebhookPayload { user_id, event })
+        .send()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Webhook request failed: {}", e)))?;
+
+    if !res.status().is_success() {
+        return Err(AppError::BadRequest(format!(
+            "Webhook returned status {}",
+            res.status()
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,220 @@
+//! Whether users are offline, online, waiting in a lobby or playing. Worked
+//! out again whenever one of their sockets opens or closes and when their
+//! games start or end, and pushed to their connected friends when it changes.
+//! Sockets live on several instances, so each instance records the users it
+//! holds in Redis and changes reach friends over the cluster channel.
+
+use axum::extract::ws::Message;
+use std::{collections::HashSet, sync::OnceLock, time::Duration};
+use tokio::time::{MissedTickBehavior, interval};
+use uuid::Uuid;
+
+use crate::{
+    cluster::{ClusterMessage, publish},
+    db::{
+        lobby::get::{get_lobby_players, get_user_open_lobbies},
+        user::{
+            friends::get_friend_ids,
+            presence::{
+                PRESENCE_TTL_SECS, is_user_connected, renew_user_presence, set_user_connected,
+                swap_user_status,
+            },
+        },
+    },
+    errors::AppError,
+    games::ownership::instance_id,
+    models::{
+        chat::ChatServerMessage,
+        game::LobbyState,
+        lobby::LobbyServerMessage,
+        presence::{Presence, UserStatus},
+    },
+    state::{ChatConnectionInfoMap, ConnectionInfoMap, RedisClient},
+};
+
+struct PresenceContext {
+    connections: ConnectionInfoMap,
+    chat_connections: ChatConnectionInfoMap,
+    redis: RedisClient,
+}
+
+static CONTEXT: OnceLock<PresenceContext> = OnceLock::new();
+
+/// Gives presence updates the connection maps. Called once from `start_server`.
+pub fn init(
+    connections: ConnectionInfoMap,
+    chat_connections: ChatConnectionInfoMap,
+    redis: RedisClient,
+) {
+    let context = PresenceContext {
+        connections,
+        chat_connections,
+        redis,
+    };
+    if CONTEXT.set(context).is_err() {
+        tracing::warn!("Presence already initialized");
+    }
+}
+
+pub async fn get_status(user_id: Uuid, redis: RedisClient) -> Result<UserStatus, AppError> {
+    if !is_user_connected(user_id, redis.clone()).await? {
+        return Ok(UserStatus::Offline);
+    }
+
+    let lobbies = get_user_open_lobbies(user_id, redis).await?;
+    // A game being played outranks lobbies the user is waiting in
+    if let Some((lobby_id, _)) = lobbies
+        .iter()
+        .find(|(_, state)| *state == LobbyState::InProgress)
+    {
+        return Ok(UserStatus::InGame {
+            lobby_id: *lobby_id,
+        });
+    }
+
+    Ok(match lobbies.first() {
+        Some((lobby_id, _)) => UserStatus::InLobby {
+            lobby_id: *lobby_id,
+        },
+        None => UserStatus::Online,
+    })
+}
+
+/// Works out the user's presence in the background, telling their friends
+/// if it changed.
+pub fn refresh_presence(user_id: Uuid) {
+    let Some(context) = CONTEXT.get() else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        let on_socket = context.connections.lock().await.contains_key(&user_id);
+        let on_chat_socket = context.chat_connections.lock().await.contains_key(&user_id);
+        if let Err(e) = set_user_connected(
+            user_id,
+            instance_id(),
+            on_socket || on_chat_socket,
+            context.redis.clone(),
+        )
+        .await
+        {
+            tracing::error!("Failed to record presence of {}: {}", user_id, e);
+            return;
+        }
+
+        let status = match get_status(user_id, context.redis.clone()).await {
+            Ok(status) => status,
+            Err(e) => {
+                tracing::error!("Failed to get presence of {}: {}", user_id, e);
+                return;
+            }
+        };
+
+        match swap_user_status(user_id, status, context.redis.clone()).await {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                tracing::error!("Failed to store presence of {}: {}", user_id, e);
+                return;
+            }
+        }
+
+        // Every instance pushes to the friends it holds, this one included
+        let presence = Presence { user_id, status };
+        let msg = ClusterMessage::PresenceChanged { presence };
+        if let Err(e) = publish(&msg, &context.redis).await {
+            tracing::error!("Failed to publish presence of {}: {}", user_id, e);
+            push_to_friends(presence).await;
+        }
+    });
+}
+
+/// Keeps this instance's word on who is connected from running out.
+pub async fn start_presence_heartbeat() {
+    let Some(context) = CONTEXT.get() else {
+        return;
+    };
+
+    let mut ticker = interval(Duration::from_secs(PRESENCE_TTL_SECS / 3));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        ticker.tick().await;
+
+        let mut user_ids: HashSet<Uuid> =
+            context.connections.lock().await.keys().copied().collect();
+        user_ids.extend(context.chat_connections.lock().await.keys().copied());
+        let user_ids: Vec<Uuid> = user_ids.into_iter().collect();
+
+        if let Err(e) = renew_user_presence(&user_ids, instance_id(), context.redis.clone()).await {
+            tracing::error!("Failed to renew presence: {}", e);
+        }
+    }
+}
+
+/// Refreshes everyone in the lobby, for when its game starts or ends.
+pub async fn refresh_lobby_presence(lobby_id: Uuid, redis: RedisClient) {
+    match get_lobby_players(lobby_id, None, redis).await {
+        Ok(players) => {
+            for player in players {
+                refresh_presence(player.id);
+            }
+        }
+        Err(e) => {
+            tracing::error!("Failed to get players of lobby {}: {}", lobby_id, e);
+        }
+    }
+}
+
+/// Sends a presence change to the user's friends connected to this instance.
+pub async fn push_to_friends(presence: Presence) {
+    let Some(context) = CONTEXT.get() else {
+        return;
+    };
+
+    let friend_ids = match get_friend_ids(presence.user_id, context.redis.clone()).await {
+        Ok(friend_ids) => friend_ids,
+        Err(e) => {
+            tracing::error!("Failed to get friends of {}: {}", presence.user_id, e);
+            return;
+        }
+    };
+    if friend_ids.is_empty() {
+        return;
+    }
+
+    let (Ok(lobby_msg), Ok(chat_msg)) = (
+        serde_json::to_string(&LobbyServerMessage::FriendPresence { presence }),
+        serde_json::to_string(&ChatServerMessage::FriendPresence { presence }),
+    ) else {
+        tracing::error!("Failed to serialize presence of {}", presence.user_id);
+        return;
+    };
+
+    for friend_id in friend_ids {
+        // Like lobby invites, the game socket is preferred over the chat one
+        let conn_info = context.connections.lock().await.get(&friend_id).cloned();
+        if let Some(conn_info) = conn_info {
+            if let Err(e) = conn_info.sender.send(Message::Text(
+                conn_info.protocol_version.adapt(&lobby_msg).into(),
+            )) {
+                tracing::debug!("Failed to send presence to {}: {}", friend_id, e);
+            }
+            continue;
+        }
+
+        let chat_conn_info = context
+            .chat_connections
+            .lock()
+            .await
+            .get(&friend_id)
+            .cloned();
+        if let Some(conn_info) = chat_conn_info
+            && let Err(e) = conn_info.sender.send(Message::Text(
+                conn_info.protocol_version.adapt(&chat_msg).into(),
+            ))
+        {
+            tracing::debug!("Failed to send presence to {}: {}", friend_id, e);
+        }
+    }
+}
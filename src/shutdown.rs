@@ -0,0 +1,107 @@
+use futures::future::join_all;
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        game::state::mark_games_interrupted,
+        lobby::{
+            countdown::clear_lobby_countdown, get::get_lobby_ids_by_state,
+            patch::update_lobby_state,
+        },
+    },
+    games::ownership::{owned_games, release_owned_games},
+    models::game::LobbyState,
+    state::AppState,
+    ws::lifecycle::DisconnectReason,
+};
+
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+static DRAINING: AtomicBool = AtomicBool::new(false);
+
+/// True once shutdown has started. Background tasks check this to stop
+/// acting on games that are about to be handed over to the next boot.
+pub fn is_draining() -> bool {
+    DRAINING.load(Ordering::SeqCst)
+}
+
+/// Puts games into a state the next boot can pick up, then tells every
+/// connected client the server is restarting.
+pub async fn drain(state: &AppState) {
+    DRAINING.store(true, Ordering::SeqCst);
+    tracing::info!("Draining active games before shutdown");
+
+    if tokio::time::timeout(DRAIN_TIMEOUT, drain_inner(state))
+        .await
+        .is_err()
+    {
+        tracing::warn!(
+            "Shutdown drain timed out after {}s",
+            DRAIN_TIMEOUT.as_secs()
+        );
+    }
+}
+
+async fn drain_inner(state: &AppState) {
+    // A countdown cannot finish once we are gone, so send the lobbies this
+    // instance counts down back to Waiting instead of leaving them stuck in
+    // Starting. Countdowns of instances staying up are left alone.
+    let owned = owned_games();
+    let starting: Vec<Uuid> =
+        match get_lobby_ids_by_state(LobbyState::Starting, state.redis.clone()).await {
+            Ok(lobby_ids) => lobby_ids
+                .into_iter()
+                .filter(|id| owned.contains(id))
+                .collect(),
+            Err(e) => {
+                tracing::error!("Failed to get starting lobbies: {}", e);
+                Vec::new()
+            }
+        };
+    for &lobby_id in &starting {
+        if let Err(e) = update_lobby_state(lobby_id, LobbyState::Waiting, state.redis.clone()).await
+        {
+            tracing::error!("Failed to reset starting lobby {}: {}", lobby_id, e);
+        }
+        if let Err(e) = clear_lobby_countdown(lobby_id, state.redis.clone()).await {
+            tracing::error!("Failed to clear countdown for lobby {}: {}", lobby_id, e);
+        }
+    }
+
+    // Only games this instance runs; the rest belong to instances staying up
+    let lobby_ids: Vec<Uuid> = owned
+        .into_iter()
+        .filter(|id| !starting.contains(id))
+        .collect();
+    if let Err(e) = mark_games_interrupted(&lobby_ids, state.redis.clone()).await {
+        tracing::error!("Failed to mark interrupted games: {}", e);
+    } else if !lobby_ids.is_empty() {
+        tracing::info!(
+            "Marked {} in-progress games as interrupted",
+            lobby_ids.len()
+        );
+    }
+    release_owned_games(&state.redis).await;
+
+    let senders: Vec<_> = {
+        let conns = state.connections.lock().await;
+        let chat_conns = state.chat_connections.lock().await;
+        conns
+            .values()
+            .map(|c| c.sender.clone())
+            .chain(chat_conns.values().map(|c| c.sender.clone()))
+            .collect()
+    };
+
+    tracing::info!("Closing {} websocket connections", senders.len());
+    for sender in &senders {
+        sender.close(DisconnectReason::ServerRestarting);
+    }
+
+    // Closes are only queued; wait for the writers to flush them
+    join_all(senders.iter().map(|sender| sender.closed())).await;
+}
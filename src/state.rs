@@ -1,28 +1,31 @@
-use axum::extract::ws::{Message, WebSocket};
 use bb8::Pool;
 use bb8_redis::RedisConnectionManager;
-use futures::stream::SplitSink;
 use std::{collections::HashMap, sync::Arc};
 use teloxide::Bot;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+use crate::{config::Config, models::protocol::ProtocolVersion, ws::outbound::OutboundSender};
+
 #[derive(Clone)]
 pub struct AppState {
     pub connections: ConnectionInfoMap,
     pub chat_connections: ChatConnectionInfoMap,
     pub redis: RedisClient,
     pub bot: Bot,
+    pub config: Arc<Config>,
 }
 
 #[derive(Debug)]
 pub struct ConnectionInfo {
-    pub sender: Arc<Mutex<SplitSink<WebSocket, Message>>>,
+    pub sender: OutboundSender,
+    pub protocol_version: ProtocolVersion,
 }
 
 #[derive(Debug)]
 pub struct ChatConnectionInfo {
-    pub sender: Arc<Mutex<SplitSink<WebSocket, Message>>>,
+    pub sender: OutboundSender,
+    pub protocol_version: ProtocolVersion,
 }
 
 pub type ConnectionInfoMap = Arc<Mutex<HashMap<Uuid, Arc<ConnectionInfo>>>>;
@@ -0,0 +1,233 @@
+//! Webhooks for external integrations. Events a webhook subscribed to are
+//! queued in Redis and POSTed by a worker, signed with the webhook's secret
+//! and retried with exponential backoff until they go through.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{
+    db::webhook::{
+        deliveries::{claim_due_deliveries, remove_delivery, schedule_delivery},
+        subscriptions::{get_webhook, get_webhooks},
+    },
+    errors::AppError,
+    events::GameEvent,
+    models::webhook::{WebhookDelivery, WebhookEvent},
+    state::RedisClient,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a claimed delivery is held before another instance may retry it
+const DELIVERY_LEASE_MS: i64 = 60_000;
+const DELIVERIES_PER_POLL: usize = 50;
+/// Attempts before a delivery is given up on, about 40 minutes of retries
+const MAX_DELIVERY_ATTEMPTS: u32 = 8;
+/// Wait after the first failed attempt, doubled after each one after it
+const RETRY_BASE_SECS: i64 = 10;
+
+const DELIVERY_HEADER: &str = "X-Stacks-Wars-Delivery";
+const TIMESTAMP_HEADER: &str = "X-Stacks-Wars-Timestamp";
+/// `sha256=` and the hex HMAC-SHA256 of `{timestamp}.{body}` keyed with the
+/// webhook's secret
+const SIGNATURE_HEADER: &str = "X-Stacks-Wars-Signature";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload<'a> {
+    id: Uuid,
+    event: WebhookEvent,
+    created_at: DateTime<Utc>,
+    data: &'a GameEvent,
+}
+
+/// What a webhook subscribes to for this event, `None` for internal events.
+fn webhook_event(event: &GameEvent) -> Option<WebhookEvent> {
+    match event {
+        GameEvent::LobbyCreated { .. } => Some(WebhookEvent::LobbyCreated),
+        GameEvent::GameStarted { .. } => Some(WebhookEvent::GameStarted),
+        GameEvent::GameEnded { .. } => Some(WebhookEvent::GameEnded),
+        GameEvent::PrizeAwarded { .. } => Some(WebhookEvent::PrizeAwarded),
+        GameEvent::PrizeClaimed { .. } => Some(WebhookEvent::PrizeClaimed),
+        GameEvent::LobbyStateChanged { .. }
+        | GameEvent::PlayerEliminated { .. }
+        | GameEvent::PlayerFinished { .. }
+        | GameEvent::Connection { .. } => None,
+    }
+}
+
+/// Queues the event for every active webhook subscribed to it.
+pub async fn queue_deliveries(event: GameEvent, redis: RedisClient) {
+    let Some(kind) = webhook_event(&event) else {
+        return;
+    };
+
+    let webhooks = match get_webhooks(redis.clone()).await {
+        Ok(webhooks) => webhooks,
+        Err(e) => {
+            tracing::error!("Failed to load webhooks for {:?}: {}", kind, e);
+            return;
+        }
+    };
+
+    let now = Utc::now();
+    for signed in webhooks {
+        let webhook = signed.webhook;
+        if !webhook.active || !webhook.events.contains(&kind) {
+            continue;
+        }
+
+        let id = Uuid::new_v4();
+        let payload = WebhookPayload {
+            id,
+            event: kind,
+            created_at: now,
+            data: &event,
+        };
+        let body = match serde_json::to_string(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!("Failed to serialize {:?} webhook payload: {}", kind, e);
+                return;
+            }
+        };
+
+        let delivery = WebhookDelivery {
+            id,
+            webhook_id: webhook.id,
+            body,
+            attempts: 0,
+        };
+        if let Err(e) = schedule_delivery(&delivery, now.timestamp_millis(), redis.clone()).await {
+            tracing::error!("Failed to queue webhook delivery to {}: {}", webhook.id, e);
+        }
+    }
+}
+
+/// Sends queued deliveries as they come due.
+pub async fn start_webhook_worker(redis: RedisClient) {
+    tracing::info!("Starting webhook worker");
+
+    let client = match reqwest::Client::builder().timeout(DELIVERY_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!("Failed to build webhook client: {}", e);
+            return;
+        }
+    };
+
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        let now = Utc::now().timestamp_millis();
+        let due = match claim_due_deliveries(
+            now,
+            now + DELIVERY_LEASE_MS,
+            DELIVERIES_PER_POLL,
+            redis.clone(),
+        )
+        .await
+        {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::error!("Failed to fetch due webhook deliveries: {}", e);
+                continue;
+            }
+        };
+
+        futures::future::join_all(
+            due.into_iter()
+                .map(|delivery| attempt_delivery(delivery, &client, redis.clone())),
+        )
+        .await;
+    }
+}
+
+async fn attempt_delivery(
+    mut delivery: WebhookDelivery,
+    client: &reqwest::Client,
+    redis: RedisClient,
+) {
+    let result = match get_webhook(delivery.webhook_id, redis.clone()).await {
+        Ok(signed) if signed.webhook.active => {
+            post_delivery(&delivery, &signed.webhook.url, &signed.secret, client).await
+        }
+        // Paused or deleted since the event, nobody is waiting for it
+        Ok(_) | Err(AppError::NotFound(_)) => Ok(()),
+        Err(e) => Err(e),
+    };
+
+    let outcome = match result {
+        Ok(()) => remove_delivery(delivery.id, redis).await,
+        Err(e) => {
+            delivery.attempts += 1;
+            if delivery.attempts >= MAX_DELIVERY_ATTEMPTS {
+                tracing::warn!(
+                    "Giving up on webhook delivery {} to {} after {} attempts: {}",
+                    delivery.id,
+                    delivery.webhook_id,
+                    delivery.attempts,
+                    e
+                );
+                remove_delivery(delivery.id, redis).await
+            } else {
+                let backoff_ms = RETRY_BASE_SECS * 1000 * (1 << (delivery.attempts - 1));
+                tracing::debug!(
+                    "Webhook delivery {} to {} failed, retrying in {}ms: {}",
+                    delivery.id,
+                    delivery.webhook_id,
+                    backoff_ms,
+                    e
+                );
+                let due_at_ms = Utc::now().timestamp_millis() + backoff_ms;
+                schedule_delivery(&delivery, due_at_ms, redis).await
+            }
+        }
+    };
+
+    if let Err(e) = outcome {
+        tracing::error!("Failed to update webhook delivery {}: {}", delivery.id, e);
+    }
+}
+
+async fn post_delivery(
+    delivery: &WebhookDelivery,
+    url: &str,
+    secret: &str,
+    client: &reqwest::Client,
+) -> Result<(), AppError> {
+    let timestamp = Utc::now().timestamp();
+    let res = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(DELIVERY_HEADER, delivery.id.to_string())
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(SIGNATURE_HEADER, sign(secret, timestamp, &delivery.body))
+        .body(delivery.body.clone())
+        .send()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Webhook request failed: {}", e)))?;
+
+    if !res.status().is_success() {
+        return Err(AppError::BadRequest(format!(
+            "Webhook returned status {}",
+            res.status()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Signs the body with the send time, so a captured request can't be
+/// replayed later under a fresh timestamp.
+fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any size");
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
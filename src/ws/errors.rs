@@ -0,0 +1,90 @@
+//! What a client is told when handling one of its socket messages fails.
+//! Most errors become an error code it can act on; errors that leave the
+//! socket nothing to do, like its lobby being gone or the user being banned,
+//! close it instead with a reason saying why.
+
+use uuid::Uuid;
+
+use crate::{
+    db::lobby::get::lobby_exists,
+    errors::AppError,
+    games::core::GameEngine,
+    models::error_code::ErrorCode,
+    state::{ConnectionInfoMap, RedisClient},
+    ws::lifecycle::DisconnectReason,
+};
+
+/// How the socket answers an error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SocketErrorAction {
+    Reply { code: ErrorCode, message: String },
+    Close(DisconnectReason),
+}
+
+/// Maps an error raised for the lobby's socket to what its client should
+/// see. Server-side failures are logged here and reach the client without
+/// their details.
+pub async fn socket_error_action(
+    lobby_id: Uuid,
+    error: &AppError,
+    redis: &RedisClient,
+) -> SocketErrorAction {
+    match error {
+        AppError::AccountSuspended { .. } => {
+            return SocketErrorAction::Close(DisconnectReason::AccountSuspended);
+        }
+        // Whatever was missing, nothing more can happen once the lobby is gone
+        AppError::NotFound(_)
+            if matches!(lobby_exists(lobby_id, redis.clone()).await, Ok(false)) =>
+        {
+            return SocketErrorAction::Close(DisconnectReason::LobbyNotFound);
+        }
+        _ => {}
+    }
+
+    let code = ErrorCode::from(error);
+    let message = if code == ErrorCode::InternalError {
+        tracing::error!("Failed to handle message in lobby {}: {}", lobby_id, error);
+        "Unexpected server error".to_string()
+    } else {
+        error.to_string()
+    };
+    SocketErrorAction::Reply { code, message }
+}
+
+/// Closes the player's game socket with the reason.
+pub async fn close_player_socket(
+    player_id: Uuid,
+    reason: DisconnectReason,
+    connections: &ConnectionInfoMap,
+) {
+    if let Some(conn_info) = connections.lock().await.get(&player_id) {
+        conn_info.sender.close(reason);
+    }
+}
+
+/// Tells a player of `E` why their message failed, or closes their socket
+/// when it can't go on.
+pub async fn report_socket_error<E: GameEngine>(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    error: &AppError,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    match socket_error_action(lobby_id, error, redis).await {
+        SocketErrorAction::Reply { code, message } => {
+            let error_msg = E::error_message(code, message);
+            E::send_to_player(player_id, lobby_id, &error_msg, connections, redis).await;
+        }
+        SocketErrorAction::Close(reason) => {
+            tracing::info!(
+                "Closing socket of player {} in lobby {}: {}",
+                player_id,
+                lobby_id,
+                error
+            );
+            close_player_socket(player_id, reason, connections).await;
+        }
+    }
+}
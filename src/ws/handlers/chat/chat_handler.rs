@@ -8,18 +8,24 @@ use std::net::SocketAddr;
 
 use crate::{
     db::{
-        chat::get::get_chat_history,
+        chat::get::{CHAT_PAGE_SIZE, get_chat_page},
         lobby::get::{get_lobby_info, get_lobby_players},
         user::get::get_user_by_id,
     },
     models::{
-        chat::ChatServerMessage,
+        chat::{ChatServerMessage, PresenceStatus},
         game::{LobbyState, Player, PlayerState, WsQueryParams},
+        protocol::ProtocolVersion,
     },
     state::{AppState, ChatConnectionInfoMap, RedisClient},
-    ws::handlers::chat::{message_handler, utils::*},
+    ws::handlers::{
+        chat::{message_handler, utils::*},
+        utils::{send_hello, traced},
+    },
+    ws::lifecycle::{ConnectionStage, DisconnectReason, close_when_idle, emit_connection_event},
+    ws::sessions::{SocketKind, authorize_socket},
 };
-use axum::extract::ws::{CloseFrame, Message};
+use axum::extract::ws::Message;
 use uuid::Uuid;
 
 pub async fn chat_handler(
@@ -32,6 +38,8 @@ pub async fn chat_handler(
     tracing::debug!("New chat WebSocket connection from {}", addr);
 
     let player_id = query.user_id;
+    authorize_socket(&query, SocketKind::Chat, state.redis.clone()).await?;
+    let protocol_version = ProtocolVersion::negotiate(query.protocol_version);
     let redis = state.redis.clone();
     let chat_connections = state.chat_connections.clone();
 
@@ -47,14 +55,10 @@ pub async fn chat_handler(
             player_id
         );
 
-        return Ok(ws.on_upgrade(move |mut socket| async move {
-            let close_frame = CloseFrame {
-                code: axum::extract::ws::close_code::NORMAL,
-                reason: "finished".into(),
-            };
-
+        return Ok(ws.on_upgrade(traced(move |mut socket| async move {
+            let close_frame = DisconnectReason::GameFinished.close_frame();
             let _ = socket.send(Message::Close(Some(close_frame))).await;
-        }));
+        })));
     }
 
     // Get user info to create player object
@@ -70,32 +74,45 @@ pub async fn chat_handler(
         tx_id: None,
         claim: None,
         prize: None,
+        wars_point: None,
         last_ping: None,
+        joined_at: None,
         user: Some(user.clone()),
     };
 
-    Ok(ws.on_upgrade(move |socket| {
-        handle_chat_socket(socket, lobby_id, player, chat_connections, redis)
-    }))
+    Ok(ws.on_upgrade(traced(move |socket| {
+        handle_chat_socket(
+            socket,
+            lobby_id,
+            player,
+            protocol_version,
+            chat_connections,
+            redis,
+        )
+    })))
 }
 
 async fn handle_chat_socket(
     socket: WebSocket,
     lobby_id: Uuid,
     player: Player,
+    protocol_version: ProtocolVersion,
     chat_connections: ChatConnectionInfoMap,
     redis: RedisClient,
 ) {
-    let (sender, receiver) = socket.split();
+    let (mut sender, receiver) = socket.split();
+    send_hello(&mut sender, protocol_version).await;
 
-    store_chat_connection_and_send_queued_messages(
+    let outbound = store_chat_connection_and_send_queued_messages(
         lobby_id,
         player.id,
         sender,
+        protocol_version,
         &chat_connections,
         &redis,
     )
     .await;
+    let receiver = close_when_idle(receiver, outbound);
 
     // Check if player is in the lobby and send permission status
     let is_lobby_member =
@@ -107,18 +124,27 @@ async fn handle_chat_socket(
             }
         };
 
+    let stage = if is_lobby_member {
+        ConnectionStage::JoinedLobby
+    } else {
+        ConnectionStage::Spectating
+    };
+    emit_connection_event(player.id, Some(lobby_id), SocketKind::Chat, stage);
+
     let permit_msg = ChatServerMessage::PermitChat {
         allowed: is_lobby_member,
     };
     send_chat_message_to_player(player.id, &permit_msg, &chat_connections).await;
 
-    // If player is a lobby member, send chat history from Redis
+    // If player is a lobby member, send the latest chat history from Redis.
+    // Older pages are fetched over HTTP.
     if is_lobby_member {
-        match get_chat_history(lobby_id, &redis).await {
-            Ok(chat_history) => {
-                if !chat_history.is_empty() {
+        match get_chat_page(lobby_id, player.id, None, CHAT_PAGE_SIZE, &redis).await {
+            Ok(page) => {
+                if !page.messages.is_empty() {
                     let history_msg = ChatServerMessage::ChatHistory {
-                        messages: chat_history,
+                        messages: page.messages,
+                        has_more: page.has_more,
                     };
                     send_chat_message_to_player(player.id, &history_msg, &chat_connections).await;
                 }
@@ -129,14 +155,37 @@ async fn handle_chat_socket(
         }
     }
 
+    if is_lobby_member {
+        message_handler::broadcast_presence(
+            lobby_id,
+            player.id,
+            PresenceStatus::Joined,
+            &chat_connections,
+            &redis,
+        )
+        .await;
+    }
+
     message_handler::handle_incoming_chat_messages(
         receiver,
         lobby_id,
         &player,
+        is_lobby_member,
         &chat_connections,
         redis.clone(),
     )
     .await;
 
-    remove_chat_connection(player.id, &chat_connections).await;
+    remove_chat_connection(lobby_id, player.id, &chat_connections).await;
+
+    if is_lobby_member {
+        message_handler::broadcast_presence(
+            lobby_id,
+            player.id,
+            PresenceStatus::Left,
+            &chat_connections,
+            &redis,
+        )
+        .await;
+    }
 }
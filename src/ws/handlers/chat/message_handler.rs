@@ -1,26 +1,82 @@
 use axum::extract::ws::Message;
 use chrono::Utc;
-use futures::{SinkExt, StreamExt};
+use futures::StreamExt;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 use crate::{
-    db::{chat::post::store_chat_message, lobby::get::get_lobby_players},
+    config,
+    db::{
+        audit::post::record_audit_entry,
+        chat::{
+            moderation::{
+                delete_chat_message, get_chat_message, get_chat_mute_secs, mute_chat_user,
+                store_chat_report,
+            },
+            post::store_chat_message,
+        },
+        lobby::get::{get_lobby_info, get_lobby_players},
+        user::block::get_blocked_by_ids,
+    },
+    errors::AppError,
     models::{
-        chat::{ChatClientMessage, ChatMessage, ChatServerMessage},
+        audit::AuditEntry,
+        chat::{ChatClientMessage, ChatMessage, ChatReport, ChatServerMessage, PresenceStatus},
         game::{Player, PlayerState},
     },
+    moderation::filter_chat_text,
     state::{ChatConnectionInfoMap, RedisClient},
     ws::handlers::chat::utils::{queue_chat_message_for_player, send_chat_message_to_player},
 };
 
+/// A member who sends nothing for this long is shown as away
+const AWAY_AFTER: Duration = Duration::from_secs(60);
+/// Minimum gap between relayed typing events of one member
+const TYPING_THROTTLE: Duration = Duration::from_secs(3);
+
 pub async fn handle_incoming_chat_messages(
     mut receiver: impl StreamExt<Item = Result<Message, axum::Error>> + Unpin,
     lobby_id: Uuid,
     player: &Player,
+    is_lobby_member: bool,
     chat_connections: &ChatConnectionInfoMap,
     redis: RedisClient,
 ) {
-    while let Some(msg_result) = receiver.next().await {
+    let mut away = false;
+    let mut last_typing: Option<Instant> = None;
+
+    loop {
+        let msg_result = match tokio::time::timeout(AWAY_AFTER, receiver.next()).await {
+            Ok(Some(msg_result)) => msg_result,
+            Ok(None) => break,
+            Err(_) => {
+                if is_lobby_member && !away {
+                    away = true;
+                    broadcast_presence(
+                        lobby_id,
+                        player.id,
+                        PresenceStatus::Away,
+                        chat_connections,
+                        &redis,
+                    )
+                    .await;
+                }
+                continue;
+            }
+        };
+
+        if away {
+            away = false;
+            broadcast_presence(
+                lobby_id,
+                player.id,
+                PresenceStatus::Joined,
+                chat_connections,
+                &redis,
+            )
+            .await;
+        }
+
         match msg_result {
             Ok(msg) => match msg {
                 Message::Text(text) => {
@@ -78,6 +134,20 @@ pub async fn handle_incoming_chat_messages(
                                     continue;
                                 }
 
+                                match get_chat_mute_secs(lobby_id, player.id, &redis).await {
+                                    Ok(Some(secs)) => {
+                                        send_chat_error(
+                                            player.id,
+                                            &format!("You are muted for another {secs} seconds"),
+                                            chat_connections,
+                                        )
+                                        .await;
+                                        continue;
+                                    }
+                                    Ok(None) => {}
+                                    Err(e) => tracing::error!("Failed to check chat mute: {}", e),
+                                }
+
                                 if text.trim().is_empty() {
                                     let error_msg = ChatServerMessage::Error {
                                         message: "Message cannot be empty".to_string(),
@@ -91,9 +161,29 @@ pub async fn handle_incoming_chat_messages(
                                     continue;
                                 }
 
+                                let text = match filter_chat_text(text.trim(), redis.clone()).await
+                                {
+                                    Ok(text) => text,
+                                    Err(AppError::BadRequest(message)) => {
+                                        let error_msg = ChatServerMessage::Error { message };
+                                        send_chat_message_to_player(
+                                            player.id,
+                                            &error_msg,
+                                            chat_connections,
+                                        )
+                                        .await;
+                                        continue;
+                                    }
+                                    Err(e) => {
+                                        // Don't lose the message over a filter outage
+                                        tracing::error!("Failed to filter chat message: {}", e);
+                                        text.trim().to_string()
+                                    }
+                                };
+
                                 let chat_message = ChatMessage {
                                     id: Uuid::new_v4(),
-                                    text: text.trim().to_string(),
+                                    text,
                                     sender: player.clone(),
                                     timestamp: Utc::now(),
                                 };
@@ -105,11 +195,68 @@ pub async fn handle_incoming_chat_messages(
                                     tracing::error!("Failed to store chat message in Redis: {}", e);
                                 }
 
+                                // Players who blocked the sender never receive it
+                                let recipients: Vec<Player> =
+                                    match get_blocked_by_ids(player.id, redis.clone()).await {
+                                        Ok(blocked_by) => lobby_players
+                                            .into_iter()
+                                            .filter(|p| !blocked_by.contains(&p.id))
+                                            .collect(),
+                                        Err(e) => {
+                                            tracing::error!("Failed to get blockers: {}", e);
+                                            lobby_players
+                                        }
+                                    };
+
+                                let chat_msg = ChatServerMessage::Chat {
+                                    message: Box::new(chat_message),
+                                };
                                 broadcast_chat_to_lobby(
-                                    &chat_message,
-                                    &lobby_players,
+                                    &chat_msg,
+                                    &recipients,
+                                    chat_connections,
+                                    lobby_id,
+                                    &redis,
+                                )
+                                .await;
+                            }
+                            ChatClientMessage::MuteUser { user_id, minutes } => {
+                                handle_mute_user(
+                                    lobby_id,
+                                    player,
+                                    user_id,
+                                    minutes,
                                     chat_connections,
+                                    &redis,
+                                )
+                                .await;
+                            }
+                            ChatClientMessage::DeleteMessage { message_id } => {
+                                handle_delete_message(
                                     lobby_id,
+                                    player,
+                                    message_id,
+                                    chat_connections,
+                                    &redis,
+                                )
+                                .await;
+                            }
+                            ChatClientMessage::Typing => {
+                                if !is_lobby_member
+                                    || last_typing.is_some_and(|t| t.elapsed() < TYPING_THROTTLE)
+                                {
+                                    continue;
+                                }
+                                last_typing = Some(Instant::now());
+                                relay_typing(lobby_id, player.id, chat_connections, &redis).await;
+                            }
+                            ChatClientMessage::ReportMessage { message_id, reason } => {
+                                handle_report_message(
+                                    lobby_id,
+                                    player,
+                                    message_id,
+                                    reason,
+                                    chat_connections,
                                     &redis,
                                 )
                                 .await;
@@ -158,16 +305,13 @@ pub async fn handle_incoming_chat_messages(
 }
 
 async fn broadcast_chat_to_lobby(
-    chat_message: &ChatMessage,
+    chat_msg: &ChatServerMessage,
     lobby_players: &[Player],
     chat_connections: &ChatConnectionInfoMap,
     lobby_id: Uuid,
     redis: &RedisClient,
 ) {
-    let chat_msg = ChatServerMessage::Chat {
-        message: chat_message.clone(),
-    };
-    let serialized = match serde_json::to_string(&chat_msg) {
+    let serialized = match serde_json::to_string(chat_msg) {
         Ok(json) => json,
         Err(e) => {
             tracing::error!("Failed to serialize chat message: {}", e);
@@ -175,45 +319,304 @@ async fn broadcast_chat_to_lobby(
         }
     };
 
-    let connection_guard = chat_connections.lock().await;
-
-    for player in lobby_players {
-        if let Some(conn_info) = connection_guard.get(&player.id) {
-            let mut sender = conn_info.sender.lock().await;
-            if let Err(e) = sender.send(Message::Text(serialized.clone().into())).await {
-                tracing::warn!("Failed to send chat message to player {}: {}", player.id, e);
+    let targets: Vec<_> = {
+        let connection_guard = chat_connections.lock().await;
+        lobby_players
+            .iter()
+            .map(|player| (player.id, connection_guard.get(&player.id).cloned()))
+            .collect()
+    };
 
-                if chat_msg.should_queue() {
-                    drop(sender);
-                    drop(connection_guard);
+    for (player_id, conn_info) in targets {
+        if let Some(conn_info) = conn_info {
+            if let Err(e) = conn_info.sender.send(Message::Text(
+                conn_info.protocol_version.adapt(&serialized).into(),
+            )) {
+                tracing::warn!("Failed to send chat message to player {}: {}", player_id, e);
 
-                    if let Err(queue_err) = queue_chat_message_for_player(
-                        player.id,
+                if chat_msg.should_queue()
+                    && let Err(queue_err) = queue_chat_message_for_player(
+                        player_id,
                         lobby_id,
                         serialized.clone(),
                         redis,
                     )
                     .await
-                    {
-                        tracing::error!(
-                            "Failed to queue chat message for player {}: {}",
-                            player.id,
-                            queue_err
-                        );
-                    }
-                    return;
+                {
+                    tracing::error!(
+                        "Failed to queue chat message for player {}: {}",
+                        player_id,
+                        queue_err
+                    );
                 }
             }
         } else if chat_msg.should_queue() {
             if let Err(e) =
-                queue_chat_message_for_player(player.id, lobby_id, serialized.clone(), redis).await
+                queue_chat_message_for_player(player_id, lobby_id, serialized.clone(), redis).await
             {
                 tracing::error!(
                     "Failed to queue chat message for offline player {}: {}",
-                    player.id,
+                    player_id,
                     e
                 );
             }
         }
     }
 }
+
+/// Longest mute a moderator can hand out
+const MAX_MUTE_MINUTES: u32 = 24 * 60;
+const MAX_REPORT_REASON_LEN: usize = 200;
+
+async fn send_chat_error(player_id: Uuid, message: &str, chat_connections: &ChatConnectionInfoMap) {
+    let error_msg = ChatServerMessage::Error {
+        message: message.to_string(),
+    };
+    send_chat_message_to_player(player_id, &error_msg, chat_connections).await;
+}
+
+/// Lobby creators and admins moderate a lobby's chat.
+async fn is_chat_moderator(lobby_id: Uuid, user_id: Uuid, redis: &RedisClient) -> bool {
+    if config::get().admin_user_ids.contains(&user_id) {
+        return true;
+    }
+
+    match get_lobby_info(lobby_id, redis.clone()).await {
+        Ok(lobby) => lobby.creator.id == user_id,
+        Err(e) => {
+            tracing::error!("Failed to get lobby {}: {}", lobby_id, e);
+            false
+        }
+    }
+}
+
+async fn broadcast_to_chat_members(
+    msg: &ChatServerMessage,
+    lobby_id: Uuid,
+    chat_connections: &ChatConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    match get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await {
+        Ok(players) => {
+            broadcast_chat_to_lobby(msg, &players, chat_connections, lobby_id, redis).await
+        }
+        Err(e) => tracing::error!("Failed to get lobby players: {}", e),
+    }
+}
+
+async fn handle_mute_user(
+    lobby_id: Uuid,
+    moderator: &Player,
+    user_id: Uuid,
+    minutes: u32,
+    chat_connections: &ChatConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    if user_id == moderator.id {
+        send_chat_error(moderator.id, "You cannot mute yourself", chat_connections).await;
+        return;
+    }
+    if !is_chat_moderator(lobby_id, moderator.id, redis).await {
+        send_chat_error(
+            moderator.id,
+            "Only the lobby creator can mute players",
+            chat_connections,
+        )
+        .await;
+        return;
+    }
+
+    let minutes = minutes.clamp(1, MAX_MUTE_MINUTES);
+    let until = match mute_chat_user(lobby_id, user_id, minutes, redis).await {
+        Ok(until) => until,
+        Err(e) => {
+            tracing::error!("Failed to mute {} in lobby {}: {}", user_id, lobby_id, e);
+            send_chat_error(moderator.id, "Failed to mute player", chat_connections).await;
+            return;
+        }
+    };
+
+    let entry = AuditEntry::new(moderator.id, format!("chat_mute:{lobby_id}"), Some(user_id));
+    if let Err(e) = record_audit_entry(&entry, redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    let muted_msg = ChatServerMessage::UserMuted { user_id, until };
+    broadcast_to_chat_members(&muted_msg, lobby_id, chat_connections, redis).await;
+}
+
+async fn handle_delete_message(
+    lobby_id: Uuid,
+    player: &Player,
+    message_id: Uuid,
+    chat_connections: &ChatConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let message = match get_chat_message(lobby_id, message_id, redis).await {
+        Ok(message) => message,
+        Err(AppError::NotFound(message)) => {
+            send_chat_error(player.id, &message, chat_connections).await;
+            return;
+        }
+        Err(e) => {
+            tracing::error!("Failed to get chat message {}: {}", message_id, e);
+            send_chat_error(player.id, "Failed to delete message", chat_connections).await;
+            return;
+        }
+    };
+
+    let is_author = message.sender.id == player.id;
+    if !is_author && !is_chat_moderator(lobby_id, player.id, redis).await {
+        send_chat_error(
+            player.id,
+            "You can only delete your own messages",
+            chat_connections,
+        )
+        .await;
+        return;
+    }
+
+    if let Err(e) = delete_chat_message(lobby_id, message_id, redis).await {
+        tracing::error!("Failed to delete chat message {}: {}", message_id, e);
+        send_chat_error(player.id, "Failed to delete message", chat_connections).await;
+        return;
+    }
+
+    if !is_author {
+        let entry = AuditEntry::new(
+            player.id,
+            format!("chat_delete:{lobby_id}"),
+            Some(message.sender.id),
+        );
+        if let Err(e) = record_audit_entry(&entry, redis.clone()).await {
+            tracing::error!("Failed to write audit entry: {}", e);
+        }
+    }
+
+    let deleted_msg = ChatServerMessage::MessageDeleted { message_id };
+    broadcast_to_chat_members(&deleted_msg, lobby_id, chat_connections, redis).await;
+}
+
+/// Keeps a copy of the message for admins and records the report in the audit log.
+async fn handle_report_message(
+    lobby_id: Uuid,
+    reporter: &Player,
+    message_id: Uuid,
+    reason: Option<String>,
+    chat_connections: &ChatConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let message = match get_chat_message(lobby_id, message_id, redis).await {
+        Ok(message) => message,
+        Err(AppError::NotFound(message)) => {
+            send_chat_error(reporter.id, &message, chat_connections).await;
+            return;
+        }
+        Err(e) => {
+            tracing::error!("Failed to get chat message {}: {}", message_id, e);
+            send_chat_error(reporter.id, "Failed to report message", chat_connections).await;
+            return;
+        }
+    };
+
+    if message.sender.id == reporter.id {
+        send_chat_error(
+            reporter.id,
+            "You cannot report your own message",
+            chat_connections,
+        )
+        .await;
+        return;
+    }
+
+    let reason = reason
+        .map(|r| {
+            r.trim()
+                .chars()
+                .take(MAX_REPORT_REASON_LEN)
+                .collect::<String>()
+        })
+        .filter(|r| !r.is_empty());
+    let sender_id = message.sender.id;
+    let report = ChatReport {
+        lobby_id,
+        message,
+        reported_by: reporter.id,
+        reason,
+        reported_at: Utc::now(),
+    };
+    if let Err(e) = store_chat_report(&report, redis).await {
+        tracing::error!("Failed to store chat report for {}: {}", message_id, e);
+        send_chat_error(reporter.id, "Failed to report message", chat_connections).await;
+        return;
+    }
+
+    let entry = AuditEntry::new(
+        reporter.id,
+        format!("chat_report:{lobby_id}:{message_id}"),
+        Some(sender_id),
+    );
+    if let Err(e) = record_audit_entry(&entry, redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    let reported_msg = ChatServerMessage::MessageReported { message_id };
+    send_chat_message_to_player(reporter.id, &reported_msg, chat_connections).await;
+}
+
+/// Sends a member's presence to the other members of the lobby.
+pub async fn broadcast_presence(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    status: PresenceStatus,
+    chat_connections: &ChatConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let players = match get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
+    {
+        Ok(players) => players,
+        Err(e) => {
+            tracing::error!("Failed to get lobby players: {}", e);
+            return;
+        }
+    };
+    let others: Vec<Player> = players.into_iter().filter(|p| p.id != player_id).collect();
+
+    let presence_msg = ChatServerMessage::Presence {
+        user_id: player_id,
+        status,
+    };
+    broadcast_chat_to_lobby(&presence_msg, &others, chat_connections, lobby_id, redis).await;
+}
+
+/// Tells the other members someone is typing, unless they are muted or the
+/// recipient blocked them.
+async fn relay_typing(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    chat_connections: &ChatConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    if let Ok(Some(_)) = get_chat_mute_secs(lobby_id, player_id, redis).await {
+        return;
+    }
+
+    let players = match get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
+    {
+        Ok(players) => players,
+        Err(e) => {
+            tracing::error!("Failed to get lobby players: {}", e);
+            return;
+        }
+    };
+    let blocked_by = get_blocked_by_ids(player_id, redis.clone())
+        .await
+        .unwrap_or_default();
+    let recipients: Vec<Player> = players
+        .into_iter()
+        .filter(|p| p.id != player_id && !blocked_by.contains(&p.id))
+        .collect();
+
+    let typing_msg = ChatServerMessage::Typing { user_id: player_id };
+    broadcast_chat_to_lobby(&typing_msg, &recipients, chat_connections, lobby_id, redis).await;
+}
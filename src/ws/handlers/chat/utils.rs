@@ -1,17 +1,23 @@
 use axum::extract::ws::{Message, WebSocket};
-use futures::{SinkExt, stream::SplitSink};
+use futures::stream::SplitSink;
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
     errors::AppError,
     models::{
         chat::ChatServerMessage,
+        protocol::ProtocolVersion,
         redis::{KeyPart, RedisKey},
     },
+    presence::refresh_presence,
     state::{ChatConnectionInfo, ChatConnectionInfoMap, RedisClient},
+    ws::{
+        lifecycle::{ConnectionStage, DisconnectReason, emit_connection_event},
+        outbound::OutboundSender,
+        sessions::SocketKind,
+    },
 };
-use std::sync::Arc;
-use tokio::sync::Mutex;
 
 pub async fn queue_chat_message_for_player(
     player_id: Uuid,
@@ -87,17 +93,20 @@ pub async fn store_chat_connection_and_send_queued_messages(
     lobby_id: Uuid,
     player_id: Uuid,
     sender: SplitSink<WebSocket, Message>,
+    protocol_version: ProtocolVersion,
     connections: &ChatConnectionInfoMap,
     redis: &RedisClient,
-) {
+) -> OutboundSender {
     // Store the connection
     let conn_info = Arc::new(ChatConnectionInfo {
-        sender: Arc::new(Mutex::new(sender)),
+        sender: OutboundSender::spawn(sender),
+        protocol_version,
     });
     connections
         .lock()
         .await
         .insert(player_id, conn_info.clone());
+    refresh_presence(player_id);
 
     // Send queued messages
     match get_queued_chat_messages_for_player(player_id, lobby_id, redis).await {
@@ -110,10 +119,12 @@ pub async fn store_chat_connection_and_send_queued_messages(
                     lobby_id
                 );
 
-                let mut sender_guard = conn_info.sender.lock().await;
                 let mut sent_count = 0;
                 for message in messages {
-                    if let Err(e) = sender_guard.send(Message::Text(message.into())).await {
+                    if let Err(e) = conn_info
+                        .sender
+                        .send(Message::Text(protocol_version.adapt(&message).into()))
+                    {
                         tracing::error!(
                             "Failed to send queued chat message to player {} in lobby {}: {}",
                             player_id,
@@ -143,12 +154,29 @@ pub async fn store_chat_connection_and_send_queued_messages(
             );
         }
     }
+
+    conn_info.sender.clone()
 }
 
-pub async fn remove_chat_connection(player_id: Uuid, chat_connections: &ChatConnectionInfoMap) {
+pub async fn remove_chat_connection(
+    lobby_id: Uuid,
+    player_id: Uuid,
+    chat_connections: &ChatConnectionInfoMap,
+) {
     let mut conn_map = chat_connections.lock().await;
-    if conn_map.remove(&player_id).is_some() {
+    if let Some(conn_info) = conn_map.remove(&player_id) {
         tracing::debug!("Removed chat connection for player {}", player_id);
+        refresh_presence(player_id);
+        let reason = conn_info
+            .sender
+            .close_reason()
+            .unwrap_or(DisconnectReason::ClientClosed);
+        emit_connection_event(
+            player_id,
+            Some(lobby_id),
+            SocketKind::Chat,
+            ConnectionStage::Disconnected { reason },
+        );
     }
 }
 
@@ -166,10 +194,11 @@ pub async fn send_chat_message_to_player(
     };
 
     let connection_guard = connections.lock().await;
-    if let Some(conn_info) = connection_guard.get(&player_id) {
-        let mut sender = conn_info.sender.lock().await;
-        if let Err(e) = sender.send(Message::Text(serialized.into())).await {
-            tracing::debug!("Failed to send message to player {}: {}", player_id, e);
-        }
+    if let Some(conn_info) = connection_guard.get(&player_id)
+        && let Err(e) = conn_info.sender.send(Message::Text(
+            conn_info.protocol_version.adapt(&serialized).into(),
+        ))
+    {
+        tracing::debug!("Failed to send message to player {}: {}", player_id, e);
     }
 }
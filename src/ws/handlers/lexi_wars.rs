@@ -3,15 +3,18 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
 };
-use futures::{SinkExt, StreamExt, stream::SplitStream};
+use futures::{SinkExt, StreamExt};
 use std::net::SocketAddr;
 use uuid::Uuid;
 
 use crate::{
     db::{
-        game::state::{
-            get_current_rule, get_current_turn, get_game_started, get_rule_context,
-            set_current_turn, set_rule_context, set_rule_index,
+        game::{
+            player_words::get_players_used_words,
+            state::{
+                get_current_rule, get_current_turn, get_game_started, get_player_scores,
+                get_rule_context, set_current_turn, set_rule_context, set_rule_index,
+            },
         },
         lobby::{
             get::{get_connected_players_ids, get_lobby_info, get_lobby_players},
@@ -21,18 +24,32 @@ use crate::{
         },
     },
     errors::AppError,
-    games::lexi_wars::{
-        self,
-        engine::start_auto_start_timer,
-        rules::RuleContext,
-        utils::{broadcast_to_player, generate_random_letter},
+    games::{
+        core::{bets::handle_bet, reactions::handle_reaction},
+        lexi_wars::{
+            self,
+            bot::{connect_bots, is_bot},
+            engine::{LexiWars, start_auto_start_timer},
+            pause::check_connected_players,
+            rules::RuleContext,
+            scoring::word_highlights,
+            utils::{
+                broadcast_to_lobby_and_spectators, broadcast_to_player, generate_random_letter,
+            },
+        },
     },
     models::{
         game::{ClaimState, LobbyInfo, LobbyState, Player, PlayerState, WsQueryParams},
-        lexi_wars::{LexiWarsServerMessage, PlayerStanding},
+        lexi_wars::{LexiWarsClientMessage, LexiWarsServerMessage, PlayerStanding},
+        protocol::ProtocolVersion,
     },
     state::{AppState, ConnectionInfoMap, RedisClient},
-    ws::handlers::utils::{remove_connection, store_connection_and_send_queued_messages},
+    ws::handlers::utils::{
+        get_pending_results_for_player, remove_connection, replay_pending_results, send_hello,
+        store_connection_and_send_queued_messages, traced,
+    },
+    ws::lifecycle::{ConnectionStage, close_when_idle, emit_connection_event},
+    ws::sessions::{SocketKind, authorize_socket},
 };
 
 pub async fn lexi_wars_handler(
@@ -45,6 +62,8 @@ pub async fn lexi_wars_handler(
     tracing::debug!("New Lexi-Wars WebSocket connection from {}", addr);
 
     let player_id = query.user_id;
+    authorize_socket(&query, SocketKind::Game, state.redis.clone()).await?;
+    let protocol_version = ProtocolVersion::negotiate(query.protocol_version);
     let redis = state.redis.clone();
     let connections = state.connections.clone();
     let bot = state.bot.clone();
@@ -57,11 +76,37 @@ pub async fn lexi_wars_handler(
     if lobby.state != LobbyState::InProgress {
         if lobby.state == LobbyState::Finished {
             tracing::info!("Player {} trying to connect to finished game", player_id);
+            let token_symbol = lobby.pool_symbol().to_string();
 
             // Send game over info and close connection
-            return Ok(ws.on_upgrade(move |mut socket| async move {
+            return Ok(ws.on_upgrade(traced(move |mut socket| async move {
+                // Replay unacknowledged results first, they carry the exact messages sent at end_game
+                match get_pending_results_for_player(player_id, lobby_id, &redis).await {
+                    Ok(results) if !results.is_empty() => {
+                        replay_pending_results(
+                            &mut socket,
+                            player_id,
+                            lobby_id,
+                            results,
+                            |msg| matches!(msg, LexiWarsClientMessage::ResultAck),
+                            &redis,
+                        )
+                        .await;
+                        let _ = socket.close().await;
+                        return;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to get pending results for player {}: {}",
+                            player_id,
+                            e
+                        );
+                    }
+                }
+
                 // Send GameOver message first
-                let game_over_msg = LexiWarsServerMessage::GameOver;
+                let game_over_msg = LexiWarsServerMessage::GameOver { reason: None };
                 let serialized = serde_json::to_string(&game_over_msg).unwrap();
                 let _ = socket
                     .send(axum::extract::ws::Message::Text(serialized.into()))
@@ -78,12 +123,26 @@ pub async fn lexi_wars_handler(
                     // Sort by rank (1st place first)
                     players_with_ranks.sort_by_key(|p| p.rank.unwrap());
 
+                    let scores = get_player_scores(lobby_id, redis.clone())
+                        .await
+                        .unwrap_or_default();
+                    let player_ids: Vec<Uuid> = players_with_ranks.iter().map(|p| p.id).collect();
+                    let mut used_words =
+                        get_players_used_words(lobby_id, &player_ids, redis.clone())
+                            .await
+                            .unwrap_or_default();
                     let standing: Vec<PlayerStanding> = players_with_ranks
                         .clone()
                         .into_iter()
-                        .map(|player| PlayerStanding {
-                            rank: player.rank.unwrap(),
-                            player,
+                        .map(|player| {
+                            let words = used_words.remove(&player.id).unwrap_or_default();
+                            PlayerStanding {
+                                rank: player.rank.unwrap(),
+                                score: scores.get(&player.id).copied().unwrap_or(0),
+                                highlights: word_highlights(&words),
+                                used_words: words,
+                                player,
+                            }
                         })
                         .collect();
 
@@ -99,22 +158,23 @@ pub async fn lexi_wars_handler(
                     {
                         if let Some(prize_amount) = connecting_player.prize {
                             // Check if player has not claimed the prize
-                            let should_send_prize = match &connecting_player.claim {
-                                Some(ClaimState::NotClaimed) => true,
-                                None => false,
-                                Some(ClaimState::Claimed { .. }) => false,
-                            };
+                            let should_send_prize =
+                                matches!(&connecting_player.claim, Some(ClaimState::Claimable));
 
                             if should_send_prize {
                                 let prize_msg = LexiWarsServerMessage::Prize {
                                     amount: prize_amount,
+                                    token_symbol,
                                 };
                                 let serialized = serde_json::to_string(&prize_msg).unwrap();
                                 let _ = socket
                                     .send(axum::extract::ws::Message::Text(serialized.into()))
                                     .await;
                             } else {
-                                let prize_msg = LexiWarsServerMessage::Prize { amount: 0.0 };
+                                let prize_msg = LexiWarsServerMessage::Prize {
+                                    amount: 0.0,
+                                    token_symbol,
+                                };
                                 let serialized = serde_json::to_string(&prize_msg).unwrap();
                                 let _ = socket
                                     .send(axum::extract::ws::Message::Text(serialized.into()))
@@ -135,31 +195,31 @@ pub async fn lexi_wars_handler(
                 }
 
                 let _ = socket.close().await;
-            }));
+            })));
         } else if lobby.state == LobbyState::Starting {
             tracing::debug!("Player {} trying to connect to starting lobby", player_id);
 
             // Send StartFailed message and close connection
-            return Ok(ws.on_upgrade(move |mut socket| async move {
+            return Ok(ws.on_upgrade(traced(move |mut socket| async move {
                 let start_failed_msg = LexiWarsServerMessage::StartFailed;
                 let serialized = serde_json::to_string(&start_failed_msg).unwrap();
                 let _ = socket
                     .send(axum::extract::ws::Message::Text(serialized.into()))
                     .await;
                 let _ = socket.close().await;
-            }));
+            })));
         } else if lobby.state == LobbyState::Waiting {
             tracing::debug!("Player {} trying to connect to waiting lobby", player_id);
 
             // Send StartFailed message and close connection
-            return Ok(ws.on_upgrade(move |mut socket| async move {
+            return Ok(ws.on_upgrade(traced(move |mut socket| async move {
                 let start_failed_msg = LexiWarsServerMessage::StartFailed;
                 let serialized = serde_json::to_string(&start_failed_msg).unwrap();
                 let _ = socket
                     .send(axum::extract::ws::Message::Text(serialized.into()))
                     .await;
                 let _ = socket.close().await;
-            }));
+            })));
         } else {
             tracing::error!("lobby {} has unexpected state: {:?}", lobby_id, lobby.state);
             return Err(AppError::BadRequest("lobby has unexpected state".into()).to_response());
@@ -182,12 +242,24 @@ pub async fn lexi_wars_handler(
         .await
         .map_err(|e| e.to_response())?;
 
+    let is_reconnecting = connected_player_ids.contains(&player_id);
+    let ctx = SocketContext {
+        lobby_id,
+        user_id: player_id,
+        players: players_clone,
+        connected_player_ids,
+        connections,
+        lobby_info: lobby,
+        redis,
+        game_started: is_game_started,
+        protocol_version,
+        bot,
+    };
+
     // Handle different connection scenarios
     match (matched_player, is_game_started) {
         // Case 1: Player is a lobby member
         (Some(player), game_started) => {
-            let is_reconnecting = connected_player_ids.contains(&player_id);
-
             if game_started && !is_reconnecting {
                 // Lobby member connecting to started game for first time -> spectator
                 tracing::info!(
@@ -196,22 +268,10 @@ pub async fn lexi_wars_handler(
                     lobby_id
                 );
 
-                Ok(ws.on_upgrade(move |socket| {
-                    let lobby_info = lobby.clone();
-                    handle_lexi_wars_socket(
-                        socket,
-                        lobby_id,
-                        player_id,
-                        None, // Pass None to make them a spectator
-                        players_clone,
-                        connected_player_ids,
-                        connections,
-                        lobby_info,
-                        redis,
-                        is_game_started,
-                        bot.clone(),
-                    )
-                }))
+                Ok(ws.on_upgrade(traced(move |socket| {
+                    // Pass None to make them a spectator
+                    handle_lexi_wars_socket(socket, None, ctx)
+                })))
             } else {
                 // Either game hasn't started or player is reconnecting -> normal player
                 if is_reconnecting {
@@ -224,44 +284,18 @@ pub async fn lexi_wars_handler(
                     );
                 }
 
-                Ok(ws.on_upgrade(move |socket| {
-                    let lobby_info = lobby.clone();
-                    handle_lexi_wars_socket(
-                        socket,
-                        lobby_id,
-                        player_id,
-                        Some(player),
-                        players_clone,
-                        connected_player_ids,
-                        connections,
-                        lobby_info,
-                        redis,
-                        is_game_started,
-                        bot.clone(),
-                    )
-                }))
+                Ok(ws.on_upgrade(traced(move |socket| {
+                    handle_lexi_wars_socket(socket, Some(player), ctx)
+                })))
             }
         }
         // Case 2: Not a lobby member, but game has started - add as spectator
         (None, true) => {
             tracing::info!("User {} joining lobby {} as spectator", player_id, lobby_id);
 
-            Ok(ws.on_upgrade(move |socket| {
-                let lobby_info = lobby.clone();
-                handle_lexi_wars_socket(
-                    socket,
-                    lobby_id,
-                    player_id,
-                    None,
-                    players_clone,
-                    connected_player_ids,
-                    connections,
-                    lobby_info,
-                    redis,
-                    is_game_started,
-                    bot.clone(),
-                )
-            }))
+            Ok(ws.on_upgrade(traced(move |socket| {
+                handle_lexi_wars_socket(socket, None, ctx)
+            })))
         }
         // Case 3: Not a lobby member and game hasn't started - add as spectator. TODO we should probably disconnect
         (None, false) => {
@@ -270,67 +304,79 @@ pub async fn lexi_wars_handler(
                 player_id,
                 lobby_id
             );
-            Ok(ws.on_upgrade(move |socket| {
-                let lobby_info = lobby.clone();
-                handle_lexi_wars_socket(
-                    socket,
-                    lobby_id,
-                    player_id,
-                    None,
-                    players_clone,
-                    connected_player_ids,
-                    connections,
-                    lobby_info,
-                    redis,
-                    is_game_started,
-                    bot.clone(),
-                )
-            }))
+            Ok(ws.on_upgrade(traced(move |socket| {
+                handle_lexi_wars_socket(socket, None, ctx)
+            })))
         }
     }
 }
 
-async fn handle_lexi_wars_socket(
-    socket: WebSocket,
+/// Lobby state looked up before the upgrade, handed to the socket handler.
+struct SocketContext {
     lobby_id: Uuid,
+    /// The connecting user, whether they play or spectate
     user_id: Uuid,
-    player: Option<Player>,
     players: Vec<Player>,
     connected_player_ids: Vec<Uuid>,
     connections: ConnectionInfoMap,
     lobby_info: LobbyInfo,
     redis: RedisClient,
     game_started: bool,
+    protocol_version: ProtocolVersion,
     bot: teloxide::Bot,
-) {
-    let (sender, receiver) = socket.split();
+}
+
+async fn handle_lexi_wars_socket(socket: WebSocket, player: Option<Player>, ctx: SocketContext) {
+    let SocketContext {
+        lobby_id,
+        user_id,
+        game_started,
+        protocol_version,
+        ref players,
+        ref connections,
+        ref lobby_info,
+        ref redis,
+        ref bot,
+        ..
+    } = ctx;
+    let (mut sender, receiver) = socket.split();
+    send_hello(&mut sender, protocol_version).await;
 
     // Handle connection setup differently for players vs spectators
     if let Some(ref p) = player {
         // This is a lobby participant (player)
-        store_connection_and_send_queued_messages(p.id, lobby_id, sender, &connections, &redis)
-            .await;
+        let outbound = store_connection_and_send_queued_messages(
+            p.id,
+            lobby_id,
+            sender,
+            protocol_version,
+            connections,
+            redis,
+        )
+        .await;
+        let receiver = close_when_idle(receiver, outbound);
+        emit_connection_event(
+            p.id,
+            Some(lobby_id),
+            SocketKind::Game,
+            ConnectionStage::JoinedLobby,
+        );
 
         let start_msg = LexiWarsServerMessage::Start {
             time: if game_started { 0 } else { 15 },
             started: game_started,
         };
-        broadcast_to_player(p.id, lobby_id, &start_msg, &connections, &redis).await;
+        broadcast_to_player(p.id, lobby_id, &start_msg, connections, redis).await;
 
-        setup_player_and_lobby(
-            p,
-            lobby_info.clone(),
-            players.clone(),
-            connected_player_ids.clone(),
-            game_started,
-            &connections,
-            &redis,
-            &bot,
-        )
-        .await;
+        setup_player_and_lobby(p, &ctx).await;
 
         // Handle player reconnection state
         if game_started {
+            // Their return may bring enough players back to end a pause
+            if let Err(e) = check_connected_players(lobby_id, connections, redis, bot).await {
+                tracing::error!("Failed to check connected players: {}", e);
+            }
+
             // Send current turn if available
             if let Ok(Some(current_turn_id)) = get_current_turn(lobby_id, redis.clone()).await {
                 if let Some(current_player) = players.iter().find(|gp| gp.id == current_turn_id) {
@@ -338,7 +384,7 @@ async fn handle_lexi_wars_socket(
                         current_turn: current_player.clone(),
                         countdown: 15, // Default countdown for reconnection
                     };
-                    broadcast_to_player(p.id, lobby_id, &turn_msg, &connections, &redis).await;
+                    broadcast_to_player(p.id, lobby_id, &turn_msg, connections, redis).await;
                 }
             }
 
@@ -347,8 +393,11 @@ async fn handle_lexi_wars_socket(
                 if current_turn_id == p.id {
                     if let Ok(Some(current_rule)) = get_current_rule(lobby_id, redis.clone()).await
                     {
-                        let rule_msg = LexiWarsServerMessage::Rule { rule: current_rule };
-                        broadcast_to_player(p.id, lobby_id, &rule_msg, &connections, &redis).await;
+                        let rule_msg = LexiWarsServerMessage::Rule {
+                            rule: current_rule,
+                            language: lobby_info.settings.language(),
+                        };
+                        broadcast_to_player(p.id, lobby_id, &rule_msg, connections, redis).await;
                     }
                 }
             }
@@ -361,7 +410,7 @@ async fn handle_lexi_wars_socket(
             p,
             lobby_id,
             receiver,
-            &connections,
+            connections,
             redis.clone(),
             bot.clone(),
         )
@@ -390,35 +439,62 @@ async fn handle_lexi_wars_socket(
             );
         }
 
-        remove_connection(p.id, &connections).await;
+        remove_connection(p.id, lobby_id, connections).await;
+
+        if game_started
+            && let Err(e) = check_connected_players(lobby_id, connections, redis, bot).await
+        {
+            tracing::error!("Failed to check connected players: {}", e);
+        }
     } else {
         // This is a spectator - use the provided user_id
         let spectator_id = user_id;
 
         // Add as spectator
-        if let Err(e) = add_spectator(lobby_id, spectator_id, redis.clone()).await {
-            tracing::error!("Failed to add spectator: {}", e);
-        }
+        let spectator_count = match add_spectator(lobby_id, spectator_id, redis.clone()).await {
+            Ok(count) => Some(count),
+            Err(e) => {
+                tracing::error!("Failed to add spectator: {}", e);
+                None
+            }
+        };
 
         // Store connection for spectator
-        store_connection_and_send_queued_messages(
+        let outbound = store_connection_and_send_queued_messages(
             spectator_id,
             lobby_id,
             sender,
-            &connections,
-            &redis,
+            protocol_version,
+            connections,
+            redis,
         )
         .await;
+        let receiver = close_when_idle(receiver, outbound);
+        emit_connection_event(
+            spectator_id,
+            Some(lobby_id),
+            SocketKind::Game,
+            ConnectionStage::Spectating,
+        );
 
         let start_msg = LexiWarsServerMessage::Start {
             time: if game_started { 0 } else { 15 },
             started: game_started,
         };
-        broadcast_to_player(spectator_id, lobby_id, &start_msg, &connections, &redis).await;
+        broadcast_to_player(spectator_id, lobby_id, &start_msg, connections, redis).await;
 
         // Send spectator message to indicate their status
         let spectator_msg = LexiWarsServerMessage::Spectator;
-        broadcast_to_player(spectator_id, lobby_id, &spectator_msg, &connections, &redis).await;
+        broadcast_to_player(spectator_id, lobby_id, &spectator_msg, connections, redis).await;
+
+        if let Some(spectator_count) = spectator_count {
+            let joined_msg = LexiWarsServerMessage::SpectatorJoined {
+                user_id: spectator_id,
+                spectator_count,
+            };
+            broadcast_to_lobby_and_spectators(&joined_msg, players, lobby_id, connections, redis)
+                .await;
+        }
 
         // Send current game state to spectator
         if game_started {
@@ -429,31 +505,44 @@ async fn handle_lexi_wars_socket(
                         current_turn: current_player.clone(),
                         countdown: 15, // Default countdown for spectator
                     };
-                    broadcast_to_player(spectator_id, lobby_id, &turn_msg, &connections, &redis)
+                    broadcast_to_player(spectator_id, lobby_id, &turn_msg, connections, redis)
                         .await;
                 }
             }
             // Spectators can see rules too
             if let Ok(Some(current_rule)) = get_current_rule(lobby_id, redis.clone()).await {
-                let rule_msg = LexiWarsServerMessage::Rule { rule: current_rule };
-                broadcast_to_player(spectator_id, lobby_id, &rule_msg, &connections, &redis).await;
+                let rule_msg = LexiWarsServerMessage::Rule {
+                    rule: current_rule,
+                    language: lobby_info.settings.language(),
+                };
+                broadcast_to_player(spectator_id, lobby_id, &rule_msg, connections, redis).await;
             }
         }
 
         // Handle spectator messages (they can only receive, not send game messages)
         if let Err(e) =
-            handle_spectator_messages(spectator_id, lobby_id, receiver, &connections, &redis).await
+            handle_spectator_messages(spectator_id, lobby_id, receiver, connections, redis).await
         {
             tracing::error!("Error handling spectator messages: {}", e);
         }
 
         // Handle spectator disconnection
-        if let Err(e) = remove_spectator(lobby_id, spectator_id, redis.clone()).await {
-            tracing::error!("Failed to remove spectator: {}", e);
+        remove_connection(spectator_id, lobby_id, connections).await;
+
+        match remove_spectator(lobby_id, spectator_id, redis.clone()).await {
+            Ok(spectator_count) => {
+                let left_msg = LexiWarsServerMessage::SpectatorLeft {
+                    user_id: spectator_id,
+                    spectator_count,
+                };
+                broadcast_to_lobby_and_spectators(&left_msg, players, lobby_id, connections, redis)
+                    .await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to remove spectator: {}", e);
+            }
         }
 
-        remove_connection(spectator_id, &connections).await;
-
         tracing::info!(
             "Spectator {} disconnected from lobby {}",
             spectator_id,
@@ -464,13 +553,12 @@ async fn handle_lexi_wars_socket(
 
 async fn handle_spectator_messages(
     spectator_id: Uuid,
-    _lobby_id: Uuid,
-    mut receiver: SplitStream<WebSocket>,
-    _connections: &ConnectionInfoMap,
-    _redis: &RedisClient,
+    lobby_id: Uuid,
+    mut receiver: impl StreamExt<Item = Result<axum::extract::ws::Message, axum::Error>> + Unpin,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    // Spectators can only receive messages, they just need to stay connected
-    // We'll consume the receiver to keep the connection alive but ignore most messages
+    // Spectators mostly receive; they can only react and bet
     while let Some(msg_result) = receiver.next().await {
         match msg_result {
             Ok(msg) => match msg {
@@ -484,6 +572,32 @@ async fn handle_spectator_messages(
                 axum::extract::ws::Message::Pong(_) => {
                     tracing::debug!("WebSocket pong from spectator {}", spectator_id);
                 }
+                axum::extract::ws::Message::Text(text) => {
+                    match serde_json::from_str::<LexiWarsClientMessage>(&text) {
+                        Ok(LexiWarsClientMessage::Reaction { emoji }) => {
+                            handle_reaction::<LexiWars>(
+                                lobby_id,
+                                spectator_id,
+                                emoji,
+                                connections,
+                                redis,
+                            )
+                            .await;
+                        }
+                        Ok(LexiWarsClientMessage::PlaceBet { winner_id, amount }) => {
+                            handle_bet::<LexiWars>(
+                                lobby_id,
+                                spectator_id,
+                                winner_id,
+                                amount,
+                                connections,
+                                redis,
+                            )
+                            .await;
+                        }
+                        _ => {}
+                    }
+                }
                 _ => {
                     // Spectators can't send game messages, but we'll just ignore them
                 }
@@ -498,16 +612,17 @@ async fn handle_spectator_messages(
     Ok(())
 }
 
-async fn setup_player_and_lobby(
-    player: &Player,
-    lobby_info: LobbyInfo,
-    players: Vec<Player>,
-    connected_player_ids: Vec<Uuid>,
-    game_started: bool,
-    connections: &ConnectionInfoMap,
-    redis: &RedisClient,
-    telegram_bot: &teloxide::Bot,
-) {
+async fn setup_player_and_lobby(player: &Player, ctx: &SocketContext) {
+    let SocketContext {
+        game_started,
+        ref players,
+        ref connected_player_ids,
+        ref connections,
+        ref lobby_info,
+        ref redis,
+        bot: ref telegram_bot,
+        ..
+    } = *ctx;
     let lobby_id = lobby_info.id;
 
     // Initialize game state if not exists
@@ -519,7 +634,7 @@ async fn setup_player_and_lobby(
             .is_none()
         {
             let rule_context = RuleContext {
-                min_word_length: 4,
+                min_word_length: lobby_info.settings.difficulty().min_word_length(),
                 random_letter: generate_random_letter(),
             };
             let _ = set_rule_context(lobby_id, &rule_context, redis.clone()).await;
@@ -536,6 +651,13 @@ async fn setup_player_and_lobby(
         }
     }
 
+    // Practice bots come online with the first player to reach the game
+    let connected_bot_ids = if game_started {
+        Vec::new()
+    } else {
+        connect_bots(lobby_id, connected_player_ids, redis).await
+    };
+
     // Track connected player by adding to Redis connected players set
     if !connected_player_ids.contains(&player.id) {
         // Verify the player is actually part of the lobby before adding to connected players
@@ -554,11 +676,15 @@ async fn setup_player_and_lobby(
 
     // Get updated count for logging and auto-start check
     let updated_connected_count = connected_player_ids.len()
+        + connected_bot_ids.len()
         + if connected_player_ids.contains(&player.id) {
             0
         } else {
             1
         };
+    let other_players_connected = connected_player_ids
+        .iter()
+        .any(|id| *id != player.id && !is_bot(*id));
 
     tracing::info!(
         "Player {} connected to lobby {}. Connected: {}/{}",
@@ -569,7 +695,7 @@ async fn setup_player_and_lobby(
     );
 
     // Start auto-start timer when first player connects and game hasn't started
-    if updated_connected_count == 1 && !game_started {
+    if !other_players_connected && !game_started {
         tracing::info!(
             "First player connected, starting auto-start timer for lobby {}",
             lobby_id
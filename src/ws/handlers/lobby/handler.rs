@@ -1,13 +1,14 @@
 use axum::{
     extract::{ConnectInfo, Path, Query, State, WebSocketUpgrade, ws::WebSocket},
+    http::HeaderMap,
     response::IntoResponse,
 };
 use futures::{SinkExt, StreamExt};
 use std::net::SocketAddr;
 
 use crate::ws::handlers::{
-    lobby::message_handler::handler::send_error_to_player,
-    utils::store_connection_and_send_queued_messages,
+    lobby::message_handler::handler::report_error_to_player,
+    utils::{remove_connection, send_hello, store_connection_and_send_queued_messages, traced},
 };
 use crate::{
     db::{
@@ -16,52 +17,65 @@ use crate::{
             countdown::get_lobby_countdown,
             get::{get_lobby_info, get_lobby_player, get_lobby_players},
             join_requests::get_player_join_request,
-            patch::{join_lobby, leave_lobby},
+            patch::{join_lobby, leave_lobby, next_lobby_owner, transfer_lobby_ownership},
         },
-        user::get::get_user_by_id,
+        user::{get::get_user_by_id, linked_accounts::record_connection},
     },
+    http::handlers::auth::device_name,
     models::{
         game::{LobbyState, Player, PlayerState, WsQueryParams},
         lobby::{JoinState, LobbyServerMessage},
+        protocol::ProtocolVersion,
     },
-    state::{AppState, ChatConnectionInfoMap, RedisClient},
-    ws::handlers::lobby::message_handler::handler::{self, get_pending_players},
+    state::{AppState, ChatConnectionInfoMap, ConnectionInfoMap, RedisClient},
+    ws::handlers::lobby::message_handler::{
+        handler::{self, get_pending_players},
+        transfer_ownership::announce_new_owner,
+    },
+    ws::lifecycle::{ConnectionStage, DisconnectReason, close_when_idle, emit_connection_event},
+    ws::sessions::{SocketKind, authorize_socket},
 };
-use crate::{state::ConnectionInfoMap, ws::handlers::utils::remove_connection};
 use axum::extract::ws::{CloseFrame, Message};
 use uuid::Uuid;
 
+/// How long a disconnected creator keeps the lobby before it is handed on
+const CREATOR_RECONNECT_GRACE_SECS: u64 = 30;
+
 pub async fn lobby_ws_handler(
     ws: WebSocketUpgrade,
     Query(query): Query<WsQueryParams>,
     Path(lobby_id): Path<Uuid>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, (axum::http::StatusCode, String)> {
     tracing::debug!("New lobby WS connection from {}", addr);
 
     let player_id = query.user_id;
+    authorize_socket(&query, SocketKind::Game, state.redis.clone()).await?;
+    record_connection(
+        player_id,
+        addr.ip(),
+        device_name(&headers),
+        state.redis.clone(),
+    );
+    let protocol_version = ProtocolVersion::negotiate(query.protocol_version);
     let redis = state.redis.clone();
-    let connections = state.connections.clone();
-    let chat_connections = state.chat_connections.clone();
-    let bot = state.bot.clone();
 
     let players = get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone())
         .await
         .map_err(|e| e.to_response())?;
 
     if let Some(matched_player) = players.iter().find(|p| p.id == player_id).cloned() {
-        return Ok(ws.on_upgrade(move |socket| {
+        return Ok(ws.on_upgrade(traced(move |socket| {
             handle_lobby_socket(
                 socket,
                 lobby_id,
                 matched_player.into(),
-                connections,
-                chat_connections,
-                redis,
-                bot,
+                protocol_version,
+                state,
             )
-        }));
+        })));
     }
 
     let user = get_user_by_id(player_id, redis.clone())
@@ -90,33 +104,33 @@ pub async fn lobby_ws_handler(
         tx_id: None,
         claim: None,
         prize: None,
+        wars_point: None,
         last_ping: None,
+        joined_at: None,
         user: Some(user.clone()),
     };
 
-    Ok(ws.on_upgrade(move |socket| {
-        handle_lobby_socket(
-            socket,
-            lobby_id,
-            idle_player,
-            connections,
-            chat_connections,
-            redis,
-            bot,
-        )
-    }))
+    Ok(ws.on_upgrade(traced(move |socket| {
+        handle_lobby_socket(socket, lobby_id, idle_player, protocol_version, state)
+    })))
 }
 
 async fn handle_lobby_socket(
     socket: WebSocket,
     lobby_id: Uuid,
     player: Player,
-    connections: ConnectionInfoMap,
-    chat_connections: ChatConnectionInfoMap,
-    redis: RedisClient,
-    bot: teloxide::Bot,
+    protocol_version: ProtocolVersion,
+    state: AppState,
 ) {
+    let AppState {
+        connections,
+        chat_connections,
+        redis,
+        bot,
+        ..
+    } = state;
     let (mut sender, receiver) = socket.split();
+    send_hello(&mut sender, protocol_version).await;
 
     // Check lobby state immediately upon connection
     match get_lobby_info(lobby_id, redis.clone()).await {
@@ -138,7 +152,10 @@ async fn handle_lobby_socket(
                 }
             };
 
-            if let Err(e) = sender.send(Message::Text(serialized.into())).await {
+            if let Err(e) = sender
+                .send(Message::Text(protocol_version.adapt(&serialized).into()))
+                .await
+            {
                 tracing::error!("Failed to send countdown to player {}: {}", player.id, e);
                 return;
             }
@@ -149,14 +166,7 @@ async fn handle_lobby_socket(
                     Ok(players) => players.into_iter().map(|p| p.id).collect::<Vec<_>>(),
                     Err(e) => {
                         tracing::error!("❌ Failed to get ready players: {}", e);
-                        send_error_to_player(
-                            player.id,
-                            lobby_id,
-                            e.to_string(),
-                            &connections,
-                            &redis,
-                        )
-                        .await;
+                        report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
                         vec![]
                     }
                 };
@@ -184,7 +194,10 @@ async fn handle_lobby_socket(
                 }
             };
 
-            if let Err(e) = sender.send(Message::Text(serialized.into())).await {
+            if let Err(e) = sender
+                .send(Message::Text(protocol_version.adapt(&serialized).into()))
+                .await
+            {
                 tracing::error!("Failed to send game state to player {}: {}", player.id, e);
                 return;
             }
@@ -207,7 +220,10 @@ async fn handle_lobby_socket(
                         }
                     };
 
-                    if let Err(e) = sender.send(Message::Text(serialized.into())).await {
+                    if let Err(e) = sender
+                        .send(Message::Text(protocol_version.adapt(&serialized).into()))
+                        .await
+                    {
                         tracing::error!("Failed to send join state to player {}: {}", player.id, e);
                         return;
                     }
@@ -252,7 +268,10 @@ async fn handle_lobby_socket(
                             }
                         };
 
-                        if let Err(e) = sender.send(Message::Text(serialized.into())).await {
+                        if let Err(e) = sender
+                            .send(Message::Text(protocol_version.adapt(&serialized).into()))
+                            .await
+                        {
                             tracing::error!(
                                 "Failed to send pending players to player {}: {}",
                                 player.id,
@@ -278,11 +297,7 @@ async fn handle_lobby_socket(
                     player.id
                 );
 
-                let close_frame = CloseFrame {
-                    code: axum::extract::ws::close_code::NORMAL,
-                    reason: "inProgress".into(),
-                };
-
+                let close_frame = DisconnectReason::GameInProgress.close_frame();
                 let _ = sender.send(Message::Close(Some(close_frame))).await;
                 return;
             }
@@ -294,11 +309,7 @@ async fn handle_lobby_socket(
                     player.id
                 );
 
-                let close_frame = CloseFrame {
-                    code: axum::extract::ws::close_code::NORMAL,
-                    reason: "finished".into(),
-                };
-
+                let close_frame = DisconnectReason::GameFinished.close_frame();
                 let _ = sender.send(Message::Close(Some(close_frame))).await;
                 return;
             }
@@ -314,8 +325,22 @@ async fn handle_lobby_socket(
         }
     }
 
-    store_connection_and_send_queued_messages(player.id, lobby_id, sender, &connections, &redis)
-        .await;
+    let outbound = store_connection_and_send_queued_messages(
+        player.id,
+        lobby_id,
+        sender,
+        protocol_version,
+        &connections,
+        &redis,
+    )
+    .await;
+    let receiver = close_when_idle(receiver, outbound);
+    emit_connection_event(
+        player.id,
+        Some(lobby_id),
+        SocketKind::Game,
+        ConnectionStage::JoinedLobby,
+    );
 
     if let Ok(players) = get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
     {
@@ -341,7 +366,7 @@ async fn handle_lobby_socket(
     )
     .await;
 
-    remove_connection(player.id, &connections).await;
+    remove_connection(player.id, lobby_id, &connections).await;
 
     match get_lobby_player(lobby_id, player.id, redis.clone()).await {
         Ok(current_player) => {
@@ -364,6 +389,20 @@ async fn handle_lobby_socket(
         }
     }
 
+    let creator_left = matches!(
+        get_lobby_info(lobby_id, redis.clone()).await,
+        Ok(info) if info.creator.id == player.id && info.state == LobbyState::Waiting
+    );
+    if creator_left {
+        tokio::spawn(transfer_if_creator_gone(
+            lobby_id,
+            player.id,
+            connections.clone(),
+            chat_connections.clone(),
+            redis.clone(),
+        ));
+    }
+
     if let Ok(players) = get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
     {
         let msg = LobbyServerMessage::PlayerUpdated { players };
@@ -371,3 +410,55 @@ async fn handle_lobby_socket(
             .await;
     }
 }
+
+/// Passes the lobby on if its creator has not reconnected within the grace period.
+async fn transfer_if_creator_gone(
+    lobby_id: Uuid,
+    creator_id: Uuid,
+    connections: ConnectionInfoMap,
+    chat_connections: ChatConnectionInfoMap,
+    redis: RedisClient,
+) {
+    tokio::time::sleep(std::time::Duration::from_secs(CREATOR_RECONNECT_GRACE_SECS)).await;
+
+    if connections.lock().await.contains_key(&creator_id) {
+        return;
+    }
+
+    let still_waiting = matches!(
+        get_lobby_info(lobby_id, redis.clone()).await,
+        Ok(info) if info.creator.id == creator_id && info.state == LobbyState::Waiting
+    );
+    if !still_waiting {
+        return;
+    }
+
+    let new_owner = match next_lobby_owner(lobby_id, creator_id, redis.clone()).await {
+        Ok(Some(new_owner)) => new_owner,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::error!("Failed to pick next owner for lobby {}: {}", lobby_id, e);
+            return;
+        }
+    };
+
+    if let Err(e) = transfer_lobby_ownership(lobby_id, new_owner, redis.clone()).await {
+        tracing::error!("Failed to transfer lobby {} ownership: {}", lobby_id, e);
+        return;
+    }
+
+    tracing::info!(
+        "Creator {} disconnected, lobby {} passed to {}",
+        creator_id,
+        lobby_id,
+        new_owner
+    );
+    announce_new_owner(
+        lobby_id,
+        creator_id,
+        &connections,
+        &chat_connections,
+        &redis,
+    )
+    .await;
+}
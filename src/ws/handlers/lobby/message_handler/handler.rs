@@ -1,26 +1,34 @@
 use axum::extract::ws::Message;
 use chrono::Utc;
-use futures::{SinkExt, StreamExt};
+use futures::StreamExt;
 use uuid::Uuid;
 
 use crate::{
-    db::lobby::{
-        get::get_lobby_players,
-        join_requests::{get_lobby_join_requests, get_player_join_request, update_join_request},
+    db::{
+        lobby::{
+            get::get_lobby_players,
+            join_requests::{
+                get_lobby_join_requests, get_player_join_request, update_join_request,
+            },
+        },
+        user::post::record_user_error,
     },
     errors::AppError,
     models::{
         User,
         chat::ChatServerMessage,
+        error_code::ErrorCode,
         game::{Player, PlayerState},
         lobby::{JoinState, LobbyClientMessage, LobbyServerMessage, PendingJoin},
     },
     state::{ChatConnectionInfoMap, ConnectionInfoMap, RedisClient},
+    ws::errors::{SocketErrorAction, close_player_socket, socket_error_action},
     ws::handlers::{
         chat::utils::send_chat_message_to_player,
         lobby::message_handler::{
-            join_lobby::join_lobby, kick_player, last_ping, leave_lobby, permit_join, ping,
-            request_join, request_leave, update_game_state, update_player_state,
+            invite_friend, join_lobby::join_lobby, kick_player, last_ping, leave_lobby,
+            permit_join, ping, ready_check_ack, request_join, request_leave, transfer_ownership,
+            update_game_state, update_player_state,
         },
         utils::queue_message_for_player,
     },
@@ -42,44 +50,51 @@ pub async fn broadcast_to_lobby(
     };
 
     if let Ok(players) = get_lobby_players(lobby_id, None, redis.clone()).await {
-        let connection_guard = connections.lock().await;
+        let targets: Vec<_> = {
+            let connection_guard = connections.lock().await;
+            players
+                .iter()
+                .map(|player| connection_guard.get(&player.id).cloned())
+                .collect()
+        };
 
-        for player in &players {
-            if let Some(conn_info) = connection_guard.get(&player.id) {
-                // Try to send immediately
-                let mut sender = conn_info.sender.lock().await;
-                if let Err(e) = sender.send(Message::Text(serialized.clone().into())).await {
+        for (player, conn_info) in players.iter().zip(targets) {
+            if let Some(conn_info) = conn_info {
+                // Hand the message to the player's writer
+                if let Err(e) = conn_info.sender.send(Message::Text(
+                    conn_info.protocol_version.adapt(&serialized).into(),
+                )) {
                     tracing::debug!("Failed to send message to player {}: {}", player.id, e);
 
                     // Only queue the message if it should be queued
-                    if msg.should_queue() {
-                        drop(sender); // Release the sender lock
-                        drop(connection_guard); // Release the connection guard
-
-                        if let Err(queue_err) = queue_message_for_player(
+                    if msg.should_queue()
+                        && let Err(queue_err) = queue_message_for_player(
                             player.id,
                             lobby_id,
                             serialized.clone(),
+                            msg.queue_policy(),
                             &redis,
                         )
                         .await
-                        {
-                            tracing::error!(
-                                "Failed to queue message for player {}: {}",
-                                player.id,
-                                queue_err
-                            );
-                        }
+                    {
+                        tracing::error!(
+                            "Failed to queue message for player {}: {}",
+                            player.id,
+                            queue_err
+                        );
                     }
-
-                    return; // Exit early to avoid double-locking
                 }
             } else {
                 // Player not connected, only queue if message should be queued
                 if msg.should_queue() {
-                    if let Err(e) =
-                        queue_message_for_player(player.id, lobby_id, serialized.clone(), &redis)
-                            .await
+                    if let Err(e) = queue_message_for_player(
+                        player.id,
+                        lobby_id,
+                        serialized.clone(),
+                        msg.queue_policy(),
+                        &redis,
+                    )
+                    .await
                     {
                         tracing::error!(
                             "Failed to queue message for offline player {}: {}",
@@ -133,16 +148,41 @@ async fn notify_chat_about_lobby_changes(
 pub async fn send_error_to_player(
     player_id: Uuid,
     lobby_id: Uuid,
+    code: ErrorCode,
     message: impl Into<String>,
     connection_info: &ConnectionInfoMap,
     redis: &RedisClient,
 ) {
-    let error_msg = LobbyServerMessage::Error {
-        message: message.into(),
-    };
+    let message: String = message.into();
+
+    // Keep a short history for the support view
+    if let Err(e) = record_user_error(player_id, lobby_id, message.clone(), redis.clone()).await {
+        tracing::debug!("Failed to record error for player {}: {}", player_id, e);
+    }
+
+    let error_msg = LobbyServerMessage::Error { code, message };
     send_to_player(player_id, lobby_id, connection_info, &error_msg, redis).await;
 }
 
+/// Sends the player the error their message ran into, or closes their socket
+/// when it can't go on.
+pub async fn report_error_to_player(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    error: &AppError,
+    connection_info: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    match socket_error_action(lobby_id, error, redis).await {
+        SocketErrorAction::Reply { code, message } => {
+            send_error_to_player(player_id, lobby_id, code, message, connection_info, redis).await;
+        }
+        SocketErrorAction::Close(reason) => {
+            close_player_socket(player_id, reason, connection_info).await;
+        }
+    }
+}
+
 pub async fn send_to_player(
     player_id: Uuid,
     lobby_id: Uuid,
@@ -158,32 +198,38 @@ pub async fn send_to_player(
         }
     };
 
-    let conns = connection_info.lock().await;
-    if let Some(conn_info) = conns.get(&player_id) {
-        let mut sender = conn_info.sender.lock().await;
-        if let Err(e) = sender.send(Message::Text(serialized.clone().into())).await {
+    let conn_info = connection_info.lock().await.get(&player_id).cloned();
+    if let Some(conn_info) = conn_info {
+        if let Err(e) = conn_info.sender.send(Message::Text(
+            conn_info.protocol_version.adapt(&serialized).into(),
+        )) {
             tracing::debug!("Failed to send message to player {}: {}", player_id, e);
 
             // Only queue the message if it should be queued
-            if msg.should_queue() {
-                drop(sender);
-                drop(conns);
-
-                if let Err(queue_err) =
-                    queue_message_for_player(player_id, lobby_id, serialized, redis).await
-                {
-                    tracing::error!(
-                        "Failed to queue message for player {}: {}",
-                        player_id,
-                        queue_err
-                    );
-                }
+            if msg.should_queue()
+                && let Err(queue_err) = queue_message_for_player(
+                    player_id,
+                    lobby_id,
+                    serialized,
+                    msg.queue_policy(),
+                    redis,
+                )
+                .await
+            {
+                tracing::error!(
+                    "Failed to queue message for player {}: {}",
+                    player_id,
+                    queue_err
+                );
             }
         }
     } else {
         // Player not connected, only queue if message should be queued
         if msg.should_queue() {
-            if let Err(e) = queue_message_for_player(player_id, lobby_id, serialized, redis).await {
+            if let Err(e) =
+                queue_message_for_player(player_id, lobby_id, serialized, msg.queue_policy(), redis)
+                    .await
+            {
                 tracing::error!(
                     "Failed to queue message for offline player {}: {}",
                     player_id,
@@ -328,6 +374,31 @@ pub async fn handle_incoming_messages(
                                 )
                                 .await
                             }
+                            LobbyClientMessage::InviteFriend { user_id } => {
+                                invite_friend(
+                                    user_id,
+                                    lobby_id,
+                                    player,
+                                    connections,
+                                    chat_connections,
+                                    &redis,
+                                )
+                                .await
+                            }
+                            LobbyClientMessage::ReadyCheckAck => {
+                                ready_check_ack(lobby_id, player, connections, &redis).await
+                            }
+                            LobbyClientMessage::TransferOwnership { user_id } => {
+                                transfer_ownership(
+                                    user_id,
+                                    lobby_id,
+                                    player,
+                                    connections,
+                                    chat_connections,
+                                    &redis,
+                                )
+                                .await
+                            }
                         }
                     } else {
                         tracing::debug!("uncaught message: {text}");
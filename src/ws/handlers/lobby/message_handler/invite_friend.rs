@@ -0,0 +1,103 @@
+use crate::{
+    db::{lobby::get::get_lobby_info, user::friends::are_friends},
+    models::{
+        chat::ChatServerMessage,
+        error_code::ErrorCode,
+        friends::LobbyInvite,
+        game::{LobbyState, Player},
+        lobby::LobbyServerMessage,
+    },
+    state::{ChatConnectionInfoMap, ConnectionInfoMap, RedisClient},
+    ws::handlers::{
+        chat::utils::send_chat_message_to_player,
+        lobby::message_handler::handler::{
+            report_error_to_player, send_error_to_player, send_to_player,
+        },
+    },
+};
+use uuid::Uuid;
+
+pub async fn invite_friend(
+    friend_id: Uuid,
+    lobby_id: Uuid,
+    player: &Player,
+    connections: &ConnectionInfoMap,
+    chat_connections: &ChatConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    match are_friends(player.id, friend_id, redis.clone()).await {
+        Ok(true) => {}
+        Ok(false) => {
+            send_error_to_player(
+                player.id,
+                lobby_id,
+                ErrorCode::NotFriends,
+                "You can only invite friends",
+                connections,
+                redis,
+            )
+            .await;
+            return;
+        }
+        Err(e) => {
+            tracing::error!("Failed to check friendship: {}", e);
+            report_error_to_player(player.id, lobby_id, &e, connections, redis).await;
+            return;
+        }
+    }
+
+    let lobby_info = match get_lobby_info(lobby_id, redis.clone()).await {
+        Ok(info) => info,
+        Err(e) => {
+            tracing::error!("Failed to fetch lobby info: {}", e);
+            report_error_to_player(player.id, lobby_id, &e, connections, redis).await;
+            return;
+        }
+    };
+
+    if lobby_info.state != LobbyState::Waiting {
+        send_error_to_player(
+            player.id,
+            lobby_id,
+            ErrorCode::InvalidLobbyState,
+            "Friends can only be invited while the lobby is waiting",
+            connections,
+            redis,
+        )
+        .await;
+        return;
+    }
+
+    let invite = LobbyInvite {
+        lobby_id,
+        lobby_name: lobby_info.name,
+        from: player.clone().into_user(),
+    };
+
+    // Prefer the game socket, fall back to an open chat socket
+    let on_lobby_socket = connections.lock().await.contains_key(&friend_id);
+    let on_chat_socket = chat_connections.lock().await.contains_key(&friend_id);
+
+    if on_lobby_socket {
+        let msg = LobbyServerMessage::LobbyInvite { invite };
+        send_to_player(friend_id, lobby_id, connections, &msg, redis).await;
+    } else if on_chat_socket {
+        let msg = ChatServerMessage::LobbyInvite { invite };
+        send_chat_message_to_player(friend_id, &msg, chat_connections).await;
+    } else {
+        send_error_to_player(
+            player.id,
+            lobby_id,
+            ErrorCode::FriendOffline,
+            "Your friend is not online",
+            connections,
+            redis,
+        )
+        .await;
+        return;
+    }
+
+    tracing::info!("{} invited {} to lobby {}", player.id, friend_id, lobby_id);
+    let msg = LobbyServerMessage::InviteSent { user_id: friend_id };
+    send_to_player(player.id, lobby_id, connections, &msg, redis).await;
+}
@@ -1,13 +1,16 @@
 use crate::{
     db::lobby::{get::get_lobby_players, join_requests::get_player_join_request, patch},
     models::{
+        error_code::ErrorCode,
         game::{Player, PlayerState},
-        lobby::{JoinState, LobbyServerMessage},
+        lobby::{JoinOutcome, JoinState, LobbyServerMessage},
     },
     state::{ChatConnectionInfoMap, ConnectionInfoMap, RedisClient},
     ws::handlers::lobby::message_handler::{
         broadcast_to_lobby,
-        handler::{get_pending_players, send_error_to_player},
+        handler::{
+            get_pending_players, report_error_to_player, send_error_to_player, send_to_player,
+        },
     },
 };
 use uuid::Uuid;
@@ -24,31 +27,58 @@ pub async fn join_lobby(
     match get_player_join_request(lobby_id, player.id, redis.clone()).await {
         Ok(Some(join_request)) => {
             if join_request.state == JoinState::Allowed {
-                if let Err(e) = patch::join_lobby(
+                match patch::join_lobby(
                     lobby_id,
                     player.id,
-                    tx_id,
+                    tx_id.clone(),
                     PlayerState::Joined,
                     redis.clone(),
                 )
                 .await
                 {
-                    tracing::error!("Failed to join lobby: {}", e);
-                    send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis)
+                    Err(e) => {
+                        tracing::error!("Failed to join lobby: {}", e);
+                        report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
+                    }
+                    Ok(JoinOutcome::Pending) => {
+                        tracing::info!(
+                            "{} join to lobby {} pending tx confirmation",
+                            player.id,
+                            lobby_id
+                        );
+                        let msg = LobbyServerMessage::JoinPending {
+                            tx_id: tx_id.unwrap_or_default(),
+                        };
+                        send_to_player(player.id, lobby_id, connections, &msg, redis).await;
+                    }
+                    Ok(JoinOutcome::Closed) => {
+                        send_error_to_player(
+                            player.id,
+                            lobby_id,
+                            ErrorCode::InvalidLobbyState,
+                            "Lobby is no longer accepting players",
+                            connections,
+                            redis,
+                        )
                         .await;
-                } else if let Ok(players) =
-                    get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
-                {
-                    tracing::info!("{} joined lobby {} successfully", player.id, lobby_id);
-                    let msg = LobbyServerMessage::PlayerUpdated { players };
-                    broadcast_to_lobby(
-                        lobby_id,
-                        &msg,
-                        &connections,
-                        Some(&chat_connections),
-                        redis.clone(),
-                    )
-                    .await;
+                    }
+                    Ok(JoinOutcome::Joined) => {
+                        if let Ok(players) =
+                            get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone())
+                                .await
+                        {
+                            tracing::info!("{} joined lobby {} successfully", player.id, lobby_id);
+                            let msg = LobbyServerMessage::PlayerUpdated { players };
+                            broadcast_to_lobby(
+                                lobby_id,
+                                &msg,
+                                &connections,
+                                Some(&chat_connections),
+                                redis.clone(),
+                            )
+                            .await;
+                        }
+                    }
                 }
 
                 if let Ok(pending_players) = get_pending_players(lobby_id, redis.clone()).await {
@@ -57,9 +87,15 @@ pub async fn join_lobby(
                 }
             } else {
                 // Player has a request but it's not allowed
-                let message = match join_request.state {
-                    JoinState::Pending => "Join request is still pending approval",
-                    JoinState::Rejected => "Join request has been rejected",
+                let (code, message) = match join_request.state {
+                    JoinState::Pending => (
+                        ErrorCode::JoinRequestPending,
+                        "Join request is still pending approval",
+                    ),
+                    JoinState::Rejected => (
+                        ErrorCode::JoinRequestRejected,
+                        "Join request has been rejected",
+                    ),
                     JoinState::Allowed => unreachable!(), // Already handled above
                 };
 
@@ -69,7 +105,8 @@ pub async fn join_lobby(
                     lobby_id,
                     join_request.state
                 );
-                send_error_to_player(player.id, lobby_id, message, &connections, &redis).await;
+                send_error_to_player(player.id, lobby_id, code, message, &connections, &redis)
+                    .await;
             }
         }
         Ok(None) => {
@@ -82,6 +119,7 @@ pub async fn join_lobby(
             send_error_to_player(
                 player.id,
                 lobby_id,
+                ErrorCode::JoinRequestMissing,
                 "No join request found. Please request to join first.",
                 &connections,
                 &redis,
@@ -99,6 +137,7 @@ pub async fn join_lobby(
             send_error_to_player(
                 player.id,
                 lobby_id,
+                ErrorCode::InternalError,
                 "Failed to verify join request",
                 &connections,
                 &redis,
@@ -1,5 +1,6 @@
 use crate::{
     db::{
+        audit::post::record_audit_entry,
         lobby::{
             get::{get_lobby_info, get_lobby_players},
             join_requests::remove_join_request,
@@ -8,14 +9,17 @@ use crate::{
         user::get::get_user_by_id,
     },
     models::{
+        audit::AuditEntry,
+        error_code::ErrorCode,
         game::{LobbyState, Player, PlayerState},
         lobby::LobbyServerMessage,
     },
     state::{ChatConnectionInfoMap, ConnectionInfoMap, RedisClient},
     ws::handlers::lobby::message_handler::{
         broadcast_to_lobby,
-        handler::{send_error_to_player, send_to_player},
+        handler::{report_error_to_player, send_error_to_player, send_to_player},
     },
+    ws::lifecycle::DisconnectReason,
 };
 use uuid::Uuid;
 
@@ -32,7 +36,7 @@ pub async fn kick_player(
         Ok(info) => info,
         Err(e) => {
             tracing::error!("Failed to fetch lobby info: {}", e);
-            send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis).await;
+            report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
             return;
         }
     };
@@ -42,6 +46,7 @@ pub async fn kick_player(
         send_error_to_player(
             player.id,
             lobby_id,
+            ErrorCode::NotLobbyCreator,
             "Only creator can kick players",
             &connections,
             &redis,
@@ -55,6 +60,7 @@ pub async fn kick_player(
         send_error_to_player(
             player.id,
             lobby_id,
+            ErrorCode::InvalidLobbyState,
             "Cannot kick player when game is in starting",
             &connections,
             &redis,
@@ -66,9 +72,20 @@ pub async fn kick_player(
     // Remove player
     if let Err(e) = leave_lobby(lobby_id, player_id, redis.clone(), bot).await {
         tracing::error!("Failed to kick player: {}", e);
-        send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis).await;
-    } else if let Ok(players) =
-        get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
+        report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
+        return;
+    }
+
+    let entry = AuditEntry::new(
+        player.id,
+        format!("kick_player:{lobby_id}"),
+        Some(player_id),
+    );
+    if let Err(e) = record_audit_entry(&entry, redis.clone()).await {
+        tracing::error!("Failed to write audit entry: {}", e);
+    }
+
+    if let Ok(players) = get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
     {
         if let Err(e) = remove_join_request(lobby_id, player_id, redis.clone()).await {
             tracing::warn!(
@@ -92,8 +109,7 @@ pub async fn kick_player(
             Ok(user) => user,
             Err(e) => {
                 tracing::error!("Failed to fetch player info: {}", e);
-                send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis)
-                    .await;
+                report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
                 return;
             }
         };
@@ -120,5 +136,10 @@ pub async fn kick_player(
             &redis,
         )
         .await;
+
+        // Queued behind the messages above, so the client sees why first
+        if let Some(conn_info) = connections.lock().await.get(&player_id) {
+            conn_info.sender.close(DisconnectReason::Kicked);
+        }
     }
 }
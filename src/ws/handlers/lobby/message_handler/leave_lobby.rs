@@ -7,6 +7,7 @@ use crate::{
         },
         user::patch::decrease_wars_point,
     },
+    games::core::prize::leave_penalty,
     models::{
         game::{Player, PlayerState},
         lobby::LobbyServerMessage,
@@ -14,7 +15,8 @@ use crate::{
     state::{ChatConnectionInfoMap, ConnectionInfoMap, RedisClient},
     ws::handlers::lobby::message_handler::{
         broadcast_to_lobby,
-        handler::{send_error_to_player, send_to_player},
+        handler::{report_error_to_player, send_to_player},
+        transfer_ownership::announce_new_owner,
     },
 };
 use uuid::Uuid;
@@ -27,9 +29,13 @@ pub async fn leave_lobby(
     redis: &RedisClient,
     bot: teloxide::Bot,
 ) {
+    // Read before leaving, since a departing creator hands the lobby on
+    let lobby_info = get_lobby_info(lobby_id, redis.clone()).await;
+    let was_creator = matches!(&lobby_info, Ok(info) if info.creator.id == player.id);
+
     if let Err(e) = patch::leave_lobby(lobby_id, player.id, redis.clone(), bot).await {
         tracing::error!("Failed to leave lobby: {}", e);
-        send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis).await;
+        report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
     } else if let Ok(players) =
         get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
     {
@@ -44,47 +50,44 @@ pub async fn leave_lobby(
             );
         }
 
-        match get_lobby_info(lobby_id, redis.clone()).await {
-            Ok(lobby_info) => {
-                if lobby_info.creator.id != player.id {
-                    // Subtract 10 wars points for leaving the lobby (only for non-creators)
-                    match decrease_wars_point(player.id, 10.0, redis.clone()).await {
-                        Ok(new_total) => {
-                            tracing::info!(
-                                "Subtracted 10 wars points from player {} for leaving lobby. New total: {}",
-                                player.id,
-                                new_total
-                            );
+        match lobby_info {
+            Ok(lobby_info) => match leave_penalty(
+                lobby_info.settings.casual,
+                lobby_info.creator.id == player.id,
+            ) {
+                Some(amount) => match decrease_wars_point(player.id, amount, redis.clone()).await {
+                    Ok(new_total) => {
+                        tracing::info!(
+                            "Subtracted {} wars points from player {} for leaving lobby. New total: {}",
+                            amount,
+                            player.id,
+                            new_total
+                        );
 
-                            let wars_point_msg = LobbyServerMessage::WarsPointDeduction {
-                                amount: 10.0,
-                                new_total,
-                                reason: "Left lobby".to_string(),
-                            };
-                            send_to_player(
-                                player.id,
-                                lobby_id,
-                                connections,
-                                &wars_point_msg,
-                                redis,
-                            )
+                        let wars_point_msg = LobbyServerMessage::WarsPointDeduction {
+                            amount,
+                            new_total,
+                            reason: "Left lobby".to_string(),
+                        };
+                        send_to_player(player.id, lobby_id, connections, &wars_point_msg, redis)
                             .await;
-                        }
-                        Err(e) => {
-                            tracing::error!(
-                                "Failed to subtract wars points from player {} for leaving lobby: {}",
-                                player.id,
-                                e
-                            );
-                        }
                     }
-                } else {
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to subtract wars points from player {} for leaving lobby: {}",
+                            player.id,
+                            e
+                        );
+                    }
+                },
+                None => {
                     tracing::debug!(
-                        "Player {} is the lobby creator, no wars points deducted for leaving",
-                        player.id
+                        "Player {} left lobby {} without a wars point penalty",
+                        player.id,
+                        lobby_id
                     );
                 }
-            }
+            },
             Err(e) => {
                 tracing::error!(
                     "Failed to get lobby info to check creator: {}. Proceeding without wars point deduction.",
@@ -93,6 +96,7 @@ pub async fn leave_lobby(
             }
         }
 
+        let players_empty = players.is_empty();
         let msg = LobbyServerMessage::PlayerUpdated { players };
         broadcast_to_lobby(
             lobby_id,
@@ -103,6 +107,11 @@ pub async fn leave_lobby(
         )
         .await;
         send_to_player(player.id, lobby_id, &connections, &msg, redis).await;
+
+        if was_creator && !players_empty {
+            announce_new_owner(lobby_id, player.id, connections, chat_connections, redis).await;
+        }
+
         let left_msg = LobbyServerMessage::Left;
         send_to_player(player.id, lobby_id, &connections, &left_msg, redis).await;
     }
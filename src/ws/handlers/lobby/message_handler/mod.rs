@@ -1,24 +1,30 @@
 pub mod handler;
+pub mod invite_friend;
 pub mod join_lobby;
 pub mod kick_player;
 pub mod last_ping;
 pub mod leave_lobby;
 pub mod permit_join;
 pub mod ping;
+pub mod ready_check_ack;
 pub mod request_join;
 pub mod request_leave;
+pub mod transfer_ownership;
 pub mod update_game_state;
 pub mod update_player_state;
 
 pub use handler::broadcast_to_lobby;
 pub use handler::handle_incoming_messages;
+pub use invite_friend::invite_friend;
 pub use join_lobby::join_lobby;
 pub use kick_player::kick_player;
 pub use last_ping::last_ping;
 pub use leave_lobby::leave_lobby;
 pub use permit_join::permit_join;
 pub use ping::ping;
+pub use ready_check_ack::ready_check_ack;
 pub use request_join::request_join;
 pub use request_leave::request_leave;
+pub use transfer_ownership::transfer_ownership;
 pub use update_game_state::update_game_state;
 pub use update_player_state::update_player_state;
@@ -1,13 +1,17 @@
 use crate::{
     db::lobby::{get::get_lobby_info, join_requests::get_player_join_request},
     models::{
+        error_code::ErrorCode,
         game::{LobbyState, Player},
         lobby::{JoinState, LobbyServerMessage},
     },
     state::{ConnectionInfoMap, RedisClient},
     ws::handlers::lobby::message_handler::{
         broadcast_to_lobby,
-        handler::{get_pending_players, send_error_to_player, send_to_player, set_join_state},
+        handler::{
+            get_pending_players, report_error_to_player, send_error_to_player, send_to_player,
+            set_join_state,
+        },
     },
 };
 use uuid::Uuid;
@@ -24,7 +28,7 @@ pub async fn permit_join(
         Ok(info) => info,
         Err(e) => {
             tracing::error!("Failed to fetch lobby info: {}", e);
-            send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis).await;
+            report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
             return;
         }
     };
@@ -34,6 +38,7 @@ pub async fn permit_join(
         send_error_to_player(
             player.id,
             lobby_id,
+            ErrorCode::NotLobbyCreator,
             "Only lobby creator can permit joins".to_string(),
             &connections,
             &redis,
@@ -47,6 +52,7 @@ pub async fn permit_join(
         send_error_to_player(
             player.id,
             lobby_id,
+            ErrorCode::InvalidLobbyState,
             "Cannot permit joins when game is starting".to_string(),
             &connections,
             &redis,
@@ -62,6 +68,7 @@ pub async fn permit_join(
             send_error_to_player(
                 player.id,
                 lobby_id,
+                ErrorCode::JoinRequestMissing,
                 "No join request found for this user",
                 &connections,
                 &redis,
@@ -71,7 +78,7 @@ pub async fn permit_join(
         }
         Err(e) => {
             tracing::error!("Failed to get join request: {}", e);
-            send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis).await;
+            report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
             return;
         }
     };
@@ -91,7 +98,7 @@ pub async fn permit_join(
     .await
     {
         tracing::error!("Failed to update join state: {}", e);
-        send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis).await;
+        report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
         return;
     }
 
@@ -0,0 +1,69 @@
+use crate::{
+    db::lobby::{
+        get::{get_lobby_info, get_lobby_players},
+        ready_check::ack_ready_check,
+    },
+    models::{
+        error_code::ErrorCode,
+        game::{Player, PlayerState},
+        lobby::LobbyServerMessage,
+    },
+    state::{ConnectionInfoMap, RedisClient},
+    ws::handlers::lobby::message_handler::{
+        broadcast_to_lobby,
+        handler::{report_error_to_player, send_error_to_player},
+    },
+};
+use uuid::Uuid;
+
+pub async fn ready_check_ack(
+    lobby_id: Uuid,
+    player: &Player,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    // Acks from players who have not joined are kept but never counted
+    let acks = match ack_ready_check(lobby_id, player.id, redis.clone()).await {
+        Ok(Some(acks)) => acks,
+        Ok(None) => {
+            send_error_to_player(
+                player.id,
+                lobby_id,
+                ErrorCode::InvalidLobbyState,
+                "No ready-check is running",
+                connections,
+                redis,
+            )
+            .await;
+            return;
+        }
+        Err(e) => {
+            tracing::error!("Failed to record ready-check ack: {}", e);
+            report_error_to_player(player.id, lobby_id, &e, connections, redis).await;
+            return;
+        }
+    };
+
+    let (settings, joined) = match (
+        get_lobby_info(lobby_id, redis.clone()).await,
+        get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await,
+    ) {
+        (Ok(info), Ok(players)) => (info.settings, players),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Failed to fetch lobby for ready-check: {}", e);
+            return;
+        }
+    };
+
+    let ready_players: Vec<Uuid> = joined
+        .iter()
+        .map(|p| p.id)
+        .filter(|id| acks.contains(id))
+        .collect();
+
+    let msg = LobbyServerMessage::ReadyCheckUpdate {
+        ready_players,
+        required: settings.ready_required(joined.len()),
+    };
+    broadcast_to_lobby(lobby_id, &msg, connections, None, redis.clone()).await;
+}
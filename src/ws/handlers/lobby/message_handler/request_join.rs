@@ -1,9 +1,17 @@
 use crate::{
-    models::{game::Player, lobby::LobbyServerMessage},
+    db::{lobby::get::get_lobby_info, user::block::has_blocked},
+    models::{
+        error_code::ErrorCode,
+        game::Player,
+        lobby::{JoinState, LobbyServerMessage},
+    },
     state::{ConnectionInfoMap, RedisClient},
     ws::handlers::lobby::message_handler::{
         broadcast_to_lobby,
-        handler::{get_pending_players, request_to_join, send_error_to_player, send_to_player},
+        handler::{
+            get_pending_players, request_to_join, send_error_to_player, send_to_player,
+            set_join_state,
+        },
     },
 };
 use uuid::Uuid;
@@ -16,6 +24,33 @@ pub async fn request_join(
 ) {
     let user = player.clone().into();
 
+    // Requests to a creator who blocked this player are turned down right away
+    let blocked_by_creator = match get_lobby_info(lobby_id, redis.clone()).await {
+        Ok(lobby_info) => has_blocked(lobby_info.creator.id, player.id, redis.clone())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to check block list: {}", e);
+                false
+            }),
+        Err(e) => {
+            tracing::error!("Failed to fetch lobby info: {}", e);
+            false
+        }
+    };
+
+    if blocked_by_creator {
+        tracing::info!(
+            "Auto-rejecting join request from blocked player {}",
+            player.id
+        );
+        if let Err(e) = set_join_state(lobby_id, user, JoinState::Rejected, redis.clone()).await {
+            tracing::error!("Failed to reject join request: {}", e);
+        }
+        let msg = LobbyServerMessage::Rejected;
+        send_to_player(player.id, lobby_id, connections, &msg, redis).await;
+        return;
+    }
+
     match request_to_join(lobby_id, user, redis.clone()).await {
         Ok(_) => {
             if let Ok(pending_players) = get_pending_players(lobby_id, redis.clone()).await {
@@ -33,6 +68,7 @@ pub async fn request_join(
             send_error_to_player(
                 player.id,
                 lobby_id,
+                ErrorCode::InternalError,
                 "Failed to send join request",
                 &connections,
                 &redis,
@@ -0,0 +1,116 @@
+use crate::{
+    db::lobby::{get::get_lobby_info, patch::transfer_lobby_ownership},
+    models::{
+        error_code::ErrorCode,
+        game::{LobbyState, Player},
+        lobby::LobbyServerMessage,
+    },
+    state::{ChatConnectionInfoMap, ConnectionInfoMap, RedisClient},
+    ws::handlers::lobby::message_handler::{
+        broadcast_to_lobby,
+        handler::{report_error_to_player, send_error_to_player},
+    },
+};
+use uuid::Uuid;
+
+pub async fn transfer_ownership(
+    new_owner_id: Uuid,
+    lobby_id: Uuid,
+    player: &Player,
+    connections: &ConnectionInfoMap,
+    chat_connections: &ChatConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let lobby_info = match get_lobby_info(lobby_id, redis.clone()).await {
+        Ok(info) => info,
+        Err(e) => {
+            tracing::error!("Failed to fetch lobby info: {}", e);
+            report_error_to_player(player.id, lobby_id, &e, connections, redis).await;
+            return;
+        }
+    };
+
+    if lobby_info.creator.id != player.id {
+        tracing::warn!("Unauthorized ownership transfer attempt by {}", player.id);
+        send_error_to_player(
+            player.id,
+            lobby_id,
+            ErrorCode::NotLobbyCreator,
+            "Only creator can transfer ownership",
+            connections,
+            redis,
+        )
+        .await;
+        return;
+    }
+
+    if lobby_info.state != LobbyState::Waiting {
+        send_error_to_player(
+            player.id,
+            lobby_id,
+            ErrorCode::InvalidLobbyState,
+            "Ownership can only be transferred while the lobby is waiting",
+            connections,
+            redis,
+        )
+        .await;
+        return;
+    }
+
+    if new_owner_id == player.id {
+        send_error_to_player(
+            player.id,
+            lobby_id,
+            ErrorCode::BadRequest,
+            "You already own this lobby",
+            connections,
+            redis,
+        )
+        .await;
+        return;
+    }
+
+    if let Err(e) = transfer_lobby_ownership(lobby_id, new_owner_id, redis.clone()).await {
+        tracing::error!("Failed to transfer lobby ownership: {}", e);
+        report_error_to_player(player.id, lobby_id, &e, connections, redis).await;
+        return;
+    }
+
+    tracing::info!(
+        "Lobby {} ownership transferred from {} to {}",
+        lobby_id,
+        player.id,
+        new_owner_id
+    );
+    announce_new_owner(lobby_id, player.id, connections, chat_connections, redis).await;
+}
+
+/// Tells the lobby and its chat who the creator is after a transfer.
+pub async fn announce_new_owner(
+    lobby_id: Uuid,
+    previous_owner_id: Uuid,
+    connections: &ConnectionInfoMap,
+    chat_connections: &ChatConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let new_owner = match get_lobby_info(lobby_id, redis.clone()).await {
+        Ok(info) => info.creator,
+        Err(e) => {
+            tracing::error!("Failed to fetch new owner for lobby {}: {}", lobby_id, e);
+            return;
+        }
+    };
+
+    let msg = LobbyServerMessage::OwnershipTransferred {
+        previous_owner_id,
+        new_owner,
+    };
+    broadcast_to_lobby(
+        lobby_id,
+        &msg,
+        connections,
+        Some(chat_connections),
+        redis.clone(),
+    )
+    .await;
+}
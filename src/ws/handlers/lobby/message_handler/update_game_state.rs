@@ -1,21 +1,28 @@
 use crate::{
+    config,
     db::lobby::{
         countdown::{clear_lobby_countdown, set_lobby_countdown},
         get::{get_lobby_info, get_lobby_players},
         patch::{leave_lobby, update_lobby_state},
+        ready_check::{clear_ready_check, get_ready_acks, start_ready_check},
     },
+    events::{GameEvent, emit},
+    games::{lexi_wars::bot::ack_ready_check_for_bots, ownership::hold_game},
     models::{
+        error_code::ErrorCode,
         game::{LobbyState, Player, PlayerState},
         lobby::LobbyServerMessage,
     },
     state::{ConnectionInfoMap, RedisClient},
     ws::handlers::{
-        lobby::message_handler::{broadcast_to_lobby, handler::send_error_to_player},
+        lobby::message_handler::{
+            broadcast_to_lobby,
+            handler::{report_error_to_player, send_error_to_player},
+        },
         utils::remove_connection,
     },
+    ws::lifecycle::DisconnectReason,
 };
-use axum::extract::ws::{CloseFrame, Message};
-use futures::SinkExt;
 use uuid::Uuid;
 
 pub async fn update_game_state(
@@ -30,7 +37,7 @@ pub async fn update_game_state(
         Ok(info) => info,
         Err(e) => {
             tracing::error!("Failed to fetch lobby info: {}", e);
-            send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis).await;
+            report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
             return;
         }
     };
@@ -40,6 +47,7 @@ pub async fn update_game_state(
         send_error_to_player(
             player.id,
             lobby_id,
+            ErrorCode::NotLobbyCreator,
             "Only creator can update game state",
             &connections,
             &redis,
@@ -48,9 +56,21 @@ pub async fn update_game_state(
         return;
     }
 
+    if new_state == LobbyState::Starting && lobby_info.settings.ready_check {
+        begin_ready_check(lobby_id, player, connections, redis, bot).await;
+        return;
+    }
+
+    // The countdown runs on this instance, which resets it on shutdown
+    if new_state == LobbyState::Starting
+        && let Err(e) = hold_game(lobby_id, redis).await
+    {
+        tracing::error!("Failed to take lease of lobby {}: {}", lobby_id, e);
+    }
+
     if let Err(e) = update_lobby_state(lobby_id, new_state.clone(), redis.clone()).await {
         tracing::error!("Failed to update game state: {}", e);
-        send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis).await;
+        report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
     } else {
         tracing::info!(
             "Lobby {} state updated to {:?} by player {}",
@@ -77,8 +97,172 @@ pub async fn update_game_state(
             if let Err(e) = clear_lobby_countdown(lobby_id, redis.clone()).await {
                 tracing::error!("Failed to clear countdown for lobby {}: {}", lobby_id, e);
             }
+            // A running ready-check stops once its key is gone
+            if let Err(e) = clear_ready_check(lobby_id, redis.clone()).await {
+                tracing::error!("Failed to clear ready-check for lobby {}: {}", lobby_id, e);
+            }
+        }
+    }
+}
+
+async fn begin_ready_check(
+    lobby_id: Uuid,
+    player: &Player,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+    bot: &teloxide::Bot,
+) {
+    let already_running = match get_ready_acks(lobby_id, redis.clone()).await {
+        Ok(acks) => acks.is_some(),
+        Err(e) => {
+            tracing::error!("Failed to check ready-check for lobby {}: {}", lobby_id, e);
+            false
+        }
+    };
+    if already_running {
+        send_error_to_player(
+            player.id,
+            lobby_id,
+            ErrorCode::InvalidLobbyState,
+            "A ready-check is already running",
+            connections,
+            redis,
+        )
+        .await;
+        return;
+    }
+
+    let timeout_secs = config::get().ready_check_secs;
+    let (settings, joined) = match (
+        get_lobby_info(lobby_id, redis.clone()).await,
+        get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await,
+    ) {
+        (Ok(info), Ok(players)) => (info.settings, players.len()),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Failed to prepare ready-check: {}", e);
+            report_error_to_player(player.id, lobby_id, &e, connections, redis).await;
+            return;
+        }
+    };
+
+    if let Err(e) = start_ready_check(lobby_id, player.id, timeout_secs, redis.clone()).await {
+        tracing::error!("Failed to start ready-check for lobby {}: {}", lobby_id, e);
+        report_error_to_player(player.id, lobby_id, &e, connections, redis).await;
+        return;
+    }
+
+    ack_ready_check_for_bots(lobby_id, redis).await;
+
+    tracing::info!("Ready-check started in lobby {} by {}", lobby_id, player.id);
+    let msg = LobbyServerMessage::ReadyCheck {
+        timeout_secs,
+        required: settings.ready_required(joined),
+    };
+    broadcast_to_lobby(lobby_id, &msg, connections, None, redis.clone()).await;
+
+    let redis_clone = redis.clone();
+    let conns_clone = connections.clone();
+    let player_clone = player.clone();
+    let bot_clone = bot.clone();
+    tokio::spawn(async move {
+        run_ready_check(
+            lobby_id,
+            timeout_secs,
+            player_clone,
+            redis_clone,
+            conns_clone,
+            bot_clone,
+        )
+        .await;
+    });
+}
+
+/// Waits for enough acks, then moves the lobby into the countdown. Reverts
+/// to `Waiting` if the deadline passes first.
+async fn run_ready_check(
+    lobby_id: Uuid,
+    timeout_secs: u32,
+    player: Player,
+    redis: RedisClient,
+    connections: ConnectionInfoMap,
+    bot: teloxide::Bot,
+) {
+    let mut missing_players = Vec::new();
+
+    for _ in 0..=timeout_secs {
+        let acks = match get_ready_acks(lobby_id, redis.clone()).await {
+            Ok(Some(acks)) => acks,
+            // Cleared by the creator or a state change
+            Ok(None) => return,
+            Err(e) => {
+                tracing::error!("Failed to read ready-check for lobby {}: {}", lobby_id, e);
+                break;
+            }
+        };
+
+        let (settings, joined) = match (
+            get_lobby_info(lobby_id, redis.clone()).await,
+            get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await,
+        ) {
+            (Ok(info), Ok(players)) => (info.settings, players),
+            (Err(e), _) | (_, Err(e)) => {
+                tracing::error!("Failed to check ready-check progress: {}", e);
+                break;
+            }
+        };
+
+        let ready = joined.iter().filter(|p| acks.contains(&p.id)).count();
+        if ready >= settings.ready_required(joined.len()) {
+            if let Err(e) = clear_ready_check(lobby_id, redis.clone()).await {
+                tracing::error!("Failed to clear ready-check for lobby {}: {}", lobby_id, e);
+            }
+            if let Err(e) = hold_game(lobby_id, &redis).await {
+                tracing::error!("Failed to take lease of lobby {}: {}", lobby_id, e);
+            }
+            if let Err(e) = update_lobby_state(lobby_id, LobbyState::Starting, redis.clone()).await
+            {
+                tracing::error!("Failed to update game state: {}", e);
+                report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
+                return;
+            }
+
+            tracing::info!("Ready-check passed in lobby {} ({} ready)", lobby_id, ready);
+            let msg = LobbyServerMessage::LobbyState {
+                state: LobbyState::Starting,
+                joined_players: None,
+                started: false,
+            };
+            broadcast_to_lobby(lobby_id, &msg, &connections, None, redis.clone()).await;
+            start_countdown(lobby_id, player, redis, connections, bot).await;
+            return;
         }
+
+        missing_players = joined
+            .iter()
+            .filter(|p| !acks.contains(&p.id))
+            .map(|p| p.id)
+            .collect();
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+
+    if let Err(e) = clear_ready_check(lobby_id, redis.clone()).await {
+        tracing::error!("Failed to clear ready-check for lobby {}: {}", lobby_id, e);
     }
+
+    tracing::info!(
+        "Ready-check failed in lobby {}: {} players missing",
+        lobby_id,
+        missing_players.len()
+    );
+    let failed = LobbyServerMessage::ReadyCheckFailed { missing_players };
+    broadcast_to_lobby(lobby_id, &failed, &connections, None, redis.clone()).await;
+
+    let msg = LobbyServerMessage::LobbyState {
+        state: LobbyState::Waiting,
+        joined_players: None,
+        started: false,
+    };
+    broadcast_to_lobby(lobby_id, &msg, &connections, None, redis.clone()).await;
 }
 
 async fn close_lobby_connections(
@@ -109,20 +293,12 @@ async fn close_lobby_connections(
         };
 
     for (player_id, connection_info) in target_connections {
-        {
-            let mut sender = connection_info.sender.lock().await;
-            tracing::info!(
-                "Closing lobby connection for player {} (game starting)",
-                player_id
-            );
-
-            let close_frame = CloseFrame {
-                code: axum::extract::ws::close_code::NORMAL,
-                reason: "Game starting - redirecting to game".into(),
-            };
+        tracing::info!(
+            "Closing lobby connection for player {} (game starting)",
+            player_id
+        );
 
-            let _ = sender.send(Message::Close(Some(close_frame))).await;
-        }
+        connection_info.sender.close(DisconnectReason::GameStarting);
     }
 
     // Remove all idle players from the lobby when game starts
@@ -151,13 +327,15 @@ async fn start_countdown(
     connections: ConnectionInfoMap,
     bot: teloxide::Bot,
 ) {
+    let countdown_secs = config::get().lobby_countdown_secs;
+
     // Initialize countdown state in Redis
-    if let Err(e) = set_lobby_countdown(lobby_id, 15, redis.clone()).await {
+    if let Err(e) = set_lobby_countdown(lobby_id, countdown_secs, redis.clone()).await {
         tracing::error!("Failed to set countdown for lobby {}: {}", lobby_id, e);
         return;
     }
 
-    for i in (0..=15).rev() {
+    for i in (0..=countdown_secs).rev() {
         // Update countdown state in Redis
         if let Err(e) = set_lobby_countdown(lobby_id, i, redis.clone()).await {
             tracing::error!("Failed to update countdown for lobby {}: {}", lobby_id, e);
@@ -186,8 +364,7 @@ async fn start_countdown(
             }
             Err(e) => {
                 tracing::error!("Failed to check state: {}", e);
-                send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis)
-                    .await;
+                report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
 
                 // Clear countdown state on error
                 if let Err(e) = clear_lobby_countdown(lobby_id, redis.clone()).await {
@@ -210,14 +387,7 @@ async fn start_countdown(
                     Ok(players) => players.into_iter().map(|p| p.id).collect::<Vec<_>>(),
                     Err(e) => {
                         tracing::error!("❌ Failed to get ready players: {}", e);
-                        send_error_to_player(
-                            player.id,
-                            lobby_id,
-                            e.to_string(),
-                            &connections,
-                            &redis,
-                        )
-                        .await;
+                        report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
                         vec![]
                     }
                 };
@@ -227,27 +397,25 @@ async fn start_countdown(
                     Ok(players) => players,
                     Err(e) => {
                         tracing::error!("❌ Failed to get lobby players: {}", e);
-                        send_error_to_player(
-                            player.id,
-                            lobby_id,
-                            e.to_string(),
-                            &connections,
-                            &redis,
-                        )
-                        .await;
+                        report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
                         vec![]
                     }
                 };
 
             tracing::info!("Game started with {} ready players", joined_players.len());
 
+            // Leased before the state changes, so recovery on another
+            // instance never sees this game without an owner
+            if let Err(e) = hold_game(lobby_id, &redis).await {
+                tracing::error!("Failed to take lease of lobby {}: {}", lobby_id, e);
+            }
+
             // Update lobby state to InProgress after countdown completes
             if let Err(e) =
                 update_lobby_state(lobby_id, LobbyState::InProgress, redis.clone()).await
             {
                 tracing::error!("Failed to update lobby state to InProgress: {}", e);
-                send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis)
-                    .await;
+                report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
                 return;
             }
 
@@ -258,6 +426,12 @@ async fn start_countdown(
             };
             broadcast_to_lobby(lobby_id, &msg, &connections, None, redis.clone()).await;
 
+            emit(GameEvent::GameStarted {
+                lobby_id,
+                lobby_name: info.name.clone(),
+                player_ids: players.iter().map(|p| p.id).collect(),
+            });
+
             // Clear countdown state since game has officially started
             if let Err(e) = clear_lobby_countdown(lobby_id, redis.clone()).await {
                 tracing::error!("Failed to clear countdown for lobby {}: {}", lobby_id, e);
@@ -267,14 +441,13 @@ async fn start_countdown(
                 // Get all player IDs from the lobby for disconnection
                 let all_player_ids: Vec<Uuid> = players.iter().map(|p| p.id).collect();
 
-                // Remove connections from state
-                for player in &players {
-                    remove_connection(player.id, &connections).await;
-                }
-
-                // Close WebSocket connections with proper close frame
+                // Close WebSocket connections with proper close frame, then
+                // remove them from state
                 close_lobby_connections(lobby_id, &all_player_ids, &connections, &redis, &bot)
                     .await;
+                for player in &players {
+                    remove_connection(player.id, lobby_id, &connections).await;
+                }
             }
         }
     }
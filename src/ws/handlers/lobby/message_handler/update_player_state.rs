@@ -8,7 +8,7 @@ use crate::{
         lobby::LobbyServerMessage,
     },
     state::{ChatConnectionInfoMap, ConnectionInfoMap, RedisClient},
-    ws::handlers::lobby::message_handler::{broadcast_to_lobby, handler::send_error_to_player},
+    ws::handlers::lobby::message_handler::{broadcast_to_lobby, handler::report_error_to_player},
 };
 use uuid::Uuid;
 
@@ -24,7 +24,7 @@ pub async fn update_player_state(
         patch::update_player_state(lobby_id, player.id, new_state.clone(), redis.clone()).await
     {
         tracing::error!("Failed to update state: {}", e);
-        send_error_to_player(player.id, lobby_id, e.to_string(), &connections, &redis).await;
+        report_error_to_player(player.id, lobby_id, &e, &connections, &redis).await;
     } else if let Ok(players) =
         get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
     {
@@ -1,4 +1,5 @@
 pub mod handler;
 pub mod message_handler;
+pub mod pending_tx;
 
 pub use handler::lobby_ws_handler;
@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use crate::{
+    db::{
+        lobby::{
+            get::{get_lobby_info, get_lobby_players},
+            patch::join_lobby,
+            refunds::record_entry_refund,
+        },
+        tx::{
+            TxVerification,
+            pending::{
+                MAX_PENDING_JOIN_ATTEMPTS, PendingJoinTx, get_due_pending_joins,
+                queue_pending_join, remove_pending_join,
+            },
+            verify_payment_tx,
+        },
+        user::get::get_user_by_id,
+    },
+    models::{
+        error_code::ErrorCode,
+        game::{LobbyInfo, PlayerState},
+        lobby::{JoinOutcome, LobbyServerMessage},
+    },
+    state::{ChatConnectionInfoMap, ConnectionInfoMap, RedisClient},
+    ws::handlers::lobby::message_handler::{
+        broadcast_to_lobby,
+        handler::{report_error_to_player, send_error_to_player},
+    },
+};
+
+const PENDING_TX_POLL_SECS: u64 = 5;
+
+/// Retries joins whose entry payment wasn't confirmed yet when the player tried to join.
+pub async fn start_pending_tx_worker(
+    connections: ConnectionInfoMap,
+    chat_connections: ChatConnectionInfoMap,
+    redis: RedisClient,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(PENDING_TX_POLL_SECS));
+
+    loop {
+        interval.tick().await;
+
+        let due = match get_due_pending_joins(redis.clone()).await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::error!("Failed to fetch pending joins: {}", e);
+                continue;
+            }
+        };
+
+        for pending in due {
+            let lobby_id = pending.lobby_id;
+            let user_id = pending.user_id;
+
+            match join_lobby(
+                lobby_id,
+                user_id,
+                Some(pending.tx_id.clone()),
+                PlayerState::Joined,
+                redis.clone(),
+            )
+            .await
+            {
+                Ok(JoinOutcome::Pending) => {
+                    tracing::debug!(
+                        "Tx {} for {} in lobby {} still pending (attempt {})",
+                        pending.tx_id,
+                        user_id,
+                        lobby_id,
+                        pending.attempts
+                    );
+                }
+                // A late confirmation must not add a player to a game already under way
+                Ok(JoinOutcome::Closed) => match get_lobby_info(lobby_id, redis.clone()).await {
+                    Ok(lobby) => {
+                        settle_closed_lobby_join(&pending, &lobby, &connections, &redis).await;
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Pending join for {} in lobby {} failed: {}",
+                            user_id,
+                            lobby_id,
+                            e
+                        );
+                        if let Err(e) = remove_pending_join(lobby_id, user_id, redis.clone()).await
+                        {
+                            tracing::error!("Failed to remove pending join: {}", e);
+                        }
+                    }
+                },
+                Ok(JoinOutcome::Joined) => {
+                    tracing::info!("{} joined lobby {} after tx confirmed", user_id, lobby_id);
+                    if let Ok(players) =
+                        get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone()).await
+                    {
+                        let msg = LobbyServerMessage::PlayerUpdated { players };
+                        broadcast_to_lobby(
+                            lobby_id,
+                            &msg,
+                            &connections,
+                            Some(&chat_connections),
+                            redis.clone(),
+                        )
+                        .await;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Pending join for {} in lobby {} failed: {}",
+                        user_id,
+                        lobby_id,
+                        e
+                    );
+                    if let Err(e) = remove_pending_join(lobby_id, user_id, redis.clone()).await {
+                        tracing::error!("Failed to remove pending join: {}", e);
+                    }
+                    report_error_to_player(user_id, lobby_id, &e, &connections, &redis).await;
+                }
+            }
+        }
+    }
+}
+
+/// Drops a pending join whose lobby stopped taking players. Once the entry
+/// payment confirms it is flagged for a refund; until then it stays queued,
+/// so a payment that lands late is still refunded.
+async fn settle_closed_lobby_join(
+    pending: &PendingJoinTx,
+    lobby: &LobbyInfo,
+    connections: &ConnectionInfoMap,
+    redis: &RedisClient,
+) {
+    let lobby_id = pending.lobby_id;
+    let user_id = pending.user_id;
+
+    let verification = match (&lobby.contract_address, lobby.entry_amount) {
+        (Some(contract), Some(entry_amount)) if entry_amount > 0.0 => {
+            match get_user_by_id(user_id, redis.clone()).await {
+                Ok(user) => {
+                    verify_payment_tx(
+                        &pending.tx_id,
+                        &user.wallet_address,
+                        contract,
+                        entry_amount,
+                        lobby.asset.as_ref(),
+                        redis.clone(),
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            }
+        }
+        // Nothing was paid, so there's nothing to refund
+        _ => Ok(TxVerification::Verified),
+    };
+
+    let mut message = "The lobby started before your payment confirmed, so you weren't added";
+    match verification {
+        Ok(TxVerification::Pending) if pending.attempts < MAX_PENDING_JOIN_ATTEMPTS => {
+            if let Err(e) =
+                queue_pending_join(lobby_id, user_id, &pending.tx_id, redis.clone()).await
+            {
+                tracing::error!("Failed to requeue pending join: {}", e);
+            }
+            return;
+        }
+        Ok(TxVerification::Pending) => {
+            tracing::warn!(
+                "Tx {} for {} in closed lobby {} never confirmed",
+                pending.tx_id,
+                user_id,
+                lobby_id
+            );
+        }
+        Ok(TxVerification::Verified) => {
+            match record_entry_refund(lobby, user_id, redis.clone()).await {
+                Ok(Some(refund)) => {
+                    tracing::info!(
+                        "Entry of {} in lobby {} confirmed after it closed, {} {} refundable",
+                        user_id,
+                        lobby_id,
+                        refund.amount,
+                        refund.token_symbol
+                    );
+                    message = "The lobby started before your payment confirmed, so your entry will be refunded";
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::error!("Failed to record refund for {}: {}", user_id, e);
+                    return;
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Pending join for {} in closed lobby {} failed: {}",
+                user_id,
+                lobby_id,
+                e
+            );
+        }
+    }
+
+    if let Err(e) = remove_pending_join(lobby_id, user_id, redis.clone()).await {
+        tracing::error!("Failed to remove pending join: {}", e);
+    }
+    send_error_to_player(
+        user_id,
+        lobby_id,
+        ErrorCode::InvalidLobbyState,
+        message,
+        connections,
+        redis,
+    )
+    .await;
+}
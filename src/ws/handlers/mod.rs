@@ -1,7 +1,13 @@
 pub mod chat;
+pub mod connect_four;
 pub mod lexi_wars;
 pub mod lobby;
+pub mod rps;
+pub mod typing_race;
 pub mod utils;
 
+pub use connect_four::connect_four_handler;
 pub use lexi_wars::lexi_wars_handler;
 pub use lobby::lobby_ws_handler;
+pub use rps::rps_handler;
+pub use typing_race::typing_race_handler;
@@ -0,0 +1,288 @@
+use axum::{
+    extract::{ConnectInfo, Path, Query, State, WebSocketUpgrade, ws::WebSocket},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use futures::{SinkExt, StreamExt};
+use std::net::SocketAddr;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        game::{rps::get_rps_round, state::get_game_started},
+        lobby::{
+            get::{get_connected_players_ids, get_lobby_info, get_lobby_players},
+            patch::{
+                add_connected_player, add_spectator, remove_connected_player, remove_spectator,
+            },
+        },
+    },
+    games::{
+        core::{bets::handle_bet, delivery::broadcast_to_player, reactions::handle_reaction},
+        rps::{
+            self,
+            engine::{ROUND_SECS, Rps},
+        },
+    },
+    models::{
+        game::{LobbyInfo, LobbyState, Player, PlayerState, WsQueryParams},
+        protocol::ProtocolVersion,
+        rps::{RpsClientMessage, RpsServerMessage},
+    },
+    state::AppState,
+    ws::handlers::utils::{
+        get_pending_results_for_player, remove_connection, replay_pending_results, send_hello,
+        store_connection_and_send_queued_messages, traced,
+    },
+    ws::lifecycle::{ConnectionStage, close_when_idle, emit_connection_event},
+    ws::sessions::{SocketKind, authorize_socket},
+};
+
+pub async fn rps_handler(
+    ws: WebSocketUpgrade,
+    Query(query): Query<WsQueryParams>,
+    Path(lobby_id): Path<Uuid>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    tracing::debug!("New Rock-Paper-Scissors WebSocket connection from {}", addr);
+
+    let player_id = query.user_id;
+    authorize_socket(&query, SocketKind::Game, state.redis.clone()).await?;
+    let protocol_version = ProtocolVersion::negotiate(query.protocol_version);
+    let redis = state.redis.clone();
+
+    let lobby = get_lobby_info(lobby_id, redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+
+    match lobby.state {
+        LobbyState::InProgress => {}
+        LobbyState::Finished => {
+            tracing::info!("Player {} trying to connect to finished match", player_id);
+
+            return Ok(ws.on_upgrade(traced(move |mut socket| async move {
+                match get_pending_results_for_player(player_id, lobby_id, &redis).await {
+                    Ok(results) if !results.is_empty() => {
+                        replay_pending_results(
+                            &mut socket,
+                            player_id,
+                            lobby_id,
+                            results,
+                            |msg| matches!(msg, RpsClientMessage::ResultAck),
+                            &redis,
+                        )
+                        .await;
+                    }
+                    Ok(_) => {
+                        send_and_close(&mut socket, &RpsServerMessage::GameOver).await;
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to get pending results for player {}: {}",
+                            player_id,
+                            e
+                        );
+                    }
+                }
+                let _ = socket.close().await;
+            })));
+        }
+        LobbyState::Waiting | LobbyState::Starting => {
+            tracing::debug!(
+                "Player {} trying to connect to lobby not in progress",
+                player_id
+            );
+
+            return Ok(ws.on_upgrade(traced(move |mut socket| async move {
+                send_and_close(&mut socket, &RpsServerMessage::StartFailed).await;
+            })));
+        }
+    }
+
+    let players = get_lobby_players(lobby_id, Some(PlayerState::Joined), redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+    let game_started = get_game_started(lobby_id, redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+    let connected_player_ids = get_connected_players_ids(lobby_id, redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+
+    // Members play unless the match started without them; everyone else watches
+    let player = players
+        .into_iter()
+        .find(|p| p.id == player_id)
+        .filter(|_| !game_started || connected_player_ids.contains(&player_id));
+
+    Ok(ws.on_upgrade(traced(move |socket| {
+        handle_rps_socket(
+            socket,
+            lobby,
+            player_id,
+            player,
+            game_started,
+            protocol_version,
+            state,
+        )
+    })))
+}
+
+async fn send_and_close(socket: &mut WebSocket, msg: &RpsServerMessage) {
+    if let Ok(serialized) = serde_json::to_string(msg) {
+        let _ = socket
+            .send(axum::extract::ws::Message::Text(serialized.into()))
+            .await;
+    }
+    let _ = socket.close().await;
+}
+
+async fn handle_rps_socket(
+    socket: WebSocket,
+    lobby_info: LobbyInfo,
+    user_id: Uuid,
+    player: Option<Player>,
+    game_started: bool,
+    protocol_version: ProtocolVersion,
+    state: AppState,
+) {
+    let lobby_id = lobby_info.id;
+    let AppState {
+        redis,
+        connections,
+        bot,
+        ..
+    } = state;
+    let (mut sender, receiver) = socket.split();
+    send_hello(&mut sender, protocol_version).await;
+
+    let outbound = store_connection_and_send_queued_messages(
+        user_id,
+        lobby_id,
+        sender,
+        protocol_version,
+        &connections,
+        &redis,
+    )
+    .await;
+    let mut receiver = close_when_idle(receiver, outbound);
+
+    let start_msg = RpsServerMessage::Start {
+        time: if game_started { 0 } else { 15 },
+        started: game_started,
+    };
+    broadcast_to_player(user_id, lobby_id, &start_msg, &connections, &redis).await;
+
+    if game_started && let Ok(Some(round)) = get_rps_round(lobby_id, redis.clone()).await {
+        let round_msg = RpsServerMessage::Round {
+            round,
+            best_of: lobby_info.settings.best_of(),
+            countdown: ROUND_SECS, // Default countdown for reconnection
+        };
+        broadcast_to_player(user_id, lobby_id, &round_msg, &connections, &redis).await;
+    }
+
+    if let Some(p) = player {
+        emit_connection_event(
+            p.id,
+            Some(lobby_id),
+            SocketKind::Game,
+            ConnectionStage::JoinedLobby,
+        );
+        if !game_started {
+            let connected_player_ids = get_connected_players_ids(lobby_id, redis.clone())
+                .await
+                .unwrap_or_default();
+            if !connected_player_ids.contains(&p.id)
+                && let Err(e) = add_connected_player(lobby_id, p.id, redis.clone()).await
+            {
+                tracing::error!("Failed to add connected player: {}", e);
+            }
+
+            // Start auto-start timer when first player connects
+            if connected_player_ids.is_empty() {
+                tracing::info!(
+                    "First player connected, starting auto-start timer for lobby {}",
+                    lobby_id
+                );
+                rps::start_auto_start_timer(
+                    lobby_id,
+                    connections.clone(),
+                    redis.clone(),
+                    bot.clone(),
+                );
+            }
+        }
+
+        rps::handle_incoming_messages(
+            &p,
+            lobby_id,
+            receiver,
+            &connections,
+            redis.clone(),
+            bot.clone(),
+        )
+        .await;
+
+        // Connected players stay counted once the match is running
+        let game_started = get_game_started(lobby_id, redis.clone())
+            .await
+            .unwrap_or(false);
+        if !game_started
+            && let Err(e) = remove_connected_player(lobby_id, p.id, redis.clone()).await
+        {
+            tracing::error!("Failed to remove disconnected player: {}", e);
+        }
+
+        tracing::info!("Player {} disconnected from lobby {}", p.id, lobby_id);
+        remove_connection(p.id, lobby_id, &connections).await;
+    } else {
+        if let Err(e) = add_spectator(lobby_id, user_id, redis.clone()).await {
+            tracing::error!("Failed to add spectator: {}", e);
+        }
+        emit_connection_event(
+            user_id,
+            Some(lobby_id),
+            SocketKind::Game,
+            ConnectionStage::Spectating,
+        );
+        let spectator_msg = RpsServerMessage::Spectator;
+        broadcast_to_player(user_id, lobby_id, &spectator_msg, &connections, &redis).await;
+
+        // Spectators can only react and bet
+        while let Some(Ok(msg)) = receiver.next().await {
+            match msg {
+                axum::extract::ws::Message::Text(text) => {
+                    match serde_json::from_str::<RpsClientMessage>(&text) {
+                        Ok(RpsClientMessage::Reaction { emoji }) => {
+                            handle_reaction::<Rps>(lobby_id, user_id, emoji, &connections, &redis)
+                                .await;
+                        }
+                        Ok(RpsClientMessage::PlaceBet { winner_id, amount }) => {
+                            handle_bet::<Rps>(
+                                lobby_id,
+                                user_id,
+                                winner_id,
+                                amount,
+                                &connections,
+                                &redis,
+                            )
+                            .await;
+                        }
+                        _ => {}
+                    }
+                }
+                axum::extract::ws::Message::Close(_) => break,
+                _ => {}
+            }
+        }
+
+        remove_connection(user_id, lobby_id, &connections).await;
+        if let Err(e) = remove_spectator(lobby_id, user_id, redis.clone()).await {
+            tracing::error!("Failed to remove spectator: {}", e);
+        }
+
+        tracing::info!("Spectator {} disconnected from lobby {}", user_id, lobby_id);
+    }
+}
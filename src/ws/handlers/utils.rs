@@ -1,19 +1,110 @@
 use axum::extract::ws::{Message, WebSocket};
+use bb8::PooledConnection;
+use bb8_redis::RedisConnectionManager;
+use chrono::Utc;
 use futures::{SinkExt, stream::SplitSink};
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use redis::{AsyncCommands, Script};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+use tracing::{Instrument, Span, instrument::Instrumented};
 
 use crate::errors::AppError;
-use crate::models::redis::{KeyPart, RedisKey};
+use crate::models::{
+    protocol::ProtocolVersion,
+    queue::QueuePolicy,
+    redis::{KeyPart, RedisKey},
+};
+use crate::presence::refresh_presence;
 use crate::state::ConnectionInfoMap;
 use crate::state::{ConnectionInfo, RedisClient};
+use crate::ws::{
+    lifecycle::{ConnectionStage, DisconnectReason, emit_connection_event},
+    outbound::OutboundSender,
+    sessions::SocketKind,
+};
 use uuid::Uuid;
 
+/// Wraps an upgrade callback so the socket task stays in the upgrading
+/// request's span, keeping its correlation id on every log line of the
+/// connection.
+pub fn traced<F, Fut>(callback: F) -> impl FnOnce(WebSocket) -> Instrumented<Fut> + Send + 'static
+where
+    F: FnOnce(WebSocket) -> Fut + Send + 'static,
+    Fut: Future<Output = ()>,
+{
+    let span = Span::current();
+    move |socket| callback(socket).instrument(span)
+}
+
+const DEFAULT_QUEUE_MAX_LEN: usize = 50;
+const DEFAULT_REPLAY_WINDOW_SECS: i64 = 120;
+/// How long unacknowledged end-of-game results are kept for a player
+const PENDING_RESULTS_TTL_SECS: i64 = 7 * 24 * 60 * 60;
+/// How long a finished-game socket stays open waiting for the client's result ack
+const RESULT_ACK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Queues a message in one step, so two messages queued at once can't each
+/// keep a copy the other should have dropped. Expired entries and, for
+/// latest-only messages, older copies of the same type are removed first.
+static QUEUE_MESSAGE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r"
+        local now = tonumber(ARGV[3])
+        local latest_only = ARGV[4] == '1'
+        for _, raw in ipairs(redis.call('LRANGE', KEYS[1], 0, -1)) do
+            local ok, queued = pcall(cjson.decode, raw)
+            local stale = not ok or type(queued) ~= 'table'
+                or type(queued.expires_at) ~= 'number'
+                or queued.expires_at <= now
+                or (latest_only and queued.kind == ARGV[2])
+            if stale then
+                redis.call('LREM', KEYS[1], 1, raw)
+            end
+        end
+        redis.call('LPUSH', KEYS[1], ARGV[1])
+        redis.call('LTRIM', KEYS[1], 0, tonumber(ARGV[5]) - 1)
+        redis.call('EXPIRE', KEYS[1], ARGV[6])
+        return 1
+        ",
+    )
+});
+
+#[derive(Debug, Serialize, Deserialize)]
+struct QueuedMessage {
+    kind: String,
+    queued_at: i64,
+    expires_at: i64,
+    payload: String,
+}
+
+/// Returns the lobby's (max queue length, replay window), falling back to defaults.
+async fn get_queue_settings(
+    lobby_id: Uuid,
+    conn: &mut PooledConnection<'_, RedisConnectionManager>,
+) -> Result<(usize, i64), AppError> {
+    let (max_len, window): (Option<usize>, Option<i64>) = conn
+        .hget(
+            RedisKey::lobby(KeyPart::Id(lobby_id)),
+            &["queue_max_len", "replay_window_secs"],
+        )
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok((
+        max_len.unwrap_or(DEFAULT_QUEUE_MAX_LEN).max(1),
+        window.unwrap_or(DEFAULT_REPLAY_WINDOW_SECS).max(1),
+    ))
+}
+
 // Redis message queue functions
 pub async fn queue_message_for_player(
     player_id: Uuid,
     lobby_id: Uuid,
     message: String,
+    policy: QueuePolicy,
     redis: &RedisClient,
 ) -> Result<(), AppError> {
     let mut conn = redis.get().await.map_err(|e| match e {
@@ -22,20 +113,31 @@ pub async fn queue_message_for_player(
     })?;
 
     let key = RedisKey::player_missed_msgs(KeyPart::Id(lobby_id), KeyPart::Id(player_id));
+    let (max_len, window) = get_queue_settings(lobby_id, &mut conn).await?;
+    let now = Utc::now().timestamp();
 
-    // Use Redis list to store messages with 2-minute TTL
-    let _: () = redis::cmd("LPUSH")
-        .arg(&key)
-        .arg(&message)
-        .query_async(&mut *conn)
-        .await
-        .map_err(AppError::RedisCommandError)?;
+    let kind = serde_json::from_str::<serde_json::Value>(&message)
+        .ok()
+        .and_then(|v| v.get("type").and_then(|t| t.as_str()).map(String::from))
+        .unwrap_or_default();
+    let entry = QueuedMessage {
+        kind,
+        queued_at: now,
+        expires_at: now + policy.ttl_secs.min(window),
+        payload: message,
+    };
+    let serialized =
+        serde_json::to_string(&entry).map_err(|e| AppError::Serialization(e.to_string()))?;
 
-    // Set TTL to 2 minutes (120 seconds)
-    let _: () = redis::cmd("EXPIRE")
-        .arg(&key)
-        .arg(120)
-        .query_async(&mut *conn)
+    let _: () = QUEUE_MESSAGE
+        .key(&key)
+        .arg(&serialized)
+        .arg(&entry.kind)
+        .arg(now)
+        .arg(if policy.latest_only { 1 } else { 0 })
+        .arg(max_len)
+        .arg(window)
+        .invoke_async(&mut *conn)
         .await
         .map_err(AppError::RedisCommandError)?;
 
@@ -64,36 +166,178 @@ pub async fn get_queued_messages_for_player(
         .await
         .map_err(AppError::RedisCommandError)?;
 
-    if !messages.is_empty() {
-        // Delete the key after retrieving messages
-        let _: () = redis::cmd("DEL")
-            .arg(&key)
-            .query_async(&mut *conn)
-            .await
-            .map_err(AppError::RedisCommandError)?;
-
-        tracing::info!(
-            "Retrieved {} queued messages for player {}",
-            messages.len(),
-            player_id
-        );
+    if messages.is_empty() {
+        return Ok(Vec::new());
     }
 
+    // Delete the key after retrieving messages
+    let _: () = redis::cmd("DEL")
+        .arg(&key)
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    let (_, window) = get_queue_settings(lobby_id, &mut conn).await?;
+    let now = Utc::now().timestamp();
+
+    // Only replay what is still fresh and inside the lobby's replay window
+    let replay: Vec<String> = messages
+        .into_iter()
+        .filter_map(|raw| serde_json::from_str::<QueuedMessage>(&raw).ok())
+        .filter(|queued| queued.expires_at > now && queued.queued_at >= now - window)
+        .map(|queued| queued.payload)
+        .collect();
+
+    tracing::info!(
+        "Retrieved {} queued messages for player {}",
+        replay.len(),
+        player_id
+    );
+
     // Reverse the messages since LPUSH adds to the front but we want chronological order
-    Ok(messages.into_iter().rev().collect())
+    Ok(replay.into_iter().rev().collect())
+}
+
+/// Stores an end-of-game message that is replayed on every reconnect until the
+/// player acknowledges it with `ack_results_for_player`.
+pub async fn queue_result_for_player(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    message: String,
+    redis: &RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::player_pending_results(KeyPart::Id(lobby_id), KeyPart::Id(player_id));
+
+    let _: () = redis::pipe()
+        .rpush(&key, message)
+        .ignore()
+        .expire(&key, PENDING_RESULTS_TTL_SECS)
+        .ignore()
+        .query_async(&mut *conn)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    tracing::debug!(
+        "Stored pending result for player {} in lobby {}",
+        player_id,
+        lobby_id
+    );
+    Ok(())
+}
+
+pub async fn get_pending_results_for_player(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    redis: &RedisClient,
+) -> Result<Vec<String>, AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::player_pending_results(KeyPart::Id(lobby_id), KeyPart::Id(player_id));
+
+    // Unlike the missed message queue, results stay until they are acknowledged
+    let results: Vec<String> = conn
+        .lrange(&key, 0, -1)
+        .await
+        .map_err(AppError::RedisCommandError)?;
+
+    Ok(results)
+}
+
+pub async fn ack_results_for_player(
+    player_id: Uuid,
+    lobby_id: Uuid,
+    redis: &RedisClient,
+) -> Result<(), AppError> {
+    let mut conn = redis.get().await.map_err(|e| match e {
+        bb8::RunError::User(err) => AppError::RedisCommandError(err),
+        bb8::RunError::TimedOut => AppError::RedisPoolError("Redis connection timed out".into()),
+    })?;
+
+    let key = RedisKey::player_pending_results(KeyPart::Id(lobby_id), KeyPart::Id(player_id));
+
+    let _: () = conn.del(&key).await.map_err(AppError::RedisCommandError)?;
+
+    tracing::debug!(
+        "Player {} acknowledged results in lobby {}",
+        player_id,
+        lobby_id
+    );
+    Ok(())
+}
+
+/// Replays unacknowledged results on a finished-game socket, clearing them
+/// once the client sends a message `is_ack` accepts.
+pub async fn replay_pending_results<C: DeserializeOwned>(
+    socket: &mut WebSocket,
+    player_id: Uuid,
+    lobby_id: Uuid,
+    results: Vec<String>,
+    is_ack: fn(&C) -> bool,
+    redis: &RedisClient,
+) {
+    for result in results {
+        if let Err(e) = socket.send(Message::Text(result.into())).await {
+            tracing::debug!("Failed to replay result to player {}: {}", player_id, e);
+            return;
+        }
+    }
+
+    // Keep the results until the client confirms it received them
+    let acked = tokio::time::timeout(RESULT_ACK_TIMEOUT, async {
+        while let Some(Ok(msg)) = socket.recv().await {
+            if let Message::Text(text) = msg
+                && let Ok(client_msg) = serde_json::from_str::<C>(&text)
+                && is_ack(&client_msg)
+            {
+                return true;
+            }
+        }
+        false
+    })
+    .await
+    .unwrap_or(false);
+
+    if acked && let Err(e) = ack_results_for_player(player_id, lobby_id, redis).await {
+        tracing::error!("Failed to ack results for player {}: {}", player_id, e);
+    }
+}
+
+/// Opens the version handshake. Legacy clients get nothing.
+pub async fn send_hello(
+    sender: &mut SplitSink<WebSocket, Message>,
+    protocol_version: ProtocolVersion,
+) {
+    if let Some(hello) = protocol_version.hello()
+        && let Err(e) = sender.send(Message::Text(hello.into())).await
+    {
+        tracing::debug!("Failed to send protocol hello: {}", e);
+    }
 }
 
 async fn store_connection(
     player_id: Uuid,
     sender: SplitSink<WebSocket, Message>,
+    protocol_version: ProtocolVersion,
     connections: &ConnectionInfoMap,
-) {
+) -> OutboundSender {
     let mut conns = connections.lock().await;
+    let outbound = OutboundSender::spawn(sender);
     let conn_info = ConnectionInfo {
-        sender: Arc::new(Mutex::new(sender)),
+        sender: outbound.clone(),
+        protocol_version,
     };
     conns.insert(player_id, Arc::new(conn_info));
     tracing::debug!("Stored connection for player {}", player_id);
+    refresh_presence(player_id);
+    outbound
 }
 
 pub async fn store_connection_and_send_queued_messages(
@@ -101,11 +345,12 @@ pub async fn store_connection_and_send_queued_messages(
     lobby_id: Uuid,
 
     sender: SplitSink<WebSocket, Message>,
+    protocol_version: ProtocolVersion,
     connections: &ConnectionInfoMap,
     redis: &RedisClient,
-) {
+) -> OutboundSender {
     // Store the connection first
-    store_connection(player_id, sender, connections).await;
+    let outbound = store_connection(player_id, sender, protocol_version, connections).await;
 
     // Check for queued messages and send them
     match get_queued_messages_for_player(player_id, lobby_id, redis).await {
@@ -118,12 +363,13 @@ pub async fn store_connection_and_send_queued_messages(
                     lobby_id
                 );
 
-                let conns = connections.lock().await;
-                if let Some(conn_info) = conns.get(&player_id) {
-                    let mut sender_guard = conn_info.sender.lock().await;
-
+                let conn_info = connections.lock().await.get(&player_id).cloned();
+                if let Some(conn_info) = conn_info {
                     for message in messages {
-                        if let Err(e) = sender_guard.send(Message::Text(message.into())).await {
+                        if let Err(e) = conn_info
+                            .sender
+                            .send(Message::Text(protocol_version.adapt(&message).into()))
+                        {
                             tracing::error!(
                                 "Failed to send queued message to player {}: {}",
                                 player_id,
@@ -146,11 +392,55 @@ pub async fn store_connection_and_send_queued_messages(
             );
         }
     }
+
+    // Results are sent after the backlog so they are the last thing the client sees
+    match get_pending_results_for_player(player_id, lobby_id, redis).await {
+        Ok(results) => {
+            if !results.is_empty() {
+                let conn_info = connections.lock().await.get(&player_id).cloned();
+                if let Some(conn_info) = conn_info {
+                    for result in results {
+                        if let Err(e) = conn_info
+                            .sender
+                            .send(Message::Text(protocol_version.adapt(&result).into()))
+                        {
+                            tracing::error!(
+                                "Failed to send pending result to player {}: {}",
+                                player_id,
+                                e
+                            );
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to retrieve pending results for player {}: {}",
+                player_id,
+                e
+            );
+        }
+    }
+
+    outbound
 }
 
-pub async fn remove_connection(player_id: Uuid, connections: &ConnectionInfoMap) {
+pub async fn remove_connection(player_id: Uuid, lobby_id: Uuid, connections: &ConnectionInfoMap) {
     let mut conns = connections.lock().await;
-    if conns.remove(&player_id).is_some() {
+    if let Some(conn_info) = conns.remove(&player_id) {
         tracing::debug!("Removed connection for player {}", player_id);
+        refresh_presence(player_id);
+        let reason = conn_info
+            .sender
+            .close_reason()
+            .unwrap_or(DisconnectReason::ClientClosed);
+        emit_connection_event(
+            player_id,
+            Some(lobby_id),
+            SocketKind::Game,
+            ConnectionStage::Disconnected { reason },
+        );
     }
 }
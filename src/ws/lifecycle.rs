@@ -0,0 +1,185 @@
+//! Lifecycle of each socket: connected, authenticated, joined a lobby or
+//! spectating, then disconnected with a reason. Every step goes out on the
+//! event bus, and the reason a socket was closed is also its close frame, so
+//! clients can tell a kick from a ban, a restart or an idle timeout.
+
+use axum::extract::ws::{CloseFrame, close_code};
+use dashmap::DashMap;
+use futures::{Stream, StreamExt, stream};
+use serde::Serialize;
+use std::{
+    sync::{
+        LazyLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    config,
+    events::{GameEvent, emit},
+    models::admin::ConnectionMetrics,
+    state::AppState,
+    ws::{outbound::OutboundSender, sessions::SocketKind},
+};
+
+/// Why a socket closed, sent as the reason of its close frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum DisconnectReason {
+    /// The client went away without the server closing it
+    ClientClosed,
+    /// Removed from the lobby by its creator
+    Kicked,
+    /// Banned while connected
+    AccountSuspended,
+    /// The session the socket connected with was signed out
+    SessionRevoked,
+    ServerRestarting,
+    /// Sent nothing, not even heartbeats, for `WS_IDLE_TIMEOUT_SECS`
+    IdleTimeout,
+    /// Fell too far behind on its messages
+    TooSlow,
+    /// The lobby was removed while the socket was in it
+    LobbyNotFound,
+    /// The lobby's game started and players move to the game socket
+    GameStarting,
+    GameInProgress,
+    GameFinished,
+}
+
+impl DisconnectReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ClientClosed => "clientClosed",
+            Self::Kicked => "kicked",
+            Self::AccountSuspended => "accountSuspended",
+            Self::SessionRevoked => "sessionRevoked",
+            Self::ServerRestarting => "serverRestarting",
+            Self::IdleTimeout => "idleTimeout",
+            Self::TooSlow => "tooSlow",
+            Self::LobbyNotFound => "lobbyNotFound",
+            Self::GameStarting => "gameStarting",
+            Self::GameInProgress => "inProgress",
+            Self::GameFinished => "finished",
+        }
+    }
+
+    pub fn close_frame(&self) -> CloseFrame {
+        let code = match self {
+            Self::Kicked | Self::AccountSuspended | Self::SessionRevoked | Self::TooSlow => {
+                close_code::POLICY
+            }
+            Self::ServerRestarting => close_code::RESTART,
+            Self::ClientClosed
+            | Self::IdleTimeout
+            | Self::LobbyNotFound
+            | Self::GameStarting
+            | Self::GameInProgress
+            | Self::GameFinished => close_code::NORMAL,
+        };
+
+        CloseFrame {
+            code,
+            reason: self.as_str().into(),
+        }
+    }
+}
+
+/// A step in a socket's life
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "stage", rename_all = "camelCase")]
+pub enum ConnectionStage {
+    /// The handshake was accepted
+    Connected,
+    /// The socket sent a valid token
+    Authenticated,
+    /// Playing, or waiting in the lobby
+    JoinedLobby,
+    Spectating,
+    Disconnected {
+        reason: DisconnectReason,
+    },
+}
+
+/// Puts a step of a socket's life on the event bus.
+pub fn emit_connection_event(
+    user_id: Uuid,
+    lobby_id: Option<Uuid>,
+    socket: SocketKind,
+    stage: ConnectionStage,
+) {
+    emit(GameEvent::Connection {
+        user_id,
+        lobby_id,
+        socket,
+        stage,
+    });
+}
+
+/// Ends the stream, closing the socket as idle, once the client has sent
+/// nothing for `WS_IDLE_TIMEOUT_SECS`.
+pub fn close_when_idle<S>(
+    receiver: S,
+    sender: OutboundSender,
+) -> impl Stream<Item = S::Item> + Unpin
+where
+    S: Stream + Unpin,
+{
+    let idle_after = Duration::from_secs(config::get().ws_idle_timeout_secs);
+    Box::pin(stream::unfold(
+        (receiver, sender),
+        move |(mut receiver, sender)| async move {
+            match tokio::time::timeout(idle_after, receiver.next()).await {
+                Ok(Some(item)) => Some((item, (receiver, sender))),
+                Ok(None) => None,
+                Err(_) => {
+                    sender.close(DisconnectReason::IdleTimeout);
+                    None
+                }
+            }
+        },
+    ))
+}
+
+static CONNECTED: AtomicU64 = AtomicU64::new(0);
+static AUTHENTICATED: AtomicU64 = AtomicU64::new(0);
+static JOINED_LOBBY: AtomicU64 = AtomicU64::new(0);
+static SPECTATING: AtomicU64 = AtomicU64::new(0);
+static DISCONNECTED: LazyLock<DashMap<DisconnectReason, u64>> = LazyLock::new(DashMap::new);
+
+/// Event subscriber keeping the counts behind `ConnectionMetrics`.
+pub async fn count_connection_event(event: GameEvent) {
+    let GameEvent::Connection { stage, .. } = event else {
+        return;
+    };
+
+    let counter = match stage {
+        ConnectionStage::Connected => &CONNECTED,
+        ConnectionStage::Authenticated => &AUTHENTICATED,
+        ConnectionStage::JoinedLobby => &JOINED_LOBBY,
+        ConnectionStage::Spectating => &SPECTATING,
+        ConnectionStage::Disconnected { reason } => {
+            *DISCONNECTED.entry(reason).or_default() += 1;
+            return;
+        }
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+pub async fn get_connection_metrics(state: &AppState) -> ConnectionMetrics {
+    ConnectionMetrics {
+        open_game_sockets: state.connections.lock().await.len(),
+        open_chat_sockets: state.chat_connections.lock().await.len(),
+        connected: CONNECTED.load(Ordering::Relaxed),
+        authenticated: AUTHENTICATED.load(Ordering::Relaxed),
+        joined_lobby: JOINED_LOBBY.load(Ordering::Relaxed),
+        spectating: SPECTATING.load(Ordering::Relaxed),
+        disconnected: DISCONNECTED
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect(),
+    }
+}
@@ -1,5 +1,9 @@
+pub mod errors;
 pub mod handlers;
+pub mod lifecycle;
+pub mod outbound;
 //pub mod lobby;
 pub mod routes;
+pub mod sessions;
 
 pub use routes::create_ws_routes;
@@ -0,0 +1,124 @@
+use axum::extract::ws::{Message, WebSocket};
+use futures::{SinkExt, stream::SplitSink};
+use std::{
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+use tokio::sync::{
+    Notify,
+    mpsc::{self, error::TrySendError},
+};
+
+use crate::ws::lifecycle::DisconnectReason;
+
+/// Messages a connection may have waiting before it counts as too slow
+const OUTBOUND_BUFFER: usize = 256;
+/// A single write taking longer than this also counts as too slow
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, thiserror::Error)]
+pub enum OutboundError {
+    #[error("connection is closed")]
+    Closed,
+    #[error("client fell too far behind and is being disconnected")]
+    TooSlow,
+}
+
+/// Write half of a websocket. Sends only enqueue; a writer task per connection
+/// drains the queue, so one slow client never holds up a broadcast.
+#[derive(Debug, Clone)]
+pub struct OutboundSender {
+    tx: mpsc::Sender<Message>,
+    disconnect: Arc<Notify>,
+    /// Why the server closed the socket, when it did
+    reason: Arc<OnceLock<DisconnectReason>>,
+}
+
+impl OutboundSender {
+    pub fn spawn(sink: SplitSink<WebSocket, Message>) -> Self {
+        let (tx, rx) = mpsc::channel(OUTBOUND_BUFFER);
+        let disconnect = Arc::new(Notify::new());
+        let reason = Arc::new(OnceLock::new());
+        tokio::spawn(write_loop(sink, rx, disconnect.clone(), reason.clone()));
+        Self {
+            tx,
+            disconnect,
+            reason,
+        }
+    }
+
+    /// Queues a message. A client whose queue is full gets disconnected.
+    pub fn send(&self, msg: Message) -> Result<(), OutboundError> {
+        match self.tx.try_send(msg) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Closed(_)) => Err(OutboundError::Closed),
+            Err(TrySendError::Full(_)) => {
+                let _ = self.reason.set(DisconnectReason::TooSlow);
+                self.disconnect.notify_one();
+                Err(OutboundError::TooSlow)
+            }
+        }
+    }
+
+    /// Queues a close frame behind anything already queued. The first reason
+    /// given is the one the socket is closed with.
+    pub fn close(&self, reason: DisconnectReason) {
+        if self.reason.set(reason).is_err() {
+            return;
+        }
+        if self
+            .tx
+            .try_send(Message::Close(Some(reason.close_frame())))
+            .is_err()
+        {
+            self.disconnect.notify_one();
+        }
+    }
+
+    pub fn close_reason(&self) -> Option<DisconnectReason> {
+        self.reason.get().copied()
+    }
+
+    /// Resolves once the writer task has stopped.
+    pub async fn closed(&self) {
+        self.tx.closed().await
+    }
+}
+
+async fn write_loop(
+    mut sink: SplitSink<WebSocket, Message>,
+    mut rx: mpsc::Receiver<Message>,
+    disconnect: Arc<Notify>,
+    reason: Arc<OnceLock<DisconnectReason>>,
+) {
+    loop {
+        let msg = tokio::select! {
+            msg = rx.recv() => match msg {
+                Some(msg) => msg,
+                None => break,
+            },
+            _ = disconnect.notified() => {
+                tracing::warn!("Disconnecting websocket client that fell behind");
+                let reason = *reason.get_or_init(|| DisconnectReason::TooSlow);
+                let frame = Message::Close(Some(reason.close_frame()));
+                let _ = tokio::time::timeout(WRITE_TIMEOUT, sink.send(frame)).await;
+                break;
+            }
+        };
+
+        let is_close = matches!(msg, Message::Close(_));
+        match tokio::time::timeout(WRITE_TIMEOUT, sink.send(msg)).await {
+            Ok(Ok(())) if is_close => break,
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                tracing::debug!("Websocket write failed: {}", e);
+                break;
+            }
+            Err(_) => {
+                tracing::warn!("Websocket write timed out, disconnecting client");
+                let _ = reason.set(DisconnectReason::TooSlow);
+                break;
+            }
+        }
+    }
+}
@@ -1,13 +1,19 @@
 use axum::{Router, routing::get};
 
 use crate::{
+    games::registry::registered_games,
     state::AppState,
-    ws::handlers::{chat::chat_handler::chat_handler, lexi_wars_handler, lobby_ws_handler},
+    ws::handlers::{chat::chat_handler::chat_handler, lobby_ws_handler},
 };
 
 pub fn create_ws_routes(state: AppState) -> Router {
-    Router::new()
-        .route("/ws/lexiwars/{lobby_id}", get(lexi_wars_handler))
+    let router = registered_games()
+        .iter()
+        .fold(Router::new(), |router, game| {
+            router.route(game.ws_path, (game.ws_handler)())
+        });
+
+    router
         .route("/ws/lobby/{lobby_id}", get(lobby_ws_handler))
         .route("/ws/chat/{lobby_id}", get(chat_handler))
         .with_state(state)
@@ -0,0 +1,136 @@
+use axum::http::StatusCode;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    cluster::{ClusterMessage, publish},
+    db::moderation::bans::ensure_not_banned,
+    models::game::WsQueryParams,
+    state::{AppState, RedisClient},
+    ws::lifecycle::{ConnectionStage, DisconnectReason, emit_connection_event},
+};
+
+/// Which connection map a socket is kept in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SocketKind {
+    Game,
+    Chat,
+}
+
+/// Session of the token each player's latest socket connected with. The
+/// connection maps keep a single socket per player, so this names the session
+/// of the socket they hold. Local to this instance, like the maps, so
+/// revocations are sent to every instance over the cluster channel.
+static SOCKET_SESSIONS: LazyLock<DashMap<(Uuid, SocketKind), Uuid>> = LazyLock::new(DashMap::new);
+
+/// Checks the token a socket connected with and records its session so
+/// revoking the session closes the socket. Tokens of another user or of a
+/// revoked session are refused, as are banned users.
+pub async fn authorize_socket(
+    query: &WsQueryParams,
+    kind: SocketKind,
+    redis: RedisClient,
+) -> Result<(), (StatusCode, String)> {
+    ensure_not_banned(query.user_id, redis.clone())
+        .await
+        .map_err(|e| e.to_response())?;
+    emit_connection_event(query.user_id, None, kind, ConnectionStage::Connected);
+
+    let claims = AuthClaims::from_token(&query.token)?;
+    if claims.0.sub != query.user_id.to_string() {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            "Token does not belong to this user".into(),
+        ));
+    }
+    claims.ensure_session(redis).await?;
+    emit_connection_event(query.user_id, None, kind, ConnectionStage::Authenticated);
+
+    if let Some(session_id) = claims.0.sid {
+        SOCKET_SESSIONS.insert((query.user_id, kind), session_id);
+    } else {
+        SOCKET_SESSIONS.remove(&(query.user_id, kind));
+    }
+    Ok(())
+}
+
+/// Closes the user's sockets that connected with the session, returning how many.
+pub async fn close_session_sockets(user_id: Uuid, session_id: Uuid, state: &AppState) -> usize {
+    let mut closed = 0;
+
+    for kind in [SocketKind::Game, SocketKind::Chat] {
+        if SOCKET_SESSIONS
+            .remove_if(&(user_id, kind), |_, id| *id == session_id)
+            .is_none()
+        {
+            continue;
+        }
+
+        let sender = match kind {
+            SocketKind::Game => state
+                .connections
+                .lock()
+                .await
+                .get(&user_id)
+                .map(|info| info.sender.clone()),
+            SocketKind::Chat => state
+                .chat_connections
+                .lock()
+                .await
+                .get(&user_id)
+                .map(|info| info.sender.clone()),
+        };
+
+        if let Some(sender) = sender {
+            sender.close(DisconnectReason::SessionRevoked);
+            closed += 1;
+        }
+    }
+
+    closed
+}
+
+/// Closes the sockets of a revoked session on every instance, returning how
+/// many this instance closed.
+pub async fn revoke_session_sockets(user_id: Uuid, session_id: Uuid, state: &AppState) -> usize {
+    let closed = close_session_sockets(user_id, session_id, state).await;
+
+    let msg = ClusterMessage::SessionRevoked {
+        user_id,
+        session_id,
+    };
+    if let Err(e) = publish(&msg, &state.redis).await {
+        tracing::error!("Failed to publish revoked session {}: {}", session_id, e);
+    }
+
+    closed
+}
+
+/// Closes every socket the user holds, for when they are banned. Returns how
+/// many were closed.
+pub async fn close_user_sockets(user_id: Uuid, state: &AppState) -> usize {
+    let game_sender = state
+        .connections
+        .lock()
+        .await
+        .get(&user_id)
+        .map(|info| info.sender.clone());
+    let chat_sender = state
+        .chat_connections
+        .lock()
+        .await
+        .get(&user_id)
+        .map(|info| info.sender.clone());
+
+    let mut closed = 0;
+    for sender in [game_sender, chat_sender].into_iter().flatten() {
+        sender.close(DisconnectReason::AccountSuspended);
+        closed += 1;
+    }
+
+    closed
+}
@@ -0,0 +1,62 @@
+use k256::ecdsa::SigningKey;
+use stacks_wars_be::auth::{
+    c32_address, recover_stacks_address, stacks_address, stacks_message_hash,
+};
+
+fn sign(key: &SigningKey, message: &str) -> String {
+    let (signature, recovery_id) = key
+        .sign_prehash_recoverable(&stacks_message_hash(message))
+        .unwrap();
+    let mut rsv = signature.to_bytes().to_vec();
+    rsv.push(recovery_id.to_byte());
+    hex::encode(rsv)
+}
+
+#[test]
+fn test_c32_addresses_match_known_vectors() {
+    let hash: [u8; 20] = hex::decode("a46ff88886c2ef9762d970b4d2c63678835bd39d")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(
+        c32_address(22, &hash),
+        "SP2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKNRV9EJ7"
+    );
+    assert_eq!(
+        c32_address(26, &hash),
+        "ST2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQYAC0RQ"
+    );
+    assert_eq!(c32_address(22, &[0; 20]), "SP000000000000000000002Q6VF78");
+}
+
+#[test]
+fn test_signature_recovers_signer_address() {
+    let key = SigningKey::from_slice(&[7; 32]).unwrap();
+    let public_key = key.verifying_key().to_encoded_point(true);
+    let address = stacks_address(public_key.as_bytes(), true).unwrap();
+    assert!(address.starts_with("SP"));
+
+    let message = "Sign in to Stacks Wars\nNonce: 1234";
+    let signature = sign(&key, message);
+    assert_eq!(
+        recover_stacks_address(message, &signature, true).unwrap(),
+        address
+    );
+    assert!(
+        recover_stacks_address(message, &signature, false)
+            .unwrap()
+            .starts_with("ST")
+    );
+}
+
+#[test]
+fn test_signature_over_other_message_recovers_other_address() {
+    let key = SigningKey::from_slice(&[7; 32]).unwrap();
+    let public_key = key.verifying_key().to_encoded_point(true);
+    let address = stacks_address(public_key.as_bytes(), true).unwrap();
+
+    let signature = sign(&key, "Nonce: 1234");
+    let recovered = recover_stacks_address("Nonce: 5678", &signature, true);
+    assert!(recovered.is_err() || recovered.unwrap() != address);
+    assert!(recover_stacks_address("Nonce: 1234", "abcd", true).is_err());
+}
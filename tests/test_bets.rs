@@ -0,0 +1,21 @@
+use stacks_wars_be::games::core::bets::split_pot;
+use uuid::Uuid;
+
+#[test]
+fn test_winner_backers_share_the_pot() {
+    let winner = Uuid::new_v4();
+    let loser = Uuid::new_v4();
+    let stakes = [(winner, 30.0), (winner, 10.0), (loser, 60.0)];
+
+    assert_eq!(split_pot(&stakes, Some(winner)), vec![75.0, 25.0, 0.0]);
+}
+
+#[test]
+fn test_stakes_returned_without_winning_backers() {
+    let winner = Uuid::new_v4();
+    let loser = Uuid::new_v4();
+    let stakes = [(loser, 20.0), (loser, 5.0)];
+
+    assert_eq!(split_pot(&stakes, Some(winner)), vec![20.0, 5.0]);
+    assert_eq!(split_pot(&stakes, None), vec![20.0, 5.0]);
+}
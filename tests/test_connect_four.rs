@@ -0,0 +1,76 @@
+use stacks_wars_be::games::connect_four::board::{Board, COLUMNS, ROWS};
+
+#[test]
+fn test_discs_stack_from_the_bottom() {
+    let mut board = Board::default();
+    assert_eq!(board.drop_disc(3, 1), Some(0));
+    assert_eq!(board.drop_disc(3, 2), Some(1));
+    assert_eq!(board.get(3, 0), 1);
+    assert_eq!(board.get(3, 1), 2);
+    assert_eq!(board.get(4, 0), 0);
+}
+
+#[test]
+fn test_full_column_rejects_discs() {
+    let mut board = Board::default();
+    for i in 0..ROWS {
+        assert!(board.drop_disc(0, (i % 2) as u8 + 1).is_some());
+    }
+    assert_eq!(board.drop_disc(0, 1), None);
+}
+
+#[test]
+fn test_horizontal_vertical_and_diagonal_wins() {
+    let mut horizontal = Board::default();
+    for column in 0..4 {
+        horizontal.drop_disc(column, 1);
+    }
+    assert_eq!(
+        horizontal.winning_line(2, 0),
+        Some(vec![(0, 0), (1, 0), (2, 0), (3, 0)])
+    );
+
+    let mut vertical = Board::default();
+    for _ in 0..4 {
+        vertical.drop_disc(6, 2);
+    }
+    assert!(vertical.winning_line(6, 3).is_some());
+
+    // Rising diagonal from (0, 0) to (3, 3)
+    let mut diagonal = Board::default();
+    for column in 0..4 {
+        for _ in 0..column {
+            diagonal.drop_disc(column, 2);
+        }
+        diagonal.drop_disc(column, 1);
+    }
+    assert_eq!(
+        diagonal.winning_line(3, 3),
+        Some(vec![(0, 0), (1, 1), (2, 2), (3, 3)])
+    );
+}
+
+#[test]
+fn test_three_in_a_row_is_not_a_win() {
+    let mut board = Board::default();
+    for column in 0..3 {
+        board.drop_disc(column, 1);
+    }
+    board.drop_disc(3, 2);
+    assert_eq!(board.winning_line(1, 0), None);
+    assert_eq!(board.winning_line(3, 0), None);
+}
+
+#[test]
+fn test_board_round_trips_and_detects_full() {
+    let mut board = Board::default();
+    for column in 0..COLUMNS {
+        for row in 0..ROWS {
+            // Alternate in pairs so no line of four forms
+            board.drop_disc(column, ((row / 2 + column) % 2) as u8 + 1);
+        }
+    }
+    assert!(board.is_full());
+    assert_eq!(Board::parse(&board.encode()), Some(board));
+    assert_eq!(Board::parse("1234"), None);
+}
@@ -0,0 +1,17 @@
+use stacks_wars_be::games::core::prize::{LEAVE_PENALTY, leave_penalty};
+
+#[test]
+fn test_player_pays_for_leaving() {
+    assert_eq!(leave_penalty(false, false), Some(LEAVE_PENALTY));
+}
+
+#[test]
+fn test_creator_leaves_for_free() {
+    assert_eq!(leave_penalty(false, true), None);
+}
+
+#[test]
+fn test_casual_lobby_leaves_wars_points_alone() {
+    assert_eq!(leave_penalty(true, false), None);
+    assert_eq!(leave_penalty(true, true), None);
+}
@@ -1,4 +1,6 @@
-use stacks_wars_be::games::lexi_wars::rules::{RuleContext, find_rule_by_name, get_rules};
+use stacks_wars_be::games::lexi_wars::rules::{
+    RuleContext, find_rule_by_name, get_enabled_rules, get_rules,
+};
 
 fn create_test_context() -> RuleContext {
     RuleContext {
@@ -458,3 +460,77 @@ fn test_different_contexts() {
     assert!((rules2[1].validate)("puzzle", &ctx2).is_ok());
     assert!((rules2[1].validate)("puzzle", &ctx1).is_err());
 }
+
+#[test]
+fn test_enabled_rules_keep_progression_order() {
+    let ctx = create_test_context();
+
+    let enabled = vec!["ends_with_letter".to_string(), "min_length".to_string()];
+    let rules = get_enabled_rules(&ctx, Some(&enabled));
+    let names: Vec<&str> = rules.iter().map(|rule| rule.name.as_str()).collect();
+    assert_eq!(names, vec!["min_length", "ends_with_letter"]);
+
+    // No selection plays every rule
+    assert_eq!(get_enabled_rules(&ctx, None).len(), get_rules(&ctx).len());
+}
+
+#[test]
+fn test_score_word_rewards_length_rarity_and_rule() {
+    use stacks_wars_be::games::lexi_wars::scoring::score_word;
+
+    assert!(score_word("apple", None) > score_word("ape", None));
+    assert!(score_word("jazz", None) > score_word("tale", None));
+    assert!(
+        score_word("apple", Some("ends_with_letter")) > score_word("apple", Some("min_length"))
+    );
+}
+
+#[test]
+fn test_check_submission_flags_inhuman_speed() {
+    use stacks_wars_be::games::lexi_wars::anticheat::{Suspicion, check_submission};
+
+    assert_eq!(check_submission("apple", 5_000), None);
+    assert_eq!(check_submission("apple", 400), Some(Suspicion::TooFast));
+    assert_eq!(check_submission("apple", 50), Some(Suspicion::Pasted));
+}
+
+#[test]
+fn test_word_highlights_pick_longest_and_rarest() {
+    use stacks_wars_be::games::lexi_wars::scoring::{longest_word, rarest_word};
+
+    let words: Vec<String> = ["tale", "jazz", "strain", "quiz"]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+    assert_eq!(longest_word(&words), Some("strain"));
+    assert_eq!(rarest_word(&words), Some("jazz"));
+    assert_eq!(longest_word(&[]), None);
+}
+
+#[test]
+fn test_custom_rule_definition() {
+    use stacks_wars_be::games::lexi_wars::rule_dsl::RuleDefinition;
+
+    let ctx = create_test_context();
+    let definition: RuleDefinition = serde_json::from_str(
+        r#"{"name": "ends_with_letter_ing", "type": "ends_with", "text": "{letter}ing", "description": "Word must end with '{letter}ing'"}"#,
+    )
+    .unwrap();
+    assert!(definition.validate().is_ok());
+
+    let rule = definition.to_rule(&ctx);
+    assert_eq!(
+        rule.description,
+        format!("Word must end with '{}ing'", ctx.random_letter)
+    );
+    let word = format!("ab{}ing", ctx.random_letter);
+    assert!((rule.validate)(&word, &ctx).is_ok());
+    assert!((rule.validate)("sing", &ctx).is_err());
+
+    // Built-in rules can't be redefined
+    let builtin: RuleDefinition = serde_json::from_str(
+        r#"{"name": "min_length", "type": "min_length", "length": 3, "description": "Too short"}"#,
+    )
+    .unwrap();
+    assert!(builtin.validate().is_err());
+}
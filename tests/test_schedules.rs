@@ -0,0 +1,52 @@
+use chrono::{DateTime, TimeZone, Utc, Weekday};
+use stacks_wars_be::games::schedules::Recurrence;
+
+fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+        .unwrap()
+}
+
+#[test]
+fn test_weekly_recurrence_finds_next_weekday() {
+    // Every Friday 8pm UTC; 2025-06-04 is a Wednesday
+    let friday_night = Recurrence::Week {
+        weekday: Weekday::Fri,
+        hour: 20,
+        minute: 0,
+    };
+
+    assert_eq!(
+        friday_night.next_after(at(2025, 6, 4, 12, 0)),
+        at(2025, 6, 6, 20, 0)
+    );
+    // An occurrence that just ran moves a full week ahead
+    assert_eq!(
+        friday_night.next_after(at(2025, 6, 6, 20, 0)),
+        at(2025, 6, 13, 20, 0)
+    );
+}
+
+#[test]
+fn test_daily_recurrence_rolls_over_to_tomorrow() {
+    let daily = Recurrence::Day {
+        hour: 9,
+        minute: 30,
+    };
+
+    assert_eq!(
+        daily.next_after(at(2025, 6, 4, 8, 0)),
+        at(2025, 6, 4, 9, 30)
+    );
+    assert_eq!(
+        daily.next_after(at(2025, 6, 4, 10, 0)),
+        at(2025, 6, 5, 9, 30)
+    );
+    assert!(
+        Recurrence::Day {
+            hour: 24,
+            minute: 0
+        }
+        .validate()
+        .is_err()
+    );
+}
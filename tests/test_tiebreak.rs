@@ -0,0 +1,112 @@
+use stacks_wars_be::games::{
+    core::prize::{Placing, group_by_key, group_placings},
+    lexi_wars::tiebreak::{SurvivorRecord, rank_survivors, rank_survivors_by_words},
+};
+use uuid::Uuid;
+
+fn survivor(score: u64, words: u64, response_ms: u64) -> SurvivorRecord {
+    SurvivorRecord {
+        player_id: Uuid::new_v4(),
+        score,
+        words,
+        response_ms,
+    }
+}
+
+#[test]
+fn test_higher_score_ranks_first() {
+    let low = survivor(40, 2, 2_000);
+    let high = survivor(90, 3, 30_000);
+
+    assert_eq!(
+        rank_survivors(vec![low.clone(), high.clone()]),
+        vec![vec![high.player_id], vec![low.player_id]]
+    );
+}
+
+#[test]
+fn test_faster_answers_break_a_score_tie() {
+    let slow = survivor(60, 2, 16_000);
+    let fast = survivor(60, 3, 9_000);
+    let silent = survivor(60, 0, 0);
+
+    assert_eq!(
+        rank_survivors(vec![silent.clone(), slow.clone(), fast.clone()]),
+        vec![
+            vec![fast.player_id],
+            vec![slow.player_id],
+            vec![silent.player_id]
+        ]
+    );
+}
+
+#[test]
+fn test_level_players_share_a_group_in_given_order() {
+    let first = survivor(0, 0, 0);
+    let second = survivor(0, 0, 0);
+    let leader = survivor(10, 1, 5_000);
+
+    assert_eq!(
+        rank_survivors(vec![first.clone(), second.clone(), leader.clone()]),
+        vec![
+            vec![leader.player_id],
+            vec![first.player_id, second.player_id]
+        ]
+    );
+}
+
+#[test]
+fn test_time_limit_ranks_most_words_first() {
+    let scorer = survivor(120, 2, 8_000);
+    let prolific = survivor(80, 5, 40_000);
+    let fast = survivor(60, 5, 20_000);
+
+    assert_eq!(
+        rank_survivors_by_words(vec![scorer.clone(), prolific.clone(), fast.clone()]),
+        vec![
+            vec![prolific.player_id],
+            vec![fast.player_id],
+            vec![scorer.player_id]
+        ]
+    );
+}
+
+#[test]
+fn test_equal_keys_group_in_given_order() {
+    let (a, b, c, d) = (
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+    );
+    let score = |id: &Uuid| match id {
+        id if *id == c => 9,
+        id if *id == d => 1,
+        _ => 5,
+    };
+
+    assert_eq!(
+        group_by_key(&[a, b, c, d], |id| std::cmp::Reverse(score(id))),
+        vec![vec![c], vec![a, b], vec![d]]
+    );
+}
+
+#[test]
+fn test_drawn_group_shares_its_rank_and_skips_the_places_it_covers() {
+    let (a, b, c, d) = (
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+    );
+
+    assert_eq!(
+        group_placings(&[vec![a], vec![b, c], vec![d]]),
+        vec![
+            (a, Placing::shared(1, 1)),
+            (b, Placing::shared(2, 2)),
+            (c, Placing::shared(2, 2)),
+            (d, Placing::shared(4, 1)),
+        ]
+    );
+}
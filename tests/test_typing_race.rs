@@ -0,0 +1,38 @@
+use stacks_wars_be::games::typing_race::scoring::score_submission;
+
+#[test]
+fn test_perfect_submission_scores_full_accuracy() {
+    // 5 words of 4 letters plus spaces is 25 chars, 5 words in one minute
+    let score = score_submission(
+        "able back cake dame each",
+        "able back cake dame each",
+        60_000,
+    );
+    assert_eq!(score.correct_words, 5);
+    assert_eq!(score.accuracy, 1.0);
+    assert_eq!(score.wpm, 5.0);
+    assert_eq!(score.score, 50);
+}
+
+#[test]
+fn test_typos_lower_accuracy_and_score() {
+    let clean = score_submission("able back cake dame", "able back cake dame", 30_000);
+    let sloppy = score_submission("able back cake dame", "able bakc cake dmae", 30_000);
+    assert_eq!(sloppy.correct_words, 2);
+    assert_eq!(sloppy.accuracy, 0.5);
+    assert!(sloppy.score < clean.score);
+}
+
+#[test]
+fn test_faster_submission_scores_higher() {
+    let fast = score_submission("able back cake dame", "able back cake dame", 10_000);
+    let slow = score_submission("able back cake dame", "able back cake dame", 40_000);
+    assert!(fast.score > slow.score);
+}
+
+#[test]
+fn test_empty_submission_scores_zero() {
+    let score = score_submission("able back cake dame", "", 5_000);
+    assert_eq!(score.correct_words, 0);
+    assert_eq!(score.score, 0);
+}